@@ -77,6 +77,9 @@ export default defineConfig({
             { text: 'Pull Request', link: '/studio/modes/pr' },
             { text: 'Changelog', link: '/studio/modes/changelog' },
             { text: 'Release Notes', link: '/studio/modes/release-notes' },
+            { text: 'Dashboard', link: '/studio/modes/dashboard' },
+            { text: 'Stash', link: '/studio/modes/stash' },
+            { text: 'Rebase', link: '/studio/modes/rebase' },
           ]
         }
       ],
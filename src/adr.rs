@@ -0,0 +1,167 @@
+//! Architecture decision records drafted from a commit range
+//!
+//! ADRs follow Michael Nygard's template (status, context, decision,
+//! consequences) and live in `docs/adr/` as `NNNN-title.md`, numbered after
+//! the highest record already there.
+
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Default ADR directory, relative to the repository root
+pub const ADR_DIR: &str = "docs/adr";
+
+/// Numbering some models add to the title on their own (`ADR-7:`, `0007.`)
+static NUMBER_PREFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:(?:adr[-\s]*)?\d+\s*[.:]\s+|adr[-\s]*\d+\s+)").expect("valid regex")
+});
+
+/// A drafted record, ready to write
+#[derive(Debug, Clone)]
+pub struct AdrDraft {
+    pub number: u32,
+    pub title: String,
+    /// Full markdown, starting with `# N. Title`
+    pub content: String,
+}
+
+impl AdrDraft {
+    /// Build a record from the model's markdown
+    ///
+    /// The title comes from `title` or the first `# ` heading. The heading is
+    /// rewritten as `# N. Title` and a `Date:` line is added below it.
+    pub fn from_markdown(number: u32, markdown: &str, title: Option<&str>) -> Result<Self> {
+        let markdown = markdown.trim();
+        let (heading, body) = match markdown.split_once('\n') {
+            Some((first, rest)) if first.starts_with("# ") => (Some(&first[2..]), rest),
+            _ if markdown.starts_with("# ") => (Some(&markdown[2..]), ""),
+            _ => (None, markdown),
+        };
+        let title = title
+            .or(heading)
+            .map(|t| NUMBER_PREFIX.replace(t.trim(), "").trim().to_string())
+            .filter(|t| !t.is_empty())
+            .context("The drafted ADR has no title; pass one with --title")?;
+
+        let date = chrono::Local::now().format("%Y-%m-%d");
+        let content = format!("# {number}. {title}\n\nDate: {date}\n\n{}\n", body.trim());
+        Ok(Self {
+            number,
+            title,
+            content,
+        })
+    }
+
+    /// `NNNN-title.md`
+    pub fn file_name(&self) -> String {
+        format!("{:04}-{}.md", self.number, slugify(&self.title))
+    }
+
+    /// Write the record into `dir`, creating it if needed
+    pub fn write(&self, dir: &Path) -> Result<PathBuf> {
+        crate::read_only::ensure_writable("Writing the ADR")?;
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(self.file_name());
+        if path.exists() {
+            bail!("{} already exists", path.display());
+        }
+        std::fs::write(&path, &self.content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Number for the next record in `dir`: one past the highest `NNNN-*.md`
+pub fn next_number(dir: &Path) -> u32 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 1;
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let stem = name.strip_suffix(".md")?;
+            let (number, _) = stem.split_once('-')?;
+            number.parse::<u32>().ok()
+        })
+        .max()
+        .map_or(1, |n| n + 1)
+}
+
+/// Lowercase, dash-separated file name fragment for a title
+pub fn slugify(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Task prompt for drafting a record from `from..to` and the user's request
+pub fn prompt(from: &str, to: &str, request: &str, title: Option<&str>) -> String {
+    let mut prompt = format!(
+        "Draft an architecture decision record for the changes in {from}..{to}. Pass \
+         from=\"{from}\" and to=\"{to}\" to `git_log`, `git_diff` and `git_changed_files`.\n\n\
+         ## Decision to Record\n{}\n",
+        request.trim()
+    );
+    if let Some(title) = title {
+        prompt.push_str(&format!("\n## Title\nUse this title: {title}\n"));
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_number_follows_highest_record() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(next_number(&dir.path().join("missing")), 1);
+        for name in [
+            "0001-record-architecture-decisions.md",
+            "0007-use-sqlite.md",
+            "README.md",
+            "template.md",
+        ] {
+            std::fs::write(dir.path().join(name), "").expect("write");
+        }
+        assert_eq!(next_number(dir.path()), 8);
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(
+            slugify("Use SQLite for the cache (not Redis)"),
+            "use-sqlite-for-the-cache-not-redis"
+        );
+        assert_eq!(slugify("  Adopt  tokio/async  "), "adopt-tokio-async");
+    }
+
+    #[test]
+    fn test_from_markdown_normalizes_heading() {
+        let markdown =
+            "# ADR-3: Move sessions to Redis\n\n## Status\n\nProposed\n\n## Context\n...";
+        let draft = AdrDraft::from_markdown(12, markdown, None).expect("draft");
+        assert_eq!(draft.title, "Move sessions to Redis");
+        assert_eq!(draft.file_name(), "0012-move-sessions-to-redis.md");
+        assert!(
+            draft
+                .content
+                .starts_with("# 12. Move sessions to Redis\n\nDate: ")
+        );
+        assert!(draft.content.contains("## Status\n\nProposed"));
+
+        let titled =
+            AdrDraft::from_markdown(1, "## Context\n...", Some("Split the API")).expect("draft");
+        assert!(titled.content.starts_with("# 1. Split the API\n"));
+        assert!(AdrDraft::from_markdown(1, "## Context\n...", None).is_err());
+
+        let kept = AdrDraft::from_markdown(2, "# 2.0 migration plan", None).expect("draft");
+        assert_eq!(kept.title, "2.0 migration plan");
+    }
+}
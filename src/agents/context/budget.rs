@@ -0,0 +1,293 @@
+//! Token budgets for diffs
+//!
+//! A large changeset can hold more diff text than the model's context
+//! window. When `git_diff` returns full diffs it fits them into a budget of
+//! half the provider's token limit: files are taken most relevant first and
+//! shown whole while they fit, the files left over share what remains as
+//! truncated diffs, and files whose share would be too small to read are
+//! summarized by the stats in the file list alone. The tool result lists
+//! what was cut, [`DiffCoverage`] collects those lists over a generation,
+//! and markdown outputs end with a footer naming the files Iris didn't see
+//! in full.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+use crate::agents::context_report::group_digits;
+use crate::doc_context::{count_tokens, truncate_to_tokens};
+
+/// Diff tokens `git_diff` may return when the provider's token limit isn't
+/// known
+pub const DEFAULT_DIFF_BUDGET: usize = 64_000;
+
+/// Smallest share of the budget worth showing a truncated diff for
+const MIN_TRUNCATED_TOKENS: usize = 200;
+
+/// Heading of the omissions section in `git_diff` output
+const OMITTED_HEADING: &str = "=== OMITTED (token budget) ===";
+
+/// Diff budget for a model with `token_limit` tokens of context, leaving
+/// the other half for prompts, other tool results and the output
+pub const fn diff_budget_for(token_limit: usize) -> usize {
+    token_limit / 2
+}
+
+/// A file whose diff didn't fit the budget in full
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Omission {
+    pub path: String,
+    /// Estimated tokens of the whole diff
+    pub tokens: usize,
+    /// Tokens of the diff that were shown; zero when only its stats were
+    pub kept_tokens: usize,
+}
+
+impl Omission {
+    /// Whether the diff was left out entirely
+    pub fn is_summarized(&self) -> bool {
+        self.kept_tokens == 0
+    }
+
+    fn to_line(&self) -> String {
+        let kind = if self.is_summarized() {
+            "summarized"
+        } else {
+            "truncated"
+        };
+        format!(
+            "- {} ({kind}: {} of {} tokens shown)",
+            self.path, self.kept_tokens, self.tokens
+        )
+    }
+
+    fn parse_line(line: &str) -> Option<Self> {
+        let (path, rest) = line.strip_prefix("- ")?.rsplit_once(" (")?;
+        let (_, counts) = rest.split_once(": ")?;
+        let (kept, rest) = counts.split_once(" of ")?;
+        let tokens = rest.strip_suffix(" tokens shown)")?;
+        Some(Self {
+            path: path.to_string(),
+            tokens: tokens.parse().ok()?,
+            kept_tokens: kept.parse().ok()?,
+        })
+    }
+}
+
+/// Fit diffs, most relevant first, into `budget` tokens
+///
+/// Returns each diff as it should be shown, `None` for diffs left out, and
+/// the files that were truncated or left out in relevance order.
+pub fn fit_diffs<'a>(
+    diffs: &[(&str, &'a str)],
+    budget: usize,
+) -> (Vec<Option<Cow<'a, str>>>, Vec<Omission>) {
+    let tokens: Vec<usize> = diffs.iter().map(|(_, diff)| count_tokens(diff)).collect();
+    let mut fitted = vec![None; diffs.len()];
+    let mut remaining = budget;
+    let mut deferred = Vec::new();
+    for (i, (_, diff)) in diffs.iter().enumerate() {
+        if tokens[i] <= remaining {
+            remaining -= tokens[i];
+            fitted[i] = Some(Cow::Borrowed(*diff));
+        } else {
+            deferred.push(i);
+        }
+    }
+
+    // Files that didn't fit share what is left, so one huge diff can't
+    // crowd out every file after it
+    let mut omissions = Vec::new();
+    for (n, &i) in deferred.iter().enumerate() {
+        let (path, diff) = diffs[i];
+        let share = remaining / (deferred.len() - n);
+        let mut kept_tokens = 0;
+        if share >= MIN_TRUNCATED_TOKENS {
+            let (kept, used, _) = truncate_to_tokens(diff, share);
+            if used > 0 {
+                remaining -= used;
+                kept_tokens = used;
+                fitted[i] = Some(Cow::Owned(format!(
+                    "{kept}\n... [diff truncated to fit the token budget]\n"
+                )));
+            }
+        }
+        omissions.push(Omission {
+            path: path.to_string(),
+            tokens: tokens[i],
+            kept_tokens,
+        });
+    }
+    (fitted, omissions)
+}
+
+/// The heading `git_diff` writes above each file's diff
+pub fn diff_heading(path: &str, relevance: f32) -> String {
+    format!("--- {path} [{:.0}% relevance]\n", relevance * 100.0)
+}
+
+/// The `git_diff` output section listing omitted files, or an empty string
+pub fn omissions_section(omissions: &[Omission]) -> String {
+    if omissions.is_empty() {
+        return String::new();
+    }
+    let mut section = format!(
+        "\n{OMITTED_HEADING}\nThese diffs didn't fit the token budget. Request fewer files with files=['path'] to see them in full.\n"
+    );
+    for omission in omissions {
+        section.push_str(&omission.to_line());
+        section.push('\n');
+    }
+    section
+}
+
+/// Which diffs the agent saw in full over one generation
+#[derive(Debug, Clone, Default)]
+pub struct DiffCoverage {
+    full: Vec<String>,
+    omitted: Vec<Omission>,
+}
+
+impl DiffCoverage {
+    /// Note the files a `git_diff` result showed whole or cut
+    pub fn record(&mut self, result: &str) {
+        let mut in_omissions = false;
+        let mut cut = Vec::new();
+        let mut shown = Vec::new();
+        for line in result.lines() {
+            if line == OMITTED_HEADING {
+                in_omissions = true;
+            } else if in_omissions {
+                cut.extend(Omission::parse_line(line));
+            } else if let Some(heading) = line.strip_prefix("--- ")
+                && let Some((path, _)) = heading.rsplit_once(" [")
+            {
+                shown.push(path.to_string());
+            }
+        }
+
+        for path in shown {
+            if !cut.iter().any(|omission| omission.path == path) && !self.full.contains(&path) {
+                self.full.push(path);
+            }
+        }
+        for omission in cut {
+            match self.omitted.iter_mut().find(|o| o.path == omission.path) {
+                Some(existing) if existing.kept_tokens < omission.kept_tokens => {
+                    *existing = omission;
+                }
+                Some(_) => {}
+                None => self.omitted.push(omission),
+            }
+        }
+    }
+
+    /// Files never shown in full, in the order they were first cut
+    pub fn omissions(&self) -> Vec<Omission> {
+        self.omitted
+            .iter()
+            .filter(|omission| !self.full.contains(&omission.path))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Markdown footer naming the files left out of the diffs, or an empty
+/// string when every diff was read in full
+pub fn footer(omissions: &[Omission]) -> String {
+    if omissions.is_empty() {
+        return String::new();
+    }
+    let count = omissions.len();
+    let mut footer = format!(
+        "\n\n---\n\n_{count} file{} didn't fit the token budget and {} not read in full:_\n\n",
+        if count == 1 { "" } else { "s" },
+        if count == 1 { "was" } else { "were" }
+    );
+    for omission in omissions {
+        let detail = if omission.is_summarized() {
+            format!("summarized, {} tokens", group_digits(omission.tokens))
+        } else {
+            format!(
+                "truncated, {} of {} tokens",
+                group_digits(omission.kept_tokens),
+                group_digits(omission.tokens)
+            )
+        };
+        footer.push_str(&format!("- `{}` ({detail})\n", omission.path));
+    }
+    footer
+}
+
+/// Add the omissions footer to `content`
+pub fn append_footer(content: &str, omissions: &[Omission]) -> String {
+    if omissions.is_empty() {
+        return content.to_string();
+    }
+    format!("{}{}", content.trim_end(), footer(omissions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_diffs_prioritizes_and_reports() {
+        let small = "+ small change\n".repeat(10);
+        let huge = "+ a long generated line of code\n".repeat(2000);
+        let medium = "+ medium change here\n".repeat(60);
+        let diffs = [
+            ("src/huge.rs", huge.as_str()),
+            ("src/small.rs", small.as_str()),
+            ("src/medium.rs", medium.as_str()),
+        ];
+        let budget = count_tokens(&small) + count_tokens(&medium) + 500;
+
+        let (fitted, omissions) = fit_diffs(&diffs, budget);
+        assert_eq!(fitted[1].as_deref(), Some(small.as_str()));
+        assert_eq!(fitted[2].as_deref(), Some(medium.as_str()));
+        assert!(
+            fitted[0]
+                .as_deref()
+                .is_some_and(|d| d.contains("truncated"))
+        );
+        assert_eq!(omissions.len(), 1);
+        assert_eq!(omissions[0].path, "src/huge.rs");
+        assert!(omissions[0].kept_tokens > 0 && omissions[0].kept_tokens <= 500);
+
+        let (fitted, omissions) = fit_diffs(&diffs, 10);
+        assert!(fitted.iter().all(Option::is_none));
+        assert!(omissions.iter().all(Omission::is_summarized));
+    }
+
+    #[test]
+    fn test_coverage_forgets_files_shown_later() {
+        let cut = |path: &str, kept_tokens| Omission {
+            path: path.to_string(),
+            tokens: 5000,
+            kept_tokens,
+        };
+        let first = format!(
+            "=== DIFFS ===\n{}+ x\n{}",
+            diff_heading("src/a.rs", 0.9),
+            omissions_section(&[cut("src/b.rs", 300), cut("src/c d.rs", 0)])
+        );
+        let second = format!(
+            "=== DIFFS ===\n{}+ all of b\n",
+            diff_heading("src/b.rs", 0.5)
+        );
+
+        let mut coverage = DiffCoverage::default();
+        coverage.record(&first);
+        assert_eq!(
+            coverage.omissions(),
+            [cut("src/b.rs", 300), cut("src/c d.rs", 0)]
+        );
+        coverage.record(&second);
+        assert_eq!(coverage.omissions(), [cut("src/c d.rs", 0)]);
+
+        let out = append_footer("## Summary\n\nDone.\n", &coverage.omissions());
+        assert!(out.starts_with("## Summary\n\nDone.\n\n---\n\n_1 file didn't fit"));
+        assert!(out.ends_with("- `src/c d.rs` (summarized, 5,000 tokens)\n"));
+        assert_eq!(append_footer("Done.", &[]), "Done.");
+    }
+}
@@ -0,0 +1,211 @@
+//! Per-file analysis cached across runs
+//!
+//! Regenerating a commit message with tweaked instructions sees the same
+//! staged diffs as the run before. Relevance scores and fast-model file
+//! summaries depend only on a file's path, change type and diff, so they are
+//! kept under `.git/iris/cache`, one JSON file per diff keyed by a hash of
+//! those three, and reused until the diff changes. Summaries are kept per
+//! model. The cache holds at most [`MAX_ENTRIES`] files; the least recently
+//! written are dropped once per process.
+
+use anyhow::Result;
+use git2::{ObjectType, Oid};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+use crate::context::StagedFile;
+use crate::git::GitRepo;
+use crate::log_debug;
+
+/// Bump when the analysis changes shape or meaning, so old entries miss
+const CACHE_VERSION: u32 = 1;
+
+/// Entries kept before the oldest are pruned
+pub const MAX_ENTRIES: usize = 2000;
+
+static PRUNE: Once = Once::new();
+
+/// Relevance of one file's diff, as `git_diff` reports it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileAnalysis {
+    /// 0.0 to 1.0, higher is more important to the change
+    pub relevance: f32,
+    /// Why the file scored what it did, like "new file" or "adds function"
+    pub reasons: Vec<String>,
+}
+
+/// What is known about one diff
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CachedFile {
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    analysis: Option<FileAnalysis>,
+    /// Fast-model summaries by model name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    summaries: BTreeMap<String, String>,
+}
+
+/// Cached analysis for a repository's diffs
+#[derive(Debug, Clone)]
+pub struct ContextCache {
+    dir: PathBuf,
+}
+
+impl ContextCache {
+    /// Cache under the repository's git directory, shared by its worktrees
+    pub fn for_repo(repo: &GitRepo) -> Result<Self> {
+        let git = repo.open_repo()?;
+        let cache = Self::at(git.commondir().join("iris").join("cache"));
+        PRUNE.call_once(|| {
+            if let Err(e) = cache.prune(MAX_ENTRIES) {
+                log_debug!("Context cache not pruned: {}", e);
+            }
+        });
+        Ok(cache)
+    }
+
+    pub fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The file's analysis, computed with `analyze` and stored on a miss
+    pub fn analysis(
+        &self,
+        file: &StagedFile,
+        analyze: impl FnOnce(&StagedFile) -> FileAnalysis,
+    ) -> FileAnalysis {
+        let mut entry = self.read(file);
+        if let Some(analysis) = entry.analysis {
+            return analysis;
+        }
+        let analysis = analyze(file);
+        entry.analysis = Some(analysis.clone());
+        self.write(file, &entry);
+        analysis
+    }
+
+    /// The summary `model` wrote for this diff before
+    pub fn summary(&self, file: &StagedFile, model: &str) -> Option<String> {
+        self.read(file).summaries.remove(model)
+    }
+
+    /// Remember the summary `model` wrote for this diff
+    pub fn store_summary(&self, file: &StagedFile, model: &str, summary: &str) {
+        let mut entry = self.read(file);
+        entry
+            .summaries
+            .insert(model.to_string(), summary.to_string());
+        self.write(file, &entry);
+    }
+
+    /// Drop all but the `keep` most recently written entries. Returns how
+    /// many were removed.
+    pub fn prune(&self, keep: usize) -> Result<usize> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Ok(0);
+        };
+        let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+                Some((modified, entry.path()))
+            })
+            .collect();
+        if files.len() <= keep {
+            return Ok(0);
+        }
+        files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+        let stale = files.split_off(keep);
+        for (_, path) in &stale {
+            fs::remove_file(path)?;
+        }
+        Ok(stale.len())
+    }
+
+    fn path(&self, file: &StagedFile) -> Option<PathBuf> {
+        let key = format!(
+            "v{CACHE_VERSION}\0{}\0{}\0{}",
+            file.path, file.change_type, file.diff
+        );
+        let hash = Oid::hash_object(ObjectType::Blob, key.as_bytes()).ok()?;
+        Some(self.dir.join(format!("{hash}.json")))
+    }
+
+    /// The entry for a diff; empty when missing or unreadable
+    fn read(&self, file: &StagedFile) -> CachedFile {
+        self.path(file)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_else(|| CachedFile {
+                path: file.path.clone(),
+                ..CachedFile::default()
+            })
+    }
+
+    /// Best-effort: a cache that can't be written only costs the next run
+    /// some time
+    fn write(&self, file: &StagedFile, entry: &CachedFile) {
+        let Some(path) = self.path(file) else {
+            return;
+        };
+        let result = fs::create_dir_all(&self.dir)
+            .and_then(|()| fs::write(&path, serde_json::to_string(entry).unwrap_or_default()));
+        if let Err(e) = result {
+            log_debug!("Context cache entry for {} not saved: {}", file.path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ChangeType;
+
+    fn file(diff: &str) -> StagedFile {
+        StagedFile {
+            path: "src/lib.rs".to_string(),
+            change_type: ChangeType::Modified,
+            diff: diff.to_string(),
+            content: None,
+            content_excluded: false,
+        }
+    }
+
+    #[test]
+    fn test_cache_keyed_by_diff() {
+        let dir = tempfile::TempDir::new().expect("temp dir");
+        let cache = ContextCache::at(dir.path().join("cache"));
+        let analysis = FileAnalysis {
+            relevance: 0.8,
+            reasons: vec!["source code".to_string()],
+        };
+
+        let first = cache.analysis(&file("+a\n"), |_| analysis.clone());
+        assert_eq!(first, analysis);
+        let cached = cache.analysis(&file("+a\n"), |_| panic!("should be cached"));
+        assert_eq!(cached, analysis);
+        let changed = cache.analysis(&file("+b\n"), |_| FileAnalysis {
+            relevance: 0.1,
+            reasons: Vec::new(),
+        });
+        assert!((changed.relevance - 0.1).abs() < f32::EPSILON);
+
+        cache.store_summary(&file("+a\n"), "fast", "Adds a.");
+        assert_eq!(
+            cache.summary(&file("+a\n"), "fast").as_deref(),
+            Some("Adds a.")
+        );
+        assert_eq!(cache.summary(&file("+a\n"), "other"), None);
+        assert_eq!(cache.analysis(&file("+a\n"), |_| panic!("kept")), analysis);
+
+        assert_eq!(cache.prune(1).expect("prune"), 1);
+        assert_eq!(fs::read_dir(cache.dir()).expect("dir").count(), 1);
+    }
+}
@@ -3,6 +3,9 @@
 //! This module provides structured, validated context for agent tasks,
 //! replacing fragile string-based parameter passing.
 
+pub mod budget;
+pub mod cache;
+
 use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 
@@ -43,6 +46,12 @@ pub enum TaskContext {
         version_name: Option<String>,
         /// Release date in YYYY-MM-DD format
         date: String,
+        /// Directory the changelog covers, for one package of a monorepo
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+        /// Whether release notes end with an upgrade guide for breaking changes
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        upgrade_guide: bool,
     },
 
     /// Amend the previous commit with staged changes
@@ -149,6 +158,49 @@ impl TaskContext {
             to: to.unwrap_or_else(|| "HEAD".to_string()),
             version_name,
             date: date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string()),
+            path: None,
+            upgrade_guide: false,
+        }
+    }
+
+    /// Limit a changelog context to changes under `path`
+    ///
+    /// Other contexts are returned unchanged.
+    #[must_use]
+    pub fn scoped_to(mut self, scope: Option<String>) -> Self {
+        if let Self::Changelog { path, .. } = &mut self {
+            *path = scope;
+        }
+        self
+    }
+
+    /// Ask for an upgrade guide in release notes with breaking changes
+    ///
+    /// Other contexts are returned unchanged.
+    #[must_use]
+    pub fn with_upgrade_guide(mut self, enabled: bool) -> Self {
+        if let Self::Changelog { upgrade_guide, .. } = &mut self {
+            *upgrade_guide = enabled;
+        }
+        self
+    }
+
+    /// Whether a changelog context asks for an upgrade guide
+    pub fn wants_upgrade_guide(&self) -> bool {
+        matches!(
+            self,
+            Self::Changelog {
+                upgrade_guide: true,
+                ..
+            }
+        )
+    }
+
+    /// Directory a changelog context is limited to
+    pub fn scope(&self) -> Option<&str> {
+        match self {
+            Self::Changelog { path, .. } => path.as_deref(),
+            _ => None,
         }
     }
 
@@ -170,6 +222,14 @@ impl TaskContext {
             Self::Commit { commit_id } => {
                 format!("git_diff(from=\"{commit_id}^1\", to=\"{commit_id}\")")
             }
+            Self::Changelog {
+                from,
+                to,
+                path: Some(path),
+                ..
+            } => {
+                format!("git_diff(from=\"{from}\", to=\"{to}\", files=[\"{path}\"])")
+            }
             Self::Range { from, to } | Self::Changelog { from, to, .. } => {
                 format!("git_diff(from=\"{from}\", to=\"{to}\")")
             }
@@ -226,11 +286,17 @@ impl std::fmt::Display for TaskContext {
                 to,
                 version_name,
                 date,
+                path,
+                ..
             } => {
                 let version_str = version_name
                     .as_ref()
                     .map_or_else(|| "unreleased".to_string(), |v| format!("v{v}"));
-                write!(f, "changelog {version_str} ({date}) from {from} to {to}")
+                write!(f, "changelog {version_str} ({date}) from {from} to {to}")?;
+                match path {
+                    Some(path) => write!(f, " in {path}"),
+                    None => Ok(()),
+                }
             }
             Self::Amend { .. } => write!(f, "amending previous commit"),
             Self::Discover => write!(f, "auto-discovered changes"),
@@ -357,11 +423,18 @@ mod tests {
         );
         assert!(matches!(
             ctx,
-            TaskContext::Changelog { from, to, version_name, date }
+            TaskContext::Changelog { ref from, ref to, ref version_name, ref date, path: None, upgrade_guide: false }
                 if from == "v1.0.0" && to == "HEAD"
-                && version_name == Some("1.1.0".to_string())
+                && *version_name == Some("1.1.0".to_string())
                 && date == "2025-01-15"
         ));
+
+        let scoped = ctx.scoped_to(Some("crates/foo".to_string()));
+        assert_eq!(scoped.scope(), Some("crates/foo"));
+        assert!(scoped.diff_hint().contains("files=[\"crates/foo\"]"));
+        assert!(scoped.to_string().ends_with(" in crates/foo"));
+        assert!(!scoped.wants_upgrade_guide());
+        assert!(scoped.with_upgrade_guide(true).wants_upgrade_guide());
     }
 
     #[test]
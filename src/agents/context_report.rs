@@ -0,0 +1,265 @@
+//! Prompt context size reports
+//!
+//! Each generation records where its prompt tokens went: the system prompt,
+//! the task description, project documentation, diffs and other analysis the
+//! agent fetched through tools, plus the output. Studio shows the latest
+//! report with `Ctrl+T` and `git-iris trace` prints saved ones, so it's clear
+//! what a preset, a `[context.docs]` setting or a large diff costs.
+
+use chrono::{DateTime, Utc};
+use rig::agent::{CancelSignal, PromptHook, StreamingPromptHook};
+use rig::completion::{CompletionModel, Usage};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use crate::agents::context::budget::{DiffCoverage, Omission};
+use crate::doc_context::count_tokens;
+
+/// Token breakdown of one generation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextReport {
+    /// Capability that ran (`commit`, `review`, `pr`, ...)
+    pub capability: String,
+    pub model: String,
+    pub created_at: DateTime<Utc>,
+    /// Capability prompt, style instructions and output format rules
+    pub system_tokens: usize,
+    /// Task description: refs, file lists, custom instructions
+    pub task_tokens: usize,
+    /// `[context.docs]` excerpts, glossary entries and `project_docs` tool
+    /// results
+    pub docs_tokens: usize,
+    /// `git_diff` tool results
+    pub diff_tokens: usize,
+    /// All other tool results (file reads, searches, subagents)
+    pub analysis_tokens: usize,
+    pub output_tokens: usize,
+    pub tool_calls: usize,
+    /// Input tokens across all turns as the provider reported them; tool
+    /// results are resent every turn, so this exceeds the sum above
+    #[serde(default)]
+    pub billed_input_tokens: u64,
+    /// Files whose diffs didn't fit the token budget in full
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub omitted: Vec<Omission>,
+}
+
+impl ContextReport {
+    /// Tokens of distinct content sent to the model
+    pub fn input_tokens(&self) -> usize {
+        self.system_tokens
+            + self.task_tokens
+            + self.docs_tokens
+            + self.diff_tokens
+            + self.analysis_tokens
+    }
+
+    /// Input sections with their token counts, in prompt order
+    pub fn sections(&self) -> [(&'static str, usize); 5] {
+        [
+            ("System prompt", self.system_tokens),
+            ("Task context", self.task_tokens),
+            ("Documentation", self.docs_tokens),
+            ("Diffs", self.diff_tokens),
+            ("Analysis", self.analysis_tokens),
+        ]
+    }
+
+    /// Count `tokens` of the task prompt as documentation instead
+    pub fn attribute_docs(&mut self, tokens: usize) {
+        let moved = tokens.min(self.task_tokens);
+        self.task_tokens -= moved;
+        self.docs_tokens += moved;
+    }
+
+    /// Plain-text table, one section per line
+    pub fn to_table(&self) -> String {
+        let total = self.input_tokens().max(1);
+        let mut table = format!(
+            "{} · {} · {}\n",
+            self.capability,
+            self.model,
+            self.created_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+        );
+        for (label, tokens) in self.sections() {
+            table.push_str(&format!(
+                "  {label:<16}{:>9}  {:>3}%\n",
+                group_digits(tokens),
+                tokens * 100 / total
+            ));
+        }
+        table.push_str(&format!(
+            "  {:<16}{:>9}\n  {:<16}{:>9}\n",
+            "Input",
+            group_digits(self.input_tokens()),
+            "Output",
+            group_digits(self.output_tokens)
+        ));
+        if self.billed_input_tokens > 0 {
+            table.push_str(&format!(
+                "  {:<16}{:>9}  across {} tool call{}\n",
+                "Billed input",
+                group_digits(usize::try_from(self.billed_input_tokens).unwrap_or(usize::MAX)),
+                self.tool_calls,
+                if self.tool_calls == 1 { "" } else { "s" }
+            ));
+        }
+        if !self.omitted.is_empty() {
+            table.push_str(&format!(
+                "  {:<16}{:>9}  over the token budget\n",
+                "Diffs cut",
+                self.omitted.len()
+            ));
+        }
+        table
+    }
+}
+
+/// `12345` as `12,345`
+pub fn group_digits(n: usize) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// Tool result tokens by kind, filled in while the agent runs
+#[derive(Debug, Clone, Default)]
+struct ToolTally {
+    docs: usize,
+    diff: usize,
+    analysis: usize,
+    calls: usize,
+    coverage: DiffCoverage,
+}
+
+/// Prompt hook that sizes tool results as the agent receives them
+#[derive(Debug, Clone, Default)]
+pub struct ContextTracker(Arc<Mutex<ToolTally>>);
+
+impl ContextTracker {
+    fn record(&self, tool_name: &str, result: &str) {
+        let Ok(mut tally) = self.0.lock() else {
+            return;
+        };
+        let tokens = count_tokens(result);
+        match tool_name {
+            "git_diff" => {
+                tally.diff += tokens;
+                tally.coverage.record(result);
+            }
+            "project_docs" => tally.docs += tokens,
+            _ => tally.analysis += tokens,
+        }
+        tally.calls += 1;
+    }
+
+    /// Report for a finished generation
+    ///
+    /// `prompt` is everything sent on the first turn, of which `task_prompt`
+    /// is the task description; the rest counts as system prompt.
+    pub fn report(
+        &self,
+        capability: &str,
+        model: &str,
+        prompt: &str,
+        task_prompt: &str,
+        output: &str,
+        usage: Usage,
+    ) -> ContextReport {
+        let tally = self.0.lock().map(|t| t.clone()).unwrap_or_default();
+        let task_tokens = count_tokens(task_prompt);
+        let output_tokens = if usage.output_tokens > 0 {
+            usize::try_from(usage.output_tokens).unwrap_or(usize::MAX)
+        } else {
+            count_tokens(output)
+        };
+        ContextReport {
+            capability: capability.to_string(),
+            model: model.to_string(),
+            created_at: Utc::now(),
+            system_tokens: count_tokens(prompt).saturating_sub(task_tokens),
+            task_tokens,
+            docs_tokens: tally.docs,
+            diff_tokens: tally.diff,
+            analysis_tokens: tally.analysis,
+            output_tokens,
+            tool_calls: tally.calls,
+            billed_input_tokens: usage.input_tokens,
+            omitted: tally.coverage.omissions(),
+        }
+    }
+}
+
+impl<M: CompletionModel> PromptHook<M> for ContextTracker {
+    fn on_tool_result(
+        &self,
+        tool_name: &str,
+        _args: &str,
+        result: &str,
+        _cancel_sig: CancelSignal,
+    ) -> impl Future<Output = ()> + Send {
+        self.record(tool_name, result);
+        async {}
+    }
+}
+
+impl<M: CompletionModel> StreamingPromptHook<M> for ContextTracker {
+    fn on_tool_result(
+        &self,
+        tool_name: &str,
+        _args: &str,
+        result: &str,
+        _cancel_sig: CancelSignal,
+    ) -> impl Future<Output = ()> + Send {
+        self.record(tool_name, result);
+        async {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_splits_prompt_and_tools() {
+        let tracker = ContextTracker::default();
+        tracker.record("git_diff", &"+ added line\n".repeat(50));
+        tracker.record("project_docs", "# Readme\n\nHello.");
+        tracker.record("file_read", "fn main() {}");
+
+        let task = "Review the changes between main and HEAD.";
+        let prompt = format!("You are Iris, a careful reviewer.\n\n{task}\n\nRespond in JSON.");
+        let usage = Usage {
+            input_tokens: 9000,
+            output_tokens: 120,
+            total_tokens: 9120,
+        };
+        let mut report = tracker.report("review", "test-model", &prompt, task, "ignored", usage);
+
+        assert_eq!(report.tool_calls, 3);
+        assert_eq!(report.output_tokens, 120);
+        assert_eq!(report.task_tokens, count_tokens(task));
+        assert!(report.system_tokens > 0);
+        assert!(report.diff_tokens > report.docs_tokens);
+        assert_eq!(report.analysis_tokens, count_tokens("fn main() {}"));
+
+        let docs = report.docs_tokens;
+        report.attribute_docs(3);
+        assert_eq!(report.docs_tokens, docs + 3);
+        assert_eq!(report.task_tokens, count_tokens(task) - 3);
+
+        let table = report.to_table();
+        assert!(table.starts_with("review · test-model"));
+        assert!(table.contains("Billed input        9,000  across 3 tool calls"));
+        assert_eq!(group_digits(1_234_567), "1,234,567");
+    }
+}
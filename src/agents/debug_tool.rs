@@ -3,18 +3,22 @@
 use anyhow::Result;
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 use crate::agents::debug;
+use crate::agents::policy::{self, ToolPolicy};
 
-/// Wrapper that adds debug logging to any Rig tool
+/// Wrapper that adds debug logging and the repository's tool policy to any
+/// Rig tool
 pub struct DebugTool<T>
 where
     T: Tool,
 {
     inner: T,
+    policy: Arc<ToolPolicy>,
     _phantom: PhantomData<T>,
 }
 
@@ -22,9 +26,10 @@ impl<T> DebugTool<T>
 where
     T: Tool,
 {
-    pub fn new(tool: T) -> Self {
+    pub fn new(tool: T, policy: &Arc<ToolPolicy>) -> Self {
         Self {
             inner: tool,
+            policy: Arc::clone(policy),
             _phantom: PhantomData,
         }
     }
@@ -37,6 +42,7 @@ where
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
+            policy: Arc::clone(&self.policy),
             _phantom: PhantomData,
         }
     }
@@ -49,6 +55,7 @@ where
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugTool")
             .field("inner", &self.inner)
+            .field("policy", &self.policy)
             .finish()
     }
 }
@@ -65,28 +72,12 @@ where
     }
 }
 
-impl<'de, T> Deserialize<'de> for DebugTool<T>
-where
-    T: Tool + Deserialize<'de>,
-{
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let inner = T::deserialize(deserializer)?;
-        Ok(Self {
-            inner,
-            _phantom: PhantomData,
-        })
-    }
-}
-
 impl<T> Tool for DebugTool<T>
 where
     T: Tool + Send + Sync,
-    T::Args: Debug + Send + Sync,
+    T::Args: Debug + Serialize + Send + Sync,
     T::Output: Debug + Send + Sync,
-    T::Error: Send + Sync,
+    T::Error: From<anyhow::Error> + Send + Sync,
 {
     const NAME: &'static str = T::NAME;
     type Error = T::Error;
@@ -102,6 +93,22 @@ where
         let args_str = format!("{:?}", args);
         debug::debug_tool_call(Self::NAME, &args_str);
 
+        // Refuse calls the repository's tool policy doesn't allow
+        let args_json = serde_json::to_value(&args).unwrap_or_default();
+        if let Err(violation) = self.policy.check_tool_call(Self::NAME, &args_json) {
+            debug::debug_error(&format!(
+                "Tool {} refused: {}",
+                Self::NAME,
+                violation.reason
+            ));
+            return Err(anyhow::anyhow!(
+                "Refused by the repository's tool policy ({}): {}",
+                policy::POLICY_FILE,
+                violation.reason
+            )
+            .into());
+        }
+
         // Start timer
         let timer = debug::DebugTimer::start(&format!("Tool: {}", Self::NAME));
 
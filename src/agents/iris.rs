@@ -14,22 +14,122 @@ use serde_json::json;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
 
-// Embed capability TOML files at compile time so they're always available
-const CAPABILITY_COMMIT: &str = include_str!("capabilities/commit.toml");
-const CAPABILITY_PR: &str = include_str!("capabilities/pr.toml");
-const CAPABILITY_REVIEW: &str = include_str!("capabilities/review.toml");
-const CAPABILITY_CHANGELOG: &str = include_str!("capabilities/changelog.toml");
-const CAPABILITY_RELEASE_NOTES: &str = include_str!("capabilities/release_notes.toml");
-const CAPABILITY_CHAT: &str = include_str!("capabilities/chat.toml");
-const CAPABILITY_SEMANTIC_BLAME: &str = include_str!("capabilities/semantic_blame.toml");
-
+use crate::agents::context_report::{ContextReport, ContextTracker};
+use crate::agents::policy::ToolPolicy;
+use crate::agents::prompts;
 use crate::agents::tools::{GitRepoInfo, ParallelAnalyze, Workspace};
+use crate::config::{GenerationParams, Tone, Verbosity};
 // Added to ensure builder extension methods like `.max_tokens` are in scope
 
+/// Output token cap for the main agent unless a capability overrides it
+/// (large enough for structured outputs like PRs and release notes)
+const DEFAULT_MAX_TOKENS: u64 = 16384;
+
 /// Type alias for a dynamic agent that can work with any completion model
 pub type DynAgent = Agent<Box<dyn CompletionModel + Send + Sync>>;
 
+/// HTTP client that sends `headers` with every request, streaming included
+pub(crate) fn http_client(headers: &[(String, String)]) -> reqwest::Client {
+    reqwest::Client::builder()
+        .default_headers(crate::providers::header_map(headers))
+        .build()
+        .unwrap_or_default()
+}
+
+/// Rig's provider registry for our providers, sending `headers` with every
+/// request
+///
+/// Rig's own Ollama factory panics unless `OLLAMA_API_BASE_URL` is set; this
+/// one follows `OLLAMA_HOST` and falls back to `localhost:11434`. Rig's
+/// Gemini factory only reads `GEMINI_API_KEY` and panics without it; this
+/// one reads `GOOGLE_API_KEY` too, and a missing key surfaces as the API's
+/// invalid key error instead. `OpenAI` and Anthropic read the same variables
+/// as Rig's factories, but share the header-carrying HTTP client.
+pub(crate) fn client_builder(headers: &[(String, String)]) -> DynClientBuilder {
+    use crate::providers::Provider;
+    use rig::client::ProviderClient;
+    use rig::client::builder::ClientFactory;
+    use rig::providers::{anthropic, gemini, ollama, openai};
+
+    let http = http_client(headers);
+
+    let client = http.clone();
+    let openai = move || -> Box<dyn ProviderClient> {
+        let api_key = Provider::OpenAI.env_api_key().unwrap_or_default();
+        let base_url = std::env::var("OPENAI_BASE_URL").ok();
+        let builder = openai::Client::builder(&api_key).with_client(client.clone());
+        Box::new(match &base_url {
+            Some(url) => builder.base_url(url).build(),
+            None => builder.build(),
+        })
+    };
+
+    let client = http.clone();
+    let anthropic = move || -> Box<dyn ProviderClient> {
+        let api_key = Provider::Anthropic.env_api_key().unwrap_or_default();
+        Box::new(
+            anthropic::Client::builder(&api_key)
+                .with_client(client.clone())
+                .build()
+                .expect("default Anthropic headers are valid"),
+        )
+    };
+
+    let client = http.clone();
+    let google = move || -> Box<dyn ProviderClient> {
+        let api_key = Provider::Google.env_api_key().unwrap_or_default();
+        Box::new(
+            gemini::Client::builder(&api_key)
+                .with_client(client.clone())
+                .build()
+                .expect("Gemini client has no fallible settings"),
+        )
+    };
+
+    let local = move || -> Box<dyn ProviderClient> {
+        Box::new(
+            ollama::Client::builder()
+                .base_url(&crate::providers::ollama_base_url())
+                .with_client(http.clone())
+                .build(),
+        )
+    };
+    let local_val = local.clone();
+
+    DynClientBuilder::new()
+        .register(ClientFactory::new(
+            Provider::OpenAI.name(),
+            openai,
+            openai::Client::<reqwest::Client>::from_val_boxed,
+        ))
+        .register(ClientFactory::new(
+            Provider::Anthropic.name(),
+            anthropic,
+            anthropic::Client::<reqwest::Client>::from_val_boxed,
+        ))
+        .register(ClientFactory::new(
+            Provider::Ollama.name(),
+            local,
+            move |_| local_val(),
+        ))
+        .register(ClientFactory::new(
+            Provider::Google.name(),
+            google,
+            gemini::Client::<reqwest::Client>::from_val_boxed,
+        ))
+}
+
+/// Whether a provider error says the model can't call tools
+///
+/// Many local models served by Ollama reject any request that lists tools.
+fn lacks_tool_support(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("does not support tools") || error.contains("tools are not supported")
+}
+
 /// Trait for streaming callback to handle real-time response processing
 #[async_trait::async_trait]
 pub trait StreamingCallback: Send + Sync {
@@ -96,6 +196,109 @@ impl fmt::Display for StructuredResponse {
     }
 }
 
+impl StructuredResponse {
+    /// Apply the ASCII-only formatting pass (drops the gitmoji field)
+    pub fn into_ascii(self) -> Self {
+        use crate::types::to_ascii;
+        match self {
+            Self::CommitMessage(msg) => Self::CommitMessage(crate::types::GeneratedMessage {
+                emoji: None,
+                title: to_ascii(&msg.title).trim().to_string(),
+                message: to_ascii(&msg.message),
+                completion_message: msg.completion_message,
+                variant: msg.variant,
+            }),
+            Self::PullRequest(pr) => Self::PullRequest(crate::types::MarkdownPullRequest {
+                content: to_ascii(&pr.content),
+            }),
+            Self::Changelog(cl) => Self::Changelog(crate::types::MarkdownChangelog {
+                content: to_ascii(&cl.content),
+            }),
+            Self::ReleaseNotes(rn) => Self::ReleaseNotes(crate::types::MarkdownReleaseNotes {
+                content: to_ascii(&rn.content),
+                ..rn
+            }),
+            Self::MarkdownReview(review) => Self::MarkdownReview(crate::types::MarkdownReview {
+                content: to_ascii(&review.content),
+            }),
+            Self::SemanticBlame(text) => Self::SemanticBlame(to_ascii(&text)),
+            Self::PlainText(text) => Self::PlainText(to_ascii(&text)),
+        }
+    }
+
+    /// Record detected breaking changes: `!` and footer for commit messages,
+    /// a flagged section for changelogs and release notes
+    pub fn with_breaking_changes(self, report: &crate::git::BreakingReport) -> Self {
+        if report.is_empty() {
+            return self;
+        }
+        match self {
+            Self::CommitMessage(mut msg) => {
+                crate::git::apply_breaking_changes(&mut msg, &report.changes);
+                Self::CommitMessage(msg)
+            }
+            Self::Changelog(cl) => Self::Changelog(crate::types::MarkdownChangelog {
+                content: report.flag_markdown(&cl.content),
+            }),
+            Self::ReleaseNotes(rn) => Self::ReleaseNotes(crate::types::MarkdownReleaseNotes {
+                content: report.flag_markdown(&rn.content),
+                ..rn
+            }),
+            other => other,
+        }
+    }
+
+    /// Append the upgrade guide to release notes that left it out
+    pub fn with_upgrade_guide(self, guide: &crate::upgrade_guide::UpgradeGuide) -> Self {
+        match self {
+            Self::ReleaseNotes(rn) => Self::ReleaseNotes(crate::types::MarkdownReleaseNotes {
+                content: guide.append_to(&rn.content),
+                ..rn
+            }),
+            other => other,
+        }
+    }
+
+    /// Append the dependency update table to PR descriptions and reviews
+    pub fn with_dependency_updates(
+        self,
+        report: &crate::dependency_updates::DependencyReport,
+    ) -> Self {
+        match self {
+            Self::PullRequest(pr) => Self::PullRequest(crate::types::MarkdownPullRequest {
+                content: report.append_to(&pr.content),
+            }),
+            Self::MarkdownReview(review) => Self::MarkdownReview(crate::types::MarkdownReview {
+                content: report.append_to(&review.content),
+            }),
+            other => other,
+        }
+    }
+
+    /// Name the files whose diffs didn't fit the token budget at the end of
+    /// markdown outputs
+    pub fn with_omissions(self, omitted: &[crate::agents::context::budget::Omission]) -> Self {
+        use crate::agents::context::budget::append_footer;
+
+        match self {
+            Self::PullRequest(pr) => Self::PullRequest(crate::types::MarkdownPullRequest {
+                content: append_footer(&pr.content, omitted),
+            }),
+            Self::Changelog(cl) => Self::Changelog(crate::types::MarkdownChangelog {
+                content: append_footer(&cl.content, omitted),
+            }),
+            Self::ReleaseNotes(rn) => Self::ReleaseNotes(crate::types::MarkdownReleaseNotes {
+                content: append_footer(&rn.content, omitted),
+                ..rn
+            }),
+            Self::MarkdownReview(review) => Self::MarkdownReview(crate::types::MarkdownReview {
+                content: append_footer(&review.content, omitted),
+            }),
+            other => other,
+        }
+    }
+}
+
 /// Extract JSON from a potentially verbose response that might contain explanations
 fn extract_json_from_response(response: &str) -> Result<String> {
     use crate::agents::debug;
@@ -186,7 +389,10 @@ fn extract_json_from_response(response: &str) -> Result<String> {
             ));
             // Include more context in the error for debugging
             let preview = if json_content.len() > 200 {
-                format!("{}...", &json_content[..200])
+                format!(
+                    "{}...",
+                    &json_content[..json_content.floor_char_boundary(200)]
+                )
             } else {
                 json_content.to_string()
             };
@@ -358,6 +564,14 @@ pub struct IrisAgent {
     content_update_sender: Option<crate::agents::tools::ContentUpdateSender>,
     /// Persistent workspace for notes and task tracking (shared across agent invocations)
     workspace: Workspace,
+    /// Token breakdown of the last generation
+    context_report: Option<ContextReport>,
+    /// Whether the agent may call tools; without them it answers from the
+    /// prompt alone
+    tools_enabled: bool,
+    /// Repository whose tool policy applies; the one containing the current
+    /// directory when unset
+    repo_root: Option<PathBuf>,
 }
 
 impl IrisAgent {
@@ -373,6 +587,9 @@ impl IrisAgent {
             config: None,
             content_update_sender: None,
             workspace: Workspace::new(),
+            context_report: None,
+            tools_enabled: true,
+            repo_root: None,
         })
     }
 
@@ -389,6 +606,40 @@ impl IrisAgent {
         self.fast_model.as_deref().unwrap_or(&self.model)
     }
 
+    /// HTTP headers configured for the provider, like the `OpenAI` organization
+    fn request_headers(&self) -> Vec<(String, String)> {
+        let Ok(provider) = self.provider.parse::<crate::providers::Provider>() else {
+            return Vec::new();
+        };
+        self.config.as_ref().map_or_else(
+            || crate::providers::ProviderConfig::default().request_headers(provider),
+            |config| config.request_headers(provider),
+        )
+    }
+
+    /// Diff tokens `git_diff` may return: half the provider's token limit
+    fn diff_budget(&self) -> usize {
+        let Ok(provider) = self.provider.parse::<crate::providers::Provider>() else {
+            return crate::agents::context::budget::DEFAULT_DIFF_BUDGET;
+        };
+        let token_limit = self.config.as_ref().map_or_else(
+            || provider.context_window(),
+            |config| config.token_limit(provider),
+        );
+        crate::agents::context::budget::diff_budget_for(token_limit)
+    }
+
+    /// The repository's tool policy, loaded once per built agent
+    fn tool_policy(&self) -> Arc<ToolPolicy> {
+        let root = self
+            .repo_root
+            .clone()
+            .or_else(|| crate::git::GitRepo::get_repo_root().ok());
+        Arc::new(root.map_or_else(ToolPolicy::default, |root| {
+            ToolPolicy::load_or_refuse(&root)
+        }))
+    }
+
     /// Build the actual agent for execution
     ///
     /// Note: We create a fresh `DynClientBuilder` each time because Rig's builder is
@@ -397,18 +648,20 @@ impl IrisAgent {
     fn build_agent(&self) -> Result<Agent<impl CompletionModel + 'static>> {
         use crate::agents::debug_tool::DebugTool;
 
-        let client_builder = DynClientBuilder::new();
+        let headers = self.request_headers();
+        let providers = client_builder(&headers);
+        let policy = self.tool_policy();
+        let diff_budget = self.diff_budget();
 
-        let agent_builder = client_builder
-            .agent(&self.provider, &self.model)
-            .map_err(|e| {
-                anyhow::anyhow!(
-                    "Failed to create agent builder for provider '{}': {}",
-                    self.provider,
-                    e
-                )
-            })?;
-        let agent_builder = self.apply_reasoning_defaults(agent_builder);
+        let agent_builder = providers.agent(&self.provider, &self.model).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to create agent builder for provider '{}': {}",
+                self.provider,
+                e
+            )
+        })?;
+        let generation = self.generation_params();
+        let agent_builder = self.apply_provider_params(agent_builder, &generation);
 
         let preamble = self.preamble.as_deref().unwrap_or(
             "You are Iris, a helpful AI assistant specialized in Git operations and workflows.
@@ -440,8 +693,8 @@ You have access to Git tools, code analysis tools, and powerful sub-agent capabi
         // This sub-agent has tools but cannot spawn more sub-agents (prevents recursion)
         // Uses fast model for cost efficiency since subagent tasks are focused/bounded
         let fast_model = self.effective_fast_model();
-        let client_builder = DynClientBuilder::new();
-        let sub_agent_builder = client_builder
+        let providers = client_builder(&headers);
+        let sub_agent_builder = providers
             .agent(&self.provider, fast_model)
             .map_err(|e| anyhow::anyhow!("Failed to create sub-agent: {}", e))?
             .name("analyze_subagent")
@@ -455,36 +708,60 @@ Guidelines:
 - Highlight important issues, patterns, or insights
 - Keep your response focused and concise")
             .max_tokens(4096);
-        let sub_agent_builder = self.apply_reasoning_defaults(sub_agent_builder);
+        let sub_agent_builder =
+            self.apply_provider_params(sub_agent_builder, &GenerationParams::default());
         // Use shared tool registry for core tools (prevents drift with subagents)
-        let sub_agent = crate::attach_core_tools!(sub_agent_builder).build();
+        let sub_agent = crate::attach_core_tools!(sub_agent_builder, &policy, diff_budget).build();
 
         // Start with preamble and max_tokens, then attach core tools via registry
-        let agent_builder = agent_builder.preamble(preamble).max_tokens(16384); // Increased for complex structured outputs like PRs and release notes
+        let agent_builder = agent_builder
+            .preamble(preamble)
+            .max_tokens(generation.max_tokens_or(DEFAULT_MAX_TOKENS));
+        let agent_builder = match generation.temperature {
+            Some(temperature) => agent_builder.temperature(temperature),
+            None => agent_builder,
+        };
 
         // Attach core tools (shared with subagents) + GitRepoInfo (main agent only)
-        let agent_builder = crate::attach_core_tools!(agent_builder)
-            .tool(DebugTool::new(GitRepoInfo))
+        let agent_builder = crate::attach_core_tools!(agent_builder, &policy, diff_budget)
+            .tool(DebugTool::new(GitRepoInfo, &policy))
             // Workspace for Iris's notes and task management (clone to share Arc-backed state)
-            .tool(DebugTool::new(self.workspace.clone()))
+            .tool(DebugTool::new(self.workspace.clone(), &policy))
             // Parallel analysis for distributing work across multiple subagents
-            .tool(DebugTool::new(ParallelAnalyze::with_timeout(
-                &self.provider,
-                fast_model,
-                self.config
-                    .as_ref()
-                    .map_or(120, |c| c.subagent_timeout_secs),
-            )))
-            // Sub-agent delegation (Rig's built-in agent-as-tool!)
-            .tool(sub_agent);
+            .tool(DebugTool::new(
+                ParallelAnalyze::with_timeout(
+                    &self.provider,
+                    fast_model,
+                    self.config
+                        .as_ref()
+                        .map_or(120, |c| c.subagent_timeout_secs),
+                    &headers,
+                    &policy,
+                    diff_budget,
+                ),
+                &policy,
+            ));
+        // Sub-agent delegation (Rig's built-in agent-as-tool!); its calls can't
+        // be refused one by one, so a policy that denies it leaves it off
+        let agent_builder = if policy.denies_tool("analyze_subagent") {
+            agent_builder
+        } else {
+            agent_builder.tool(sub_agent)
+        };
 
         // Add content update tools if a sender is configured (Studio chat mode)
         if let Some(sender) = &self.content_update_sender {
             use crate::agents::tools::{UpdateCommitTool, UpdatePRTool, UpdateReviewTool};
             let agent = agent_builder
-                .tool(DebugTool::new(UpdateCommitTool::new(sender.clone())))
-                .tool(DebugTool::new(UpdatePRTool::new(sender.clone())))
-                .tool(DebugTool::new(UpdateReviewTool::new(sender.clone())))
+                .tool(DebugTool::new(
+                    UpdateCommitTool::new(sender.clone()),
+                    &policy,
+                ))
+                .tool(DebugTool::new(UpdatePRTool::new(sender.clone()), &policy))
+                .tool(DebugTool::new(
+                    UpdateReviewTool::new(sender.clone()),
+                    &policy,
+                ))
                 .build();
             Ok(agent)
         } else {
@@ -492,19 +769,160 @@ Guidelines:
         }
     }
 
-    fn apply_reasoning_defaults<M>(&self, builder: RigAgentBuilder<M>) -> RigAgentBuilder<M>
+    /// Build an agent without tools, for models that can't call them
+    ///
+    /// Answers from it rely on the context already in the prompt.
+    fn build_plain_agent(&self) -> Result<Agent<impl CompletionModel + 'static>> {
+        let generation = self.generation_params();
+        let agent_builder = client_builder(&self.request_headers())
+            .agent(&self.provider, &self.model)
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to create agent builder for provider '{}': {}",
+                    self.provider,
+                    e
+                )
+            })?;
+        let agent_builder = self
+            .apply_provider_params(agent_builder, &generation)
+            .preamble(self.preamble.as_deref().unwrap_or(
+                "You are Iris, a helpful AI assistant specialized in Git operations and workflows.",
+            ))
+            .max_tokens(generation.max_tokens_or(DEFAULT_MAX_TOKENS));
+        let agent_builder = match generation.temperature {
+            Some(temperature) => agent_builder.temperature(temperature),
+            None => agent_builder,
+        };
+        Ok(agent_builder.build())
+    }
+
+    /// Prompt the agent with tools, retrying without them if the model
+    /// rejects tool calls
+    async fn prompt_with_tools(
+        &self,
+        prompt: &str,
+        tracker: &ContextTracker,
+    ) -> Result<PromptResponse> {
+        if !self.tools_enabled {
+            let agent = self.build_plain_agent()?;
+            return Ok(agent
+                .prompt(prompt)
+                .extended_details()
+                .with_hook(tracker.clone())
+                .await?);
+        }
+
+        let agent = self.build_agent()?;
+        let error = match agent
+            .prompt(prompt)
+            .multi_turn(50)
+            .extended_details()
+            .with_hook(tracker.clone())
+            .await
+        {
+            Ok(response) => return Ok(response),
+            Err(e) if lacks_tool_support(&e.to_string()) => e,
+            Err(e) => return Err(e.into()),
+        };
+
+        tracing::warn!(
+            "{} can't call tools ({}), continuing without them",
+            self.model,
+            error
+        );
+        crate::iris_status_dynamic!(
+            crate::agents::status::IrisPhase::Generation,
+            format!(
+                "{} has no tool support, working from the prompt alone",
+                self.model
+            ),
+            3,
+            4
+        );
+        let agent = self.build_plain_agent()?;
+        Ok(agent
+            .prompt(prompt)
+            .extended_details()
+            .with_hook(tracker.clone())
+            .await?)
+    }
+
+    /// Generation params configured for the current capability
+    fn generation_params(&self) -> GenerationParams {
+        self.config
+            .as_ref()
+            .map(|c| c.generation_params(self.current_capability().unwrap_or("default")))
+            .unwrap_or_default()
+    }
+
+    /// Attach provider-specific request params (reasoning effort, sampling knobs).
+    ///
+    /// Rig replaces rather than merges `additional_params`, so everything goes
+    /// into a single object.
+    fn apply_provider_params<M>(
+        &self,
+        builder: RigAgentBuilder<M>,
+        generation: &GenerationParams,
+    ) -> RigAgentBuilder<M>
     where
         M: CompletionModel,
     {
-        if self.provider == "openai" && Self::requires_reasoning_effort(&self.model) {
-            builder.additional_params(json!({
-                "reasoning": {
-                    "effort": "low"
-                }
-            }))
-        } else {
+        let params = Self::provider_params(&self.provider, &self.model, generation);
+        if params.as_object().is_some_and(serde_json::Map::is_empty) {
             builder
+        } else {
+            builder.additional_params(params)
+        }
+    }
+
+    /// Build the `additional_params` object for a provider
+    fn provider_params(
+        provider: &str,
+        model: &str,
+        generation: &GenerationParams,
+    ) -> serde_json::Value {
+        let mut params = json!({});
+        let has_penalties =
+            generation.presence_penalty.is_some() || generation.frequency_penalty.is_some();
+
+        match provider {
+            "openai" => {
+                if Self::requires_reasoning_effort(model) {
+                    params["reasoning"] = json!({ "effort": "low" });
+                }
+                if let Some(top_p) = generation.top_p {
+                    params["top_p"] = json!(top_p);
+                }
+            }
+            "anthropic" => {
+                if let Some(top_p) = generation.top_p {
+                    params["top_p"] = json!(top_p);
+                }
+            }
+            "google" => {
+                // Gemini takes sampling knobs inside generationConfig
+                let mut config = json!({});
+                if let Some(top_p) = generation.top_p {
+                    config["topP"] = json!(top_p);
+                }
+                if let Some(penalty) = generation.presence_penalty {
+                    config["presencePenalty"] = json!(penalty);
+                }
+                if let Some(penalty) = generation.frequency_penalty {
+                    config["frequencyPenalty"] = json!(penalty);
+                }
+                if config.as_object().is_some_and(|c| !c.is_empty()) {
+                    params["generationConfig"] = config;
+                }
+                return params;
+            }
+            _ => {}
+        }
+
+        if has_penalties {
+            crate::log_debug!("Presence/frequency penalties are not supported by {provider}");
         }
+        params
     }
 
     fn requires_reasoning_effort(model: &str) -> bool {
@@ -514,7 +932,8 @@ Guidelines:
 
     /// Execute task using agent with tools and parse structured JSON response
     /// This is the core method that enables Iris to use tools and generate structured outputs
-    async fn execute_with_agent<T>(&self, system_prompt: &str, user_prompt: &str) -> Result<T>
+    #[allow(clippy::too_many_lines)]
+    async fn execute_with_agent<T>(&mut self, system_prompt: &str, user_prompt: &str) -> Result<T>
     where
         T: JsonSchema + for<'a> serde::Deserialize<'a> + serde::Serialize + Send + Sync + 'static,
     {
@@ -523,7 +942,8 @@ Guidelines:
         use crate::messages::get_capability_message;
         use schemars::schema_for;
 
-        let capability = self.current_capability().unwrap_or("commit");
+        let capability = self.current_capability().unwrap_or("commit").to_string();
+        let capability = capability.as_str();
 
         debug::debug_phase_change(&format!("AGENT EXECUTION: {}", std::any::type_name::<T>()));
 
@@ -531,10 +951,8 @@ Guidelines:
         let msg = get_capability_message(capability);
         crate::iris_status_dynamic!(IrisPhase::Planning, msg.text, 2, 4);
 
-        // Build agent with all tools attached
-        let agent = self.build_agent()?;
         debug::debug_context_management(
-            "Agent built with tools",
+            "Building agent with tools",
             &format!(
                 "Provider: {}, Model: {} (fast: {})",
                 self.provider,
@@ -569,7 +987,8 @@ Guidelines:
             Your entire response should be ONLY the JSON object."
         );
 
-        debug::debug_llm_request(&full_prompt, Some(16384));
+        let max_tokens = self.generation_params().max_tokens_or(DEFAULT_MAX_TOKENS);
+        debug::debug_llm_request(&full_prompt, usize::try_from(max_tokens).ok());
 
         // Update status - generation phase (capability-aware)
         let gen_msg = get_capability_message(capability);
@@ -585,13 +1004,18 @@ Guidelines:
             "LLM request",
             "Sending prompt to agent with multi_turn(50)",
         );
-        let prompt_response: PromptResponse = agent
-            .prompt(&full_prompt)
-            .multi_turn(50)
-            .extended_details()
-            .await?;
+        let tracker = ContextTracker::default();
+        let prompt_response = self.prompt_with_tools(&full_prompt, &tracker).await?;
 
         timer.finish();
+        self.context_report = Some(tracker.report(
+            capability,
+            &self.model,
+            &full_prompt,
+            user_prompt,
+            &prompt_response.output,
+            prompt_response.total_usage,
+        ));
 
         // Extract usage stats for debug output
         let usage = &prompt_response.total_usage;
@@ -646,9 +1070,27 @@ Guidelines:
         Ok(result)
     }
 
+    /// Ask for the configured output length and tone, when they aren't the defaults
+    fn inject_output_style(
+        config: &crate::config::Config,
+        system_prompt: &mut String,
+        capability: &str,
+    ) {
+        let generation = config.generation_params(capability);
+        if let Some(instructions) = generation.verbosity.and_then(Verbosity::instructions) {
+            system_prompt.push_str("\n\n=== LENGTH ===\n");
+            system_prompt.push_str(instructions);
+        }
+        if let Some(instructions) = generation.tone.and_then(Tone::instructions) {
+            system_prompt.push_str("\n\n=== TONE ===\n");
+            system_prompt.push_str(instructions);
+        }
+    }
+
     /// Inject style instructions into the system prompt based on config and capability
     ///
-    /// Handles: instruction presets, gitmoji, conventional commits, and capability-specific styling
+    /// Handles: instruction presets, length and tone, gitmoji, conventional commits, and
+    /// capability-specific styling
     fn inject_style_instructions(&self, system_prompt: &mut String, capability: &str) {
         let Some(config) = &self.config else {
             return;
@@ -663,7 +1105,8 @@ Guidelines:
         let use_style_detection =
             capability == "commit" && is_default_mode && config.gitmoji_override.is_none();
 
-        let gitmoji_enabled = config.use_gitmoji && !is_conventional && !use_style_detection;
+        let gitmoji_enabled =
+            config.use_gitmoji && !is_conventional && !use_style_detection && !config.ascii_only;
 
         // Inject instruction preset if configured (skip for default mode)
         if !preset_name.is_empty() && !is_default_mode {
@@ -678,6 +1121,15 @@ Guidelines:
             }
         }
 
+        Self::inject_output_style(config, system_prompt, capability);
+
+        if config.ascii_only {
+            system_prompt.push_str("\n\n=== ASCII ONLY ===\n");
+            system_prompt.push_str(
+                "Use only ASCII characters in all output: no emojis, smart quotes, em dashes, ellipsis characters, arrows, or other Unicode symbols. The 'emoji' field must be null.",
+            );
+        }
+
         // Handle commit-specific styling (structured JSON output with emoji field)
         if capability == "commit" {
             if use_style_detection {
@@ -790,7 +1242,7 @@ Guidelines:
 
         // Use agent with tools for all structured outputs
         // The agent will use tools as needed and respond with JSON
-        match output_type.as_str() {
+        let response = match output_type.as_str() {
             "GeneratedMessage" => {
                 let response = self
                     .execute_with_agent::<crate::types::GeneratedMessage>(
@@ -798,7 +1250,7 @@ Guidelines:
                         user_prompt,
                     )
                     .await?;
-                Ok(StructuredResponse::CommitMessage(response))
+                StructuredResponse::CommitMessage(response)
             }
             "MarkdownPullRequest" => {
                 let response = self
@@ -807,7 +1259,7 @@ Guidelines:
                         user_prompt,
                     )
                     .await?;
-                Ok(StructuredResponse::PullRequest(response))
+                StructuredResponse::PullRequest(response)
             }
             "MarkdownChangelog" => {
                 let response = self
@@ -816,7 +1268,7 @@ Guidelines:
                         user_prompt,
                     )
                     .await?;
-                Ok(StructuredResponse::Changelog(response))
+                StructuredResponse::Changelog(response)
             }
             "MarkdownReleaseNotes" => {
                 let response = self
@@ -825,20 +1277,20 @@ Guidelines:
                         user_prompt,
                     )
                     .await?;
-                Ok(StructuredResponse::ReleaseNotes(response))
+                StructuredResponse::ReleaseNotes(response)
             }
             "MarkdownReview" => {
                 let response = self
                     .execute_with_agent::<crate::types::MarkdownReview>(&system_prompt, user_prompt)
                     .await?;
-                Ok(StructuredResponse::MarkdownReview(response))
+                StructuredResponse::MarkdownReview(response)
             }
             "SemanticBlame" => {
                 // For semantic blame, we want plain text response
                 let agent = self.build_agent()?;
                 let full_prompt = format!("{system_prompt}\n\n{user_prompt}");
                 let response = agent.prompt(&full_prompt).multi_turn(10).await?;
-                Ok(StructuredResponse::SemanticBlame(response))
+                StructuredResponse::SemanticBlame(response)
             }
             _ => {
                 // Fallback to regular agent for unknown types
@@ -846,9 +1298,11 @@ Guidelines:
                 let full_prompt = format!("{system_prompt}\n\n{user_prompt}");
                 // Use multi_turn to allow tool calls even for unknown capability types
                 let response = agent.prompt(&full_prompt).multi_turn(50).await?;
-                Ok(StructuredResponse::PlainText(response))
+                StructuredResponse::PlainText(response)
             }
-        }
+        };
+
+        Ok(self.finalize_response(response))
     }
 
     /// Execute a task with streaming, calling the callback with each text chunk
@@ -857,6 +1311,7 @@ Guidelines:
     /// The callback receives `(chunk, aggregated_text)` for each delta.
     ///
     /// Returns the final structured response after streaming completes.
+    #[allow(clippy::too_many_lines)]
     pub async fn execute_task_streaming<F>(
         &mut self,
         capability: &str,
@@ -909,9 +1364,16 @@ Guidelines:
         crate::iris_status_dynamic!(IrisPhase::Generation, gen_msg.text, 3, 4);
 
         // Use streaming prompt
-        let mut stream = agent.stream_prompt(&full_prompt).multi_turn(50).await;
+        let tracker = ContextTracker::default();
+        let mut stream = agent
+            .stream_prompt(&full_prompt)
+            .multi_turn(50)
+            .with_hook(tracker.clone())
+            .await;
+        let mut usage = rig::completion::Usage::new();
 
         let mut aggregated_text = String::new();
+        let mut streaming_failed = false;
 
         // Consume the stream
         while let Some(item) = stream.next().await {
@@ -938,8 +1400,20 @@ Guidelines:
                         4
                     );
                 }
-                Ok(MultiTurnStreamItem::FinalResponse(_)) => {
+                Ok(MultiTurnStreamItem::FinalResponse(response)) => {
                     // Stream complete
+                    usage = response.usage();
+                    break;
+                }
+                // Local models may not stream tool calls, or call tools at
+                // all; nothing has been shown yet, so retry without streaming
+                Err(e)
+                    if aggregated_text.is_empty()
+                        && (self.provider == crate::providers::Provider::Ollama.name()
+                            || lacks_tool_support(&e.to_string())) =>
+                {
+                    tracing::warn!("Streaming failed ({}), retrying without streaming", e);
+                    streaming_failed = true;
                     break;
                 }
                 Err(e) => {
@@ -951,6 +1425,14 @@ Guidelines:
             }
         }
 
+        if streaming_failed {
+            drop(stream);
+            let response = self.prompt_with_tools(&full_prompt, &tracker).await?;
+            aggregated_text = response.output;
+            usage = response.total_usage;
+            on_chunk(&aggregated_text, &aggregated_text);
+        }
+
         // Update status
         crate::iris_status_dynamic!(
             IrisPhase::Synthesis,
@@ -959,6 +1441,15 @@ Guidelines:
             4
         );
 
+        self.context_report = Some(tracker.report(
+            capability,
+            &self.model,
+            &full_prompt,
+            user_prompt,
+            &aggregated_text,
+            usage,
+        ));
+
         // Convert the aggregated text to structured response based on output type
         let response = match output_type.as_str() {
             "MarkdownReview" => StructuredResponse::MarkdownReview(crate::types::MarkdownReview {
@@ -975,6 +1466,7 @@ Guidelines:
             "MarkdownReleaseNotes" => {
                 StructuredResponse::ReleaseNotes(crate::types::MarkdownReleaseNotes {
                     content: aggregated_text,
+                    suggested_version: None,
                 })
             }
             "SemanticBlame" => StructuredResponse::SemanticBlame(aggregated_text),
@@ -983,47 +1475,39 @@ Guidelines:
 
         crate::iris_status_completed!();
 
-        Ok(response)
+        Ok(self.finalize_response(response))
     }
 
-    /// Load capability configuration from embedded TOML, returning both prompt and output type
-    fn load_capability_config(&self, capability: &str) -> Result<(String, String)> {
-        let _ = self; // Keep &self for method syntax consistency
-        // Use embedded capability strings - always available regardless of working directory
-        let content = match capability {
-            "commit" => CAPABILITY_COMMIT,
-            "pr" => CAPABILITY_PR,
-            "review" => CAPABILITY_REVIEW,
-            "changelog" => CAPABILITY_CHANGELOG,
-            "release_notes" => CAPABILITY_RELEASE_NOTES,
-            "chat" => CAPABILITY_CHAT,
-            "semantic_blame" => CAPABILITY_SEMANTIC_BLAME,
-            _ => {
-                // Return generic prompt for unknown capabilities
-                return Ok((
-                    format!(
-                        "You are helping with a {capability} task. Use the available Git tools to assist the user."
-                    ),
-                    "PlainText".to_string(),
-                ));
-            }
+    /// Apply output policies from config to a finished response
+    fn finalize_response(&self, response: StructuredResponse) -> StructuredResponse {
+        let Some(config) = self.config.as_ref() else {
+            return response;
         };
+        let response = if config.ascii_only {
+            response.into_ascii()
+        } else {
+            response
+        };
+        match response {
+            StructuredResponse::CommitMessage(mut msg) => {
+                config.commit_style.apply(&mut msg);
+                StructuredResponse::CommitMessage(msg)
+            }
+            other => other,
+        }
+    }
 
-        // Parse TOML to extract both task_prompt and output_type
-        let parsed: toml::Value = toml::from_str(content)?;
-
-        let task_prompt = parsed
-            .get("task_prompt")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("No task_prompt found in capability file"))?;
-
-        let output_type = parsed
-            .get("output_type")
-            .and_then(|v| v.as_str())
-            .unwrap_or("PlainText")
-            .to_string();
-
-        Ok((task_prompt.to_string(), output_type))
+    /// Load capability configuration, returning both prompt and output type.
+    ///
+    /// Embedded definitions are merged with any repository prompt overrides.
+    fn load_capability_config(&self, capability: &str) -> Result<(String, String)> {
+        let _ = self; // Keep &self for method syntax consistency
+        let repo_root = crate::git::GitRepo::get_repo_root().ok();
+        let prompt = prompts::load_capability(capability, repo_root.as_deref())?;
+        for path in &prompt.overrides {
+            crate::log_debug!("Applied prompt override: {}", path.display());
+        }
+        Ok((prompt.task_prompt, prompt.output_type))
     }
 
     /// Get the current capability being executed
@@ -1053,6 +1537,11 @@ Guidelines:
         self.provider_config = config;
     }
 
+    /// Take the token breakdown of the last generation, if it was measured
+    pub fn take_context_report(&mut self) -> Option<ContextReport> {
+        self.context_report.take()
+    }
+
     /// Set custom preamble
     pub fn set_preamble(&mut self, preamble: String) {
         self.preamble = Some(preamble);
@@ -1063,10 +1552,20 @@ Guidelines:
         self.config = Some(config);
     }
 
+    /// Apply the tool policy of the repository at `root`
+    pub fn set_repo_root(&mut self, root: PathBuf) {
+        self.repo_root = Some(root);
+    }
+
     /// Set fast model for subagents
     pub fn set_fast_model(&mut self, fast_model: String) {
         self.fast_model = Some(fast_model);
     }
+
+    /// Allow or forbid tool calls, for prompts that carry all their context
+    pub fn set_tools_enabled(&mut self, enabled: bool) {
+        self.tools_enabled = enabled;
+    }
 }
 
 /// Builder for creating `IrisAgent` instances with different configurations
@@ -1145,4 +1644,39 @@ Line2\"}";
         assert_eq!(sanitized.as_ref(), "{\"description\": \"Line1\\nLine2\"}");
         serde_json::from_str::<Value>(sanitized.as_ref()).expect("json sanitized");
     }
+
+    #[test]
+    fn provider_params_follow_provider_conventions() {
+        use super::IrisAgent;
+        use crate::config::GenerationParams;
+
+        let generation = GenerationParams {
+            top_p: Some(0.9),
+            presence_penalty: Some(0.5),
+            ..GenerationParams::default()
+        };
+
+        let openai = IrisAgent::provider_params("openai", "gpt-5.1", &generation);
+        assert_eq!(openai["reasoning"]["effort"], "low");
+        assert_eq!(openai["top_p"], 0.9);
+        assert!(openai.get("presence_penalty").is_none());
+
+        let google = IrisAgent::provider_params("google", "gemini-2.5-flash", &generation);
+        assert_eq!(google["generationConfig"]["topP"], 0.9);
+        assert_eq!(google["generationConfig"]["presencePenalty"], 0.5);
+
+        let anthropic =
+            IrisAgent::provider_params("anthropic", "claude", &GenerationParams::default());
+        assert!(anthropic.as_object().is_some_and(serde_json::Map::is_empty));
+    }
+
+    #[test]
+    fn test_client_builder_registers_google() {
+        // Builds without GEMINI_API_KEY, which Rig's own factory requires
+        assert!(
+            super::client_builder(&[])
+                .agent("google", "gemini-2.5-flash")
+                .is_ok()
+        );
+    }
 }
@@ -5,8 +5,12 @@
 
 // Core agent components
 pub mod context;
+pub mod context_report;
 pub mod core;
 pub mod iris;
+pub mod policy;
+pub mod prompts;
+pub mod summarize;
 
 // Agent tools
 pub mod tools;
@@ -27,10 +31,11 @@ pub mod output_validator;
 
 // Re-exports for public API
 pub use context::TaskContext;
+pub use context_report::ContextReport;
 pub use core::{AgentBackend, AgentContext, TaskResult};
 pub use iris::{IrisAgent, IrisAgentBuilder, StreamingCallback, StructuredResponse};
 pub use setup::{AgentSetupService, IrisAgentService, handle_with_agent};
 pub use status_messages::{
-    StatusContext, StatusMessage, StatusMessageBatch, StatusMessageGenerator,
+    StatusContext, StatusMessage, StatusMessageBatch, StatusMessageGenerator, StatusPersonality,
 };
-pub use tools::{GitChangedFiles, GitDiff, GitLog, GitRepoInfo, GitStatus};
+pub use tools::{GitChangedFiles, GitDiff, GitLog, GitRepoInfo, GitStats, GitStatus};
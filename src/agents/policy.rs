@@ -0,0 +1,396 @@
+//! Agent tool access policy
+//!
+//! A repository can limit what Iris's tools may do in it with
+//! `.git-iris/policy.toml`:
+//!
+//! ```toml
+//! [tools]
+//! deny = ["parallel_analyze"]  # tools that may not be called at all
+//! shell = false                # refuse tools that run external programs
+//! workspace_writes = false     # the workspace may be read but not written
+//!
+//! [paths]
+//! allow = ["src/", "docs/**/*.md"]  # the only paths tools may read
+//! deny = ["**/.env", "secrets/"]    # never readable, even when allowed
+//! ```
+//!
+//! Each agent loads its repository's policy once, when it's built, and every
+//! tool call goes through [`ToolPolicy::check_tool_call`] before it runs. A
+//! refused call returns an error to the model, is logged, and is kept so the
+//! CLI and Studio can tell the user afterwards (see [`take_violations`]).
+//! Tools that return content from many files at once, like `git_diff` without
+//! a file list and `code_search`, leave out the paths the policy denies, and
+//! `project_docs` checks each file it picks with [`ToolPolicy::check_read`].
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Component, Path};
+use std::sync::Mutex;
+
+use crate::log_debug;
+use crate::risk::glob_to_regex;
+
+/// Policy file, relative to the repository root
+pub const POLICY_FILE: &str = ".git-iris/policy.toml";
+
+/// Tools that run an external program
+const SHELL_TOOLS: &[&str] = &["code_search"];
+
+/// Refusals since the last [`take_violations`]
+static VIOLATIONS: Mutex<Vec<PolicyViolation>> = Mutex::new(Vec::new());
+
+/// A tool call the policy refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyViolation {
+    /// Tool that was called
+    pub tool: String,
+    /// Why it was refused
+    pub reason: String,
+}
+
+impl std::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.tool, self.reason)
+    }
+}
+
+/// `[tools]` section of the policy file
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ToolRules {
+    deny: Vec<String>,
+    shell: bool,
+    workspace_writes: bool,
+}
+
+impl Default for ToolRules {
+    fn default() -> Self {
+        Self {
+            deny: Vec::new(),
+            shell: true,
+            workspace_writes: true,
+        }
+    }
+}
+
+/// `[paths]` section of the policy file
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PathRules {
+    allow: Vec<String>,
+    deny: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PolicyFile {
+    tools: ToolRules,
+    paths: PathRules,
+}
+
+/// What agent tools may do in a repository
+#[derive(Debug)]
+pub struct ToolPolicy {
+    deny_tools: Vec<String>,
+    allow_shell: bool,
+    workspace_writes: bool,
+    /// Readable paths; everything when empty
+    allow_paths: Vec<Regex>,
+    deny_paths: Vec<Regex>,
+    /// Why the policy file couldn't be read; every call is refused then
+    unreadable: Option<String>,
+}
+
+impl Default for ToolPolicy {
+    fn default() -> Self {
+        Self::from_file(PolicyFile::default())
+    }
+}
+
+impl ToolPolicy {
+    /// Parse a policy file's contents
+    pub fn parse(content: &str) -> Result<Self> {
+        let file: PolicyFile = toml::from_str(content)?;
+        Ok(Self::from_file(file))
+    }
+
+    /// Load `repo_root`'s policy; everything is allowed without one
+    pub fn load(repo_root: &Path) -> Result<Self> {
+        let path = repo_root.join(POLICY_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                Self::parse(&content).with_context(|| format!("Invalid {}", path.display()))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// Load `repo_root`'s policy, refusing every call when the file can't be
+    /// read or parsed rather than allowing them
+    pub fn load_or_refuse(repo_root: &Path) -> Self {
+        Self::load(repo_root).unwrap_or_else(|e| Self {
+            unreadable: Some(format!("{e:#}")),
+            ..Self::default()
+        })
+    }
+
+    fn from_file(file: PolicyFile) -> Self {
+        let compile =
+            |patterns: &[String]| patterns.iter().filter_map(|p| glob_to_regex(p)).collect();
+        Self {
+            deny_tools: file.tools.deny,
+            allow_shell: file.tools.shell,
+            workspace_writes: file.tools.workspace_writes,
+            allow_paths: compile(&file.paths.allow),
+            deny_paths: compile(&file.paths.deny),
+            unreadable: None,
+        }
+    }
+
+    /// Why `tool` may not be called with `args`, or `None` when it may
+    pub fn check(&self, tool: &str, args: &Value) -> Option<String> {
+        if let Some(reason) = &self.unreadable {
+            return Some(reason.clone());
+        }
+        if self.deny_tools.iter().any(|denied| denied == tool) {
+            return Some("this tool is disabled".to_string());
+        }
+        if !self.allow_shell && SHELL_TOOLS.contains(&tool) {
+            return Some("tools that run external programs are disabled".to_string());
+        }
+        if tool == "workspace"
+            && !self.workspace_writes
+            && args.get("action").and_then(Value::as_str) != Some("get_summary")
+        {
+            return Some("only get_summary is allowed".to_string());
+        }
+        requested_paths(tool, args)
+            .into_iter()
+            .find_map(|path| self.check_path(path))
+    }
+
+    /// Whether tools may return the contents of `path`
+    pub fn allows_path(&self, path: &str) -> bool {
+        self.unreadable.is_none() && self.check_path(path).is_none()
+    }
+
+    /// Whether `tool` is disabled outright
+    ///
+    /// For tools that can't be refused per call, like the sub-agent, and so are
+    /// left off the agent instead.
+    pub fn denies_tool(&self, tool: &str) -> bool {
+        self.unreadable.is_some() || self.deny_tools.iter().any(|denied| denied == tool)
+    }
+
+    /// Check a tool call, recording a refusal
+    pub fn check_tool_call(&self, tool: &str, args: &Value) -> Result<(), PolicyViolation> {
+        match self.check(tool, args) {
+            Some(reason) => Err(record(tool, reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check a file `tool` picked itself before reading it, recording a refusal
+    ///
+    /// For tools whose arguments don't name the files they read, like
+    /// `project_docs`. Paths outside the repository are given absolute.
+    pub fn check_read(&self, tool: &str, path: &str) -> Result<(), PolicyViolation> {
+        let reason = self.unreadable.clone().or_else(|| self.check_path(path));
+        match reason {
+            Some(reason) => Err(record(tool, reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Why `path` may not be read, or `None` when it may
+    fn check_path(&self, path: &str) -> Option<String> {
+        if self.allow_paths.is_empty() && self.deny_paths.is_empty() {
+            return None;
+        }
+        let Some(normalized) = normalize(path) else {
+            return Some(format!("'{path}' is outside the repository"));
+        };
+        if self.deny_paths.iter().any(|re| re.is_match(&normalized)) {
+            return Some(format!("'{path}' is denied"));
+        }
+        if !self.allow_paths.is_empty()
+            && !self.allow_paths.iter().any(|re| re.is_match(&normalized))
+        {
+            return Some(format!("'{path}' is outside the allowed paths"));
+        }
+        None
+    }
+}
+
+/// Log a refusal and keep it for [`take_violations`]
+fn record(tool: &str, reason: String) -> PolicyViolation {
+    let violation = PolicyViolation {
+        tool: tool.to_string(),
+        reason,
+    };
+    log_debug!("Tool call refused by policy: {}", violation);
+    tracing::warn!("Tool call refused by policy: {}", violation);
+    if let Ok(mut violations) = VIOLATIONS.lock() {
+        violations.push(violation.clone());
+    }
+    violation
+}
+
+/// Repository paths a tool call reads
+fn requested_paths<'a>(tool: &str, args: &'a Value) -> Vec<&'a str> {
+    match tool {
+        "file_read" => args
+            .get("path")
+            .and_then(Value::as_str)
+            .into_iter()
+            .collect(),
+        "git_diff" => args
+            .get("files")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Resolve `.` and `..` in a relative path; `None` if it leaves the root
+fn normalize(path: &str) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                parts.pop()?;
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// Refusals recorded since the last call, oldest first
+pub fn take_violations() -> Vec<PolicyViolation> {
+    VIOLATIONS
+        .lock()
+        .map(|mut violations| std::mem::take(&mut *violations))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_policy_rules() {
+        let policy = ToolPolicy::parse(
+            r#"
+            [tools]
+            deny = ["parallel_analyze"]
+            shell = false
+            workspace_writes = false
+
+            [paths]
+            allow = ["src/"]
+            deny = ["src/secrets/"]
+            "#,
+        )
+        .expect("valid policy");
+
+        assert!(policy.check("parallel_analyze", &json!({})).is_some());
+        assert!(
+            policy
+                .check("code_search", &json!({"query": "x"}))
+                .is_some()
+        );
+        assert!(policy.check("git_log", &json!({})).is_none());
+
+        assert!(
+            policy
+                .check("workspace", &json!({"action": "get_summary"}))
+                .is_none()
+        );
+        assert!(
+            policy
+                .check("workspace", &json!({"action": "add_note"}))
+                .is_some()
+        );
+
+        assert!(
+            policy
+                .check("file_read", &json!({"path": "src/main.rs"}))
+                .is_none()
+        );
+        assert!(
+            policy
+                .check("file_read", &json!({"path": "./src/lib.rs"}))
+                .is_none()
+        );
+        assert!(
+            policy
+                .check("file_read", &json!({"path": "Cargo.toml"}))
+                .is_some()
+        );
+        assert!(
+            policy
+                .check("file_read", &json!({"path": "src/../Cargo.toml"}))
+                .is_some()
+        );
+        assert!(
+            policy
+                .check("file_read", &json!({"path": "src/secrets/key.pem"}))
+                .is_some()
+        );
+        assert!(
+            policy
+                .check("git_diff", &json!({"files": ["src/a.rs", "README.md"]}))
+                .is_some()
+        );
+
+        assert!(policy.allows_path("src/a.rs"));
+        assert!(!policy.allows_path("src/secrets/key.pem"));
+        assert!(!policy.allows_path("README.md"));
+        assert!(policy.denies_tool("parallel_analyze"));
+        assert!(!policy.denies_tool("git_log"));
+    }
+
+    #[test]
+    fn test_unreadable_policy_refuses_everything() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(
+            ToolPolicy::load_or_refuse(dir.path())
+                .check("git_log", &json!({}))
+                .is_none()
+        );
+
+        std::fs::create_dir(dir.path().join(".git-iris")).expect("policy dir");
+        std::fs::write(dir.path().join(POLICY_FILE), "[tools\n").expect("policy file");
+        let policy = ToolPolicy::load_or_refuse(dir.path());
+        assert!(policy.check("git_log", &json!({})).is_some());
+        assert!(!policy.allows_path("src/a.rs"));
+        assert!(policy.denies_tool("analyze_subagent"));
+    }
+
+    #[test]
+    fn test_default_policy_allows_everything() {
+        let policy = ToolPolicy::default();
+        assert!(policy.check("code_search", &json!({})).is_none());
+        assert!(
+            policy
+                .check("workspace", &json!({"action": "add_task"}))
+                .is_none()
+        );
+        assert!(
+            policy
+                .check("file_read", &json!({"path": "a/b.rs"}))
+                .is_none()
+        );
+        assert!(ToolPolicy::parse("[tools]\nallow_all = true\n").is_err());
+    }
+}
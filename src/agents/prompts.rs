@@ -0,0 +1,194 @@
+//! Capability prompt loading
+//!
+//! Capability definitions are embedded TOML files. A repository can replace
+//! a capability's task prompt with `.git-iris/prompts/<capability>.md`, or
+//! extend it with `.git-iris/prompts/<capability>.append.md`.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Embed capability TOML files at compile time so they're always available
+const CAPABILITY_COMMIT: &str = include_str!("capabilities/commit.toml");
+const CAPABILITY_PR: &str = include_str!("capabilities/pr.toml");
+const CAPABILITY_REVIEW: &str = include_str!("capabilities/review.toml");
+const CAPABILITY_CHANGELOG: &str = include_str!("capabilities/changelog.toml");
+const CAPABILITY_RELEASE_NOTES: &str = include_str!("capabilities/release_notes.toml");
+const CAPABILITY_CHAT: &str = include_str!("capabilities/chat.toml");
+const CAPABILITY_SEMANTIC_BLAME: &str = include_str!("capabilities/semantic_blame.toml");
+const CAPABILITY_RELEASE_READINESS: &str = include_str!("capabilities/release_readiness.toml");
+const CAPABILITY_RECOVER: &str = include_str!("capabilities/recover.toml");
+const CAPABILITY_ONBOARD: &str = include_str!("capabilities/onboard.toml");
+const CAPABILITY_ADR: &str = include_str!("capabilities/adr.toml");
+const CAPABILITY_PLAN: &str = include_str!("capabilities/plan.toml");
+const CAPABILITY_REBASE: &str = include_str!("capabilities/rebase.toml");
+const CAPABILITY_STASH: &str = include_str!("capabilities/stash.toml");
+const CAPABILITY_SEMANTIC_SEARCH: &str = include_str!("capabilities/semantic_search.toml");
+const CAPABILITY_RECAP: &str = include_str!("capabilities/recap.toml");
+const CAPABILITY_EXPLAIN_REPO: &str = include_str!("capabilities/explain_repo.toml");
+const CAPABILITY_FILE_BLAME: &str = include_str!("capabilities/file_blame.toml");
+
+/// Capabilities with embedded definitions
+pub const CAPABILITIES: &[&str] = &[
+    "commit",
+    "review",
+    "pr",
+    "changelog",
+    "release_notes",
+    "chat",
+    "semantic_blame",
+    "release_readiness",
+    "recover",
+    "onboard",
+    "adr",
+    "plan",
+    "rebase",
+    "stash",
+    "semantic_search",
+    "recap",
+    "explain_repo",
+    "file_blame",
+];
+
+/// Directory (relative to the repository root) holding prompt overrides
+pub const PROMPTS_DIR: &str = ".git-iris/prompts";
+
+/// A capability's effective task prompt
+#[derive(Debug, Clone)]
+pub struct CapabilityPrompt {
+    /// Task prompt after applying overrides
+    pub task_prompt: String,
+    /// Structured output type the capability produces
+    pub output_type: String,
+    /// Override files that were applied, in order
+    pub overrides: Vec<PathBuf>,
+}
+
+/// Embedded TOML definition for a capability
+fn embedded(capability: &str) -> Option<&'static str> {
+    Some(match capability {
+        "commit" => CAPABILITY_COMMIT,
+        "pr" => CAPABILITY_PR,
+        "review" => CAPABILITY_REVIEW,
+        "changelog" => CAPABILITY_CHANGELOG,
+        "release_notes" => CAPABILITY_RELEASE_NOTES,
+        "chat" => CAPABILITY_CHAT,
+        "semantic_blame" => CAPABILITY_SEMANTIC_BLAME,
+        "release_readiness" => CAPABILITY_RELEASE_READINESS,
+        "recover" => CAPABILITY_RECOVER,
+        "onboard" => CAPABILITY_ONBOARD,
+        "adr" => CAPABILITY_ADR,
+        "plan" => CAPABILITY_PLAN,
+        "rebase" => CAPABILITY_REBASE,
+        "stash" => CAPABILITY_STASH,
+        "semantic_search" => CAPABILITY_SEMANTIC_SEARCH,
+        "recap" => CAPABILITY_RECAP,
+        "explain_repo" => CAPABILITY_EXPLAIN_REPO,
+        "file_blame" => CAPABILITY_FILE_BLAME,
+        _ => return None,
+    })
+}
+
+/// Load a capability prompt, applying overrides from `repo_root` if given
+pub fn load_capability(capability: &str, repo_root: Option<&Path>) -> Result<CapabilityPrompt> {
+    let (task_prompt, output_type) = match embedded(capability) {
+        Some(content) => {
+            let parsed: toml::Value = toml::from_str(content)?;
+            let task_prompt = parsed
+                .get("task_prompt")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow::anyhow!("No task_prompt found in capability file"))?;
+            let output_type = parsed
+                .get("output_type")
+                .and_then(|v| v.as_str())
+                .unwrap_or("PlainText");
+            (task_prompt.to_string(), output_type.to_string())
+        }
+        // Generic prompt for unknown capabilities
+        None => (
+            format!(
+                "You are helping with a {capability} task. Use the available Git tools to assist the user."
+            ),
+            "PlainText".to_string(),
+        ),
+    };
+
+    let mut prompt = CapabilityPrompt {
+        task_prompt,
+        output_type,
+        overrides: Vec::new(),
+    };
+    if let Some(root) = repo_root {
+        apply_overrides(&mut prompt, &root.join(PROMPTS_DIR), capability)?;
+    }
+    Ok(prompt)
+}
+
+/// Apply `<capability>.md` (replace) then `<capability>.append.md` (append)
+fn apply_overrides(prompt: &mut CapabilityPrompt, dir: &Path, capability: &str) -> Result<()> {
+    let replace = dir.join(format!("{capability}.md"));
+    if let Some(content) = read_override(&replace)? {
+        prompt.task_prompt = content;
+        prompt.overrides.push(replace);
+    }
+
+    let append = dir.join(format!("{capability}.append.md"));
+    if let Some(content) = read_override(&append)? {
+        prompt.task_prompt = format!("{}\n\n{}", prompt.task_prompt.trim_end(), content);
+        prompt.overrides.push(append);
+    }
+    Ok(())
+}
+
+/// Read an override file, treating missing or blank files as absent
+fn read_override(path: &Path) -> Result<Option<String>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read prompt override {}", path.display()))?;
+    let content = content.trim();
+    Ok((!content.is_empty()).then(|| content.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_embedded_prompt_without_overrides() {
+        let prompt = load_capability("commit", None).expect("commit capability");
+        assert_eq!(prompt.output_type, "GeneratedMessage");
+        assert!(prompt.overrides.is_empty());
+        assert!(prompt.task_prompt.contains("commit message"));
+    }
+
+    #[test]
+    fn test_replace_and_append_overrides() {
+        let root = TempDir::new().expect("temp dir");
+        let dir = root.path().join(PROMPTS_DIR);
+        fs::create_dir_all(&dir).expect("prompts dir");
+        fs::write(dir.join("review.md"), "Review for security only.\n").expect("write");
+        fs::write(dir.join("review.append.md"), "Cite OWASP IDs.").expect("write");
+
+        let prompt = load_capability("review", Some(root.path())).expect("review capability");
+        assert_eq!(
+            prompt.task_prompt,
+            "Review for security only.\n\nCite OWASP IDs."
+        );
+        assert_eq!(prompt.output_type, "MarkdownReview");
+        assert_eq!(prompt.overrides.len(), 2);
+    }
+
+    #[test]
+    fn test_blank_override_is_ignored() {
+        let root = TempDir::new().expect("temp dir");
+        let dir = root.path().join(PROMPTS_DIR);
+        fs::create_dir_all(&dir).expect("prompts dir");
+        fs::write(dir.join("pr.md"), "  \n").expect("write");
+
+        let prompt = load_capability("pr", Some(root.path())).expect("pr capability");
+        assert!(prompt.overrides.is_empty());
+    }
+}
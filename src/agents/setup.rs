@@ -4,15 +4,44 @@
 //! including configuration loading, client creation, and agent setup.
 
 use anyhow::Result;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use crate::agents::context::TaskContext;
+use crate::agents::context::budget::{Omission, diff_budget_for};
+use crate::agents::context::cache::ContextCache;
+use crate::agents::context_report::ContextReport;
 use crate::agents::iris::StructuredResponse;
+use crate::agents::summarize;
 use crate::agents::{AgentBackend, IrisAgent, IrisAgentBuilder};
+use crate::analytics::UsageEvent;
+use crate::changelog::is_within;
 use crate::common::CommonParams;
+use crate::companion::CompanionStorage;
 use crate::config::Config;
-use crate::git::GitRepo;
+use crate::context::{RecentCommit, StagedFile};
+use crate::dependency_updates::{
+    DEPENDENCY_PRESET, DependencyReport, detect_dependency_updates, is_bot_author, is_bot_branch,
+};
+use crate::doc_context::{DocsContext, count_tokens, truncate_to_tokens};
+use crate::error::{IrisError, classify_provider_failure};
+use crate::git::{
+    BreakingReport, GitLocation, GitRepo, detect_breaking_changes, is_breaking_message,
+};
+use crate::glossary::Glossary;
+use crate::lifecycle::LifecycleEvent;
 use crate::providers::Provider;
+use crate::risk::ChangedFile;
+use crate::types::SuggestedVersion;
+use crate::upgrade_guide::UpgradeGuide;
+use crate::usage::{Ledger, LedgerEntry, UsageTotals};
+
+/// Token budget for the amended commit's diff in the prompt
+const AMENDED_DIFF_TOKENS: usize = 6000;
+
+/// Most commits listed for a changelog scoped to one package
+const SCOPED_COMMIT_LIMIT: usize = 200;
 
 /// Service for setting up agents with proper configuration
 pub struct AgentSetupService {
@@ -44,7 +73,9 @@ impl AgentSetupService {
         // Setup git repo if needed
         if let Some(repo_url) = repository_url {
             // Handle remote repository setup (following existing pattern)
-            setup_service.git_repo = Some(GitRepo::new_from_url(Some(repo_url))?);
+            let repo = GitRepo::new_from_url(Some(repo_url))?;
+            use_for_tools(&repo);
+            setup_service.git_repo = Some(repo);
         } else {
             // Use local repository
             setup_service.git_repo = Some(GitRepo::new(&std::env::current_dir()?)?);
@@ -67,6 +98,9 @@ impl AgentSetupService {
         // Pass config and fast model to agent
         agent.set_config(self.config.clone());
         agent.set_fast_model(backend.fast_model);
+        if let Some(repo) = &self.git_repo {
+            agent.set_repo_root(repo.repo_path().clone());
+        }
 
         Ok(agent)
     }
@@ -76,7 +110,7 @@ impl AgentSetupService {
         let provider: Provider = backend
             .provider_name
             .parse()
-            .map_err(|_| anyhow::anyhow!("Unsupported provider: {}", backend.provider_name))?;
+            .map_err(|_| IrisError::UnknownProvider(backend.provider_name.clone()))?;
 
         // Check API key - from config or environment
         let has_api_key = self
@@ -84,12 +118,8 @@ impl AgentSetupService {
             .get_provider_config(provider.name())
             .is_some_and(crate::providers::ProviderConfig::has_api_key);
 
-        if !has_api_key && std::env::var(provider.api_key_env()).is_err() {
-            return Err(anyhow::anyhow!(
-                "No API key found for {}. Set {} or configure in ~/.config/git-iris/config.toml",
-                provider.name(),
-                provider.api_key_env()
-            ));
+        if provider.requires_api_key() && !has_api_key && provider.env_api_key().is_none() {
+            return Err(IrisError::MissingApiKey { provider }.into());
         }
 
         Ok(())
@@ -132,6 +162,15 @@ where
     handler(result).await
 }
 
+/// Point the agent tools, which open the repository themselves, at a
+/// remote repository's cached mirror instead of the current directory
+fn use_for_tools(repo: &GitRepo) {
+    crate::git::set_location(GitLocation {
+        git_dir: Some(repo.repo_path().clone()),
+        work_tree: None,
+    });
+}
+
 /// Simple factory function for creating agents with minimal configuration
 pub fn create_agent_with_defaults(provider: &str, model: &str) -> Result<IrisAgent> {
     IrisAgentBuilder::new()
@@ -176,6 +215,49 @@ pub struct IrisAgentService {
     provider: String,
     model: String,
     fast_model: String,
+    /// Latest context size report per capability
+    context_reports: Mutex<HashMap<String, ContextReport>>,
+    /// Tokens and estimated cost of this service's calls so far
+    session_usage: Mutex<UsageTotals>,
+    /// Provider of the latest agent call (differs from `provider` after a
+    /// fallback)
+    active_provider: Mutex<String>,
+}
+
+/// An agent call that can be repeated on a fallback provider
+enum AgentCall<'a> {
+    Task {
+        capability: &'a str,
+        prompt: &'a str,
+    },
+    Streaming {
+        capability: &'a str,
+        prompt: &'a str,
+        on_chunk: &'a mut (dyn FnMut(&str, &str) + Send),
+    },
+}
+
+impl AgentCall<'_> {
+    fn capability(&self) -> &str {
+        match self {
+            Self::Task { capability, .. } | Self::Streaming { capability, .. } => capability,
+        }
+    }
+
+    async fn run(&mut self, agent: &mut IrisAgent) -> Result<StructuredResponse> {
+        match self {
+            Self::Task { capability, prompt } => agent.execute_task(capability, prompt).await,
+            Self::Streaming {
+                capability,
+                prompt,
+                on_chunk,
+            } => {
+                agent
+                    .execute_task_streaming(capability, prompt, &mut **on_chunk)
+                    .await
+            }
+        }
+    }
 }
 
 impl IrisAgentService {
@@ -184,9 +266,12 @@ impl IrisAgentService {
         Self {
             config,
             git_repo: None,
+            active_provider: Mutex::new(provider.clone()),
             provider,
             model,
             fast_model,
+            context_reports: Mutex::new(HashMap::new()),
+            session_usage: Mutex::new(UsageTotals::default()),
         }
     }
 
@@ -215,7 +300,9 @@ impl IrisAgentService {
 
         // Setup git repo
         if let Some(repo_url) = repository_url {
-            service.git_repo = Some(Arc::new(GitRepo::new_from_url(Some(repo_url))?));
+            let repo = GitRepo::new_from_url(Some(repo_url))?;
+            use_for_tools(&repo);
+            service.git_repo = Some(Arc::new(repo));
         } else {
             service.git_repo = Some(Arc::new(GitRepo::new(&std::env::current_dir()?)?));
         }
@@ -223,6 +310,21 @@ impl IrisAgentService {
         Ok(service)
     }
 
+    /// A service for `config` that keeps this one's repository and session
+    /// usage, for when the config changes while a session is running
+    pub fn reconfigured(&self, config: Config) -> Result<Self> {
+        let backend = AgentBackend::from_config(&config)?;
+        let mut service = Self::new(
+            config,
+            backend.provider_name,
+            backend.model,
+            backend.fast_model,
+        );
+        service.git_repo.clone_from(&self.git_repo);
+        service.session_usage = Mutex::new(self.session_usage());
+        Ok(service)
+    }
+
     /// Check that the environment is properly configured
     pub fn check_environment(&self) -> Result<()> {
         self.config.check_environment()
@@ -241,18 +343,39 @@ impl IrisAgentService {
         capability: &str,
         context: TaskContext,
     ) -> Result<StructuredResponse> {
-        // Create the agent
-        let mut agent = self.create_agent()?;
-
         // Build task prompt with context information and any custom instructions from config
+        let breaking = self.detect_breaking(capability, &context);
+        let upgrade_guide = self.upgrade_guide(capability, &context, &breaking);
+        let dependencies = self.detect_dependency_updates(capability, &context);
+        let docs = self.project_docs(capability).prompt() + &self.glossary(&context);
+        let summaries = self.file_summaries(capability, &context).await;
         let task_prompt = Self::build_task_prompt(
             capability,
             &context,
             self.config.temp_instructions.as_deref(),
-        );
+        ) + &self.amended_commit(&context)
+            + &self.scoped_commits(&context)
+            + &summaries
+            + &breaking.prompt()
+            + &upgrade_guide.prompt()
+            + &dependencies.prompt()
+            + &docs;
 
         // Execute the task
-        agent.execute_task(capability, &task_prompt).await
+        let call = AgentCall::Task {
+            capability,
+            prompt: &task_prompt,
+        };
+        let (mut agent, provider, response) =
+            self.run_with_fallback(&self.config, None, call).await?;
+        let omitted = self.record_context_report(&mut agent, &provider, &docs);
+        let response = response
+            .with_breaking_changes(&breaking)
+            .with_upgrade_guide(&upgrade_guide)
+            .with_dependency_updates(&dependencies)
+            .with_omissions(&omitted);
+        let response = self.with_suggested_version(&context, &breaking, response);
+        Ok(self.with_risk_assessment(capability, &context, response))
     }
 
     /// Execute a task with a custom prompt (for backwards compatibility)
@@ -267,7 +390,7 @@ impl IrisAgentService {
 
     /// Execute an agent task with style overrides
     ///
-    /// Allows runtime override of preset and gitmoji settings without
+    /// Allows runtime override of preset, gitmoji and temperature without
     /// modifying the underlying config. Useful for UI flows where the
     /// user can change settings per-invocation.
     ///
@@ -277,6 +400,7 @@ impl IrisAgentService {
     /// * `preset` - Optional preset name override (e.g., "conventional", "cosmic")
     /// * `use_gitmoji` - Optional gitmoji setting override
     /// * `instructions` - Optional custom instructions from the user
+    /// * `temperature` - Optional sampling temperature for this capability
     pub async fn execute_task_with_style(
         &self,
         capability: &str,
@@ -284,6 +408,7 @@ impl IrisAgentService {
         preset: Option<&str>,
         use_gitmoji: Option<bool>,
         instructions: Option<&str>,
+        temperature: Option<f64>,
     ) -> Result<StructuredResponse> {
         // Clone config and apply style overrides
         let mut config = self.config.clone();
@@ -293,20 +418,503 @@ impl IrisAgentService {
         if let Some(gitmoji) = use_gitmoji {
             config.use_gitmoji = gitmoji;
         }
-
-        // Create agent with modified config
-        let mut agent = IrisAgentBuilder::new()
-            .with_provider(&self.provider)
-            .with_model(&self.model)
-            .build()?;
-        agent.set_config(config);
-        agent.set_fast_model(self.fast_model.clone());
+        if let Some(temperature) = temperature {
+            config
+                .generation
+                .entry(capability.to_string())
+                .or_default()
+                .temperature = Some(temperature);
+        }
 
         // Build task prompt with context information and optional instructions
-        let task_prompt = Self::build_task_prompt(capability, &context, instructions);
+        let breaking = self.detect_breaking(capability, &context);
+        let upgrade_guide = self.upgrade_guide(capability, &context, &breaking);
+        let dependencies = self.detect_dependency_updates(capability, &context);
+        let docs = self.project_docs(capability).prompt() + &self.glossary(&context);
+        let summaries = self.file_summaries(capability, &context).await;
+        let task_prompt = Self::build_task_prompt(capability, &context, instructions)
+            + &self.amended_commit(&context)
+            + &self.scoped_commits(&context)
+            + &summaries
+            + &breaking.prompt()
+            + &upgrade_guide.prompt()
+            + &dependencies.prompt()
+            + &docs;
 
         // Execute the task
-        agent.execute_task(capability, &task_prompt).await
+        let call = AgentCall::Task {
+            capability,
+            prompt: &task_prompt,
+        };
+        let (mut agent, provider, response) = self.run_with_fallback(&config, None, call).await?;
+        let omitted = self.record_context_report(&mut agent, &provider, &docs);
+        let response = response
+            .with_breaking_changes(&breaking)
+            .with_upgrade_guide(&upgrade_guide)
+            .with_dependency_updates(&dependencies)
+            .with_omissions(&omitted);
+        let response = self.with_suggested_version(&context, &breaking, response);
+        Ok(self.with_risk_assessment(capability, &context, response))
+    }
+
+    /// Note a finished generation in the local analytics file, when enabled
+    fn record_usage<T>(&self, capability: &str, started: Instant, result: &Result<T>) {
+        if self.config.local_analytics {
+            crate::analytics::record(&UsageEvent::generation(
+                capability,
+                started.elapsed(),
+                result.as_ref().err(),
+            ));
+        }
+    }
+
+    /// Keep the agent's context size report: in memory for Studio, and in
+    /// companion storage for `git-iris trace`
+    ///
+    /// Returns the files whose diffs didn't fit the token budget.
+    fn record_context_report(
+        &self,
+        agent: &mut IrisAgent,
+        provider: &str,
+        docs_prompt: &str,
+    ) -> Vec<Omission> {
+        let Some(mut report) = agent.take_context_report() else {
+            return Vec::new();
+        };
+        report.attribute_docs(count_tokens(docs_prompt));
+        if let Some(repo) = self.git_repo.as_deref()
+            && let Err(e) = CompanionStorage::new(repo.repo_path())
+                .and_then(|storage| storage.append_context_report(&report))
+        {
+            crate::log_debug!("Context report not saved: {}", e);
+        }
+        self.record_cost(&report, provider);
+        let omitted = report.omitted.clone();
+        if let Ok(mut reports) = self.context_reports.lock() {
+            reports.insert(report.capability.clone(), report);
+        }
+        omitted
+    }
+
+    /// Add a finished call to the repository's usage ledger and the session
+    /// totals
+    fn record_cost(&self, report: &ContextReport, provider: &str) {
+        let entry = LedgerEntry::from_report(report, provider, &self.config.pricing);
+        if let Some(repo) = self.git_repo.as_deref()
+            && let Err(e) = Ledger::for_repo(repo).and_then(|ledger| ledger.append(&entry))
+        {
+            crate::log_debug!("Usage not recorded: {}", e);
+        }
+        if let Ok(mut totals) = self.session_usage.lock() {
+            totals.add(&entry);
+        }
+    }
+
+    /// Tokens and estimated cost of the calls made through this service
+    pub fn session_usage(&self) -> UsageTotals {
+        self.session_usage
+            .lock()
+            .map(|totals| *totals)
+            .unwrap_or_default()
+    }
+
+    /// Token breakdown of the latest generation for `capability`
+    pub fn context_report(&self, capability: &str) -> Option<ContextReport> {
+        self.context_reports.lock().ok()?.get(capability).cloned()
+    }
+
+    /// Append the deterministic risk section to PR descriptions
+    fn with_risk_assessment(
+        &self,
+        capability: &str,
+        context: &TaskContext,
+        response: StructuredResponse,
+    ) -> StructuredResponse {
+        match (capability, context, response, self.git_repo.as_deref()) {
+            (
+                "pr",
+                TaskContext::Range { from, to },
+                StructuredResponse::PullRequest(mut pr),
+                Some(repo),
+            ) if !pr.content.contains("## Risk Assessment") => {
+                match repo.get_commit_range_files(from, to) {
+                    Ok(files) => {
+                        let files: Vec<ChangedFile> =
+                            files.iter().map(ChangedFile::from_staged).collect();
+                        let report = crate::risk::assess(&self.config, Some(repo), &files);
+                        pr.content =
+                            format!("{}\n\n{}", pr.content.trim_end(), report.to_markdown());
+                    }
+                    Err(e) => crate::log_debug!("Risk assessment skipped: {}", e),
+                }
+                StructuredResponse::PullRequest(pr)
+            }
+            (_, _, response, _) => response,
+        }
+    }
+
+    /// Attach the next version suggested by the range's commits to release
+    /// notes
+    ///
+    /// The current version is `from` when it's a version tag, else the
+    /// nearest tag before it. Best-effort: a range that can't be read leaves
+    /// the notes without a suggestion.
+    fn with_suggested_version(
+        &self,
+        context: &TaskContext,
+        breaking: &BreakingReport,
+        response: StructuredResponse,
+    ) -> StructuredResponse {
+        let (TaskContext::Changelog { from, to, .. } | TaskContext::Range { from, to }, Some(repo)) =
+            (context, self.git_repo.as_deref())
+        else {
+            return response;
+        };
+        let StructuredResponse::ReleaseNotes(mut notes) = response else {
+            return response;
+        };
+        match Self::range_commits(repo, context, from, to) {
+            Ok(commits) => {
+                let messages: Vec<String> = commits.into_iter().map(|c| c.message).collect();
+                let current = if from.chars().any(|c| c.is_ascii_digit()) && from.contains('.') {
+                    Some(from.clone())
+                } else {
+                    repo.get_tag_at_or_before(from).ok().flatten()
+                };
+                let messages: Vec<&str> = messages.iter().map(String::as_str).collect();
+                let removed: Vec<String> =
+                    breaking.changes.iter().map(ToString::to_string).collect();
+                notes.suggested_version = Some(SuggestedVersion::from_commits(
+                    current.as_deref(),
+                    &messages,
+                    &removed,
+                ));
+            }
+            Err(e) => crate::log_debug!("Version suggestion skipped: {}", e),
+        }
+        StructuredResponse::ReleaseNotes(notes)
+    }
+
+    /// Run breaking change detection for tasks that record it
+    ///
+    /// Commit messages look at the staged diff; changelogs and release notes
+    /// look at the range diff plus commits already marked as breaking.
+    /// Detection is best-effort and never fails the task.
+    fn detect_breaking(&self, capability: &str, context: &TaskContext) -> BreakingReport {
+        let Some(repo) = self.git_repo.as_deref() else {
+            return BreakingReport::default();
+        };
+        let report = match (capability, context) {
+            (
+                "commit",
+                TaskContext::Staged { .. } | TaskContext::Amend { .. } | TaskContext::Discover,
+            ) => repo.get_git_info(&self.config).map(|info| BreakingReport {
+                changes: detect_breaking_changes(&info.staged_files),
+                commits: Vec::new(),
+            }),
+            (
+                "changelog" | "release_notes",
+                TaskContext::Changelog { from, to, .. } | TaskContext::Range { from, to },
+            ) => repo.get_commit_range_files(from, to).and_then(|mut files| {
+                if let Some(path) = context.scope() {
+                    files.retain(|file| is_within(&file.path, path));
+                }
+                let commits = Self::range_commits(repo, context, from, to)?
+                    .into_iter()
+                    .filter(|commit| is_breaking_message(&commit.message))
+                    .map(|commit| {
+                        let subject = commit.message.lines().next().unwrap_or_default();
+                        format!("{}: {}", &commit.hash[..7.min(commit.hash.len())], subject)
+                    })
+                    .collect();
+                Ok(BreakingReport {
+                    changes: detect_breaking_changes(&files),
+                    commits,
+                })
+            }),
+            _ => return BreakingReport::default(),
+        };
+        report.unwrap_or_else(|e| {
+            crate::log_debug!("Breaking change detection skipped: {}", e);
+            BreakingReport::default()
+        })
+    }
+
+    /// Before and after snippets for the breaking changes in release notes
+    /// that asked for an upgrade guide
+    fn upgrade_guide(
+        &self,
+        capability: &str,
+        context: &TaskContext,
+        breaking: &BreakingReport,
+    ) -> UpgradeGuide {
+        if capability != "release_notes"
+            || !context.wants_upgrade_guide()
+            || breaking.changes.is_empty()
+        {
+            return UpgradeGuide::default();
+        }
+        self.changeset_files(context)
+            .map(|files| UpgradeGuide::from_diffs(&files, &breaking.changes))
+            .unwrap_or_default()
+    }
+
+    /// Summaries of every changed file when the changeset is too large to
+    /// read diff by diff, from the fast model of the primary provider
+    async fn file_summaries(&self, capability: &str, context: &TaskContext) -> String {
+        if !matches!(
+            capability,
+            "commit" | "review" | "pr" | "changelog" | "release_notes"
+        ) {
+            return String::new();
+        }
+        let (Some(files), Ok(provider)) = (
+            self.changeset_files(context),
+            self.provider.parse::<Provider>(),
+        ) else {
+            return String::new();
+        };
+        let budget = diff_budget_for(self.config.token_limit(provider));
+        if !summarize::needs_cascade(&files, budget) {
+            return String::new();
+        }
+
+        crate::iris_status_dynamic!(
+            crate::agents::status::IrisPhase::Analysis,
+            format!("Summarizing {} changed files...", files.len()),
+            1,
+            4
+        );
+        let summaries = summarize::summarize_files(
+            provider.name(),
+            &self.fast_model,
+            &self.config.request_headers(provider),
+            &files,
+            self.context_cache().as_ref(),
+        )
+        .await;
+        summarize::summaries_prompt(&summaries)
+    }
+
+    /// Cached per-file analysis for the repository, when there is one
+    fn context_cache(&self) -> Option<ContextCache> {
+        let repo = self.git_repo.as_deref()?;
+        ContextCache::for_repo(repo)
+            .map_err(|e| crate::log_debug!("Context cache unavailable: {}", e))
+            .ok()
+    }
+
+    /// The files a task's context changes, when it names a changeset
+    fn changeset_files(&self, context: &TaskContext) -> Option<Vec<StagedFile>> {
+        let repo = self.git_repo.as_deref()?;
+        let files = match context {
+            TaskContext::Staged { include_unstaged } => repo
+                .extract_files_info(*include_unstaged)
+                .map(|info| info.staged_files),
+            TaskContext::Commit { commit_id } => repo.get_commit_files(commit_id),
+            TaskContext::Range { from, to } | TaskContext::Changelog { from, to, .. } => {
+                repo.get_commit_range_files(from, to).map(|mut files| {
+                    if let Some(path) = context.scope() {
+                        files.retain(|file| is_within(&file.path, path));
+                    }
+                    files
+                })
+            }
+            TaskContext::Amend { .. } | TaskContext::Discover => return None,
+        };
+        files
+            .map_err(|e| crate::log_debug!("Changeset not read for summaries: {}", e))
+            .ok()
+    }
+
+    /// The commits in a range, only those touching the scope when the
+    /// context has one
+    fn range_commits(
+        repo: &GitRepo,
+        context: &TaskContext,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<RecentCommit>> {
+        match context.scope() {
+            Some(path) => repo.get_commits_touching_path(from, to, path),
+            None => repo.get_commits_between_with_callback(from, to, |commit| Ok(commit.clone())),
+        }
+    }
+
+    /// The commits that touch the directory a changelog is scoped to, so a
+    /// package's changelog leaves out the rest of the monorepo
+    ///
+    /// Best-effort: a range that can't be read leaves only the scope note.
+    fn scoped_commits(&self, context: &TaskContext) -> String {
+        let TaskContext::Changelog {
+            from,
+            to,
+            path: Some(path),
+            ..
+        } = context
+        else {
+            return String::new();
+        };
+        let mut prompt = format!(
+            "\n\n## Scope\nThis covers only `{path}`. Leave out commits and files outside it.\n"
+        );
+        let Some(repo) = self.git_repo.as_deref() else {
+            return prompt;
+        };
+        match repo.get_commits_touching_path(from, to, path) {
+            Ok(commits) if commits.is_empty() => {
+                prompt.push_str("\nNo commits in the range touch it.\n");
+            }
+            Ok(commits) => {
+                prompt.push_str("\nCommits in the range that touch it:\n");
+                for commit in commits.iter().take(SCOPED_COMMIT_LIMIT) {
+                    let subject = commit.message.lines().next().unwrap_or_default();
+                    let hash = &commit.hash[..7.min(commit.hash.len())];
+                    prompt.push_str(&format!("- {hash} {subject}\n"));
+                }
+                if commits.len() > SCOPED_COMMIT_LIMIT {
+                    let more = commits.len() - SCOPED_COMMIT_LIMIT;
+                    prompt.push_str(&format!("- …and {more} more\n"));
+                }
+            }
+            Err(e) => crate::log_debug!("Scoped commits skipped: {}", e),
+        }
+        prompt
+    }
+
+    /// The diff of the commit being amended, so the new message covers what
+    /// it already changed as well as what's staged now
+    ///
+    /// Best-effort: a root commit or an unreadable diff adds nothing.
+    fn amended_commit(&self, context: &TaskContext) -> String {
+        let (Some(repo), TaskContext::Amend { .. }) = (self.git_repo.as_deref(), context) else {
+            return String::new();
+        };
+        let diff = match repo.get_ref_diff_full("HEAD^", "HEAD") {
+            Ok(diff) if !diff.trim().is_empty() => diff,
+            Ok(_) => return String::new(),
+            Err(e) => {
+                crate::log_debug!("Amended commit diff skipped: {}", e);
+                return String::new();
+            }
+        };
+        let (diff, _, truncated) = truncate_to_tokens(diff.trim(), AMENDED_DIFF_TOKENS);
+        let mut prompt = format!(
+            "\n\n## Commit Being Amended\nHEAD already contains these changes; the staged \
+             changes are added to them.\n\n```diff\n{diff}\n```\n"
+        );
+        if truncated {
+            prompt.push_str("(diff truncated; call `git_diff(from=\"HEAD^1\")` for the rest)\n");
+        }
+        prompt
+    }
+
+    /// Glossary entries for terms that appear in the changes under analysis
+    ///
+    /// Best-effort: a missing or unreadable glossary, or a diff that can't be
+    /// computed, adds nothing.
+    fn glossary(&self, context: &TaskContext) -> String {
+        let Some(repo) = self.git_repo.as_deref() else {
+            return String::new();
+        };
+        let glossary = match Glossary::load(repo.repo_path()) {
+            Ok(Some(glossary)) => glossary,
+            Ok(None) => return String::new(),
+            Err(e) => {
+                crate::log_debug!("Glossary skipped: {}", e);
+                return String::new();
+            }
+        };
+        let diff = match context {
+            TaskContext::Staged { .. } | TaskContext::Amend { .. } | TaskContext::Discover => {
+                repo.get_staged_diff_full()
+            }
+            TaskContext::Commit { commit_id } => {
+                repo.get_ref_diff_full(&format!("{commit_id}^"), commit_id)
+            }
+            TaskContext::Range { from, to } | TaskContext::Changelog { from, to, .. } => {
+                repo.get_ref_diff_full(from, to)
+            }
+        };
+        match diff {
+            Ok(diff) => glossary.prompt(&diff),
+            Err(e) => {
+                crate::log_debug!("Glossary skipped: {}", e);
+                String::new()
+            }
+        }
+    }
+
+    /// Read the `[context.docs]` documentation for PRs, reviews and changelogs
+    ///
+    /// Best-effort: a repository that can't list its files gets no docs.
+    fn project_docs(&self, capability: &str) -> DocsContext {
+        let docs = &self.config.context.docs;
+        let (Some(repo), "pr" | "review" | "changelog" | "release_notes") =
+            (self.git_repo.as_deref(), capability)
+        else {
+            return DocsContext::default();
+        };
+        if docs.include.is_empty() {
+            return DocsContext::default();
+        }
+        match repo.get_all_tracked_files() {
+            Ok(files) => DocsContext::collect(repo.repo_path(), &files, docs),
+            Err(e) => {
+                crate::log_debug!("Project docs skipped: {}", e);
+                DocsContext::default()
+            }
+        }
+    }
+
+    /// Collect dependency updates for PRs and reviews of dependency bot branches
+    ///
+    /// Runs when the range's branch follows Dependabot/Renovate naming, every
+    /// commit comes from one of those bots, or the `dependencies` preset is
+    /// selected. Detection is best-effort and never fails the task.
+    fn detect_dependency_updates(
+        &self,
+        capability: &str,
+        context: &TaskContext,
+    ) -> DependencyReport {
+        let (Some(repo), "pr" | "review", TaskContext::Range { from, to }) =
+            (self.git_repo.as_deref(), capability, context)
+        else {
+            return DependencyReport::default();
+        };
+        let preset = self
+            .config
+            .temp_preset
+            .as_deref()
+            .unwrap_or(&self.config.instruction_preset);
+
+        let report = (|| -> Result<DependencyReport> {
+            if preset != DEPENDENCY_PRESET && !Self::is_bot_range(repo, from, to)? {
+                return Ok(DependencyReport::default());
+            }
+            let files = repo.get_commit_range_files(from, to)?;
+            Ok(DependencyReport {
+                updates: detect_dependency_updates(&files),
+            })
+        })();
+        report.unwrap_or_else(|e| {
+            crate::log_debug!("Dependency update detection skipped: {}", e);
+            DependencyReport::default()
+        })
+    }
+
+    fn is_bot_range(repo: &GitRepo, from: &str, to: &str) -> Result<bool> {
+        let branch = if to == "HEAD" {
+            repo.get_current_branch()?
+        } else {
+            to.to_string()
+        };
+        if is_bot_branch(&branch) {
+            return Ok(true);
+        }
+        let authors =
+            repo.get_commits_between_with_callback(from, to, |commit| Ok(commit.author.clone()))?;
+        Ok(!authors.is_empty() && authors.iter().all(|author| is_bot_author(author)))
     }
 
     /// Build a task prompt incorporating the context information and optional instructions
@@ -326,9 +934,7 @@ impl IrisAgentService {
 
         // Extract version and date info if this is a Changelog context
         let version_info = if let TaskContext::Changelog {
-            version_name,
-            date,
-            ..
+            version_name, date, ..
         } = context
         {
             let version_str = version_name
@@ -372,26 +978,193 @@ impl IrisAgentService {
 
     /// Create a configured Iris agent
     fn create_agent(&self) -> Result<IrisAgent> {
+        self.agent_for(&self.primary_backend(), self.config.clone())
+    }
+
+    /// Create an Iris agent on `backend` with `config`, following this
+    /// repository's tool policy
+    fn agent_for(&self, backend: &AgentBackend, config: Config) -> Result<IrisAgent> {
         let mut agent = IrisAgentBuilder::new()
-            .with_provider(&self.provider)
-            .with_model(&self.model)
+            .with_provider(&backend.provider_name)
+            .with_model(&backend.model)
             .build()?;
 
         // Pass config and fast model to agent
-        agent.set_config(self.config.clone());
-        agent.set_fast_model(self.fast_model.clone());
+        agent.set_config(config);
+        agent.set_fast_model(backend.fast_model.clone());
+        if let Some(repo) = &self.git_repo {
+            agent.set_repo_root(repo.repo_path().clone());
+        }
 
         Ok(agent)
     }
 
-    /// Create a configured Iris agent with content update tools (for Studio chat)
-    fn create_agent_with_content_updates(
+    fn primary_backend(&self) -> AgentBackend {
+        AgentBackend::new(
+            self.provider.clone(),
+            self.model.clone(),
+            self.fast_model.clone(),
+        )
+    }
+
+    /// The primary provider followed by the configured fallbacks, skipping
+    /// unknown names, repeats and providers without an API key
+    ///
+    /// The primary provider stays first even without a key when no fallback
+    /// can stand in, so the failure names it.
+    fn backends(&self) -> Vec<AgentBackend> {
+        let mut backends = vec![self.primary_backend()];
+        for name in &self.config.fallback_providers {
+            let Ok(provider) = name.parse::<Provider>() else {
+                tracing::warn!("Ignoring unknown fallback provider: {}", name);
+                continue;
+            };
+            if backends.iter().any(|b| b.provider_name == provider.name())
+                || self.config.lacks_api_key(provider)
+            {
+                continue;
+            }
+            let provider_config = self
+                .config
+                .get_provider_config(provider.name())
+                .cloned()
+                .unwrap_or_default();
+            backends.push(AgentBackend::new(
+                provider.name().to_string(),
+                provider_config.effective_model(provider).to_string(),
+                provider_config.effective_fast_model(provider).to_string(),
+            ));
+        }
+        let primary_lacks_key = self
+            .provider
+            .parse::<Provider>()
+            .is_ok_and(|provider| self.config.lacks_api_key(provider));
+        if primary_lacks_key && backends.len() > 1 {
+            backends.remove(0);
+        }
+        backends
+    }
+
+    /// Run an agent call on the primary provider, moving down the fallback
+    /// chain while calls fail
+    ///
+    /// Returns the agent that answered, for its context report, and the
+    /// provider it ran on. When every provider fails, the last failure is
+    /// returned.
+    async fn run_with_fallback(
         &self,
-        sender: crate::agents::tools::ContentUpdateSender,
-    ) -> Result<IrisAgent> {
-        let mut agent = self.create_agent()?;
-        agent.set_content_update_sender(sender);
-        Ok(agent)
+        config: &Config,
+        content_updates: Option<&crate::agents::tools::ContentUpdateSender>,
+        mut call: AgentCall<'_>,
+    ) -> Result<(IrisAgent, String, StructuredResponse)> {
+        let capability = call.capability().to_string();
+        let started = Instant::now();
+        self.run_lifecycle_hook(
+            LifecycleEvent::BeforeGenerate,
+            &capability,
+            &self.provider,
+            "",
+        )
+        .await?;
+        let backends = self.backends();
+        let mut remaining = backends.iter().peekable();
+        let result = loop {
+            let Some(backend) = remaining.next() else {
+                break Err(anyhow::anyhow!("No provider configured"));
+            };
+            if let Ok(mut active) = self.active_provider.lock() {
+                active.clone_from(&backend.provider_name);
+            }
+            let outcome = match self.agent_for(backend, config.clone()) {
+                Ok(mut agent) => {
+                    if let Some(sender) = content_updates {
+                        agent.set_content_update_sender(sender.clone());
+                    }
+                    call.run(&mut agent).await.map(|response| (agent, response))
+                }
+                Err(e) => Err(e),
+            };
+            match outcome {
+                Ok((agent, response)) => {
+                    break Ok((agent, backend.provider_name.clone(), response));
+                }
+                Err(e) => {
+                    let e = classify_provider_failure(&backend.provider_name, &backend.model, e);
+                    let Some(next) = remaining.peek() else {
+                        break Err(e);
+                    };
+                    tracing::warn!(
+                        "{} failed ({}), falling back to {}",
+                        backend.provider_name,
+                        e,
+                        next.provider_name
+                    );
+                    crate::iris_status_dynamic!(
+                        crate::agents::status::IrisPhase::Initializing,
+                        format!(
+                            "{} failed, trying {}...",
+                            backend.provider_name, next.provider_name
+                        ),
+                        1,
+                        4
+                    );
+                }
+            }
+        };
+        self.record_usage(&capability, started, &result);
+        if let Ok((_, provider, response)) = &result
+            && let Err(e) = self
+                .run_lifecycle_hook(
+                    LifecycleEvent::AfterGenerate,
+                    &capability,
+                    provider,
+                    &response.to_string(),
+                )
+                .await
+        {
+            tracing::warn!("{:#}", e);
+        }
+        result
+    }
+
+    /// Run a `[hooks]` command for a generation off the async runtime
+    async fn run_lifecycle_hook(
+        &self,
+        event: LifecycleEvent,
+        capability: &str,
+        provider: &str,
+        output: &str,
+    ) -> Result<()> {
+        if self.config.hooks.command(event).is_none() {
+            return Ok(());
+        }
+        let hooks = self.config.hooks.clone();
+        let dir = self.git_repo.as_ref().map(|repo| repo.repo_path().clone());
+        let (capability, provider, output) = (
+            capability.to_string(),
+            provider.to_string(),
+            output.to_string(),
+        );
+        tokio::task::spawn_blocking(move || {
+            hooks.run(
+                event,
+                &[
+                    ("IRIS_CAPABILITY", capability.as_str()),
+                    ("IRIS_PROVIDER", provider.as_str()),
+                ],
+                &output,
+                dir.as_deref(),
+            )
+        })
+        .await?
+    }
+
+    /// Provider of the latest agent call: the primary one unless a fallback
+    /// had to answer
+    pub fn active_provider(&self) -> String {
+        self.active_provider
+            .lock()
+            .map_or_else(|_| self.provider.clone(), |active| active.clone())
     }
 
     /// Execute a chat task with content update capabilities
@@ -402,8 +1175,15 @@ impl IrisAgentService {
         task_prompt: &str,
         content_update_sender: crate::agents::tools::ContentUpdateSender,
     ) -> Result<StructuredResponse> {
-        let mut agent = self.create_agent_with_content_updates(content_update_sender)?;
-        agent.execute_task("chat", task_prompt).await
+        let call = AgentCall::Task {
+            capability: "chat",
+            prompt: task_prompt,
+        };
+        let (mut agent, provider, response) = self
+            .run_with_fallback(&self.config, Some(&content_update_sender), call)
+            .await?;
+        self.record_context_report(&mut agent, &provider, "");
+        Ok(response)
     }
 
     /// Execute a chat task with streaming and content update capabilities
@@ -413,15 +1193,45 @@ impl IrisAgentService {
         &self,
         task_prompt: &str,
         content_update_sender: crate::agents::tools::ContentUpdateSender,
-        on_chunk: F,
+        mut on_chunk: F,
+    ) -> Result<StructuredResponse>
+    where
+        F: FnMut(&str, &str) + Send,
+    {
+        let call = AgentCall::Streaming {
+            capability: "chat",
+            prompt: task_prompt,
+            on_chunk: &mut on_chunk,
+        };
+        let (mut agent, provider, response) = self
+            .run_with_fallback(&self.config, Some(&content_update_sender), call)
+            .await?;
+        self.record_context_report(&mut agent, &provider, "");
+        Ok(response)
+    }
+
+    /// Execute a task with a pre-built prompt, streaming the answer
+    ///
+    /// The streaming counterpart of [`Self::execute_task_with_prompt`] for
+    /// CLI commands that print the answer as it arrives.
+    pub async fn execute_prompt_streaming<F>(
+        &self,
+        capability: &str,
+        task_prompt: &str,
+        mut on_chunk: F,
     ) -> Result<StructuredResponse>
     where
         F: FnMut(&str, &str) + Send,
     {
-        let mut agent = self.create_agent_with_content_updates(content_update_sender)?;
-        agent
-            .execute_task_streaming("chat", task_prompt, on_chunk)
-            .await
+        let call = AgentCall::Streaming {
+            capability,
+            prompt: task_prompt,
+            on_chunk: &mut on_chunk,
+        };
+        let (mut agent, provider, response) =
+            self.run_with_fallback(&self.config, None, call).await?;
+        self.record_context_report(&mut agent, &provider, "");
+        Ok(response)
     }
 
     /// Execute an agent task with streaming
@@ -440,20 +1250,42 @@ impl IrisAgentService {
         &self,
         capability: &str,
         context: TaskContext,
-        on_chunk: F,
+        mut on_chunk: F,
     ) -> Result<StructuredResponse>
     where
         F: FnMut(&str, &str) + Send,
     {
-        let mut agent = self.create_agent()?;
+        let breaking = self.detect_breaking(capability, &context);
+        let upgrade_guide = self.upgrade_guide(capability, &context, &breaking);
+        let dependencies = self.detect_dependency_updates(capability, &context);
+        let docs = self.project_docs(capability).prompt() + &self.glossary(&context);
+        let summaries = self.file_summaries(capability, &context).await;
         let task_prompt = Self::build_task_prompt(
             capability,
             &context,
             self.config.temp_instructions.as_deref(),
-        );
-        agent
-            .execute_task_streaming(capability, &task_prompt, on_chunk)
-            .await
+        ) + &self.amended_commit(&context)
+            + &self.scoped_commits(&context)
+            + &summaries
+            + &breaking.prompt()
+            + &upgrade_guide.prompt()
+            + &dependencies.prompt()
+            + &docs;
+        let call = AgentCall::Streaming {
+            capability,
+            prompt: &task_prompt,
+            on_chunk: &mut on_chunk,
+        };
+        let (mut agent, provider, response) =
+            self.run_with_fallback(&self.config, None, call).await?;
+        let omitted = self.record_context_report(&mut agent, &provider, &docs);
+        let response = response
+            .with_breaking_changes(&breaking)
+            .with_upgrade_guide(&upgrade_guide)
+            .with_dependency_updates(&dependencies)
+            .with_omissions(&omitted);
+        let response = self.with_suggested_version(&context, &breaking, response);
+        Ok(self.with_risk_assessment(capability, &context, response))
     }
 
     /// Get the configuration
@@ -3,9 +3,11 @@
 //! Generates witty, contextual waiting messages while users wait for
 //! agent operations to complete. Uses fire-and-forget async with hard
 //! timeout to ensure we never block on status messages.
+//!
+//! The tone is set by a [`StatusPersonality`] pack. The offline and silent
+//! packs never call the fast model.
 
 use anyhow::Result;
-use rig::client::builder::DynClientBuilder;
 use rig::completion::Prompt;
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
@@ -95,6 +97,96 @@ impl Default for StatusMessage {
     }
 }
 
+/// Tone of the waiting and completion messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusPersonality {
+    /// Witty, contextual messages from the fast model
+    #[default]
+    Playful,
+    /// Plain, informative messages from the fast model
+    Professional,
+    /// Built-in witty messages, no model calls
+    Offline,
+    /// Fixed task labels only, no model calls
+    Silent,
+}
+
+impl StatusPersonality {
+    /// All packs, in settings order
+    pub const ALL: &'static [Self] = &[
+        Self::Playful,
+        Self::Professional,
+        Self::Offline,
+        Self::Silent,
+    ];
+
+    /// Name used in config files and settings
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Playful => "playful",
+            Self::Professional => "professional",
+            Self::Offline => "offline",
+            Self::Silent => "silent",
+        }
+    }
+
+    /// Whether messages come from the fast model
+    pub fn uses_model(self) -> bool {
+        matches!(self, Self::Playful | Self::Professional)
+    }
+
+    /// Whether this is the default pack (used to keep config files minimal)
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    pub fn is_default(&self) -> bool {
+        *self == Self::Playful
+    }
+
+    /// System prompt for the fast model
+    fn preamble(self) -> &'static str {
+        match self {
+            Self::Professional => {
+                "You write short progress messages for a Git assistant named Iris. \
+                 Plain, precise and neutral: say what is being worked on, no jokes or wordplay. \
+                 Capitalize first letter, end with ellipsis. Under 35 chars. No emojis. \
+                 Just the message text, nothing else."
+            }
+            _ => {
+                "You write fun waiting messages for a Git AI named Iris. \
+                 Concise, yet fun and encouraging, add vibes, be clever, not cheesy. \
+                 Capitalize first letter, end with ellipsis. Under 35 chars. No emojis. \
+                 Just the message text, nothing else."
+            }
+        }
+    }
+
+    /// Closing instructions for the waiting message prompt
+    fn task_instructions(self) -> &'static str {
+        match self {
+            Self::Professional => {
+                "\nYour task is to use the limited context above to write a brief progress \
+                 message shown to the user while the main task executes. State what is being \
+                 done, mentioning the specific area when the context allows. No humor. \
+                 Capitalize the first letter and end with ellipsis. Under 35 chars. No emojis.\n\n\
+                 Just the message:"
+            }
+            _ => {
+                "\nYour task is to use the limited context above to generate a fun waiting message \
+                 shown to the user while the main task executes. Concise, yet fun and encouraging. \
+                 Add fun vibes depending on the context. Be clever. \
+                 Capitalize the first letter and end with ellipsis. Under 35 chars. No emojis.\n\n\
+                 Just the message:"
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for StatusPersonality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 /// Capitalize first letter of a string (sentence case)
 fn capitalize_first(s: &str) -> String {
     let mut chars = s.chars();
@@ -110,6 +202,10 @@ pub struct StatusMessageGenerator {
     fast_model: String,
     /// Hard timeout for status message generation (ms)
     timeout_ms: u64,
+    /// Tone of the generated messages
+    personality: StatusPersonality,
+    /// HTTP headers sent with each request, like the `OpenAI` organization
+    headers: Vec<(String, String)>,
 }
 
 impl StatusMessageGenerator {
@@ -124,9 +220,23 @@ impl StatusMessageGenerator {
             provider: provider.into(),
             fast_model: fast_model.into(),
             timeout_ms: 1500, // 1.5 seconds - fast model should respond quickly
+            personality: StatusPersonality::default(),
+            headers: Vec::new(),
         }
     }
 
+    /// Set the personality pack
+    pub fn with_personality(mut self, personality: StatusPersonality) -> Self {
+        self.personality = personality;
+        self
+    }
+
+    /// Send `headers` with each request
+    pub fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.headers = headers;
+        self
+    }
+
     /// Set custom timeout in milliseconds
     pub fn with_timeout_ms(mut self, ms: u64) -> Self {
         self.timeout_ms = ms;
@@ -137,6 +247,11 @@ impl StatusMessageGenerator {
     ///
     /// Returns default message if generation fails or times out.
     pub async fn generate(&self, context: &StatusContext) -> StatusMessage {
+        match self.personality {
+            StatusPersonality::Offline => return Self::offline_message(context),
+            StatusPersonality::Silent => return Self::default_message(context),
+            StatusPersonality::Playful | StatusPersonality::Professional => {}
+        }
         match timeout(
             Duration::from_millis(self.timeout_ms),
             self.generate_internal(context),
@@ -158,15 +273,28 @@ impl StatusMessageGenerator {
         context: StatusContext,
         tx: mpsc::UnboundedSender<StatusMessage>,
     ) {
+        match self.personality {
+            StatusPersonality::Offline => {
+                let _ = tx.send(Self::offline_message(&context));
+                return;
+            }
+            StatusPersonality::Silent => return,
+            StatusPersonality::Playful | StatusPersonality::Professional => {}
+        }
+
         let provider = self.provider.clone();
         let fast_model = self.fast_model.clone();
         let timeout_ms = self.timeout_ms;
+        let personality = self.personality;
+        let headers = self.headers.clone();
 
         tokio::spawn(async move {
             let generator = StatusMessageGenerator {
                 provider,
                 fast_model,
                 timeout_ms,
+                personality,
+                headers,
             };
 
             if let Ok(Ok(msg)) = timeout(
@@ -190,19 +318,13 @@ impl StatusMessageGenerator {
 
     /// Build the agent synchronously (this is important for Send safety)
     fn build_status_agent(
-        provider: &str,
-        fast_model: &str,
+        &self,
     ) -> Result<rig::agent::Agent<impl rig::completion::CompletionModel + 'static>> {
-        let client_builder = DynClientBuilder::new();
+        let client_builder = crate::agents::iris::client_builder(&self.headers);
         let agent = client_builder
-            .agent(provider, fast_model)
+            .agent(&self.provider, &self.fast_model)
             .map_err(|e| anyhow::anyhow!("Failed to create status agent: {}", e))?
-            .preamble(
-                "You write fun waiting messages for a Git AI named Iris. \
-                 Concise, yet fun and encouraging, add vibes, be clever, not cheesy. \
-                 Capitalize first letter, end with ellipsis. Under 35 chars. No emojis. \
-                 Just the message text, nothing else.",
-            )
+            .preamble(self.personality.preamble())
             .max_tokens(50)
             .build();
         Ok(agent)
@@ -210,7 +332,7 @@ impl StatusMessageGenerator {
 
     /// Internal generation logic
     async fn generate_internal(&self, context: &StatusContext) -> Result<StatusMessage> {
-        let prompt = Self::build_prompt(context);
+        let prompt = Self::build_prompt(context, self.personality);
         tracing::info!(
             "Building status agent with provider={}, model={}",
             self.provider,
@@ -219,7 +341,7 @@ impl StatusMessageGenerator {
 
         // Build agent synchronously (DynClientBuilder is not Send)
         // The returned agent IS Send, so we can await after this
-        let agent = match Self::build_status_agent(&self.provider, &self.fast_model) {
+        let agent = match self.build_status_agent() {
             Ok(a) => a,
             Err(e) => {
                 tracing::warn!("Failed to build status agent: {}", e);
@@ -256,7 +378,7 @@ impl StatusMessageGenerator {
     }
 
     /// Build the prompt for status message generation
-    fn build_prompt(context: &StatusContext) -> String {
+    fn build_prompt(context: &StatusContext, personality: StatusPersonality) -> String {
         let mut prompt = String::from("Context:\n");
 
         prompt.push_str(&format!("Task: {}\n", context.task_type));
@@ -273,16 +395,18 @@ impl StatusMessageGenerator {
             prompt.push_str(&format!("File count: {}\n", count));
         }
 
-        prompt.push_str(
-            "\nYour task is to use the limited context above to generate a fun waiting message \
-             shown to the user while the main task executes. Concise, yet fun and encouraging. \
-             Add fun vibes depending on the context. Be clever. \
-             Capitalize the first letter and end with ellipsis. Under 35 chars. No emojis.\n\n\
-             Just the message:",
-        );
+        prompt.push_str(personality.task_instructions());
         prompt
     }
 
+    /// Pick a built-in waiting message for the task (offline pack)
+    fn offline_message(context: &StatusContext) -> StatusMessage {
+        StatusMessage {
+            message: crate::messages::get_capability_message(&context.task_type).text,
+            time_hint: None,
+        }
+    }
+
     /// Get a default message based on context (used as fallback)
     fn default_message(context: &StatusContext) -> StatusMessage {
         let message = match context.task_type.as_str() {
@@ -304,6 +428,9 @@ impl StatusMessageGenerator {
 
     /// Generate a completion message when a task finishes
     pub async fn generate_completion(&self, context: &StatusContext) -> StatusMessage {
+        if !self.personality.uses_model() {
+            return Self::default_completion(context);
+        }
         match timeout(
             Duration::from_millis(self.timeout_ms),
             self.generate_completion_internal(context),
@@ -318,7 +445,7 @@ impl StatusMessageGenerator {
     async fn generate_completion_internal(&self, context: &StatusContext) -> Result<StatusMessage> {
         let prompt = Self::build_completion_prompt(context);
 
-        let agent = Self::build_status_agent(&self.provider, &self.fast_model)?;
+        let agent = self.build_status_agent()?;
         let response = agent.prompt(&prompt).await?;
         let message = capitalize_first(response.trim());
 
@@ -480,17 +607,58 @@ mod tests {
             .with_branch("feature/awesome")
             .with_file_count(3);
 
-        let prompt = StatusMessageGenerator::build_prompt(&ctx);
+        let prompt = StatusMessageGenerator::build_prompt(&ctx, StatusPersonality::Playful);
         assert!(prompt.contains("commit"));
         assert!(prompt.contains("analyzing staged changes"));
         assert!(prompt.contains("feature/awesome"));
-        assert!(prompt.contains("3"));
+        assert!(prompt.contains('3'));
+        assert!(prompt.contains("fun waiting message"));
+
+        let prompt = StatusMessageGenerator::build_prompt(&ctx, StatusPersonality::Professional);
+        assert!(prompt.contains("No humor"));
+    }
+
+    #[test]
+    fn test_offline_packs_never_call_model() {
+        // No provider configured: any model call would fail and fall back
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let ctx = StatusContext::new("review", "analyzing code changes");
+
+        let silent =
+            StatusMessageGenerator::new("", "").with_personality(StatusPersonality::Silent);
+        let msg = rt.block_on(silent.generate(&ctx));
+        assert_eq!(msg.message, "Analyzing code changes...");
+
+        let offline =
+            StatusMessageGenerator::new("", "").with_personality(StatusPersonality::Offline);
+        let msg = rt.block_on(offline.generate(&ctx));
+        assert_ne!(msg.message, StatusMessage::default().message);
+        assert_ne!(msg.message, "Analyzing code changes...");
+
+        let (tx, mut rx) = StatusMessageGenerator::create_channel();
+        rt.block_on(async { silent.spawn_generation(ctx.clone(), tx) });
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_personality_config_names() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            p: StatusPersonality,
+        }
+
+        for personality in StatusPersonality::ALL {
+            let toml = format!("p = \"{personality}\"");
+            let parsed: Wrapper = toml::from_str(&toml).expect("parse");
+            assert_eq!(parsed.p, *personality);
+        }
+        assert!(StatusPersonality::default().uses_model());
     }
 
     /// Debug test to evaluate status message quality
-    /// Run with: cargo test debug_status_messages -- --ignored --nocapture
+    /// Run with: `cargo test debug_status_messages -- --ignored --nocapture`
     #[test]
-    #[ignore]
+    #[ignore = "requires API key and prints sample output"]
     fn debug_status_messages() {
         use tokio::runtime::Runtime;
 
@@ -512,7 +680,7 @@ mod tests {
             let generator = StatusMessageGenerator::new(&provider, &model);
 
             // Test scenarios
-            let scenarios = vec![
+            let scenarios = [
                 StatusContext::new("commit", "crafting commit message")
                     .with_branch("main")
                     .with_files(vec![
@@ -558,7 +726,7 @@ mod tests {
 
                 // Generate 5 messages for each scenario
                 for j in 1..=5 {
-                    let msg = generator.generate(&ctx).await;
+                    let msg = generator.generate(ctx).await;
                     println!("  {}: {}", j, msg.message);
                 }
                 println!();
@@ -0,0 +1,205 @@
+//! Summarization cascade for huge changesets
+//!
+//! A changeset with hundreds of files doesn't fit the main agent's context,
+//! even as a token-budgeted `git_diff`. From [`CASCADE_MIN_FILES`] files up,
+//! or when the diffs outgrow the diff budget, the service first has the
+//! fast model summarize every file's diff in parallel (the map step) and
+//! gives the main agent those summaries in its task prompt (the reduce
+//! step). Iris keeps its tools, so it can still read the diffs of the few
+//! files whose details matter.
+
+use futures::stream::{self, StreamExt};
+use rig::completion::Prompt;
+use std::time::Duration;
+
+use crate::agents::context::cache::ContextCache;
+use crate::context::{ChangeType, StagedFile};
+use crate::doc_context::{count_tokens, truncate_to_tokens};
+
+/// Changesets with at least this many files are summarized first
+pub const CASCADE_MIN_FILES: usize = 100;
+
+/// Files summarized at the same time
+const CONCURRENCY: usize = 8;
+
+/// How long one file's summary may take
+const SUMMARY_TIMEOUT: Duration = Duration::from_secs(45);
+
+/// Diff tokens sent per file; longer diffs are cut at a line boundary
+const MAX_DIFF_TOKENS: usize = 4000;
+
+const PREAMBLE: &str = "You summarize one file's diff for a reviewer who can't read it. \
+    Answer in one or two plain sentences: what changed and why it matters. \
+    Name added, removed or renamed public items. No preamble, no markdown.";
+
+/// What changed in one file, in a sentence or two
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSummary {
+    pub path: String,
+    pub change_type: ChangeType,
+    pub summary: String,
+}
+
+/// Whether a changeset is too large to read diff by diff
+pub fn needs_cascade(files: &[StagedFile], diff_budget: usize) -> bool {
+    files.len() >= CASCADE_MIN_FILES
+        || files
+            .iter()
+            .map(|file| count_tokens(&file.diff))
+            .sum::<usize>()
+            > diff_budget
+}
+
+/// Summarize every file's diff with `model`, in file order
+///
+/// Files without a readable diff (excluded, binary, or held back by the
+/// size limits) and files whose summary fails fall back to their line
+/// counts, so every file is accounted for. Summaries `model` wrote for the
+/// same diffs before are taken from `cache`.
+pub async fn summarize_files(
+    provider: &str,
+    model: &str,
+    headers: &[(String, String)],
+    files: &[StagedFile],
+    cache: Option<&ContextCache>,
+) -> Vec<FileSummary> {
+    // Owned futures: borrowing across `buffered` trips up the Send checks
+    // of the tasks Studio spawns
+    let tasks: Vec<_> = files
+        .iter()
+        .map(|file| {
+            let (provider, model, headers) =
+                (provider.to_string(), model.to_string(), headers.to_vec());
+            let (file, cache) = (file.clone(), cache.cloned());
+            async move { summarize_file(&provider, &model, &headers, &file, cache.as_ref()).await }
+        })
+        .collect();
+    stream::iter(tasks).buffered(CONCURRENCY).collect().await
+}
+
+async fn summarize_file(
+    provider: &str,
+    model: &str,
+    headers: &[(String, String)],
+    file: &StagedFile,
+    cache: Option<&ContextCache>,
+) -> FileSummary {
+    let fallback = || FileSummary {
+        path: file.path.clone(),
+        change_type: file.change_type.clone(),
+        summary: line_counts(&file.diff),
+    };
+    if file.diff.trim().is_empty() || file.diff.starts_with('[') {
+        return fallback();
+    }
+    if let Some(summary) = cache.and_then(|cache| cache.summary(file, model)) {
+        return FileSummary {
+            summary,
+            ..fallback()
+        };
+    }
+
+    let (diff, _, truncated) = truncate_to_tokens(&file.diff, MAX_DIFF_TOKENS);
+    let prompt = format!(
+        "{} `{}`{}:\n\n```diff\n{diff}\n```",
+        file.change_type,
+        file.path,
+        if truncated { " (diff cut short)" } else { "" }
+    );
+    // Built synchronously: the client builder isn't Send, the agent is
+    let agent = match crate::agents::iris::client_builder(headers).agent(provider, model) {
+        Ok(builder) => builder.preamble(PREAMBLE).max_tokens(200).build(),
+        Err(e) => {
+            crate::log_debug!("Summary agent for {} unavailable: {}", file.path, e);
+            return fallback();
+        }
+    };
+    match tokio::time::timeout(SUMMARY_TIMEOUT, agent.prompt(&prompt)).await {
+        Ok(Ok(summary)) if !summary.trim().is_empty() => {
+            let summary = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+            if let Some(cache) = cache {
+                cache.store_summary(file, model, &summary);
+            }
+            FileSummary {
+                summary,
+                ..fallback()
+            }
+        }
+        Ok(Ok(_)) => fallback(),
+        Ok(Err(e)) => {
+            crate::log_debug!("Summary of {} failed: {}", file.path, e);
+            fallback()
+        }
+        Err(_) => {
+            crate::log_debug!("Summary of {} timed out", file.path);
+            fallback()
+        }
+    }
+}
+
+/// `+12 -3 lines`, or the marker a diff was replaced with
+fn line_counts(diff: &str) -> String {
+    if diff.starts_with('[') {
+        return diff.lines().next().unwrap_or_default().to_string();
+    }
+    let added = diff.lines().filter(|line| line.starts_with('+')).count();
+    let removed = diff.lines().filter(|line| line.starts_with('-')).count();
+    format!("+{added} -{removed} lines")
+}
+
+/// Task prompt section handing the summaries to the main agent
+pub fn summaries_prompt(summaries: &[FileSummary]) -> String {
+    if summaries.is_empty() {
+        return String::new();
+    }
+    let mut prompt = format!(
+        "\n\n## File Summaries\nThis changeset has {} files, too many to read diff by diff, so each file's diff was summarized below. Work from these summaries, and call git_diff with files=['path'] only for the few files whose details you need.\n\n",
+        summaries.len()
+    );
+    for summary in summaries {
+        prompt.push_str(&format!(
+            "- `{}` ({}): {}\n",
+            summary.path, summary.change_type, summary.summary
+        ));
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, diff: &str) -> StagedFile {
+        StagedFile {
+            path: path.to_string(),
+            change_type: ChangeType::Modified,
+            diff: diff.to_string(),
+            content: None,
+            content_excluded: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_diffs_skip_the_model() {
+        let files = [
+            file("logo.png", "[Binary file changed]"),
+            file("Cargo.lock", "[Content excluded]"),
+        ];
+        let summaries = summarize_files("openai", "unused", &[], &files, None).await;
+        assert_eq!(summaries[0].summary, "[Binary file changed]");
+
+        let prompt = summaries_prompt(&summaries);
+        assert!(prompt.contains("This changeset has 2 files"));
+        assert!(prompt.ends_with("- `Cargo.lock` (Modified): [Content excluded]\n"));
+        assert_eq!(line_counts("+a\n+b\n-c\n context\n"), "+2 -1 lines");
+    }
+
+    #[test]
+    fn test_cascade_threshold() {
+        let small = vec![file("src/lib.rs", "+ one line\n"); 3];
+        assert!(!needs_cascade(&small, 10_000));
+        assert!(needs_cascade(&small, 2));
+        let many = vec![file("src/lib.rs", "+ one line\n"); CASCADE_MIN_FILES];
+        assert!(needs_cascade(&many, usize::MAX));
+    }
+}
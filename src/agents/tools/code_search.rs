@@ -9,34 +9,37 @@ use rig::tool::Tool;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::process::Command;
+use std::sync::Arc;
 
-use super::common::parameters_schema;
+use super::common::{get_current_repo, parameters_schema};
+use crate::agents::policy::ToolPolicy;
 use crate::define_tool_error;
 
 define_tool_error!(CodeSearchError);
 
 /// Code search tool for finding related files and functions
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CodeSearch;
-
-impl Default for CodeSearch {
-    fn default() -> Self {
-        Self
-    }
+pub struct CodeSearch {
+    /// Matches in paths the policy denies are left out
+    #[serde(skip)]
+    policy: Arc<ToolPolicy>,
 }
 
 impl CodeSearch {
-    pub fn new() -> Self {
-        Self
+    pub fn new(policy: &Arc<ToolPolicy>) -> Self {
+        Self {
+            policy: Arc::clone(policy),
+        }
     }
 
-    /// Execute a ripgrep search for patterns
+    /// Execute a ripgrep search for patterns, skipping files `policy` denies
     fn execute_ripgrep_search(
         query: &str,
         repo_path: &Path,
         file_pattern: Option<&str>,
         search_type: &str,
         max_results: usize,
+        policy: &ToolPolicy,
     ) -> Result<Vec<SearchResult>> {
         let mut cmd = Command::new("rg");
 
@@ -69,52 +72,72 @@ impl CodeSearch {
             cmd.args(["-g", pattern]);
         }
 
-        // Limit results and add context
-        cmd.args(["-n", "--color", "never", "-A", "3", "-B", "1"]);
+        // Limit results and add context; a NUL after each file name tells
+        // which file every line, context included, comes from
+        cmd.args(["-n", "--null", "--color", "never", "-A", "3", "-B", "1"]);
         cmd.current_dir(repo_path);
 
         let output = cmd.output()?;
         let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(Self::parse_ripgrep_output(
+            &stdout,
+            search_type,
+            max_results,
+            policy,
+        ))
+    }
 
+    /// Group `rg --null` output into one result per file, skipping files
+    /// `policy` denies
+    fn parse_ripgrep_output(
+        stdout: &str,
+        search_type: &str,
+        max_results: usize,
+        policy: &ToolPolicy,
+    ) -> Vec<SearchResult> {
         let mut results = Vec::new();
         let mut current_file = String::new();
         let mut line_number = 0;
         let mut content_lines = Vec::new();
 
-        for line in stdout.lines().take(max_results * 4) {
-            // rough estimate with context
-            if line.contains(':') && !line.starts_with('-') {
-                // Parse file:line:content format
-                let parts: Vec<&str> = line.splitn(3, ':').collect();
-                if parts.len() >= 3 {
-                    let file_path = parts[0].to_string();
-                    if let Ok(line_num) = parts[1].parse::<usize>() {
-                        let content = parts[2].to_string();
-
-                        if file_path != current_file && !current_file.is_empty() {
-                            // Finalize previous result
-                            results.push(SearchResult {
-                                file_path: current_file.clone(),
-                                line_number,
-                                content: content_lines.join("\n"),
-                                match_type: search_type.to_string(),
-                                context_lines: content_lines.len(),
-                            });
-                            content_lines.clear();
-                        }
-
-                        current_file = file_path;
-                        line_number = line_num;
-                        content_lines.push(content);
-
-                        if results.len() >= max_results {
-                            break;
-                        }
-                    }
+        let lines = stdout
+            .lines()
+            .filter_map(|line| line.split_once('\0'))
+            .filter(|(file_path, _)| policy.allows_path(file_path));
+        // rough estimate with context
+        for (file_path, rest) in lines.take(max_results * 4) {
+            // Matches are "N:content", context lines "N-content"
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let (number, rest) = rest.split_at(digits);
+            if let Some(content) = rest.strip_prefix(':')
+                && let Ok(line_num) = number.parse::<usize>()
+            {
+                if file_path != current_file && !current_file.is_empty() {
+                    // Finalize previous result
+                    results.push(SearchResult {
+                        file_path: current_file.clone(),
+                        line_number,
+                        content: content_lines.join("\n"),
+                        match_type: search_type.to_string(),
+                        context_lines: content_lines.len(),
+                    });
+                    content_lines.clear();
                 }
-            } else if !line.starts_with('-') && !current_file.is_empty() {
+
+                current_file = file_path.to_string();
+                line_number = line_num;
+                content_lines.push(content.to_string());
+
+                if results.len() >= max_results {
+                    break;
+                }
+            } else if file_path == current_file
+                && let Some(content) = rest.strip_prefix('-')
+            {
                 // Context line
-                content_lines.push(line.to_string());
+                content_lines.push(content.to_string());
             }
         }
 
@@ -129,7 +152,7 @@ impl CodeSearch {
             });
         }
 
-        Ok(results)
+        results
     }
 }
 
@@ -205,15 +228,21 @@ impl Tool for CodeSearch {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
-        let current_dir = std::env::current_dir().map_err(CodeSearchError::from)?;
+        let repo = get_current_repo().map_err(CodeSearchError::from)?;
+        if repo.is_bare() {
+            return Err(CodeSearchError(
+                "The repository is bare, so there are no files to search; use file_read to read files at HEAD".into(),
+            ));
+        }
         let max_results = args.max_results.min(100); // Cap at 100
 
         let results = Self::execute_ripgrep_search(
             &args.query,
-            &current_dir,
+            repo.repo_path(),
             args.file_pattern.as_deref(),
             args.search_type.as_str(),
             max_results,
+            &self.policy,
         )
         .map_err(CodeSearchError::from)?;
 
@@ -228,3 +257,33 @@ impl Tool for CodeSearch {
         serde_json::to_string_pretty(&result).map_err(|e| CodeSearchError(e.to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ripgrep_output_skips_denied_files() {
+        let stdout = "src/a.rs\x001-use std::env;\n\
+                      src/a.rs\x002:let key = env::var(\"KEY\");\n\
+                      src/a.rs\x003-}\n\
+                      --\n\
+                      .env\x001-# local\n\
+                      .env\x002:KEY=hunter2\n\
+                      src/b.rs\x005:// KEY: rotated\n";
+        let policy = ToolPolicy::parse("[paths]\ndeny = [\".env\"]\n").expect("policy");
+
+        let results = CodeSearch::parse_ripgrep_output(stdout, "text", 20, &policy);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].file_path, "src/a.rs");
+        assert_eq!(results[0].line_number, 2);
+        assert_eq!(results[0].content, "let key = env::var(\"KEY\");\n}");
+        assert_eq!(results[1].file_path, "src/b.rs");
+        assert_eq!(results[1].content, "// KEY: rotated");
+        assert!(results.iter().all(|r| !r.content.contains("hunter2")));
+
+        let everything =
+            CodeSearch::parse_ripgrep_output(stdout, "text", 20, &ToolPolicy::default());
+        assert_eq!(everything.len(), 3);
+    }
+}
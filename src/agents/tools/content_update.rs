@@ -110,7 +110,7 @@ impl Tool for UpdateCommitTool {
 
         let result = json!({
             "success": true,
-            "message": "Commit message updated successfully",
+            "message": "Commit message sent; the user reviews changes to existing content before they apply",
             "new_title": args.title,
             "emoji": args.emoji
         });
@@ -169,7 +169,7 @@ impl Tool for UpdatePRTool {
 
         let result = json!({
             "success": true,
-            "message": "PR description updated successfully",
+            "message": "PR description sent; the user reviews changes to existing content before they apply",
             "content_length": content_len
         });
 
@@ -227,7 +227,7 @@ impl Tool for UpdateReviewTool {
 
         let result = json!({
             "success": true,
-            "message": "Review updated successfully",
+            "message": "Review sent; the user reviews changes to existing content before they apply",
             "content_length": content_len
         });
 
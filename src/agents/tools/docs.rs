@@ -7,16 +7,30 @@ use anyhow::Result;
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use super::common::parameters_schema;
+use crate::agents::policy::ToolPolicy;
 
 // Use standard tool error macro for consistency
 crate::define_tool_error!(DocsError);
 
 /// Tool for fetching project documentation files
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ProjectDocs;
+pub struct ProjectDocs {
+    /// Files in paths the policy denies aren't read
+    #[serde(skip)]
+    policy: Arc<ToolPolicy>,
+}
+
+impl ProjectDocs {
+    pub fn new(policy: &Arc<ToolPolicy>) -> Self {
+        Self {
+            policy: Arc::clone(policy),
+        }
+    }
+}
 
 /// Type of documentation to fetch
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema, Default)]
@@ -39,6 +53,55 @@ pub enum DocType {
     Context,
     /// All documentation files
     All,
+    /// Changelog of an updated dependency (requires `package`)
+    Dependency,
+}
+
+impl DocType {
+    /// Files to look for in the project root, in order
+    fn candidates(&self) -> &'static [&'static str] {
+        match self {
+            DocType::Readme => &[
+                "README.md",
+                "README.rst",
+                "README.txt",
+                "README",
+                "readme.md",
+            ],
+            DocType::Contributing => &["CONTRIBUTING.md", "CONTRIBUTING", "contributing.md"],
+            DocType::Changelog => &[
+                "CHANGELOG.md",
+                "CHANGELOG",
+                "HISTORY.md",
+                "CHANGES.md",
+                "changelog.md",
+            ],
+            DocType::License => &["LICENSE", "LICENSE.md", "LICENSE.txt", "license"],
+            DocType::CodeOfConduct => &["CODE_OF_CONDUCT.md", "code_of_conduct.md"],
+            DocType::Agents => &[
+                "AGENTS.md",
+                "CLAUDE.md",
+                ".github/copilot-instructions.md",
+                ".cursor/rules",
+                "CODING_GUIDELINES.md",
+            ],
+            DocType::Context => &[
+                "README.md",
+                "AGENTS.md",
+                "CLAUDE.md",
+                ".github/copilot-instructions.md",
+            ],
+            DocType::Dependency => &[],
+            DocType::All => &[
+                "README.md",
+                "AGENTS.md",
+                "CLAUDE.md",
+                "CONTRIBUTING.md",
+                "CHANGELOG.md",
+                "CODE_OF_CONDUCT.md",
+            ],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
@@ -49,6 +112,15 @@ pub struct ProjectDocsArgs {
     /// Maximum characters to return (default: 5000, max: 20000)
     #[serde(default = "default_max_chars")]
     pub max_chars: usize,
+    /// Package name, for `dependency`
+    #[serde(default)]
+    pub package: Option<String>,
+    /// Package version to read the changelog of, for `dependency`
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Package ecosystem (cargo, npm, python, go), for `dependency`
+    #[serde(default)]
+    pub ecosystem: Option<String>,
 }
 
 fn default_max_chars() -> usize {
@@ -65,7 +137,7 @@ impl Tool for ProjectDocs {
         ToolDefinition {
             name: "project_docs".to_string(),
             description:
-                "Fetch project documentation for context. Types: readme, contributing, changelog, license, codeofconduct, agents (AGENTS.md/CLAUDE.md), context (readme + agent instructions - RECOMMENDED), all"
+                "Fetch project documentation for context. Types: readme, contributing, changelog, license, codeofconduct, agents (AGENTS.md/CLAUDE.md), context (readme + agent instructions - RECOMMENDED), all, dependency (changelog of an updated package; pass package, version, ecosystem)"
                     .to_string(),
             parameters: parameters_schema::<ProjectDocsArgs>(),
         }
@@ -73,62 +145,39 @@ impl Tool for ProjectDocs {
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
         let current_dir = std::env::current_dir().map_err(DocsError::from)?;
+        Ok(self.read_docs(&current_dir, &args).await)
+    }
+}
+
+impl ProjectDocs {
+    /// Read the documentation `args` asks for from `root`
+    async fn read_docs(&self, root: &Path, args: &ProjectDocsArgs) -> String {
         let max_chars = args.max_chars.min(20000);
 
-        let files_to_check = match args.doc_type {
-            DocType::Readme => vec![
-                "README.md",
-                "README.rst",
-                "README.txt",
-                "README",
-                "readme.md",
-            ],
-            DocType::Contributing => vec!["CONTRIBUTING.md", "CONTRIBUTING", "contributing.md"],
-            DocType::Changelog => vec![
-                "CHANGELOG.md",
-                "CHANGELOG",
-                "HISTORY.md",
-                "CHANGES.md",
-                "changelog.md",
-            ],
-            DocType::License => vec!["LICENSE", "LICENSE.md", "LICENSE.txt", "license"],
-            DocType::CodeOfConduct => vec!["CODE_OF_CONDUCT.md", "code_of_conduct.md"],
-            DocType::Agents => vec![
-                "AGENTS.md",
-                "CLAUDE.md",
-                ".github/copilot-instructions.md",
-                ".cursor/rules",
-                "CODING_GUIDELINES.md",
-            ],
-            DocType::Context => vec![
-                "README.md",
-                "AGENTS.md",
-                "CLAUDE.md",
-                ".github/copilot-instructions.md",
-            ],
-            DocType::All => vec![
-                "README.md",
-                "AGENTS.md",
-                "CLAUDE.md",
-                "CONTRIBUTING.md",
-                "CHANGELOG.md",
-                "CODE_OF_CONDUCT.md",
-            ],
-        };
+        if matches!(args.doc_type, DocType::Dependency) {
+            return dependency_changelog(root, args, max_chars, &self.policy).await;
+        }
+
+        let files_to_check = args.doc_type.candidates();
 
         let mut output = String::new();
         let mut found_any = false;
         // Track if we found an agent instructions file (AGENTS.md often symlinks to CLAUDE.md)
         let mut found_agent_doc = false;
+        let mut withheld = 0;
 
-        for filename in files_to_check {
+        for &filename in files_to_check {
             // Skip CLAUDE.md if we already found AGENTS.md (avoid duplicate from symlink)
             if filename == "CLAUDE.md" && found_agent_doc {
                 continue;
             }
 
-            let path: PathBuf = current_dir.join(filename);
+            let path: PathBuf = root.join(filename);
             if path.exists() {
+                if self.policy.check_read(Self::NAME, filename).is_err() {
+                    withheld += 1;
+                    continue;
+                }
                 match tokio::fs::read_to_string(&path).await {
                     Ok(content) => {
                         found_any = true;
@@ -173,7 +222,115 @@ impl Tool for ProjectDocs {
                 args.doc_type
             );
         }
+        if withheld > 0 {
+            output.push_str(&format!(
+                "\n({withheld} documentation files withheld by the repository's tool policy)\n"
+            ));
+        }
+
+        output
+    }
+}
+
+/// Changelog file names shipped inside packages
+const PACKAGE_CHANGELOGS: &[&str] = &["CHANGELOG.md", "CHANGES.md", "HISTORY.md", "RELEASES.md"];
+
+/// Read an updated package's changelog from local sources
+///
+/// Looks in `node_modules` and the Cargo registry cache, skipping files
+/// `policy` denies; when nothing is found locally, returns the registry's
+/// release history link instead.
+async fn dependency_changelog(
+    current_dir: &Path,
+    args: &ProjectDocsArgs,
+    max_chars: usize,
+    policy: &ToolPolicy,
+) -> String {
+    use crate::dependency_updates::Ecosystem;
+
+    let Some(package) = args.package.as_deref().filter(|p| !p.is_empty()) else {
+        return "The dependency doc type needs a package name.".to_string();
+    };
+    let version = args
+        .version
+        .as_deref()
+        .map(|v| v.trim_start_matches(['^', '~', '=', '>', '<', ' ', 'v']));
+    let ecosystem = args.ecosystem.as_deref().and_then(Ecosystem::parse);
+
+    // Relative to the repository, or absolute outside it, as the policy sees them
+    let mut dirs = vec![Path::new("node_modules").join(package)];
+    if let (Some(Ecosystem::Cargo) | None, Some(version)) = (ecosystem, version) {
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+        if let Some(Ok(registries)) =
+            cargo_home.map(|home| std::fs::read_dir(home.join("registry").join("src")))
+        {
+            dirs.extend(
+                registries
+                    .flatten()
+                    .map(|entry| entry.path().join(format!("{package}-{version}"))),
+            );
+        }
+    }
+
+    for dir in dirs {
+        for name in PACKAGE_CHANGELOGS {
+            let path = dir.join(name);
+            let full_path = current_dir.join(&path);
+            if !full_path.is_file()
+                || policy
+                    .check_read(ProjectDocs::NAME, &path.to_string_lossy())
+                    .is_err()
+            {
+                continue;
+            }
+            if let Ok(content) = tokio::fs::read_to_string(&full_path).await {
+                let truncated: String = content.chars().take(max_chars).collect();
+                return format!("=== {package} {name} ===\n{truncated}");
+            }
+        }
+    }
+
+    ecosystem.map_or_else(
+        || format!("No local changelog found for {package}."),
+        |ecosystem| {
+            format!(
+                "No local changelog found for {package}. Release history: {}",
+                ecosystem.release_history_url(package)
+            )
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_denied_docs_are_not_read() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("README.md"), "secret readme").expect("readme");
+        std::fs::write(dir.path().join("AGENTS.md"), "agent notes").expect("agents");
+
+        let policy = ToolPolicy::parse("[paths]\ndeny = [\"README.md\"]\n").expect("policy");
+        let docs = ProjectDocs::new(&Arc::new(policy));
+        let args = ProjectDocsArgs {
+            doc_type: DocType::Context,
+            max_chars: default_max_chars(),
+            package: None,
+            version: None,
+            ecosystem: None,
+        };
 
-        Ok(output)
+        let output = docs.read_docs(dir.path(), &args).await;
+        assert!(!output.contains("secret readme"));
+        assert!(output.contains("agent notes"));
+        assert!(output.contains("1 documentation files withheld"));
+        assert!(
+            crate::agents::policy::take_violations()
+                .iter()
+                .any(|v| v.tool == "project_docs" && v.reason.contains("README.md"))
+        );
     }
 }
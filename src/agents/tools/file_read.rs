@@ -98,6 +98,64 @@ impl FileRead {
         output.push_str("\nUse file_read with a specific file path to read contents.\n");
         Ok(output)
     }
+
+    /// Line-numbered output for the requested range of `content`
+    fn format_content(args: &FileReadArgs, content: Vec<u8>) -> Result<String, FileReadError> {
+        // Check for binary content
+        if Self::is_binary(&content) {
+            return Ok(format!(
+                "[Binary file detected: {} - content not displayed]",
+                args.path
+            ));
+        }
+
+        // Convert to string
+        let content_str = String::from_utf8(content).map_err(|e| FileReadError(e.to_string()))?;
+
+        let lines: Vec<&str> = content_str.lines().collect();
+        let total_lines = lines.len();
+
+        // Calculate range
+        let start = args.start_line.unwrap_or(1).saturating_sub(1); // Convert to 0-indexed
+        let max_lines = args.num_lines.unwrap_or(Self::DEFAULT_MAX_LINES).min(1000);
+        let end = (start + max_lines).min(total_lines);
+
+        // Build output with line numbers
+        let mut output = String::new();
+        output.push_str(&format!(
+            "=== {} ({} total lines) ===\n",
+            args.path, total_lines
+        ));
+
+        if start > 0 || end < total_lines {
+            output.push_str(&format!(
+                "Showing lines {}-{} of {}\n",
+                start + 1,
+                end,
+                total_lines
+            ));
+        }
+        output.push('\n');
+
+        for (i, line) in lines.iter().enumerate().skip(start).take(end - start) {
+            output.push_str(&format!(
+                "{:>width$}│ {}\n",
+                i + 1,
+                line,
+                width = Self::LINE_NUM_WIDTH
+            ));
+        }
+
+        if end < total_lines {
+            output.push_str(&format!(
+                "\n... {} more lines (use start_line={} to continue)\n",
+                total_lines - end,
+                end + 1
+            ));
+        }
+
+        Ok(output)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
@@ -137,6 +195,21 @@ impl Tool for FileRead {
             ));
         }
 
+        // A bare repository has no files on disk; read them as committed at HEAD
+        if repo.is_bare() {
+            if Self::is_binary_extension(&args.path) {
+                return Ok(format!(
+                    "[Binary file: {} - content not displayed]",
+                    args.path
+                ));
+            }
+            let content = repo
+                .read_committed_file("HEAD", &args.path)
+                .map_err(FileReadError::from)?
+                .ok_or_else(|| FileReadError(format!("File not found at HEAD: {}", args.path)))?;
+            return Self::format_content(&args, content);
+        }
+
         // Join path to repo root
         let file_path = repo_path.join(&args.path);
 
@@ -177,60 +250,6 @@ impl Tool for FileRead {
 
         // Read the file (use canonical path for actual read)
         let content = fs::read(&canonical_file).map_err(|e| FileReadError(e.to_string()))?;
-
-        // Check for binary content
-        if Self::is_binary(&content) {
-            return Ok(format!(
-                "[Binary file detected: {} - content not displayed]",
-                args.path
-            ));
-        }
-
-        // Convert to string
-        let content_str = String::from_utf8(content).map_err(|e| FileReadError(e.to_string()))?;
-
-        let lines: Vec<&str> = content_str.lines().collect();
-        let total_lines = lines.len();
-
-        // Calculate range
-        let start = args.start_line.unwrap_or(1).saturating_sub(1); // Convert to 0-indexed
-        let max_lines = args.num_lines.unwrap_or(Self::DEFAULT_MAX_LINES).min(1000);
-        let end = (start + max_lines).min(total_lines);
-
-        // Build output with line numbers
-        let mut output = String::new();
-        output.push_str(&format!(
-            "=== {} ({} total lines) ===\n",
-            args.path, total_lines
-        ));
-
-        if start > 0 || end < total_lines {
-            output.push_str(&format!(
-                "Showing lines {}-{} of {}\n",
-                start + 1,
-                end,
-                total_lines
-            ));
-        }
-        output.push('\n');
-
-        for (i, line) in lines.iter().enumerate().skip(start).take(end - start) {
-            output.push_str(&format!(
-                "{:>width$}│ {}\n",
-                i + 1,
-                line,
-                width = Self::LINE_NUM_WIDTH
-            ));
-        }
-
-        if end < total_lines {
-            output.push_str(&format!(
-                "\n... {} more lines (use start_line={} to continue)\n",
-                total_lines - end,
-                end + 1
-            ));
-        }
-
-        Ok(output)
+        Self::format_content(&args, content)
     }
 }
@@ -6,10 +6,14 @@ use anyhow::Result;
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
+use crate::agents::context::budget;
+use crate::agents::context::cache::{ContextCache, FileAnalysis};
+use crate::agents::policy::ToolPolicy;
 use crate::context::ChangeType;
 use crate::define_tool_error;
-use crate::git::StagedFile;
+use crate::git::{FileStats, HistoryStats, StagedFile};
 
 use super::common::{get_current_repo, parameters_schema};
 
@@ -233,11 +237,26 @@ fn calculate_relevance_score(file: &StagedFile) -> (f32, Vec<&'static str>) {
     (score, reasons)
 }
 
+/// Relevance of a file, from the context cache when its diff was scored before
+fn analyze_file(cache: Option<&ContextCache>, file: &StagedFile) -> FileAnalysis {
+    let analyze = |file: &StagedFile| {
+        let (relevance, reasons) = calculate_relevance_score(file);
+        FileAnalysis {
+            relevance,
+            reasons: reasons.into_iter().map(str::to_string).collect(),
+        }
+    };
+    match cache {
+        Some(cache) => cache.analysis(file, analyze),
+        None => analyze(file),
+    }
+}
+
 /// Scored file for output
 struct ScoredFile<'a> {
     file: &'a StagedFile,
     score: f32,
-    reasons: Vec<&'static str>,
+    reasons: Vec<String>,
 }
 
 /// Build the diff output string from scored files
@@ -246,6 +265,7 @@ fn format_diff_output(
     total_files: usize,
     is_filtered: bool,
     include_diffs: bool,
+    diff_budget: usize,
 ) -> String {
     let mut output = String::new();
     let showing = scored_files.len();
@@ -304,16 +324,22 @@ fn format_diff_output(
 
     // Diffs or hint
     if include_diffs {
+        // Most relevant files first, within the model's token budget
+        let diffs: Vec<(&str, &str)> = scored_files
+            .iter()
+            .map(|sf| (sf.file.path.as_str(), sf.file.diff.as_str()))
+            .collect();
+        let (fitted, omissions) = budget::fit_diffs(&diffs, diff_budget);
         output.push_str("=== DIFFS ===\n");
-        for sf in scored_files {
-            output.push_str(&format!(
-                "--- {} [{:.0}% relevance]\n",
-                sf.file.path,
-                sf.score * 100.0
-            ));
-            output.push_str(&sf.file.diff);
+        for (sf, diff) in scored_files.iter().zip(fitted) {
+            let Some(diff) = diff else {
+                continue;
+            };
+            output.push_str(&budget::diff_heading(&sf.file.path, sf.score));
+            output.push_str(&diff);
             output.push('\n');
         }
+        output.push_str(&budget::omissions_section(&omissions));
     } else if is_filtered {
         output.push_str("(Use detail='standard' to see full diffs for these files)\n");
     } else {
@@ -374,7 +400,22 @@ impl Tool for GitStatus {
 
 // Git diff tool
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GitDiff;
+pub struct GitDiff {
+    /// Files in paths the policy denies are left out
+    #[serde(skip)]
+    policy: Arc<ToolPolicy>,
+    /// Diff tokens one call may return, set from the agent's provider
+    budget: usize,
+}
+
+impl GitDiff {
+    pub fn new(policy: &Arc<ToolPolicy>, budget: usize) -> Self {
+        Self {
+            policy: Arc::clone(policy),
+            budget,
+        }
+    }
+}
 
 /// Detail level for diff output
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema, Default)]
@@ -452,15 +493,24 @@ impl Tool for GitDiff {
             }
         };
 
+        // Leave out what the repository's tool policy denies
+        let changed = files.len();
+        let files: Vec<StagedFile> = files
+            .into_iter()
+            .filter(|file| self.policy.allows_path(&file.path))
+            .collect();
+        let withheld = changed - files.len();
+
         // Score and sort files by relevance
+        let cache = ContextCache::for_repo(&repo).ok();
         let mut scored_files: Vec<ScoredFile> = files
             .iter()
             .map(|file| {
-                let (score, reasons) = calculate_relevance_score(file);
+                let analysis = analyze_file(cache.as_ref(), file);
                 ScoredFile {
                     file,
-                    score,
-                    reasons,
+                    score: analysis.relevance,
+                    reasons: analysis.reasons,
                 }
             })
             .collect();
@@ -483,12 +533,19 @@ impl Tool for GitDiff {
 
         // Build output
         let include_diffs = matches!(args.detail, DetailLevel::Standard);
-        Ok(format_diff_output(
+        let mut output = format_diff_output(
             &scored_files,
             total_files,
             is_filtered,
             include_diffs,
-        ))
+            self.budget,
+        );
+        if withheld > 0 {
+            output.push_str(&format!(
+                "\n({withheld} changed files withheld by the repository's tool policy)\n"
+            ));
+        }
+        Ok(output)
     }
 }
 
@@ -537,6 +594,138 @@ impl Tool for GitLog {
     }
 }
 
+// Git history statistics tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitStats;
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GitStatsArgs {
+    /// Days of history to summarize (default 90)
+    #[serde(default)]
+    pub days: Option<u32>,
+    /// Files or directories to report on in detail, such as the changed files
+    #[serde(default)]
+    pub paths: Option<Vec<String>>,
+    /// Files listed in each ranking (default 10)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Days ago a timestamp was, for "last changed" lines
+fn days_ago(timestamp: i64, now: i64) -> String {
+    match (now - timestamp).max(0) / 86_400 {
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        days => format!("{days} days ago"),
+    }
+}
+
+/// One line of history for a file or directory
+fn format_file_stats(path: &str, file: &FileStats, now: i64) -> String {
+    let authors = file
+        .top_authors(3)
+        .iter()
+        .map(|(name, count)| format!("{name} ({count})"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut line = format!(
+        "  {path}: {} commits, +{} -{}, last changed {}; authors: {authors}",
+        file.commits,
+        file.additions,
+        file.deletions,
+        days_ago(file.last_changed, now)
+    );
+    if file.authors.len() > 3 {
+        line.push_str(&format!(" and {} more", file.authors.len() - 3));
+    }
+    if file.reverts > 0 {
+        line.push_str(&format!("; reverted {} times", file.reverts));
+    }
+    line.push('\n');
+    line
+}
+
+/// Build the stats output string
+fn format_stats_output(stats: &HistoryStats, paths: &[String], limit: usize, now: i64) -> String {
+    let mut output = format!(
+        "=== HISTORY (last {} days, merges excluded) ===\n{} commits, {} reverts\nCommits per week (oldest first): {}\n\n",
+        stats.window_days,
+        stats.total_commits,
+        stats.reverts,
+        stats
+            .weekly_commits
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+
+    if !paths.is_empty() {
+        output.push_str("Requested paths:\n");
+        for path in paths {
+            match stats.for_path(path) {
+                Some(file) => output.push_str(&format_file_stats(path, &file, now)),
+                None => output.push_str(&format!("  {path}: no commits in this window\n")),
+            }
+        }
+        output.push('\n');
+    }
+
+    output.push_str("Top churn:\n");
+    for (path, file) in stats.top_churn(limit) {
+        output.push_str(&format_file_stats(path, file, now));
+    }
+
+    let reverted = stats.most_reverted(limit);
+    if !reverted.is_empty() {
+        output.push_str("\nTouched by reverts:\n");
+        for (path, file) in reverted {
+            output.push_str(&format!(
+                "  {path}: reverted {} times, last changed {}\n",
+                file.reverts,
+                days_ago(file.last_changed, now)
+            ));
+        }
+    }
+    output
+}
+
+impl Tool for GitStats {
+    const NAME: &'static str = "git_stats";
+    type Error = GitError;
+    type Args = GitStatsArgs;
+    type Output = String;
+
+    async fn definition(&self, _: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "git_stats".to_string(),
+            description: "Get aggregate commit history in one call: commits per week, the files with the most churn, who commits to which paths, and which files were touched by reverts. Pass paths=['src/auth.rs','src/api/'] to get the history of the files you're describing or reviewing.".to_string(),
+            parameters: parameters_schema::<GitStatsArgs>(),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let repo = get_current_repo().map_err(GitError::from)?;
+
+        let days = args.days.unwrap_or(90).clamp(1, 3650);
+        let limit = args.limit.unwrap_or(10).clamp(1, 50);
+        let paths: Vec<String> = args
+            .paths
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|p| !p.is_empty())
+            .collect();
+
+        let stats = repo.get_history_stats(days).map_err(GitError::from)?;
+        Ok(format_stats_output(
+            &stats,
+            &paths,
+            limit,
+            chrono::Utc::now().timestamp(),
+        ))
+    }
+}
+
 // Git repository info tool
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitRepoInfo;
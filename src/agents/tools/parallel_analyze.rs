@@ -8,7 +8,7 @@ use anyhow::Result;
 use rig::{
     client::CompletionClient,
     completion::{Prompt, ToolDefinition},
-    providers::{anthropic, openai},
+    providers::{anthropic, gemini, ollama, openai},
     tool::Tool,
 };
 use schemars::JsonSchema;
@@ -19,12 +19,13 @@ use std::time::Duration;
 use tokio::sync::Mutex;
 
 use crate::agents::debug as agent_debug;
+use crate::agents::policy::ToolPolicy;
 
 /// Default timeout for individual subagent tasks (2 minutes)
 const DEFAULT_SUBAGENT_TIMEOUT_SECS: u64 = 120;
 
 /// Arguments for parallel analysis
-#[derive(Debug, Deserialize, JsonSchema)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ParallelAnalyzeArgs {
     /// List of analysis tasks to run in parallel.
     /// Each task should be a focused prompt describing what to analyze.
@@ -69,25 +70,65 @@ enum SubagentRunner {
         client: anthropic::Client,
         model: String,
     },
+    Google {
+        client: gemini::Client,
+        model: String,
+    },
+    Ollama {
+        client: ollama::Client,
+        model: String,
+    },
 }
 
 impl SubagentRunner {
-    fn new(provider: &str, model: &str) -> Result<Self> {
+    /// A runner whose requests carry `headers`
+    fn new(provider: &str, model: &str, headers: &[(String, String)]) -> Result<Self> {
+        use crate::providers::Provider;
+
+        let http = crate::agents::iris::http_client(headers);
         match provider {
             "openai" => {
-                let client = openai::Client::from_env();
+                let api_key = Provider::OpenAI.env_api_key().unwrap_or_default();
+                let base_url = std::env::var("OPENAI_BASE_URL").ok();
+                let builder = openai::Client::builder(&api_key).with_client(http);
+                let client = match &base_url {
+                    Some(url) => builder.base_url(url).build(),
+                    None => builder.build(),
+                };
                 Ok(Self::OpenAI {
                     client,
                     model: model.to_string(),
                 })
             }
             "anthropic" => {
-                let client = anthropic::Client::from_env();
+                let api_key = Provider::Anthropic.env_api_key().unwrap_or_default();
+                let client = anthropic::Client::builder(&api_key)
+                    .with_client(http)
+                    .build()?;
                 Ok(Self::Anthropic {
                     client,
                     model: model.to_string(),
                 })
             }
+            "google" => {
+                let api_key = Provider::Google.env_api_key().unwrap_or_default();
+                Ok(Self::Google {
+                    client: gemini::Client::builder(&api_key)
+                        .with_client(http)
+                        .build()?,
+                    model: model.to_string(),
+                })
+            }
+            "ollama" => {
+                let client = ollama::Client::builder()
+                    .base_url(&crate::providers::ollama_base_url())
+                    .with_client(http)
+                    .build();
+                Ok(Self::Ollama {
+                    client,
+                    model: model.to_string(),
+                })
+            }
             _ => Err(anyhow::anyhow!(
                 "Unsupported provider for parallel analysis: {}",
                 provider
@@ -95,7 +136,12 @@ impl SubagentRunner {
         }
     }
 
-    async fn run_task(&self, task: &str) -> SubagentResult {
+    async fn run_task(
+        &self,
+        task: &str,
+        policy: &Arc<ToolPolicy>,
+        diff_budget: usize,
+    ) -> SubagentResult {
         let preamble = "You are a specialized analysis sub-agent. Complete the assigned \
             task thoroughly and return a focused summary.\n\n\
             Guidelines:\n\
@@ -108,12 +154,22 @@ impl SubagentRunner {
         let result = match self {
             Self::OpenAI { client, model } => {
                 let builder = client.agent(model).preamble(preamble).max_tokens(4096);
-                let agent = crate::attach_core_tools!(builder).build();
+                let agent = crate::attach_core_tools!(builder, policy, diff_budget).build();
                 agent.prompt(task).await
             }
             Self::Anthropic { client, model } => {
                 let builder = client.agent(model).preamble(preamble).max_tokens(4096);
-                let agent = crate::attach_core_tools!(builder).build();
+                let agent = crate::attach_core_tools!(builder, policy, diff_budget).build();
+                agent.prompt(task).await
+            }
+            Self::Google { client, model } => {
+                let builder = client.agent(model).preamble(preamble).max_tokens(4096);
+                let agent = crate::attach_core_tools!(builder, policy, diff_budget).build();
+                agent.prompt(task).await
+            }
+            Self::Ollama { client, model } => {
+                let builder = client.agent(model).preamble(preamble).max_tokens(4096);
+                let agent = crate::attach_core_tools!(builder, policy, diff_budget).build();
                 agent.prompt(task).await
             }
         };
@@ -142,29 +198,50 @@ pub struct ParallelAnalyze {
     model: String,
     /// Timeout in seconds for each subagent task
     timeout_secs: u64,
+    /// The parent agent's tool policy, which subagents follow too
+    policy: Arc<ToolPolicy>,
+    /// Diff tokens a subagent's `git_diff` may return
+    diff_budget: usize,
 }
 
 impl ParallelAnalyze {
     /// Create a new parallel analyzer with default timeout
-    pub fn new(provider: &str, model: &str) -> Self {
-        Self::with_timeout(provider, model, DEFAULT_SUBAGENT_TIMEOUT_SECS)
+    pub fn new(provider: &str, model: &str, policy: &Arc<ToolPolicy>, diff_budget: usize) -> Self {
+        Self::with_timeout(
+            provider,
+            model,
+            DEFAULT_SUBAGENT_TIMEOUT_SECS,
+            &[],
+            policy,
+            diff_budget,
+        )
     }
 
-    /// Create a new parallel analyzer with custom timeout
-    pub fn with_timeout(provider: &str, model: &str, timeout_secs: u64) -> Self {
+    /// Create a new parallel analyzer with custom timeout, sending `headers`
+    /// with every subagent request
+    pub fn with_timeout(
+        provider: &str,
+        model: &str,
+        timeout_secs: u64,
+        headers: &[(String, String)],
+        policy: &Arc<ToolPolicy>,
+        diff_budget: usize,
+    ) -> Self {
         // Default to openai if creation fails
-        let runner = SubagentRunner::new(provider, model).unwrap_or_else(|_| {
+        let runner = SubagentRunner::new(provider, model, headers).unwrap_or_else(|_| {
             tracing::warn!(
                 "Failed to create {} runner, falling back to openai",
                 provider
             );
-            SubagentRunner::new("openai", "gpt-4o").expect("OpenAI fallback should work")
+            SubagentRunner::new("openai", "gpt-4o", &[]).expect("OpenAI fallback should work")
         });
 
         Self {
             runner,
             model: model.to_string(),
             timeout_secs,
+            policy: Arc::clone(policy),
+            diff_budget,
         }
     }
 }
@@ -232,13 +309,19 @@ impl Tool for ParallelAnalyze {
         let timeout = Duration::from_secs(self.timeout_secs);
         for (index, task) in tasks.into_iter().enumerate() {
             let runner = self.runner.clone();
+            let policy = Arc::clone(&self.policy);
+            let diff_budget = self.diff_budget;
             let results = Arc::clone(&results);
             let task_timeout = timeout;
             let timeout_secs = self.timeout_secs;
 
             let handle = tokio::spawn(async move {
                 // Wrap task execution in timeout to prevent hanging
-                let result = match tokio::time::timeout(task_timeout, runner.run_task(&task)).await
+                let result = match tokio::time::timeout(
+                    task_timeout,
+                    runner.run_task(&task, &policy, diff_budget),
+                )
+                .await
                 {
                     Ok(result) => result,
                     Err(_) => SubagentResult {
@@ -7,28 +7,33 @@
 ///
 /// These are the standard tools available to all agents and subagents for
 /// code analysis tasks. Does NOT include delegation tools (`Workspace`, `ParallelAnalyze`,
-/// sub-agent) to prevent recursion.
+/// sub-agent) to prevent recursion. Every tool is checked against `policy`,
+/// the agent's `Arc<ToolPolicy>`, and `git_diff` returns at most
+/// `diff_budget` tokens of diffs.
 ///
 /// # Usage
 /// ```ignore
-/// let agent = attach_core_tools!(client.agent(model).preamble("..."));
+/// let agent = attach_core_tools!(client.agent(model).preamble("..."), &policy, diff_budget);
 /// ```
 #[macro_export]
 macro_rules! attach_core_tools {
-    ($builder:expr) => {{
+    ($builder:expr, $policy:expr, $diff_budget:expr) => {{
         use $crate::agents::debug_tool::DebugTool;
         use $crate::agents::tools::{
-            CodeSearch, FileRead, GitChangedFiles, GitDiff, GitLog, GitStatus, ProjectDocs,
+            CodeSearch, FileRead, GitChangedFiles, GitDiff, GitLog, GitStats, GitStatus,
+            ProjectDocs,
         };
 
+        let policy: &std::sync::Arc<$crate::agents::policy::ToolPolicy> = $policy;
         $builder
-            .tool(DebugTool::new(GitStatus))
-            .tool(DebugTool::new(GitDiff))
-            .tool(DebugTool::new(GitLog))
-            .tool(DebugTool::new(GitChangedFiles))
-            .tool(DebugTool::new(FileRead))
-            .tool(DebugTool::new(CodeSearch))
-            .tool(DebugTool::new(ProjectDocs))
+            .tool(DebugTool::new(GitStatus, policy))
+            .tool(DebugTool::new(GitDiff::new(policy, $diff_budget), policy))
+            .tool(DebugTool::new(GitLog, policy))
+            .tool(DebugTool::new(GitChangedFiles, policy))
+            .tool(DebugTool::new(GitStats, policy))
+            .tool(DebugTool::new(FileRead, policy))
+            .tool(DebugTool::new(CodeSearch::new(policy), policy))
+            .tool(DebugTool::new(ProjectDocs::new(policy), policy))
     }};
 }
 
@@ -38,6 +43,7 @@ pub const CORE_TOOLS: &[&str] = &[
     "git_diff",
     "git_log",
     "git_changed_files",
+    "git_stats",
     "file_read",
     "code_search",
     "project_docs",
@@ -52,6 +58,6 @@ mod tests {
 
     #[test]
     fn core_tools_count() {
-        assert_eq!(CORE_TOOLS.len(), 7);
+        assert_eq!(CORE_TOOLS.len(), 8);
     }
 }
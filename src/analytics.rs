@@ -0,0 +1,345 @@
+//! Local usage analytics
+//!
+//! With `local_analytics = true`, git-iris appends one line to
+//! `~/.iris/analytics.jsonl` for every command run, Studio mode entered and
+//! generation finished, noting whether it succeeded and, for failures, the
+//! first line of the error. Nothing is ever uploaded: the file exists so
+//! `git-iris stats usage` can show which features get used and how often
+//! generations fail.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Past this size the oldest half of the file is dropped
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Longest error message kept
+const MAX_ERROR_CHARS: usize = 200;
+
+/// Failures listed in the report
+const RECENT_ERRORS: usize = 5;
+
+/// What a usage event records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageKind {
+    /// A CLI command (`gen`, `stats usage`, …)
+    Command,
+    /// A Studio mode entered
+    Mode,
+    /// A capability run by the agent (`commit`, `review`, `chat`, …)
+    Generation,
+}
+
+/// One line of the analytics file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageEvent {
+    pub at: DateTime<Utc>,
+    pub kind: UsageKind,
+    pub name: String,
+    #[serde(default = "default_ok")]
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn default_ok() -> bool {
+    true
+}
+
+impl UsageEvent {
+    fn new(kind: UsageKind, name: &str) -> Self {
+        Self {
+            at: Utc::now(),
+            kind,
+            name: name.to_string(),
+            ok: true,
+            duration_ms: None,
+            error: None,
+        }
+    }
+
+    fn with_error(mut self, error: Option<&anyhow::Error>) -> Self {
+        if let Some(error) = error {
+            self.ok = false;
+            let first_line = error.to_string();
+            let first_line = first_line.lines().next().unwrap_or_default();
+            self.error = Some(first_line.chars().take(MAX_ERROR_CHARS).collect());
+        }
+        self
+    }
+
+    /// A CLI command finished
+    pub fn command(name: &str, error: Option<&anyhow::Error>) -> Self {
+        Self::new(UsageKind::Command, name).with_error(error)
+    }
+
+    /// A Studio mode was entered
+    pub fn mode(name: &str) -> Self {
+        Self::new(UsageKind::Mode, name)
+    }
+
+    /// A generation finished
+    pub fn generation(capability: &str, elapsed: Duration, error: Option<&anyhow::Error>) -> Self {
+        let mut event = Self::new(UsageKind::Generation, capability).with_error(error);
+        event.duration_ms = u64::try_from(elapsed.as_millis()).ok();
+        event
+    }
+}
+
+/// Location of the analytics file
+pub fn analytics_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".iris").join("analytics.jsonl"))
+}
+
+/// Append an event; failures are logged, never surfaced
+pub fn record(event: &UsageEvent) {
+    let Some(path) = analytics_path() else {
+        return;
+    };
+    if let Err(e) = append_to(&path, event) {
+        crate::log_debug!("Usage event not recorded: {}", e);
+    }
+}
+
+fn append_to(path: &Path, event: &UsageEvent) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::metadata(path).is_ok_and(|meta| meta.len() > MAX_FILE_BYTES) {
+        let content = fs::read_to_string(path)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let kept = lines[lines.len() / 2..].join("\n");
+        fs::write(path, kept + "\n")?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(event)?)?;
+    Ok(())
+}
+
+/// Events recorded since `since` (all of them for `None`), oldest first;
+/// unreadable lines are skipped
+pub fn load(since: Option<DateTime<Utc>>) -> Result<Vec<UsageEvent>> {
+    let Some(path) = analytics_path() else {
+        return Ok(Vec::new());
+    };
+    load_from(&path, since)
+}
+
+fn load_from(path: &Path, since: Option<DateTime<Utc>>) -> Result<Vec<UsageEvent>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<UsageEvent>(line).ok())
+        .filter(|event| since.is_none_or(|since| event.at >= since))
+        .collect())
+}
+
+/// Totals for one command, mode or capability
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageCount {
+    pub runs: usize,
+    pub failed: usize,
+    total_ms: u64,
+    timed: usize,
+}
+
+impl UsageCount {
+    /// Mean duration of the timed runs
+    pub fn average_ms(&self) -> Option<u64> {
+        u64::try_from(self.timed)
+            .ok()
+            .and_then(|timed| self.total_ms.checked_div(timed))
+    }
+}
+
+/// Usage grouped by kind and name
+#[derive(Debug, Clone, Default)]
+pub struct UsageSummary {
+    pub counts: BTreeMap<(UsageKind, String), UsageCount>,
+    pub first: Option<DateTime<Utc>>,
+    pub events: usize,
+    /// Latest failures, newest first
+    pub recent_errors: Vec<UsageEvent>,
+}
+
+impl UsageSummary {
+    pub fn from_events(events: &[UsageEvent]) -> Self {
+        let mut summary = Self {
+            first: events.iter().map(|e| e.at).min(),
+            events: events.len(),
+            ..Self::default()
+        };
+        for event in events {
+            let count = summary
+                .counts
+                .entry((event.kind, event.name.clone()))
+                .or_default();
+            count.runs += 1;
+            if !event.ok {
+                count.failed += 1;
+            }
+            if let Some(ms) = event.duration_ms {
+                count.total_ms += ms;
+                count.timed += 1;
+            }
+        }
+        summary.recent_errors = events
+            .iter()
+            .rev()
+            .filter(|e| !e.ok)
+            .take(RECENT_ERRORS)
+            .cloned()
+            .collect();
+        summary
+    }
+
+    /// Entries of one kind, most used first
+    fn ranked(&self, kind: UsageKind) -> Vec<(&str, &UsageCount)> {
+        let mut rows: Vec<(&str, &UsageCount)> = self
+            .counts
+            .iter()
+            .filter(|((k, _), _)| *k == kind)
+            .map(|((_, name), count)| (name.as_str(), count))
+            .collect();
+        rows.sort_by(|a, b| b.1.runs.cmp(&a.1.runs).then_with(|| a.0.cmp(b.0)));
+        rows
+    }
+
+    /// Render the report as markdown
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# Usage\n\n");
+        if let Some(first) = self.first {
+            md.push_str(&format!(
+                "{} events since {}. Recorded locally, never uploaded.\n",
+                self.events,
+                first.format("%Y-%m-%d")
+            ));
+        }
+
+        let generations = self.ranked(UsageKind::Generation);
+        if !generations.is_empty() {
+            md.push_str(
+                "\n## Generations\n\n| Capability | Runs | Success | Avg time |\n| --- | --- | --- | --- |\n",
+            );
+            for (name, count) in generations {
+                let average = count.average_ms().map_or_else(
+                    || "—".to_string(),
+                    |ms| format!("{:.1}s", Duration::from_millis(ms).as_secs_f64()),
+                );
+                md.push_str(&format!(
+                    "| {name} | {} | {} | {average} |\n",
+                    count.runs,
+                    success_rate(count)
+                ));
+            }
+        }
+
+        let commands = self.ranked(UsageKind::Command);
+        if !commands.is_empty() {
+            md.push_str("\n## Commands\n\n| Command | Runs | Success |\n| --- | --- | --- |\n");
+            for (name, count) in commands {
+                md.push_str(&format!(
+                    "| {name} | {} | {} |\n",
+                    count.runs,
+                    success_rate(count)
+                ));
+            }
+        }
+
+        let modes = self.ranked(UsageKind::Mode);
+        if !modes.is_empty() {
+            md.push_str("\n## Studio Modes\n\n| Mode | Entered |\n| --- | --- |\n");
+            for (name, count) in modes {
+                md.push_str(&format!("| {name} | {} |\n", count.runs));
+            }
+        }
+
+        if !self.recent_errors.is_empty() {
+            md.push_str("\n## Recent Failures\n\n| When | What | Error |\n| --- | --- | --- |\n");
+            for event in &self.recent_errors {
+                md.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    event.at.format("%Y-%m-%d %H:%M"),
+                    event.name,
+                    event
+                        .error
+                        .as_deref()
+                        .unwrap_or_default()
+                        .replace('|', "\\|")
+                ));
+            }
+        }
+        md
+    }
+}
+
+fn success_rate(count: &UsageCount) -> String {
+    let succeeded = count.runs - count.failed;
+    (succeeded * 100)
+        .checked_div(count.runs)
+        .map_or_else(|| "—".to_string(), |rate| format!("{rate}%"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_summarize() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("analytics.jsonl");
+        let failure = anyhow::anyhow!("Rate limited by anthropic, try again shortly\ndetails");
+        for event in [
+            UsageEvent::command("gen", None),
+            UsageEvent::mode("Commit"),
+            UsageEvent::generation("commit", Duration::from_millis(1200), None),
+            UsageEvent::generation("commit", Duration::from_millis(800), Some(&failure)),
+            UsageEvent::generation("review", Duration::from_millis(3100), None),
+        ] {
+            append_to(&path, &event).expect("append");
+        }
+        fs::write(
+            &path,
+            fs::read_to_string(&path).expect("read") + "not json\n",
+        )
+        .expect("write");
+
+        let events = load_from(&path, None).expect("load");
+        assert_eq!(events.len(), 5);
+        let summary = UsageSummary::from_events(&events);
+        let commit = &summary.counts[&(UsageKind::Generation, "commit".to_string())];
+        assert_eq!((commit.runs, commit.failed), (2, 1));
+        assert_eq!(commit.average_ms(), Some(1000));
+        assert_eq!(
+            summary.recent_errors[0].error.as_deref(),
+            Some("Rate limited by anthropic, try again shortly")
+        );
+
+        let md = summary.to_markdown();
+        assert!(md.contains("| commit | 2 | 50% | 1.0s |"));
+        assert!(md.contains("| gen | 1 | 100% |"));
+        assert!(md.contains("| Commit | 1 |"));
+        assert!(
+            load_from(&path, Some(Utc::now() + chrono::Duration::hours(1)))
+                .expect("load")
+                .is_empty()
+        );
+    }
+}
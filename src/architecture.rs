@@ -0,0 +1,275 @@
+//! Architecture overview of a repository
+//!
+//! `git-iris explain-repo` and Explore's overview action describe how a
+//! codebase fits together: its main modules, entry points, build and test
+//! commands and the files that change most. The facts come from the tree and
+//! recent history; Iris reads the project's metadata and docs and explains
+//! them. The finished overview only depends on the commit, so it is kept under
+//! `.git/iris/overview`, one markdown file per `HEAD` commit, and reused until
+//! `HEAD` moves.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::git::GitRepo;
+use crate::onboarding::{Activity, ProjectCommand, detect_commands, entry_points, recent_log};
+use crate::pr_split::directory_key;
+
+/// Days of history used for hot files
+pub const HOT_FILE_WINDOW_DAYS: u32 = 90;
+
+/// Modules listed
+const MAX_MODULES: usize = 15;
+
+/// Hot files listed
+const MAX_HOT_FILES: usize = 10;
+
+/// Overviews kept before the oldest are pruned
+const MAX_CACHED: usize = 10;
+
+/// A directory that groups code, with how many tracked files it holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    pub path: String,
+    pub files: usize,
+}
+
+/// Facts an architecture overview is built from
+#[derive(Debug)]
+pub struct ArchitectureFacts {
+    pub project: String,
+    /// Full id of the commit described
+    pub commit: String,
+    pub tracked_files: usize,
+    /// Largest modules, two directory levels deep
+    pub modules: Vec<Module>,
+    pub entry_points: Vec<String>,
+    pub commands: Vec<ProjectCommand>,
+    /// Most frequently changed files with their commit counts
+    pub hot_files: Vec<(String, usize)>,
+}
+
+impl ArchitectureFacts {
+    /// Gather facts from the tree at `HEAD` and recent history
+    pub fn gather(repo: &GitRepo) -> Result<Self> {
+        let root = repo.repo_path();
+        let files = repo.get_all_tracked_files()?;
+        let mut activity = Activity::from_git_log(&recent_log(root, HOT_FILE_WINDOW_DAYS)?);
+        let tracked: HashSet<&String> = files.iter().collect();
+        activity.files.retain(|path, _| tracked.contains(path));
+
+        Ok(Self {
+            project: root
+                .file_name()
+                .map_or_else(|| "repository".to_string(), |n| n.to_string_lossy().into()),
+            commit: repo.head_commit_id()?,
+            tracked_files: files.len(),
+            modules: summarize_modules(&files),
+            entry_points: entry_points(&files),
+            commands: detect_commands(root, &files),
+            hot_files: activity.hot_spots(MAX_HOT_FILES),
+        })
+    }
+
+    /// Markdown overview, led by Iris's explanation when there is one
+    pub fn to_markdown(&self, explanation: Option<&str>) -> String {
+        let short = self.commit.get(..7).unwrap_or(&self.commit);
+        let mut out = format!(
+            "# Architecture: {}\n\nAt commit `{short}`, {} tracked files.\n\n",
+            self.project, self.tracked_files
+        );
+        if let Some(explanation) = explanation.map(str::trim).filter(|e| !e.is_empty()) {
+            out.push_str(&format!("## How It Fits Together\n\n{explanation}\n\n"));
+        }
+
+        out.push_str("## Main Modules\n\n");
+        for module in &self.modules {
+            let noun = if module.files == 1 { "file" } else { "files" };
+            out.push_str(&format!("- `{}` ({} {noun})\n", module.path, module.files));
+        }
+
+        out.push_str("\n## Entry Points\n\n");
+        if self.entry_points.is_empty() {
+            out.push_str("No conventional entry points found.\n");
+        }
+        for entry in &self.entry_points {
+            out.push_str(&format!("- `{entry}`\n"));
+        }
+
+        out.push_str("\n## Build and Test\n\n");
+        if self.commands.is_empty() {
+            out.push_str("No build or test commands found in the usual manifests.\n");
+        } else {
+            out.push_str("| Purpose | Command | From |\n| --- | --- | --- |\n");
+            for cmd in &self.commands {
+                out.push_str(&format!(
+                    "| {} | `{}` | `{}` |\n",
+                    cmd.purpose, cmd.command, cmd.source
+                ));
+            }
+        }
+
+        out.push_str(&format!(
+            "\n## Hot Files (last {HOT_FILE_WINDOW_DAYS} days)\n\n"
+        ));
+        if self.hot_files.is_empty() {
+            out.push_str("No commits in this window.\n");
+        }
+        for (path, commits) in &self.hot_files {
+            out.push_str(&format!("- `{path}`: {commits} commits\n"));
+        }
+        out
+    }
+
+    /// Task prompt handing the facts to Iris
+    pub fn to_prompt(&self) -> String {
+        format!(
+            "Explain the architecture of `{}`. These facts were computed from the repository \
+             and are shown to the reader after your explanation:\n\n{}",
+            self.project,
+            self.to_markdown(None)
+        )
+    }
+}
+
+/// Directories two levels deep by size, with root files counted together
+fn summarize_modules(files: &[String]) -> Vec<Module> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for file in files {
+        *counts.entry(directory_key(file)).or_default() += 1;
+    }
+    let mut modules: Vec<Module> = counts
+        .into_iter()
+        .map(|(path, files)| Module { path, files })
+        .collect();
+    modules.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.path.cmp(&b.path)));
+    modules.truncate(MAX_MODULES);
+    modules
+}
+
+/// Overviews cached per commit
+#[derive(Debug, Clone)]
+pub struct OverviewCache {
+    dir: PathBuf,
+}
+
+impl OverviewCache {
+    /// Cache under the repository's git directory, shared by its worktrees
+    pub fn for_repo(repo: &GitRepo) -> Result<Self> {
+        let git = repo.open_repo()?;
+        Ok(Self::at(git.commondir().join("iris").join("overview")))
+    }
+
+    pub fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// File holding the overview of `commit`
+    pub fn path(&self, commit: &str) -> PathBuf {
+        self.dir.join(format!("{commit}.md"))
+    }
+
+    /// Cached overview of `commit`, if there is one
+    pub fn load(&self, commit: &str) -> Option<String> {
+        fs::read_to_string(self.path(commit)).ok()
+    }
+
+    /// Save the overview of `commit`, dropping the oldest beyond
+    /// [`MAX_CACHED`], and return where it was written
+    pub fn store(&self, commit: &str, markdown: &str) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.path(commit);
+        fs::write(&path, markdown)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        prune(&self.dir, MAX_CACHED, &path)?;
+        Ok(path)
+    }
+}
+
+/// Remove all but the `keep` most recently written files in `dir`, never
+/// `current`
+fn prune(dir: &Path, keep: usize, current: &Path) -> Result<()> {
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect();
+    // Sort the file just written first; timestamps can tie
+    files.sort_by_key(|(modified, path)| (path != current, std::cmp::Reverse(*modified)));
+    for (_, path) in files.iter().skip(keep) {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Overview of `HEAD` from the cache, or written by Iris with `explain` and
+/// cached. Returns the markdown and the file it is kept in.
+pub async fn cached_overview<F, Fut>(
+    repo: &GitRepo,
+    refresh: bool,
+    explain: F,
+) -> Result<(String, PathBuf)>
+where
+    F: FnOnce(String) -> Fut,
+    Fut: std::future::Future<Output = Result<String>>,
+{
+    let cache = OverviewCache::for_repo(repo)?;
+    let commit = repo.head_commit_id()?;
+    if !refresh && let Some(markdown) = cache.load(&commit) {
+        return Ok((markdown, cache.path(&commit)));
+    }
+
+    let facts = ArchitectureFacts::gather(repo)?;
+    let explanation = explain(facts.to_prompt()).await?;
+    let markdown = facts.to_markdown(Some(&explanation));
+    let path = cache.store(&facts.commit, &markdown)?;
+    Ok((markdown, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modules_and_cache() {
+        let files: Vec<String> = [
+            "Cargo.toml",
+            "src/main.rs",
+            "src/agents/iris.rs",
+            "src/agents/tools/git.rs",
+            "src/studio/app/mod.rs",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        let modules = summarize_modules(&files);
+        assert_eq!(
+            modules[0],
+            Module {
+                path: "src/agents".to_string(),
+                files: 2
+            }
+        );
+        assert!(modules.iter().any(|m| m.path == "(root)"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = OverviewCache::at(dir.path().join("overview"));
+        assert!(cache.load("abc123").is_none());
+        let path = cache.store("abc123", "# Architecture\n").unwrap();
+        assert_eq!(path, cache.path("abc123"));
+        assert_eq!(cache.load("abc123").as_deref(), Some("# Architecture\n"));
+
+        for i in 0..MAX_CACHED + 2 {
+            cache.store(&format!("c{i}"), "x").unwrap();
+        }
+        assert_eq!(
+            fs::read_dir(cache.dir.as_path()).unwrap().count(),
+            MAX_CACHED
+        );
+    }
+}
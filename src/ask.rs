@@ -0,0 +1,128 @@
+//! Questions about a diff, answered in the terminal
+//!
+//! `git-iris ask "<question>"` runs the chat capability outside Studio: the
+//! staged changes or a commit range go along with the question, the answer
+//! streams to stdout, and the sources it cites are checked against the
+//! repository afterwards. It works anywhere a terminal does, including over
+//! SSH and from editors or scripts that can't host the TUI.
+
+use anyhow::{Result, bail};
+
+use crate::doc_context::truncate_to_tokens;
+use crate::git::GitRepo;
+
+/// Tokens of diff sent with the question; Iris reads the rest with its tools
+const DIFF_BUDGET: usize = 12_000;
+
+/// The changes a question is about
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AskScope {
+    /// Changes staged for the next commit
+    Staged,
+    /// Changes between two commits
+    Range { from: String, to: String },
+}
+
+impl AskScope {
+    /// Parse a `--range` argument: `A..B`, or `A` for `A..HEAD`
+    pub fn parse_range(range: &str) -> Result<Self> {
+        if range.contains("...") {
+            bail!("Three-dot ranges aren't supported; use A..B");
+        }
+        let (from, to) = range.split_once("..").unwrap_or((range, ""));
+        if from.is_empty() {
+            bail!("The range needs a starting commit, as in main..HEAD");
+        }
+        Ok(Self::Range {
+            from: from.to_string(),
+            to: if to.is_empty() { "HEAD" } else { to }.to_string(),
+        })
+    }
+
+    /// The unified diff of the changes
+    pub fn diff(&self, repo: &GitRepo) -> Result<String> {
+        match self {
+            Self::Staged => {
+                if repo.is_remote() {
+                    bail!("A remote repository has no staged changes; pass --range");
+                }
+                repo.get_staged_diff_full()
+            }
+            Self::Range { from, to } => repo.get_ref_diff_full(from, to),
+        }
+    }
+}
+
+impl std::fmt::Display for AskScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Staged => f.write_str("the staged changes"),
+            Self::Range { from, to } => write!(f, "{from}..{to}"),
+        }
+    }
+}
+
+/// Prompt for the chat capability: the question and the diff it's about
+pub fn prompt(question: &str, scope: &AskScope, diff: &str) -> String {
+    let (diff, _, truncated) = truncate_to_tokens(diff, DIFF_BUDGET);
+    let note = if truncated {
+        "\nThe diff was cut short; use `git_diff` or `file_read` for the rest.\n"
+    } else {
+        ""
+    };
+    let range_hint = match scope {
+        AskScope::Staged => String::new(),
+        AskScope::Range { from, to } => {
+            format!("Pass `from=\"{from}\"` and `to=\"{to}\"` to `git_diff` for these changes.\n")
+        }
+    };
+    format!(
+        "The developer is asking from the terminal, outside Studio, about {scope}. \
+         There is no conversation history and no content to update; answer the question \
+         in markdown, citing your sources.\n{range_hint}\n\
+         ## Diff\n```diff\n{}\n```\n{note}\n## Question\n{}\n",
+        diff.trim_end(),
+        question.trim()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(
+            AskScope::parse_range("main..feature").expect("range"),
+            AskScope::Range {
+                from: "main".to_string(),
+                to: "feature".to_string()
+            }
+        );
+        assert_eq!(
+            AskScope::parse_range("v1.2.0").expect("range").to_string(),
+            "v1.2.0..HEAD"
+        );
+        assert_eq!(
+            AskScope::parse_range("HEAD~3..")
+                .expect("range")
+                .to_string(),
+            "HEAD~3..HEAD"
+        );
+        assert!(AskScope::parse_range("..HEAD").is_err());
+        assert!(AskScope::parse_range("main...HEAD").is_err());
+    }
+
+    #[test]
+    fn test_prompt_includes_question_and_diff() {
+        let prompt = prompt(
+            " Why is the retry capped? ",
+            &AskScope::Staged,
+            "+const MAX_RETRIES: u32 = 3;\n",
+        );
+        assert!(prompt.contains("about the staged changes"));
+        assert!(prompt.contains("+const MAX_RETRIES"));
+        assert!(prompt.ends_with("## Question\nWhy is the retry capped?\n"));
+        assert!(!prompt.contains("cut short"));
+    }
+}
@@ -0,0 +1,413 @@
+//! Model benchmarks
+//!
+//! `git-iris bench` runs a fixed set of tasks against two or more models and
+//! reports latency, token usage and estimated cost, with the outputs side by
+//! side. Each fixture carries its whole diff in the prompt and tools are off,
+//! so every model sees the same input whatever repository the command runs
+//! in.
+
+use anyhow::{Context, Result, bail};
+use std::fmt;
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
+
+use crate::agents::IrisAgentBuilder;
+use crate::config::Config;
+use crate::providers::Provider;
+use crate::usage::{LedgerEntry, compact};
+
+/// Narrowest column worth printing outputs side by side in
+const MIN_COLUMN_WIDTH: usize = 24;
+
+/// Column separator for side-by-side outputs
+const SEPARATOR: &str = " │ ";
+
+/// A task every model runs with the same prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixture {
+    pub name: &'static str,
+    /// Capability whose prompt and output format the task uses
+    pub capability: &'static str,
+    pub prompt: &'static str,
+}
+
+/// The built-in fixtures, in the order they run
+pub const FIXTURES: &[Fixture] = &[
+    Fixture {
+        name: "commit",
+        capability: "commit",
+        prompt: COMMIT_FIXTURE,
+    },
+    Fixture {
+        name: "review",
+        capability: "review",
+        prompt: REVIEW_FIXTURE,
+    },
+];
+
+const COMMIT_FIXTURE: &str = r#"Benchmark task: write a commit message for these staged changes. The complete diff is below; there is no repository to inspect, so don't call tools.
+
+Branch: feature/retry-backoff
+
+```diff
+diff --git a/src/net/client.rs b/src/net/client.rs
+index 3b1c2e0..8f4d9a1 100644
+--- a/src/net/client.rs
++++ b/src/net/client.rs
+@@ -12,18 +12,31 @@ use crate::net::Request;
+ pub struct Client {
+     http: reqwest::Client,
+-    retries: u32,
++    retry: RetryPolicy,
+ }
+
++/// How failed requests are retried
++pub struct RetryPolicy {
++    pub attempts: u32,
++    /// Delay before the first retry; doubles after each one
++    pub base_delay: Duration,
++}
++
+ impl Client {
+     pub async fn send(&self, request: Request) -> Result<Response> {
+-        let mut last_error = None;
+-        for _ in 0..=self.retries {
++        let mut delay = self.retry.base_delay;
++        let mut attempt = 0;
++        loop {
+             match self.http.execute(request.build()?).await {
+                 Ok(response) => return Ok(response),
+-                Err(e) => last_error = Some(e),
++                Err(e) if attempt < self.retry.attempts && is_transient(&e) => {
++                    tokio::time::sleep(delay).await;
++                    delay *= 2;
++                    attempt += 1;
++                }
++                Err(e) => return Err(e.into()),
+             }
+         }
+-        Err(last_error.expect("at least one attempt").into())
+     }
+ }
++
++fn is_transient(error: &reqwest::Error) -> bool {
++    error.is_timeout() || error.is_connect()
++}
+```"#;
+
+const REVIEW_FIXTURE: &str = r#"Benchmark task: review these changes briefly, listing only the most important issues. The complete diff is below; there is no repository to inspect, so don't call tools.
+
+```diff
+diff --git a/src/api/users.rs b/src/api/users.rs
+index 51a0c3d..c29e7b4 100644
+--- a/src/api/users.rs
++++ b/src/api/users.rs
+@@ -40,6 +40,24 @@ impl UserStore {
+         Ok(rows.into_iter().map(User::from).collect())
+     }
+
++    /// Find users whose name starts with `prefix`
++    pub async fn search(&self, prefix: &str, page: usize) -> Result<Vec<User>> {
++        let sql = format!(
++            "SELECT * FROM users WHERE name LIKE '{prefix}%' LIMIT 50 OFFSET {}",
++            page * 50
++        );
++        let rows = sqlx::query(&sql).fetch_all(&self.pool).await?;
++        let mut users = Vec::new();
++        for row in rows {
++            users.push(User::from(row));
++        }
++        Ok(users)
++    }
++
++    pub async fn delete(&self, id: &str) -> Result<()> {
++        let id: i64 = id.parse().unwrap();
++        sqlx::query("DELETE FROM users WHERE id = ?").bind(id).execute(&self.pool).await?;
++        Ok(())
++    }
+ }
+```"#;
+
+/// Fixtures named in `names`, or all of them when `names` is empty
+pub fn fixtures(names: &[String]) -> Result<Vec<Fixture>> {
+    if names.is_empty() {
+        return Ok(FIXTURES.to_vec());
+    }
+    names
+        .iter()
+        .map(|name| {
+            FIXTURES
+                .iter()
+                .find(|fixture| fixture.name == name)
+                .copied()
+                .with_context(|| {
+                    let known: Vec<&str> = FIXTURES.iter().map(|f| f.name).collect();
+                    format!("Unknown task '{name}'. Tasks: {}", known.join(", "))
+                })
+        })
+        .collect()
+}
+
+/// A model to benchmark
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchTarget {
+    pub provider: Provider,
+    pub model: String,
+    pub fast_model: String,
+}
+
+impl BenchTarget {
+    /// Parse `provider` or `provider:model`
+    ///
+    /// A bare provider uses its configured model. The model may contain
+    /// colons itself, like Ollama's `qwen2.5-coder:7b`.
+    pub fn parse(spec: &str, config: &Config) -> Result<Self> {
+        let (name, model) = spec
+            .split_once(':')
+            .map_or((spec, None), |(name, model)| (name, Some(model)));
+        let provider: Provider = name.trim().parse()?;
+        let provider_config = config
+            .get_provider_config(provider.name())
+            .cloned()
+            .unwrap_or_default();
+        let model = model
+            .map(str::trim)
+            .filter(|model| !model.is_empty())
+            .unwrap_or_else(|| provider_config.effective_model(provider));
+        Ok(Self {
+            provider,
+            model: model.to_string(),
+            fast_model: provider_config.effective_fast_model(provider).to_string(),
+        })
+    }
+
+    /// The default provider and the configured fallbacks that have an API key
+    pub fn defaults(config: &Config) -> Vec<Self> {
+        let mut targets: Vec<Self> = Vec::new();
+        let names = std::iter::once(&config.default_provider).chain(&config.fallback_providers);
+        for name in names {
+            let Ok(target) = Self::parse(name, config) else {
+                continue;
+            };
+            if !config.lacks_api_key(target.provider)
+                && !targets.iter().any(|t| t.provider == target.provider)
+            {
+                targets.push(target);
+            }
+        }
+        targets
+    }
+}
+
+impl fmt::Display for BenchTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.provider, self.model)
+    }
+}
+
+/// One model's run of one fixture
+#[derive(Debug, Clone)]
+pub struct BenchRun {
+    pub target: BenchTarget,
+    pub fixture: &'static str,
+    pub latency: Duration,
+    /// Tokens and cost, when the provider reported usage
+    pub usage: Option<LedgerEntry>,
+    /// The generated output, or the error the call failed with
+    pub output: Result<String, String>,
+}
+
+/// Check that the targets can be benchmarked before spending any tokens
+pub fn check_targets(targets: &[BenchTarget], config: &Config) -> Result<()> {
+    if targets.len() < 2 {
+        bail!(
+            "Benchmarking needs at least two models; pass --model for each, like --model openai --model anthropic:claude-haiku-4-5-20251001"
+        );
+    }
+    if let Some(target) = targets.iter().find(|t| config.lacks_api_key(t.provider)) {
+        bail!(
+            "No API key for {} (set it in your config or {})",
+            target.provider,
+            target.provider.api_key_env()
+        );
+    }
+    Ok(())
+}
+
+/// Run one fixture on one model
+pub async fn run(config: &Config, target: &BenchTarget, fixture: &Fixture) -> BenchRun {
+    let started = Instant::now();
+    let mut usage = None;
+    let output = match IrisAgentBuilder::new()
+        .with_provider(target.provider.name())
+        .with_model(&target.model)
+        .build()
+    {
+        Ok(mut agent) => {
+            agent.set_config(config.clone());
+            agent.set_fast_model(target.fast_model.clone());
+            agent.set_tools_enabled(false);
+            let response = agent.execute_task(fixture.capability, fixture.prompt).await;
+            usage = agent.take_context_report().map(|report| {
+                LedgerEntry::from_report(&report, target.provider.name(), &config.pricing)
+            });
+            response.map(|response| response.to_string())
+        }
+        Err(e) => Err(e),
+    };
+    BenchRun {
+        target: target.clone(),
+        fixture: fixture.name,
+        latency: started.elapsed(),
+        usage,
+        output: output.map_err(|e| e.to_string()),
+    }
+}
+
+/// Latency, tokens and cost of every run as a markdown table
+pub fn summary_markdown(runs: &[BenchRun]) -> String {
+    let mut md = String::from(
+        "# Model Benchmark\n\n| Task | Model | Latency | Input | Output | Cost |\n| --- | --- | --- | --- | --- | --- |\n",
+    );
+    for run in runs {
+        let (input, output, cost) = run.usage.as_ref().map_or_else(
+            || ("–".to_string(), "–".to_string(), "–".to_string()),
+            |usage| {
+                (
+                    compact(usage.input_tokens),
+                    compact(usage.output_tokens),
+                    usage
+                        .cost_usd
+                        .map_or_else(|| "?".to_string(), |cost| format!("${cost:.4}")),
+                )
+            },
+        );
+        let latency = if run.output.is_ok() {
+            format!("{:.1}s", run.latency.as_secs_f64())
+        } else {
+            "failed".to_string()
+        };
+        md.push_str(&format!(
+            "| {} | {} | {latency} | {input} | {output} | {cost} |\n",
+            run.fixture, run.target
+        ));
+    }
+    md
+}
+
+/// Every run's output, one section per task and model
+pub fn outputs_markdown(runs: &[BenchRun]) -> String {
+    let mut md = String::new();
+    for run in runs {
+        md.push_str(&format!(
+            "\n## {} · {}\n\n{}\n",
+            run.fixture,
+            run.target,
+            output_text(run).trim_end()
+        ));
+    }
+    md
+}
+
+/// The output of a run, or its error
+pub fn output_text(run: &BenchRun) -> String {
+    match &run.output {
+        Ok(output) => output.clone(),
+        Err(e) => format!("Failed: {e}"),
+    }
+}
+
+/// Lay out titled texts as columns that fit in `width`
+///
+/// Returns `None` when the columns would be too narrow to read.
+pub fn side_by_side(columns: &[(String, String)], width: usize) -> Option<String> {
+    let count = columns.len().max(1);
+    let column_width = width.saturating_sub(SEPARATOR.width() * (count - 1)) / count;
+    if column_width < MIN_COLUMN_WIDTH {
+        return None;
+    }
+
+    let wrapped: Vec<Vec<String>> = columns
+        .iter()
+        .map(|(title, text)| {
+            let mut lines: Vec<String> = textwrap::wrap(title, column_width)
+                .into_iter()
+                .map(std::borrow::Cow::into_owned)
+                .collect();
+            lines.push("─".repeat(column_width));
+            for line in text.lines() {
+                if line.trim().is_empty() {
+                    lines.push(String::new());
+                } else {
+                    lines.extend(
+                        textwrap::wrap(line, column_width)
+                            .into_iter()
+                            .map(std::borrow::Cow::into_owned),
+                    );
+                }
+            }
+            lines
+        })
+        .collect();
+
+    let height = wrapped.iter().map(Vec::len).max().unwrap_or(0);
+    let mut out = String::new();
+    for row in 0..height {
+        let cells: Vec<String> = wrapped
+            .iter()
+            .map(|lines| {
+                let cell = lines.get(row).map_or("", String::as_str);
+                format!(
+                    "{cell}{}",
+                    " ".repeat(column_width.saturating_sub(cell.width()))
+                )
+            })
+            .collect();
+        out.push_str(cells.join(SEPARATOR).trim_end());
+        out.push('\n');
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_targets() {
+        let config = Config::default();
+        let target = BenchTarget::parse("ollama:qwen2.5-coder:7b", &config).expect("target");
+        assert_eq!(target.provider, Provider::Ollama);
+        assert_eq!(target.model, "qwen2.5-coder:7b");
+        assert_eq!(
+            BenchTarget::parse("claude", &config)
+                .expect("alias")
+                .to_string(),
+            "anthropic:claude-sonnet-4-5-20250929"
+        );
+        assert!(BenchTarget::parse("cohere:command", &config).is_err());
+
+        assert_eq!(fixtures(&[]).expect("all").len(), FIXTURES.len());
+        assert!(fixtures(&["haiku".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let columns = [
+            ("openai".to_string(), "feat: add retry\n\nBody".to_string()),
+            ("anthropic".to_string(), "✨ Add backoff".to_string()),
+        ];
+        let out = side_by_side(&columns, 2 * MIN_COLUMN_WIDTH + 3).expect("fits");
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines[0].trim_end(),
+            format!("openai{}│ anthropic", " ".repeat(19))
+        );
+        assert_eq!(
+            lines[2],
+            format!("feat: add retry{}│ ✨ Add backoff", " ".repeat(10))
+        );
+        assert_eq!(lines[4], format!("Body{}│", " ".repeat(21)));
+        assert!(side_by_side(&columns, 40).is_none());
+    }
+}
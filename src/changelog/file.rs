@@ -0,0 +1,293 @@
+//! Keep a Changelog files
+//!
+//! [`ChangelogFile`] splits a `CHANGELOG.md` into its introduction, one
+//! section per release and the link definitions at the end, so a generated
+//! release can be added without touching anything else. Generated entries
+//! for `[Unreleased]` are merged into the existing section group by group;
+//! a version is added below `[Unreleased]`, or replaces the section of the
+//! same version when the changelog is regenerated.
+
+/// Title and introduction for a new changelog
+pub const DEFAULT_HEADER: &str = "# Changelog\n\nAll notable changes to this project will be documented in this file.\n\nThe format is based on [Keep a Changelog](https://keepachangelog.com/en/1.0.0/),\nand this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).\n";
+
+/// Separator written after each generated release
+const SEPARATOR: &str = "<!-- -------------------------------------------------------------- -->";
+
+/// One `## ` section of a changelog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    /// The heading line, e.g. `## [1.2.0] - 2025-06-01`
+    pub heading: String,
+    /// Everything up to the next release, trimmed
+    pub body: String,
+}
+
+impl Release {
+    /// The version in the heading, without brackets: `1.2.0` or `Unreleased`
+    pub fn name(&self) -> &str {
+        let title = self.heading.trim_start_matches('#').trim();
+        match title.strip_prefix('[') {
+            Some(rest) => rest.split(']').next().unwrap_or(rest),
+            None => title.split_whitespace().next().unwrap_or(title),
+        }
+    }
+
+    pub fn is_unreleased(&self) -> bool {
+        self.name().eq_ignore_ascii_case("unreleased")
+    }
+
+    /// Add the entries of `other` to this release's `### ` groups
+    ///
+    /// Groups match by their words, so `### ✨ Added` and `### Added` are
+    /// the same group. Entries already listed are skipped, and groups this
+    /// release lacks are added at the end.
+    fn merge(&mut self, other: &Self) {
+        let (intro, mut groups) = split_groups(&self.body);
+        let (other_intro, other_groups) = split_groups(&other.body);
+        for (heading, entries) in other_groups {
+            match groups
+                .iter_mut()
+                .find(|(existing, _)| group_key(existing) == group_key(&heading))
+            {
+                Some((_, existing)) => {
+                    for entry in entries {
+                        if !existing.contains(&entry) {
+                            existing.push(entry);
+                        }
+                    }
+                }
+                None => groups.push((heading, entries)),
+            }
+        }
+
+        let mut parts = Vec::new();
+        let intro = if intro.is_empty() { other_intro } else { intro };
+        if !intro.is_empty() {
+            parts.push(intro);
+        }
+        for (heading, entries) in groups {
+            parts.push(format!("{heading}\n\n{}", entries.join("\n")));
+        }
+        self.body = parts.join("\n\n");
+    }
+}
+
+/// A changelog split into introduction, releases and link definitions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogFile {
+    /// Title and introduction before the first release
+    pub header: String,
+    /// Releases in file order, newest first
+    pub releases: Vec<Release>,
+    /// `[1.2.0]: https://…` link definitions after the last release
+    pub links: String,
+}
+
+impl ChangelogFile {
+    /// Split markdown into releases at every `## ` heading outside code blocks
+    pub fn parse(markdown: &str) -> Self {
+        let mut header = Vec::new();
+        let mut releases: Vec<(String, Vec<&str>)> = Vec::new();
+        let mut in_code_block = false;
+        for line in markdown.lines() {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+            }
+            if !in_code_block && line.starts_with("## ") {
+                releases.push((line.trim_end().to_string(), Vec::new()));
+            } else if let Some((_, body)) = releases.last_mut() {
+                body.push(line);
+            } else {
+                header.push(line);
+            }
+        }
+
+        // Link definitions trail the last release
+        let mut links = Vec::new();
+        if let Some((_, body)) = releases.last_mut() {
+            while let Some(line) = body.last() {
+                if line.trim().is_empty() || is_link_definition(line) {
+                    links.push(body.pop().unwrap_or_default());
+                } else {
+                    break;
+                }
+            }
+            links.reverse();
+        }
+
+        Self {
+            header: header.join("\n").trim_end().to_string(),
+            releases: releases
+                .into_iter()
+                .map(|(heading, body)| Release {
+                    heading,
+                    body: body.join("\n").trim().to_string(),
+                })
+                .collect(),
+            links: links.join("\n").trim().to_string(),
+        }
+    }
+
+    /// Add a generated release, keeping every other release
+    ///
+    /// `[Unreleased]` entries merge into the existing `[Unreleased]` section.
+    /// A version replaces the section of the same version if there is one,
+    /// and otherwise goes above the newest release, below `[Unreleased]`.
+    pub fn insert(&mut self, mut release: Release) {
+        if release.is_unreleased()
+            && let Some(existing) = self.releases.iter_mut().find(|r| r.is_unreleased())
+        {
+            existing.merge(&release);
+            return;
+        }
+
+        if !release.is_unreleased() {
+            release.body = format!("{}\n\n{SEPARATOR}", release.body);
+        }
+        if let Some(existing) = self
+            .releases
+            .iter_mut()
+            .find(|r| r.name() == release.name())
+        {
+            *existing = release;
+        } else {
+            let index = usize::from(self.releases.first().is_some_and(Release::is_unreleased));
+            self.releases.insert(index, release);
+        }
+    }
+
+    /// The changelog as markdown, with the default header if it had none
+    pub fn render(&self) -> String {
+        let mut parts = vec![
+            if self.header.is_empty() {
+                DEFAULT_HEADER.trim_end()
+            } else {
+                self.header.as_str()
+            }
+            .to_string(),
+        ];
+        for release in &self.releases {
+            if release.body.is_empty() {
+                parts.push(release.heading.clone());
+            } else {
+                parts.push(format!("{}\n\n{}", release.heading, release.body));
+            }
+        }
+        if !self.links.is_empty() {
+            parts.push(self.links.clone());
+        }
+        let mut out = parts.join("\n\n");
+        out.push('\n');
+        out
+    }
+}
+
+fn is_link_definition(line: &str) -> bool {
+    line.starts_with('[') && line.contains("]: ")
+}
+
+/// A release body split into its text before the first `### ` group, and
+/// each group's heading and non-blank lines
+fn split_groups(body: &str) -> (String, Vec<(String, Vec<String>)>) {
+    let mut intro = Vec::new();
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for line in body.lines() {
+        if line.starts_with("### ") {
+            groups.push((line.trim_end().to_string(), Vec::new()));
+        } else if let Some((_, entries)) = groups.last_mut() {
+            if !line.trim().is_empty() && line.trim() != SEPARATOR {
+                entries.push(line.trim_end().to_string());
+            }
+        } else {
+            intro.push(line);
+        }
+    }
+    (intro.join("\n").trim().to_string(), groups)
+}
+
+/// The words of a group heading, lowercased: `### ✨ Added` is `added`
+fn group_key(heading: &str) -> String {
+    heading
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The first release in `markdown`
+    fn release(markdown: &str) -> Release {
+        ChangelogFile::parse(markdown).releases.remove(0)
+    }
+
+    const EXISTING: &str = "# Changelog\n\nNotes for humans.\n\n\
+        ## [Unreleased]\n\n### ✨ Added\n\n- Dark mode\n\n\
+        ## [1.0.0] - 2025-01-01\n\n### Fixed\n\n- Crash on start\n\n```\n## not a release\n```\n\n\
+        [Unreleased]: https://example.com/compare/v1.0.0...HEAD\n\
+        [1.0.0]: https://example.com/releases/v1.0.0\n";
+
+    #[test]
+    fn test_parse_keeps_every_part() {
+        let file = ChangelogFile::parse(EXISTING);
+        assert_eq!(file.header, "# Changelog\n\nNotes for humans.");
+        let names: Vec<&str> = file.releases.iter().map(Release::name).collect();
+        assert_eq!(names, ["Unreleased", "1.0.0"]);
+        assert!(file.releases[1].body.ends_with("## not a release\n```"));
+        assert!(file.links.starts_with("[Unreleased]: "));
+        assert_eq!(file.render(), EXISTING);
+    }
+
+    #[test]
+    fn test_unreleased_entries_merge_by_group() {
+        let mut file = ChangelogFile::parse(EXISTING);
+        file.insert(release(
+            "## [Unreleased]\n\n### Added\n\n- Dark mode\n- Search\n\n### Fixed\n\n- Typo\n",
+        ));
+        assert_eq!(file.releases.len(), 2);
+        assert_eq!(
+            file.releases[0].body,
+            "### ✨ Added\n\n- Dark mode\n- Search\n\n### Fixed\n\n- Typo"
+        );
+    }
+
+    #[test]
+    fn test_versions_go_below_unreleased_and_replace_themselves() {
+        let mut file = ChangelogFile::parse(EXISTING);
+        file.insert(release(
+            "Intro the model wrote\n\n## [1.1.0] - 2025-02-01\n\n### Added\n\n- Search\n",
+        ));
+        let names: Vec<&str> = file.releases.iter().map(Release::name).collect();
+        assert_eq!(names, ["Unreleased", "1.1.0", "1.0.0"]);
+
+        file.insert(release(
+            "## [1.1.0] - 2025-02-02\n\n### Added\n\n- Search v2\n",
+        ));
+        assert_eq!(file.releases.len(), 3);
+        assert_eq!(file.releases[1].heading, "## [1.1.0] - 2025-02-02");
+        assert!(file.render().contains("- Search v2\n\n<!-- ---"));
+        assert!(
+            file.render()
+                .ends_with("[1.0.0]: https://example.com/releases/v1.0.0\n")
+        );
+    }
+
+    #[test]
+    fn test_new_file_gets_default_header() {
+        let mut file = ChangelogFile::parse("");
+        file.insert(release("## [Unreleased]\n\n### Added\n\n- Search\n"));
+        assert!(
+            file.render()
+                .starts_with("# Changelog\n\nAll notable changes")
+        );
+        assert!(
+            file.render()
+                .ends_with("## [Unreleased]\n\n### Added\n\n- Search\n")
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Changelog file utilities
+//!
+//! This module provides utilities for managing changelog files.
+//! The changelog/release notes *types* are in the `types` module.
+
+mod file;
+mod packages;
+
+pub use file::{ChangelogFile, DEFAULT_HEADER, Release};
+pub use packages::{Package, is_within, resolve_packages, workspace_packages};
+
+use crate::git::GitRepo;
+use crate::log_debug;
+use anyhow::{Context, Result};
+use regex;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Utilities for changelog file management
+pub struct ChangelogGenerator;
+
+impl ChangelogGenerator {
+    /// Updates a changelog file with new content
+    ///
+    /// This function reads the existing changelog file (if it exists) and adds
+    /// the generated release with [`ChangelogFile::insert`]: `[Unreleased]`
+    /// entries merge into the existing `[Unreleased]` section, and a version
+    /// goes above the previous releases. Everything else in the file is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `changelog_content` - The new changelog content to add
+    /// * `changelog_path` - Path to the changelog file
+    /// * `git_repo` - `GitRepo` instance to use for retrieving commit dates
+    /// * `to_ref` - The "to" Git reference (commit/tag) to extract the date from
+    /// * `version_name` - Optional custom version name to use instead of version from Git
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or an error
+    pub fn update_changelog_file(
+        changelog_content: &str,
+        changelog_path: &str,
+        git_repo: &Arc<GitRepo>,
+        to_ref: &str,
+        version_name: Option<String>,
+    ) -> Result<()> {
+        crate::read_only::ensure_writable("Writing the changelog")?;
+        let path = Path::new(changelog_path);
+        crate::outputs::ensure_parent_dir(path)?;
+
+        // Strip ANSI color codes
+        let stripped_content = strip_ansi_codes(changelog_content);
+
+        // Skip the separator line if it exists (the first line with "━━━" or similar)
+        let clean_content =
+            if stripped_content.starts_with("━") || stripped_content.starts_with('-') {
+                // Find the first newline and skip everything before it
+                if let Some(pos) = stripped_content.find('\n') {
+                    stripped_content[pos + 1..].to_string()
+                } else {
+                    stripped_content
+                }
+            } else {
+                stripped_content
+            };
+
+        let mut version_content = clean_content;
+
+        // If version_name is provided, override the existing version
+        if let Some(version) = version_name {
+            let re = regex::Regex::new(r"(?m)^## \[([^\]]+)\]").expect("Failed to compile regex");
+            if re.is_match(&version_content) {
+                version_content = re
+                    .replace(&version_content, &format!("## [{version}]"))
+                    .to_string();
+                log_debug!("Replaced version with user-provided version: {}", version);
+            } else {
+                log_debug!("Could not find version header to replace in changelog content");
+            }
+        }
+
+        // Date released versions; [Unreleased] has no date yet
+        let mut changelog = ChangelogFile::parse(&version_content);
+        if let Some(release) = changelog.releases.first_mut()
+            && !release.is_unreleased()
+        {
+            let commit_date = match git_repo.get_commit_date(to_ref) {
+                Ok(date) => {
+                    log_debug!("Got commit date for {}: {}", to_ref, date);
+                    date
+                }
+                Err(e) => {
+                    log_debug!("Failed to get commit date for {}: {}", to_ref, e);
+                    chrono::Local::now().format("%Y-%m-%d").to_string()
+                }
+            };
+            release.heading = dated_heading(&release.heading, &commit_date);
+        }
+        let release = changelog
+            .releases
+            .into_iter()
+            .next()
+            .context("The generated changelog has no `## ` release heading")?;
+
+        let mut changelog = if path.exists() {
+            let existing_content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read changelog file: {changelog_path}"))?;
+            ChangelogFile::parse(&existing_content)
+        } else {
+            ChangelogFile::parse("")
+        };
+        changelog.insert(release);
+
+        fs::write(path, changelog.render())
+            .with_context(|| format!("Failed to write to changelog file: {changelog_path}"))?;
+
+        Ok(())
+    }
+}
+
+/// A release heading with `date` after the version, unless it has a date
+fn dated_heading(heading: &str, date: &str) -> String {
+    let Some(bracket) = heading.find(']') else {
+        return heading.to_string();
+    };
+    let (version, rest) = heading.split_at(bracket + 1);
+    let rest = rest.trim();
+    match rest.strip_prefix('-').map(str::trim) {
+        Some(existing) if !existing.is_empty() => heading.to_string(),
+        _ if rest.is_empty() || rest == "-" => format!("{version} - {date}"),
+        _ => format!("{version} - {date} {rest}"),
+    }
+}
+
+/// Strips ANSI color/style codes from a string
+fn strip_ansi_codes(s: &str) -> String {
+    let re = regex::Regex::new(r"\x1B\[([0-9]{1,2}(;[0-9]{1,2})*)?[m|K]")
+        .expect("Failed to compile ANSI escape code regex");
+    re.replace_all(s, "").to_string()
+}
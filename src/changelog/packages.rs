@@ -0,0 +1,311 @@
+//! Workspace members for monorepo changelogs
+//!
+//! `--package` and `--all-packages` scope a changelog to members of a Cargo
+//! workspace (`[workspace] members` in the root `Cargo.toml`) or an npm,
+//! Yarn or pnpm workspace (`workspaces` in the root `package.json`).
+//! Member patterns may use `*` within a path component, like `crates/*`.
+
+use anyhow::{Result, bail};
+use std::fs;
+use std::path::Path;
+
+/// A directory a changelog can be scoped to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    /// The package name from its manifest, or the directory name
+    pub name: String,
+    /// Directory relative to the repository root, with `/` separators
+    pub path: String,
+}
+
+/// Whether `path` is `dir` or inside it
+pub fn is_within(path: &str, dir: &str) -> bool {
+    let dir = normalize(dir);
+    dir.is_empty()
+        || path == dir
+        || path
+            .strip_prefix(dir.as_str())
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Workspace members declared in the root `Cargo.toml` and `package.json`
+///
+/// Members are sorted by path. A manifest that is missing or declares no
+/// workspace contributes nothing.
+pub fn workspace_packages(root: &Path) -> Result<Vec<Package>> {
+    let mut packages = Vec::new();
+    if let Ok(manifest) = fs::read_to_string(root.join("Cargo.toml")) {
+        let manifest: toml::Value = toml::from_str(&manifest)?;
+        let workspace = manifest.get("workspace");
+        let patterns = |key: &str| -> Vec<String> {
+            workspace
+                .and_then(|w| w.get(key))
+                .and_then(toml::Value::as_array)
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|item| item.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let excluded: Vec<String> = patterns("exclude")
+            .iter()
+            .flat_map(|pattern| expand(root, pattern, "Cargo.toml"))
+            .collect();
+        for path in patterns("members")
+            .iter()
+            .flat_map(|pattern| expand(root, pattern, "Cargo.toml"))
+        {
+            if !excluded.contains(&path) {
+                let name = cargo_package_name(&root.join(&path));
+                packages.push(package(name, path));
+            }
+        }
+    }
+
+    if let Ok(manifest) = fs::read_to_string(root.join("package.json")) {
+        let manifest: serde_json::Value = serde_json::from_str(&manifest)?;
+        let workspaces = manifest.get("workspaces");
+        // Either a list of patterns or Yarn's `{ "packages": [...] }`
+        let patterns = workspaces
+            .and_then(|w| w.get("packages"))
+            .or(workspaces)
+            .and_then(serde_json::Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        for pattern in patterns.iter().filter_map(serde_json::Value::as_str) {
+            for path in expand(root, pattern, "package.json") {
+                let name = fs::read_to_string(root.join(&path).join("package.json"))
+                    .ok()
+                    .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+                    .and_then(|json| json.get("name")?.as_str().map(str::to_string));
+                packages.push(package(name, path));
+            }
+        }
+    }
+
+    packages.sort_by(|a, b| a.path.cmp(&b.path));
+    packages.dedup_by(|a, b| a.path == b.path);
+    Ok(packages)
+}
+
+/// Resolve `--path`, `--package` and `--all-packages` into the directories
+/// to generate changelogs for, in the order given
+///
+/// A path that is a workspace member takes the member's name. Returns an
+/// empty list when nothing was asked for, meaning the whole repository.
+pub fn resolve_packages(
+    root: &Path,
+    paths: &[String],
+    names: &[String],
+    all: bool,
+) -> Result<Vec<Package>> {
+    if paths.is_empty() && names.is_empty() && !all {
+        return Ok(Vec::new());
+    }
+    let members = workspace_packages(root)?;
+    let mut packages = Vec::new();
+
+    if all {
+        if members.is_empty() {
+            bail!("No workspace members found in Cargo.toml or package.json");
+        }
+        packages.extend(members.iter().cloned());
+    }
+    for name in names {
+        let Some(member) = members
+            .iter()
+            .find(|m| m.name == *name || m.path == normalize(name))
+        else {
+            let available: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+            if available.is_empty() {
+                bail!("Unknown package '{name}': no workspace members found");
+            }
+            bail!(
+                "Unknown package '{name}'. Workspace members: {}",
+                available.join(", ")
+            );
+        };
+        packages.push(member.clone());
+    }
+    for path in paths {
+        let path = normalize(path);
+        if !root.join(&path).is_dir() {
+            bail!("'{path}' is not a directory in the repository");
+        }
+        let member = members.iter().find(|m| m.path == path);
+        packages.push(member.cloned().unwrap_or_else(|| package(None, path)));
+    }
+
+    let mut seen = Vec::new();
+    packages.retain(|p| {
+        let new = !seen.contains(&p.path);
+        seen.push(p.path.clone());
+        new
+    });
+    Ok(packages)
+}
+
+fn package(name: Option<String>, path: String) -> Package {
+    let name = name.unwrap_or_else(|| path.rsplit('/').next().unwrap_or(&path).to_string());
+    Package { name, path }
+}
+
+fn cargo_package_name(dir: &Path) -> Option<String> {
+    let manifest: toml::Value =
+        toml::from_str(&fs::read_to_string(dir.join("Cargo.toml")).ok()?).ok()?;
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn normalize(path: &str) -> String {
+    path.replace('\\', "/")
+        .trim_start_matches("./")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Directories matching a member pattern that contain `manifest`
+fn expand(root: &Path, pattern: &str, manifest: &str) -> Vec<String> {
+    let mut found = vec![String::new()];
+    for component in normalize(pattern).split('/').filter(|c| !c.is_empty()) {
+        found = found
+            .into_iter()
+            .flat_map(|dir| {
+                if !component.contains('*') {
+                    return vec![join(&dir, component)];
+                }
+                let Ok(entries) = fs::read_dir(root.join(&dir)) else {
+                    return Vec::new();
+                };
+                let mut matches: Vec<String> = entries
+                    .filter_map(Result::ok)
+                    .filter(|entry| entry.path().is_dir())
+                    .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+                    .filter(|name| !name.starts_with('.') && wildcard_match(component, name))
+                    .map(|name| join(&dir, &name))
+                    .collect();
+                matches.sort();
+                matches
+            })
+            .collect();
+    }
+    found.retain(|dir| !dir.is_empty() && root.join(dir).join(manifest).is_file());
+    found
+}
+
+fn join(dir: &str, name: &str) -> String {
+    if dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{dir}/{name}")
+    }
+}
+
+/// Match `name` against a pattern where `*` stands for any run of characters
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        if i == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(path, content).expect("write file");
+    }
+
+    #[test]
+    fn test_workspace_members_from_cargo_and_npm() {
+        let dir = TempDir::new().expect("temp dir");
+        let root = dir.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\", \"tools/cli\"]\nexclude = [\"crates/scratch\"]\n",
+        );
+        write(
+            root,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"acme-core\"\n",
+        );
+        write(
+            root,
+            "crates/scratch/Cargo.toml",
+            "[package]\nname = \"scratch\"\n",
+        );
+        write(root, "crates/notes/README.md", "not a crate");
+        write(root, "tools/cli/Cargo.toml", "[package]\nname = \"acme\"\n");
+        write(
+            root,
+            "package.json",
+            r#"{ "workspaces": { "packages": ["web/*"] } }"#,
+        );
+        write(root, "web/app/package.json", r#"{ "name": "@acme/app" }"#);
+
+        let packages = workspace_packages(root).expect("packages");
+        let found: Vec<(&str, &str)> = packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.path.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("acme-core", "crates/core"),
+                ("acme", "tools/cli"),
+                ("@acme/app", "web/app")
+            ]
+        );
+
+        let scoped = resolve_packages(
+            root,
+            &["./crates/notes/".to_string(), "tools/cli".to_string()],
+            &["acme".to_string()],
+            false,
+        )
+        .expect("resolve");
+        let names: Vec<&str> = scoped.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["acme", "notes"]);
+        assert!(resolve_packages(root, &[], &["nope".to_string()], false).is_err());
+        assert_eq!(
+            resolve_packages(root, &[], &[], true).expect("all").len(),
+            3
+        );
+        assert!(
+            resolve_packages(root, &[], &[], false)
+                .expect("none")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_is_within() {
+        assert!(is_within("crates/foo/src/lib.rs", "crates/foo/"));
+        assert!(is_within("crates/foo", "./crates/foo"));
+        assert!(!is_within("crates/foobar/src/lib.rs", "crates/foo"));
+        assert!(wildcard_match("*-plugin", "git-plugin"));
+        assert!(!wildcard_match("app-*", "lib-app"));
+    }
+}
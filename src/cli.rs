@@ -1,14 +1,21 @@
 use crate::commands;
 use crate::common::CommonParams;
+use crate::companion::{HistoryKind, ReportFormat};
+use crate::git::GitLocation;
+use crate::instruction_presets::PresetType;
 use crate::log_debug;
+use crate::output::{OutputFormat, is_json_output};
+use crate::outputs::Artifact;
 use crate::providers::Provider;
 use crate::theme;
+use crate::types::{ReviewFormat, Severity};
 use crate::ui;
 use clap::builder::{Styles, styling::AnsiColor};
-use clap::{CommandFactory, Parser, Subcommand, crate_version};
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, crate_version};
 use clap_complete::{Shell, generate};
 use colored::Colorize;
 use std::io;
+use std::path::PathBuf;
 
 /// Default log file path for debug output
 pub const LOG_FILE: &str = "git-iris-debug.log";
@@ -74,6 +81,24 @@ pub struct Cli {
     )]
     pub repository_url: Option<String>,
 
+    /// Path to the repository, like `git --git-dir`
+    #[arg(
+        long = "git-dir",
+        global = true,
+        value_name = "DIR",
+        help = "Path to the repository, instead of searching from the current directory"
+    )]
+    pub git_dir: Option<PathBuf>,
+
+    /// Path to the working tree, like `git --work-tree`
+    #[arg(
+        long = "work-tree",
+        global = true,
+        value_name = "DIR",
+        help = "Path to the working tree to use with the repository"
+    )]
+    pub work_tree: Option<PathBuf>,
+
     /// Enable debug mode for detailed agent observability
     #[arg(
         long = "debug",
@@ -89,6 +114,33 @@ pub struct Cli {
         help = "Override theme for this session (use 'git-iris themes' to list available)"
     )]
     pub theme: Option<String>,
+
+    /// Print the command's result as text or JSON
+    #[arg(
+        long = "output",
+        global = true,
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t = OutputFormat::Text,
+        help = "Result format: text, or json on stdout with messages on stderr (gen, review, pr, changelog, release-notes)"
+    )]
+    pub output: OutputFormat,
+
+    /// Refuse every operation that changes the repository, files or GitHub
+    #[arg(
+        long = "read-only",
+        global = true,
+        help = "Disable staging, commits, file writes and other changes; generation and analysis still work"
+    )]
+    pub read_only: bool,
+
+    /// Read every changed file in full, ignoring `[context.limits]`
+    #[arg(
+        long = "no-limits",
+        global = true,
+        help = "Ignore repository size limits and read every diff in full, however large"
+    )]
+    pub no_limits: bool,
 }
 
 /// Enumeration of available subcommands
@@ -166,6 +218,49 @@ pub enum Commands {
             help = "Target branch for comparison (e.g., 'feature-branch', 'pr-branch'). Used with --from for branch comparison reviews"
         )]
         to: Option<String>,
+
+        /// Review a pull (merge) request of origin by number
+        #[arg(
+            long,
+            value_name = "NUMBER",
+            conflicts_with_all = ["commit", "from", "to", "include_unstaged"],
+            help = "Review pull request NUMBER of origin (GitHub, or a merge request on GitLab): fetches its head and reviews it against the merge base with its base branch"
+        )]
+        pr: Option<u64>,
+
+        /// Output format
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = ReviewFormat::Markdown,
+            help = "Output format: markdown, lsp-diagnostics for a JSON map of file to LSP diagnostics, github-annotations for GitHub Actions workflow commands, or sarif for code scanning"
+        )]
+        format: ReviewFormat,
+
+        /// Review a range non-interactively and fail on serious findings
+        #[arg(
+            long,
+            help = "Review --from..--to (or --commit) without interaction, print the findings, and exit with code 2 when any reach --fail-on"
+        )]
+        ci: bool,
+
+        /// Lowest severity that fails a --ci review
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = Severity::High,
+            value_name = "SEVERITY",
+            requires = "ci",
+            help = "Lowest severity that fails a --ci review: low, medium, high or critical"
+        )]
+        fail_on: Severity,
+
+        /// Save the review to the file set by [outputs]
+        #[arg(
+            long,
+            help = "Save the review to the [outputs] review path (defaults to .reviews/{branch}.md)"
+        )]
+        write: bool,
     },
 
     /// Generate a pull request description
@@ -210,6 +305,13 @@ pub enum Commands {
             help = "Target branch, commit, or commitish for comparison. For single commit analysis, specify just this parameter with a commit hash or commitish (e.g., --to HEAD~2)"
         )]
         to: Option<String>,
+
+        /// Suggest splitting a large branch into several PRs
+        #[arg(
+            long,
+            help = "Suggest splitting a large branch into several PRs grouped by code owner and directory, with a description for each"
+        )]
+        split: bool,
     },
 
     /// Generate a changelog
@@ -221,6 +323,9 @@ pub enum Commands {
         #[command(flatten)]
         common: CommonParams,
 
+        #[command(flatten)]
+        scope: PackageScope,
+
         /// Starting Git reference (commit hash, tag, or branch name)
         #[arg(long, required = true)]
         from: String,
@@ -244,6 +349,14 @@ pub enum Commands {
         /// Explicit version name to use in the changelog instead of getting it from Git
         #[arg(long, help = "Explicit version name to use in the changelog")]
         version_name: Option<String>,
+
+        /// Update the file set by [outputs] instead of --file
+        #[arg(
+            long,
+            conflicts_with = "file",
+            help = "Update the [outputs] changelog path (defaults to CHANGELOG.md)"
+        )]
+        write: bool,
     },
 
     /// Generate release notes
@@ -255,6 +368,9 @@ pub enum Commands {
         #[command(flatten)]
         common: CommonParams,
 
+        #[command(flatten)]
+        scope: PackageScope,
+
         /// Starting Git reference (commit hash, tag, or branch name)
         #[arg(long, required = true)]
         from: String,
@@ -281,6 +397,304 @@ pub enum Commands {
         /// Explicit version name to use in the release notes instead of getting it from Git
         #[arg(long, help = "Explicit version name to use in the release notes")]
         version_name: Option<String>,
+
+        /// Recommend the next semantic version from the commits in the range
+        #[arg(
+            long,
+            help = "Recommend the next semantic version (major/minor/patch) with a justification"
+        )]
+        suggest_version: bool,
+
+        /// Add an upgrade guide when the range has breaking changes
+        #[arg(
+            long,
+            help = "When breaking changes are detected, end the notes with an Upgrade Guide section with before/after snippets from the diffs"
+        )]
+        upgrade_guide: bool,
+
+        /// Also write the upgrade guide to docs/upgrading/<version>.md
+        #[arg(
+            long,
+            help = "Also write the upgrade guide to docs/upgrading/<version>.md (implies --upgrade-guide)"
+        )]
+        write_upgrade_guide: bool,
+
+        /// Save the release notes to the file set by [outputs]
+        #[arg(
+            long,
+            help = "Save the notes to the [outputs] release notes path (defaults to docs/releases/{version}.md)"
+        )]
+        write: bool,
+    },
+
+    /// Check whether a range is ready to release
+    #[command(
+        about = "Check release readiness",
+        long_about = "Build a release checklist for the commits since the last tag: migrations, docs, changelog, version bump and breaking-change call-outs are checked from git data, then Iris adds commentary."
+    )]
+    ReleaseCheck {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Starting Git reference. Defaults to the most recent tag.
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Ending Git reference. Defaults to HEAD if not specified.
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Output raw markdown without any console formatting
+        #[arg(long, help = "Output raw markdown without any console formatting")]
+        raw: bool,
+
+        /// Skip AI commentary and only print the checklist
+        #[arg(long, help = "Only print the checklist, without AI commentary")]
+        no_ai: bool,
+
+        /// Exit with an error if any check fails
+        #[arg(long, help = "Exit with an error if any check fails (for CI)")]
+        strict: bool,
+    },
+
+    /// Find and restore lost commits from the reflog
+    #[command(
+        about = "Recover lost commits and branches from the reflog",
+        long_about = "List recent HEAD reflog entries with a summary of what each state contained, then restore one by creating a branch at it or resetting the current branch to it. Every restore shows the equivalent git command and asks for confirmation."
+    )]
+    Recover {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Number of reflog entries to list
+        #[arg(short = 'n', long, default_value_t = 20)]
+        count: usize,
+
+        /// Reflog entry to restore (the N in HEAD@{N})
+        #[arg(long, value_name = "N")]
+        restore: Option<usize>,
+
+        /// Name of the branch to create at the restored state
+        #[arg(long, requires = "restore", conflicts_with = "reset")]
+        branch: Option<String>,
+
+        /// Hard-reset the current branch to the restored state instead of creating a branch
+        #[arg(long, requires = "restore")]
+        reset: bool,
+
+        /// List entries without AI summaries
+        #[arg(long, help = "List entries without AI summaries")]
+        no_ai: bool,
+
+        /// Restore without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Recap where you left off on the current branch
+    #[command(
+        about = "Recap what you were doing on the current branch",
+        long_about = "Combine what Studio's companion remembers about the branch (recently touched files, notes, last focus, message drafts), recent HEAD reflog entries and the uncommitted diff into a short narrative from Iris of where work stopped and what is left."
+    )]
+    Recap {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Number of reflog entries to include
+        #[arg(short = 'n', long, default_value_t = crate::recap::DEFAULT_REFLOG_ENTRIES)]
+        reflog: usize,
+
+        /// Output raw markdown without any console formatting
+        #[arg(long, help = "Output raw markdown without any console formatting")]
+        raw: bool,
+
+        /// Print the gathered facts without asking Iris
+        #[arg(long, help = "Print the gathered facts without an AI recap")]
+        no_ai: bool,
+    },
+
+    /// Generate a newcomer onboarding report
+    #[command(
+        about = "Generate an onboarding report for newcomers",
+        long_about = "Build a newcomer-oriented report: project structure, build and test commands, hot areas of churn, key contributors per subsystem and suggested first files to read, led by an overview from Iris. Print it as markdown, write it to a file, or open it in Studio's Explore mode."
+    )]
+    Onboard {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Days of history used for churn and contributors
+        #[arg(long, default_value_t = 90)]
+        days: u32,
+
+        /// Output raw markdown without any console formatting
+        #[arg(long, help = "Output raw markdown without any console formatting")]
+        raw: bool,
+
+        /// Skip the AI overview and only print the computed sections
+        #[arg(
+            long,
+            help = "Only print the computed sections, without an AI overview"
+        )]
+        no_ai: bool,
+
+        /// Write the report to a markdown file
+        #[arg(
+            short = 'o',
+            long,
+            value_name = "FILE",
+            help = "Write the report to a file"
+        )]
+        file: Option<String>,
+
+        /// Open the report in Studio's Explore mode
+        #[arg(long, conflicts_with = "raw")]
+        studio: bool,
+    },
+
+    /// Explain the repository's architecture
+    #[command(
+        about = "Explain how the repository's architecture fits together",
+        long_about = "Describe the repository's main modules, entry points, build and test commands and hot files by churn, with an explanation from Iris of how they fit together. The overview is cached per commit and reused until HEAD moves."
+    )]
+    ExplainRepo {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Output raw markdown without any console formatting
+        #[arg(long, help = "Output raw markdown without any console formatting")]
+        raw: bool,
+
+        /// Skip Iris's explanation and only print the computed sections
+        #[arg(
+            long,
+            help = "Only print the computed sections, without an AI explanation"
+        )]
+        no_ai: bool,
+
+        /// Regenerate the overview even if one is cached for this commit
+        #[arg(long, conflicts_with = "no_ai")]
+        refresh: bool,
+
+        /// Write the overview to a markdown file
+        #[arg(
+            short = 'o',
+            long,
+            value_name = "FILE",
+            help = "Write the overview to a file"
+        )]
+        file: Option<String>,
+
+        /// Open the overview in Studio's Explore mode
+        #[arg(long, conflicts_with_all = ["raw", "file"])]
+        studio: bool,
+    },
+
+    /// Draft an architecture decision record from a branch or commit range
+    #[command(
+        about = "Draft an architecture decision record (ADR) from a commit range",
+        long_about = "Draft an ADR with context, decision and consequences from the commits in a range and a short description of the decision. The record is written to docs/adr/NNNN-title.md, numbered after the highest existing record."
+    )]
+    Adr {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Starting branch, tag, or commit
+        #[arg(long, default_value = "main")]
+        from: String,
+
+        /// Ending branch, tag, or commit
+        #[arg(long, default_value = "HEAD")]
+        to: String,
+
+        /// Title of the record (default: chosen by Iris)
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Directory holding the records
+        #[arg(long, value_name = "DIR", default_value = crate::adr::ADR_DIR)]
+        dir: String,
+
+        /// Print the record as raw markdown instead of writing it
+        #[arg(long, help = "Print raw markdown instead of writing the file")]
+        print: bool,
+
+        /// The decision to record, in a sentence or two
+        #[arg(required = true, value_name = "DECISION")]
+        decision: Vec<String>,
+    },
+
+    /// Plan a branch, commits and files for a task before writing code
+    #[command(
+        about = "Plan a branch, commits and files for a task before writing code",
+        long_about = "Turn a task description into a suggested branch name, a sequence of commits and the files they'll touch, using the code search and project metadata tools. The plan is saved for this repository; run without a task to see which planned commits have landed. Studio's dashboard shows the same checklist."
+    )]
+    Plan {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Print the plan as raw markdown instead of saving it
+        #[arg(long, conflicts_with = "clear")]
+        print: bool,
+
+        /// Forget the saved plan
+        #[arg(long)]
+        clear: bool,
+
+        /// The task to plan; omit to show progress on the saved plan
+        #[arg(value_name = "TASK", conflicts_with = "clear")]
+        task: Vec<String>,
+    },
+
+    /// Ask Iris a question about the staged changes or a commit range
+    #[command(
+        about = "Ask a question about the staged changes or a commit range",
+        long_about = "Answer a question about the staged changes, or a commit range with --range, right in the terminal. The answer streams as it's written and ends with the files and commits it relied on, checked against the repository. Uses the same chat capability as Studio, without the TUI."
+    )]
+    Ask {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Commit range to ask about: A..B, or A for A..HEAD
+        #[arg(long, value_name = "RANGE", conflicts_with = "staged")]
+        range: Option<String>,
+
+        /// Ask about the staged changes (the default)
+        #[arg(long)]
+        staged: bool,
+
+        /// The question
+        #[arg(required = true, value_name = "QUESTION")]
+        question: Vec<String>,
+    },
+
+    /// Plan and run an interactive rebase with Iris
+    #[command(
+        about = "Squash, fix up, reword and drop commits with a plan from Iris",
+        long_about = "Rebase the current branch onto a base, with Iris suggesting which commits to squash, fix up, reword or drop and why. The plan prints as a todo list and runs after confirmation; every new commit is built before the branch moves, so a conflict leaves the branch untouched. Use --studio to edit the plan step by step in Iris Studio."
+    )]
+    Rebase {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Branch or commit to rebase onto
+        #[arg(value_name = "BASE", default_value = "main")]
+        base: String,
+
+        /// Skip the AI suggestion and pick every commit as it is
+        #[arg(long)]
+        no_ai: bool,
+
+        /// Print the plan without running it
+        #[arg(long, conflicts_with = "yes")]
+        print: bool,
+
+        /// Run the plan without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Edit the plan in Iris Studio instead
+        #[arg(long, conflicts_with_all = ["no_ai", "print", "yes"])]
+        studio: bool,
     },
 
     /// Launch Iris Studio - unified TUI for all operations
@@ -296,7 +710,7 @@ pub enum Commands {
         #[arg(
             long,
             value_name = "MODE",
-            help = "Initial mode: explore, commit, review, pr, changelog"
+            help = "Initial mode: explore, commit, review, pr, changelog, dashboard, stash, rebase"
         )]
         mode: Option<String>,
 
@@ -311,8 +725,14 @@ pub enum Commands {
 
     // Configuration and utility commands
     /// Configure the AI-assisted Git commit message generator
-    #[command(about = "Configure Git-Iris settings and providers")]
+    #[command(
+        about = "Configure Git-Iris settings and providers",
+        args_conflicts_with_subcommands = true
+    )]
     Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+
         #[command(flatten)]
         common: CommonParams,
 
@@ -344,6 +764,13 @@ pub enum Commands {
             help = "Set timeout in seconds for parallel subagent tasks (default: 120)"
         )]
         subagent_timeout: Option<u64>,
+
+        /// Move API keys and the GitHub and GitLab tokens from the config file into the OS keyring
+        #[arg(
+            long,
+            help = "Move API keys and the GitHub and GitLab tokens from the config file into the OS keyring"
+        )]
+        migrate_secrets: bool,
     },
 
     /// Create or update a project-specific configuration file
@@ -385,57 +812,555 @@ pub enum Commands {
         print: bool,
     },
 
-    /// List available instruction presets
-    #[command(about = "List available instruction presets")]
-    ListPresets,
+    /// Export the last Studio session as a shareable report
+    #[command(
+        about = "Export the last Studio session as a report",
+        long_about = "Bundle the chat thread, generated artifacts, and the diff/refs they were based on from the most recent Iris Studio session into a single markdown or HTML report."
+    )]
+    ExportSession {
+        /// Report format
+        #[arg(long, value_enum, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
 
-    /// List available themes
-    #[command(about = "List available themes")]
-    Themes,
+        /// Write the report to a file instead of stdout
+        #[arg(
+            short = 'o',
+            long,
+            value_name = "FILE",
+            help = "Write the report to a file"
+        )]
+        file: Option<String>,
+    },
 
-    /// Generate shell completions
+    /// Undo the last commit git-iris made
     #[command(
-        about = "Generate shell completions",
-        long_about = "Generate shell completion scripts for bash, zsh, fish, elvish, or powershell.\n\nUsage examples:\n• Bash: git-iris completions bash >> ~/.bashrc\n• Zsh:  git-iris completions zsh >> ~/.zshrc\n• Fish: git-iris completions fish > ~/.config/fish/completions/git-iris.fish"
+        about = "Undo the last commit git-iris made, keeping its changes and message",
+        long_about = "Soft-reset the last commit made by git-iris. Its changes stay staged and its message opens as a draft in the Studio Commit editor. Refuses if HEAD has moved since or the commit has been pushed."
     )]
-    Completions {
-        /// Shell to generate completions for
-        #[arg(value_enum)]
-        shell: Shell,
+    Undo,
+
+    /// Show where prompt tokens went in recent generations
+    #[command(
+        about = "Show the prompt context size of recent generations",
+        long_about = "Print the token breakdown recorded for recent generations in this repository: system prompt, task context, documentation, diffs and other analysis the agent fetched, and the output. Use it to see what a preset, a [context.docs] setting or a large diff costs."
+    )]
+    Trace {
+        /// Number of recent generations to show
+        #[arg(short = 'n', long, default_value_t = 1)]
+        count: usize,
+
+        /// Only show generations of this capability (`commit`, `review`, `pr`, `changelog`, `release_notes`)
+        #[arg(long)]
+        capability: Option<String>,
     },
-}
 
-/// Define custom styles for Clap
-fn get_styles() -> Styles {
-    Styles::styled()
-        .header(AnsiColor::Magenta.on_default().bold())
-        .usage(AnsiColor::Cyan.on_default().bold())
-        .literal(AnsiColor::Green.on_default().bold())
-        .placeholder(AnsiColor::Yellow.on_default())
-        .valid(AnsiColor::Blue.on_default().bold())
-        .invalid(AnsiColor::Red.on_default().bold())
-        .error(AnsiColor::Red.on_default().bold())
-}
+    /// Browse generated history from past Studio sessions
+    #[command(about = "Search generated history from past Studio sessions")]
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
 
-/// Parse the command-line arguments
-pub fn parse_args() -> Cli {
-    Cli::parse()
-}
+    /// Reports computed from repository history
+    #[command(about = "Report statistics computed from repository history")]
+    Stats {
+        #[command(subcommand)]
+        action: StatsAction,
+    },
 
-/// Generate dynamic help including available LLM providers
-fn get_dynamic_help() -> String {
-    let providers_list = Provider::all_names()
-        .iter()
-        .map(|p| format!("{}", p.bold()))
-        .collect::<Vec<_>>()
-        .join(" • ");
+    /// Inspect capability prompts and repository overrides
+    #[command(about = "Show capability prompts and repository overrides")]
+    Prompt {
+        #[command(subcommand)]
+        action: PromptAction,
+    },
+
+    /// Manage git hooks that run git-iris
+    #[command(about = "Install or remove git hooks that pre-fill commit messages")]
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+
+    /// Update a downloaded git-iris binary to the latest release
+    #[command(
+        name = "self-update",
+        about = "Update git-iris to the latest release",
+        long_about = "Replace this git-iris binary with the latest GitHub release for your platform. Installs managed by cargo, Homebrew or a system package manager are left alone; the command tells you how to upgrade them instead."
+    )]
+    SelfUpdate {
+        /// Only report whether a newer release exists
+        #[arg(long, help = "Only check for a newer release, don't install it")]
+        check: bool,
+    },
+
+    /// Show token usage and estimated cost for this repository
+    #[command(
+        about = "Show token usage and estimated cost for this repository",
+        long_about = "Summarize the usage ledger in .git/iris/usage.jsonl: every agent call (commit, review, PR, changelog, chat, …) with its input and output tokens and an estimated cost, grouped by capability, model and day. Prices come from a built-in table; add [pricing] entries to your config for models it doesn't know."
+    )]
+    Usage {
+        /// Only count the last N days
+        #[arg(long)]
+        days: Option<u32>,
+
+        /// Output raw markdown without any console formatting
+        #[arg(long, help = "Output raw markdown without any console formatting")]
+        raw: bool,
+    },
+
+    /// Compare models on the same fixed tasks
+    #[command(
+        about = "Compare models on fixed commit and review tasks",
+        long_about = "Run built-in fixture tasks (a commit message and a short review of a sample diff) on two or more models and report each run's latency, token usage and estimated cost, with the outputs side by side. The fixtures carry their whole diff and tools are off, so every model sees the same input. Without --model, compares the default provider with the configured fallback providers that have an API key."
+    )]
+    Bench {
+        /// A model to compare, as PROVIDER or PROVIDER:MODEL; repeat for each
+        #[arg(long = "model", short = 'm', value_name = "PROVIDER[:MODEL]")]
+        models: Vec<String>,
+
+        /// Only run these tasks (commit, review); repeat for several
+        #[arg(long = "task", value_name = "TASK")]
+        tasks: Vec<String>,
+
+        /// Output raw markdown without any console formatting
+        #[arg(long, help = "Output raw markdown without any console formatting")]
+        raw: bool,
+    },
+
+    /// Manage cached mirrors of repositories analyzed with --repo
+    #[command(
+        name = "remote-cache",
+        about = "List or clear cached mirrors of --repo repositories",
+        long_about = "Repositories analyzed with --repo <URL> are mirrored into the user cache directory, so later runs only fetch new commits. List the mirrors with their size and last fetch, or clear one or all of them."
+    )]
+    RemoteCache {
+        #[command(subcommand)]
+        action: RemoteCacheAction,
+    },
+
+    /// Stable, script-friendly commands for editors and other TUIs
+    #[command(
+        about = "Script-friendly commands with stable output (no spinners or color)",
+        long_about = "Plumbing commands for lazygit custom commands, editor plugins and other tools. They print only the result to stdout, never show spinners, banners or color, and report errors on stderr with a non-zero exit code. The --json output is versioned and stable."
+    )]
+    Plumbing {
+        #[command(subcommand)]
+        action: PlumbingAction,
+    },
+
+    /// List available instruction presets
+    #[command(about = "List available instruction presets")]
+    ListPresets,
+
+    /// Manage custom instruction presets
+    #[command(
+        about = "List, show, create or edit instruction presets",
+        long_about = "Manage instruction presets. Custom presets are TOML files in ~/.config/git-iris/presets/ (yours) and .git-iris/presets/ (the repository's, shared with the team). A custom preset overrides a built-in one with the same name, and a repository preset overrides a personal one."
+    )]
+    Preset {
+        #[command(subcommand)]
+        action: PresetAction,
+    },
+
+    /// List available themes, or export the active one
+    #[command(
+        about = "List available themes",
+        long_about = "List available themes, or export the active theme's resolved colors with `git-iris theme export` so other tools can match it.",
+        visible_alias = "theme"
+    )]
+    Themes {
+        #[command(subcommand)]
+        action: Option<ThemeAction>,
+    },
+
+    /// Generate shell completions
+    #[command(
+        about = "Generate shell completions",
+        long_about = "Generate shell completion scripts for bash, zsh, fish, elvish, or powershell.\n\nUsage examples:\n• Bash: git-iris completions bash >> ~/.bashrc\n• Zsh:  git-iris completions zsh >> ~/.zshrc\n• Fish: git-iris completions fish > ~/.config/fish/completions/git-iris.fish"
+    )]
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+/// Subcommands for `git-iris history`
+#[derive(Subcommand)]
+pub enum HistoryAction {
+    /// Full-text search over past commit messages, reviews, PRs, changelogs and chats
+    #[command(
+        about = "Full-text search over past commit messages, reviews, and chats",
+        long_about = "Search the generated history archived by Iris Studio. All query terms must match (case-insensitive)."
+    )]
+    Search {
+        /// Search terms
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Only show entries of this kind
+        #[arg(long, value_enum)]
+        mode: Option<HistoryKind>,
+
+        /// Only show sessions since a date (YYYY-MM-DD) or age (e.g. 7d)
+        #[arg(long, value_name = "WHEN")]
+        since: Option<String>,
+
+        /// Maximum number of results
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+}
+
+/// Subcommands for `git-iris stats`
+#[derive(Subcommand)]
+pub enum StatsAction {
+    /// Score past commit messages against the active preset
+    #[command(
+        about = "Score commit message quality over history",
+        long_about = "Score past commit messages against the conventions of the active preset: subject length distribution, Conventional Commits compliance, emoji usage and the authors with the most violations. Merge commits are skipped."
+    )]
+    Messages {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Number of commits to score, newest first
+        #[arg(short = 'n', long, default_value_t = 500)]
+        count: usize,
+
+        /// Output raw markdown without any console formatting
+        #[arg(long, help = "Output raw markdown without any console formatting")]
+        raw: bool,
+    },
+
+    /// Summarize the local usage analytics
+    #[command(
+        about = "Show which features you use and how often generations fail",
+        long_about = "Summarize the local analytics file (~/.iris/analytics.jsonl): generations per capability with success rate and average time, commands run, Studio modes entered and the latest failures. Recording is off until you set local_analytics = true in your config, and nothing is ever uploaded."
+    )]
+    Usage {
+        /// Only count the last N days
+        #[arg(long)]
+        days: Option<u32>,
+
+        /// Output raw markdown without any console formatting
+        #[arg(long, help = "Output raw markdown without any console formatting")]
+        raw: bool,
+    },
+}
+
+/// Subcommands for `git-iris config`
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Package settings, presets, prompts and theme into a shareable bundle
+    #[command(
+        about = "Export a team-shareable config bundle (never includes API keys)",
+        long_about = "Package provider models, presets, instructions, generation parameters, prompt overrides from .git-iris/prompts and a custom theme into a single TOML file. API keys are never exported."
+    )]
+    Export {
+        /// Bundle file to write
+        #[arg(long, value_name = "FILE")]
+        bundle: String,
+    },
+
+    /// Apply a config bundle
+    #[command(
+        about = "Import a config bundle",
+        long_about = "Apply a bundle created with `config export`. Settings go to your personal config (or .irisconfig with --project), prompt overrides to .git-iris/prompts, and a bundled theme to your themes directory. Existing API keys are kept."
+    )]
+    Import {
+        /// Bundle file to read
+        #[arg(value_name = "FILE")]
+        bundle: String,
+
+        /// Write settings to the project config instead of your personal config
+        #[arg(long)]
+        project: bool,
+    },
+}
+
+/// Subcommands for `git-iris remote-cache`
+#[derive(Subcommand)]
+pub enum RemoteCacheAction {
+    /// List cached mirrors
+    #[command(about = "List cached mirrors with their size and last fetch")]
+    List,
+
+    /// Remove cached mirrors
+    #[command(about = "Remove the mirror of a repository, or all mirrors")]
+    Clear {
+        /// Repository URL, as passed to --repo
+        #[arg(value_name = "URL", required_unless_present = "all")]
+        url: Option<String>,
+
+        /// Remove every mirror
+        #[arg(long, conflicts_with = "url")]
+        all: bool,
+    },
+}
+
+/// Subcommands for `git-iris theme`
+#[derive(Subcommand)]
+pub enum ThemeAction {
+    /// Print the active theme's resolved colors
+    #[command(
+        about = "Print the active theme's resolved colors",
+        long_about = "Print the palette, tokens, styles and gradients of the active theme (or --theme) with every reference resolved to a hex color, for generating fzf, bat or shell prompt colors that match git-iris."
+    )]
+    Export {
+        /// Output format
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = theme::ExportFormat::Json,
+            help = "Output format: json, css custom properties, or shell variable assignments"
+        )]
+        format: theme::ExportFormat,
+    },
+}
+
+/// Subcommands for `git-iris preset`
+#[derive(Subcommand)]
+pub enum PresetAction {
+    /// List built-in and custom presets
+    #[command(about = "List built-in and custom presets")]
+    List,
+
+    /// Print a preset's instructions and where it comes from
+    #[command(about = "Print a preset's instructions and where it comes from")]
+    Show {
+        /// Preset name
+        name: String,
+    },
+
+    /// Create a preset file
+    #[command(
+        about = "Create a preset file",
+        long_about = "Create <name>.toml in the repository's .git-iris/presets/ (or ~/.config/git-iris/presets/ with --user) and open it in $VISUAL or $EDITOR."
+    )]
+    New {
+        /// Preset name: letters, digits, '-' and '_'
+        name: String,
+
+        /// Commands the preset applies to
+        #[arg(long = "type", value_enum, default_value_t = PresetType::Both)]
+        preset_type: PresetType,
+
+        /// Start from an existing preset's instructions
+        #[arg(long, value_name = "PRESET")]
+        from: Option<String>,
+
+        /// Save it with your presets instead of the repository's
+        #[arg(long)]
+        user: bool,
+
+        /// Only create the file, don't open an editor
+        #[arg(long)]
+        no_edit: bool,
+    },
+
+    /// Open a custom preset in $VISUAL or $EDITOR
+    #[command(about = "Open a custom preset in $VISUAL or $EDITOR")]
+    Edit {
+        /// Preset name
+        name: String,
+    },
+}
+
+/// Subcommands for `git-iris prompt`
+#[derive(Subcommand)]
+pub enum PromptAction {
+    /// Print the effective task prompt for a capability
+    #[command(
+        about = "Print the effective task prompt for a capability",
+        long_about = "Print a capability's task prompt after applying overrides from .git-iris/prompts/<capability>.md (replace) and .git-iris/prompts/<capability>.append.md (append)."
+    )]
+    Show {
+        /// Capability name
+        #[arg(value_parser = clap::builder::PossibleValuesParser::new(crate::agents::prompts::CAPABILITIES))]
+        capability: String,
+    },
+}
+
+/// Subcommands for `git-iris plumbing`
+#[derive(Subcommand)]
+pub enum PlumbingAction {
+    /// Generate a commit message for the staged changes
+    #[command(about = "Print a commit message for the staged changes")]
+    Message {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Emit versioned JSON instead of the plain message
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Review staged changes, a commit, or a branch range
+    #[command(about = "Print a code review as markdown or JSON")]
+    Review {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Emit versioned JSON instead of markdown
+        #[arg(long)]
+        json: bool,
+
+        /// Include unstaged changes in the review
+        #[arg(long)]
+        include_unstaged: bool,
+
+        /// Review a specific commit by ID (hash, branch, or reference)
+        #[arg(long)]
+        commit: Option<String>,
+
+        /// Starting branch for comparison
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Target branch for comparison
+        #[arg(long)]
+        to: Option<String>,
+    },
+
+    /// Draft an architecture decision record and write it to the ADR directory
+    #[command(about = "Write an ADR for a commit range and print its path or JSON")]
+    Adr {
+        #[command(flatten)]
+        common: CommonParams,
+
+        /// Emit versioned JSON instead of the file path
+        #[arg(long)]
+        json: bool,
+
+        /// Starting branch, tag, or commit
+        #[arg(long, default_value = "main")]
+        from: String,
+
+        /// Ending branch, tag, or commit
+        #[arg(long, default_value = "HEAD")]
+        to: String,
+
+        /// Title of the record (default: chosen by Iris)
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Directory holding the records
+        #[arg(long, value_name = "DIR", default_value = crate::adr::ADR_DIR)]
+        dir: String,
+
+        /// The decision to record
+        #[arg(required = true, value_name = "DECISION")]
+        decision: Vec<String>,
+    },
+
+    /// Check a commit message file against the project's commit style
+    #[command(
+        about = "Check a commit message file against [commit_style]",
+        long_about = "Check a commit message file, such as the one git passes to a commit-msg hook, against the [commit_style] rules. Comment lines are ignored. Violations are printed to stderr and the exit code is non-zero."
+    )]
+    CheckMessage {
+        /// Commit message file
+        #[arg(value_name = "FILE")]
+        file: String,
+    },
+}
+
+/// Subcommands for `git-iris hooks`
+#[derive(Subcommand)]
+pub enum HooksAction {
+    /// Install git hooks that run git-iris
+    #[command(
+        about = "Install a prepare-commit-msg hook that pre-fills generated messages",
+        long_about = "Install a prepare-commit-msg hook so a plain `git commit` opens the editor with a generated message already filled in. Commits with -m, -F, amends, merges and squashes are left alone. With --commit-msg, also install a commit-msg hook that rejects messages breaking the [commit_style] rules.\n\nAn existing hook is moved to <hook>.pre-iris and run first, so nothing is overwritten. --uninstall removes the git-iris hooks and puts the originals back. Set GIT_IRIS_SKIP_HOOKS=1 to skip the hooks for one commit."
+    )]
+    Install {
+        /// Also install a commit-msg hook that checks the commit style rules
+        #[arg(long)]
+        commit_msg: bool,
+
+        /// Remove the git-iris hooks and restore any hooks they wrapped
+        #[arg(long)]
+        uninstall: bool,
+    },
+}
+
+/// Directories of a monorepo to generate separate changelogs for
+#[derive(Args, Clone, Debug, Default)]
+pub struct PackageScope {
+    /// Only include commits that touch this directory
+    #[arg(
+        long = "path",
+        value_name = "DIR",
+        help = "Only include commits that touch this directory (repeatable)"
+    )]
+    pub paths: Vec<String>,
+
+    /// Only include commits that touch this workspace member
+    #[arg(
+        long = "package",
+        value_name = "NAME",
+        help = "Only include commits that touch this Cargo or npm workspace member (repeatable)"
+    )]
+    pub packages: Vec<String>,
+
+    /// Generate one changelog per workspace member
+    #[arg(
+        long,
+        conflicts_with_all = ["paths", "packages"],
+        help = "Generate one changelog per Cargo or npm workspace member"
+    )]
+    pub all_packages: bool,
+}
+
+/// Define custom styles for Clap
+fn get_styles() -> Styles {
+    Styles::styled()
+        .header(AnsiColor::Magenta.on_default().bold())
+        .usage(AnsiColor::Cyan.on_default().bold())
+        .literal(AnsiColor::Green.on_default().bold())
+        .placeholder(AnsiColor::Yellow.on_default())
+        .valid(AnsiColor::Blue.on_default().bold())
+        .invalid(AnsiColor::Red.on_default().bold())
+        .error(AnsiColor::Red.on_default().bold())
+}
+
+/// Parse the command-line arguments
+pub fn parse_args() -> Cli {
+    Cli::parse()
+}
+
+/// Generate dynamic help including available LLM providers
+fn get_dynamic_help() -> String {
+    let providers_list = Provider::all_names()
+        .iter()
+        .map(|p| format!("{}", p.bold()))
+        .collect::<Vec<_>>()
+        .join(" • ");
 
     format!("\nAvailable LLM Providers: {providers_list}")
 }
 
+/// Commands that can print their result with `--output json`
+const JSON_COMMANDS: &[&str] = &["gen", "review", "pr", "changelog", "release-notes"];
+
+/// Command name for usage analytics, including a subcommand (`stats usage`)
+fn command_name(matches: &clap::ArgMatches) -> String {
+    match matches.subcommand() {
+        Some((name, sub)) => match sub.subcommand_name() {
+            Some(action) => format!("{name} {action}"),
+            None => name.to_string(),
+        },
+        None => "studio".to_string(),
+    }
+}
+
 /// Main function to parse arguments and handle the command
 pub async fn main() -> anyhow::Result<()> {
-    let cli = parse_args();
+    let matches = Cli::command().get_matches();
+    let command = command_name(&matches);
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     if cli.version {
         ui::print_version(crate_version!());
@@ -452,11 +1377,29 @@ pub async fn main() -> anyhow::Result<()> {
         crate::logger::disable_logging();
     }
 
+    crate::git::set_location(GitLocation {
+        git_dir: cli.git_dir.clone(),
+        work_tree: cli.work_tree.clone(),
+    });
+
     // Set quiet mode in the UI module
     if cli.quiet {
         crate::ui::set_quiet_mode(true);
     }
 
+    crate::read_only::set_read_only(cli.read_only);
+    crate::context::set_no_limits(cli.no_limits);
+
+    if cli.output == OutputFormat::Json {
+        if !JSON_COMMANDS.contains(&command.as_str()) {
+            anyhow::bail!(
+                "`{command}` has no JSON output; --output json works with {}",
+                JSON_COMMANDS.join(", ")
+            );
+        }
+        crate::output::set_output_format(cli.output);
+    }
+
     // Initialize theme
     initialize_theme(cli.theme.as_deref());
 
@@ -466,7 +1409,7 @@ pub async fn main() -> anyhow::Result<()> {
         crate::agents::debug::debug_header("🔮 IRIS DEBUG MODE ACTIVATED 🔮");
     }
 
-    if let Some(command) = cli.command {
+    let result = if let Some(command) = cli.command {
         handle_command(command, cli.repository_url).await
     } else {
         // Default: launch Studio with auto-detect mode
@@ -478,7 +1421,22 @@ pub async fn main() -> anyhow::Result<()> {
             cli.repository_url,
         )
         .await
+    };
+
+    for warning in crate::context::take_limit_warnings() {
+        ui::print_warning(&warning);
+    }
+    for violation in crate::agents::policy::take_violations() {
+        ui::print_warning(&format!("Tool policy refused {violation}"));
+    }
+
+    if crate::config::Config::load().is_ok_and(|config| config.local_analytics) {
+        crate::analytics::record(&crate::analytics::UsageEvent::command(
+            &command,
+            result.as_ref().err(),
+        ));
     }
+    result
 }
 
 /// Initialize the theme from CLI flag or config
@@ -512,6 +1470,15 @@ fn initialize_theme(cli_theme: Option<&str>) {
     }
 }
 
+/// `gen --auto-commit --output json` result: the message and the commit it made
+#[derive(serde::Serialize)]
+struct CommittedMessage<'a> {
+    #[serde(flatten)]
+    message: &'a crate::types::GeneratedMessage,
+    branch: &'a str,
+    commit: &'a str,
+}
+
 /// Configuration for the Gen command
 #[allow(clippy::struct_excessive_bools)]
 struct GenConfig {
@@ -532,7 +1499,6 @@ async fn handle_gen_with_agent(
     use crate::agents::{IrisAgentService, StructuredResponse, TaskContext};
     use crate::config::Config;
     use crate::git::GitRepo;
-    use crate::instruction_presets::PresetType;
     use crate::output::format_commit_result;
     use crate::services::GitCommitService;
     use crate::studio::{Mode, run_studio};
@@ -548,6 +1514,10 @@ async fn handle_gen_with_agent(
         ui::print_info("Run 'git-iris list-presets' to see available presets for commits.");
     }
 
+    if is_json_output() && !config.print_only && !config.auto_commit {
+        anyhow::bail!("--output json needs --print or --auto-commit; otherwise gen opens Studio");
+    }
+
     // Amend mode requires --print or --auto-commit (Studio amend support coming later)
     if config.amend && !config.print_only && !config.auto_commit {
         ui::print_warning("--amend requires --print or --auto-commit for now.");
@@ -564,11 +1534,10 @@ async fn handle_gen_with_agent(
     let use_gitmoji = config.use_gitmoji && cfg.use_gitmoji;
 
     // Create GitCommitService for commit operations
-    let commit_service = Arc::new(GitCommitService::new(
-        git_repo.clone(),
-        use_gitmoji,
-        config.verify,
-    ));
+    let commit_service = Arc::new(
+        GitCommitService::new(git_repo.clone(), use_gitmoji, config.verify)
+            .with_lifecycle_hooks(cfg.hooks.clone()),
+    );
 
     // Create IrisAgentService for LLM operations
     let agent_service = Arc::new(IrisAgentService::from_common_params(
@@ -584,6 +1553,9 @@ async fn handle_gen_with_agent(
         // For amend mode, we allow empty staged changes (amending message only)
         // For regular commits, we require staged changes
         if git_info.staged_files.is_empty() && !config.amend {
+            if is_json_output() {
+                return Err(crate::error::IrisError::NothingStaged.into());
+            }
             ui::print_warning(
                 "No staged changes. Please stage your changes before generating a commit message.",
             );
@@ -624,6 +1596,9 @@ async fn handle_gen_with_agent(
         spinner.finish_and_clear();
 
         if config.print_only {
+            if is_json_output() {
+                return crate::output::print_json("gen", &generated_message);
+            }
             println!("{}", format_commit_message(&generated_message));
             return Ok(());
         }
@@ -638,17 +1613,61 @@ async fn handle_gen_with_agent(
             ));
         }
 
+        if cfg.ascii_only {
+            crate::types::check_ascii(&format_commit_message(&generated_message))?;
+        }
+        for violation in cfg
+            .commit_style
+            .check(&format_commit_message(&generated_message))
+        {
+            ui::print_warning(&format!("Style: {violation}"));
+        }
+
+        // The commit and message an amend replaces, for its drift record
+        let amended = config
+            .amend
+            .then(|| {
+                let repo = commit_service.repo();
+                Some((
+                    repo.head_commit_id().ok()?,
+                    repo.get_head_commit_message().ok()?,
+                ))
+            })
+            .flatten();
         let commit_result = if config.amend {
-            commit_service.perform_amend(&format_commit_message(&generated_message))
+            commit_service.amend_commit(&format_commit_message(&generated_message))
         } else {
             commit_service.perform_commit(&format_commit_message(&generated_message))
         };
 
         match commit_result {
             Ok(result) => {
-                let output =
-                    format_commit_result(&result, &format_commit_message(&generated_message));
-                println!("{output}");
+                if is_json_output() {
+                    crate::output::print_json(
+                        "gen",
+                        &CommittedMessage {
+                            message: &generated_message,
+                            branch: &result.branch,
+                            commit: &result.commit_hash,
+                        },
+                    )?;
+                } else {
+                    let output =
+                        format_commit_result(&result, &format_commit_message(&generated_message));
+                    println!("{output}");
+                }
+                if let Some((commit, message)) = amended
+                    && let Err(e) =
+                        crate::companion::record_amend(commit_service.repo(), commit, message)
+                {
+                    log_debug!("Failed to record message revision: {}", e);
+                }
+                if !config.amend {
+                    crate::undo::record_commit(commit_service.repo());
+                    ui::print_info(
+                        "Changed your mind? `git-iris undo` restores this commit as a draft",
+                    );
+                }
             }
             Err(e) => {
                 let action = if config.amend { "amend" } else { "commit" };
@@ -731,8 +1750,12 @@ fn handle_config(
     )
 }
 
-/// Handle the `Review` command
-#[allow(clippy::too_many_arguments)]
+/// Handle the `Review` command; `fail_on` is set for `--ci` reviews
+#[allow(
+    clippy::too_many_arguments,
+    clippy::too_many_lines,
+    clippy::fn_params_excessive_bools
+)]
 async fn handle_review(
     common: CommonParams,
     print: bool,
@@ -740,31 +1763,90 @@ async fn handle_review(
     repository_url: Option<String>,
     include_unstaged: bool,
     commit: Option<String>,
-    from: Option<String>,
-    to: Option<String>,
+    mut from: Option<String>,
+    mut to: Option<String>,
+    pr: Option<u64>,
+    format: ReviewFormat,
+    fail_on: Option<Severity>,
+    write: bool,
 ) -> anyhow::Result<()> {
     log_debug!(
-        "Handling 'review' command with common: {:?}, print: {}, raw: {}, include_unstaged: {}, commit: {:?}, from: {:?}, to: {:?}",
+        "Handling 'review' command with common: {:?}, print: {}, raw: {}, include_unstaged: {}, commit: {:?}, from: {:?}, to: {:?}, pr: {:?}, format: {:?}, fail_on: {:?}, write: {}",
         common,
         print,
         raw,
         include_unstaged,
         commit,
         from,
-        to
+        to,
+        pr,
+        format,
+        fail_on,
+        write
     );
 
+    use crate::agents::{IrisAgentService, StructuredResponse, TaskContext};
+
+    // A remote pull request becomes the range from its merge base to its head
+    let fetched = match pr {
+        Some(number) => {
+            let fetched = fetch_review_pull_request(number, repository_url.as_ref()).await?;
+            from = Some(fetched.merge_base.clone());
+            to = Some(fetched.head.clone());
+            Some(fetched)
+        }
+        None => None,
+    };
+
+    if fail_on.is_some() {
+        // CI has nothing staged; review what the pipeline checked out
+        if commit.is_none() && from.is_none() {
+            anyhow::bail!(
+                "--ci reviews a range: pass --from <REF> (--to defaults to HEAD) or --commit"
+            );
+        }
+        if commit.is_none() {
+            to.get_or_insert_with(|| "HEAD".to_string());
+        }
+    }
+
+    // Validate parameters and create structured context
+    let context = TaskContext::for_review(commit, from, to, include_unstaged)?;
+
+    // Keep stdout for the review itself
+    let warning = fetched.as_ref().and_then(|f| f.warning.as_deref());
+    if let Some(warning) = warning
+        && (raw || format != ReviewFormat::Markdown || fail_on.is_some())
+    {
+        eprintln!("{warning}");
+    }
+
+    if format != ReviewFormat::Markdown || fail_on.is_some() {
+        if is_json_output() {
+            anyhow::bail!("--output json only works with the markdown review format outside --ci");
+        }
+        let findings = print_review_findings(&common, repository_url, context, format).await?;
+        if let Some(threshold) = fail_on {
+            let count = findings.iter().filter(|f| f.severity >= threshold).count();
+            if count > 0 {
+                return Err(crate::error::IrisError::ReviewFindings { count, threshold }.into());
+            }
+        }
+        return Ok(());
+    }
+
     // For raw output, skip all formatting
     if !raw {
         ui::print_version(crate_version!());
         ui::print_newline();
+        if let Some(fetched) = &fetched {
+            ui::print_info(&pull_request_banner(fetched));
+        }
+        if let Some(warning) = warning {
+            ui::print_warning(warning);
+        }
     }
 
-    use crate::agents::{IrisAgentService, TaskContext};
-
-    // Validate parameters and create structured context
-    let context = TaskContext::for_review(commit, from, to, include_unstaged)?;
-
     // Create spinner for progress indication (skip for raw output)
     let spinner = if raw {
         None
@@ -781,11 +1863,156 @@ async fn handle_review(
         s.finish_and_clear();
     }
 
-    if raw || print {
+    if is_json_output() {
+        crate::output::print_response_json("review", &response)?;
+    } else if raw || print {
         println!("{response}");
     } else {
         ui::print_success("Code review completed successfully");
         println!("{response}");
+        if let (Some(repo), StructuredResponse::MarkdownReview(review)) =
+            (service.git_repo(), &response)
+        {
+            let sources = crate::types::verify_sources(&review.content, repo);
+            for source in sources.iter().filter(|s| !s.is_verified()) {
+                ui::print_warning(&format!(
+                    "Unverified source {}: {}",
+                    source.citation,
+                    source.problem.as_deref().unwrap_or_default()
+                ));
+            }
+        }
+    }
+
+    if write && let StructuredResponse::MarkdownReview(review) = &response {
+        // A pull request's review is named for it, not the checked out branch
+        let branch = match pr {
+            Some(number) => Some(format!("pr-{number}")),
+            None => service
+                .git_repo()
+                .and_then(|repo| repo.get_current_branch().ok()),
+        };
+        let path = service
+            .config()
+            .outputs
+            .path(Artifact::Review, None, branch.as_deref());
+        write_artifact_file(Artifact::Review, &path, &review.content, raw)?;
+    }
+    Ok(())
+}
+
+/// Fetch pull request `number` of origin for `review --pr`
+async fn fetch_review_pull_request(
+    number: u64,
+    repository_url: Option<&String>,
+) -> anyhow::Result<crate::integrations::forge::FetchedPullRequest> {
+    if repository_url.is_some() {
+        anyhow::bail!("--pr reviews a pull request of this clone's origin; drop --repo");
+    }
+    let config = crate::config::Config::load()?;
+    crate::integrations::forge::fetch_pull_request(&config.github, &config.gitlab, number).await
+}
+
+/// "Reviewing PR #12: Title into main (abc1234..def5678)"
+fn pull_request_banner(fetched: &crate::integrations::forge::FetchedPullRequest) -> String {
+    let short = |id: &str| id.get(..7).unwrap_or(id).to_string();
+    format!(
+        "Reviewing {}{} into {} ({}..{})",
+        fetched.label,
+        fetched
+            .title
+            .as_deref()
+            .map(|title| format!(": {title}"))
+            .unwrap_or_default(),
+        fetched.base,
+        short(&fetched.merge_base),
+        short(&fetched.head),
+    )
+}
+
+/// Save an artifact to its `[outputs]` path and say where it went
+fn write_artifact_file(
+    artifact: Artifact,
+    path: &str,
+    content: &str,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    crate::outputs::write_artifact(artifact, std::path::Path::new(path), content)?;
+    if !quiet && !is_json_output() {
+        ui::print_success(&format!(
+            "✨ {} saved to {}",
+            artifact.label(),
+            path.bright_green()
+        ));
+    }
+    Ok(())
+}
+
+/// Print a review for tools and return its findings: the raw markdown, a
+/// JSON map of file to LSP diagnostics, GitHub Actions workflow commands plus
+/// a job summary, or a SARIF log
+async fn print_review_findings(
+    common: &CommonParams,
+    repository_url: Option<String>,
+    context: crate::agents::TaskContext,
+    format: ReviewFormat,
+) -> anyhow::Result<Vec<crate::types::ReviewFinding>> {
+    use crate::agents::{IrisAgentService, StructuredResponse};
+
+    // Only the findings may reach stdout
+    crate::plumbing::quiet_output();
+
+    let service = IrisAgentService::from_common_params(common, repository_url)?;
+    let review = match service.execute_task("review", context).await? {
+        StructuredResponse::MarkdownReview(review) => review,
+        other => anyhow::bail!("Expected a review, got {}", other),
+    };
+    let tracked_files = service
+        .git_repo()
+        .map(|repo| repo.get_all_tracked_files())
+        .transpose()?
+        .unwrap_or_default();
+
+    let findings = review.findings();
+    match format {
+        ReviewFormat::Markdown => println!("{}", review.content.trim_end()),
+        ReviewFormat::LspDiagnostics => {
+            let diagnostics = crate::types::lsp_diagnostics(&findings, &tracked_files);
+            println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+        }
+        ReviewFormat::GithubAnnotations => {
+            print_github_annotations(&findings, &tracked_files)?;
+        }
+        ReviewFormat::Sarif => {
+            let log = crate::types::sarif_log(&findings, &tracked_files);
+            println!("{}", serde_json::to_string_pretty(&log)?);
+        }
+    }
+    Ok(findings)
+}
+
+/// Print workflow commands for `findings`, and append a summary to the job's
+/// summary page when running in GitHub Actions
+fn print_github_annotations(
+    findings: &[crate::types::ReviewFinding],
+    tracked_files: &[String],
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    print!(
+        "{}",
+        crate::types::github_annotations(findings, tracked_files)
+    );
+    // Set by GitHub Actions; the summary page shows whatever is appended
+    if let Some(path) = std::env::var_os("GITHUB_STEP_SUMMARY") {
+        use std::io::Write;
+        let summary = crate::types::github_step_summary(findings, tracked_files);
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(summary.as_bytes()))
+            .context("Failed to write $GITHUB_STEP_SUMMARY")?;
     }
     Ok(())
 }
@@ -801,16 +2028,20 @@ async fn handle_changelog(
     update: bool,
     file: Option<String>,
     version_name: Option<String>,
+    write: bool,
+    scope: PackageScope,
 ) -> anyhow::Result<()> {
     log_debug!(
-        "Handling 'changelog' command with common: {:?}, from: {}, to: {:?}, raw: {}, update: {}, file: {:?}, version_name: {:?}",
+        "Handling 'changelog' command with common: {:?}, from: {}, to: {:?}, raw: {}, update: {}, file: {:?}, version_name: {:?}, write: {}, scope: {:?}",
         common,
         from,
         to,
         raw,
         update,
         file,
-        version_name
+        version_name,
+        write,
+        scope
     );
 
     // For raw output, skip all formatting
@@ -825,37 +2056,26 @@ async fn handle_changelog(
     use anyhow::Context;
     use std::sync::Arc;
 
-    // Create structured context for changelog with version_name and current date
-    let context = TaskContext::for_changelog(from.clone(), to.clone(), version_name.clone(), None);
-    let to_ref = to.unwrap_or_else(|| "HEAD".to_string());
-
-    // Create spinner for progress indication (skip for raw output)
-    let spinner = if raw {
-        None
-    } else {
-        Some(ui::create_spinner("Initializing Iris..."))
-    };
+    let to_ref = to.clone().unwrap_or_else(|| "HEAD".to_string());
 
     // Use IrisAgentService for agent execution
     let service = IrisAgentService::from_common_params(&common, repository_url.clone())?;
-    let response = service.execute_task("changelog", context).await?;
-
-    // Finish spinner
-    if let Some(s) = spinner {
-        s.finish_and_clear();
-    }
-
-    // Print the changelog
-    println!("{response}");
+    let packages = scoped_packages(&service, &scope)?;
+    let file = if write {
+        Some(
+            service
+                .config()
+                .outputs
+                .path(Artifact::Changelog, version_name.as_deref(), None),
+        )
+    } else {
+        file
+    };
 
-    if update {
-        // Extract the formatted content for file update
-        let formatted_content = response.to_string();
-        let changelog_path = file.unwrap_or_else(|| "CHANGELOG.md".to_string());
+    // Create GitRepo for file updates
+    let git_repo = if update {
         let repo_url_for_update = repository_url.or(common.repository_url.clone());
-
-        // Create GitRepo for file update
-        let git_repo = if let Some(url) = repo_url_for_update {
+        Some(if let Some(url) = repo_url_for_update {
             Arc::new(
                 GitRepo::clone_remote_repository(&url)
                     .context("Failed to clone repository for changelog update")?,
@@ -866,7 +2086,32 @@ async fn handle_changelog(
                 GitRepo::new(&repo_path)
                     .context("Failed to create GitRepo for changelog update")?,
             )
+        })
+    } else {
+        None
+    };
+
+    for package in &packages {
+        // Create structured context for changelog with version_name and current date
+        let context =
+            TaskContext::for_changelog(from.clone(), to.clone(), version_name.clone(), None)
+                .scoped_to(package.as_ref().map(|p| p.path.clone()));
+        let response =
+            generate_for_package(&service, "changelog", context, package.as_ref(), raw).await?;
+
+        // Print the changelog
+        if is_json_output() {
+            crate::output::print_response_json("changelog", &response)?;
+        } else {
+            println!("{response}");
+        }
+
+        let Some(git_repo) = &git_repo else {
+            continue;
         };
+        // Extract the formatted content for file update
+        let formatted_content = response.to_string();
+        let changelog_path = package_file(package.as_ref(), file.as_deref(), "CHANGELOG.md");
 
         // Update changelog file
         let update_spinner =
@@ -875,9 +2120,9 @@ async fn handle_changelog(
         match ChangelogGenerator::update_changelog_file(
             &formatted_content,
             &changelog_path,
-            &git_repo,
+            git_repo,
             &to_ref,
-            version_name,
+            version_name.clone(),
         ) {
             Ok(()) => {
                 update_spinner.finish_and_clear();
@@ -896,101 +2141,1018 @@ async fn handle_changelog(
     Ok(())
 }
 
-/// Handle the `Release Notes` command
-#[allow(clippy::too_many_arguments)]
-async fn handle_release_notes(
+/// Packages selected by `--path`, `--package` or `--all-packages`
+///
+/// `None` stands for the whole repository when no scope was given.
+fn scoped_packages(
+    service: &crate::agents::IrisAgentService,
+    scope: &PackageScope,
+) -> anyhow::Result<Vec<Option<crate::changelog::Package>>> {
+    let root = match service.git_repo() {
+        Some(repo) => repo.repo_path().clone(),
+        None => std::env::current_dir()?,
+    };
+    let packages = crate::changelog::resolve_packages(
+        &root,
+        &scope.paths,
+        &scope.packages,
+        scope.all_packages,
+    )?;
+    if packages.is_empty() {
+        return Ok(vec![None]);
+    }
+    Ok(packages.into_iter().map(Some).collect())
+}
+
+/// Run a changelog or release notes task, naming the package first when
+/// the run is scoped to one
+async fn generate_for_package(
+    service: &crate::agents::IrisAgentService,
+    capability: &str,
+    context: crate::agents::TaskContext,
+    package: Option<&crate::changelog::Package>,
+    raw: bool,
+) -> anyhow::Result<crate::agents::StructuredResponse> {
+    if let Some(package) = package
+        && !is_json_output()
+    {
+        if raw {
+            println!("<!-- {} ({}) -->", package.name, package.path);
+        } else {
+            ui::print_info(&format!("📦 {} ({})", package.name, package.path));
+        }
+    }
+
+    // Create spinner for progress indication (skip for raw output)
+    let spinner = if raw {
+        None
+    } else {
+        Some(ui::create_spinner("Initializing Iris..."))
+    };
+    let response = service.execute_task(capability, context).await?;
+    if let Some(s) = spinner {
+        s.finish_and_clear();
+    }
+    Ok(response)
+}
+
+/// The file `--update` writes: `file` inside the package directory when
+/// scoped to a package, else `file` as given
+fn package_file(
+    package: Option<&crate::changelog::Package>,
+    file: Option<&str>,
+    default: &str,
+) -> String {
+    let file = file.unwrap_or(default);
+    match package {
+        Some(package) => format!("{}/{file}", package.path),
+        None => file.to_string(),
+    }
+}
+
+/// Options for the `release-notes` command
+#[allow(clippy::struct_excessive_bools)]
+struct ReleaseNotesOptions {
+    update: bool,
+    file: Option<String>,
+    version_name: Option<String>,
+    suggest_version: bool,
+    upgrade_guide: bool,
+    write_upgrade_guide: bool,
+    write: bool,
+}
+
+/// Handle the `Release Notes` command
+async fn handle_release_notes(
+    common: CommonParams,
+    from: String,
+    to: Option<String>,
+    raw: bool,
+    repository_url: Option<String>,
+    options: ReleaseNotesOptions,
+    scope: PackageScope,
+) -> anyhow::Result<()> {
+    let ReleaseNotesOptions {
+        update,
+        file,
+        version_name,
+        suggest_version,
+        upgrade_guide,
+        write_upgrade_guide,
+        write,
+    } = options;
+    log_debug!(
+        "Handling 'release-notes' command with common: {:?}, from: {}, to: {:?}, raw: {}, update: {}, file: {:?}, version_name: {:?}, suggest_version: {}, upgrade_guide: {}, write_upgrade_guide: {}, write: {}, scope: {:?}",
+        common,
+        from,
+        to,
+        raw,
+        update,
+        file,
+        version_name,
+        suggest_version,
+        upgrade_guide,
+        write_upgrade_guide,
+        write,
+        scope
+    );
+
+    // For raw output, skip all formatting
+    if !raw {
+        ui::print_version(crate_version!());
+        ui::print_newline();
+    }
+
+    use crate::agents::{IrisAgentService, StructuredResponse, TaskContext};
+
+    // Use IrisAgentService for agent execution
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+
+    for package in scoped_packages(&service, &scope)? {
+        // Create structured context for release notes with version_name and current date
+        let context =
+            TaskContext::for_changelog(from.clone(), to.clone(), version_name.clone(), None)
+                .scoped_to(package.as_ref().map(|p| p.path.clone()))
+                .with_upgrade_guide(upgrade_guide || write_upgrade_guide);
+        let response =
+            generate_for_package(&service, "release_notes", context, package.as_ref(), raw).await?;
+
+        if is_json_output() {
+            // The suggestion is part of the JSON result
+            crate::output::print_response_json("release-notes", &response)?;
+        } else {
+            println!("{response}");
+        }
+
+        if suggest_version && !is_json_output() {
+            print_suggested_version(&response, raw);
+        }
+
+        // Handle --update flag
+        if update {
+            let release_notes_path =
+                package_file(package.as_ref(), file.as_deref(), "RELEASE_NOTES.md");
+            update_release_notes_file(&release_notes_path, &response.to_string())?;
+        }
+
+        if write_upgrade_guide {
+            write_upgrade_guide_file(&response, package.as_ref(), version_name.as_deref())?;
+        }
+
+        if write && let StructuredResponse::ReleaseNotes(notes) = &response {
+            let suggested = notes
+                .suggested_version
+                .as_ref()
+                .and_then(|suggestion| suggestion.next.as_deref());
+            let version = version_name.as_deref().or(suggested);
+            let path = service
+                .config()
+                .outputs
+                .path(Artifact::ReleaseNotes, version, None);
+            let path = package_file(package.as_ref(), Some(&path), "");
+            write_artifact_file(Artifact::ReleaseNotes, &path, &notes.content, raw)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the Upgrade Guide section of release notes to
+/// `docs/upgrading/<version>.md`, inside the package when scoped to one
+///
+/// The version is `--version-name`, else the suggested next version.
+fn write_upgrade_guide_file(
+    response: &crate::agents::StructuredResponse,
+    package: Option<&crate::changelog::Package>,
+    version_name: Option<&str>,
+) -> anyhow::Result<()> {
+    use crate::upgrade_guide::{guide_path, section};
+
+    let crate::agents::StructuredResponse::ReleaseNotes(notes) = response else {
+        return Ok(());
+    };
+    let Some(guide) = section(&notes.content) else {
+        ui::print_info("No breaking changes detected, so no upgrade guide was written");
+        return Ok(());
+    };
+    let suggested = notes
+        .suggested_version
+        .as_ref()
+        .and_then(|suggestion| suggestion.next.as_deref());
+    let Some(version) = version_name.or(suggested) else {
+        ui::print_warning("No version for the upgrade guide file; pass --version-name");
+        return Ok(());
+    };
+
+    crate::read_only::ensure_writable("Writing the upgrade guide")?;
+    let path = package_file(package, Some(&guide_path(version)), "");
+    if let Some(dir) = std::path::Path::new(&path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, format!("# Upgrading to {version}\n\n{guide}"))?;
+    ui::print_success(&format!(
+        "✨ Upgrade guide written to {}",
+        path.bright_green()
+    ));
+    Ok(())
+}
+
+fn print_suggested_version(response: &crate::agents::StructuredResponse, raw: bool) {
+    match response {
+        crate::agents::StructuredResponse::ReleaseNotes(crate::types::MarkdownReleaseNotes {
+            suggested_version: Some(suggestion),
+            ..
+        }) => {
+            if raw {
+                println!("\nSuggested version: {suggestion}");
+            } else {
+                ui::print_newline();
+                ui::print_info(&format!(
+                    "Suggested version: {}",
+                    suggestion.to_string().bright_cyan()
+                ));
+            }
+        }
+        _ => ui::print_warning("No version suggestion: the commit range couldn't be read"),
+    }
+}
+
+/// Prepend release notes to the file, creating it if needed
+fn update_release_notes_file(
+    release_notes_path: &str,
+    formatted_content: &str,
+) -> anyhow::Result<()> {
+    use std::fs;
+    use std::path::Path;
+
+    crate::read_only::ensure_writable("Writing release notes")?;
+    let update_spinner = ui::create_spinner(&format!(
+        "Updating release notes file at {release_notes_path}..."
+    ));
+
+    // Write or append to file
+    let path = Path::new(release_notes_path);
+    let result = if path.exists() {
+        // Prepend to existing file
+        let existing = fs::read_to_string(path)?;
+        fs::write(path, format!("{formatted_content}\n\n---\n\n{existing}"))
+    } else {
+        // Create new file
+        fs::write(path, formatted_content)
+    };
+
+    match result {
+        Ok(()) => {
+            update_spinner.finish_and_clear();
+            ui::print_success(&format!(
+                "✨ Release notes successfully updated at {}",
+                release_notes_path.bright_green()
+            ));
+            Ok(())
+        }
+        Err(e) => {
+            update_spinner.finish_and_clear();
+            ui::print_error(&format!("Failed to update release notes file: {e}"));
+            Err(e.into())
+        }
+    }
+}
+
+/// Handle the release-check command
+async fn handle_release_check(
+    common: CommonParams,
+    from: Option<String>,
+    to: Option<String>,
+    raw: bool,
+    no_ai: bool,
+    strict: bool,
+    repository_url: Option<String>,
+) -> anyhow::Result<()> {
+    use crate::agents::IrisAgentService;
+    use crate::release_readiness::ReleaseChecklist;
+    use crate::types::render_markdown_for_terminal;
+    use anyhow::Context;
+
+    log_debug!(
+        "Handling 'release-check' command with from: {:?}, to: {:?}, raw: {}, no_ai: {}, strict: {}",
+        from,
+        to,
+        raw,
+        no_ai,
+        strict
+    );
+
+    if !raw {
+        ui::print_version(crate_version!());
+        ui::print_newline();
+    }
+
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    let repo = service
+        .git_repo()
+        .context("Release checks need a git repository")?;
+    let to = to.unwrap_or_else(|| "HEAD".to_string());
+    let checklist = ReleaseChecklist::build(repo, from, &to)?;
+
+    let commentary = if no_ai {
+        None
+    } else {
+        let spinner = (!raw).then(|| ui::create_spinner("Reviewing release readiness..."));
+        let response = service
+            .execute_task_with_prompt("release_readiness", &checklist.to_prompt())
+            .await?;
+        if let Some(s) = spinner {
+            s.finish_and_clear();
+        }
+        Some(response.to_string())
+    };
+
+    let mut markdown = checklist.to_markdown();
+    if let Some(commentary) = commentary {
+        markdown.push_str(&format!("\n## Commentary\n\n{}\n", commentary.trim()));
+    }
+    if raw {
+        println!("{}", markdown.trim_end());
+    } else {
+        println!("{}", render_markdown_for_terminal(&markdown));
+    }
+
+    if strict && !checklist.is_ready() {
+        anyhow::bail!("Release checklist has failing checks");
+    }
+    Ok(())
+}
+
+/// Options for the `onboard` command
+#[allow(clippy::struct_excessive_bools)]
+struct OnboardOptions {
+    days: u32,
+    raw: bool,
+    no_ai: bool,
+    file: Option<String>,
+    studio: bool,
+}
+
+/// Handle the `Onboard` command - build, print, save or open the report
+async fn handle_onboard(
+    common: CommonParams,
+    options: OnboardOptions,
+    repository_url: Option<String>,
+) -> anyhow::Result<()> {
+    use crate::agents::IrisAgentService;
+    use crate::onboarding::OnboardingReport;
+    use crate::types::render_markdown_for_terminal;
+    use anyhow::Context;
+    use std::sync::Arc;
+
+    if !options.raw {
+        ui::print_version(crate_version!());
+        ui::print_newline();
+    }
+
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    let repo = service
+        .git_repo()
+        .cloned()
+        .context("Onboarding needs a git repository")?;
+    let report = OnboardingReport::build(&repo, options.days)?;
+
+    let overview = if options.no_ai {
+        None
+    } else {
+        let spinner = (!options.raw).then(|| ui::create_spinner("Studying the project..."));
+        let response = service
+            .execute_task_with_prompt("onboard", &report.to_prompt())
+            .await?;
+        if let Some(s) = spinner {
+            s.finish_and_clear();
+        }
+        Some(response.to_string())
+    };
+    let markdown = report.to_markdown(overview.as_deref());
+
+    if options.studio {
+        let path = options.file.map_or_else(
+            || std::env::temp_dir().join(format!("{}-onboarding.md", report.project)),
+            std::path::PathBuf::from,
+        );
+        std::fs::write(&path, &markdown)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        let config = service.config().clone();
+        return crate::studio::run_studio_with_document(
+            config,
+            Some(repo),
+            Some(Arc::new(service)),
+            path,
+        );
+    }
+
+    if let Some(path) = options.file {
+        crate::read_only::ensure_writable("Writing the onboarding report")?;
+        std::fs::write(&path, &markdown).with_context(|| format!("Failed to write {path}"))?;
+        ui::print_success(&format!("Onboarding report written to {path}"));
+    } else if options.raw {
+        println!("{}", markdown.trim_end());
+    } else {
+        println!("{}", render_markdown_for_terminal(&markdown));
+    }
+    Ok(())
+}
+
+/// Options for the `explain-repo` command
+#[allow(clippy::struct_excessive_bools)]
+struct ExplainRepoOptions {
+    raw: bool,
+    no_ai: bool,
+    refresh: bool,
+    file: Option<String>,
+    studio: bool,
+}
+
+/// Handle the `ExplainRepo` command - print, save or open the overview
+async fn handle_explain_repo(
+    common: CommonParams,
+    options: ExplainRepoOptions,
+    repository_url: Option<String>,
+) -> anyhow::Result<()> {
+    use crate::agents::IrisAgentService;
+    use crate::architecture::{ArchitectureFacts, cached_overview};
+    use crate::types::render_markdown_for_terminal;
+    use anyhow::Context;
+    use std::sync::Arc;
+
+    if !options.raw {
+        ui::print_version(crate_version!());
+        ui::print_newline();
+    }
+
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    let repo = service
+        .git_repo()
+        .cloned()
+        .context("Explaining a repository needs a git repository")?;
+
+    let (markdown, cached_path) = if options.no_ai {
+        (ArchitectureFacts::gather(&repo)?.to_markdown(None), None)
+    } else {
+        let spinner = (!options.raw).then(|| ui::create_spinner("Mapping the architecture..."));
+        let service = &service;
+        let (markdown, path) = cached_overview(&repo, options.refresh, |prompt| async move {
+            let response = service
+                .execute_task_with_prompt("explain_repo", &prompt)
+                .await?;
+            Ok(response.to_string())
+        })
+        .await?;
+        if let Some(s) = spinner {
+            s.finish_and_clear();
+        }
+        (markdown, Some(path))
+    };
+
+    if options.studio {
+        let path = if let Some(path) = cached_path {
+            path
+        } else {
+            let path = std::env::temp_dir().join("git-iris-architecture.md");
+            std::fs::write(&path, &markdown)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            path
+        };
+        let config = service.config().clone();
+        return crate::studio::run_studio_with_document(
+            config,
+            Some(repo),
+            Some(Arc::new(service)),
+            path,
+        );
+    }
+
+    if let Some(path) = options.file {
+        crate::read_only::ensure_writable("Writing the architecture overview")?;
+        std::fs::write(&path, &markdown).with_context(|| format!("Failed to write {path}"))?;
+        ui::print_success(&format!("Architecture overview written to {path}"));
+    } else if options.raw {
+        println!("{}", markdown.trim_end());
+    } else {
+        println!("{}", render_markdown_for_terminal(&markdown));
+    }
+    Ok(())
+}
+
+/// Handle the `Recap` command - gather recent work and have Iris narrate it
+async fn handle_recap(
+    common: CommonParams,
+    reflog: usize,
+    raw: bool,
+    no_ai: bool,
+    repository_url: Option<String>,
+) -> anyhow::Result<()> {
+    use crate::agents::IrisAgentService;
+    use crate::recap::RecapFacts;
+    use crate::types::render_markdown_for_terminal;
+    use anyhow::Context;
+
+    if !raw {
+        ui::print_version(crate_version!());
+        ui::print_newline();
+    }
+
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    let repo = service
+        .git_repo()
+        .context("A recap needs a git repository")?;
+    let facts = RecapFacts::gather(repo, reflog)?;
+
+    let markdown = if no_ai {
+        facts.to_markdown()
+    } else if facts.is_empty() {
+        "git-iris has no record of recent work on this branch.".to_string()
+    } else {
+        let spinner = (!raw).then(|| ui::create_spinner("Remembering where you left off..."));
+        let response = service
+            .execute_task_with_prompt("recap", &facts.to_prompt())
+            .await?;
+        if let Some(s) = spinner {
+            s.finish_and_clear();
+        }
+        response.to_string()
+    };
+
+    if raw {
+        println!("{}", markdown.trim_end());
+    } else {
+        println!("{}", render_markdown_for_terminal(&markdown));
+    }
+    Ok(())
+}
+
+/// Options for the `recover` command
+struct RecoverOptions {
+    count: usize,
+    restore: Option<usize>,
+    branch: Option<String>,
+    reset: bool,
+    no_ai: bool,
+    yes: bool,
+}
+
+/// Restore `HEAD@{index}` as a new branch or by resetting the current branch
+fn restore_reflog_entry(
+    repo: &crate::git::GitRepo,
+    index: usize,
+    options: RecoverOptions,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let entry = repo
+        .get_reflog(index + 1)?
+        .into_iter()
+        .find(|e| e.index == index)
+        .with_context(|| format!("HEAD@{{{index}}} is not in the reflog"))?;
+    ui::print_info(&format!("Restoring {entry}"));
+
+    if options.reset {
+        let branch = repo.get_current_branch()?;
+        ui::print_warning(&format!(
+            "This moves `{branch}` to {} and discards uncommitted changes.",
+            entry.short_id()
+        ));
+        ui::print_message(&format!("  git reset --hard {}", entry.short_id()));
+        if !options.yes && !ui::confirm("Run this command?") {
+            ui::print_info("Nothing changed");
+            return Ok(());
+        }
+        repo.reset_to_reflog(index)?;
+        ui::print_success(&format!(
+            "Reset `{branch}` to {}; the previous tip is HEAD@{{1}}",
+            entry.short_id()
+        ));
+    } else {
+        let name = options
+            .branch
+            .unwrap_or_else(|| format!("recovered/{}", entry.short_id()));
+        ui::print_message(&format!("  git branch {name} {}", entry.short_id()));
+        if !options.yes && !ui::confirm("Run this command?") {
+            ui::print_info("Nothing changed");
+            return Ok(());
+        }
+        repo.create_branch_from_reflog(index, &name)?;
+        ui::print_success(&format!("Created branch `{name}` at {}", entry.short_id()));
+    }
+    Ok(())
+}
+
+/// Handle the `Recover` command - list reflog states or restore one
+async fn handle_recover(
+    common: CommonParams,
+    options: RecoverOptions,
+    repository_url: Option<String>,
+) -> anyhow::Result<()> {
+    use crate::agents::IrisAgentService;
+    use anyhow::Context;
+    use std::collections::HashMap;
+
+    ui::print_version(crate_version!());
+    ui::print_newline();
+
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    let repo = service
+        .git_repo()
+        .context("Recovery needs a git repository")?;
+    if repo.is_remote() {
+        anyhow::bail!("Recovery only works on local repositories");
+    }
+
+    if let Some(index) = options.restore {
+        return restore_reflog_entry(repo, index, options);
+    }
+
+    let entries = repo.get_reflog(options.count)?;
+    if entries.is_empty() {
+        ui::print_info("The reflog is empty; there is nothing to recover");
+        return Ok(());
+    }
+
+    let summaries: HashMap<String, String> = if options.no_ai {
+        HashMap::new()
+    } else {
+        let spinner = ui::create_spinner("Summarizing reflog...");
+        let prompt = entries
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let response = service.execute_task_with_prompt("recover", &prompt).await;
+        spinner.finish_and_clear();
+        match response {
+            Ok(response) => response
+                .to_string()
+                .lines()
+                .filter_map(|line| line.trim().split_once(": "))
+                .map(|(selector, summary)| (selector.to_string(), summary.to_string()))
+                .collect(),
+            Err(e) => {
+                ui::print_warning(&format!("AI summaries unavailable: {e}"));
+                HashMap::new()
+            }
+        }
+    };
+
+    for entry in &entries {
+        let summary = summaries
+            .get(&entry.selector())
+            .cloned()
+            .unwrap_or_else(|| format!("{}: {}", entry.action, entry.detail));
+        let line = format!(
+            "{:<10} {}  {}  {}",
+            entry.selector(),
+            entry.short_id(),
+            entry
+                .time
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M"),
+            summary
+        );
+        if entry.is_lost() {
+            ui::print_warning(&format!("{line}  [lost]"));
+        } else {
+            ui::print_message(&line);
+        }
+    }
+
+    ui::print_newline();
+    ui::print_info(
+        "Restore a state with `git-iris recover --restore <N>` (creates a branch) or add `--reset` to move the current branch",
+    );
+    Ok(())
+}
+
+/// Handle the `Adr` command - draft a record and write it to the ADR directory
+async fn handle_adr(
+    common: CommonParams,
+    from: &str,
+    to: &str,
+    title: Option<String>,
+    dir: &str,
+    print: bool,
+    decision: &str,
+    repository_url: Option<String>,
+) -> anyhow::Result<()> {
+    use crate::adr::{AdrDraft, next_number, prompt};
+    use crate::agents::IrisAgentService;
+    use crate::types::render_markdown_for_terminal;
+    use anyhow::Context;
+
+    if !print {
+        ui::print_version(crate_version!());
+        ui::print_newline();
+    }
+
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    let repo = service
+        .git_repo()
+        .context("Drafting an ADR needs a git repository")?;
+    let dir = repo.repo_path().join(dir);
+
+    let spinner = (!print).then(|| ui::create_spinner("Drafting the decision record..."));
+    let response = service
+        .execute_task_with_prompt("adr", &prompt(from, to, decision, title.as_deref()))
+        .await?;
+    if let Some(s) = spinner {
+        s.finish_and_clear();
+    }
+    let draft =
+        AdrDraft::from_markdown(next_number(&dir), &response.to_string(), title.as_deref())?;
+
+    if print {
+        println!("{}", draft.content.trim_end());
+        return Ok(());
+    }
+    let path = draft.write(&dir)?;
+    println!("{}", render_markdown_for_terminal(&draft.content));
+    ui::print_success(&format!("ADR written to {}", path.display()));
+    Ok(())
+}
+
+/// Handle the `Plan` command - draft a plan for a task, or show progress on
+/// the saved one
+async fn handle_plan(
+    common: CommonParams,
+    task: &str,
+    print: bool,
+    clear: bool,
+    repository_url: Option<String>,
+) -> anyhow::Result<()> {
+    use crate::agents::IrisAgentService;
+    use crate::companion::CompanionStorage;
+    use crate::plan::{Plan, PlanProgress, prompt};
+    use anyhow::Context;
+
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    let repo = service
+        .git_repo()
+        .context("Planning needs a git repository")?;
+    let storage = CompanionStorage::new(repo.repo_path())?;
+
+    if clear {
+        storage.clear_plan()?;
+        ui::print_success("Plan cleared");
+        return Ok(());
+    }
+
+    if task.trim().is_empty() {
+        let Some(plan) = storage.load_plan()? else {
+            ui::print_info("No saved plan. Start one with `git-iris plan \"<task>\"`");
+            return Ok(());
+        };
+        let progress = PlanProgress::load(&repo.open_repo()?, plan)?;
+        print!("{}", progress.plan.checklist(&progress.landed));
+        ui::print_newline();
+        ui::print_info(&format!(
+            "{}/{} planned commits landed",
+            progress.done(),
+            progress.plan.commits.len()
+        ));
+        return Ok(());
+    }
+
+    if !print {
+        ui::print_version(crate_version!());
+        ui::print_newline();
+    }
+    let spinner = (!print).then(|| ui::create_spinner("Planning the work..."));
+    let response = service
+        .execute_task_with_prompt("plan", &prompt(task))
+        .await?;
+    if let Some(s) = spinner {
+        s.finish_and_clear();
+    }
+    let markdown = response.to_string();
+    if print {
+        println!("{}", markdown.trim_end());
+        return Ok(());
+    }
+
+    // An unborn branch has no HEAD; every commit then counts as progress
+    let base = repo.head_commit_id().unwrap_or_default();
+    let plan = Plan::from_markdown(task, &markdown, &base)?;
+    storage.save_plan(&plan)?;
+    print!("{}", plan.checklist(&vec![None; plan.commits.len()]));
+    ui::print_newline();
+    if !plan.branch.is_empty() {
+        ui::print_info(&format!("Start with `git switch -c {}`", plan.branch));
+    }
+    ui::print_success("Plan saved; run `git-iris plan` to check progress");
+    Ok(())
+}
+
+/// Handle the `Ask` command - stream an answer about a diff to stdout
+async fn handle_ask(
     common: CommonParams,
-    from: String,
-    to: Option<String>,
-    raw: bool,
+    question: &str,
+    range: Option<&str>,
     repository_url: Option<String>,
-    update: bool,
-    file: Option<String>,
-    version_name: Option<String>,
 ) -> anyhow::Result<()> {
-    log_debug!(
-        "Handling 'release-notes' command with common: {:?}, from: {}, to: {:?}, raw: {}, update: {}, file: {:?}, version_name: {:?}",
-        common,
-        from,
-        to,
-        raw,
-        update,
-        file,
-        version_name
-    );
+    use crate::agents::IrisAgentService;
+    use crate::ask::{AskScope, prompt};
+    use crate::types::verify_sources;
+    use anyhow::Context;
+    use std::io::Write;
 
-    // For raw output, skip all formatting
-    if !raw {
-        ui::print_version(crate_version!());
+    let scope = range.map_or(Ok(AskScope::Staged), AskScope::parse_range)?;
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    let repo = service
+        .git_repo()
+        .context("Asking about changes needs a git repository")?;
+    let diff = scope.diff(repo)?;
+    if diff.trim().is_empty() {
+        anyhow::bail!("There are no changes in {scope} to ask about");
+    }
+
+    let mut spinner = Some(ui::create_spinner("Iris is reading the changes..."));
+    let mut stdout = std::io::stdout();
+    let response = service
+        .execute_prompt_streaming("chat", &prompt(question, &scope, &diff), |chunk, _| {
+            if let Some(s) = spinner.take() {
+                s.finish_and_clear();
+            }
+            let _ = stdout.write_all(chunk.as_bytes());
+            let _ = stdout.flush();
+        })
+        .await;
+    if let Some(s) = spinner {
+        s.finish_and_clear();
+    }
+    let answer = response?.to_string();
+    if !answer.ends_with('\n') {
+        println!();
+    }
+
+    let unverified: Vec<_> = verify_sources(&answer, repo)
+        .into_iter()
+        .filter(|source| !source.is_verified())
+        .collect();
+    if !unverified.is_empty() {
         ui::print_newline();
+        for source in unverified {
+            ui::print_warning(&format!(
+                "Unverified source {}: {}",
+                source.citation,
+                source.problem.unwrap_or_default()
+            ));
+        }
     }
+    Ok(())
+}
 
-    use crate::agents::{IrisAgentService, TaskContext};
-    use std::fs;
-    use std::path::Path;
+/// Options for the `rebase` command
+#[allow(clippy::struct_excessive_bools)]
+struct RebaseOptions {
+    no_ai: bool,
+    print: bool,
+    yes: bool,
+    studio: bool,
+}
 
-    // Create structured context for release notes with version_name and current date
-    let context = TaskContext::for_changelog(from, to, version_name, None);
+/// Plan a rebase of the current branch, have Iris suggest groupings, and run it
+async fn handle_rebase(
+    common: CommonParams,
+    base: String,
+    options: RebaseOptions,
+    repository_url: Option<String>,
+) -> anyhow::Result<()> {
+    use crate::agents::IrisAgentService;
+    use anyhow::Context;
 
-    // Create spinner for progress indication (skip for raw output)
-    let spinner = if raw {
-        None
-    } else {
-        Some(ui::create_spinner("Initializing Iris..."))
-    };
+    if options.studio {
+        return handle_studio(
+            common,
+            Some("rebase".to_string()),
+            Some(base),
+            None,
+            repository_url,
+        )
+        .await;
+    }
 
-    // Use IrisAgentService for agent execution
     let service = IrisAgentService::from_common_params(&common, repository_url)?;
-    let response = service.execute_task("release_notes", context).await?;
-
-    // Finish spinner
-    if let Some(s) = spinner {
-        s.finish_and_clear();
+    let repo = service
+        .git_repo()
+        .context("Rebasing needs a git repository")?;
+    let mut plan = repo.rebase_plan(&base)?;
+    if plan.steps.is_empty() {
+        ui::print_info(&format!(
+            "{} has no commits since {}; there is nothing to rebase",
+            plan.branch, plan.base
+        ));
+        return Ok(());
     }
 
-    println!("{response}");
+    if !options.no_ai {
+        let spinner = ui::create_spinner("Iris is planning the rebase...");
+        let response = service
+            .execute_task_with_prompt("rebase", &plan.prompt())
+            .await;
+        spinner.finish_and_clear();
+        let applied = response.and_then(|response| plan.apply_suggestion(&response.to_string()));
+        if let Err(e) = applied {
+            ui::print_warning(&format!(
+                "No suggestion from Iris, picking every commit: {e}"
+            ));
+        }
+    }
 
-    // Handle --update flag
-    if update {
-        let release_notes_path = file.unwrap_or_else(|| "RELEASE_NOTES.md".to_string());
-        let formatted_content = response.to_string();
+    ui::print_info(&format!(
+        "Rebase {} onto {} ({} commits → {})",
+        plan.branch,
+        plan.base,
+        plan.steps.len(),
+        plan.result_count()
+    ));
+    ui::print_newline();
+    ui::print_message(plan.todo().trim_end());
+    ui::print_newline();
 
-        let update_spinner = ui::create_spinner(&format!(
-            "Updating release notes file at {release_notes_path}..."
-        ));
+    if plan.is_unchanged() {
+        ui::print_info("The plan leaves the branch as it is");
+        return Ok(());
+    }
+    if options.print {
+        return Ok(());
+    }
+    plan.validate()?;
+    if !options.yes && !ui::confirm("Run this rebase?") {
+        return Ok(());
+    }
 
-        // Write or append to file
-        let path = Path::new(&release_notes_path);
-        let result = if path.exists() {
-            // Prepend to existing file
-            let existing = fs::read_to_string(path)?;
-            fs::write(path, format!("{formatted_content}\n\n---\n\n{existing}"))
-        } else {
-            // Create new file
-            fs::write(path, &formatted_content)
-        };
+    let outcome = repo.execute_rebase(&plan)?;
+    ui::print_success(&format!(
+        "Rebased {} into {} commits",
+        plan.branch, outcome.commits
+    ));
+    ui::print_info(&format!(
+        "Undo with `git reset --hard {}` or `git-iris recover`",
+        &outcome.old_head[..7.min(outcome.old_head.len())]
+    ));
+    Ok(())
+}
 
-        match result {
-            Ok(()) => {
-                update_spinner.finish_and_clear();
-                ui::print_success(&format!(
-                    "✨ Release notes successfully updated at {}",
-                    release_notes_path.bright_green()
-                ));
-            }
-            Err(e) => {
-                update_spinner.finish_and_clear();
-                ui::print_error(&format!("Failed to update release notes file: {e}"));
-                return Err(e.into());
-            }
+/// Run a plumbing command
+async fn handle_plumbing(
+    action: PlumbingAction,
+    repository_url: Option<String>,
+) -> anyhow::Result<()> {
+    match action {
+        PlumbingAction::Message { common, json } => {
+            crate::plumbing::handle_message(common, json, repository_url).await
+        }
+        PlumbingAction::Review {
+            common,
+            json,
+            include_unstaged,
+            commit,
+            from,
+            to,
+        } => {
+            crate::plumbing::handle_review(
+                common,
+                json,
+                include_unstaged,
+                commit,
+                from,
+                to,
+                repository_url,
+            )
+            .await
+        }
+        PlumbingAction::Adr {
+            common,
+            json,
+            from,
+            to,
+            title,
+            dir,
+            decision,
+        } => {
+            crate::plumbing::handle_adr(
+                common,
+                json,
+                &from,
+                &to,
+                title,
+                &dir,
+                &decision.join(" "),
+                repository_url,
+            )
+            .await
         }
+        PlumbingAction::CheckMessage { file } => crate::plumbing::handle_check_message(&file),
     }
-
-    Ok(())
 }
 
 /// Handle the command based on parsed arguments
-#[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
 pub async fn handle_command(
     command: Commands,
     repository_url: Option<String>,
@@ -1020,12 +3182,28 @@ pub async fn handle_command(
             .await
         }
         Commands::Config {
+            action: Some(action),
+            ..
+        } => match action {
+            ConfigAction::Export { bundle } => commands::handle_config_export_command(&bundle),
+            ConfigAction::Import { bundle, project } => {
+                commands::handle_config_import_command(&bundle, project)
+            }
+        },
+        Commands::Config {
+            action: None,
+            migrate_secrets: true,
+            ..
+        } => commands::handle_migrate_secrets_command(),
+        Commands::Config {
+            action: None,
             common,
             api_key,
             fast_model,
             token_limit,
             param,
             subagent_timeout,
+            migrate_secrets: false,
         } => handle_config(
             &common,
             api_key,
@@ -1043,7 +3221,13 @@ pub async fn handle_command(
             commit,
             from,
             to,
+            pr,
+            format,
+            ci,
+            fail_on,
+            write,
         } => {
+            let ci = ci.then_some(fail_on);
             handle_review(
                 common,
                 print,
@@ -1053,6 +3237,10 @@ pub async fn handle_command(
                 commit,
                 from,
                 to,
+                pr,
+                format,
+                ci,
+                write,
             )
             .await
         }
@@ -1064,6 +3252,8 @@ pub async fn handle_command(
             update,
             file,
             version_name,
+            write,
+            scope,
         } => {
             handle_changelog(
                 common,
@@ -1071,9 +3261,11 @@ pub async fn handle_command(
                 to,
                 raw,
                 repository_url,
-                update,
+                update || write,
                 file,
                 version_name,
+                write,
+                scope,
             )
             .await
         }
@@ -1085,6 +3277,11 @@ pub async fn handle_command(
             update,
             file,
             version_name,
+            suggest_version,
+            upgrade_guide,
+            write_upgrade_guide,
+            write,
+            scope,
         } => {
             handle_release_notes(
                 common,
@@ -1092,12 +3289,148 @@ pub async fn handle_command(
                 to,
                 raw,
                 repository_url,
-                update,
+                ReleaseNotesOptions {
+                    update,
+                    file,
+                    version_name,
+                    suggest_version,
+                    upgrade_guide,
+                    write_upgrade_guide,
+                    write,
+                },
+                scope,
+            )
+            .await
+        }
+        Commands::ReleaseCheck {
+            common,
+            from,
+            to,
+            raw,
+            no_ai,
+            strict,
+        } => handle_release_check(common, from, to, raw, no_ai, strict, repository_url).await,
+        Commands::Onboard {
+            common,
+            days,
+            raw,
+            no_ai,
+            file,
+            studio,
+        } => {
+            let options = OnboardOptions {
+                days,
+                raw,
+                no_ai,
                 file,
-                version_name,
+                studio,
+            };
+            handle_onboard(common, options, repository_url).await
+        }
+        Commands::Adr {
+            common,
+            from,
+            to,
+            title,
+            dir,
+            print,
+            decision,
+        } => {
+            handle_adr(
+                common,
+                &from,
+                &to,
+                title,
+                &dir,
+                print,
+                &decision.join(" "),
+                repository_url,
+            )
+            .await
+        }
+        Commands::Plan {
+            common,
+            print,
+            clear,
+            task,
+        } => handle_plan(common, &task.join(" "), print, clear, repository_url).await,
+        Commands::Ask {
+            common,
+            range,
+            staged: _,
+            question,
+        } => {
+            handle_ask(
+                common,
+                &question.join(" "),
+                range.as_deref(),
+                repository_url,
+            )
+            .await
+        }
+        Commands::Rebase {
+            common,
+            base,
+            no_ai,
+            print,
+            yes,
+            studio,
+        } => {
+            handle_rebase(
+                common,
+                base,
+                RebaseOptions {
+                    no_ai,
+                    print,
+                    yes,
+                    studio,
+                },
+                repository_url,
             )
             .await
         }
+        Commands::Recover {
+            common,
+            count,
+            restore,
+            branch,
+            reset,
+            no_ai,
+            yes,
+        } => {
+            let options = RecoverOptions {
+                count,
+                restore,
+                branch,
+                reset,
+                no_ai,
+                yes,
+            };
+            handle_recover(common, options, repository_url).await
+        }
+        Commands::ExplainRepo {
+            common,
+            raw,
+            no_ai,
+            refresh,
+            file,
+            studio,
+        } => {
+            let options = ExplainRepoOptions {
+                raw,
+                no_ai,
+                refresh,
+                file,
+                studio,
+            };
+            handle_explain_repo(common, options, repository_url).await
+        }
+        Commands::Recap {
+            common,
+            reflog,
+            raw,
+            no_ai,
+        } => handle_recap(common, reflog, raw, no_ai, repository_url).await,
         Commands::ProjectConfig {
             common,
             fast_model,
@@ -1114,11 +3447,82 @@ pub async fn handle_command(
             subagent_timeout,
             print,
         ),
+        Commands::ExportSession { format, file } => {
+            commands::handle_export_session_command(format, file.as_deref())
+        }
+        Commands::Undo => commands::handle_undo_command(),
+        Commands::Trace { count, capability } => {
+            commands::handle_trace_command(count, capability.as_deref())
+        }
+        Commands::History { action } => match action {
+            HistoryAction::Search {
+                query,
+                mode,
+                since,
+                limit,
+            } => commands::handle_history_search_command(
+                &query.join(" "),
+                mode,
+                since.as_deref(),
+                limit,
+            ),
+        },
+        Commands::Stats { action } => match action {
+            StatsAction::Messages { common, count, raw } => {
+                commands::handle_stats_messages_command(&common, count, raw, repository_url)
+            }
+            StatsAction::Usage { days, raw } => commands::handle_stats_usage_command(days, raw),
+        },
+        Commands::Prompt { action } => match action {
+            PromptAction::Show { capability } => commands::handle_prompt_show_command(&capability),
+        },
+        Commands::Plumbing { action } => handle_plumbing(action, repository_url).await,
+        Commands::Hooks { action } => match action {
+            HooksAction::Install {
+                commit_msg,
+                uninstall,
+            } => commands::handle_hooks_install_command(commit_msg, uninstall),
+        },
+        Commands::SelfUpdate { check } => commands::handle_self_update_command(check).await,
+        Commands::Usage { days, raw } => commands::handle_usage_command(days, raw),
+        Commands::Bench { models, tasks, raw } => {
+            commands::handle_bench_command(&models, &tasks, raw).await
+        }
+        Commands::RemoteCache { action } => match action {
+            RemoteCacheAction::List => commands::handle_remote_cache_list_command(),
+            RemoteCacheAction::Clear { url, .. } => {
+                commands::handle_remote_cache_clear_command(url.as_deref())
+            }
+        },
         Commands::ListPresets => commands::handle_list_presets_command(),
-        Commands::Themes => {
+        Commands::Preset { action } => match action {
+            PresetAction::List => commands::handle_list_presets_command(),
+            PresetAction::Show { name } => commands::handle_preset_show_command(&name),
+            PresetAction::New {
+                name,
+                preset_type,
+                from,
+                user,
+                no_edit,
+            } => commands::handle_preset_new_command(
+                &name,
+                preset_type,
+                from.as_deref(),
+                user,
+                !no_edit,
+            ),
+            PresetAction::Edit { name } => commands::handle_preset_edit_command(&name),
+        },
+        Commands::Themes { action: None } => {
             handle_themes();
             Ok(())
         }
+        Commands::Themes {
+            action: Some(ThemeAction::Export { format }),
+        } => {
+            print!("{}", theme::export(&theme::current(), format));
+            Ok(())
+        }
         Commands::Completions { shell } => {
             handle_completions(shell);
             Ok(())
@@ -1130,7 +3534,14 @@ pub async fn handle_command(
             copy,
             from,
             to,
-        } => handle_pr(common, print, raw, copy, from, to, repository_url).await,
+            split,
+        } => {
+            if split {
+                handle_pr_split(common, raw, from, to, repository_url).await
+            } else {
+                handle_pr(common, print, raw, copy, from, to, repository_url).await
+            }
+        }
         Commands::Studio {
             common,
             mode,
@@ -1241,8 +3652,6 @@ async fn handle_pr_with_agent(
     repository_url: Option<String>,
 ) -> anyhow::Result<()> {
     use crate::agents::{IrisAgentService, StructuredResponse, TaskContext};
-    use crate::instruction_presets::PresetType;
-    use arboard::Clipboard;
 
     // Check if the preset is appropriate for PR descriptions (skip for raw output only)
     if !raw
@@ -1279,22 +3688,22 @@ async fn handle_pr_with_agent(
         return Err(anyhow::anyhow!("Expected pull request response"));
     };
 
+    if is_json_output() {
+        return crate::output::print_json("pr", &generated_pr);
+    }
+
     // Handle clipboard copy
     if copy {
         let raw_content = generated_pr.raw_content();
-        match Clipboard::new() {
-            Ok(mut clipboard) => match clipboard.set_text(raw_content) {
-                Ok(()) => {
-                    ui::print_success("PR description copied to clipboard");
-                }
-                Err(e) => {
-                    ui::print_error(&format!("Failed to copy to clipboard: {e}"));
-                    // Fall back to printing raw
-                    println!("{raw_content}");
-                }
-            },
+        match crate::clipboard::copy_text(raw_content, service.config().clipboard_osc52) {
+            Ok(crate::clipboard::CopyMethod::System) => {
+                ui::print_success("PR description copied to clipboard");
+            }
+            Ok(crate::clipboard::CopyMethod::Osc52) => {
+                ui::print_success("PR description sent to terminal clipboard (OSC52)");
+            }
             Err(e) => {
-                ui::print_error(&format!("Clipboard unavailable: {e}"));
+                ui::print_error(&format!("Failed to copy to clipboard: {e}"));
                 // Fall back to printing raw
                 println!("{raw_content}");
             }
@@ -1333,6 +3742,10 @@ async fn handle_pr(
         to
     );
 
+    if copy && is_json_output() {
+        anyhow::bail!("--output json can't be combined with --copy");
+    }
+
     // For raw output, skip version banner (piped output should be clean)
     // For copy mode, show the banner since we're giving user feedback
     if !raw {
@@ -1343,6 +3756,116 @@ async fn handle_pr(
     handle_pr_with_agent(common, print, raw, copy, from, to, repository_url).await
 }
 
+/// Handle `pr --split` - propose and describe one PR per ownership group
+async fn handle_pr_split(
+    common: CommonParams,
+    raw: bool,
+    from: Option<String>,
+    to: Option<String>,
+    repository_url: Option<String>,
+) -> anyhow::Result<()> {
+    use crate::agents::IrisAgentService;
+    use crate::context::RecentCommit;
+    use crate::pr_split::{CodeOwners, CommitFiles, plan_split, should_split};
+    use crate::risk::ChangedFile;
+    use crate::types::render_markdown_for_terminal;
+    use anyhow::Context;
+
+    log_debug!(
+        "Handling 'pr --split' command with from: {:?}, to: {:?}, raw: {}",
+        from,
+        to,
+        raw
+    );
+
+    if is_json_output() {
+        anyhow::bail!("--output json isn't supported with --split");
+    }
+
+    if !raw {
+        ui::print_version(crate_version!());
+        ui::print_newline();
+    }
+
+    let from = from.unwrap_or_else(|| "main".to_string());
+    let to = to.unwrap_or_else(|| "HEAD".to_string());
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    let repo = service
+        .git_repo()
+        .context("PR splitting needs a git repository")?;
+
+    let files: Vec<ChangedFile> = repo
+        .get_commit_range_files(&from, &to)?
+        .iter()
+        .map(ChangedFile::from_staged)
+        .collect();
+    if !should_split(&files) {
+        ui::print_info(&format!(
+            "{from}..{to} changes {} files; small enough to review as one PR",
+            files.len()
+        ));
+        return Ok(());
+    }
+
+    let commits = repo.get_commits_between_with_callback(&from, &to, |commit: &RecentCommit| {
+        let subject = commit.message.lines().next().unwrap_or_default();
+        Ok(CommitFiles {
+            summary: format!("{} {subject}", &commit.hash[..7.min(commit.hash.len())]),
+            paths: repo.get_file_paths_for_commit(&commit.hash)?,
+        })
+    })?;
+    let owners = CodeOwners::load(repo.repo_path());
+    let plan = plan_split(&files, &commits, owners.as_ref());
+    if plan.len() < 2 {
+        ui::print_info("All changes belong to one owner or directory; no split to suggest");
+        return Ok(());
+    }
+
+    let mut markdown = format!(
+        "# Suggested split of {from}..{to}\n\n{} files across {} PRs\n",
+        files.len(),
+        plan.len()
+    );
+    for (i, pr) in plan.iter().enumerate() {
+        let spinner = (!raw)
+            .then(|| ui::create_spinner(&format!("Describing PR {} of {}...", i + 1, plan.len())));
+        let description = service
+            .execute_task_with_prompt("pr", &pr.to_prompt(&from, &to, i + 1, plan.len()))
+            .await?;
+        if let Some(s) = spinner {
+            s.finish_and_clear();
+        }
+
+        markdown.push_str(&format!(
+            "\n---\n\n# PR {} of {}: {} ({} lines)\n\n## Files\n\n",
+            i + 1,
+            plan.len(),
+            pr.label,
+            pr.lines
+        ));
+        for file in &pr.files {
+            markdown.push_str(&format!("- `{file}`\n"));
+        }
+        if !pr.commits.is_empty() {
+            markdown.push_str("\n## Commits\n\n");
+            for commit in &pr.commits {
+                markdown.push_str(&format!("- {commit}\n"));
+            }
+        }
+        markdown.push_str(&format!(
+            "\n## Description\n\n{}\n",
+            description.to_string().trim()
+        ));
+    }
+
+    if raw {
+        println!("{}", markdown.trim_end());
+    } else {
+        println!("{}", render_markdown_for_terminal(&markdown));
+    }
+    Ok(())
+}
+
 /// Handle the `Studio` command
 #[allow(clippy::unused_async)] // Will need async when agent integration is complete
 async fn handle_studio(
@@ -1380,11 +3903,14 @@ async fn handle_studio(
         Arc::new(GitRepo::new_from_url(repo_url.clone()).context("Failed to create GitRepo")?);
 
     // Create services
-    let commit_service = Arc::new(GitCommitService::new(
-        git_repo.clone(),
-        cfg.use_gitmoji,
-        true, // verify hooks
-    ));
+    let commit_service = Arc::new(
+        GitCommitService::new(
+            git_repo.clone(),
+            cfg.use_gitmoji,
+            true, // verify hooks
+        )
+        .with_lifecycle_hooks(cfg.hooks.clone()),
+    );
 
     let agent_service = Arc::new(IrisAgentService::from_common_params(
         &common,
@@ -1400,6 +3926,9 @@ async fn handle_studio(
             "review" => Some(Mode::Review),
             "pr" => Some(Mode::PR),
             "changelog" => Some(Mode::Changelog),
+            "dashboard" => Some(Mode::Dashboard),
+            "stash" => Some(Mode::Stash),
+            "rebase" => Some(Mode::Rebase),
             _ => {
                 ui::print_warning(&format!("Unknown mode '{}', using auto-detect", m));
                 None
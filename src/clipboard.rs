@@ -0,0 +1,203 @@
+//! Clipboard access
+//!
+//! Copies through the system clipboard when one is available. In remote
+//! sessions (SSH, tmux, screen) there is often no usable system clipboard, so
+//! the text is sent to the user's terminal with the OSC52 escape sequence
+//! instead. The `clipboard_osc52` config setting controls the fallback.
+
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::{Deserialize, Serialize};
+use std::io::{IsTerminal, Write};
+
+/// Largest encoded payload we send; many terminals drop anything bigger
+const MAX_OSC52_BYTES: usize = 100_000;
+
+/// GNU screen truncates DCS strings, so payloads are split into chunks this size
+const SCREEN_CHUNK_BYTES: usize = 76;
+
+/// When to use the OSC52 escape sequence
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Osc52Mode {
+    /// Fall back to OSC52 in remote or multiplexed sessions
+    #[default]
+    Auto,
+    /// Always use OSC52, skipping the system clipboard
+    Always,
+    /// Only use the system clipboard
+    Never,
+}
+
+impl Osc52Mode {
+    /// Whether this is the default mode (used to keep config files minimal)
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    pub fn is_default(&self) -> bool {
+        *self == Self::Auto
+    }
+}
+
+/// How text reached the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyMethod {
+    /// Native system clipboard
+    System,
+    /// OSC52 escape sequence handled by the terminal
+    Osc52,
+}
+
+/// Terminal multiplexer the sequence has to pass through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Multiplexer {
+    None,
+    Tmux,
+    Screen,
+}
+
+/// Copy text to the clipboard according to `mode`
+pub fn copy_text(text: &str, mode: Osc52Mode) -> Result<CopyMethod> {
+    if mode == Osc52Mode::Always {
+        write_osc52(text)?;
+        return Ok(CopyMethod::Osc52);
+    }
+
+    match system_copy(text) {
+        Ok(()) => Ok(CopyMethod::System),
+        Err(e) if mode == Osc52Mode::Auto && (is_remote_session() || in_multiplexer()) => {
+            tracing::debug!("System clipboard unavailable, using OSC52: {}", e);
+            write_osc52(text)?;
+            Ok(CopyMethod::Osc52)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether we're running over SSH, where the system clipboard isn't the user's
+pub fn is_remote_session() -> bool {
+    ["SSH_TTY", "SSH_CONNECTION", "SSH_CLIENT"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some())
+}
+
+fn in_multiplexer() -> bool {
+    detect_multiplexer() != Multiplexer::None
+}
+
+fn detect_multiplexer() -> Multiplexer {
+    if std::env::var_os("TMUX").is_some() {
+        Multiplexer::Tmux
+    } else if std::env::var_os("STY").is_some() {
+        Multiplexer::Screen
+    } else {
+        Multiplexer::None
+    }
+}
+
+fn system_copy(text: &str) -> Result<()> {
+    // The Windows clipboard is a global lock; another process holding it makes
+    // the first attempts fail, so retry briefly before giving up
+    let attempts = if cfg!(windows) { 3 } else { 1 };
+    let mut last_error = None;
+    for attempt in 0..attempts {
+        if attempt > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+            Ok(()) => return Ok(()),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.map_or_else(
+        || anyhow::anyhow!("Clipboard unavailable"),
+        |e| anyhow::anyhow!("Clipboard unavailable: {e}"),
+    ))
+}
+
+/// Build the OSC52 sequence that sets the system clipboard
+fn osc52_sequence(text: &str, multiplexer: Multiplexer) -> Result<String> {
+    let payload = STANDARD.encode(text);
+    if payload.len() > MAX_OSC52_BYTES {
+        bail!(
+            "Text too large for terminal clipboard ({} KB, limit {} KB)",
+            payload.len() / 1024,
+            MAX_OSC52_BYTES / 1024
+        );
+    }
+
+    Ok(match multiplexer {
+        Multiplexer::None => format!("\x1b]52;c;{payload}\x07"),
+        // tmux forwards DCS passthrough with inner escapes doubled
+        Multiplexer::Tmux => format!("\x1bPtmux;\x1b\x1b]52;c;{payload}\x07\x1b\\"),
+        Multiplexer::Screen => {
+            // base64 is ASCII, so byte chunks are valid UTF-8
+            let chunks: Vec<&str> = payload
+                .as_bytes()
+                .chunks(SCREEN_CHUNK_BYTES)
+                .filter_map(|chunk| std::str::from_utf8(chunk).ok())
+                .collect();
+            format!("\x1bP\x1b]52;c;{}\x07\x1b\\", chunks.join("\x1b\\\x1bP"))
+        }
+    })
+}
+
+fn write_osc52(text: &str) -> Result<()> {
+    let sequence = osc52_sequence(text, detect_multiplexer())?;
+
+    // Prefer stdout, but when it's piped the terminal is still on stderr
+    let result = if std::io::stdout().is_terminal() || !std::io::stderr().is_terminal() {
+        let mut stdout = std::io::stdout();
+        stdout
+            .write_all(sequence.as_bytes())
+            .and_then(|()| stdout.flush())
+    } else {
+        let mut stderr = std::io::stderr();
+        stderr
+            .write_all(sequence.as_bytes())
+            .and_then(|()| stderr.flush())
+    };
+    result.context("Failed to write OSC52 sequence")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(
+            osc52_sequence("hi", Multiplexer::None).expect("sequence"),
+            "\x1b]52;c;aGk=\x07"
+        );
+        assert_eq!(
+            osc52_sequence("hi", Multiplexer::Tmux).expect("sequence"),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_osc52_screen_chunking() {
+        let text = "x".repeat(120);
+        let sequence = osc52_sequence(&text, Multiplexer::Screen).expect("sequence");
+        // 160 base64 bytes split into 76 + 76 + 8
+        assert_eq!(sequence.matches("\x1bP").count(), 3);
+        assert!(sequence.starts_with("\x1bP\x1b]52;c;"));
+        assert!(sequence.ends_with("\x07\x1b\\"));
+    }
+
+    #[test]
+    fn test_osc52_rejects_oversized_payload() {
+        let text = "x".repeat(MAX_OSC52_BYTES);
+        assert!(osc52_sequence(&text, Multiplexer::None).is_err());
+    }
+
+    #[test]
+    fn test_osc52_mode_from_config() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            mode: Osc52Mode,
+        }
+        let parsed: Wrapper = toml::from_str("mode = \"always\"").expect("parse");
+        assert_eq!(parsed.mode, Osc52Mode::Always);
+    }
+}
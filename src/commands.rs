@@ -1,15 +1,24 @@
-use crate::common::CommonParams;
+use crate::common::{CommonParams, format_size};
+use crate::companion::{
+    CompanionStorage, HistoryFilter, HistoryKind, ReportFormat, search_history,
+};
 use crate::config::Config;
+use crate::git::GitRepo;
 use crate::instruction_presets::{
-    PresetType, get_instruction_preset_library, list_presets_formatted_by_type,
+    PRESETS_DIR, PresetType, get_instruction_preset_library, is_valid_preset_key,
+    list_presets_formatted_by_type, preset_file_template, user_presets_dir,
 };
 use crate::log_debug;
+use crate::message_stats::{MessageRules, MessageStats};
 use crate::providers::{Provider, ProviderConfig};
+use crate::types::render_markdown_for_terminal;
 use crate::ui;
 use anyhow::Context;
 use anyhow::{Result, anyhow};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use colored::Colorize;
 use std::collections::HashMap;
+use std::fs;
 
 /// Helper to get themed colors for terminal output
 mod colors {
@@ -238,6 +247,47 @@ pub fn handle_config_command(
     Ok(())
 }
 
+/// Handle `config --migrate-secrets`
+///
+/// Moves the personal config's API keys and forge tokens into the OS keyring
+/// and sets `use_keyring` so later saves keep them out of the file.
+pub fn handle_migrate_secrets_command() -> Result<()> {
+    let mut config = Config::load_personal()?;
+    let was_enabled = config.use_keyring;
+    let moved = config
+        .migrate_secrets()
+        .context("Secrets were left in the config file")?;
+
+    if moved.is_empty() {
+        ui::print_info(if was_enabled {
+            "Secrets are already kept in the OS keyring"
+        } else {
+            "No secrets in the config file; new API keys will be stored in the OS keyring"
+        });
+    } else {
+        ui::print_success(&format!(
+            "Moved {} secret(s) to the OS keyring",
+            moved.len()
+        ));
+        for name in &moved {
+            ui::print_info(&format!("  • {name}"));
+        }
+    }
+
+    // Project configs are never read for secrets, but one may still hold them
+    if let Ok(project_config) = Config::load_project_config() {
+        let leaked = crate::config::secrets::plaintext_secrets(&project_config);
+        if !leaked.is_empty() {
+            ui::print_warning(&format!(
+                "{} in {} is ignored; remove it from the file",
+                leaked.join(", "),
+                crate::config::PROJECT_CONFIG_FILENAME
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Handle printing current project configuration
 ///
 /// Loads and displays the current project configuration if it exists,
@@ -306,19 +356,8 @@ pub fn handle_project_config_command(
         return Ok(());
     }
 
-    let mut config = Config::load_project_config().unwrap_or_else(|_| Config {
-        default_provider: String::new(),
-        providers: HashMap::new(),
-        use_gitmoji: true,
-        instructions: String::new(),
-        instruction_preset: String::new(),
-        theme: String::new(),
-        subagent_timeout_secs: 120,
-        temp_instructions: None,
-        temp_preset: None,
-        is_project_config: true,
-        gitmoji_override: None,
-    });
+    let mut config =
+        Config::load_project_config().unwrap_or_else(|_| Config::empty_project_config());
 
     let mut changes_made = false;
 
@@ -551,6 +590,18 @@ fn print_configuration(config: &Config) {
         coral,
         false,
     );
+    if !config.is_project_config {
+        print_config_row(
+            "Secrets",
+            if config.use_keyring {
+                "OS keyring"
+            } else {
+                "config file"
+            },
+            if config.use_keyring { green } else { dim },
+            false,
+        );
+    }
 
     // Custom Instructions (if any)
     if !config.instructions.is_empty() {
@@ -696,10 +747,643 @@ pub fn handle_list_presets_command() -> Result<()> {
         println!("{review_only_presets}\n");
     }
 
+    for (path, error) in library.load_errors() {
+        ui::print_warning(&format!("Skipped {}: {error}", path.display()));
+    }
+
     println!("{}", "Usage:".bright_yellow().bold());
     println!("  git-iris gen --preset <preset-key>");
     println!("  git-iris review --preset <preset-key>");
     println!("\nPreset types: [B] = Both commands, [C] = Commit only, [R] = Review only");
+    println!("Add your own with `git-iris preset new <name>`; [custom] marks preset files.");
+
+    Ok(())
+}
+
+/// Handle the '`preset show`' command
+pub fn handle_preset_show_command(name: &str) -> Result<()> {
+    let library = get_instruction_preset_library();
+    let preset = library.get_preset(name).ok_or_else(|| {
+        anyhow!("Unknown preset '{name}'. Run 'git-iris preset list' to see available presets.")
+    })?;
+
+    let dim = colors::text_dim();
+    let source = library
+        .preset_path(name)
+        .map_or_else(|| "built-in".to_string(), |path| path.display().to_string());
+    eprintln!(
+        "{}",
+        format!(
+            "# {name}: {} {} ({}, {source})",
+            preset.emoji,
+            preset.name,
+            preset.preset_type.as_str()
+        )
+        .truecolor(dim.0, dim.1, dim.2)
+    );
+    if !preset.description.is_empty() {
+        eprintln!(
+            "{}",
+            format!("# {}", preset.description).truecolor(dim.0, dim.1, dim.2)
+        );
+    }
+    println!("{}", preset.instructions.trim_end());
+    Ok(())
+}
+
+/// Handle the '`preset new`' command
+pub fn handle_preset_new_command(
+    name: &str,
+    preset_type: PresetType,
+    from: Option<&str>,
+    user: bool,
+    edit: bool,
+) -> Result<()> {
+    if !is_valid_preset_key(name) {
+        return Err(anyhow!(
+            "Invalid preset name '{name}': use letters, digits, '-' and '_'"
+        ));
+    }
+    let library = get_instruction_preset_library();
+    let base = match from {
+        Some(from) => Some(library.get_preset(from).ok_or_else(|| {
+            anyhow!("Unknown preset '{from}'. Run 'git-iris preset list' to see available presets.")
+        })?),
+        None => None,
+    };
+
+    let dir = if user {
+        user_presets_dir().ok_or_else(|| anyhow!("Unable to determine home directory"))?
+    } else {
+        GitRepo::get_repo_root()
+            .context("Run this in a repository, or pass --user for a personal preset")?
+            .join(PRESETS_DIR)
+    };
+    let path = dir.join(format!("{name}.toml"));
+    if path.exists() {
+        return Err(anyhow!(
+            "{} already exists; edit it with 'git-iris preset edit {name}'",
+            path.display()
+        ));
+    }
+    crate::read_only::ensure_writable("Creating the preset")?;
+    fs::create_dir_all(&dir)?;
+    fs::write(&path, preset_file_template(name, preset_type, base))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    ui::print_success(&format!("Created {}", path.display()));
+    if library.get_preset(name).is_some() {
+        ui::print_info(&format!("It overrides the existing '{name}' preset"));
+    }
+
+    if edit {
+        open_in_editor(&path)?;
+    }
+    Ok(())
+}
+
+/// Handle the '`preset edit`' command
+pub fn handle_preset_edit_command(name: &str) -> Result<()> {
+    let library = get_instruction_preset_library();
+    let Some(path) = library.preset_path(name) else {
+        return Err(if library.get_preset(name).is_some() {
+            anyhow!(
+                "'{name}' is built in. Copy it into an editable preset with 'git-iris preset new {name} --from {name}'"
+            )
+        } else {
+            anyhow!("Unknown preset '{name}'. Create it with 'git-iris preset new {name}'")
+        });
+    };
+    open_in_editor(path)
+}
+
+/// Open `path` in `$VISUAL` or `$EDITOR` and wait for it to close
+fn open_in_editor(path: &std::path::Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to run editor '{editor}'"))?;
+    if !status.success() {
+        return Err(anyhow!("Editor '{editor}' exited with {status}"));
+    }
+
+    // Catch mistakes now rather than on the next `--preset`
+    let library = get_instruction_preset_library();
+    if let Some((_, error)) = library.load_errors().iter().find(|(p, _)| p == path) {
+        ui::print_warning(&format!("{} won't load: {error}", path.display()));
+    }
+    Ok(())
+}
+
+/// Handle the '`export_session`' command
+pub fn handle_export_session_command(format: ReportFormat, output: Option<&str>) -> Result<()> {
+    let repo = GitRepo::new_from_url(None).context("Failed to open repository")?;
+    let storage = CompanionStorage::new(repo.repo_path())?;
+    let transcript = storage.load_transcript()?.ok_or_else(|| {
+        anyhow!("No Studio session found for this repository. Run `git-iris studio` first.")
+    })?;
+
+    let report = transcript.render(format);
+    match output {
+        Some(path) => {
+            crate::read_only::ensure_writable("Writing the session report")?;
+            std::fs::write(path, report)
+                .with_context(|| format!("Failed to write report to {path}"))?;
+            ui::print_success(&format!("Session report written to {path}"));
+        }
+        None => print!("{report}"),
+    }
+
+    Ok(())
+}
+
+/// Handle the '`undo`' command
+pub fn handle_undo_command() -> Result<()> {
+    let repo = GitRepo::new_from_url(None).context("Failed to open repository")?;
+    let undone = crate::undo::undo_last_commit(&repo, true)?;
+
+    let record = &undone.record;
+    ui::print_success(&format!(
+        "Undid {} on {}: {}",
+        record.short_hash(),
+        record.branch,
+        record.subject()
+    ));
+    ui::print_info(&format!(
+        "{} file{} staged again; the message opens as a draft in `git-iris studio`",
+        undone.files.len(),
+        if undone.files.len() == 1 { "" } else { "s" }
+    ));
+    Ok(())
+}
+
+/// Handle the '`hooks install`' command
+pub fn handle_hooks_install_command(commit_msg: bool, uninstall: bool) -> Result<()> {
+    use crate::hooks::{HookKind, Installed, Uninstalled};
+
+    let repo = GitRepo::new_from_url(None).context("Failed to open repository")?;
+    let dir = crate::hooks::hooks_dir(&repo.open_repo()?)?;
+
+    if uninstall {
+        // Uninstall removes both; only ours are touched
+        for kind in [HookKind::PrepareCommitMsg, HookKind::CommitMsg] {
+            let name = kind.file_name();
+            match crate::hooks::uninstall(&dir, kind)? {
+                Uninstalled::Removed => ui::print_success(&format!("Removed {name} hook")),
+                Uninstalled::Restored => ui::print_success(&format!(
+                    "Removed {name} hook and restored the one it wrapped"
+                )),
+                Uninstalled::NotOurs => ui::print_warning(&format!(
+                    "Left {name} alone: it wasn't installed by git-iris"
+                )),
+                Uninstalled::Missing => {}
+            }
+        }
+        return Ok(());
+    }
+
+    let mut kinds = vec![HookKind::PrepareCommitMsg];
+    if commit_msg {
+        kinds.push(HookKind::CommitMsg);
+    }
+    for kind in kinds {
+        let name = kind.file_name();
+        match crate::hooks::install(&dir, kind)? {
+            Installed::Fresh => ui::print_success(&format!("Installed {name} hook")),
+            Installed::Updated => ui::print_success(&format!("Updated {name} hook")),
+            Installed::Wrapped(previous) => ui::print_success(&format!(
+                "Installed {name} hook; the existing one moved to {} and still runs first",
+                previous.display()
+            )),
+        }
+    }
+    ui::print_info(&format!("Hooks directory: {}", dir.display()));
+    ui::print_info("Set GIT_IRIS_SKIP_HOOKS=1 to skip them for one commit");
+    Ok(())
+}
+
+/// Handle the '`stats usage`' command
+pub fn handle_stats_usage_command(days: Option<u32>, raw: bool) -> Result<()> {
+    use crate::analytics::{self, UsageSummary};
+
+    let since = days.map(|days| chrono::Utc::now() - chrono::Duration::days(i64::from(days)));
+    let events = analytics::load(since)?;
+    if events.is_empty() {
+        let enabled = Config::load().is_ok_and(|config| config.local_analytics);
+        if enabled {
+            ui::print_info("No usage recorded yet");
+        } else {
+            ui::print_info(
+                "Usage analytics are off. Enable them with `local_analytics = true` in your config; they stay on this machine.",
+            );
+        }
+        return Ok(());
+    }
+
+    let markdown = UsageSummary::from_events(&events).to_markdown();
+    if raw {
+        println!("{}", markdown.trim_end());
+    } else {
+        println!("{}", render_markdown_for_terminal(&markdown));
+    }
+    Ok(())
+}
+
+/// Handle the '`usage`' command
+pub fn handle_usage_command(days: Option<u32>, raw: bool) -> Result<()> {
+    use crate::usage::{Ledger, UsageReport};
+
+    let repo = GitRepo::new(&std::env::current_dir()?)?;
+    let since = days.map(|days| chrono::Utc::now() - chrono::Duration::days(i64::from(days)));
+    let entries = Ledger::for_repo(&repo)?.load(since)?;
+    if entries.is_empty() {
+        ui::print_info("No agent calls recorded for this repository yet");
+        return Ok(());
+    }
+
+    let markdown = UsageReport::from_entries(&entries).to_markdown();
+    if raw {
+        println!("{}", markdown.trim_end());
+    } else {
+        println!("{}", render_markdown_for_terminal(&markdown));
+    }
+    Ok(())
+}
+
+/// Handle the '`bench`' command
+pub async fn handle_bench_command(models: &[String], tasks: &[String], raw: bool) -> Result<()> {
+    use crate::bench::{self, BenchTarget};
+
+    let config = Config::load()?;
+    let targets = if models.is_empty() {
+        BenchTarget::defaults(&config)
+    } else {
+        models
+            .iter()
+            .map(|spec| BenchTarget::parse(spec, &config))
+            .collect::<Result<Vec<_>>>()?
+    };
+    bench::check_targets(&targets, &config)?;
+    let fixtures = bench::fixtures(tasks)?;
+
+    let mut runs = Vec::new();
+    for fixture in &fixtures {
+        for target in &targets {
+            let spinner = ui::create_spinner(&format!("{} · {target}", fixture.name));
+            let run = bench::run(&config, target, fixture).await;
+            spinner.finish_and_clear();
+            if let Err(e) = &run.output {
+                ui::print_warning(&format!("{} failed on {target}: {e}", fixture.name));
+            }
+            runs.push(run);
+        }
+    }
 
+    let summary = bench::summary_markdown(&runs);
+    if raw {
+        println!("{}", summary.trim_end());
+        println!("{}", bench::outputs_markdown(&runs).trim_end());
+        return Ok(());
+    }
+
+    println!("{}", render_markdown_for_terminal(&summary));
+    let width = crossterm::terminal::size().map_or(100, |(columns, _)| usize::from(columns));
+    for fixture in &fixtures {
+        let fixture_runs: Vec<_> = runs.iter().filter(|r| r.fixture == fixture.name).collect();
+        let columns: Vec<(String, String)> = fixture_runs
+            .iter()
+            .map(|run| (run.target.to_string(), bench::output_text(run)))
+            .collect();
+        print_section_header(fixture.name);
+        match bench::side_by_side(&columns, width) {
+            Some(table) => println!("{table}"),
+            None => {
+                for (title, text) in columns {
+                    println!("{}\n{}\n", title.bold(), text.trim_end());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Handle the '`self-update`' command
+pub async fn handle_self_update_command(check_only: bool) -> Result<()> {
+    use crate::update;
+
+    let spinner = ui::create_spinner("Checking for a newer release...");
+    let release = update::fetch_latest_release().await;
+    spinner.finish_and_clear();
+    let release = release?;
+
+    if !release.is_newer() {
+        ui::print_success(&format!(
+            "git-iris {} is up to date",
+            update::CURRENT_VERSION
+        ));
+        return Ok(());
+    }
+
+    ui::print_info(&format!(
+        "git-iris {} is available (you have {})",
+        release.tag_name,
+        update::CURRENT_VERSION
+    ));
+    for highlight in release.highlights() {
+        ui::print_info(&format!("  • {highlight}"));
+    }
+    ui::print_info(&format!("Release notes: {}", release.html_url));
+    if check_only {
+        return Ok(());
+    }
+
+    let spinner = ui::create_spinner(&format!("Downloading {}...", release.tag_name));
+    let updated = update::self_update(&release).await;
+    spinner.finish_and_clear();
+    let path = updated?;
+    ui::print_success(&format!(
+        "Updated {} to {}",
+        path.display(),
+        release.tag_name
+    ));
+    Ok(())
+}
+
+/// Handle the '`trace`' command
+pub fn handle_trace_command(count: usize, capability: Option<&str>) -> Result<()> {
+    let repo = GitRepo::new_from_url(None).context("Failed to open repository")?;
+    let storage = CompanionStorage::new(repo.repo_path())?;
+    let reports: Vec<_> = storage
+        .load_context_reports()?
+        .into_iter()
+        .rev()
+        .filter(|r| capability.is_none_or(|c| r.capability == c))
+        .take(count.max(1))
+        .collect();
+
+    if reports.is_empty() {
+        ui::print_warning("No generations recorded yet in this repository");
+        return Ok(());
+    }
+    for (i, report) in reports.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print!("{}", report.to_table());
+    }
+    Ok(())
+}
+
+/// Handle the '`history search`' command
+pub fn handle_history_search_command(
+    query: &str,
+    kind: Option<HistoryKind>,
+    since: Option<&str>,
+    limit: usize,
+) -> Result<()> {
+    let repo = GitRepo::new_from_url(None).context("Failed to open repository")?;
+    let storage = CompanionStorage::new(repo.repo_path())?;
+    let sessions = storage.load_history()?;
+
+    let filter = HistoryFilter {
+        query: query.to_string(),
+        kind,
+        since: since.map(parse_since).transpose()?,
+    };
+    let hits = search_history(&sessions, &filter);
+
+    if hits.is_empty() {
+        ui::print_warning(&format!(
+            "No matches for \"{query}\" in {} archived sessions",
+            sessions.len()
+        ));
+        return Ok(());
+    }
+
+    let dim = colors::text_dim();
+    let kind_color = colors::accent_secondary();
+    let branch_color = colors::accent_tertiary();
+    for hit in hits.iter().take(limit) {
+        println!(
+            "{} {} {} {}",
+            hit.date
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+                .truecolor(dim.0, dim.1, dim.2),
+            format!("[{}]", hit.kind.short_label()).truecolor(
+                kind_color.0,
+                kind_color.1,
+                kind_color.2
+            ),
+            hit.branch
+                .truecolor(branch_color.0, branch_color.1, branch_color.2),
+            hit.snippet
+        );
+    }
+    if hits.len() > limit {
+        println!("… {} more (use --limit to show more)", hits.len() - limit);
+    }
+
+    Ok(())
+}
+
+/// Handle the `remote-cache list` command
+pub fn handle_remote_cache_list_command() -> Result<()> {
+    use crate::git::remote_cache;
+
+    let root = remote_cache::cache_root().context("No cache directory available")?;
+    let mirrors = remote_cache::list_mirrors(&root)?;
+    if mirrors.is_empty() {
+        ui::print_info("No cached repositories");
+        return Ok(());
+    }
+
+    let dim = colors::text_dim();
+    for mirror in &mirrors {
+        let fetched = mirror.fetched.map_or_else(
+            || "never fetched".to_string(),
+            |time| {
+                chrono::DateTime::<chrono::Local>::from(time)
+                    .format("fetched %Y-%m-%d %H:%M")
+                    .to_string()
+            },
+        );
+        println!(
+            "{}  {}",
+            mirror.url,
+            format!("{}, {fetched}", format_size(mirror.size)).truecolor(dim.0, dim.1, dim.2)
+        );
+    }
+    let total: u64 = mirrors.iter().map(|m| m.size).sum();
+    println!(
+        "{}",
+        format!(
+            "{} mirror{} in {}, {} total",
+            mirrors.len(),
+            if mirrors.len() == 1 { "" } else { "s" },
+            root.display(),
+            format_size(total)
+        )
+        .truecolor(dim.0, dim.1, dim.2)
+    );
+    Ok(())
+}
+
+/// Handle the `remote-cache clear` command; `None` clears every mirror
+pub fn handle_remote_cache_clear_command(url: Option<&str>) -> Result<()> {
+    use crate::git::remote_cache;
+
+    let root = remote_cache::cache_root().context("No cache directory available")?;
+    let removed = remote_cache::clear_mirrors(&root, url)?;
+    match (removed.len(), url) {
+        (0, Some(url)) => ui::print_info(&format!("No cached mirror of {url}")),
+        (0, None) => ui::print_info("No cached repositories"),
+        (count, _) => {
+            let freed: u64 = removed.iter().map(|m| m.size).sum();
+            ui::print_success(&format!(
+                "Removed {count} cached mirror{} ({})",
+                if count == 1 { "" } else { "s" },
+                format_size(freed)
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Handle the `config export` command
+pub fn handle_config_export_command(bundle_path: &str) -> Result<()> {
+    use crate::config_bundle::ConfigBundle;
+
+    let config = Config::load()?;
+    let repo_root = GitRepo::get_repo_root().ok();
+    let bundle = ConfigBundle::collect(&config, repo_root.as_deref())?;
+    crate::read_only::ensure_writable("Writing the config bundle")?;
+    fs::write(bundle_path, bundle.to_toml()?)
+        .with_context(|| format!("Failed to write {bundle_path}"))?;
+
+    ui::print_success(&format!("Config bundle written to {bundle_path}"));
+    let dim = colors::text_dim();
+    println!(
+        "{}",
+        format!(
+            "  {} prompt overrides{}, API keys excluded",
+            bundle.prompts.len(),
+            bundle
+                .theme
+                .as_ref()
+                .map(|t| format!(", theme '{}'", t.name))
+                .unwrap_or_default()
+        )
+        .truecolor(dim.0, dim.1, dim.2)
+    );
+    Ok(())
+}
+
+/// Handle the `config import` command
+pub fn handle_config_import_command(bundle_path: &str, project: bool) -> Result<()> {
+    use crate::config_bundle::{ConfigBundle, user_themes_dir};
+
+    let content =
+        fs::read_to_string(bundle_path).with_context(|| format!("Failed to read {bundle_path}"))?;
+    let bundle = ConfigBundle::from_toml(&content)?;
+
+    if project {
+        let mut config =
+            Config::load_project_config().unwrap_or_else(|_| Config::empty_project_config());
+        bundle.apply_settings(&mut config);
+        config.save_as_project_config()?;
+        ui::print_success("Bundle settings written to the project config");
+    } else {
+        let mut config = Config::load_personal()?;
+        bundle.apply_settings(&mut config);
+        config.save()?;
+        ui::print_success("Bundle settings written to your personal config");
+    }
+
+    let repo_root = GitRepo::get_repo_root().ok();
+    let themes_dir = user_themes_dir();
+    let summary = bundle.install_files(repo_root.as_deref(), themes_dir.as_deref())?;
+    for path in summary.prompts.iter().chain(&summary.theme) {
+        ui::print_info(&format!("Installed {}", path.display()));
+    }
+    if summary.skipped_prompts > 0 {
+        ui::print_warning(&format!(
+            "Skipped {} prompt overrides: not inside a git repository",
+            summary.skipped_prompts
+        ));
+    }
+    Ok(())
+}
+
+/// Handle the `prompt show` command
+pub fn handle_prompt_show_command(capability: &str) -> Result<()> {
+    use crate::agents::prompts::{PROMPTS_DIR, load_capability};
+
+    let repo_root = GitRepo::get_repo_root().ok();
+    let prompt = load_capability(capability, repo_root.as_deref())?;
+
+    let dim = colors::text_dim();
+    if prompt.overrides.is_empty() {
+        eprintln!(
+            "{}",
+            format!("# {capability}: built-in prompt (no overrides in {PROMPTS_DIR})")
+                .truecolor(dim.0, dim.1, dim.2)
+        );
+    } else {
+        for path in &prompt.overrides {
+            eprintln!(
+                "{}",
+                format!("# {capability}: applied {}", path.display())
+                    .truecolor(dim.0, dim.1, dim.2)
+            );
+        }
+    }
+    println!("{}", prompt.task_prompt.trim_end());
+    Ok(())
+}
+
+/// Parse a `--since` value: either `YYYY-MM-DD` or an age like `7d`
+fn parse_since(value: &str) -> Result<DateTime<Utc>> {
+    if let Some(days) = value.strip_suffix('d') {
+        let days: i64 = days
+            .parse()
+            .with_context(|| format!("Invalid age: {value}"))?;
+        return Ok(Utc::now() - chrono::Duration::days(days));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("Invalid date (expected YYYY-MM-DD or e.g. 7d): {value}"))?;
+    Ok(date.and_time(NaiveTime::MIN).and_utc())
+}
+
+/// Handle the '`stats messages`' command
+pub fn handle_stats_messages_command(
+    common: &CommonParams,
+    count: usize,
+    raw: bool,
+    repository_url: Option<String>,
+) -> Result<()> {
+    let mut config = Config::load()?;
+    common.apply_to_config(&mut config)?;
+    let repo_url = repository_url.or(common.repository_url.clone());
+    let repo = GitRepo::new_from_url(repo_url).context("Failed to open repository")?;
+
+    let commits = repo.get_recent_commits(count)?;
+    let stats = MessageStats::from_commits(MessageRules::from_config(&config), &commits);
+    if stats.total == 0 {
+        ui::print_warning("No commits to score");
+        return Ok(());
+    }
+
+    let markdown = stats.to_markdown();
+    if raw {
+        println!("{}", markdown.trim_end());
+    } else {
+        println!("{}", render_markdown_for_terminal(&markdown));
+    }
     Ok(())
 }
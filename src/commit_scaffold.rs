@@ -0,0 +1,232 @@
+//! Conventional Commits scaffold from the staged files
+//!
+//! Without a provider, Studio still drafts a commit message: the type comes
+//! from what kind of files changed (docs, tests, CI, build manifests, new
+//! source files), the scope from the directory they share, and the body
+//! lists the files. It's a starting point to edit, not a description of the
+//! change.
+
+use std::path::Path;
+
+use crate::context::{ChangeType, StagedFile};
+use crate::types::GeneratedMessage;
+
+/// Files listed in the body before the rest are summarized
+const MAX_LISTED_FILES: usize = 10;
+
+/// Manifests, lockfiles and build scripts
+const BUILD_FILES: &[&str] = &[
+    "Cargo.toml",
+    "Cargo.lock",
+    "build.rs",
+    "package.json",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "go.mod",
+    "go.sum",
+    "pyproject.toml",
+    "poetry.lock",
+    "Gemfile",
+    "Gemfile.lock",
+    "Makefile",
+    "Dockerfile",
+];
+
+fn is_docs(path: &str) -> bool {
+    path.starts_with("docs/")
+        || Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| matches!(ext, "md" | "mdx" | "rst" | "adoc" | "txt"))
+}
+
+fn is_test(path: &str) -> bool {
+    let stem = Path::new(path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    path.starts_with("tests/")
+        || path.contains("/tests/")
+        || path.contains("/test/")
+        || path.contains("__tests__/")
+        || stem.ends_with("_test")
+        || stem.ends_with("_tests")
+        || stem.contains(".test")
+        || stem.contains(".spec")
+}
+
+fn is_ci(path: &str) -> bool {
+    path.starts_with(".github/workflows/")
+        || path.starts_with(".circleci/")
+        || path.starts_with(".gitlab-ci")
+        || path == "Jenkinsfile"
+}
+
+fn is_build(path: &str) -> bool {
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    BUILD_FILES.contains(&name) || name.starts_with("requirements")
+}
+
+/// Conventional Commits type for a set of changes
+fn commit_type(files: &[StagedFile]) -> &'static str {
+    let all = |check: fn(&str) -> bool| files.iter().all(|file| check(&file.path));
+    if all(is_docs) {
+        "docs"
+    } else if all(is_test) {
+        "test"
+    } else if all(is_ci) {
+        "ci"
+    } else if all(is_build) {
+        "build"
+    } else if files
+        .iter()
+        .all(|file| file.change_type == ChangeType::Deleted)
+    {
+        "refactor"
+    } else if files
+        .iter()
+        .any(|file| file.change_type == ChangeType::Added && !is_test(&file.path))
+    {
+        "feat"
+    } else {
+        "fix"
+    }
+}
+
+/// Directories that say no more than the commit type already does
+const GENERIC_DIRS: &[&str] = &["docs", "tests", "test", "github"];
+
+/// Component a path belongs to: its first directory below `src/`, or the
+/// file's own name for top-level files
+fn component(path: &str) -> Option<String> {
+    let path = path.strip_prefix("src/").unwrap_or(path);
+    let first = match path.split_once('/') {
+        Some((dir, _)) => dir,
+        None => Path::new(path).file_stem()?.to_str()?,
+    };
+    Some(first.trim_start_matches('.').to_lowercase())
+        .filter(|scope| !scope.is_empty() && !GENERIC_DIRS.contains(&scope.as_str()))
+}
+
+/// Scope shared by every file, if there is one
+fn scope(files: &[StagedFile]) -> Option<String> {
+    let first = component(&files.first()?.path)?;
+    files
+        .iter()
+        .all(|file| component(&file.path).as_ref() == Some(&first))
+        .then_some(first)
+}
+
+fn verb(change: &ChangeType) -> &'static str {
+    match change {
+        ChangeType::Added => "add",
+        ChangeType::Modified => "update",
+        ChangeType::Deleted => "remove",
+    }
+}
+
+/// Scaffold a commit message for the staged files; `None` when nothing is
+/// staged
+pub fn scaffold(files: &[StagedFile]) -> Option<GeneratedMessage> {
+    let first = files.first()?;
+    let kind = commit_type(files);
+    let scope = scope(files)
+        .map(|scope| format!("({scope})"))
+        .unwrap_or_default();
+    let subject = if let [only] = files {
+        let name = Path::new(&only.path).file_name().map_or_else(
+            || only.path.clone(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        format!("{} {name}", verb(&only.change_type))
+    } else if files
+        .iter()
+        .all(|file| file.change_type == first.change_type)
+    {
+        format!("{} {} files", verb(&first.change_type), files.len())
+    } else {
+        format!("update {} files", files.len())
+    };
+
+    let mut body: Vec<String> = files
+        .iter()
+        .take(MAX_LISTED_FILES)
+        .map(|file| format!("- {} {}", verb(&file.change_type), file.path))
+        .collect();
+    if files.len() > MAX_LISTED_FILES {
+        body.push(format!("- …and {} more", files.len() - MAX_LISTED_FILES));
+    }
+
+    Some(GeneratedMessage {
+        emoji: None,
+        title: format!("{kind}{scope}: {subject}"),
+        message: body.join("\n"),
+        completion_message: Some("Scaffold ready".to_string()),
+        variant: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn staged(path: &str, change_type: ChangeType) -> StagedFile {
+        StagedFile {
+            path: path.to_string(),
+            change_type,
+            diff: String::new(),
+            content: None,
+            content_excluded: false,
+        }
+    }
+
+    #[test]
+    fn test_scaffold_type_scope_and_subject() {
+        let title = |files: &[StagedFile]| scaffold(files).map(|message| message.title);
+
+        assert_eq!(title(&[]), None);
+        assert_eq!(
+            title(&[
+                staged("src/studio/app/mod.rs", ChangeType::Modified),
+                staged("src/studio/offline.rs", ChangeType::Added),
+            ])
+            .as_deref(),
+            Some("feat(studio): update 2 files")
+        );
+        assert_eq!(
+            title(&[staged("src/config.rs", ChangeType::Modified)]).as_deref(),
+            Some("fix(config): update config.rs")
+        );
+        assert_eq!(
+            title(&[
+                staged("README.md", ChangeType::Modified),
+                staged("docs/reference/cli.md", ChangeType::Modified),
+            ])
+            .as_deref(),
+            Some("docs: update 2 files")
+        );
+        assert_eq!(
+            title(&[
+                staged("tests/config_tests.rs", ChangeType::Added),
+                staged("tests/usage_tests.rs", ChangeType::Added),
+            ])
+            .as_deref(),
+            Some("test: add 2 files")
+        );
+        assert_eq!(
+            title(&[staged(".github/workflows/cicd.yml", ChangeType::Modified)]).as_deref(),
+            Some("ci: update cicd.yml")
+        );
+
+        let many: Vec<StagedFile> = (0..12)
+            .map(|i| staged(&format!("src/git/file{i}.rs"), ChangeType::Deleted))
+            .collect();
+        let message = scaffold(&many).expect("scaffold");
+        assert_eq!(message.title, "refactor(git): remove 12 files");
+        assert!(message.message.ends_with("- …and 2 more"));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::config::Config;
+use crate::config::{Config, Tone, Verbosity};
 use crate::instruction_presets::{PresetType, get_instruction_preset_library};
 use crate::providers::{Provider, ProviderConfig};
 use anyhow::Result;
@@ -38,6 +38,23 @@ pub struct CommonParams {
     #[arg(long = "no-gitmoji", help = "Disable Gitmoji", action = clap::ArgAction::SetTrue)]
     pub no_gitmoji: bool,
 
+    /// Output length for this operation
+    #[arg(
+        long,
+        value_enum,
+        value_name = "LEVEL",
+        help = "Output length for this operation: concise, standard or detailed"
+    )]
+    pub verbosity: Option<Verbosity>,
+
+    /// Tone for this operation
+    #[arg(
+        long,
+        value_enum,
+        help = "Tone for this operation: neutral, friendly or formal"
+    )]
+    pub tone: Option<Tone>,
+
     /// Internal: resolved gitmoji value (Some(true), Some(false), or None)
     #[arg(skip)]
     pub gitmoji: Option<bool>,
@@ -105,6 +122,15 @@ impl CommonParams {
             config.set_temp_preset(Some(preset.clone()));
         }
 
+        if self.verbosity.is_some() || self.tone.is_some() {
+            let params = config
+                .temp_generation
+                .entry("default".to_string())
+                .or_default();
+            params.verbosity = self.verbosity.or(params.verbosity);
+            params.tone = self.tone.or(params.tone);
+        }
+
         // Track whether gitmoji was explicitly set via CLI (for style detection)
         if let Some(use_gitmoji) = self.resolved_gitmoji() {
             config.gitmoji_override = Some(use_gitmoji);
@@ -139,3 +165,13 @@ pub fn available_providers_parser(s: &str) -> Result<String, String> {
         )),
     }
 }
+
+/// Human-readable size in KB or MB
+pub fn format_size(bytes: u64) -> String {
+    const MB: u64 = 1024 * 1024;
+    if bytes < MB {
+        format!("{} KB", bytes.div_ceil(1024))
+    } else {
+        format!("{}.{} MB", bytes / MB, bytes % MB * 10 / MB)
+    }
+}
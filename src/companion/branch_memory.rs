@@ -6,6 +6,12 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+/// Most message revisions kept per branch (oldest are dropped)
+const MAX_MESSAGE_REVISIONS: usize = 50;
+
+/// Most commit message drafts kept per branch (oldest are dropped)
+const MAX_MESSAGE_DRAFTS: usize = 50;
+
 /// Focus state - where the user was last working
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileFocus {
@@ -28,6 +34,84 @@ impl FileFocus {
     }
 }
 
+/// A commit message replaced by an amend
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageRevision {
+    /// Commit before the amend
+    pub original_commit: String,
+    /// Commit the amend produced
+    pub amended_commit: String,
+    /// Message before the amend
+    pub original: String,
+    /// Message after the amend
+    pub revised: String,
+    /// When the amend happened
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl MessageRevision {
+    /// Create a revision recorded now
+    pub fn new(
+        original_commit: String,
+        amended_commit: String,
+        original: String,
+        revised: String,
+    ) -> Self {
+        Self {
+            original_commit,
+            amended_commit,
+            original,
+            revised,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    /// First line of a message
+    fn subject(message: &str) -> &str {
+        message.lines().next().unwrap_or_default().trim()
+    }
+
+    /// Whether the amend changed the subject line, not just the body
+    pub fn subject_changed(&self) -> bool {
+        Self::subject(&self.original) != Self::subject(&self.revised)
+    }
+}
+
+/// Where a commit message draft came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DraftKind {
+    /// Written by Iris
+    Generated,
+    /// Changed by hand in the editor
+    Edited,
+}
+
+/// A commit message generated or edited on the branch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageDraft {
+    /// Full message text, subject first
+    pub message: String,
+    pub kind: DraftKind,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl MessageDraft {
+    /// Create a draft recorded now
+    pub fn new(message: String, kind: DraftKind) -> Self {
+        Self {
+            message,
+            kind,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    /// First line of the message
+    pub fn subject(&self) -> &str {
+        MessageRevision::subject(&self.message)
+    }
+}
+
 /// Per-branch persistent memory
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BranchMemory {
@@ -45,6 +129,12 @@ pub struct BranchMemory {
     pub session_count: u32,
     /// Number of commits made on this branch (across sessions)
     pub total_commits: u32,
+    /// Messages replaced by amends, oldest first
+    #[serde(default)]
+    pub message_revisions: Vec<MessageRevision>,
+    /// Commit messages generated or edited here, oldest first
+    #[serde(default)]
+    pub message_drafts: Vec<MessageDraft>,
 }
 
 impl BranchMemory {
@@ -59,6 +149,8 @@ impl BranchMemory {
             notes: Vec::new(),
             session_count: 1,
             total_commits: 0,
+            message_revisions: Vec::new(),
+            message_drafts: Vec::new(),
         }
     }
 
@@ -88,6 +180,31 @@ impl BranchMemory {
         self.total_commits += 1;
     }
 
+    /// Record a message replaced by an amend
+    pub fn record_message_revision(&mut self, revision: MessageRevision) {
+        self.message_revisions.push(revision);
+        let excess = self
+            .message_revisions
+            .len()
+            .saturating_sub(MAX_MESSAGE_REVISIONS);
+        self.message_revisions.drain(..excess);
+    }
+
+    /// Remember a commit message draft
+    ///
+    /// A message already in the list moves to the end instead of repeating,
+    /// so regenerating the same text doesn't crowd out older drafts.
+    pub fn record_message_draft(&mut self, draft: MessageDraft) {
+        if draft.message.trim().is_empty() {
+            return;
+        }
+        self.message_drafts
+            .retain(|existing| existing.message != draft.message);
+        self.message_drafts.push(draft);
+        let excess = self.message_drafts.len().saturating_sub(MAX_MESSAGE_DRAFTS);
+        self.message_drafts.drain(..excess);
+    }
+
     /// Time since last visit
     pub fn time_since_last_visit(&self) -> chrono::Duration {
         Utc::now() - self.last_visited
@@ -119,3 +236,32 @@ impl BranchMemory {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_drafts_dedup_and_cap() {
+        let mut memory = BranchMemory::new("main".to_string());
+        let draft = |text: &str, kind| MessageDraft::new(text.to_string(), kind);
+
+        memory.record_message_draft(draft("feat: add login", DraftKind::Generated));
+        memory.record_message_draft(draft("fix: typo", DraftKind::Generated));
+        memory.record_message_draft(draft("  ", DraftKind::Edited));
+        memory.record_message_draft(draft("feat: add login", DraftKind::Edited));
+        let subjects: Vec<_> = memory
+            .message_drafts
+            .iter()
+            .map(MessageDraft::subject)
+            .collect();
+        assert_eq!(subjects, ["fix: typo", "feat: add login"]);
+        assert_eq!(memory.message_drafts[1].kind, DraftKind::Edited);
+
+        for i in 0..MAX_MESSAGE_DRAFTS {
+            memory.record_message_draft(draft(&format!("chore: step {i}"), DraftKind::Generated));
+        }
+        assert_eq!(memory.message_drafts.len(), MAX_MESSAGE_DRAFTS);
+        assert_eq!(memory.message_drafts[0].subject(), "chore: step 0");
+    }
+}
@@ -6,18 +6,43 @@
 mod branch_memory;
 mod session;
 mod storage;
+mod transcript;
 mod watcher;
 
-pub use branch_memory::{BranchMemory, FileFocus};
+pub use branch_memory::{BranchMemory, DraftKind, FileFocus, MessageDraft, MessageRevision};
 pub use session::{FileActivity, SessionState};
 pub use storage::CompanionStorage;
+pub use transcript::{
+    HistoryFilter, HistoryKind, ReportFormat, SearchHit, SessionTranscript, TranscriptArtifact,
+    TranscriptMessage, search_history,
+};
 pub use watcher::{CompanionEvent, FileWatcherService};
 
 use anyhow::Result;
 use std::path::PathBuf;
+
+use crate::git::GitRepo;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
+/// Record in the branch's memory that an amend replaced `original_commit`,
+/// whose message was `original_message`, with the current `HEAD`
+pub fn record_amend(
+    repo: &GitRepo,
+    original_commit: String,
+    original_message: String,
+) -> Result<MessageRevision> {
+    let revision = MessageRevision::new(
+        original_commit,
+        repo.head_commit_id()?,
+        original_message,
+        repo.get_head_commit_message()?,
+    );
+    CompanionStorage::new(repo.repo_path())?
+        .record_message_revision(&repo.get_current_branch()?, revision.clone())?;
+    Ok(revision)
+}
+
 /// Main companion service that coordinates all subsystems
 pub struct CompanionService {
     /// Repository path being watched
@@ -42,10 +67,9 @@ impl CompanionService {
         // Initialize storage
         let storage = CompanionStorage::new(&repo_path)?;
 
-        // Try to load existing session or create new one
+        // Restore this worktree's session on the branch, or start one
         let session = storage
-            .load_session()?
-            .filter(|s| s.branch == branch) // Only restore if same branch
+            .load_session(branch)?
             .unwrap_or_else(|| SessionState::new(repo_path.clone(), branch.to_owned()));
 
         let session = Arc::new(parking_lot::RwLock::new(session));
@@ -90,6 +114,16 @@ impl CompanionService {
         self.storage.save_branch_memory(memory)
     }
 
+    /// Remember a generated or edited commit message for the branch
+    pub fn record_message_draft(&self, branch: &str, draft: MessageDraft) -> Result<()> {
+        self.storage.record_message_draft(branch, draft)
+    }
+
+    /// Sessions in progress in the repository's other worktrees, newest first
+    pub fn other_worktree_sessions(&self) -> Result<Vec<SessionState>> {
+        self.storage.other_worktree_sessions()
+    }
+
     /// Save current session state
     pub fn save_session(&self) -> Result<()> {
         let session = self.session.read();
@@ -1,43 +1,117 @@
 //! Persistence layer for Iris Companion
 //!
-//! Stores session and branch data in ~/.iris/repos/{repo-hash}/
+//! Stores data in `~/.iris/repos/{repo-hash}/`, keyed by the repository's
+//! common git dir so every worktree of a repository shares branch memory and
+//! session history. Sessions, the last commit, drafts and the work plan
+//! belong to one checkout and live under `worktrees/{worktree-hash}/`, with
+//! one session per branch.
 
-use super::{BranchMemory, SessionState};
+use super::{BranchMemory, MessageDraft, MessageRevision, SessionState, SessionTranscript};
+use crate::agents::ContextReport;
+use crate::plan::Plan;
+use crate::undo::CommitRecord;
 use anyhow::{Context, Result};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+/// Max archived session transcripts kept per repository
+const MAX_ARCHIVED_SESSIONS: usize = 200;
+
+/// Max context size reports kept per repository
+const MAX_CONTEXT_REPORTS: usize = 100;
+
+/// Files that belong to one worktree rather than the whole repository
+const WORKTREE_FILES: &[&str] = &[
+    "transcript.json",
+    "last_commit.json",
+    "draft.json",
+    "plan.json",
+];
+
 /// Storage backend for companion data
 pub struct CompanionStorage {
-    /// Base directory for this repo's data
+    /// Base directory for this repo's data, shared by its worktrees
     repo_dir: PathBuf,
     /// Branches subdirectory
     branches_dir: PathBuf,
+    /// Directory for this worktree's data
+    worktree_data: PathBuf,
 }
 
 impl CompanionStorage {
     /// Create a new storage instance for the given repository
     pub fn new(repo_path: &Path) -> Result<Self> {
-        let base_dir = Self::base_dir()?;
-        let repo_hash = Self::hash_path(repo_path);
-        let repo_dir = base_dir.join("repos").join(&repo_hash);
+        Self::in_dir(&Self::base_dir()?, repo_path)
+    }
+
+    /// Create a storage instance under `base_dir` instead of `~/.iris/`
+    pub fn in_dir(base_dir: &Path, repo_path: &Path) -> Result<Self> {
+        let (common_dir, worktree) = Self::locate(repo_path);
+        let all_repos = base_dir.join("repos");
+        let repo_dir = all_repos.join(Self::hash_path(&common_dir));
         let branches_dir = repo_dir.join("branches");
+        let worktree_data = repo_dir.join("worktrees").join(Self::hash_path(&worktree));
+
+        let legacy_dir = all_repos.join(Self::hash_path(&worktree));
+        if legacy_dir != repo_dir && legacy_dir.is_dir() && !repo_dir.exists() {
+            Self::migrate_legacy(&legacy_dir, &repo_dir, &worktree_data)?;
+        }
 
         // Ensure directories exist
-        fs::create_dir_all(&branches_dir).with_context(|| {
-            format!(
-                "Failed to create companion directory: {}",
-                branches_dir.display()
-            )
-        })?;
+        for dir in [&branches_dir, &worktree_data] {
+            fs::create_dir_all(dir).with_context(|| {
+                format!("Failed to create companion directory: {}", dir.display())
+            })?;
+        }
 
         Ok(Self {
             repo_dir,
             branches_dir,
+            worktree_data,
         })
     }
 
+    /// The repository's common git dir and the work tree of `repo_path`
+    ///
+    /// Paths outside a repository (or that fail to open) stand for both.
+    fn locate(repo_path: &Path) -> (PathBuf, PathBuf) {
+        crate::git::open_repository(repo_path).map_or_else(
+            |_| (repo_path.to_path_buf(), repo_path.to_path_buf()),
+            |repo| {
+                (
+                    repo.commondir().components().collect(),
+                    crate::git::repository_root(&repo),
+                )
+            },
+        )
+    }
+
+    /// Move data stored by work tree path, before worktrees shared a
+    /// directory, to where this worktree now looks for it
+    ///
+    /// The old session file is dropped: it wasn't keyed by branch.
+    fn migrate_legacy(legacy_dir: &Path, repo_dir: &Path, worktree_data: &Path) -> Result<()> {
+        if let Some(parent) = repo_dir.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(legacy_dir, repo_dir).with_context(|| {
+            format!(
+                "Failed to move {} to {}",
+                legacy_dir.display(),
+                repo_dir.display()
+            )
+        })?;
+        fs::create_dir_all(worktree_data)?;
+        for name in WORKTREE_FILES {
+            let path = repo_dir.join(name);
+            if path.exists() {
+                fs::rename(&path, worktree_data.join(name))?;
+            }
+        }
+        Self::remove_if_exists(&repo_dir.join("session.json"))
+    }
+
     /// Get the base companion directory (~/.iris/)
     fn base_dir() -> Result<PathBuf> {
         let home = dirs::home_dir().context("Could not determine home directory")?;
@@ -59,9 +133,17 @@ impl CompanionStorage {
         branch.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_")
     }
 
-    /// Get session file path
-    fn session_path(&self) -> PathBuf {
-        self.repo_dir.join("session.json")
+    /// Get the session file path for a branch in this worktree
+    fn session_path(&self, branch: &str) -> PathBuf {
+        let safe_name = Self::sanitize_branch_name(branch);
+        self.worktree_data
+            .join("sessions")
+            .join(format!("{safe_name}.json"))
+    }
+
+    /// Get transcript file path
+    fn transcript_path(&self) -> PathBuf {
+        self.worktree_data.join("transcript.json")
     }
 
     /// Get branch memory file path
@@ -70,18 +152,226 @@ impl CompanionStorage {
         self.branches_dir.join(format!("{safe_name}.json"))
     }
 
-    /// Save session state
+    /// Save session state for its branch in this worktree
     pub fn save_session(&self, session: &SessionState) -> Result<()> {
-        let path = self.session_path();
+        let path = self.session_path(&session.branch);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
         Self::atomic_write(&path, session)
     }
 
-    /// Load session state
-    pub fn load_session(&self) -> Result<Option<SessionState>> {
-        let path = self.session_path();
+    /// Load the session for `branch` in this worktree
+    pub fn load_session(&self, branch: &str) -> Result<Option<SessionState>> {
+        Self::load_json(&self.session_path(branch))
+    }
+
+    /// Sessions of the repository's other worktrees, newest first
+    ///
+    /// Only sessions whose worktree still has the session's branch checked
+    /// out count, so finished or removed checkouts drop out on their own.
+    pub fn other_worktree_sessions(&self) -> Result<Vec<SessionState>> {
+        let worktrees_dir = self.repo_dir.join("worktrees");
+        let mut sessions = Vec::new();
+        for entry in fs::read_dir(&worktrees_dir)? {
+            let dir = entry?.path();
+            if dir == self.worktree_data {
+                continue;
+            }
+            let Ok(files) = fs::read_dir(dir.join("sessions")) else {
+                continue;
+            };
+            for file in files {
+                let path = file?.path();
+                match Self::load_json::<SessionState>(&path) {
+                    Ok(Some(session)) if Self::is_checked_out(&session) => sessions.push(session),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Skipping unreadable session: {}", e),
+                }
+            }
+        }
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_activity));
+        Ok(sessions)
+    }
+
+    /// Whether the session's worktree still has its branch checked out
+    fn is_checked_out(session: &SessionState) -> bool {
+        git2::Repository::open(&session.repo_path)
+            .ok()
+            .and_then(|repo| {
+                repo.head()
+                    .ok()
+                    .and_then(|head| head.shorthand().map(str::to_string))
+            })
+            .is_some_and(|branch| branch == session.branch)
+    }
+
+    /// Record the last commit git-iris made
+    pub fn save_last_commit(&self, record: &CommitRecord) -> Result<()> {
+        Self::atomic_write(&self.worktree_data.join("last_commit.json"), record)
+    }
+
+    /// Load the last commit git-iris made
+    pub fn load_last_commit(&self) -> Result<Option<CommitRecord>> {
+        Self::load_json(&self.worktree_data.join("last_commit.json"))
+    }
+
+    /// Forget the last commit record
+    pub fn clear_last_commit(&self) -> Result<()> {
+        Self::remove_if_exists(&self.worktree_data.join("last_commit.json"))
+    }
+
+    /// Save an undone commit's message for the Commit editor
+    pub fn save_draft(&self, draft: &CommitRecord) -> Result<()> {
+        Self::atomic_write(&self.worktree_data.join("draft.json"), draft)
+    }
+
+    /// Load and remove the saved draft, if it was saved on `branch`
+    pub fn take_draft(&self, branch: &str) -> Result<Option<CommitRecord>> {
+        let path = self.worktree_data.join("draft.json");
+        let Some(draft) = Self::load_json::<CommitRecord>(&path)? else {
+            return Ok(None);
+        };
+        if draft.branch != branch {
+            return Ok(None);
+        }
+        Self::remove_if_exists(&path)?;
+        Ok(Some(draft))
+    }
+
+    /// Append a generation's context size report, dropping the oldest
+    pub fn append_context_report(&self, report: &ContextReport) -> Result<()> {
+        let mut reports = self.load_context_reports()?;
+        reports.push(report.clone());
+        let excess = reports.len().saturating_sub(MAX_CONTEXT_REPORTS);
+        reports.drain(..excess);
+        Self::atomic_write(&self.repo_dir.join("context_reports.json"), &reports)
+    }
+
+    /// Load saved context size reports, oldest first
+    pub fn load_context_reports(&self) -> Result<Vec<ContextReport>> {
+        Ok(Self::load_json(&self.repo_dir.join("context_reports.json"))?.unwrap_or_default())
+    }
+
+    /// Save the current work plan, replacing any earlier one
+    pub fn save_plan(&self, plan: &Plan) -> Result<()> {
+        Self::atomic_write(&self.worktree_data.join("plan.json"), plan)
+    }
+
+    /// Load the current work plan
+    pub fn load_plan(&self) -> Result<Option<Plan>> {
+        Self::load_json(&self.worktree_data.join("plan.json"))
+    }
+
+    /// Forget the current work plan
+    pub fn clear_plan(&self) -> Result<()> {
+        Self::remove_if_exists(&self.worktree_data.join("plan.json"))
+    }
+
+    fn remove_if_exists(path: &Path) -> Result<()> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Get the directory holding archived session transcripts
+    fn history_dir(&self) -> PathBuf {
+        self.repo_dir.join("history")
+    }
+
+    /// Save the latest session transcript and archive it for history search
+    pub fn save_transcript(&self, transcript: &SessionTranscript) -> Result<()> {
+        let path = self.transcript_path();
+        Self::atomic_write(&path, transcript)?;
+
+        let history_dir = self.history_dir();
+        fs::create_dir_all(&history_dir).with_context(|| {
+            format!(
+                "Failed to create history directory: {}",
+                history_dir.display()
+            )
+        })?;
+        let archived = history_dir.join(format!("{}.json", transcript.session_id));
+        Self::atomic_write(&archived, transcript)?;
+        self.prune_history()
+    }
+
+    /// Load all archived session transcripts, newest first
+    pub fn load_history(&self) -> Result<Vec<SessionTranscript>> {
+        let mut sessions = Vec::new();
+        for path in self.history_files()? {
+            match Self::load_json::<SessionTranscript>(&path) {
+                Ok(Some(session)) => sessions.push(session),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Skipping unreadable session archive: {}", e),
+            }
+        }
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.exported_at));
+        Ok(sessions)
+    }
+
+    /// List archived transcript files
+    fn history_files(&self) -> Result<Vec<PathBuf>> {
+        let history_dir = self.history_dir();
+        if !history_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&history_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "json") {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+
+    /// Drop the oldest archived sessions beyond `MAX_ARCHIVED_SESSIONS`
+    fn prune_history(&self) -> Result<()> {
+        let mut files: Vec<_> = self
+            .history_files()?
+            .into_iter()
+            .map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (modified, path)
+            })
+            .collect();
+        if files.len() <= MAX_ARCHIVED_SESSIONS {
+            return Ok(());
+        }
+        files.sort_by_key(|(modified, _)| *modified);
+        let excess = files.len() - MAX_ARCHIVED_SESSIONS;
+        for (_, path) in files.into_iter().take(excess) {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Load the latest session transcript
+    pub fn load_transcript(&self) -> Result<Option<SessionTranscript>> {
+        let path = self.transcript_path();
         Self::load_json(&path)
     }
 
+    /// Write a rendered report into the reports directory, returning its path
+    pub fn write_report(&self, file_name: &str, content: &str) -> Result<PathBuf> {
+        let reports_dir = self.repo_dir.join("reports");
+        fs::create_dir_all(&reports_dir).with_context(|| {
+            format!(
+                "Failed to create reports directory: {}",
+                reports_dir.display()
+            )
+        })?;
+        let path = reports_dir.join(file_name);
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write report: {}", path.display()))?;
+        Ok(path)
+    }
+
     /// Save branch memory
     pub fn save_branch_memory(&self, memory: &BranchMemory) -> Result<()> {
         let path = self.branch_path(&memory.branch_name);
@@ -94,6 +384,24 @@ impl CompanionStorage {
         Self::load_json(&path)
     }
 
+    /// Add a message replaced by an amend to the branch's memory
+    pub fn record_message_revision(&self, branch: &str, revision: MessageRevision) -> Result<()> {
+        let mut memory = self
+            .load_branch_memory(branch)?
+            .unwrap_or_else(|| BranchMemory::new(branch.to_string()));
+        memory.record_message_revision(revision);
+        self.save_branch_memory(&memory)
+    }
+
+    /// Add a generated or edited commit message to the branch's memory
+    pub fn record_message_draft(&self, branch: &str, draft: MessageDraft) -> Result<()> {
+        let mut memory = self
+            .load_branch_memory(branch)?
+            .unwrap_or_else(|| BranchMemory::new(branch.to_string()));
+        memory.record_message_draft(draft);
+        self.save_branch_memory(&memory)
+    }
+
     /// Atomic write using temp file + rename
     fn atomic_write<T: serde::Serialize>(path: &Path, data: &T) -> Result<()> {
         let json = serde_json::to_string_pretty(data)?;
@@ -152,12 +460,8 @@ impl CompanionStorage {
         Ok(branches)
     }
 
-    /// Delete session data
-    pub fn clear_session(&self) -> Result<()> {
-        let path = self.session_path();
-        if path.exists() {
-            fs::remove_file(&path)?;
-        }
-        Ok(())
+    /// Delete the session for `branch` in this worktree
+    pub fn clear_session(&self, branch: &str) -> Result<()> {
+        Self::remove_if_exists(&self.session_path(branch))
     }
 }
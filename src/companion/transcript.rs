@@ -0,0 +1,535 @@
+//! Shareable session transcripts for Iris Companion
+//!
+//! A transcript bundles the chat thread, generated artifacts and the diff/refs
+//! they were based on, so a Studio session can be exported as a single report.
+
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Maximum characters of diff kept in a transcript
+const MAX_DIFF_CHARS: usize = 60_000;
+
+/// Output format for session reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// File extension used for reports in this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// What a piece of session history is, used for filtering searches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum HistoryKind {
+    Commit,
+    Review,
+    Pr,
+    Changelog,
+    ReleaseNotes,
+    Chat,
+}
+
+impl HistoryKind {
+    /// All kinds, in display order
+    pub const ALL: [Self; 6] = [
+        Self::Commit,
+        Self::Review,
+        Self::Pr,
+        Self::Changelog,
+        Self::ReleaseNotes,
+        Self::Chat,
+    ];
+
+    /// Human-readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Commit => "Commit Message",
+            Self::Review => "Code Review",
+            Self::Pr => "Pull Request",
+            Self::Changelog => "Changelog",
+            Self::ReleaseNotes => "Release Notes",
+            Self::Chat => "Chat",
+        }
+    }
+
+    /// Short name for compact lists
+    pub fn short_label(self) -> &'static str {
+        match self {
+            Self::Commit => "commit",
+            Self::Review => "review",
+            Self::Pr => "pr",
+            Self::Changelog => "changelog",
+            Self::ReleaseNotes => "release",
+            Self::Chat => "chat",
+        }
+    }
+}
+
+/// A single chat turn in the transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptMessage {
+    /// "user" or "iris"
+    pub role: String,
+    /// Message body (markdown)
+    pub content: String,
+}
+
+/// A generated artifact (commit message, PR description, review, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptArtifact {
+    /// Which kind of artifact this is
+    pub kind: HistoryKind,
+    /// Artifact body (markdown)
+    pub content: String,
+}
+
+/// Everything needed to render a shareable session report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionTranscript {
+    /// Session identifier (matches the Studio history session)
+    pub session_id: Uuid,
+    /// Repository path
+    pub repo_path: PathBuf,
+    /// Branch the session ran on
+    pub branch: String,
+    /// When the session started
+    pub started_at: DateTime<Utc>,
+    /// When the transcript was captured
+    pub exported_at: DateTime<Utc>,
+    /// Refs the artifacts were generated against, e.g. ("PR", "main..HEAD")
+    pub refs: Vec<(String, String)>,
+    /// Chat thread, oldest first
+    pub messages: Vec<TranscriptMessage>,
+    /// Latest version of each generated artifact
+    pub artifacts: Vec<TranscriptArtifact>,
+    /// Unified diff the session was working on (truncated)
+    pub diff: Option<String>,
+}
+
+impl SessionTranscript {
+    /// Create an empty transcript for a repository
+    pub fn new(
+        session_id: Uuid,
+        repo_path: PathBuf,
+        branch: String,
+        started_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            session_id,
+            repo_path,
+            branch,
+            started_at,
+            exported_at: Utc::now(),
+            refs: Vec::new(),
+            messages: Vec::new(),
+            artifacts: Vec::new(),
+            diff: None,
+        }
+    }
+
+    /// Set the diff, truncating oversized patches on a line boundary
+    pub fn set_diff(&mut self, diff: &str) {
+        if diff.trim().is_empty() {
+            self.diff = None;
+            return;
+        }
+        if diff.len() <= MAX_DIFF_CHARS {
+            self.diff = Some(diff.to_string());
+            return;
+        }
+        let mut cut = MAX_DIFF_CHARS;
+        while !diff.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        let cut = diff[..cut].rfind('\n').map_or(cut, |i| i + 1);
+        self.diff = Some(format!(
+            "{}... diff truncated ({} more bytes)\n",
+            &diff[..cut],
+            diff.len() - cut
+        ));
+    }
+
+    /// Whether there is anything worth exporting
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty() && self.artifacts.is_empty() && self.diff.is_none()
+    }
+
+    /// Render as a markdown report
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Iris Session Report\n");
+        let _ = writeln!(out, "- **Repository:** `{}`", self.repo_path.display());
+        let _ = writeln!(out, "- **Branch:** `{}`", self.branch);
+        let _ = writeln!(
+            out,
+            "- **Session:** {} → {}",
+            self.started_at.format("%Y-%m-%d %H:%M UTC"),
+            self.exported_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        for (label, range) in &self.refs {
+            let _ = writeln!(out, "- **{label}:** `{range}`");
+        }
+
+        if !self.artifacts.is_empty() {
+            out.push_str("\n## Artifacts\n");
+            for artifact in &self.artifacts {
+                let _ = write!(
+                    out,
+                    "\n### {}\n\n{}\n",
+                    artifact.kind.label(),
+                    artifact.content.trim_end()
+                );
+            }
+        }
+
+        if !self.messages.is_empty() {
+            out.push_str("\n## Conversation\n");
+            for msg in &self.messages {
+                let who = if msg.role == "user" { "You" } else { "Iris" };
+                let _ = write!(out, "\n**{who}:**\n\n{}\n", msg.content.trim_end());
+            }
+        }
+
+        if let Some(diff) = &self.diff {
+            let _ = write!(out, "\n## Diff\n\n```diff\n{}```\n", diff);
+        }
+
+        out
+    }
+
+    /// Render as a self-contained HTML report
+    pub fn to_html(&self) -> String {
+        let mut out = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Iris Session Report</title>\n<style>\n\
+             body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;color:#222}\n\
+             pre{background:#f6f6f8;padding:.75rem;border-radius:6px;white-space:pre-wrap;overflow-x:auto}\n\
+             .msg{border-left:3px solid #ccc;padding-left:.75rem;margin:1rem 0}\n\
+             .iris{border-color:#c792ea}.user{border-color:#82aaff}\n\
+             .add{color:#22863a}.del{color:#b31d28}.hunk{color:#6f42c1}\n\
+             </style>\n</head>\n<body>\n<h1>Iris Session Report</h1>\n<ul>\n",
+        );
+        let _ = writeln!(
+            out,
+            "<li><b>Repository:</b> <code>{}</code></li>",
+            escape_html(&self.repo_path.display().to_string())
+        );
+        let _ = writeln!(
+            out,
+            "<li><b>Branch:</b> <code>{}</code></li>",
+            escape_html(&self.branch)
+        );
+        let _ = writeln!(
+            out,
+            "<li><b>Session:</b> {} → {}</li>",
+            self.started_at.format("%Y-%m-%d %H:%M UTC"),
+            self.exported_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        for (label, range) in &self.refs {
+            let _ = writeln!(
+                out,
+                "<li><b>{}:</b> <code>{}</code></li>",
+                escape_html(label),
+                escape_html(range)
+            );
+        }
+        out.push_str("</ul>\n");
+
+        if !self.artifacts.is_empty() {
+            out.push_str("<h2>Artifacts</h2>\n");
+            for artifact in &self.artifacts {
+                let _ = writeln!(
+                    out,
+                    "<h3>{}</h3>\n<pre>{}</pre>",
+                    artifact.kind.label(),
+                    escape_html(artifact.content.trim_end())
+                );
+            }
+        }
+
+        if !self.messages.is_empty() {
+            out.push_str("<h2>Conversation</h2>\n");
+            for msg in &self.messages {
+                let (class, who) = if msg.role == "user" {
+                    ("user", "You")
+                } else {
+                    ("iris", "Iris")
+                };
+                let _ = writeln!(
+                    out,
+                    "<div class=\"msg {class}\"><b>{who}</b><pre>{}</pre></div>",
+                    escape_html(msg.content.trim_end())
+                );
+            }
+        }
+
+        if let Some(diff) = &self.diff {
+            out.push_str("<h2>Diff</h2>\n<pre>");
+            for line in diff.lines() {
+                let class = if line.starts_with("@@") {
+                    Some("hunk")
+                } else if line.starts_with('+') && !line.starts_with("+++") {
+                    Some("add")
+                } else if line.starts_with('-') && !line.starts_with("---") {
+                    Some("del")
+                } else {
+                    None
+                };
+                match class {
+                    Some(class) => {
+                        let _ =
+                            writeln!(out, "<span class=\"{class}\">{}</span>", escape_html(line));
+                    }
+                    None => {
+                        let _ = writeln!(out, "{}", escape_html(line));
+                    }
+                }
+            }
+            out.push_str("</pre>\n");
+        }
+
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// Render in the requested format
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.to_markdown(),
+            ReportFormat::Html => self.to_html(),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// History Search
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Max characters shown in a search hit snippet
+const SNIPPET_CHARS: usize = 100;
+
+/// Filters for searching archived sessions
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    /// Whitespace-separated terms; all must appear (case-insensitive)
+    pub query: String,
+    /// Only match this kind of entry
+    pub kind: Option<HistoryKind>,
+    /// Only match sessions captured at or after this time
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl HistoryFilter {
+    /// Check whether a piece of content matches the query terms
+    fn matches_text(&self, text: &str) -> bool {
+        let haystack = text.to_lowercase();
+        self.query
+            .split_whitespace()
+            .all(|term| haystack.contains(&term.to_lowercase()))
+    }
+}
+
+/// A single match from a history search
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    /// Session the match came from
+    pub session_id: Uuid,
+    /// When that session was captured
+    pub date: DateTime<Utc>,
+    /// Branch the session ran on
+    pub branch: String,
+    /// What kind of entry matched
+    pub kind: HistoryKind,
+    /// One-line excerpt around the first match
+    pub snippet: String,
+    /// Full content of the matching entry
+    pub content: String,
+}
+
+impl SessionTranscript {
+    /// Find artifacts and chat messages in this session matching the filter
+    pub fn search(&self, filter: &HistoryFilter) -> Vec<SearchHit> {
+        if filter.since.is_some_and(|since| self.exported_at < since) {
+            return Vec::new();
+        }
+
+        let artifacts = self.artifacts.iter().map(|a| (a.kind, a.content.as_str()));
+        let messages = self
+            .messages
+            .iter()
+            .map(|m| (HistoryKind::Chat, m.content.as_str()));
+
+        artifacts
+            .chain(messages)
+            .filter(|(kind, content)| {
+                filter.kind.is_none_or(|k| k == *kind) && filter.matches_text(content)
+            })
+            .map(|(kind, content)| SearchHit {
+                session_id: self.session_id,
+                date: self.exported_at,
+                branch: self.branch.clone(),
+                kind,
+                snippet: snippet(content, &filter.query),
+                content: content.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Search a set of sessions, newest first
+pub fn search_history(sessions: &[SessionTranscript], filter: &HistoryFilter) -> Vec<SearchHit> {
+    let mut hits: Vec<SearchHit> = sessions.iter().flat_map(|s| s.search(filter)).collect();
+    hits.sort_by_key(|hit| std::cmp::Reverse(hit.date));
+    hits
+}
+
+/// Pick the line containing the first query term (or the first non-empty line)
+fn snippet(content: &str, query: &str) -> String {
+    let first_term = query.split_whitespace().next().map(str::to_lowercase);
+    let line = first_term
+        .and_then(|term| {
+            content
+                .lines()
+                .find(|line| line.to_lowercase().contains(&term))
+        })
+        .or_else(|| content.lines().find(|line| !line.trim().is_empty()))
+        .unwrap_or("")
+        .trim();
+
+    if line.chars().count() > SNIPPET_CHARS {
+        let cut: String = line.chars().take(SNIPPET_CHARS - 1).collect();
+        format!("{cut}…")
+    } else {
+        line.to_string()
+    }
+}
+
+/// Escape text for inclusion in HTML
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> SessionTranscript {
+        let mut t = SessionTranscript::new(
+            Uuid::new_v4(),
+            PathBuf::from("/tmp/repo"),
+            "feature/x".to_string(),
+            Utc::now(),
+        );
+        t.refs.push(("PR".to_string(), "main..HEAD".to_string()));
+        t.messages.push(TranscriptMessage {
+            role: "user".to_string(),
+            content: "Why <this>?".to_string(),
+        });
+        t.messages.push(TranscriptMessage {
+            role: "iris".to_string(),
+            content: "Because.".to_string(),
+        });
+        t.artifacts.push(TranscriptArtifact {
+            kind: HistoryKind::Commit,
+            content: "Add thing".to_string(),
+        });
+        t.set_diff("@@ -1 +1 @@\n-old\n+new\n");
+        t
+    }
+
+    #[test]
+    fn test_markdown_report_sections() {
+        let md = sample().to_markdown();
+        assert!(md.contains("**Branch:** `feature/x`"));
+        assert!(md.contains("**PR:** `main..HEAD`"));
+        assert!(md.contains("### Commit Message"));
+        assert!(md.contains("**You:**\n\nWhy <this>?"));
+        assert!(md.contains("```diff\n@@ -1 +1 @@"));
+    }
+
+    #[test]
+    fn test_html_report_escapes_content() {
+        let html = sample().to_html();
+        assert!(html.contains("Why &lt;this&gt;?"));
+        assert!(html.contains("<span class=\"add\">+new</span>"));
+        assert!(!html.contains("<this>"));
+    }
+
+    #[test]
+    fn test_search_matches_all_terms_and_kind() {
+        let t = sample();
+        let filter = HistoryFilter {
+            query: "add THING".to_string(),
+            ..HistoryFilter::default()
+        };
+        let hits = t.search(&filter);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, HistoryKind::Commit);
+        assert_eq!(hits[0].snippet, "Add thing");
+
+        let chat_only = HistoryFilter {
+            query: "because".to_string(),
+            kind: Some(HistoryKind::Chat),
+            since: None,
+        };
+        assert_eq!(t.search(&chat_only).len(), 1);
+
+        let wrong_kind = HistoryFilter {
+            kind: Some(HistoryKind::Review),
+            ..filter
+        };
+        assert!(t.search(&wrong_kind).is_empty());
+    }
+
+    #[test]
+    fn test_search_respects_since_and_orders_newest_first() {
+        let mut old = sample();
+        old.exported_at = Utc::now() - chrono::Duration::days(30);
+        let new = sample();
+
+        let all = search_history(&[old.clone(), new.clone()], &HistoryFilter::default());
+        assert_eq!(all.len(), 6);
+        assert_eq!(all[0].session_id, new.session_id);
+
+        let recent = HistoryFilter {
+            since: Some(Utc::now() - chrono::Duration::days(7)),
+            ..HistoryFilter::default()
+        };
+        let hits = search_history(&[old, new.clone()], &recent);
+        assert!(hits.iter().all(|h| h.session_id == new.session_id));
+    }
+
+    #[test]
+    fn test_diff_is_truncated() {
+        let mut t = sample();
+        let big = "+line\n".repeat(MAX_DIFF_CHARS);
+        t.set_diff(&big);
+        let diff = t.diff.expect("diff kept");
+        assert!(diff.len() < big.len());
+        assert!(diff.contains("diff truncated"));
+    }
+}
@@ -0,0 +1,1084 @@
+//! Configuration management for Git-Iris.
+//!
+//! Handles personal config (~/.config/git-iris/config.toml) and
+//! per-project config (.irisconfig) with proper layering.
+
+pub mod secrets;
+
+use crate::agents::StatusPersonality;
+use crate::clipboard::Osc52Mode;
+use crate::error::IrisError;
+use crate::git::GitRepo;
+use crate::instruction_presets::get_instruction_preset_library;
+use crate::integrations::github::GitHubConfig;
+use crate::integrations::gitlab::GitLabConfig;
+use crate::lifecycle::LifecycleHooks;
+use crate::log_debug;
+use crate::outputs::OutputsConfig;
+use crate::providers::{Provider, ProviderConfig};
+use crate::types::CommitStyle;
+use crate::usage::ModelPrice;
+
+use anyhow::{Context, Result, anyhow};
+use clap::ValueEnum;
+use dirs::config_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Project configuration filename
+pub const PROJECT_CONFIG_FILENAME: &str = ".irisconfig";
+
+/// Main configuration structure
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Config {
+    /// Default LLM provider
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub default_provider: String,
+    /// Providers tried in order when the default one fails or is rate limited
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_providers: Vec<String>,
+    /// Provider-specific configurations (keyed by provider name)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub providers: HashMap<String, ProviderConfig>,
+    /// Use gitmoji in commit messages
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub use_gitmoji: bool,
+    /// Custom instructions for all operations
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub instructions: String,
+    /// Instruction preset name
+    #[serde(default = "default_preset", skip_serializing_if = "is_default_preset")]
+    pub instruction_preset: String,
+    /// Theme name (empty = default `SilkCircuit` Neon)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub theme: String,
+    /// Timeout in seconds for parallel subagent tasks (default: 120)
+    #[serde(
+        default = "default_subagent_timeout",
+        skip_serializing_if = "is_default_subagent_timeout"
+    )]
+    pub subagent_timeout_secs: u64,
+    /// Sampling parameters per capability (`default` applies to all)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub generation: HashMap<String, GenerationParams>,
+    /// Commit message variants Studio generates at once (1 to `MAX_VARIANTS`)
+    #[serde(
+        default = "default_variant_count",
+        skip_serializing_if = "is_default_variant_count"
+    )]
+    pub variant_count: usize,
+    /// Presets for the first variants, in order; the rest vary temperature
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variant_presets: Vec<String>,
+    /// OSC52 clipboard fallback for remote terminals (personal setting)
+    #[serde(default, skip_serializing_if = "Osc52Mode::is_default")]
+    pub clipboard_osc52: Osc52Mode,
+    /// Tone of Studio's waiting messages
+    #[serde(default, skip_serializing_if = "StatusPersonality::is_default")]
+    pub status_personality: StatusPersonality,
+    /// Highlight likely misspellings in generated text (Studio)
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub spell_check: bool,
+    /// Strip emoji and Unicode punctuation from generated output and reject
+    /// non-ASCII commit messages
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ascii_only: bool,
+    /// Paths that raise the risk score when touched (`src/auth/`, `**/*.sql`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub critical_paths: Vec<String>,
+    /// Casing, forbidden words and prefix rules for commit messages
+    #[serde(default, skip_serializing_if = "CommitStyle::is_empty")]
+    pub commit_style: CommitStyle,
+    /// Documentation always included in PR, review and changelog prompts
+    #[serde(default, skip_serializing_if = "ContextConfig::is_empty")]
+    pub context: ContextConfig,
+    /// When Studio generates without being asked, per mode
+    #[serde(default, skip_serializing_if = "AutoGenerateConfig::is_default")]
+    pub auto_generate: AutoGenerateConfig,
+    /// GitHub token and API endpoint for opening pull requests
+    #[serde(default, skip_serializing_if = "GitHubConfig::is_empty")]
+    pub github: GitHubConfig,
+    /// GitLab token and API endpoint for reading CI status
+    #[serde(default, skip_serializing_if = "GitLabConfig::is_empty")]
+    pub gitlab: GitLabConfig,
+    /// Files `--write` and Studio's save action write artifacts to
+    #[serde(default, skip_serializing_if = "OutputsConfig::is_default")]
+    pub outputs: OutputsConfig,
+    /// Look up the latest git-iris release when Studio starts (personal config only)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub check_for_updates: bool,
+    /// Record feature usage and generation outcomes in a local file that is
+    /// never uploaded (personal config only)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub local_analytics: bool,
+    /// Keep API keys and the GitHub and GitLab tokens in the OS keyring instead of this
+    /// file (personal config only)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub use_keyring: bool,
+    /// Per-model prices (USD per million tokens) overriding the built-in table
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub pricing: HashMap<String, ModelPrice>,
+    /// Shell commands run around generations and commits (personal config only)
+    #[serde(default, skip_serializing_if = "LifecycleHooks::is_empty")]
+    pub hooks: LifecycleHooks,
+    /// Runtime-only: temporary instructions override
+    #[serde(skip)]
+    pub temp_instructions: Option<String>,
+    /// Runtime-only: temporary preset override
+    #[serde(skip)]
+    pub temp_preset: Option<String>,
+    /// Runtime-only: generation params overriding `generation` for this
+    /// session, per capability (`default` applies to all)
+    #[serde(skip)]
+    pub temp_generation: HashMap<String, GenerationParams>,
+    /// Runtime-only: flag if loaded from project config
+    #[serde(skip)]
+    pub is_project_config: bool,
+    /// Runtime-only: whether gitmoji was explicitly set via CLI (None = use style detection)
+    #[serde(skip)]
+    pub gitmoji_override: Option<bool>,
+}
+
+/// Capabilities that accept `[generation.<name>]` overrides
+pub const GENERATION_CAPABILITIES: &[&str] = &[
+    "default",
+    "commit",
+    "review",
+    "pr",
+    "changelog",
+    "release_notes",
+    "chat",
+    "semantic_blame",
+    "release_readiness",
+    "recover",
+    "onboard",
+    "adr",
+    "plan",
+    "rebase",
+    "stash",
+    "semantic_search",
+    "recap",
+    "explain_repo",
+    "file_blame",
+];
+
+/// Sampling and output parameters for a capability
+///
+/// Unset values fall back to the `default` entry, then to provider defaults.
+/// Providers ignore parameters they don't support.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct GenerationParams {
+    /// Sampling temperature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Nucleus sampling cutoff
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Maximum output tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+    /// Presence penalty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f64>,
+    /// Frequency penalty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f64>,
+    /// How long the output should be
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<Verbosity>,
+    /// Register of the generated prose
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tone: Option<Tone>,
+}
+
+impl GenerationParams {
+    /// Whether no parameter is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Overlay the values set in `other`
+    pub fn merge(&mut self, other: &Self) {
+        self.temperature = other.temperature.or(self.temperature);
+        self.top_p = other.top_p.or(self.top_p);
+        self.max_tokens = other.max_tokens.or(self.max_tokens);
+        self.presence_penalty = other.presence_penalty.or(self.presence_penalty);
+        self.frequency_penalty = other.frequency_penalty.or(self.frequency_penalty);
+        self.verbosity = other.verbosity.or(self.verbosity);
+        self.tone = other.tone.or(self.tone);
+    }
+
+    /// Output token limit: `max_tokens` if set, otherwise `default` scaled
+    /// by the verbosity
+    pub fn max_tokens_or(&self, default: u64) -> u64 {
+        self.max_tokens
+            .unwrap_or_else(|| self.verbosity.unwrap_or_default().max_tokens(default))
+    }
+}
+
+/// How long generated output should be
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Verbosity {
+    /// The essentials only
+    Concise,
+    /// The capability prompt's own length
+    #[default]
+    Standard,
+    /// Reasoning, edge cases and examples
+    Detailed,
+}
+
+impl Verbosity {
+    /// All levels, shortest first
+    pub const ALL: &'static [Self] = &[Self::Concise, Self::Standard, Self::Detailed];
+
+    /// Name used in config files and the CLI
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Concise => "concise",
+            Self::Standard => "standard",
+            Self::Detailed => "detailed",
+        }
+    }
+
+    /// Prompt section asking for this length, if it differs from the prompt's own
+    pub fn instructions(self) -> Option<&'static str> {
+        match self {
+            Self::Concise => Some(
+                "Keep the output short. Lead with what matters most, use as few sentences and bullets as carry the meaning, and leave out optional sections and background. Keep every field the format requires.",
+            ),
+            Self::Standard => None,
+            Self::Detailed => Some(
+                "Be thorough. Explain the reasoning behind the changes, cover edge cases, risks and follow-up work, and give examples where they help. Stay within the required format.",
+            ),
+        }
+    }
+
+    /// Output token limit for a capability whose usual limit is `default`
+    pub fn max_tokens(self, default: u64) -> u64 {
+        match self {
+            Self::Concise => default / 2,
+            Self::Standard | Self::Detailed => default,
+        }
+    }
+}
+
+impl std::fmt::Display for Verbosity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Register of generated prose
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Tone {
+    /// Plain and matter-of-fact
+    #[default]
+    Neutral,
+    /// Warm and approachable
+    Friendly,
+    /// Professional, without contractions or colloquialisms
+    Formal,
+}
+
+impl Tone {
+    /// All tones, in settings order
+    pub const ALL: &'static [Self] = &[Self::Neutral, Self::Friendly, Self::Formal];
+
+    /// Name used in config files and the CLI
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Neutral => "neutral",
+            Self::Friendly => "friendly",
+            Self::Formal => "formal",
+        }
+    }
+
+    /// Prompt section asking for this tone, if it differs from the prompt's own
+    pub fn instructions(self) -> Option<&'static str> {
+        match self {
+            Self::Neutral => None,
+            Self::Friendly => Some(
+                "Write in a warm, approachable voice: plain words, active voice, and the tone of a helpful colleague. Stay precise; no jokes or filler.",
+            ),
+            Self::Formal => Some(
+                "Write in a formal, professional register: complete sentences, precise terminology, and no contractions, colloquialisms or exclamation marks.",
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Tone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// `[context]` settings: what gets added to prompts up front
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContextConfig {
+    /// `[context.docs]`: project documentation to include
+    #[serde(default, skip_serializing_if = "DocsContextConfig::is_empty")]
+    pub docs: DocsContextConfig,
+    /// `[context.limits]`: how much of a changeset is read
+    #[serde(default, skip_serializing_if = "LimitsContextConfig::is_empty")]
+    pub limits: LimitsContextConfig,
+}
+
+impl ContextConfig {
+    /// Whether nothing is configured
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty() && self.limits.is_empty()
+    }
+}
+
+/// Size guardrails for reading changes on very large repositories
+///
+/// Past `max_files` files or `max_diff_bytes` of diffs, the remaining files
+/// are listed without their diffs; a single diff or file over
+/// `max_file_bytes` is summarized by its line counts. `--no-limits` turns
+/// the guardrails off.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct LimitsContextConfig {
+    /// Files whose diffs are read
+    pub max_files: usize,
+    /// Diff bytes read across all files
+    pub max_diff_bytes: usize,
+    /// Largest diff or file content read for a single file
+    pub max_file_bytes: usize,
+}
+
+impl LimitsContextConfig {
+    pub const DEFAULT: Self = Self {
+        max_files: 500,
+        max_diff_bytes: 8 * 1024 * 1024,
+        max_file_bytes: 1024 * 1024,
+    };
+
+    /// Whether the settings are all defaults
+    pub fn is_empty(&self) -> bool {
+        *self == Self::DEFAULT
+    }
+}
+
+impl Default for LimitsContextConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Documentation included in PR, review and changelog prompts
+///
+/// Files matching `include` (`README.md`, `ARCHITECTURE.md`, `docs/**`) are
+/// added in pattern order, each cut to `max_file_tokens` and all of them
+/// together to `max_tokens`.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct DocsContextConfig {
+    /// Globs of tracked files to include, relative to the repository root
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Token budget for all included documentation
+    #[serde(
+        default = "default_docs_max_tokens",
+        skip_serializing_if = "is_default_docs_max_tokens"
+    )]
+    pub max_tokens: usize,
+    /// Token budget for a single file
+    #[serde(
+        default = "default_docs_max_file_tokens",
+        skip_serializing_if = "is_default_docs_max_file_tokens"
+    )]
+    pub max_file_tokens: usize,
+}
+
+impl Default for DocsContextConfig {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            max_tokens: default_docs_max_tokens(),
+            max_file_tokens: default_docs_max_file_tokens(),
+        }
+    }
+}
+
+impl DocsContextConfig {
+    /// Whether the settings are all defaults
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// What makes Studio generate in a mode without being asked
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutoTrigger {
+    /// Generate when the mode opens with nothing generated yet
+    #[default]
+    OnEntry,
+    /// Like `on-entry`, and regenerate when the staged changes change
+    /// (Commit mode; other modes treat it as `on-entry`)
+    OnStagedChange,
+    /// Only generate when asked
+    Manual,
+}
+
+impl AutoTrigger {
+    /// Whether this is the default trigger (used to keep config files minimal)
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `[auto_generate]` settings: Studio's automatic generation per mode
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct AutoGenerateConfig {
+    /// Master switch, also toggled from Studio's status bar
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "AutoTrigger::is_default")]
+    pub commit: AutoTrigger,
+    #[serde(default, skip_serializing_if = "AutoTrigger::is_default")]
+    pub review: AutoTrigger,
+    #[serde(default, skip_serializing_if = "AutoTrigger::is_default")]
+    pub pr: AutoTrigger,
+    #[serde(default, skip_serializing_if = "AutoTrigger::is_default")]
+    pub changelog: AutoTrigger,
+    #[serde(default, skip_serializing_if = "AutoTrigger::is_default")]
+    pub release_notes: AutoTrigger,
+}
+
+impl Default for AutoGenerateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            commit: AutoTrigger::default(),
+            review: AutoTrigger::default(),
+            pr: AutoTrigger::default(),
+            changelog: AutoTrigger::default(),
+            release_notes: AutoTrigger::default(),
+        }
+    }
+}
+
+impl AutoGenerateConfig {
+    /// Whether the settings are all defaults
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_docs_max_tokens() -> usize {
+    6000
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_default_docs_max_tokens(val: &usize) -> bool {
+    *val == default_docs_max_tokens()
+}
+
+fn default_docs_max_file_tokens() -> usize {
+    2000
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_default_docs_max_file_tokens(val: &usize) -> bool {
+    *val == default_docs_max_file_tokens()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_true(val: &bool) -> bool {
+    *val
+}
+
+fn default_preset() -> String {
+    "default".to_string()
+}
+
+fn is_default_preset(val: &str) -> bool {
+    val.is_empty() || val == "default"
+}
+
+/// Most commit message variants generated at once
+pub const MAX_VARIANTS: usize = 5;
+
+fn default_variant_count() -> usize {
+    1
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_default_variant_count(val: &usize) -> bool {
+    *val == default_variant_count()
+}
+
+fn default_subagent_timeout() -> u64 {
+    120 // 2 minutes
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_default_subagent_timeout(val: &u64) -> bool {
+    *val == 120
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let mut providers = HashMap::new();
+        for provider in Provider::ALL {
+            providers.insert(
+                provider.name().to_string(),
+                ProviderConfig::with_defaults(*provider),
+            );
+        }
+
+        Self {
+            default_provider: Provider::default().name().to_string(),
+            fallback_providers: Vec::new(),
+            providers,
+            use_gitmoji: true,
+            instructions: String::new(),
+            instruction_preset: default_preset(),
+            theme: String::new(),
+            subagent_timeout_secs: default_subagent_timeout(),
+            generation: HashMap::new(),
+            variant_count: default_variant_count(),
+            variant_presets: Vec::new(),
+            clipboard_osc52: Osc52Mode::Auto,
+            status_personality: StatusPersonality::default(),
+            spell_check: true,
+            ascii_only: false,
+            critical_paths: Vec::new(),
+            commit_style: CommitStyle::default(),
+            context: ContextConfig::default(),
+            auto_generate: AutoGenerateConfig::default(),
+            github: GitHubConfig::default(),
+            gitlab: GitLabConfig::default(),
+            outputs: OutputsConfig::default(),
+            check_for_updates: false,
+            local_analytics: false,
+            use_keyring: false,
+            pricing: HashMap::new(),
+            hooks: LifecycleHooks::default(),
+            temp_instructions: None,
+            temp_preset: None,
+            temp_generation: HashMap::new(),
+            is_project_config: false,
+            gitmoji_override: None,
+        }
+    }
+}
+
+impl Config {
+    /// Load configuration (personal + project overlay)
+    pub fn load() -> Result<Self> {
+        let mut config = Self::load_personal()?;
+
+        // Overlay project config if available
+        if let Ok(project_config) = Self::load_project_config() {
+            config.merge_with_project_config(project_config);
+        }
+        crate::context::set_limits(config.context.limits);
+
+        log_debug!(
+            "Configuration loaded (provider: {}, gitmoji: {})",
+            config.default_provider,
+            config.use_gitmoji
+        );
+        Ok(config)
+    }
+
+    /// Load the personal config file only, without the project overlay
+    pub fn load_personal() -> Result<Self> {
+        let config_path = Self::get_config_path()?;
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&config_path)?;
+        let config: Self = toml::from_str(&content)?;
+        let mut config = Self::migrate_if_needed(config);
+        if config.use_keyring {
+            secrets::load_into(&mut config);
+        }
+        Ok(config)
+    }
+
+    /// Empty project config, used as the base when creating `.irisconfig`
+    pub fn empty_project_config() -> Self {
+        Self {
+            default_provider: String::new(),
+            fallback_providers: Vec::new(),
+            providers: HashMap::new(),
+            use_gitmoji: true,
+            instructions: String::new(),
+            instruction_preset: String::new(),
+            theme: String::new(),
+            subagent_timeout_secs: default_subagent_timeout(),
+            generation: HashMap::new(),
+            variant_count: default_variant_count(),
+            variant_presets: Vec::new(),
+            clipboard_osc52: Osc52Mode::Auto,
+            status_personality: StatusPersonality::default(),
+            spell_check: true,
+            ascii_only: false,
+            critical_paths: Vec::new(),
+            commit_style: CommitStyle::default(),
+            context: ContextConfig::default(),
+            auto_generate: AutoGenerateConfig::default(),
+            github: GitHubConfig::default(),
+            gitlab: GitLabConfig::default(),
+            outputs: OutputsConfig::default(),
+            check_for_updates: false,
+            local_analytics: false,
+            use_keyring: false,
+            pricing: HashMap::new(),
+            hooks: LifecycleHooks::default(),
+            temp_instructions: None,
+            temp_preset: None,
+            temp_generation: HashMap::new(),
+            is_project_config: true,
+            gitmoji_override: None,
+        }
+    }
+
+    /// Effective generation params for a capability: `default` overlaid with
+    /// its own, then the session overrides in the same order
+    pub fn generation_params(&self, capability: &str) -> GenerationParams {
+        let mut params = GenerationParams::default();
+        for layer in [&self.generation, &self.temp_generation] {
+            for key in ["default", capability] {
+                if let Some(own) = layer.get(key) {
+                    params.merge(own);
+                }
+            }
+        }
+        params
+    }
+
+    /// Load project-specific configuration
+    pub fn load_project_config() -> Result<Self> {
+        let config_path = Self::get_project_config_path()?;
+        if !config_path.exists() {
+            return Err(anyhow!("Project configuration file not found"));
+        }
+
+        let content = fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+
+        let mut config: Self = toml::from_str(&content).with_context(|| {
+            format!(
+                "Invalid {} format. Check for syntax errors.",
+                PROJECT_CONFIG_FILENAME
+            )
+        })?;
+
+        config.is_project_config = true;
+        Ok(config)
+    }
+
+    /// Get path to project config file
+    pub fn get_project_config_path() -> Result<PathBuf> {
+        let repo_root = GitRepo::get_repo_root()?;
+        Ok(repo_root.join(PROJECT_CONFIG_FILENAME))
+    }
+
+    /// Merge project config into this config (project takes precedence, but never API keys)
+    pub fn merge_with_project_config(&mut self, project_config: Self) {
+        log_debug!("Merging with project configuration");
+
+        // Override default provider if set
+        if !project_config.default_provider.is_empty()
+            && project_config.default_provider != Provider::default().name()
+        {
+            self.default_provider = project_config.default_provider;
+        }
+
+        // Merge provider configs (never override API keys from project config)
+        for (provider_name, proj_config) in project_config.providers {
+            let entry = self.providers.entry(provider_name).or_default();
+
+            if !proj_config.model.is_empty() {
+                entry.model = proj_config.model;
+            }
+            if proj_config.fast_model.is_some() {
+                entry.fast_model = proj_config.fast_model;
+            }
+            if proj_config.token_limit.is_some() {
+                entry.token_limit = proj_config.token_limit;
+            }
+            if proj_config.organization.is_some() {
+                entry.organization = proj_config.organization;
+            }
+            if proj_config.project.is_some() {
+                entry.project = proj_config.project;
+            }
+            entry
+                .additional_params
+                .extend(proj_config.additional_params);
+            entry.headers.extend(proj_config.headers);
+        }
+
+        // Override other settings
+        self.use_gitmoji = project_config.use_gitmoji;
+        self.instructions = project_config.instructions;
+
+        if project_config.instruction_preset != default_preset() {
+            self.instruction_preset = project_config.instruction_preset;
+        }
+
+        // Theme override
+        if !project_config.theme.is_empty() {
+            self.theme = project_config.theme;
+        }
+
+        if !project_config.status_personality.is_default() {
+            self.status_personality = project_config.status_personality;
+        }
+
+        // Subagent timeout override
+        if project_config.subagent_timeout_secs != default_subagent_timeout() {
+            self.subagent_timeout_secs = project_config.subagent_timeout_secs;
+        }
+
+        if project_config.variant_count != default_variant_count() {
+            self.variant_count = project_config.variant_count;
+        }
+        if !project_config.variant_presets.is_empty() {
+            self.variant_presets = project_config.variant_presets;
+        }
+        if !project_config.fallback_providers.is_empty() {
+            self.fallback_providers = project_config.fallback_providers;
+        }
+
+        // A project can require ASCII-only output; it can't lift a personal setting
+        self.ascii_only |= project_config.ascii_only;
+
+        if !project_config.critical_paths.is_empty() {
+            self.critical_paths = project_config.critical_paths;
+        }
+
+        // Commit style rules are per-project house rules
+        if !project_config.commit_style.is_empty() {
+            self.commit_style = project_config.commit_style;
+        }
+
+        // Which docs matter is a property of the repository
+        if !project_config.context.docs.is_empty() {
+            self.context.docs = project_config.context.docs;
+        }
+        if !project_config.context.limits.is_empty() {
+            self.context.limits = project_config.context.limits;
+        }
+
+        if !project_config.auto_generate.is_default() {
+            self.auto_generate = project_config.auto_generate;
+        }
+
+        // `[github]` and `[gitlab]` are never taken from a project: the personal
+        // tokens are sent to `api_url`, so a cloned repository mustn't be able
+        // to redirect them
+
+        // Where artifacts live is a repository convention
+        if !project_config.outputs.is_default() {
+            self.outputs = project_config.outputs;
+        }
+
+        // Generation params merge field by field
+        for (capability, params) in project_config.generation {
+            self.generation
+                .entry(capability)
+                .or_default()
+                .merge(&params);
+        }
+    }
+
+    /// Migrate older config formats
+    fn migrate_if_needed(mut config: Self) -> Self {
+        let mut migrated = false;
+
+        // Migrate "claude" provider to "anthropic"
+        if config.providers.contains_key("claude") {
+            log_debug!("Migrating 'claude' provider to 'anthropic'");
+            if let Some(claude_config) = config.providers.remove("claude") {
+                config
+                    .providers
+                    .insert("anthropic".to_string(), claude_config);
+            }
+            if config.default_provider == "claude" {
+                config.default_provider = "anthropic".to_string();
+            }
+            migrated = true;
+        }
+
+        if migrated && let Err(e) = config.save() {
+            log_debug!("Failed to save migrated config: {}", e);
+        }
+
+        config
+    }
+
+    /// Save configuration to personal config file
+    pub fn save(&self) -> Result<()> {
+        if self.is_project_config {
+            return Ok(());
+        }
+        crate::read_only::ensure_writable("Saving the configuration")?;
+
+        let config_path = Self::get_config_path()?;
+        let content = if self.use_keyring {
+            let mut config = self.clone();
+            secrets::store_from(&mut config)?;
+            toml::to_string_pretty(&config)?
+        } else {
+            toml::to_string_pretty(self)?
+        };
+        fs::write(config_path, content)?;
+        log_debug!("Configuration saved");
+        Ok(())
+    }
+
+    /// Move the personal config's API keys and forge tokens into the OS
+    /// keyring and keep them there from now on
+    ///
+    /// Returns the keyring entries the secrets moved to; the config file is
+    /// rewritten without them.
+    pub fn migrate_secrets(&mut self) -> Result<Vec<String>> {
+        let moved = secrets::plaintext_secrets(self);
+        self.use_keyring = true;
+        self.save()?;
+        Ok(moved)
+    }
+
+    /// Save as project-specific configuration (strips API keys)
+    pub fn save_as_project_config(&self) -> Result<()> {
+        crate::read_only::ensure_writable("Writing the project configuration")?;
+        let config_path = Self::get_project_config_path()?;
+
+        let mut project_config = self.clone();
+        project_config.is_project_config = true;
+
+        // Strip API keys for security
+        for provider_config in project_config.providers.values_mut() {
+            provider_config.api_key.clear();
+        }
+        project_config.github = GitHubConfig::default();
+        project_config.gitlab = GitLabConfig::default();
+        project_config.check_for_updates = false;
+        project_config.local_analytics = false;
+        project_config.use_keyring = false;
+        project_config.hooks = LifecycleHooks::default();
+
+        let content = toml::to_string_pretty(&project_config)?;
+        fs::write(config_path, content)?;
+        Ok(())
+    }
+
+    /// Get path to personal config file
+    pub fn get_config_path() -> Result<PathBuf> {
+        let mut path =
+            config_dir().ok_or_else(|| anyhow!("Unable to determine config directory"))?;
+        path.push("git-iris");
+        fs::create_dir_all(&path)?;
+        path.push("config.toml");
+        Ok(path)
+    }
+
+    /// Check environment prerequisites
+    pub fn check_environment(&self) -> Result<()> {
+        if !GitRepo::is_inside_repository() {
+            return Err(anyhow!(
+                "Not in a Git repository. Please run this command from within a Git repository."
+            ));
+        }
+        Ok(())
+    }
+
+    /// Set temporary instructions for this session
+    pub fn set_temp_instructions(&mut self, instructions: Option<String>) {
+        self.temp_instructions = instructions;
+    }
+
+    /// Set temporary preset for this session
+    pub fn set_temp_preset(&mut self, preset: Option<String>) {
+        self.temp_preset = preset;
+    }
+
+    /// Get effective preset name (temp overrides saved)
+    pub fn get_effective_preset_name(&self) -> &str {
+        self.temp_preset
+            .as_deref()
+            .unwrap_or(&self.instruction_preset)
+    }
+
+    /// Get effective instructions (combines preset + custom)
+    pub fn get_effective_instructions(&self) -> String {
+        let preset_library = get_instruction_preset_library();
+        let preset_instructions = self
+            .temp_preset
+            .as_ref()
+            .or(Some(&self.instruction_preset))
+            .and_then(|p| preset_library.get_preset(p))
+            .map(|p| p.instructions.clone())
+            .unwrap_or_default();
+
+        let custom = self
+            .temp_instructions
+            .as_ref()
+            .unwrap_or(&self.instructions);
+
+        format!("{preset_instructions}\n\n{custom}")
+            .trim()
+            .to_string()
+    }
+
+    /// Update configuration with new values
+    #[allow(clippy::too_many_arguments, clippy::needless_pass_by_value)]
+    pub fn update(
+        &mut self,
+        provider: Option<String>,
+        api_key: Option<String>,
+        model: Option<String>,
+        fast_model: Option<String>,
+        additional_params: Option<HashMap<String, String>>,
+        use_gitmoji: Option<bool>,
+        instructions: Option<String>,
+        token_limit: Option<usize>,
+    ) -> Result<()> {
+        if let Some(ref provider_name) = provider {
+            // Validate provider
+            let parsed: Provider = provider_name.parse().with_context(|| {
+                format!(
+                    "Unknown provider '{}'. Supported: {}",
+                    provider_name,
+                    Provider::all_names().join(", ")
+                )
+            })?;
+
+            self.default_provider = parsed.name().to_string();
+
+            // Ensure provider config exists
+            if !self.providers.contains_key(parsed.name()) {
+                self.providers.insert(
+                    parsed.name().to_string(),
+                    ProviderConfig::with_defaults(parsed),
+                );
+            }
+        }
+
+        let provider_config = self
+            .providers
+            .get_mut(&self.default_provider)
+            .context("Could not get default provider config")?;
+
+        if let Some(key) = api_key {
+            provider_config.api_key = key;
+        }
+        if let Some(m) = model {
+            provider_config.model = m;
+        }
+        if let Some(fm) = fast_model {
+            provider_config.fast_model = Some(fm);
+        }
+        if let Some(params) = additional_params {
+            provider_config.additional_params.extend(params);
+        }
+        if let Some(gitmoji) = use_gitmoji {
+            self.use_gitmoji = gitmoji;
+        }
+        if let Some(instr) = instructions {
+            self.instructions = instr;
+        }
+        if let Some(limit) = token_limit {
+            provider_config.token_limit = Some(limit);
+        }
+
+        log_debug!("Configuration updated");
+        Ok(())
+    }
+
+    /// Get the provider configuration for a specific provider
+    pub fn get_provider_config(&self, provider: &str) -> Option<&ProviderConfig> {
+        // Handle legacy "claude" alias
+        let name = if provider.eq_ignore_ascii_case("claude") {
+            "anthropic"
+        } else {
+            provider
+        };
+
+        self.providers
+            .get(name)
+            .or_else(|| self.providers.get(&name.to_lowercase()))
+    }
+
+    /// HTTP headers to send with every request to `provider`
+    pub fn request_headers(&self, provider: Provider) -> Vec<(String, String)> {
+        self.get_provider_config(provider.name()).map_or_else(
+            || ProviderConfig::default().request_headers(provider),
+            |config| config.request_headers(provider),
+        )
+    }
+
+    /// Context tokens `provider` may use: its `token_limit`, or the model's
+    /// context window
+    pub fn token_limit(&self, provider: Provider) -> usize {
+        self.get_provider_config(provider.name()).map_or_else(
+            || provider.context_window(),
+            |config| config.effective_token_limit(provider),
+        )
+    }
+
+    /// Get the current provider as `Provider` enum
+    pub fn provider(&self) -> Option<Provider> {
+        self.default_provider.parse().ok()
+    }
+
+    /// The default provider, when it needs an API key, none is configured or
+    /// set in the environment, and no fallback provider is usable either
+    pub fn missing_api_key(&self) -> Option<Provider> {
+        let provider: Provider = self.default_provider.parse().ok()?;
+        let fallback_ready = self
+            .fallback_providers
+            .iter()
+            .filter_map(|name| name.parse::<Provider>().ok())
+            .any(|fallback| !self.lacks_api_key(fallback));
+        (self.lacks_api_key(provider) && !fallback_ready).then_some(provider)
+    }
+
+    /// Whether `provider` needs an API key and has none in the config or
+    /// the environment
+    pub fn lacks_api_key(&self, provider: Provider) -> bool {
+        let configured = self
+            .get_provider_config(provider.name())
+            .is_some_and(ProviderConfig::has_api_key);
+        provider.requires_api_key() && !configured && provider.env_api_key().is_none()
+    }
+
+    /// Validate that the current provider is properly configured
+    pub fn validate(&self) -> Result<()> {
+        let provider: Provider = self
+            .default_provider
+            .parse()
+            .with_context(|| format!("Invalid provider: {}", self.default_provider))?;
+
+        let config = self
+            .get_provider_config(provider.name())
+            .ok_or_else(|| anyhow!("No configuration found for provider: {}", provider.name()))?;
+
+        if provider.requires_api_key() && !config.has_api_key() {
+            // Check environment variable as fallback
+            if provider.env_api_key().is_none() {
+                return Err(IrisError::MissingApiKey { provider }.into());
+            }
+        }
+
+        Ok(())
+    }
+}
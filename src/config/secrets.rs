@@ -0,0 +1,158 @@
+//! API keys in the OS keyring
+//!
+//! With `use_keyring = true` in the personal config, provider API keys and
+//! the GitHub and GitLab tokens live in the macOS Keychain, the Secret Service
+//! (GNOME Keyring, `KWallet`) or the Windows Credential Manager instead of
+//! `config.toml`. Each secret is an entry of the `git-iris` service, named
+//! like `openai.api_key` or `github.token`. They are read into the config
+//! when it loads and moved back out whenever it is saved, so the rest of
+//! git-iris sees the same fields either way.
+
+use super::Config;
+use crate::log_debug;
+
+use anyhow::{Context, Result, bail};
+use keyring::Entry;
+
+/// Keyring service every secret is stored under
+pub const SERVICE: &str = "git-iris";
+
+/// Entry name for the GitHub token
+pub const GITHUB_TOKEN: &str = "github.token";
+
+/// Entry name for the GitLab token
+pub const GITLAB_TOKEN: &str = "gitlab.token";
+
+/// Entry name for a provider's API key
+pub fn api_key_name(provider: &str) -> String {
+    format!("{provider}.api_key")
+}
+
+fn entry(name: &str) -> Result<Entry> {
+    Entry::new(SERVICE, name).with_context(|| format!("Invalid keyring entry '{name}'"))
+}
+
+/// Read a secret, or `None` when the keyring has no entry for it
+pub fn get(name: &str) -> Result<Option<String>> {
+    match entry(name)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read '{name}' from the keyring")),
+    }
+}
+
+/// Store a secret, checking that the keyring gives it back
+pub fn set(name: &str, secret: &str) -> Result<()> {
+    entry(name)?
+        .set_password(secret)
+        .with_context(|| format!("Failed to write '{name}' to the keyring"))?;
+    if get(name)?.as_deref() != Some(secret) {
+        bail!("The keyring did not keep '{name}'; is a keyring daemon running?");
+    }
+    Ok(())
+}
+
+/// Fill API keys and forge tokens the config leaves empty from the keyring
+///
+/// A keyring that can't be reached is logged and skipped, so the
+/// environment variables still work as a fallback.
+pub fn load_into(config: &mut Config) {
+    let read = |name: &str| match get(name) {
+        Ok(secret) => secret,
+        Err(e) => {
+            log_debug!("Keyring lookup failed: {:#}", e);
+            None
+        }
+    };
+    for (provider, provider_config) in &mut config.providers {
+        if provider_config.api_key.is_empty()
+            && let Some(key) = read(&api_key_name(provider))
+        {
+            provider_config.api_key = key;
+        }
+    }
+    if config.github.token.is_empty()
+        && let Some(token) = read(GITHUB_TOKEN)
+    {
+        config.github.token = token;
+    }
+    if config.gitlab.token.is_empty()
+        && let Some(token) = read(GITLAB_TOKEN)
+    {
+        config.gitlab.token = token;
+    }
+}
+
+/// Move every API key and forge token from the config into the
+/// keyring, clearing them in the config
+///
+/// Secrets the keyring already holds are not rewritten.
+pub fn store_from(config: &mut Config) -> Result<()> {
+    let mut secrets: Vec<(String, &mut String)> = config
+        .providers
+        .iter_mut()
+        .map(|(provider, provider_config)| (api_key_name(provider), &mut provider_config.api_key))
+        .collect();
+    secrets.push((GITHUB_TOKEN.to_string(), &mut config.github.token));
+    secrets.push((GITLAB_TOKEN.to_string(), &mut config.gitlab.token));
+    secrets.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (name, secret) in secrets {
+        if secret.is_empty() {
+            continue;
+        }
+        if get(&name)?.as_deref() != Some(secret.as_str()) {
+            set(&name, secret)?;
+        }
+        secret.clear();
+    }
+    Ok(())
+}
+
+/// Names of the secrets a config holds in plain text
+pub fn plaintext_secrets(config: &Config) -> Vec<String> {
+    let mut names: Vec<String> = config
+        .providers
+        .iter()
+        .filter(|(_, provider_config)| provider_config.has_api_key())
+        .map(|(provider, _)| api_key_name(provider))
+        .collect();
+    if !config.github.token.is_empty() {
+        names.push(GITHUB_TOKEN.to_string());
+    }
+    if !config.gitlab.token.is_empty() {
+        names.push(GITLAB_TOKEN.to_string());
+    }
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{Provider, ProviderConfig};
+
+    #[test]
+    fn test_plaintext_secrets() {
+        let mut config = Config::default();
+        config.providers.insert(
+            "openai".to_string(),
+            ProviderConfig {
+                api_key: "sk-test".to_string(),
+                ..ProviderConfig::with_defaults(Provider::OpenAI)
+            },
+        );
+        config.providers.insert(
+            "ollama".to_string(),
+            ProviderConfig::with_defaults(Provider::Ollama),
+        );
+        config.github.token = "ghp_test".to_string();
+        config.gitlab.token = "glpat_test".to_string();
+
+        assert_eq!(
+            plaintext_secrets(&config),
+            ["github.token", "gitlab.token", "openai.api_key"]
+        );
+        assert_eq!(api_key_name("anthropic"), "anthropic.api_key");
+    }
+}
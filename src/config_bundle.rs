@@ -0,0 +1,262 @@
+//! Team-shareable configuration bundles
+//!
+//! A bundle is a single TOML file carrying settings, instruction presets,
+//! generation parameters, prompt overrides and a custom theme, so a team can
+//! configure everyone identically. API keys are never exported or imported.
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::agents::prompts::{CAPABILITIES, PROMPTS_DIR};
+use crate::config::Config;
+
+/// Current bundle format version
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// A packaged team configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    /// Bundle format version
+    pub version: u32,
+    /// When the bundle was exported
+    pub created_at: DateTime<Utc>,
+    /// Settings (providers carry models and params only, never keys)
+    pub settings: Config,
+    /// Prompt override files keyed by name (`review`, `review.append`, …)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub prompts: BTreeMap<String, String>,
+    /// Custom theme referenced by `settings.theme`, if it isn't built in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<BundledTheme>,
+}
+
+/// A custom theme file carried in a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledTheme {
+    /// Theme identifier (file stem)
+    pub name: String,
+    /// Theme file contents
+    pub content: String,
+}
+
+/// What an import wrote
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    /// Prompt override files written
+    pub prompts: Vec<PathBuf>,
+    /// Theme file written
+    pub theme: Option<PathBuf>,
+    /// Prompt overrides skipped because no repository was available
+    pub skipped_prompts: usize,
+}
+
+impl ConfigBundle {
+    /// Package the effective config plus the repository's prompt overrides
+    pub fn collect(config: &Config, repo_root: Option<&Path>) -> Result<Self> {
+        let mut settings = config.clone();
+        settings.is_project_config = false;
+        settings.temp_instructions = None;
+        settings.temp_preset = None;
+        for provider in settings.providers.values_mut() {
+            provider.api_key.clear();
+        }
+
+        let mut prompts = BTreeMap::new();
+        if let Some(root) = repo_root {
+            let dir = root.join(PROMPTS_DIR);
+            for name in prompt_names() {
+                let path = dir.join(format!("{name}.md"));
+                if path.is_file() {
+                    prompts.insert(name, fs::read_to_string(&path)?);
+                }
+            }
+        }
+
+        let theme = crate::theme::list_available_themes()
+            .into_iter()
+            .find(|info| !info.builtin && info.name == config.theme)
+            .and_then(|info| info.path)
+            .map(|path| {
+                fs::read_to_string(&path).map(|content| BundledTheme {
+                    name: config.theme.clone(),
+                    content,
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            created_at: Utc::now(),
+            settings,
+            prompts,
+            theme,
+        })
+    }
+
+    /// Serialize the bundle to TOML
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    /// Parse a bundle, rejecting unknown versions and unsafe prompt names
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let bundle: Self = toml::from_str(content).context("Invalid config bundle")?;
+        if bundle.version > BUNDLE_VERSION {
+            bail!(
+                "Bundle version {} is newer than supported version {}; upgrade git-iris",
+                bundle.version,
+                BUNDLE_VERSION
+            );
+        }
+        let valid = prompt_names();
+        if let Some(name) = bundle.prompts.keys().find(|name| !valid.contains(name)) {
+            bail!("Unknown prompt override in bundle: {name}");
+        }
+        if let Some(theme) = &bundle.theme
+            && !is_safe_file_stem(&theme.name)
+        {
+            bail!("Invalid theme name in bundle: {}", theme.name);
+        }
+        Ok(bundle)
+    }
+
+    /// Copy bundled settings into `config`, keeping its API keys
+    pub fn apply_settings(&self, config: &mut Config) {
+        let settings = &self.settings;
+        config
+            .default_provider
+            .clone_from(&settings.default_provider);
+        for (name, bundled) in &settings.providers {
+            let entry = config.providers.entry(name.clone()).or_default();
+            entry.model.clone_from(&bundled.model);
+            entry.fast_model.clone_from(&bundled.fast_model);
+            entry.token_limit = bundled.token_limit;
+            entry
+                .additional_params
+                .clone_from(&bundled.additional_params);
+        }
+        config.use_gitmoji = settings.use_gitmoji;
+        config.instructions.clone_from(&settings.instructions);
+        config
+            .instruction_preset
+            .clone_from(&settings.instruction_preset);
+        config.theme.clone_from(&settings.theme);
+        config.subagent_timeout_secs = settings.subagent_timeout_secs;
+        config.generation.clone_from(&settings.generation);
+        config.ascii_only = settings.ascii_only;
+        config.critical_paths.clone_from(&settings.critical_paths);
+    }
+
+    /// Write prompt overrides into the repository and the theme into `themes_dir`
+    pub fn install_files(
+        &self,
+        repo_root: Option<&Path>,
+        themes_dir: Option<&Path>,
+    ) -> Result<ImportSummary> {
+        crate::read_only::ensure_writable("Importing a config bundle")?;
+        let mut summary = ImportSummary::default();
+
+        match repo_root {
+            Some(root) if !self.prompts.is_empty() => {
+                let dir = root.join(PROMPTS_DIR);
+                fs::create_dir_all(&dir)?;
+                for (name, content) in &self.prompts {
+                    let path = dir.join(format!("{name}.md"));
+                    fs::write(&path, content)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    summary.prompts.push(path);
+                }
+            }
+            Some(_) => {}
+            None => summary.skipped_prompts = self.prompts.len(),
+        }
+
+        if let (Some(theme), Some(dir)) = (&self.theme, themes_dir) {
+            fs::create_dir_all(dir)?;
+            let path = dir.join(format!("{}.toml", theme.name));
+            fs::write(&path, &theme.content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            summary.theme = Some(path);
+        }
+
+        Ok(summary)
+    }
+}
+
+/// Directory custom themes are installed into
+pub fn user_themes_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("git-iris").join("themes"))
+}
+
+/// Every prompt override name a bundle may carry
+fn prompt_names() -> Vec<String> {
+    CAPABILITIES
+        .iter()
+        .flat_map(|c| [(*c).to_string(), format!("{c}.append")])
+        .collect()
+}
+
+fn is_safe_file_stem(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bundle_roundtrip_strips_api_keys() {
+        let root = TempDir::new().expect("temp dir");
+        let dir = root.path().join(PROMPTS_DIR);
+        fs::create_dir_all(&dir).expect("prompts dir");
+        fs::write(dir.join("review.append.md"), "Check migrations.").expect("write");
+
+        let mut config = Config {
+            instruction_preset: "conventional".to_string(),
+            ..Config::default()
+        };
+        if let Some(openai) = config.providers.get_mut("openai") {
+            openai.api_key = "sk-secret".to_string();
+        }
+
+        let bundle = ConfigBundle::collect(&config, Some(root.path())).expect("collect");
+        let toml = bundle.to_toml().expect("serialize");
+        assert!(!toml.contains("sk-secret"));
+
+        let parsed = ConfigBundle::from_toml(&toml).expect("parse");
+        assert_eq!(
+            parsed.prompts.get("review.append").map(String::as_str),
+            Some("Check migrations.")
+        );
+
+        let mut target = Config::default();
+        if let Some(openai) = target.providers.get_mut("openai") {
+            openai.api_key = "sk-mine".to_string();
+        }
+        parsed.apply_settings(&mut target);
+        assert_eq!(target.instruction_preset, "conventional");
+        assert_eq!(
+            target.providers.get("openai").map(|p| p.api_key.as_str()),
+            Some("sk-mine")
+        );
+    }
+
+    #[test]
+    fn test_rejects_unsafe_prompt_names() {
+        let mut bundle =
+            ConfigBundle::collect(&Config::default(), None).expect("collect default bundle");
+        bundle
+            .prompts
+            .insert("../../evil".to_string(), "x".to_string());
+        let toml = bundle.to_toml().expect("serialize");
+        assert!(ConfigBundle::from_toml(&toml).is_err());
+    }
+}
@@ -1,5 +1,10 @@
 use serde::Serialize;
 use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
+
+use crate::common::format_size;
+use crate::config::LimitsContextConfig;
 
 /// Git context information for pre-flight checks and TUI display.
 /// The agent gathers detailed context dynamically via tools.
@@ -63,3 +68,177 @@ impl CommitContext {
         }
     }
 }
+
+/// Start of the text left in place of a diff the size limits kept out
+const SUMMARY_PREFIX: &str = "[Diff summarized:";
+
+/// Size limits for reading changes, from `[context.limits]`
+static LIMITS: RwLock<LimitsContextConfig> = RwLock::new(LimitsContextConfig::DEFAULT);
+
+/// Whether `--no-limits` turned the size limits off
+static NO_LIMITS: AtomicBool = AtomicBool::new(false);
+
+/// Warnings about files the limits held back, waiting to be shown
+static LIMIT_WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Use `limits` for the rest of the process
+pub fn set_limits(limits: LimitsContextConfig) {
+    if let Ok(mut current) = LIMITS.write() {
+        *current = limits;
+    }
+}
+
+/// Turn the size limits off (`--no-limits`) or back on
+pub fn set_no_limits(enabled: bool) {
+    NO_LIMITS.store(enabled, Ordering::Relaxed);
+}
+
+/// The size limits in force, or `None` with `--no-limits`
+pub fn active_limits() -> Option<LimitsContextConfig> {
+    if NO_LIMITS.load(Ordering::Relaxed) {
+        return None;
+    }
+    Some(
+        LIMITS
+            .read()
+            .map_or(LimitsContextConfig::DEFAULT, |limits| *limits),
+    )
+}
+
+/// Warnings about summarized files since the last call
+pub fn take_limit_warnings() -> Vec<String> {
+    LIMIT_WARNINGS
+        .lock()
+        .map(|mut warnings| std::mem::take(&mut *warnings))
+        .unwrap_or_default()
+}
+
+/// Whether `diff` is a summary left by the size limits
+pub fn is_summarized(diff: &str) -> bool {
+    diff.starts_with(SUMMARY_PREFIX)
+}
+
+/// Size limits for one extraction of changed files
+///
+/// Every place that reads diffs asks [`Self::skip`] before reading one and
+/// passes what it read through [`Self::check_diff`], so the limits hold the
+/// same way for staged changes, commits, ranges and branches.
+#[derive(Debug)]
+pub struct Guardrails {
+    limits: Option<LimitsContextConfig>,
+    files: usize,
+    diff_bytes: usize,
+    summarized: usize,
+}
+
+impl Guardrails {
+    /// Guardrails with the limits in force
+    pub fn active() -> Self {
+        Self::with_limits(active_limits())
+    }
+
+    pub fn with_limits(limits: Option<LimitsContextConfig>) -> Self {
+        Self {
+            limits,
+            files: 0,
+            diff_bytes: 0,
+            summarized: 0,
+        }
+    }
+
+    /// Summary to use instead of reading the next file's diff, once the
+    /// file count or total diff size is reached
+    pub fn skip(&mut self) -> Option<String> {
+        let limits = self.limits?;
+        self.files += 1;
+        if self.files > limits.max_files || self.diff_bytes >= limits.max_diff_bytes {
+            self.summarized += 1;
+            return Some(format!("{SUMMARY_PREFIX} repository size limits reached]"));
+        }
+        None
+    }
+
+    /// The diff as read, or its line counts when it's over the per-file limit
+    pub fn check_diff(&mut self, diff: String) -> String {
+        let Some(limits) = self.limits else {
+            return diff;
+        };
+        if diff.len() <= limits.max_file_bytes {
+            self.diff_bytes += diff.len();
+            return diff;
+        }
+        self.summarized += 1;
+        let added = diff.lines().filter(|line| line.starts_with('+')).count();
+        let removed = diff.lines().filter(|line| line.starts_with('-')).count();
+        format!(
+            "{SUMMARY_PREFIX} +{added} -{removed} lines; {} is over the {} per-file limit]",
+            format_size(byte_count(diff.len())),
+            format_size(byte_count(limits.max_file_bytes))
+        )
+    }
+
+    /// Whether file content of `bytes` may be read
+    pub fn allows_content(&self, bytes: u64) -> bool {
+        self.limits
+            .is_none_or(|limits| bytes <= byte_count(limits.max_file_bytes))
+    }
+
+    /// Queue a warning when any file was summarized
+    pub fn finish(self) {
+        let (Some(limits), 1..) = (self.limits, self.summarized) else {
+            return;
+        };
+        let warning = format!(
+            "{} of {} changed files were summarized without their full diffs to stay within the size limits ({} files, {} of diffs, {} per file). Use --no-limits to read everything, or raise the limits under [context.limits].",
+            self.summarized,
+            self.files,
+            limits.max_files,
+            format_size(byte_count(limits.max_diff_bytes)),
+            format_size(byte_count(limits.max_file_bytes))
+        );
+        crate::log_debug!("{}", warning);
+        if let Ok(mut warnings) = LIMIT_WARNINGS.lock()
+            && !warnings.contains(&warning)
+        {
+            warnings.push(warning);
+        }
+    }
+}
+
+fn byte_count(len: usize) -> u64 {
+    u64::try_from(len).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guardrails_summarize_past_limits() {
+        let limits = LimitsContextConfig {
+            max_files: 4,
+            max_diff_bytes: 600,
+            max_file_bytes: 600,
+        };
+        let mut guardrails = Guardrails::with_limits(Some(limits));
+        assert!(guardrails.skip().is_none());
+        let diff = guardrails.check_diff("+ added\n".repeat(100));
+        assert!(is_summarized(&diff));
+        assert!(diff.contains("+100 -0 lines"));
+
+        assert!(guardrails.skip().is_none());
+        assert_eq!(guardrails.check_diff("-x\n".repeat(10)), "-x\n".repeat(10));
+        assert!(guardrails.skip().is_none());
+        assert!(!is_summarized(&guardrails.check_diff("+y\n".repeat(200))));
+        assert!(guardrails.skip().is_some_and(|s| is_summarized(&s)));
+        assert!(!guardrails.allows_content(601));
+        assert_eq!(guardrails.summarized, 2);
+
+        let mut unlimited = Guardrails::with_limits(None);
+        assert!(unlimited.skip().is_none());
+        assert!(!is_summarized(
+            &unlimited.check_diff("+ added\n".repeat(100))
+        ));
+        assert!(unlimited.allows_content(u64::MAX));
+    }
+}
@@ -0,0 +1,451 @@
+//! Dependency update summaries for bot branches
+//!
+//! Dependabot and Renovate branches mostly change manifests and lockfiles.
+//! Version jumps are read straight from those diffs so PR descriptions and
+//! reviews can list every update, flag major bumps, and point at each
+//! package's release history.
+
+use regex::Regex;
+use std::fmt;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::context::StagedFile;
+
+/// Preset that turns on the dependency workflow for any branch
+pub const DEPENDENCY_PRESET: &str = "dependencies";
+
+/// Heading of the generated section
+const SECTION_HEADING: &str = "## Dependency Updates";
+
+/// Keys in manifests that look like versions but aren't dependencies
+const NON_DEPENDENCY_KEYS: &[&str] = &["version", "edition", "rust-version", "node", "npm"];
+
+static TOML_DEP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*([A-Za-z0-9_-]+)\s*=\s*(?:"([^"]+)"|\{[^}]*\bversion\s*=\s*"([^"]+)")"#)
+        .expect("valid regex")
+});
+static LOCK_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*name\s*=\s*"([^"]+)""#).expect("valid regex"));
+static LOCK_VERSION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*version\s*=\s*"([^"]+)""#).expect("valid regex"));
+static JSON_DEP: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*"(@?[^"]+)"\s*:\s*"([^"]+)""#).expect("valid regex"));
+static PIP_DEP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*([A-Za-z0-9_.\-]+)(?:\[[^\]]*\])?\s*(?:==|>=|~=)\s*([^\s;#,]+)")
+        .expect("valid regex")
+});
+static GO_DEP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?:require\s+)?([\w.\-]+\.[\w.\-/]+)\s+(v\d[^\s]*)").expect("valid regex")
+});
+
+/// Package ecosystem an update belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    Python,
+    Go,
+}
+
+impl Ecosystem {
+    /// Parse the ecosystem names used in prompts and tool arguments
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "cargo" | "rust" | "crates" => Some(Self::Cargo),
+            "npm" | "node" | "javascript" | "typescript" => Some(Self::Npm),
+            "python" | "pip" | "pypi" => Some(Self::Python),
+            "go" | "golang" => Some(Self::Go),
+            _ => None,
+        }
+    }
+
+    /// Ecosystem of a manifest or lockfile
+    fn for_path(path: &str) -> Option<Self> {
+        let file = Path::new(path).file_name()?.to_str()?;
+        match file {
+            "Cargo.toml" | "Cargo.lock" => Some(Self::Cargo),
+            "package.json" => Some(Self::Npm),
+            "go.mod" => Some(Self::Go),
+            f if f.starts_with("requirements") && has_extension(f, "txt") => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    /// Registry page listing a package's releases
+    pub fn release_history_url(self, package: &str) -> String {
+        match self {
+            Self::Cargo => format!("https://crates.io/crates/{package}/versions"),
+            Self::Npm => format!("https://www.npmjs.com/package/{package}?activeTab=versions"),
+            Self::Python => format!("https://pypi.org/project/{package}/#history"),
+            Self::Go => format!("https://pkg.go.dev/{package}?tab=versions"),
+        }
+    }
+}
+
+impl fmt::Display for Ecosystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cargo => write!(f, "cargo"),
+            Self::Npm => write!(f, "npm"),
+            Self::Python => write!(f, "python"),
+            Self::Go => write!(f, "go"),
+        }
+    }
+}
+
+/// Size of a version jump
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bump {
+    /// Pre-release or build changes only
+    Other,
+    Patch,
+    Minor,
+    /// Major bump, or a minor bump below 1.0
+    Major,
+}
+
+impl Bump {
+    fn between(from: &str, to: &str) -> Self {
+        let (from, to) = (version_parts(from), version_parts(to));
+        if from[0] != to[0] || (from[0] == 0 && from[1] != to[1]) {
+            Self::Major
+        } else if from[1] != to[1] {
+            Self::Minor
+        } else if from[2] != to[2] {
+            Self::Patch
+        } else {
+            Self::Other
+        }
+    }
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Other => write!(f, "other"),
+            Self::Patch => write!(f, "patch"),
+            Self::Minor => write!(f, "minor"),
+            Self::Major => write!(f, "major"),
+        }
+    }
+}
+
+/// One package moving from one version to another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyUpdate {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    pub from: String,
+    pub to: String,
+    pub bump: Bump,
+}
+
+/// Dependency updates found in a branch
+#[derive(Debug, Clone, Default)]
+pub struct DependencyReport {
+    pub updates: Vec<DependencyUpdate>,
+}
+
+impl DependencyReport {
+    pub fn is_empty(&self) -> bool {
+        self.updates.is_empty()
+    }
+
+    /// Updates that may break callers
+    pub fn major(&self) -> impl Iterator<Item = &DependencyUpdate> {
+        self.updates.iter().filter(|u| u.bump == Bump::Major)
+    }
+
+    /// Instructions appended to PR and review prompts
+    pub fn prompt(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let mut prompt = String::from(
+            "\n\n## Dependency Updates\nThis branch updates dependencies. Summarize each version \
+             jump and what it brings. For every major bump, call \
+             `project_docs(doc_type=\"dependency\", package=..., version=..., ecosystem=...)` to \
+             read the package's changelog and call out known breaking changes between the two \
+             versions. Do not repeat the update table; it is appended automatically.\n",
+        );
+        for update in &self.updates {
+            prompt.push_str(&format!(
+                "- {} `{}`: {} → {} ({})\n",
+                update.ecosystem, update.name, update.from, update.to, update.bump
+            ));
+        }
+        prompt
+    }
+
+    /// Markdown table of updates with major bumps flagged
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "{SECTION_HEADING}\n\n| Package | Ecosystem | From | To | Bump | Releases |\n| --- | --- | --- | --- | --- | --- |\n"
+        );
+        for update in &self.updates {
+            let bump = if update.bump == Bump::Major {
+                "**major** ⚠️".to_string()
+            } else {
+                update.bump.to_string()
+            };
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} | [history]({}) |\n",
+                update.name,
+                update.ecosystem,
+                update.from,
+                update.to,
+                bump,
+                update.ecosystem.release_history_url(&update.name)
+            ));
+        }
+        let major = self.major().count();
+        if major > 0 {
+            out.push_str(&format!(
+                "\n{major} major update(s): check the changelogs above for breaking changes.\n"
+            ));
+        }
+        out
+    }
+
+    /// Append the update table to generated markdown unless it is already there
+    pub fn append_to(&self, content: &str) -> String {
+        if self.is_empty() || content.contains(SECTION_HEADING) {
+            return content.to_string();
+        }
+        format!("{}\n\n{}", content.trim_end(), self.to_markdown())
+    }
+}
+
+/// Whether a branch name follows the Dependabot or Renovate conventions
+pub fn is_bot_branch(branch: &str) -> bool {
+    branch.starts_with("dependabot/")
+        || branch.starts_with("renovate/")
+        || branch.contains("/dependabot/")
+        || branch.contains("/renovate/")
+}
+
+/// Whether a commit author is a dependency bot
+pub fn is_bot_author(author: &str) -> bool {
+    let author = author.to_ascii_lowercase();
+    author.contains("dependabot") || author.contains("renovate")
+}
+
+/// Read version jumps from manifest and lockfile diffs
+///
+/// Manifests come first, so a package updated in both `Cargo.toml` and
+/// `Cargo.lock` is reported with its manifest requirement.
+pub fn detect_dependency_updates(files: &[StagedFile]) -> Vec<DependencyUpdate> {
+    let mut files: Vec<(&StagedFile, Ecosystem)> = files
+        .iter()
+        .filter_map(|f| Ecosystem::for_path(&f.path).map(|eco| (f, eco)))
+        .collect();
+    files.sort_by_key(|(f, _)| has_extension(&f.path, "lock"));
+
+    let mut updates: Vec<DependencyUpdate> = Vec::new();
+    for (file, ecosystem) in files {
+        let pairs = if has_extension(&file.path, "lock") {
+            lockfile_pairs(&file.diff)
+        } else {
+            manifest_pairs(&file.diff, ecosystem)
+        };
+        for (name, from, to) in pairs {
+            if updates
+                .iter()
+                .any(|u| u.ecosystem == ecosystem && u.name == name)
+            {
+                continue;
+            }
+            let bump = Bump::between(&from, &to);
+            updates.push(DependencyUpdate {
+                ecosystem,
+                name,
+                from,
+                to,
+                bump,
+            });
+        }
+    }
+    updates
+}
+
+/// Removed/added version pairs from a manifest diff
+fn manifest_pairs(diff: &str, ecosystem: Ecosystem) -> Vec<(String, String, String)> {
+    let pattern: &Regex = match ecosystem {
+        Ecosystem::Cargo => &TOML_DEP,
+        Ecosystem::Npm => &JSON_DEP,
+        Ecosystem::Python => &PIP_DEP,
+        Ecosystem::Go => &GO_DEP,
+    };
+    let parse = |line: &str| {
+        let caps = pattern.captures(line)?;
+        let name = caps.get(1)?.as_str();
+        let version = caps.get(2).or_else(|| caps.get(3))?.as_str();
+        (!NON_DEPENDENCY_KEYS.contains(&name) && is_version(version))
+            .then(|| (name.to_string(), version.to_string()))
+    };
+
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for line in diff.lines() {
+        if line.starts_with("---") || line.starts_with("+++") {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix('-') {
+            removed.extend(parse(rest));
+        } else if let Some(rest) = line.strip_prefix('+') {
+            added.extend(parse(rest));
+        }
+    }
+    pair_up(&removed, &added)
+}
+
+/// Removed/added version pairs from a `Cargo.lock` diff
+///
+/// Each `version` line belongs to the most recent `name` line, which may be
+/// unchanged context.
+fn lockfile_pairs(diff: &str) -> Vec<(String, String, String)> {
+    let mut name = None;
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for line in diff.lines() {
+        if line.starts_with("---") || line.starts_with("+++") {
+            continue;
+        }
+        let (marker, body) = line.split_at(line.len().min(1));
+        if let Some(caps) = LOCK_NAME.captures(body) {
+            name = Some(caps[1].to_string());
+        } else if let (Some(caps), Some(name)) = (LOCK_VERSION.captures(body), &name) {
+            match marker {
+                "-" => removed.push((name.clone(), caps[1].to_string())),
+                "+" => added.push((name.clone(), caps[1].to_string())),
+                _ => {}
+            }
+        }
+    }
+    pair_up(&removed, &added)
+}
+
+/// Match removed and added versions by package name
+fn pair_up(
+    removed: &[(String, String)],
+    added: &[(String, String)],
+) -> Vec<(String, String, String)> {
+    removed
+        .iter()
+        .filter_map(|(name, from)| {
+            added
+                .iter()
+                .find(|(added_name, to)| added_name == name && to != from)
+                .map(|(_, to)| (name.clone(), from.clone(), to.clone()))
+        })
+        .collect()
+}
+
+fn has_extension(path: &str, extension: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext == extension)
+}
+
+fn is_version(text: &str) -> bool {
+    text.trim_start_matches(['^', '~', '=', '>', '<', ' ', 'v'])
+        .starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Major, minor and patch numbers, ignoring requirement operators
+fn version_parts(version: &str) -> [u64; 3] {
+    let mut parts = [0; 3];
+    let version = version.trim_start_matches(['^', '~', '=', '>', '<', ' ', 'v']);
+    for (slot, part) in parts.iter_mut().zip(version.split('.')) {
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        *slot = digits.parse().unwrap_or(0);
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ChangeType;
+
+    fn staged(path: &str, diff: &str) -> StagedFile {
+        StagedFile {
+            path: path.to_string(),
+            change_type: ChangeType::Modified,
+            diff: diff.to_string(),
+            content: None,
+            content_excluded: false,
+        }
+    }
+
+    #[test]
+    fn test_detects_manifest_and_lockfile_updates() {
+        let files = [
+            staged(
+                "Cargo.lock",
+                "@@\n [[package]]\n name = \"serde\"\n-version = \"1.0.200\"\n+version = \"1.0.210\"\n [[package]]\n name = \"tokio\"\n-version = \"1.38.0\"\n+version = \"1.40.0\"\n",
+            ),
+            staged(
+                "Cargo.toml",
+                "@@\n-tokio = { version = \"1.38\", features = [\"full\"] }\n+tokio = { version = \"1.40\", features = [\"full\"] }\n-ratatui = \"0.28\"\n+ratatui = \"0.29\"\n",
+            ),
+            staged(
+                "web/package.json",
+                "@@\n-    \"react\": \"^18.3.1\",\n+    \"react\": \"^19.0.0\",\n",
+            ),
+        ];
+
+        let updates = detect_dependency_updates(&files);
+        let summary: Vec<_> = updates
+            .iter()
+            .map(|u| (u.name.as_str(), u.from.as_str(), u.to.as_str(), u.bump))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("tokio", "1.38", "1.40", Bump::Minor),
+                ("ratatui", "0.28", "0.29", Bump::Major),
+                ("react", "^18.3.1", "^19.0.0", Bump::Major),
+                ("serde", "1.0.200", "1.0.210", Bump::Patch),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ignores_package_version_bump() {
+        let files = [staged(
+            "Cargo.toml",
+            "@@\n [package]\n-version = \"1.2.0\"\n+version = \"1.3.0\"\n-edition = \"2021\"\n+edition = \"2024\"\n",
+        )];
+        assert!(detect_dependency_updates(&files).is_empty());
+    }
+
+    #[test]
+    fn test_report_flags_major_bumps() {
+        let report = DependencyReport {
+            updates: vec![DependencyUpdate {
+                ecosystem: Ecosystem::Npm,
+                name: "react".to_string(),
+                from: "18.3.1".to_string(),
+                to: "19.0.0".to_string(),
+                bump: Bump::Major,
+            }],
+        };
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| `react` | npm | 18.3.1 | 19.0.0 | **major** ⚠️ |"));
+        assert!(markdown.contains("https://www.npmjs.com/package/react?activeTab=versions"));
+
+        let appended = report.append_to("## Summary\n\nBump react");
+        assert_eq!(report.append_to(&appended), appended);
+    }
+
+    #[test]
+    fn test_bot_detection() {
+        assert!(is_bot_branch("dependabot/cargo/serde-1.0.210"));
+        assert!(is_bot_branch("origin/renovate/react-19.x"));
+        assert!(!is_bot_branch("feature/renovate-ui"));
+        assert!(is_bot_author("dependabot[bot]"));
+        assert!(!is_bot_author("Jane Doe"));
+    }
+}
@@ -0,0 +1,207 @@
+//! Project documentation included in prompts
+//!
+//! The agent can fetch docs with the `project_docs` tool, but whether it
+//! does is up to the model. `[context.docs]` lists the files that matter
+//! (`README.md`, `ARCHITECTURE.md`, `docs/**`) so PR descriptions, reviews
+//! and changelogs always see them, cut to a token budget so a large docs
+//! folder can't crowd out the diff.
+
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use tiktoken_rs::CoreBPE;
+
+use crate::config::DocsContextConfig;
+use crate::risk::glob_to_regex;
+
+/// Heading of the generated section
+const SECTION_HEADING: &str = "## Project Documentation";
+
+/// Appended where a file was cut short
+const TRUNCATED_MARKER: &str = "[... truncated]";
+
+static TOKENIZER: LazyLock<Option<CoreBPE>> = LazyLock::new(|| tiktoken_rs::cl100k_base().ok());
+
+/// A documentation file, possibly truncated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocExcerpt {
+    /// Path relative to the repository root
+    pub path: String,
+    pub content: String,
+    pub truncated: bool,
+}
+
+/// Documentation selected for a prompt
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocsContext {
+    pub excerpts: Vec<DocExcerpt>,
+}
+
+impl DocsContext {
+    /// Read the tracked files matching `config.include` under `root`
+    ///
+    /// Files are taken in pattern order, then path order, each at most once.
+    /// Unreadable and non-UTF-8 files are skipped. Once the total budget is
+    /// spent, remaining files are left out.
+    pub fn collect(root: &Path, tracked_files: &[String], config: &DocsContextConfig) -> Self {
+        let mut paths: Vec<&String> = Vec::new();
+        for pattern in config.include.iter().filter_map(|p| glob_to_regex(p)) {
+            let mut matched: Vec<&String> = tracked_files
+                .iter()
+                .filter(|f| pattern.is_match(f) && !paths.contains(f))
+                .collect();
+            matched.sort();
+            paths.extend(matched);
+        }
+
+        let mut remaining = config.max_tokens;
+        let mut excerpts = Vec::new();
+        for path in paths {
+            if remaining == 0 {
+                break;
+            }
+            let Ok(content) = fs::read_to_string(root.join(path)) else {
+                continue;
+            };
+            let content = content.trim();
+            if content.is_empty() {
+                continue;
+            }
+            let budget = config.max_file_tokens.min(remaining);
+            let (content, used, truncated) = truncate_to_tokens(content, budget);
+            if content.is_empty() {
+                continue;
+            }
+            remaining = remaining.saturating_sub(used);
+            excerpts.push(DocExcerpt {
+                path: path.clone(),
+                content,
+                truncated,
+            });
+        }
+        Self { excerpts }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.excerpts.is_empty()
+    }
+
+    /// Prompt section with every excerpt, or nothing if there are none
+    pub fn prompt(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let mut prompt = format!(
+            "\n\n{SECTION_HEADING}\n\nKey documentation for this repository, \
+             included by the project's `[context.docs]` settings. Use it to \
+             describe changes in the project's own terms; there is no need to \
+             fetch these files again.\n"
+        );
+        for excerpt in &self.excerpts {
+            prompt.push_str(&format!("\n### {}\n\n{}\n", excerpt.path, excerpt.content));
+            if excerpt.truncated {
+                prompt.push_str(TRUNCATED_MARKER);
+                prompt.push('\n');
+            }
+        }
+        prompt
+    }
+}
+
+/// Number of tokens in `text`, estimated at four bytes per token if the
+/// tokenizer is unavailable
+pub fn count_tokens(text: &str) -> usize {
+    TOKENIZER.as_ref().map_or_else(
+        || text.len().div_ceil(4),
+        |bpe| bpe.encode_ordinary(text).len(),
+    )
+}
+
+/// The longest run of whole lines of `text` within `budget` tokens
+///
+/// Returns the kept text, the tokens it uses and whether anything was cut.
+pub(crate) fn truncate_to_tokens(text: &str, budget: usize) -> (String, usize, bool) {
+    let total = count_tokens(text);
+    if total <= budget {
+        return (text.to_string(), total, false);
+    }
+    let mut kept = String::new();
+    let mut used = 0;
+    for line in text.lines() {
+        // Counting line by line slightly overestimates, which is the safe side
+        let cost = count_tokens(line) + 1;
+        if used + cost > budget {
+            break;
+        }
+        kept.push_str(line);
+        kept.push('\n');
+        used += cost;
+    }
+    (kept.trim_end().to_string(), used, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_follows_patterns_and_budgets() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        fs::create_dir_all(root.join("docs/guide")).expect("mkdir");
+        fs::write(root.join("README.md"), "# Demo\n\nA demo project.").expect("write");
+        fs::write(root.join("docs/guide/setup.md"), "Install it.").expect("write");
+        fs::write(
+            root.join("docs/api.md"),
+            "GET /items lists items.\n".repeat(300),
+        )
+        .expect("write");
+        fs::write(root.join("ARCHITECTURE.md"), "One layer.\n".repeat(200)).expect("write");
+        let tracked: Vec<String> = [
+            "ARCHITECTURE.md",
+            "README.md",
+            "docs/api.md",
+            "docs/guide/setup.md",
+            "src/main.rs",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+        let config = DocsContextConfig {
+            include: vec![
+                "README.md".to_string(),
+                "docs/**".to_string(),
+                "ARCHITECTURE.md".to_string(),
+                "README.md".to_string(),
+            ],
+            max_tokens: 500,
+            max_file_tokens: 300,
+        };
+        let docs = DocsContext::collect(root, &tracked, &config);
+        let paths: Vec<&str> = docs.excerpts.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "README.md",
+                "docs/api.md",
+                "docs/guide/setup.md",
+                "ARCHITECTURE.md"
+            ]
+        );
+
+        assert!(!docs.excerpts[0].truncated);
+        assert!(docs.excerpts[1].truncated);
+        assert!(count_tokens(&docs.excerpts[1].content) <= 300);
+        assert!(docs.excerpts[3].truncated);
+        let total: usize = docs.excerpts.iter().map(|e| count_tokens(&e.content)).sum();
+        assert!(total <= 500, "{total} tokens");
+
+        let prompt = docs.prompt();
+        assert!(prompt.contains("## Project Documentation"));
+        assert!(prompt.contains("### README.md\n\n# Demo"));
+        assert!(prompt.contains(TRUNCATED_MARKER));
+        assert!(DocsContext::default().prompt().is_empty());
+    }
+}
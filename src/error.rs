@@ -0,0 +1,333 @@
+//! Errors with remediation hints
+//!
+//! Failures a user can fix themselves — a missing or rejected API key, a
+//! prompt too large for the model, a dirty working tree, a failing hook —
+//! are raised as an [`IrisError`] instead of a free-form message. Each
+//! variant knows the command or setting that fixes it, so the CLI and Studio
+//! can say "run `git-iris config --api-key …`" rather than relay a raw
+//! provider response. Provider SDK errors arrive as text and are sorted into
+//! variants by [`IrisError::from_provider_failure`].
+
+use std::fmt;
+
+use crate::model_catalog::ConnectionError;
+use crate::providers::{Provider, ProviderError};
+use crate::types::Severity;
+
+/// A failure the user can act on
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IrisError {
+    #[error("No API key configured for {provider}")]
+    MissingApiKey { provider: Provider },
+    #[error("{provider} rejected the API key")]
+    Auth { provider: Provider },
+    #[error("Rate limited by {provider}")]
+    RateLimited { provider: Provider },
+    #[error("The prompt is too large for {model}")]
+    ContextTooLarge { provider: Provider, model: String },
+    #[error("{provider} has no model named {model}")]
+    ModelNotFound { provider: Provider, model: String },
+    #[error("Couldn't reach {provider}: {message}")]
+    ProviderUnavailable { provider: Provider, message: String },
+    #[error("Unknown provider: {0}")]
+    UnknownProvider(String),
+    #[error("No staged changes")]
+    NothingStaged,
+    #[error("Uncommitted changes would be lost by {action}")]
+    RepoDirty { action: String },
+    #[error("The {hook} hook failed{}", exit_code_suffix(*.code))]
+    HookFailed { hook: String, code: Option<i32> },
+    #[error("Not inside a git repository")]
+    NotARepository,
+    #[error("{action} needs a working tree, and this repository is bare")]
+    BareRepository { action: String },
+    #[error("{action} is disabled in read-only mode")]
+    ReadOnly { action: String },
+    #[error("{count} review finding{} at {} or above", if *.count == 1 { "" } else { "s" }, .threshold.label())]
+    ReviewFindings { count: usize, threshold: Severity },
+}
+
+fn exit_code_suffix(code: Option<i32>) -> String {
+    code.map(|code| format!(" with exit code {code}"))
+        .unwrap_or_default()
+}
+
+/// Known shapes of provider error text, most specific first
+const AUTH_MARKERS: &[&str] = &[
+    "authentication_error",
+    "invalid x-api-key",
+    "invalid_api_key",
+    "incorrect api key",
+    "api key not valid",
+    "permission_denied",
+    "401 unauthorized",
+    "status code: 401",
+];
+const RATE_LIMIT_MARKERS: &[&str] = &[
+    "rate_limit",
+    "rate limit",
+    "resource_exhausted",
+    "too many requests",
+    "status code: 429",
+    "insufficient_quota",
+];
+const CONTEXT_MARKERS: &[&str] = &[
+    "prompt is too long",
+    "context_length_exceeded",
+    "maximum context length",
+    "exceeds the maximum number of tokens",
+    "context window",
+    "too many tokens",
+];
+const UNAVAILABLE_MARKERS: &[&str] = &[
+    "connection refused",
+    "error sending request",
+    "dns error",
+    "timed out",
+    "overloaded_error",
+    "service unavailable",
+    "status code: 503",
+    "status code: 502",
+];
+
+impl IrisError {
+    /// What to do about it, as a short instruction
+    pub fn remediation(&self) -> Option<String> {
+        let hint = match self {
+            Self::MissingApiKey { provider } | Self::Auth { provider } => {
+                if provider.requires_api_key() {
+                    format!(
+                        "Run `git-iris config --provider {provider} --api-key <KEY>` or set {}",
+                        provider.api_key_env()
+                    )
+                } else {
+                    return None;
+                }
+            }
+            Self::RateLimited { .. } => {
+                "Wait a minute and retry, or switch providers with `--provider`".to_string()
+            }
+            Self::ContextTooLarge { .. } => "Stage fewer files, lower `token_limit` with `git-iris config --token-limit <N>`, or pick a model with a larger context window".to_string(),
+            Self::ModelNotFound { provider, .. } => format!(
+                "Choose an available model with `git-iris config --provider {provider} --model <MODEL>`"
+            ),
+            Self::ProviderUnavailable { provider, .. } => {
+                if *provider == Provider::Ollama {
+                    "Start the Ollama daemon with `ollama serve`, or point OLLAMA_HOST at it"
+                        .to_string()
+                } else {
+                    "Check your network connection and retry; `--provider` switches to another provider".to_string()
+                }
+            }
+            Self::UnknownProvider(_) => {
+                "Use one of openai, anthropic, google or ollama".to_string()
+            }
+            Self::NothingStaged => "Stage changes with `git add` first".to_string(),
+            Self::RepoDirty { .. } => {
+                "Commit or stash your changes (`git stash`) first".to_string()
+            }
+            Self::HookFailed { hook, .. } if hook == "before_generate" => {
+                "Fix what the hook reported, or change `[hooks] before_generate` in your config"
+                    .to_string()
+            }
+            Self::HookFailed { .. } => {
+                "Fix what the hook reported, or skip hooks with `--no-verify`".to_string()
+            }
+            Self::NotARepository => {
+                "Run git-iris inside a git repository, or pass `--repo <URL>`".to_string()
+            }
+            Self::BareRepository { .. } => {
+                "Pass `--work-tree <DIR>`, or compare refs with `--from`/`--to`, which works without one".to_string()
+            }
+            Self::ReadOnly { .. } => "Run the command again without `--read-only`".to_string(),
+            Self::ReviewFindings { .. } => {
+                "Address the findings, or raise the threshold with `--fail-on`".to_string()
+            }
+        };
+        Some(hint)
+    }
+
+    /// Process exit code for this failure
+    ///
+    /// Review findings exit with 2 so CI can tell a failed check from a
+    /// review that couldn't run.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::ReviewFindings { .. } => 2,
+            _ => 1,
+        }
+    }
+
+    /// Sort a provider SDK failure into a variant by its text
+    ///
+    /// Returns `None` for failures with no known remedy, which are passed on
+    /// unchanged.
+    pub fn from_provider_failure(provider: Provider, model: &str, message: &str) -> Option<Self> {
+        let lower = message.to_lowercase();
+        let matches = |markers: &[&str]| markers.iter().any(|marker| lower.contains(marker));
+
+        if matches(CONTEXT_MARKERS) {
+            Some(Self::ContextTooLarge {
+                provider,
+                model: model.to_string(),
+            })
+        } else if matches(AUTH_MARKERS) {
+            Some(Self::Auth { provider })
+        } else if matches(RATE_LIMIT_MARKERS) {
+            Some(Self::RateLimited { provider })
+        } else if lower.contains("model")
+            && (lower.contains("not found") || lower.contains("not_found_error"))
+        {
+            Some(Self::ModelNotFound {
+                provider,
+                model: model.to_string(),
+            })
+        } else if matches(UNAVAILABLE_MARKERS) {
+            Some(Self::ProviderUnavailable {
+                provider,
+                message: message.lines().next().unwrap_or_default().to_string(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The first actionable error in an error chain
+    pub fn find(error: &anyhow::Error) -> Option<Self> {
+        error.chain().find_map(|cause| {
+            if let Some(error) = cause.downcast_ref::<Self>() {
+                return Some(error.clone());
+            }
+            if let Some(error) = cause.downcast_ref::<ProviderError>() {
+                return Some(match error {
+                    ProviderError::Unknown(name) => Self::UnknownProvider(name.clone()),
+                    ProviderError::MissingApiKey(name) => Self::MissingApiKey {
+                        provider: name.parse().ok()?,
+                    },
+                });
+            }
+            if let Some(error) = cause.downcast_ref::<ConnectionError>() {
+                return match error {
+                    ConnectionError::InvalidKey(provider) => Some(Self::Auth {
+                        provider: *provider,
+                    }),
+                    ConnectionError::RateLimited(provider) => Some(Self::RateLimited {
+                        provider: *provider,
+                    }),
+                    _ => None,
+                };
+            }
+            if let Some(error) = cause.downcast_ref::<git2::Error>()
+                && error.class() == git2::ErrorClass::Repository
+                && error.code() == git2::ErrorCode::NotFound
+            {
+                return Some(Self::NotARepository);
+            }
+            None
+        })
+    }
+}
+
+/// An error with its remediation hint appended, for one-line display
+pub struct Explained<'a>(pub &'a anyhow::Error);
+
+impl fmt::Display for Explained<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)?;
+        if let Some(hint) = IrisError::find(self.0).and_then(|error| error.remediation()) {
+            write!(f, " — {hint}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Replace a provider failure with its actionable variant, when it has one
+///
+/// The original text is logged so `--log` still shows what the provider said.
+pub fn classify_provider_failure(
+    provider: &str,
+    model: &str,
+    error: anyhow::Error,
+) -> anyhow::Error {
+    if IrisError::find(&error).is_some() {
+        return error;
+    }
+    let Ok(provider) = provider.parse::<Provider>() else {
+        return error;
+    };
+    match IrisError::from_provider_failure(provider, model, &format!("{error:#}")) {
+        Some(classified) => {
+            crate::log_debug!("{} failure: {:#}", provider, error);
+            classified.into()
+        }
+        None => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_failures_map_to_remedies() {
+        let anthropic = |message: &str| {
+            IrisError::from_provider_failure(Provider::Anthropic, "claude-sonnet-4-5", message)
+        };
+        assert_eq!(
+            anthropic(
+                r#"CompletionError: ProviderError: {"type":"error","error":{"type":"authentication_error","message":"invalid x-api-key"}}"#
+            ),
+            Some(IrisError::Auth {
+                provider: Provider::Anthropic
+            })
+        );
+        assert!(matches!(
+            anthropic("prompt is too long: 215000 tokens > 200000 maximum"),
+            Some(IrisError::ContextTooLarge { .. })
+        ));
+        assert!(matches!(
+            anthropic(r#"{"type":"rate_limit_error"}"#),
+            Some(IrisError::RateLimited { .. })
+        ));
+        assert_eq!(anthropic("unexpected JSON in response"), None);
+
+        let error = classify_provider_failure(
+            "ollama",
+            "qwen2.5-coder:7b",
+            anyhow::anyhow!("HttpError: error sending request: connection refused"),
+        );
+        assert_eq!(
+            Explained(&error).to_string(),
+            "Couldn't reach ollama: HttpError: error sending request: connection refused — Start the Ollama daemon with `ollama serve`, or point OLLAMA_HOST at it"
+        );
+
+        let hook: anyhow::Error = IrisError::HookFailed {
+            hook: "pre-commit".to_string(),
+            code: Some(1),
+        }
+        .into();
+        let hook = hook.context("Commit failed");
+        assert_eq!(
+            Explained(&hook).to_string(),
+            "Commit failed — Fix what the hook reported, or skip hooks with `--no-verify`"
+        );
+        assert_eq!(
+            IrisError::find(&hook)
+                .map(|error| error.to_string())
+                .as_deref(),
+            Some("The pre-commit hook failed with exit code 1")
+        );
+    }
+
+    #[test]
+    fn test_review_findings_exit_with_two() {
+        let error: anyhow::Error = IrisError::ReviewFindings {
+            count: 3,
+            threshold: Severity::High,
+        }
+        .into();
+        assert_eq!(error.to_string(), "3 review findings at HIGH or above");
+        assert_eq!(IrisError::find(&error).map(|e| e.exit_code()), Some(2));
+        assert_eq!(IrisError::NothingStaged.exit_code(), 1);
+    }
+}
@@ -0,0 +1,164 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::location::git_command;
+
+/// Id `git blame` gives lines that aren't committed yet
+const UNCOMMITTED: &str = "0000000000000000000000000000000000000000";
+
+/// Consecutive lines of a file last changed by the same commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameRegion {
+    /// First line (1-based)
+    pub start: usize,
+    /// Last line, inclusive
+    pub end: usize,
+    /// Full commit id
+    pub commit: String,
+    pub author: String,
+    /// Author time as a Unix timestamp
+    pub time: i64,
+    /// Commit subject
+    pub summary: String,
+}
+
+impl BlameRegion {
+    /// Abbreviated commit id
+    pub fn short_id(&self) -> &str {
+        self.commit.get(..7).unwrap_or(&self.commit)
+    }
+
+    /// Whether the lines are changes in the working tree
+    pub fn is_uncommitted(&self) -> bool {
+        self.commit == UNCOMMITTED
+    }
+
+    /// Whether `line` (1-based) falls in this region
+    pub fn contains(&self, line: usize) -> bool {
+        (self.start..=self.end).contains(&line)
+    }
+
+    /// Number of lines
+    pub fn len(&self) -> usize {
+        self.end + 1 - self.start
+    }
+
+    /// Whether the region has no lines; never true for parsed regions
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+}
+
+/// Blame every line of `file` as it is in the working tree
+pub fn blame_file(repo_path: &Path, file: &Path) -> Result<Vec<BlameRegion>> {
+    let relative = file.strip_prefix(repo_path).unwrap_or(file);
+    let output = git_command()
+        .args([
+            "-C",
+            &repo_path.to_string_lossy(),
+            "blame",
+            "--porcelain",
+            "--",
+        ])
+        .arg(relative)
+        .output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git blame failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(parse_blame_regions(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Parse `git blame --porcelain` output into regions, merging adjacent lines
+/// from the same commit
+pub fn parse_blame_regions(output: &str) -> Vec<BlameRegion> {
+    // Author, time and summary are only given the first time a commit appears
+    let mut commits: HashMap<String, (String, i64, String)> = HashMap::new();
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    let mut current: Option<(String, usize)> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            if let Some(entry) = current.take() {
+                lines.push((entry.1, entry.0));
+            }
+            continue;
+        }
+        let mut fields = line.split(' ');
+        let first = fields.next().unwrap_or_default();
+        if first.len() == 40 && first.chars().all(|c| c.is_ascii_hexdigit()) {
+            let final_line = fields.nth(1).and_then(|n| n.parse().ok()).unwrap_or(0);
+            current = Some((first.to_string(), final_line));
+            continue;
+        }
+        let Some((commit, _)) = &current else {
+            continue;
+        };
+        let info = commits.entry(commit.clone()).or_default();
+        if let Some(author) = line.strip_prefix("author ") {
+            info.0 = author.to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            info.1 = time.parse().unwrap_or_default();
+        } else if let Some(summary) = line.strip_prefix("summary ") {
+            info.2 = summary.to_string();
+        }
+    }
+
+    lines.sort_by_key(|(line, _)| *line);
+    let mut regions: Vec<BlameRegion> = Vec::new();
+    for (line, commit) in lines {
+        if let Some(last) = regions.last_mut()
+            && last.commit == commit
+            && last.end + 1 == line
+        {
+            last.end = line;
+            continue;
+        }
+        let (author, time, summary) = commits.get(&commit).cloned().unwrap_or_default();
+        regions.push(BlameRegion {
+            start: line,
+            end: line,
+            commit,
+            author,
+            time,
+            summary,
+        });
+    }
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: &str = "1111111111111111111111111111111111111111";
+    const B: &str = "2222222222222222222222222222222222222222";
+
+    #[test]
+    fn test_parse_blame_regions() {
+        let output = format!(
+            "{A} 1 1 2\nauthor Ada\nauthor-time 1700000000\nsummary Add parser\nfilename src/a.rs\n\tfn a() {{\n\
+             {A} 2 2\n\t}}\n\
+             {B} 3 3 1\nauthor Grace\nauthor-time 1710000000\nsummary Fix edge case\nfilename src/a.rs\n\t// edge\n\
+             {A} 4 4 1\n\tfn b() {{}}\n\
+             {UNCOMMITTED} 5 5 1\nauthor Not Committed Yet\nauthor-time 1720000000\nsummary Version of src/a.rs from src/a.rs\nfilename src/a.rs\n\tnew\n"
+        );
+        let regions = parse_blame_regions(&output);
+
+        assert_eq!(regions.len(), 4);
+        assert_eq!((regions[0].start, regions[0].end), (1, 2));
+        assert_eq!(regions[0].author, "Ada");
+        assert_eq!(regions[0].short_id(), "1111111");
+        assert_eq!(regions[1].summary, "Fix edge case");
+        assert_eq!(regions[2].commit, A);
+        assert_eq!(regions[2].time, 1_700_000_000);
+        assert!(regions[2].contains(4));
+        assert!(regions[3].is_uncommitted());
+        assert_eq!(regions[0].len(), 2);
+    }
+}
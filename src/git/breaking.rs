@@ -0,0 +1,446 @@
+//! Breaking change detection from diffs
+//!
+//! A deterministic pass over changed files that spots removed public API:
+//! `pub` items in Rust, exported symbols in TypeScript/JavaScript, and
+//! deleted fields in schema files (protobuf, GraphQL, JSON Schema, Avro).
+//! A symbol only counts as removed if it isn't added back anywhere in the
+//! same change, so moving an item between files isn't flagged.
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::LazyLock;
+
+use crate::context::StagedFile;
+use crate::types::GeneratedMessage;
+
+static RUST_PUB_ITEM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*pub\s+(?:(?:async|const|unsafe|extern(?:\s+\x22\w+\x22)?)\s+)*(fn|struct|enum|trait|type|const|static|mod|union|macro)\s+([A-Za-z_]\w*)",
+    )
+    .expect("valid rust item regex")
+});
+
+static RUST_PUB_USE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*pub\s+use\s+[^;{]*?(\w+)\s*;").expect("valid rust use regex")
+});
+
+static TS_EXPORT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*export\s+(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?:async\s+)?(function\*?|class|const|let|var|interface|type|enum|namespace)\s+([A-Za-z_$][\w$]*)",
+    )
+    .expect("valid export regex")
+});
+
+static PROTO_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:optional|repeated|required)\s+)?[\w.]+(?:<[^>]*>)?\s+(\w+)\s*=\s*\d+\s*[;\[]",
+    )
+    .expect("valid proto regex")
+});
+
+static GRAPHQL_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(\w+)\s*(?:\([^)]*\))?\s*:\s*\[?\w+").expect("valid graphql regex")
+});
+
+static JSON_PROPERTY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*"(\w+)"\s*:\s*\{|"name"\s*:\s*"(\w+)""#).expect("valid json regex")
+});
+
+/// What kind of public surface was removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BreakingKind {
+    /// A `pub` Rust item or re-export
+    RustPublicItem,
+    /// An exported TypeScript/JavaScript symbol
+    ExportedSymbol,
+    /// A field in a schema definition
+    SchemaField,
+}
+
+/// A removed piece of public API
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BreakingChange {
+    pub kind: BreakingKind,
+    /// File the symbol was removed from
+    pub path: String,
+    /// Symbol or field name
+    pub symbol: String,
+    /// Item keyword (`fn`, `struct`, `interface`, …) when known
+    pub item: Option<String>,
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.kind, &self.item) {
+            (BreakingKind::RustPublicItem, Some(item)) => {
+                write!(f, "removed `pub {item} {}` from {}", self.symbol, self.path)
+            }
+            (BreakingKind::RustPublicItem, None) => {
+                write!(f, "removed public `{}` from {}", self.symbol, self.path)
+            }
+            (BreakingKind::ExportedSymbol, _) => {
+                write!(f, "removed export `{}` from {}", self.symbol, self.path)
+            }
+            (BreakingKind::SchemaField, _) => {
+                write!(f, "removed field `{}` from {}", self.symbol, self.path)
+            }
+        }
+    }
+}
+
+/// Scan changed files for removed public API
+pub fn detect_breaking_changes(files: &[StagedFile]) -> Vec<BreakingChange> {
+    let mut removed = Vec::new();
+    let mut added_code = HashSet::new();
+    let mut added_schema = HashSet::new();
+
+    for file in files {
+        let Some(language) = Language::for_path(&file.path) else {
+            continue;
+        };
+        for (sign, line) in diff_lines(&file.diff) {
+            for found in language.symbols(line) {
+                if sign == '-' {
+                    removed.push(BreakingChange {
+                        kind: language.kind(),
+                        path: file.path.clone(),
+                        symbol: found.0,
+                        item: found.1,
+                    });
+                } else if language == Language::Schema {
+                    added_schema.insert((file.path.clone(), found.0));
+                } else {
+                    added_code.insert((language.kind(), found.0));
+                }
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    removed
+        .into_iter()
+        .filter(|change| match change.kind {
+            BreakingKind::SchemaField => {
+                !added_schema.contains(&(change.path.clone(), change.symbol.clone()))
+            }
+            kind => !added_code.contains(&(kind, change.symbol.clone())),
+        })
+        .filter(|change| seen.insert(change.clone()))
+        .collect()
+}
+
+/// Whether a commit message already declares a breaking change
+pub fn is_breaking_message(message: &str) -> bool {
+    let subject = message.lines().next().unwrap_or_default();
+    let marked = subject
+        .split_once(':')
+        .is_some_and(|(prefix, _)| prefix.ends_with('!') && !prefix.contains(' '));
+    marked
+        || message.lines().any(|line| {
+            line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+        })
+}
+
+/// Force the `!` marker and a `BREAKING CHANGE` footer into a generated message
+///
+/// The marker is only added to conventional-commit subjects (`type(scope): …`);
+/// the footer is added unless the body already has one.
+pub fn apply_breaking_changes(message: &mut GeneratedMessage, changes: &[BreakingChange]) {
+    if changes.is_empty() {
+        return;
+    }
+
+    if let Some((prefix, rest)) = message.title.split_once(':')
+        && is_conventional_prefix(prefix)
+        && !prefix.ends_with('!')
+    {
+        message.title = format!("{prefix}!:{rest}");
+    }
+
+    if !message
+        .message
+        .lines()
+        .any(|line| line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:"))
+    {
+        let summary = changes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        let body = message.message.trim_end();
+        message.message = if body.is_empty() {
+            format!("BREAKING CHANGE: {summary}")
+        } else {
+            format!("{body}\n\nBREAKING CHANGE: {summary}")
+        };
+    }
+}
+
+/// Breaking changes found for a task, from the diff and from commit messages
+#[derive(Debug, Clone, Default)]
+pub struct BreakingReport {
+    /// Removed public API found in the diff
+    pub changes: Vec<BreakingChange>,
+    /// Commits in the range already marked breaking (`abc1234: subject`)
+    pub commits: Vec<String>,
+}
+
+impl BreakingReport {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.commits.is_empty()
+    }
+
+    /// Prompt section telling the model what was detected
+    pub fn prompt(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let mut section = String::from("\n\n## Breaking Changes Detected\n");
+        if !self.changes.is_empty() {
+            section.push_str("Static analysis found removed public API:\n");
+            for change in &self.changes {
+                section.push_str(&format!("- {change}\n"));
+            }
+        }
+        if !self.commits.is_empty() {
+            section.push_str("Commits marked as breaking:\n");
+            for commit in &self.commits {
+                section.push_str(&format!("- {commit}\n"));
+            }
+        }
+        section.push_str(
+            "\nTreat these as breaking changes: call out each one with its impact and migration path, and list them under a Breaking Changes section where the output has one.",
+        );
+        section
+    }
+
+    /// Make sure markdown output has a Breaking Changes section
+    ///
+    /// If the model left it out, one is inserted before the Metrics section
+    /// (or appended) listing everything detected.
+    pub fn flag_markdown(&self, content: &str) -> String {
+        if self.is_empty()
+            || content
+                .lines()
+                .any(|line| line.trim_start().starts_with('#') && line.contains("Breaking Changes"))
+        {
+            return content.to_string();
+        }
+
+        let mut section = String::from("### Breaking Changes\n\n");
+        for change in &self.changes {
+            section.push_str(&format!("- **BREAKING:** {change}\n"));
+        }
+        for commit in &self.commits {
+            section.push_str(&format!("- **BREAKING:** {commit}\n"));
+        }
+
+        match content.find("### Metrics") {
+            Some(index) => format!("{}{section}\n{}", &content[..index], &content[index..]),
+            None => format!("{}\n\n{section}", content.trim_end()),
+        }
+    }
+}
+
+/// `type` or `type(scope)` with a lowercase word type
+fn is_conventional_prefix(prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('!');
+    let kind = prefix.split_once('(').map_or(
+        prefix,
+        |(kind, scope)| {
+            if scope.ends_with(')') { kind } else { "" }
+        },
+    );
+    !kind.is_empty() && kind.chars().all(|c| c.is_ascii_lowercase())
+}
+
+/// Added and removed content lines, skipping file headers
+fn diff_lines(diff: &str) -> impl Iterator<Item = (char, &str)> {
+    diff.lines().filter_map(|line| {
+        if line.starts_with("+++") || line.starts_with("---") {
+            return None;
+        }
+        let mut chars = line.chars();
+        match chars.next() {
+            Some(sign @ ('+' | '-')) => Some((sign, chars.as_str())),
+            _ => None,
+        }
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    Rust,
+    Script,
+    Schema,
+}
+
+impl Language {
+    fn for_path(path: &str) -> Option<Self> {
+        let path = path.replace('\\', "/");
+        let lower = path.to_lowercase();
+        let in_tests = lower.starts_with("tests/")
+            || lower.starts_with("examples/")
+            || lower.starts_with("benches/")
+            || lower.contains("/tests/")
+            || lower.contains("/__tests__/");
+        let ext = lower.rsplit_once('.').map(|(_, ext)| ext)?;
+
+        match ext {
+            "rs" if !in_tests => Some(Self::Rust),
+            "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs"
+                if !in_tests && !lower.contains(".test.") && !lower.contains(".spec.") =>
+            {
+                Some(Self::Script)
+            }
+            "proto" | "graphql" | "gql" | "avsc" => Some(Self::Schema),
+            "json" if lower.contains("schema") => Some(Self::Schema),
+            _ => None,
+        }
+    }
+
+    fn kind(self) -> BreakingKind {
+        match self {
+            Self::Rust => BreakingKind::RustPublicItem,
+            Self::Script => BreakingKind::ExportedSymbol,
+            Self::Schema => BreakingKind::SchemaField,
+        }
+    }
+
+    /// Public symbols declared on a line, with their item keyword
+    fn symbols(self, line: &str) -> Vec<(String, Option<String>)> {
+        let capture = |re: &Regex, item: usize, name: usize| {
+            re.captures(line).and_then(|caps| {
+                caps.get(name).map(|symbol| {
+                    (
+                        symbol.as_str().to_string(),
+                        caps.get(item).map(|m| m.as_str().to_string()),
+                    )
+                })
+            })
+        };
+        match self {
+            Self::Rust => capture(&RUST_PUB_ITEM, 1, 2)
+                .or_else(|| capture(&RUST_PUB_USE, 0, 1).map(|(symbol, _)| (symbol, None)))
+                .into_iter()
+                .collect(),
+            Self::Script => capture(&TS_EXPORT, 1, 2).into_iter().collect(),
+            Self::Schema => [&*PROTO_FIELD, &*GRAPHQL_FIELD, &*JSON_PROPERTY]
+                .into_iter()
+                .find_map(|re| {
+                    re.captures(line).and_then(|caps| {
+                        caps.iter()
+                            .skip(1)
+                            .flatten()
+                            .next()
+                            .map(|m| (m.as_str().to_string(), None))
+                    })
+                })
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ChangeType;
+
+    fn file(path: &str, diff: &str) -> StagedFile {
+        StagedFile {
+            path: path.to_string(),
+            change_type: ChangeType::Modified,
+            diff: diff.to_string(),
+            content: None,
+            content_excluded: false,
+        }
+    }
+
+    #[test]
+    fn test_detects_removed_rust_pub_items() {
+        let files = [
+            file(
+                "src/lib.rs",
+                "--- a/src/lib.rs\n+++ b/src/lib.rs\n-pub fn old_api() {}\n-pub(crate) fn internal() {}\n-pub struct Moved;\n+fn private() {}\n",
+            ),
+            file("src/moved.rs", "+pub struct Moved;\n"),
+        ];
+        let changes = detect_breaking_changes(&files);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            changes[0].to_string(),
+            "removed `pub fn old_api` from src/lib.rs"
+        );
+    }
+
+    #[test]
+    fn test_detects_exports_and_schema_fields() {
+        let files = [
+            file(
+                "web/api.ts",
+                "-export function fetchUser() {}\n-export interface User {}\n+export interface User {}\n",
+            ),
+            file("web/api.test.ts", "-export function helper() {}\n"),
+            file(
+                "proto/user.proto",
+                "-  string email = 2;\n   int64 id = 1;\n",
+            ),
+        ];
+        let symbols: Vec<_> = detect_breaking_changes(&files)
+            .into_iter()
+            .map(|c| (c.kind, c.symbol))
+            .collect();
+        assert_eq!(
+            symbols,
+            [
+                (BreakingKind::ExportedSymbol, "fetchUser".to_string()),
+                (BreakingKind::SchemaField, "email".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_forces_marker_and_footer() {
+        let changes = detect_breaking_changes(&[file("src/lib.rs", "-pub fn old_api() {}\n")]);
+        let mut message = GeneratedMessage {
+            emoji: None,
+            title: "feat(api): replace old_api".to_string(),
+            message: "Switch callers to new_api.".to_string(),
+            completion_message: None,
+            variant: None,
+        };
+        apply_breaking_changes(&mut message, &changes);
+        assert_eq!(message.title, "feat(api)!: replace old_api");
+        assert!(
+            message
+                .message
+                .ends_with("\n\nBREAKING CHANGE: removed `pub fn old_api` from src/lib.rs")
+        );
+        assert!(is_breaking_message(&format!(
+            "{}\n\n{}",
+            message.title, message.message
+        )));
+
+        // Applying twice doesn't duplicate anything
+        let before = message.clone();
+        apply_breaking_changes(&mut message, &changes);
+        assert_eq!(message.title, before.title);
+        assert_eq!(message.message, before.message);
+    }
+
+    #[test]
+    fn test_flag_markdown_inserts_section_before_metrics() {
+        let report = BreakingReport {
+            changes: Vec::new(),
+            commits: vec!["abc1234: feat!: drop v1 endpoints".to_string()],
+        };
+        let flagged = report
+            .flag_markdown("## [2.0.0]\n\n### Added\n- x\n\n### Metrics\n- Total Commits: 1\n");
+        assert!(flagged.contains(
+            "### Breaking Changes\n\n- **BREAKING:** abc1234: feat!: drop v1 endpoints\n\n### Metrics"
+        ));
+        assert_eq!(report.flag_markdown(&flagged), flagged);
+    }
+}
@@ -1,9 +1,9 @@
-use crate::context::{ChangeType, RecentCommit, StagedFile};
+use crate::context::{ChangeType, Guardrails, RecentCommit, StagedFile, is_summarized};
 use crate::git::utils::{is_binary_diff, should_exclude_file};
 use crate::log_debug;
 use anyhow::{Context, Result, anyhow};
 use chrono;
-use git2::{FileMode, Repository, Status};
+use git2::{DiffOptions, FileMode, Repository, Status};
 
 /// Results from a commit operation
 #[derive(Debug)]
@@ -124,7 +124,7 @@ pub fn amend_commit(repo: &Repository, message: &str, is_remote: bool) -> Result
         ));
     }
 
-    let signature = repo.signature()?;
+    let committer = repo.signature()?;
     let mut index = repo.index()?;
     let tree_id = index.write_tree()?;
     let tree = repo.find_tree(tree_id)?;
@@ -132,11 +132,13 @@ pub fn amend_commit(repo: &Repository, message: &str, is_remote: bool) -> Result
     // Get the current HEAD commit (the one we're amending)
     let head_commit = repo.head()?.peel_to_commit()?;
 
-    // Amend the HEAD commit with the new tree and message
+    // Amend the HEAD commit with the new tree and message. Like `git commit
+    // --amend`, the original author and author date are kept; only the
+    // committer changes
     let commit_oid = head_commit.amend(
         Some("HEAD"),     // Update the HEAD reference
-        Some(&signature), // New author (use current)
-        Some(&signature), // New committer (use current)
+        None,             // Keep original author and date
+        Some(&committer), // New committer (use current)
         None,             // Keep original encoding
         Some(message),    // New message
         Some(&tree),      // New tree (includes staged changes)
@@ -244,6 +246,40 @@ where
         .collect()
 }
 
+/// Retrieves commits between two Git references that change files under
+/// `path`
+///
+/// A commit is kept when its diff against its first parent touches `path`,
+/// the way `git log from..to -- path` lists it.
+pub fn get_commits_touching_path(
+    repo: &Repository,
+    from: &str,
+    to: &str,
+    path: &str,
+) -> Result<Vec<RecentCommit>> {
+    let mut options = DiffOptions::new();
+    options.pathspec(path.trim_start_matches("./").trim_end_matches('/'));
+    let commits = get_commits_between_with_callback(repo, from, to, |commit| {
+        let commit = repo.revparse_single(&commit.hash)?.peel_to_commit()?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let diff = repo.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&commit.tree()?),
+            Some(&mut options),
+        )?;
+        Ok((diff.deltas().len() > 0).then(|| RecentCommit {
+            hash: commit.id().to_string(),
+            message: commit.message().unwrap_or_default().to_string(),
+            author: commit.author().name().unwrap_or_default().to_string(),
+            timestamp: commit.time().seconds().to_string(),
+        }))
+    })?;
+    Ok(commits.into_iter().flatten().collect())
+}
+
 /// Retrieves the files changed in a specific commit
 ///
 /// # Arguments
@@ -303,11 +339,16 @@ pub fn get_commit_files(repo: &Repository, commit_id: &str) -> Result<Vec<Staged
     )?;
 
     // Get the diff for each file
+    let mut guardrails = Guardrails::active();
     for file in &mut commit_files {
         if file.content_excluded {
             file.diff = String::from("[Content excluded]");
             continue;
         }
+        if let Some(summary) = guardrails.skip() {
+            file.diff = summary;
+            continue;
+        }
 
         let mut diff_options = git2::DiffOptions::new();
         diff_options.pathspec(&file.path);
@@ -332,9 +373,10 @@ pub fn get_commit_files(repo: &Repository, commit_id: &str) -> Result<Vec<Staged
         if is_binary_diff(&diff_string) {
             file.diff = "[Binary file changed]".to_string();
         } else {
-            file.diff = diff_string;
+            file.diff = guardrails.check_diff(diff_string);
         }
     }
+    guardrails.finish();
 
     log_debug!("Found {} files in commit", commit_files.len());
     Ok(commit_files)
@@ -412,6 +454,52 @@ pub fn get_file_paths_for_commit(repo: &Repository, commit_id: &str) -> Result<V
     Ok(file_paths)
 }
 
+/// Counts revert commits in recent history that touched each of `paths`
+///
+/// Walks back at most `limit` commits from HEAD and looks at commits whose
+/// subject starts with `Revert`, as written by `git revert`.
+pub fn count_reverts_touching(
+    repo: &Repository,
+    paths: &[String],
+    limit: usize,
+) -> Result<std::collections::HashMap<String, usize>> {
+    let mut counts = std::collections::HashMap::new();
+    if paths.is_empty() {
+        return Ok(counts);
+    }
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+
+    for oid in revwalk.take(limit) {
+        let commit = repo.find_commit(oid?)?;
+        if !commit.summary().is_some_and(|s| s.starts_with("Revert")) {
+            continue;
+        }
+        let parent_tree = if commit.parent_count() > 0 {
+            Some(commit.parent(0)?.tree()?)
+        } else {
+            None
+        };
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+        for delta in diff.deltas() {
+            let Some(path) = delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())
+                .and_then(|p| p.to_str())
+            else {
+                continue;
+            };
+            if paths.iter().any(|p| p == path) {
+                *counts.entry(path.to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    Ok(counts)
+}
+
 /// Gets the date of a commit in YYYY-MM-DD format
 ///
 /// # Arguments
@@ -509,11 +597,16 @@ pub fn get_branch_diff_files(
     )?;
 
     // Get the diff for each file
+    let mut guardrails = Guardrails::active();
     for file in &mut branch_files {
         if file.content_excluded {
             file.diff = String::from("[Content excluded]");
             continue;
         }
+        if let Some(summary) = guardrails.skip() {
+            file.diff = summary;
+            continue;
+        }
 
         let mut diff_options = git2::DiffOptions::new();
         diff_options.pathspec(&file.path);
@@ -538,19 +631,22 @@ pub fn get_branch_diff_files(
         if is_binary_diff(&diff_string) {
             file.diff = "[Binary file changed]".to_string();
         } else {
-            file.diff = diff_string;
+            file.diff = guardrails.check_diff(diff_string);
         }
 
         // Get file content from target branch if it's a modified or added file
         if matches!(file.change_type, ChangeType::Added | ChangeType::Modified)
+            && !is_summarized(&file.diff)
             && let Ok(entry) = target_tree.get_path(std::path::Path::new(&file.path))
             && let Ok(object) = entry.to_object(repo)
             && let Some(blob) = object.as_blob()
+            && guardrails.allows_content(u64::try_from(blob.size()).unwrap_or(u64::MAX))
             && let Ok(content) = std::str::from_utf8(blob.content())
         {
             file.content = Some(content.to_string());
         }
     }
+    guardrails.finish();
 
     log_debug!(
         "Found {} files changed between branches (using merge-base)",
@@ -698,11 +794,16 @@ pub fn get_commit_range_files(repo: &Repository, from: &str, to: &str) -> Result
     )?;
 
     // Get the diff for each file
+    let mut guardrails = Guardrails::active();
     for file in &mut range_files {
         if file.content_excluded {
             file.diff = String::from("[Content excluded]");
             continue;
         }
+        if let Some(summary) = guardrails.skip() {
+            file.diff = summary;
+            continue;
+        }
 
         let mut diff_options = git2::DiffOptions::new();
         diff_options.pathspec(&file.path);
@@ -724,19 +825,22 @@ pub fn get_commit_range_files(repo: &Repository, from: &str, to: &str) -> Result
         if is_binary_diff(&diff_string) {
             file.diff = "[Binary file changed]".to_string();
         } else {
-            file.diff = diff_string;
+            file.diff = guardrails.check_diff(diff_string);
         }
 
         // Get file content from to commit if it's a modified or added file
         if matches!(file.change_type, ChangeType::Added | ChangeType::Modified)
+            && !is_summarized(&file.diff)
             && let Ok(entry) = to_tree.get_path(std::path::Path::new(&file.path))
             && let Ok(object) = entry.to_object(repo)
             && let Some(blob) = object.as_blob()
+            && guardrails.allows_content(u64::try_from(blob.size()).unwrap_or(u64::MAX))
             && let Ok(content) = std::str::from_utf8(blob.content())
         {
             file.content = Some(content.to_string());
         }
     }
+    guardrails.finish();
 
     log_debug!("Found {} files changed in commit range", range_files.len());
     Ok(range_files)
@@ -0,0 +1,57 @@
+//! Rendered tree diffs, cached by object ID
+//!
+//! Studio asks for the same range diff whenever the user switches modes or
+//! re-picks refs, and on a large repository rendering it is the slow part.
+//! Trees are content-addressed, so a `(from, to)` tree pair always renders to
+//! the same diff; the cache is keyed on that pair alone and shared by every
+//! `GitRepo` in the process, which lets Studio's data loading and the agent
+//! service reuse each other's work.
+
+use git2::Oid;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{LazyLock, Mutex};
+
+/// Diffs kept at once
+const CAPACITY: NonZeroUsize = NonZeroUsize::new(32).expect("non-zero");
+
+/// Diffs larger than this are rendered every time rather than held in memory
+const MAX_CACHED_BYTES: usize = 4 * 1024 * 1024;
+
+static CACHE: LazyLock<Mutex<LruCache<(Oid, Oid), String>>> =
+    LazyLock::new(|| Mutex::new(LruCache::new(CAPACITY)));
+
+/// The cached diff from tree `from` to tree `to`
+pub(super) fn get(from: Oid, to: Oid) -> Option<String> {
+    CACHE.lock().ok()?.get(&(from, to)).cloned()
+}
+
+/// Remember the diff from tree `from` to tree `to`
+pub(super) fn insert(from: Oid, to: Oid, diff: &str) {
+    if diff.len() > MAX_CACHED_BYTES {
+        return;
+    }
+    if let Ok(mut cache) = CACHE.lock() {
+        cache.put((from, to), diff.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_by_tree_pair() {
+        let a = Oid::hash_object(git2::ObjectType::Blob, b"diff-cache-a").expect("oid");
+        let b = Oid::hash_object(git2::ObjectType::Blob, b"diff-cache-b").expect("oid");
+
+        assert_eq!(get(a, b), None);
+        insert(a, b, "+added\n");
+        assert_eq!(get(a, b).as_deref(), Some("+added\n"));
+        // Direction matters
+        assert_eq!(get(b, a), None);
+
+        insert(b, a, &"x".repeat(MAX_CACHED_BYTES + 1));
+        assert_eq!(get(b, a), None);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::context::{ChangeType, RecentCommit, StagedFile};
+use crate::context::{ChangeType, Guardrails, RecentCommit, StagedFile, is_summarized};
 use crate::git::utils::{is_binary_diff, should_exclude_file};
 use crate::log_debug;
 use anyhow::{Context, Result};
@@ -27,6 +27,7 @@ pub fn get_file_statuses(repo: &Repository) -> Result<Vec<StagedFile>> {
     let mut opts = StatusOptions::new();
     opts.include_untracked(true);
     let statuses = repo.statuses(Some(&mut opts))?;
+    let mut guardrails = Guardrails::active();
 
     for entry in statuses.iter() {
         let path = entry.path().context("Could not get path")?;
@@ -44,21 +45,27 @@ pub fn get_file_statuses(repo: &Repository) -> Result<Vec<StagedFile>> {
             let should_exclude = should_exclude_file(path);
             let diff = if should_exclude {
                 String::from("[Content excluded]")
+            } else if let Some(summary) = guardrails.skip() {
+                summary
             } else {
-                get_diff_for_file(repo, path)?
+                guardrails.check_diff(get_diff_for_file(repo, path)?)
             };
 
-            let content =
-                if should_exclude || change_type != ChangeType::Modified || is_binary_diff(&diff) {
-                    None
-                } else {
-                    let path_obj = Path::new(path);
-                    if path_obj.exists() {
+            let content = if should_exclude
+                || change_type != ChangeType::Modified
+                || is_binary_diff(&diff)
+                || is_summarized(&diff)
+            {
+                None
+            } else {
+                let path_obj = Path::new(path);
+                match fs::metadata(path_obj) {
+                    Ok(meta) if guardrails.allows_content(meta.len()) => {
                         Some(fs::read_to_string(path_obj)?)
-                    } else {
-                        None
                     }
-                };
+                    _ => None,
+                }
+            };
 
             staged_files.push(StagedFile {
                 path: path.to_string(),
@@ -69,6 +76,7 @@ pub fn get_file_statuses(repo: &Repository) -> Result<Vec<StagedFile>> {
             });
         }
     }
+    guardrails.finish();
 
     log_debug!("Found {} staged files", staged_files.len());
     Ok(staged_files)
@@ -124,6 +132,7 @@ pub fn get_unstaged_file_statuses(repo: &Repository) -> Result<Vec<StagedFile>>
     let mut opts = StatusOptions::new();
     opts.include_untracked(true);
     let statuses = repo.statuses(Some(&mut opts))?;
+    let mut guardrails = Guardrails::active();
 
     for entry in statuses.iter() {
         let path = entry.path().context("Could not get path")?;
@@ -142,21 +151,27 @@ pub fn get_unstaged_file_statuses(repo: &Repository) -> Result<Vec<StagedFile>>
             let should_exclude = should_exclude_file(path);
             let diff = if should_exclude {
                 String::from("[Content excluded]")
+            } else if let Some(summary) = guardrails.skip() {
+                summary
             } else {
-                get_diff_for_unstaged_file(repo, path)?
+                guardrails.check_diff(get_diff_for_unstaged_file(repo, path)?)
             };
 
-            let content =
-                if should_exclude || change_type != ChangeType::Modified || is_binary_diff(&diff) {
-                    None
-                } else {
-                    let path_obj = Path::new(path);
-                    if path_obj.exists() {
+            let content = if should_exclude
+                || change_type != ChangeType::Modified
+                || is_binary_diff(&diff)
+                || is_summarized(&diff)
+            {
+                None
+            } else {
+                let path_obj = Path::new(path);
+                match fs::metadata(path_obj) {
+                    Ok(meta) if guardrails.allows_content(meta.len()) => {
                         Some(fs::read_to_string(path_obj)?)
-                    } else {
-                        None
                     }
-                };
+                    _ => None,
+                }
+            };
 
             unstaged_files.push(StagedFile {
                 path: path.to_string(),
@@ -167,6 +182,7 @@ pub fn get_unstaged_file_statuses(repo: &Repository) -> Result<Vec<StagedFile>>
             });
         }
     }
+    guardrails.finish();
 
     log_debug!("Found {} unstaged files", unstaged_files.len());
     Ok(unstaged_files)
@@ -0,0 +1,84 @@
+//! Where the repository and its work tree are
+//!
+//! Like git itself, git-iris finds the repository by searching up from the
+//! current directory unless `GIT_DIR` says where it is, and takes the work
+//! tree from `GIT_WORK_TREE` when that's set. The `--git-dir` and
+//! `--work-tree` flags override both for the whole process, as does the
+//! cached mirror `--repo <URL>` analyzes. Every repository
+//! open and every `git` subprocess goes through this module so they agree on
+//! the same location, including in bare repositories, which have no work tree.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::LazyLock;
+
+use git2::{Repository, RepositoryOpenFlags};
+use parking_lot::RwLock;
+
+/// Repository and work tree to use instead of searching for them
+#[derive(Debug, Clone, Default)]
+pub struct GitLocation {
+    /// `--git-dir`: the repository, instead of searching for it
+    pub git_dir: Option<PathBuf>,
+    /// `--work-tree`: the checkout to use with it
+    pub work_tree: Option<PathBuf>,
+}
+
+static LOCATION: LazyLock<RwLock<GitLocation>> = LazyLock::new(RwLock::default);
+
+/// Use `location` from now on
+pub fn set_location(location: GitLocation) {
+    // Subprocesses run from other directories, so resolve relative paths now
+    let absolute =
+        |path: Option<PathBuf>| path.map(|path| std::path::absolute(&path).unwrap_or(path));
+    *LOCATION.write() = GitLocation {
+        git_dir: absolute(location.git_dir),
+        work_tree: absolute(location.work_tree),
+    };
+}
+
+fn location() -> GitLocation {
+    LOCATION.read().clone()
+}
+
+/// Open the repository containing `start`, honoring `GIT_DIR`,
+/// `GIT_WORK_TREE` and the command-line overrides
+pub fn open_repository(start: &Path) -> Result<Repository, git2::Error> {
+    let overrides = location();
+    let repo = match overrides.git_dir.as_deref() {
+        Some(git_dir) => Repository::open(git_dir)?,
+        None => Repository::open_ext(
+            start,
+            RepositoryOpenFlags::FROM_ENV,
+            std::iter::empty::<&OsStr>(),
+        )?,
+    };
+    if let Some(work_tree) = overrides.work_tree.as_deref() {
+        repo.set_workdir(work_tree, false)?;
+    }
+    Ok(repo)
+}
+
+/// The directory a repository's files are read from: its work tree, or the
+/// repository itself when it's bare
+pub fn repository_root(repo: &Repository) -> PathBuf {
+    // libgit2 reports directories with a trailing separator
+    repo.workdir()
+        .unwrap_or_else(|| repo.path())
+        .components()
+        .collect()
+}
+
+/// A `git` command that sees the same repository as [`open_repository`]
+pub fn git_command() -> Command {
+    let mut command = Command::new("git");
+    let location = location();
+    if let Some(git_dir) = &location.git_dir {
+        command.env("GIT_DIR", git_dir);
+    }
+    if let Some(work_tree) = &location.work_tree {
+        command.env("GIT_WORK_TREE", work_tree);
+    }
+    command
+}
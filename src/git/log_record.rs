@@ -0,0 +1,128 @@
+//! Commit records from `git log` runs that gather statistics
+//!
+//! Each commit is formatted as a `\x1e` separator followed by a header of
+//! `\x1f`-separated fields: the author, then optionally the commit timestamp
+//! and subject (`--format=%x1e%an%x1f%at%x1f%s`). The changed paths follow,
+//! one per line, from `--name-only` or with line counts from `--numstat`.
+
+/// A commit read by [`parse_log`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord<'a> {
+    pub author: &'a str,
+    /// Commit timestamp, when the format includes `%at`
+    pub timestamp: Option<i64>,
+    /// Subject, when the format includes `%s`; empty otherwise
+    pub subject: &'a str,
+    /// Paths the commit changed
+    pub files: Vec<LogFile<'a>>,
+}
+
+/// A path changed by a commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFile<'a> {
+    pub path: &'a str,
+    /// Lines added, from `--numstat`; zero otherwise and for binary files
+    pub additions: usize,
+    /// Lines deleted, from `--numstat`; zero otherwise and for binary files
+    pub deletions: usize,
+}
+
+impl<'a> LogFile<'a> {
+    /// Read a `--name-only` or `--numstat` line
+    fn parse(line: &'a str) -> Self {
+        let mut columns = line.splitn(3, '\t');
+        match (columns.next(), columns.next(), columns.next()) {
+            (Some(added), Some(deleted), Some(path)) => Self {
+                path,
+                additions: added.parse().unwrap_or(0),
+                deletions: deleted.parse().unwrap_or(0),
+            },
+            _ => Self {
+                path: line,
+                additions: 0,
+                deletions: 0,
+            },
+        }
+    }
+}
+
+/// Parse output of `git log --format=%x1e%an[%x1f%at[%x1f%s]]` with
+/// `--name-only` or `--numstat`
+///
+/// Records without an author, or with a timestamp that isn't a number, are
+/// skipped.
+pub fn parse_log(output: &str) -> impl Iterator<Item = LogRecord<'_>> {
+    output.split('\x1e').filter_map(|record| {
+        let mut lines = record.lines();
+        let mut fields = lines.next()?.splitn(3, '\x1f');
+        let author = fields.next().map(str::trim).filter(|a| !a.is_empty())?;
+        let timestamp = match fields.next() {
+            Some(timestamp) => Some(timestamp.trim().parse().ok()?),
+            None => None,
+        };
+        let subject = fields.next().unwrap_or_default();
+        let files = lines
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(LogFile::parse)
+            .collect();
+        Some(LogRecord {
+            author,
+            timestamp,
+            subject,
+            files,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, additions: usize, deletions: usize) -> LogFile<'_> {
+        LogFile {
+            path,
+            additions,
+            deletions,
+        }
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let output = "\x1eAda\n\nsrc/lib.rs\nREADME.md\n\x1e\n\norphan.rs\n\x1eGrace\n";
+        let records: Vec<LogRecord> = parse_log(output).collect();
+        assert_eq!(
+            records,
+            [
+                LogRecord {
+                    author: "Ada",
+                    timestamp: None,
+                    subject: "",
+                    files: vec![file("src/lib.rs", 0, 0), file("README.md", 0, 0)],
+                },
+                LogRecord {
+                    author: "Grace",
+                    timestamp: None,
+                    subject: "",
+                    files: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_log_with_timestamp_subject_and_numstat() {
+        let output = "\x1eAda\x1f1700000000\x1fAdd retry\n\n10\t2\tsrc/net.rs\n-\t-\tlogo.png\n\
+                      \x1eBob\x1fyesterday\x1fBad date\n\n1\t1\tsrc/net.rs\n";
+        let records: Vec<LogRecord> = parse_log(output).collect();
+        assert_eq!(
+            records,
+            [LogRecord {
+                author: "Ada",
+                timestamp: Some(1_700_000_000),
+                subject: "Add retry",
+                files: vec![file("src/net.rs", 10, 2), file("logo.png", 0, 0)],
+            }]
+        );
+    }
+}
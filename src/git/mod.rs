@@ -1,14 +1,39 @@
 // Git module providing functionality for Git repository operations
 
+mod blame;
+mod breaking;
 mod commit;
+mod diff_cache;
 mod files;
+mod location;
+mod log_record;
+mod pickaxe;
+mod rebase;
+mod reflog;
+pub mod remote_cache;
 mod repository;
+mod revision;
+mod stash;
+mod stats;
 mod utils;
 
 // Re-export primary types for public use
+pub use blame::{BlameRegion, blame_file};
+pub use breaking::{
+    BreakingChange, BreakingKind, BreakingReport, apply_breaking_changes, detect_breaking_changes,
+    is_breaking_message,
+};
 pub use commit::CommitInfo;
 pub use commit::CommitResult;
+pub use location::{GitLocation, git_command, open_repository, repository_root, set_location};
+pub use log_record::{LogFile, LogRecord, parse_log};
+pub use pickaxe::{PickaxeChange, PickaxeHit, PickaxeKind, pickaxe_search};
+pub use rebase::{RebaseAction, RebaseCommit, RebaseOutcome, RebasePlan, RebaseStep};
+pub use reflog::ReflogEntry;
 pub use repository::GitRepo;
+pub use revision::ResolvedRevision;
+pub use stash::StashEntry;
+pub use stats::{FileStats, HistoryStats};
 
 // Re-export utility functions
 pub use utils::*;
@@ -0,0 +1,286 @@
+use anyhow::{Result, anyhow};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+use super::location::git_command;
+
+/// Matched lines kept per hit, so huge generated files don't flood the result
+const MAX_LINES_PER_HIT: usize = 8;
+
+/// How `git log` matches the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PickaxeKind {
+    /// `-S`: commits that change how many times the string occurs
+    #[default]
+    String,
+    /// `-G`: commits whose added or removed lines match the regex
+    Regex,
+}
+
+impl PickaxeKind {
+    /// Flip between string and regex matching
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::String => Self::Regex,
+            Self::Regex => Self::String,
+        }
+    }
+
+    /// The `git log` option for this kind
+    pub fn flag(self) -> &'static str {
+        match self {
+            Self::String => "-S",
+            Self::Regex => "-G",
+        }
+    }
+
+    /// Display label
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Regex => "regex",
+        }
+    }
+}
+
+/// Whether a commit added or removed the matched text in a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickaxeChange {
+    Added,
+    Removed,
+    /// Matching lines were both added and removed
+    Modified,
+}
+
+impl PickaxeChange {
+    /// Display label
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Modified => "changed",
+        }
+    }
+}
+
+/// One file in a commit that `git log -S/-G` matched
+#[derive(Debug, Clone)]
+pub struct PickaxeHit {
+    /// Full commit hash
+    pub commit: String,
+    pub short_hash: String,
+    /// Commit message (first line)
+    pub subject: String,
+    pub author: String,
+    /// Commit date (`YYYY-MM-DD`)
+    pub date: String,
+    /// Relative time (e.g., "2 days ago")
+    pub relative_time: String,
+    /// Path of the file in the commit
+    pub file: PathBuf,
+    /// Line in the commit's version of the file where the first matching
+    /// line was added, if any were
+    pub line: Option<usize>,
+    /// Matching diff lines with their `+`/`-` prefix
+    pub lines: Vec<String>,
+    /// Matching lines added
+    pub added: usize,
+    /// Matching lines removed
+    pub removed: usize,
+}
+
+impl PickaxeHit {
+    /// Whether the commit introduced or removed the match
+    pub fn change(&self) -> PickaxeChange {
+        match (self.added, self.removed) {
+            (_, 0) => PickaxeChange::Added,
+            (0, _) => PickaxeChange::Removed,
+            _ => PickaxeChange::Modified,
+        }
+    }
+}
+
+/// Find the commits that added or removed `query`, newest first
+pub fn pickaxe_search(
+    repo_path: &Path,
+    query: &str,
+    kind: PickaxeKind,
+    limit: usize,
+) -> Result<Vec<PickaxeHit>> {
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let matcher = match kind {
+        PickaxeKind::String => None,
+        PickaxeKind::Regex => Some(Regex::new(query).map_err(|e| anyhow!("Invalid regex: {e}"))?),
+    };
+
+    let output = git_command()
+        .args(["-C", &repo_path.to_string_lossy(), "log"])
+        .arg(format!("{}{query}", kind.flag()))
+        .args([
+            "--format=%x1e%H%x1f%h%x1f%s%x1f%an%x1f%ad%x1f%ar",
+            "--date=short",
+            "--no-color",
+            "--no-ext-diff",
+            "--unified=0",
+            "-p",
+            "-n",
+            &limit.to_string(),
+        ])
+        .output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git log {} failed: {}",
+            kind.flag(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_pickaxe_log(&stdout, |line| match &matcher {
+        Some(regex) => regex.is_match(line),
+        None => line.contains(query),
+    }))
+}
+
+/// Parse `git log -p --unified=0` output into one hit per matching file,
+/// keeping the diff lines `matches` accepts
+pub fn parse_pickaxe_log(output: &str, matches: impl Fn(&str) -> bool) -> Vec<PickaxeHit> {
+    let mut hits = Vec::new();
+
+    for record in output.split('\x1e').filter(|r| !r.trim().is_empty()) {
+        let (header, patch) = record.split_once('\n').unwrap_or((record, ""));
+        let fields: Vec<&str> = header.splitn(6, '\x1f').collect();
+        let [commit, short_hash, subject, author, date, relative_time] = fields[..] else {
+            continue;
+        };
+        let mut current: Option<PickaxeHit> = None;
+        let mut in_header = false;
+        let mut new_line = 0;
+
+        for line in patch.lines() {
+            if line.starts_with("diff --git ") {
+                if let Some(hit) = current.take().filter(|h| !h.lines.is_empty()) {
+                    hits.push(hit);
+                }
+                in_header = true;
+                current = Some(PickaxeHit {
+                    commit: commit.to_string(),
+                    short_hash: short_hash.to_string(),
+                    subject: subject.to_string(),
+                    author: author.to_string(),
+                    date: date.to_string(),
+                    relative_time: relative_time.to_string(),
+                    file: PathBuf::new(),
+                    line: None,
+                    lines: Vec::new(),
+                    added: 0,
+                    removed: 0,
+                });
+                continue;
+            }
+            let Some(hit) = current.as_mut() else {
+                continue;
+            };
+
+            if in_header {
+                // Prefer the new path; deleted files only have the old one
+                if let Some(path) = line.strip_prefix("+++ b/") {
+                    hit.file = PathBuf::from(path);
+                } else if let Some(path) = line.strip_prefix("--- a/")
+                    && hit.file.as_os_str().is_empty()
+                {
+                    hit.file = PathBuf::from(path);
+                }
+            }
+            if line.starts_with("@@") {
+                in_header = false;
+                new_line = hunk_new_start(line).unwrap_or(0);
+                continue;
+            }
+            if in_header {
+                continue;
+            }
+
+            if let Some(text) = line.strip_prefix('+') {
+                if matches(text) {
+                    hit.added += 1;
+                    hit.line.get_or_insert(new_line);
+                    if hit.lines.len() < MAX_LINES_PER_HIT {
+                        hit.lines.push(line.to_string());
+                    }
+                }
+                new_line += 1;
+            } else if let Some(text) = line.strip_prefix('-')
+                && matches(text)
+            {
+                hit.removed += 1;
+                if hit.lines.len() < MAX_LINES_PER_HIT {
+                    hit.lines.push(line.to_string());
+                }
+            }
+        }
+
+        if let Some(hit) = current.filter(|h| !h.lines.is_empty()) {
+            hits.push(hit);
+        }
+    }
+
+    hits
+}
+
+/// Start line of the new side of a `@@ -a,b +c,d @@` hunk header
+fn hunk_new_start(header: &str) -> Option<usize> {
+    let new = header
+        .split_whitespace()
+        .find(|part| part.starts_with('+'))?;
+    new.trim_start_matches('+').split(',').next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str =
+        "\x1eaaaaaaa1\x1faaaaaaa\x1fDrop legacy flag\x1fAda\x1f2024-03-02\x1f2 days ago
+diff --git a/src/old.rs b/src/old.rs
+deleted file mode 100644
+--- a/src/old.rs
++++ /dev/null
+@@ -3 +0,0 @@
+-const LEGACY_FLAG: bool = true;
+\x1ebbbbbbb2\x1fbbbbbbb\x1fAdd legacy flag\x1fGrace\x1f2024-01-05\x1f2 months ago
+diff --git a/src/old.rs b/src/old.rs
+--- a/src/old.rs
++++ b/src/old.rs
+@@ -10,0 +11,2 @@ fn main() {
++// Keep the old behaviour behind a flag
++const LEGACY_FLAG: bool = true;
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -1 +1 @@
+-Nothing here
++Something else
+";
+
+    #[test]
+    fn test_parse_pickaxe_log() {
+        let hits = parse_pickaxe_log(LOG, |line| line.contains("LEGACY_FLAG"));
+        assert_eq!(hits.len(), 2);
+
+        let removed = &hits[0];
+        assert_eq!(removed.short_hash, "aaaaaaa");
+        assert_eq!(removed.file, PathBuf::from("src/old.rs"));
+        assert_eq!(removed.change(), PickaxeChange::Removed);
+        assert_eq!(removed.line, None);
+
+        let added = &hits[1];
+        assert_eq!(added.subject, "Add legacy flag");
+        assert_eq!(added.date, "2024-01-05");
+        assert_eq!(added.change(), PickaxeChange::Added);
+        assert_eq!(added.line, Some(12));
+        assert_eq!(added.lines, vec!["+const LEGACY_FLAG: bool = true;"]);
+    }
+}
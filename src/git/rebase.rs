@@ -0,0 +1,552 @@
+use anyhow::{Result, anyhow, bail};
+use git2::{Commit, Oid, Repository, Sort, build::CheckoutBuilder};
+use std::fmt;
+
+/// What a rebase does with one commit, as in a `git rebase -i` todo list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebaseAction {
+    Pick,
+    /// Pick with a new subject line
+    Reword,
+    /// Fold into the previous commit, keeping both messages
+    Squash,
+    /// Fold into the previous commit, keeping only its message
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword => "reword",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    /// Parse an action word or its one-letter abbreviation
+    pub fn parse(word: &str) -> Option<Self> {
+        match word.to_lowercase().as_str() {
+            "pick" | "p" => Some(Self::Pick),
+            "reword" | "r" => Some(Self::Reword),
+            "squash" | "s" => Some(Self::Squash),
+            "fixup" | "f" => Some(Self::Fixup),
+            "drop" | "d" => Some(Self::Drop),
+            _ => None,
+        }
+    }
+
+    /// Whether the commit is folded into the one before it
+    pub fn is_fold(self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+impl fmt::Display for RebaseAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A commit on the branch being rebased
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebaseCommit {
+    /// Full commit id
+    pub id: String,
+    /// Position on the branch, oldest first
+    pub position: usize,
+    pub summary: String,
+    pub message: String,
+    pub author: String,
+    /// Paths the commit changes
+    pub files: Vec<String>,
+}
+
+impl RebaseCommit {
+    pub fn short_id(&self) -> &str {
+        &self.id[..7.min(self.id.len())]
+    }
+}
+
+/// One line of a rebase plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebaseStep {
+    pub commit: RebaseCommit,
+    pub action: RebaseAction,
+    /// Replacement subject line for a reword
+    pub new_subject: Option<String>,
+    /// Why Iris suggested this step
+    pub rationale: Option<String>,
+}
+
+impl RebaseStep {
+    fn pick(commit: RebaseCommit) -> Self {
+        Self {
+            commit,
+            action: RebaseAction::Pick,
+            new_subject: None,
+            rationale: None,
+        }
+    }
+
+    /// Subject the commit will have after the rebase
+    pub fn subject(&self) -> &str {
+        match (&self.new_subject, self.action) {
+            (Some(subject), RebaseAction::Reword) => subject,
+            _ => &self.commit.summary,
+        }
+    }
+
+    /// Full message with the reworded subject, if any
+    fn message(&self) -> String {
+        match (&self.new_subject, self.action) {
+            (Some(subject), RebaseAction::Reword) => {
+                let body = self
+                    .commit
+                    .message
+                    .split_once('\n')
+                    .map_or("", |(_, body)| body);
+                format!("{subject}\n{body}")
+            }
+            _ => self.commit.message.clone(),
+        }
+    }
+}
+
+impl fmt::Display for RebaseStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<6} {} {}",
+            self.action,
+            self.commit.short_id(),
+            self.subject()
+        )
+    }
+}
+
+/// Commits to replay onto a base, and what to do with each, in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebasePlan {
+    /// Base as the user named it, such as `main`
+    pub base: String,
+    /// Commit the branch is replayed onto: the tip of `base`
+    pub onto: String,
+    /// Where the branch forked from `base`; the same as `onto` when the
+    /// branch is already on top of it
+    pub fork: String,
+    /// Branch being rebased
+    pub branch: String,
+    /// Tip of the branch when the plan was made
+    pub head: String,
+    pub steps: Vec<RebaseStep>,
+}
+
+impl RebasePlan {
+    /// Plan to pick every commit on the current branch since it forked from `base`
+    pub fn load(repo: &Repository, base: &str) -> Result<Self> {
+        let head = repo.head()?;
+        if !head.is_branch() {
+            bail!("HEAD is detached; check out the branch to rebase");
+        }
+        let branch = head.shorthand().unwrap_or_default().to_string();
+        let head_id = head
+            .target()
+            .ok_or_else(|| anyhow!("The current branch has no commits"))?;
+        let base_id = repo.revparse_single(base)?.peel_to_commit()?.id();
+        let fork = repo.merge_base(base_id, head_id)?;
+
+        let mut walk = repo.revwalk()?;
+        walk.push(head_id)?;
+        walk.hide(fork)?;
+        walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+
+        let mut steps = Vec::new();
+        for (position, id) in walk.enumerate() {
+            let commit = repo.find_commit(id?)?;
+            if commit.parent_count() > 1 {
+                bail!(
+                    "{} is a merge commit; rebasing merges isn't supported",
+                    &commit.id().to_string()[..7]
+                );
+            }
+            steps.push(RebaseStep::pick(read_commit(repo, &commit, position)?));
+        }
+        Ok(Self {
+            base: base.to_string(),
+            onto: base_id.to_string(),
+            fork: fork.to_string(),
+            branch,
+            head: head_id.to_string(),
+            steps,
+        })
+    }
+
+    /// Whether the plan would leave the branch as it is
+    pub fn is_unchanged(&self) -> bool {
+        self.onto == self.fork
+            && self
+                .steps
+                .iter()
+                .enumerate()
+                .all(|(i, step)| step.action == RebaseAction::Pick && step.commit.position == i)
+    }
+
+    /// Number of commits on the branch after the rebase
+    pub fn result_count(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| matches!(step.action, RebaseAction::Pick | RebaseAction::Reword))
+            .count()
+    }
+
+    /// Check the plan can run: a squash or fixup needs a commit to fold into
+    pub fn validate(&self) -> Result<()> {
+        let first = self
+            .steps
+            .iter()
+            .find(|step| step.action != RebaseAction::Drop)
+            .ok_or_else(|| anyhow!("The plan drops every commit"))?;
+        if first.action.is_fold() {
+            bail!(
+                "{} {} has no earlier commit to {} into",
+                first.commit.short_id(),
+                first.commit.summary,
+                first.action
+            );
+        }
+        Ok(())
+    }
+
+    /// Move the step at `index` by one place; returns its new index
+    pub fn move_step(&mut self, index: usize, down: bool) -> Option<usize> {
+        let target = if down {
+            index + 1
+        } else {
+            index.checked_sub(1)?
+        };
+        if target >= self.steps.len() {
+            return None;
+        }
+        self.steps.swap(index, target);
+        Some(target)
+    }
+
+    /// Back to picking every commit in its original order
+    pub fn reset(&mut self) {
+        self.steps.sort_by_key(|step| step.commit.position);
+        for step in &mut self.steps {
+            step.action = RebaseAction::Pick;
+            step.new_subject = None;
+            step.rationale = None;
+        }
+    }
+
+    /// The plan as a `git rebase -i` todo list, with rationale as comments
+    pub fn todo(&self) -> String {
+        let mut todo = String::new();
+        for step in &self.steps {
+            todo.push_str(&format!("{step}\n"));
+            if let Some(rationale) = &step.rationale {
+                todo.push_str(&format!("    # {rationale}\n"));
+            }
+        }
+        todo
+    }
+
+    /// Prompt describing the commits for the rebase capability
+    pub fn prompt(&self) -> String {
+        let mut prompt = format!(
+            "Plan an interactive rebase of `{}` onto `{}` ({} commits, oldest first). \
+             Use `git_diff` with `from=\"<hash>^\"` and `to=\"<hash>\"` to read a commit.\n\n## Commits\n",
+            self.branch,
+            self.base,
+            self.steps.len()
+        );
+        for step in &self.steps {
+            let commit = &step.commit;
+            prompt.push_str(&format!(
+                "\n### {} {}\nAuthor: {}\nFiles: {}\n",
+                commit.short_id(),
+                commit.summary,
+                commit.author,
+                commit.files.join(", ")
+            ));
+            let body = commit
+                .message
+                .trim()
+                .split_once('\n')
+                .map(|(_, b)| b.trim());
+            if let Some(body) = body.filter(|b| !b.is_empty()) {
+                prompt.push_str(&format!("\n{body}\n"));
+            }
+        }
+        prompt
+    }
+
+    /// Replace the plan with the todo list Iris suggested
+    ///
+    /// Lines are `<action> <hash> [subject]`, each optionally followed by
+    /// `# rationale` lines; the subject after a `reword` is the new one.
+    /// The suggestion must list every commit exactly once, or it's rejected
+    /// and the plan is left alone.
+    pub fn apply_suggestion(&mut self, suggestion: &str) -> Result<()> {
+        let mut steps: Vec<RebaseStep> = Vec::new();
+        for line in suggestion.lines() {
+            let line = line.trim().trim_start_matches(['-', '*']).trim();
+            if let Some(comment) = line.strip_prefix('#') {
+                if let Some(step) = steps.last_mut() {
+                    let comment = comment.trim();
+                    step.rationale = Some(match step.rationale.take() {
+                        Some(earlier) => format!("{earlier} {comment}"),
+                        None => comment.to_string(),
+                    });
+                }
+                continue;
+            }
+            let mut words = line.splitn(3, ' ');
+            let (Some(action), Some(hash)) = (
+                words.next().and_then(RebaseAction::parse),
+                words.next().map(|h| h.trim_matches('`')),
+            ) else {
+                continue;
+            };
+            let Some(commit) = self.find_commit(hash) else {
+                continue;
+            };
+            if steps.iter().any(|step| step.commit.id == commit.id) {
+                continue;
+            }
+            let subject = words
+                .next()
+                .map(|s| s.trim().trim_matches('`').to_string())
+                .filter(|s| !s.is_empty() && *s != commit.summary);
+            steps.push(RebaseStep {
+                commit: commit.clone(),
+                action,
+                new_subject: subject.filter(|_| action == RebaseAction::Reword),
+                rationale: None,
+            });
+        }
+
+        if steps.len() != self.steps.len() {
+            bail!(
+                "The suggestion covers {} of {} commits",
+                steps.len(),
+                self.steps.len()
+            );
+        }
+        for step in &mut steps {
+            if step.action == RebaseAction::Reword && step.new_subject.is_none() {
+                step.action = RebaseAction::Pick;
+            }
+        }
+        self.steps = steps;
+        Ok(())
+    }
+
+    fn find_commit(&self, hash: &str) -> Option<&RebaseCommit> {
+        if hash.len() < 4 {
+            return None;
+        }
+        self.steps
+            .iter()
+            .map(|step| &step.commit)
+            .find(|commit| commit.id.starts_with(hash))
+    }
+}
+
+/// Result of running a plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebaseOutcome {
+    /// Branch tip before the rebase, for undoing it
+    pub old_head: String,
+    /// Branch tip after the rebase
+    pub new_head: String,
+    /// Commits on the branch after the rebase
+    pub commits: usize,
+}
+
+fn read_commit(repo: &Repository, commit: &Commit<'_>, position: usize) -> Result<RebaseCommit> {
+    let parent = commit.parent(0).ok().map(|p| p.tree()).transpose()?;
+    let diff = repo.diff_tree_to_tree(parent.as_ref(), Some(&commit.tree()?), None)?;
+    let files = diff
+        .deltas()
+        .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+    Ok(RebaseCommit {
+        id: commit.id().to_string(),
+        position,
+        summary: commit.summary().unwrap_or_default().to_string(),
+        message: commit.message().unwrap_or_default().to_string(),
+        author: commit.author().name().unwrap_or_default().to_string(),
+        files,
+    })
+}
+
+/// Replay the plan's commits onto its base and move the branch to the result
+///
+/// Every new commit is built in memory first; the work tree and the branch
+/// only change once the whole plan has applied. A step that conflicts stops
+/// the rebase with nothing changed.
+pub fn execute(repo: &Repository, plan: &RebasePlan) -> Result<RebaseOutcome> {
+    plan.validate()?;
+    let head = repo.head()?;
+    let refname = head
+        .name()
+        .ok_or_else(|| anyhow!("The current branch name isn't valid UTF-8"))?
+        .to_string();
+    let head_id = head
+        .target()
+        .ok_or_else(|| anyhow!("The current branch has no commits"))?;
+    if head_id.to_string() != plan.head || head.shorthand() != Some(plan.branch.as_str()) {
+        bail!("The branch changed since the plan was made; reload it and try again");
+    }
+
+    let committer = repo.signature()?;
+    let mut tip = repo.find_commit(Oid::from_str(&plan.onto)?)?;
+    let mut commits = 0;
+    for step in plan.steps.iter().filter(|s| s.action != RebaseAction::Drop) {
+        let commit = repo.find_commit(Oid::from_str(&step.commit.id)?)?;
+        let tree = if commit.parent_id(0).ok() == Some(tip.id()) {
+            commit.tree()?
+        } else {
+            let mut index = repo.cherrypick_commit(&commit, &tip, 0, None)?;
+            if index.has_conflicts() {
+                bail!(
+                    "{} {} conflicts with the steps before it; reorder the plan or rebase by hand",
+                    step.commit.short_id(),
+                    step.commit.summary
+                );
+            }
+            repo.find_tree(index.write_tree_to(repo)?)?
+        };
+
+        let new_id = if step.action.is_fold() {
+            let message = match step.action {
+                RebaseAction::Squash => format!(
+                    "{}\n\n{}",
+                    tip.message().unwrap_or_default().trim_end(),
+                    step.message()
+                ),
+                _ => tip.message().unwrap_or_default().to_string(),
+            };
+            let parents: Vec<Commit> = tip.parents().collect();
+            let parents: Vec<&Commit> = parents.iter().collect();
+            repo.commit(None, &tip.author(), &committer, &message, &tree, &parents)?
+        } else if step.action == RebaseAction::Pick && commit.parent_id(0).ok() == Some(tip.id()) {
+            // Untouched so far: keep the original commit
+            commits += 1;
+            commit.id()
+        } else {
+            commits += 1;
+            repo.commit(
+                None,
+                &commit.author(),
+                &committer,
+                &step.message(),
+                &tree,
+                &[&tip],
+            )?
+        };
+        tip = repo.find_commit(new_id)?;
+    }
+
+    repo.checkout_tree(tip.as_object(), Some(CheckoutBuilder::new().safe()))?;
+    repo.reference_matching(
+        &refname,
+        tip.id(),
+        true,
+        head_id,
+        &format!("git-iris rebase: onto {}", plan.base),
+    )?;
+    Ok(RebaseOutcome {
+        old_head: head_id.to_string(),
+        new_head: tip.id().to_string(),
+        commits,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan() -> RebasePlan {
+        let steps = ["aaaaaaa1", "bbbbbbb2", "ccccccc3"]
+            .iter()
+            .enumerate()
+            .map(|(position, id)| {
+                RebaseStep::pick(RebaseCommit {
+                    id: (*id).to_string(),
+                    position,
+                    summary: format!("commit {position}"),
+                    message: format!("commit {position}\n\nbody {position}\n"),
+                    author: "Ada".to_string(),
+                    files: vec![],
+                })
+            })
+            .collect();
+        RebasePlan {
+            base: "main".to_string(),
+            onto: String::new(),
+            fork: String::new(),
+            branch: "feature".to_string(),
+            head: String::new(),
+            steps,
+        }
+    }
+
+    #[test]
+    fn test_apply_suggestion() {
+        let mut plan = plan();
+        plan.apply_suggestion(
+            "Here is the plan:\n\n\
+             reword aaaaaaa feat: add parser\n    # \"commit 0\" says nothing\n\
+             pick ccccccc commit 2\n\
+             fixup bbbbbbb commit 1\n    # Typo fix for the parser\n",
+        )
+        .expect("suggestion");
+        assert_eq!(plan.steps[0].action, RebaseAction::Reword);
+        assert_eq!(plan.steps[0].subject(), "feat: add parser");
+        assert_eq!(plan.steps[0].message(), "feat: add parser\n\nbody 0\n");
+        assert_eq!(
+            plan.steps[0].rationale.as_deref(),
+            Some("\"commit 0\" says nothing")
+        );
+        assert_eq!(plan.steps[2].commit.position, 1);
+        assert_eq!(plan.steps[2].action, RebaseAction::Fixup);
+        assert_eq!(plan.result_count(), 2);
+        assert!(!plan.is_unchanged());
+        plan.validate().expect("valid");
+
+        plan.reset();
+        assert!(plan.is_unchanged());
+    }
+
+    #[test]
+    fn test_incomplete_suggestion_is_rejected() {
+        let mut plan = plan();
+        assert!(
+            plan.apply_suggestion("pick aaaaaaa\nsquash bbbbbbb\n")
+                .is_err()
+        );
+        assert!(plan.is_unchanged());
+    }
+
+    #[test]
+    fn test_validate_needs_a_commit_to_fold_into() {
+        let mut plan = plan();
+        plan.steps[0].action = RebaseAction::Drop;
+        plan.steps[1].action = RebaseAction::Squash;
+        assert!(plan.validate().is_err());
+        assert_eq!(plan.move_step(1, false), Some(0));
+        assert_eq!(plan.move_step(0, false), None);
+        plan.steps[1].action = RebaseAction::Pick;
+        assert!(plan.validate().is_err());
+        plan.steps[0].action = RebaseAction::Pick;
+        plan.validate().expect("valid");
+    }
+}
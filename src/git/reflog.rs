@@ -0,0 +1,130 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use git2::{Oid, Repository};
+use std::fmt;
+
+/// One `HEAD` reflog entry with what the state it recorded contained
+#[derive(Debug, Clone)]
+pub struct ReflogEntry {
+    /// Position in the reflog (`HEAD@{index}`)
+    pub index: usize,
+    /// Commit `HEAD` pointed to after this entry
+    pub commit: String,
+    /// Reflog action, such as `commit`, `rebase (start)` or `reset`
+    pub action: String,
+    /// Reflog message after the action (`moving to HEAD~3`, the commit subject, …)
+    pub detail: String,
+    /// Subject of `commit`
+    pub subject: String,
+    pub time: DateTime<Utc>,
+    /// Commits in this state that the current `HEAD` doesn't contain
+    pub commits_not_in_head: usize,
+    /// Whether any branch or tag still reaches `commit`
+    pub reachable: bool,
+}
+
+impl ReflogEntry {
+    /// `HEAD@{n}` selector for this entry
+    pub fn selector(&self) -> String {
+        format!("HEAD@{{{}}}", self.index)
+    }
+
+    pub fn short_id(&self) -> &str {
+        &self.commit[..7.min(self.commit.len())]
+    }
+
+    /// Whether the entry holds commits that would be lost without the reflog
+    pub fn is_lost(&self) -> bool {
+        !self.reachable && self.commits_not_in_head > 0
+    }
+}
+
+impl fmt::Display for ReflogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}: {} | {}",
+            self.selector(),
+            self.short_id(),
+            self.action,
+            self.detail,
+            self.subject
+        )?;
+        if self.commits_not_in_head > 0 {
+            write!(f, " | {} commits not in HEAD", self.commits_not_in_head)?;
+        }
+        if !self.reachable {
+            write!(f, " | unreachable")?;
+        }
+        Ok(())
+    }
+}
+
+/// Read the most recent `HEAD` reflog entries
+pub fn read_reflog(repo: &Repository, limit: usize) -> Result<Vec<ReflogEntry>> {
+    let reflog = repo.reflog("HEAD")?;
+    let head = repo.head().ok().and_then(|h| h.target());
+    let tips = ref_tips(repo)?;
+
+    let mut entries = Vec::new();
+    for (index, entry) in reflog.iter().enumerate().take(limit) {
+        let id = entry.id_new();
+        if id.is_zero() {
+            continue;
+        }
+        let message = entry.message().unwrap_or_default();
+        let (action, detail) = message
+            .split_once(": ")
+            .map_or((message, ""), |(action, detail)| (action, detail));
+        let subject = repo
+            .find_commit(id)
+            .ok()
+            .and_then(|c| c.summary().map(String::from))
+            .unwrap_or_default();
+        let commits_not_in_head = match head {
+            Some(head) if head != id => repo.graph_ahead_behind(id, head)?.0,
+            Some(_) => 0,
+            None => 1,
+        };
+        let reachable = tips
+            .iter()
+            .any(|tip| *tip == id || repo.graph_descendant_of(*tip, id).unwrap_or(false));
+        let time =
+            DateTime::from_timestamp(entry.committer().when().seconds(), 0).unwrap_or_default();
+
+        entries.push(ReflogEntry {
+            index,
+            commit: id.to_string(),
+            action: action.to_string(),
+            detail: detail.to_string(),
+            subject,
+            time,
+            commits_not_in_head,
+            reachable,
+        });
+    }
+    Ok(entries)
+}
+
+/// Commit recorded by `HEAD@{index}`
+pub fn reflog_commit(repo: &Repository, index: usize) -> Result<Oid> {
+    let reflog = repo.reflog("HEAD")?;
+    reflog
+        .get(index)
+        .map(|entry| entry.id_new())
+        .ok_or_else(|| anyhow!("HEAD@{{{index}}} is not in the reflog"))
+}
+
+/// Tips of all local branches and tags
+fn ref_tips(repo: &Repository) -> Result<Vec<Oid>> {
+    let mut tips = Vec::new();
+    for reference in repo.references()? {
+        let reference = reference?;
+        if (reference.is_branch() || reference.is_tag())
+            && let Ok(commit) = reference.peel_to_commit()
+        {
+            tips.push(commit.id());
+        }
+    }
+    Ok(tips)
+}
@@ -0,0 +1,139 @@
+//! Cached mirrors of remote repositories
+//!
+//! `--repo <URL>` analyzes a repository that isn't checked out locally. Its
+//! history is fetched into a bare mirror under the user cache directory and
+//! reused by later runs, which only fetch what's new. Mirrors carry full
+//! history without a work tree: changelogs and reviews need every commit
+//! between two refs, and libgit2 can't fetch the blobs a partial clone leaves
+//! out, so neither shallow nor partial clones would do.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result, anyhow};
+use git2::{AutotagOption, FetchOptions, FetchPrune, ObjectType, Oid, Repository};
+
+use crate::log_debug;
+
+/// Directory holding the mirrors
+pub fn cache_root() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("git-iris").join("repos"))
+}
+
+/// Mirror directory for `url`: the repository name, for people browsing
+/// the cache, and a hash of the URL to keep mirrors apart
+fn mirror_dir(root: &Path, url: &str) -> Result<PathBuf> {
+    let name: String = url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let hash = Oid::hash_object(ObjectType::Blob, url.as_bytes())?.to_string();
+    Ok(root.join(format!("{name}-{}.git", &hash[..12])))
+}
+
+/// Fetch `url` into its mirror under `root`, cloning it the first time, and
+/// return the mirror's path
+pub fn fetch_mirror(root: &Path, url: &str) -> Result<PathBuf> {
+    let dir = mirror_dir(root, url)?;
+    let repo = if dir.join("HEAD").exists() {
+        log_debug!("Updating cached mirror of {} at {:?}", url, dir);
+        Repository::open_bare(&dir).context("Cached mirror is damaged; clear it and retry")?
+    } else {
+        log_debug!("Cloning {} into cache at {:?}", url, dir);
+        fs::create_dir_all(&dir)?;
+        let repo = Repository::init_bare(&dir)?;
+        repo.remote("origin", url)?;
+        repo
+    };
+    update_mirror(&repo).map_err(|e| anyhow!("Failed to fetch {url}: {e}"))?;
+    Ok(dir)
+}
+
+/// Bring a mirror's branches and tags up to date with its origin, and point
+/// `HEAD` at the origin's default branch
+pub fn update_mirror(repo: &Repository) -> Result<()> {
+    let mut remote = repo.find_remote("origin")?;
+    let mut options = FetchOptions::new();
+    options.prune(FetchPrune::On);
+    options.download_tags(AutotagOption::All);
+    remote.fetch(
+        &["+refs/heads/*:refs/heads/*", "+refs/tags/*:refs/tags/*"],
+        Some(&mut options),
+        None,
+    )?;
+    if let Ok(default) = remote.default_branch()
+        && let Some(name) = default.as_str()
+    {
+        repo.set_head(name)?;
+    }
+    Ok(())
+}
+
+/// A mirror in the cache
+#[derive(Debug, Clone)]
+pub struct CachedMirror {
+    pub path: PathBuf,
+    /// Remote the mirror was fetched from
+    pub url: String,
+    /// Total size on disk
+    pub size: u64,
+    /// When it was last fetched
+    pub fetched: Option<SystemTime>,
+}
+
+/// Mirrors in `root`, by URL
+pub fn list_mirrors(root: &Path) -> Result<Vec<CachedMirror>> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Ok(Vec::new());
+    };
+    let mut mirrors: Vec<CachedMirror> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            let repo = Repository::open_bare(&path).ok()?;
+            let url = repo.find_remote("origin").ok()?.url()?.to_string();
+            Some(CachedMirror {
+                size: dir_size(&path),
+                fetched: fs::metadata(path.join("FETCH_HEAD"))
+                    .and_then(|m| m.modified())
+                    .ok(),
+                path,
+                url,
+            })
+        })
+        .collect();
+    mirrors.sort_by(|a, b| a.url.cmp(&b.url));
+    Ok(mirrors)
+}
+
+/// Remove the mirror of `url`, or every mirror when `url` is `None`.
+/// Returns the mirrors removed.
+pub fn clear_mirrors(root: &Path, url: Option<&str>) -> Result<Vec<CachedMirror>> {
+    let mirrors: Vec<CachedMirror> = list_mirrors(root)?
+        .into_iter()
+        .filter(|mirror| url.is_none_or(|url| mirror.url == url))
+        .collect();
+    for mirror in &mirrors {
+        fs::remove_dir_all(&mirror.path)
+            .with_context(|| format!("Failed to remove {}", mirror.path.display()))?;
+    }
+    Ok(mirrors)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    fs::read_dir(path).map_or(0, |entries| {
+        entries
+            .filter_map(Result::ok)
+            .map(|entry| match entry.file_type() {
+                Ok(kind) if kind.is_dir() => dir_size(&entry.path()),
+                _ => entry.metadata().map_or(0, |m| m.len()),
+            })
+            .sum()
+    })
+}
@@ -1,27 +1,32 @@
 use crate::config::Config;
 use crate::context::{CommitContext, RecentCommit, StagedFile};
+use crate::error::IrisError;
 use crate::git::commit::{self, CommitResult};
+use crate::git::diff_cache;
 use crate::git::files::{
     RepoFilesInfo, get_ahead_behind, get_all_tracked_files, get_file_statuses,
     get_unstaged_file_statuses, get_untracked_files,
 };
-use crate::git::utils::is_inside_work_tree;
+use crate::git::location::{open_repository, repository_root};
+use crate::git::rebase::{self, RebaseOutcome, RebasePlan};
+use crate::git::reflog::{self, ReflogEntry};
+use crate::git::remote_cache;
+use crate::git::revision::{self, ResolvedRevision};
+use crate::git::stash::{self, StashEntry};
+use crate::git::stats::HistoryStats;
 use crate::log_debug;
+use crate::read_only::ensure_writable;
 use anyhow::{Context as AnyhowContext, Result, anyhow};
 use git2::{Repository, Tree};
 use std::env;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use tempfile::TempDir;
 use url::Url;
 
 /// Represents a Git repository and provides methods for interacting with it.
 #[derive(Debug)]
 pub struct GitRepo {
     repo_path: PathBuf,
-    /// Optional temporary directory for cloned repositories
-    #[allow(dead_code)] // This field is needed to maintain ownership of temp directories
-    temp_dir: Option<TempDir>,
     /// Whether this is a remote repository
     is_remote: bool,
     /// Original remote URL if this is a cloned repository
@@ -39,9 +44,12 @@ impl GitRepo {
     ///
     /// A Result containing the `GitRepo` instance or an error.
     pub fn new(repo_path: &Path) -> Result<Self> {
+        // Resolve to the work tree (or a bare repository's own directory);
+        // paths outside any repository are kept and fail when opened
+        let repo_path = open_repository(repo_path)
+            .map_or_else(|_| repo_path.to_path_buf(), |repo| repository_root(&repo));
         Ok(Self {
-            repo_path: repo_path.to_path_buf(),
-            temp_dir: None,
+            repo_path,
             is_remote: false,
             remote_url: None,
         })
@@ -65,38 +73,30 @@ impl GitRepo {
         }
     }
 
-    /// Clones a remote repository and creates a `GitRepo` instance for it.
+    /// Creates a `GitRepo` for a remote repository from its cached mirror,
+    /// cloning the mirror on first use and fetching new commits after that.
     ///
     /// # Arguments
     ///
-    /// * `url` - The URL of the remote repository to clone.
+    /// * `url` - The URL of the remote repository.
     ///
     /// # Returns
     ///
     /// A Result containing the `GitRepo` instance or an error.
     pub fn clone_remote_repository(url: &str) -> Result<Self> {
-        log_debug!("Cloning remote repository from URL: {}", url);
+        log_debug!("Opening remote repository from URL: {}", url);
 
         // Validate URL
         let _ = Url::parse(url).map_err(|e| anyhow!("Invalid repository URL: {}", e))?;
 
-        // Create a temporary directory for the clone
-        let temp_dir = TempDir::new()?;
-        let temp_path = temp_dir.path();
+        let root =
+            remote_cache::cache_root().ok_or_else(|| anyhow!("No cache directory available"))?;
+        let repo_path = remote_cache::fetch_mirror(&root, url)?;
 
-        log_debug!("Created temporary directory for clone: {:?}", temp_path);
-
-        // Clone the repository into the temporary directory
-        let repo = match Repository::clone(url, temp_path) {
-            Ok(repo) => repo,
-            Err(e) => return Err(anyhow!("Failed to clone repository: {}", e)),
-        };
-
-        log_debug!("Successfully cloned repository to {:?}", repo.path());
+        log_debug!("Using cached mirror at {:?}", repo_path);
 
         Ok(Self {
-            repo_path: temp_path.to_path_buf(),
-            temp_dir: Some(temp_dir),
+            repo_path,
             is_remote: true,
             remote_url: Some(url.to_string()),
         })
@@ -104,7 +104,43 @@ impl GitRepo {
 
     /// Open the repository at the stored path
     pub fn open_repo(&self) -> Result<Repository, git2::Error> {
-        Repository::open(&self.repo_path)
+        if self.is_remote {
+            Repository::open(&self.repo_path)
+        } else {
+            open_repository(&self.repo_path)
+        }
+    }
+
+    /// Open the repository for `action`, which needs a working tree
+    fn open_work_tree(&self, action: &str) -> Result<Repository> {
+        let repo = self.open_repo()?;
+        if repo.is_bare() {
+            return Err(IrisError::BareRepository {
+                action: action.to_string(),
+            }
+            .into());
+        }
+        Ok(repo)
+    }
+
+    /// Whether the repository has no working tree
+    pub fn is_bare(&self) -> bool {
+        self.open_repo().is_ok_and(|repo| repo.is_bare())
+    }
+
+    /// Contents of `path` as committed at `rev`; `None` when there's no such
+    /// file. Bare repositories have no files on disk, so this is how their
+    /// files are read.
+    pub fn read_committed_file(&self, rev: &str, path: &str) -> Result<Option<Vec<u8>>> {
+        let repo = self.open_repo()?;
+        let tree = repo.revparse_single(rev)?.peel_to_tree()?;
+        let Ok(entry) = tree.get_path(Path::new(path)) else {
+            return Ok(None);
+        };
+        Ok(entry
+            .to_object(&repo)?
+            .as_blob()
+            .map(|blob| blob.content().to_vec()))
     }
 
     /// Returns whether this `GitRepo` instance is working with a remote repository
@@ -129,20 +165,7 @@ impl GitRepo {
         }
 
         log_debug!("Updating remote repository");
-        let repo = self.open_repo()?;
-
-        // Find the default remote (usually "origin")
-        let remotes = repo.remotes()?;
-        let remote_name = remotes
-            .iter()
-            .flatten()
-            .next()
-            .ok_or_else(|| anyhow!("No remote found"))?;
-
-        // Fetch updates from the remote
-        let mut remote = repo.find_remote(remote_name)?;
-        remote.fetch(&["master", "main"], None, None)?;
-
+        remote_cache::update_mirror(&self.open_repo()?)?;
         log_debug!("Successfully updated remote repository");
         Ok(())
     }
@@ -216,11 +239,11 @@ impl GitRepo {
             let status = child.wait()?;
 
             if !status.success() {
-                return Err(anyhow!(
-                    "Hook '{}' failed with exit code: {:?}",
-                    hook_name,
-                    status.code()
-                ));
+                return Err(IrisError::HookFailed {
+                    hook: hook_name.to_string(),
+                    code: status.code(),
+                }
+                .into());
             }
 
             log_debug!("Hook '{}' executed successfully", hook_name);
@@ -233,33 +256,15 @@ impl GitRepo {
 
     /// Get the root directory of the current git repository
     pub fn get_repo_root() -> Result<PathBuf> {
-        // Check if we're in a git repository
-        if !is_inside_work_tree()? {
-            return Err(anyhow!(
+        let repo = open_repository(&env::current_dir()?).map_err(|_| {
+            anyhow!(
                 "Not in a Git repository. Please run this command from within a Git repository."
-            ));
-        }
-
-        // Use git rev-parse to find the repository root
-        let output = Command::new("git")
-            .args(["rev-parse", "--show-toplevel"])
-            .output()
-            .context("Failed to execute git command")?;
-
-        if !output.status.success() {
-            return Err(anyhow!(
-                "Failed to get repository root: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ));
+            )
+        })?;
+        if repo.is_bare() {
+            return Err(anyhow!("The repository is bare and has no working tree"));
         }
-
-        // Convert the output to a path
-        let root = String::from_utf8(output.stdout)
-            .context("Invalid UTF-8 output from git command")?
-            .trim()
-            .to_string();
-
-        Ok(PathBuf::from(root))
+        Ok(repository_root(&repo))
     }
 
     /// Retrieves the README content at a specific commit.
@@ -323,7 +328,7 @@ impl GitRepo {
 
     /// Extract files info without crossing async boundaries
     pub fn extract_files_info(&self, include_unstaged: bool) -> Result<RepoFilesInfo> {
-        let repo = self.open_repo()?;
+        let repo = self.open_work_tree("Reading staged changes")?;
 
         // Get basic repo info
         let branch = self.get_current_branch()?;
@@ -350,7 +355,7 @@ impl GitRepo {
 
     /// Gets unstaged file changes from the repository
     pub fn get_unstaged_files(&self) -> Result<Vec<StagedFile>> {
-        let repo = self.open_repo()?;
+        let repo = self.open_work_tree("Reading unstaged changes")?;
         get_unstaged_file_statuses(&repo)
     }
 
@@ -361,6 +366,9 @@ impl GitRepo {
     /// - --- and +++ file headers
     /// - @@ hunk headers
     /// - +/- content lines
+    ///
+    /// Diffs are cached by tree pair, so asking again for refs that haven't
+    /// moved skips rendering.
     pub fn get_ref_diff_full(&self, from: &str, to: &str) -> Result<String> {
         let repo = self.open_repo()?;
 
@@ -370,35 +378,15 @@ impl GitRepo {
 
         let from_tree = from_commit.tree()?;
         let to_tree = to_commit.tree()?;
+        if let Some(diff) = diff_cache::get(from_tree.id(), to_tree.id()) {
+            return Ok(diff);
+        }
 
         // Get diff between the two trees
         let diff = repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), None)?;
+        let diff_string = format_patch(&diff)?;
 
-        // Format as unified diff
-        let mut diff_string = String::new();
-        diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
-            // For diff content lines (+/-/context), prefix with origin char
-            if matches!(line.origin(), '+' | '-' | ' ') {
-                diff_string.push(line.origin());
-            }
-            // All line types get their content appended
-            diff_string.push_str(&String::from_utf8_lossy(line.content()));
-
-            if line.origin() == 'F'
-                && !diff_string.contains("diff --git")
-                && let Some(new_file) = delta.new_file().path()
-            {
-                let header = format!("diff --git a/{0} b/{0}\n", new_file.display());
-                if !diff_string.ends_with(&header) {
-                    diff_string.insert_str(
-                        diff_string.rfind("---").unwrap_or(diff_string.len()),
-                        &header,
-                    );
-                }
-            }
-            true
-        })?;
-
+        diff_cache::insert(from_tree.id(), to_tree.id(), &diff_string);
         Ok(diff_string)
     }
 
@@ -412,12 +400,11 @@ impl GitRepo {
     pub fn get_staged_diff_full(&self) -> Result<String> {
         let repo = self.open_repo()?;
 
-        // Get the HEAD tree to diff against
-        let head = repo.head()?;
-        let head_tree = head.peel_to_tree()?;
+        // Get the HEAD tree to diff against, none before the first commit
+        let head_tree = head_tree(&repo)?;
 
         // Get staged changes (index vs HEAD)
-        let diff = repo.diff_tree_to_index(Some(&head_tree), None, None)?;
+        let diff = repo.diff_tree_to_index(head_tree.as_ref(), None, None)?;
 
         // Format as unified diff
         let mut diff_string = String::new();
@@ -509,7 +496,7 @@ impl GitRepo {
     /// A Result containing the `CommitContext` or an error.
     pub fn get_git_info(&self, _config: &Config) -> Result<CommitContext> {
         // Get data that doesn't cross async boundaries
-        let repo = self.open_repo()?;
+        let repo = self.open_work_tree("Reading staged changes")?;
         log_debug!("Getting git info for repo path: {:?}", repo.path());
 
         let branch = self.get_current_branch()?;
@@ -660,6 +647,33 @@ impl GitRepo {
         Ok(commits)
     }
 
+    /// Commit frequency, churn, authors and reverts per file over the last
+    /// `days` days of history reachable from HEAD, merges excluded
+    pub fn get_history_stats(&self, days: u32) -> Result<HistoryStats> {
+        let since = format!("--since={days} days ago");
+        let output = crate::git::git_command()
+            .arg("-C")
+            .arg(&self.repo_path)
+            .args([
+                "log",
+                "--no-merges",
+                "--no-renames",
+                &since,
+                "--format=%x1e%an%x1f%at%x1f%s",
+                "--numstat",
+            ])
+            .output()
+            .context("Failed to run git log")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "git log failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        let log = String::from_utf8_lossy(&output.stdout);
+        Ok(HistoryStats::from_git_log(&log, days, chrono::Utc::now()))
+    }
+
     /// Commits changes and verifies the commit.
     ///
     /// # Arguments
@@ -670,13 +684,14 @@ impl GitRepo {
     ///
     /// A Result containing the `CommitResult` or an error.
     pub fn commit_and_verify(&self, message: &str) -> Result<CommitResult> {
+        ensure_writable("Committing")?;
         if self.is_remote {
             return Err(anyhow!(
                 "Cannot commit to a remote repository in read-only mode"
             ));
         }
 
-        let repo = self.open_repo()?;
+        let repo = self.open_work_tree("Committing")?;
         match commit::commit(&repo, message, self.is_remote) {
             Ok(result) => {
                 if let Err(e) = self.execute_hook("post-commit") {
@@ -742,15 +757,34 @@ impl GitRepo {
         commit::get_commits_between_with_callback(&repo, from, to, callback)
     }
 
+    /// Get commits between two references that change files under `path`
+    pub fn get_commits_touching_path(
+        &self,
+        from: &str,
+        to: &str,
+        path: &str,
+    ) -> Result<Vec<RecentCommit>> {
+        let repo = self.open_repo()?;
+        commit::get_commits_touching_path(&repo, from, to, path)
+    }
+
     /// Commit changes to the repository
     pub fn commit(&self, message: &str) -> Result<CommitResult> {
-        let repo = self.open_repo()?;
+        ensure_writable("Committing")?;
+        if self.is_remote {
+            return commit::commit(&self.open_repo()?, message, true);
+        }
+        let repo = self.open_work_tree("Committing")?;
         commit::commit(&repo, message, self.is_remote)
     }
 
     /// Amend the previous commit with staged changes and a new message
     pub fn amend_commit(&self, message: &str) -> Result<CommitResult> {
-        let repo = self.open_repo()?;
+        ensure_writable("Amending")?;
+        if self.is_remote {
+            return commit::amend_commit(&self.open_repo()?, message, true);
+        }
+        let repo = self.open_work_tree("Amending")?;
         commit::amend_commit(&repo, message, self.is_remote)
     }
 
@@ -762,7 +796,12 @@ impl GitRepo {
 
     /// Check if inside a working tree
     pub fn is_inside_work_tree() -> Result<bool> {
-        is_inside_work_tree()
+        crate::git::utils::is_inside_work_tree()
+    }
+
+    /// Check if inside a repository, bare or not
+    pub fn is_inside_repository() -> bool {
+        env::current_dir().is_ok_and(|dir| open_repository(&dir).is_ok())
     }
 
     /// Get the files changed in a specific commit
@@ -777,8 +816,281 @@ impl GitRepo {
         commit::get_file_paths_for_commit(&repo, commit_id)
     }
 
+    /// Find the most recent tag reachable from `rev`, excluding a tag on `rev` itself
+    ///
+    /// Returns `None` when the history has no tags.
+    pub fn get_latest_tag(&self, rev: &str) -> Result<Option<String>> {
+        let repo = self.open_repo()?;
+        let target = repo.revparse_single(rev)?.peel_to_commit()?;
+
+        let mut opts = git2::DescribeOptions::new();
+        opts.describe_tags();
+        let mut format = git2::DescribeFormatOptions::new();
+        format.abbreviated_size(0);
+
+        let describe = |commit: &git2::Commit| -> Result<Option<String>> {
+            match commit.as_object().describe(&opts) {
+                Ok(description) => Ok(Some(description.format(Some(&format))?)),
+                Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        };
+
+        let Some(tag) = describe(&target)? else {
+            return Ok(None);
+        };
+        // Preparing a release that's already tagged: compare against the previous tag
+        let tagged = repo.revparse_single(&tag)?.peel_to_commit()?;
+        if tagged.id() == target.id() {
+            return match target.parent(0) {
+                Ok(parent) => describe(&parent),
+                Err(_) => Ok(None),
+            };
+        }
+        Ok(Some(tag))
+    }
+
+    /// Find the most recent tag reachable from `rev`, including a tag on `rev` itself
+    pub fn get_tag_at_or_before(&self, rev: &str) -> Result<Option<String>> {
+        let repo = self.open_repo()?;
+        let target = repo.revparse_single(rev)?.peel_to_commit()?;
+
+        let mut opts = git2::DescribeOptions::new();
+        opts.describe_tags();
+        let mut format = git2::DescribeFormatOptions::new();
+        format.abbreviated_size(0);
+        match target.as_object().describe(&opts) {
+            Ok(description) => Ok(Some(description.format(Some(&format))?)),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Count recent revert commits touching each of the given paths
+    pub fn count_reverts_touching(
+        &self,
+        paths: &[String],
+        limit: usize,
+    ) -> Result<std::collections::HashMap<String, usize>> {
+        let repo = self.open_repo()?;
+        commit::count_reverts_touching(&repo, paths, limit)
+    }
+
+    /// Read the most recent `HEAD` reflog entries
+    pub fn get_reflog(&self, limit: usize) -> Result<Vec<ReflogEntry>> {
+        let repo = self.open_repo()?;
+        reflog::read_reflog(&repo, limit)
+    }
+
+    /// Create a branch at the commit recorded by `HEAD@{index}`
+    pub fn create_branch_from_reflog(&self, index: usize, name: &str) -> Result<()> {
+        ensure_writable("Creating a branch")?;
+        let repo = self.open_repo()?;
+        let commit = repo.find_commit(reflog::reflog_commit(&repo, index)?)?;
+        repo.branch(name, &commit, false)?;
+        Ok(())
+    }
+
+    /// Hard-reset the current branch to the commit recorded by `HEAD@{index}`
+    ///
+    /// Refuses to run with uncommitted changes, since a hard reset would discard them.
+    pub fn reset_to_reflog(&self, index: usize) -> Result<()> {
+        ensure_writable("Resetting the branch")?;
+        let repo = self.open_repo()?;
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(false);
+        if !repo.statuses(Some(&mut options))?.is_empty() {
+            return Err(IrisError::RepoDirty {
+                action: "a hard reset".to_string(),
+            }
+            .into());
+        }
+        let target = repo.find_object(reflog::reflog_commit(&repo, index)?, None)?;
+        repo.reset(&target, git2::ResetType::Hard, None)?;
+        Ok(())
+    }
+
+    /// Plan a rebase of the current branch onto `base`, picking every commit
+    /// since the branch forked from it
+    pub fn rebase_plan(&self, base: &str) -> Result<RebasePlan> {
+        let repo = self.open_repo()?;
+        RebasePlan::load(&repo, base)
+    }
+
+    /// Run a rebase plan and move the current branch to the result
+    ///
+    /// Refuses to run with uncommitted changes, since the work tree is
+    /// checked out to the rebased branch.
+    pub fn execute_rebase(&self, plan: &RebasePlan) -> Result<RebaseOutcome> {
+        ensure_writable("Rebasing")?;
+        let repo = self.open_work_tree("Rebasing")?;
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(false);
+        if !repo.statuses(Some(&mut options))?.is_empty() {
+            return Err(IrisError::RepoDirty {
+                action: "a rebase".to_string(),
+            }
+            .into());
+        }
+        rebase::execute(&repo, plan)
+    }
+
+    /// Read the stash list, newest first
+    pub fn list_stashes(&self) -> Result<Vec<StashEntry>> {
+        let mut repo = self.open_repo()?;
+        stash::list_stashes(&mut repo)
+    }
+
+    /// Full unified diff of a stash: its tracked changes against the commit
+    /// it was made on, then any untracked files it saved
+    pub fn get_stash_diff(&self, index: usize, id: &str) -> Result<String> {
+        let mut repo = self.open_repo()?;
+        let stash_id = stash::stash_id(&mut repo, index, id)?;
+        let commit = repo.find_commit(stash_id)?;
+        let base = commit.parent(0)?.tree()?;
+        let mut diff_string =
+            format_patch(&repo.diff_tree_to_tree(Some(&base), Some(&commit.tree()?), None)?)?;
+        if let Ok(untracked) = commit.parent(2) {
+            let diff = repo.diff_tree_to_tree(None, Some(&untracked.tree()?), None)?;
+            diff_string.push_str(&format_patch(&diff)?);
+        }
+        Ok(diff_string)
+    }
+
+    /// Full unified diff of everything a stash would save: staged and
+    /// unstaged changes against `HEAD`, and untracked files when asked
+    pub fn get_worktree_diff_full(&self, include_untracked: bool) -> Result<String> {
+        let repo = self.open_work_tree("Reading local changes")?;
+        let head = repo.head()?.peel_to_tree()?;
+        let mut options = git2::DiffOptions::new();
+        options
+            .include_untracked(include_untracked)
+            .recurse_untracked_dirs(include_untracked)
+            .show_untracked_content(include_untracked);
+        let diff = repo.diff_tree_to_workdir_with_index(Some(&head), Some(&mut options))?;
+        format_patch(&diff)
+    }
+
+    /// Stash local changes, like `git stash push`, and return the new stash
+    ///
+    /// Without a `message` git's `WIP on <branch>` one is used.
+    pub fn create_stash(
+        &self,
+        message: Option<&str>,
+        include_untracked: bool,
+    ) -> Result<StashEntry> {
+        ensure_writable("Stashing changes")?;
+        let mut repo = self.open_work_tree("Stashing changes")?;
+        let signature = repo.signature()?;
+        let flags = if include_untracked {
+            git2::StashFlags::INCLUDE_UNTRACKED
+        } else {
+            git2::StashFlags::DEFAULT
+        };
+        match repo.stash_save2(&signature, message, Some(flags)) {
+            Ok(_) => {}
+            Err(e) if e.code() == git2::ErrorCode::NotFound => {
+                return Err(anyhow!("No local changes to stash"));
+            }
+            Err(e) => return Err(e.into()),
+        }
+        stash::list_stashes(&mut repo)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("The new stash is missing from the stash list"))
+    }
+
+    /// Apply `stash@{index}` to the working tree, keeping it in the list
+    ///
+    /// `id` must be the stash's full id, as listed; the index alone may point
+    /// at a different stash by now.
+    pub fn apply_stash(&self, index: usize, id: &str) -> Result<()> {
+        ensure_writable("Applying a stash")?;
+        let mut repo = self.open_work_tree("Applying a stash")?;
+        stash::stash_id(&mut repo, index, id)?;
+        repo.stash_apply(index, None)?;
+        Ok(())
+    }
+
+    /// Apply `stash@{index}` to the working tree and remove it from the list
+    pub fn pop_stash(&self, index: usize, id: &str) -> Result<()> {
+        ensure_writable("Popping a stash")?;
+        let mut repo = self.open_work_tree("Popping a stash")?;
+        stash::stash_id(&mut repo, index, id)?;
+        repo.stash_pop(index, None)?;
+        Ok(())
+    }
+
+    /// Delete `stash@{index}` without applying it
+    pub fn drop_stash(&self, index: usize, id: &str) -> Result<()> {
+        ensure_writable("Dropping a stash")?;
+        let mut repo = self.open_repo()?;
+        stash::stash_id(&mut repo, index, id)?;
+        repo.stash_drop(index)?;
+        Ok(())
+    }
+
+    /// Resolve a revision expression to a commit, with a readable reason
+    /// when it doesn't name one
+    pub fn resolve_revision(&self, spec: &str) -> Result<ResolvedRevision, String> {
+        let repo = self.open_repo().map_err(|e| e.message().to_string())?;
+        revision::resolve_revision(&repo, spec)
+    }
+
+    /// Full id of the `HEAD` commit
+    pub fn head_commit_id(&self) -> Result<String> {
+        let repo = self.open_repo()?;
+        Ok(repo.head()?.peel_to_commit()?.id().to_string())
+    }
+
+    /// Soft-reset the current branch to the parent of `HEAD`, leaving the
+    /// commit's changes staged. Returns the paths the commit changed.
+    ///
+    /// `expected` must be the full id of `HEAD`. Refuses when `HEAD` has moved
+    /// on, is a root or merge commit, or a remote-tracking branch already
+    /// contains it.
+    pub fn undo_commit(&self, expected: &str) -> Result<Vec<PathBuf>> {
+        ensure_writable("Undoing a commit")?;
+        let repo = self.open_repo()?;
+        let head = repo.head()?.peel_to_commit()?;
+        let short = &expected[..7.min(expected.len())];
+        if head.id().to_string() != expected {
+            return Err(anyhow!(
+                "HEAD has moved since {short} was committed; undo it with git instead"
+            ));
+        }
+        if head.parent_count() != 1 {
+            return Err(anyhow!("{short} is a root or merge commit"));
+        }
+
+        for reference in repo.references_glob("refs/remotes/*")? {
+            let reference = reference?;
+            let Some(remote) = reference.target() else {
+                continue; // symbolic refs like origin/HEAD
+            };
+            if remote == head.id() || repo.graph_descendant_of(remote, head.id())? {
+                return Err(anyhow!(
+                    "{short} is already on {}; undoing it would rewrite published history",
+                    reference.shorthand().unwrap_or("a remote")
+                ));
+            }
+        }
+
+        let parent = head.parent(0)?;
+        let diff = repo.diff_tree_to_tree(Some(&parent.tree()?), Some(&head.tree()?), None)?;
+        let paths = diff
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+            .map(Path::to_path_buf)
+            .collect();
+
+        repo.reset(parent.as_object(), git2::ResetType::Soft, None)?;
+        Ok(paths)
+    }
+
     /// Stage a file (add to index)
     pub fn stage_file(&self, path: &Path) -> Result<()> {
+        ensure_writable("Staging files")?;
         let repo = self.open_repo()?;
         let mut index = repo.index()?;
 
@@ -797,6 +1109,7 @@ impl GitRepo {
 
     /// Unstage a file (remove from index, keep working tree changes)
     pub fn unstage_file(&self, path: &Path) -> Result<()> {
+        ensure_writable("Unstaging files")?;
         let repo = self.open_repo()?;
 
         // Get HEAD tree to reset index entry
@@ -846,6 +1159,7 @@ impl GitRepo {
 
     /// Stage all modified/new/deleted files
     pub fn stage_all(&self) -> Result<()> {
+        ensure_writable("Staging files")?;
         let repo = self.open_repo()?;
         let mut index = repo.index()?;
         index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
@@ -855,6 +1169,7 @@ impl GitRepo {
 
     /// Unstage all files (reset index to HEAD)
     pub fn unstage_all(&self) -> Result<()> {
+        ensure_writable("Unstaging files")?;
         let repo = self.open_repo()?;
         let head = repo.head()?;
         let head_commit = head.peel_to_commit()?;
@@ -862,9 +1177,53 @@ impl GitRepo {
         Ok(())
     }
 
+    /// The staged changes to `files` as a patch, for [`Self::stage_patch`]
+    ///
+    /// Includes binary changes, so staging it again reproduces the index
+    /// entries exactly, partially staged files included.
+    pub fn staged_patch(&self, files: &[PathBuf]) -> Result<Vec<u8>> {
+        let repo = self.open_repo()?;
+        let head_tree = head_tree(&repo)?;
+        let mut options = git2::DiffOptions::new();
+        options.show_binary(true).disable_pathspec_match(true);
+        for file in files {
+            options.pathspec(file);
+        }
+        let diff = repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut options))?;
+
+        let mut patch = Vec::new();
+        diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
+            if matches!(line.origin(), ' ' | '+' | '-') {
+                patch.extend_from_slice(line.origin().encode_utf8(&mut [0; 4]).as_bytes());
+            }
+            patch.extend_from_slice(line.content());
+            true
+        })?;
+        Ok(patch)
+    }
+
+    /// Reset the index to `HEAD` and stage exactly `patch` on top of it
+    ///
+    /// Works before the first commit too, starting from an empty index.
+    pub fn stage_patch(&self, patch: &[u8]) -> Result<()> {
+        ensure_writable("Staging files")?;
+        let repo = self.open_repo()?;
+        let mut index = repo.index()?;
+        match head_tree(&repo)? {
+            Some(tree) => index.read_tree(&tree)?,
+            None => index.clear()?,
+        }
+        index.write()?;
+
+        let diff = git2::Diff::from_buffer(patch)?;
+        repo.apply(&diff, git2::ApplyLocation::Index, None)
+            .context("The changes no longer apply to the index")?;
+        Ok(())
+    }
+
     /// Get list of untracked files (new files not in the index)
     pub fn get_untracked_files(&self) -> Result<Vec<String>> {
-        let repo = self.open_repo()?;
+        let repo = self.open_work_tree("Listing untracked files")?;
         get_untracked_files(&repo)
     }
 
@@ -885,11 +1244,40 @@ impl GitRepo {
     }
 }
 
-impl Drop for GitRepo {
-    fn drop(&mut self) {
-        // The TempDir will be automatically cleaned up when dropped
-        if self.is_remote {
-            log_debug!("Cleaning up temporary repository at {:?}", self.repo_path);
+/// Format a diff as a unified patch with `diff --git` headers, for the
+/// Studio diff view
+fn format_patch(diff: &git2::Diff) -> Result<String> {
+    let mut diff_string = String::new();
+    diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
+        // For diff content lines (+/-/context), prefix with origin char
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            diff_string.push(line.origin());
+        }
+        // All line types get their content appended
+        diff_string.push_str(&String::from_utf8_lossy(line.content()));
+
+        if line.origin() == 'F'
+            && !diff_string.contains("diff --git")
+            && let Some(new_file) = delta.new_file().path()
+        {
+            let header = format!("diff --git a/{0} b/{0}\n", new_file.display());
+            if !diff_string.ends_with(&header) {
+                diff_string.insert_str(
+                    diff_string.rfind("---").unwrap_or(diff_string.len()),
+                    &header,
+                );
+            }
         }
+        true
+    })?;
+    Ok(diff_string)
+}
+
+/// `HEAD`'s tree, or `None` before the first commit
+fn head_tree(repo: &Repository) -> Result<Option<Tree<'_>>> {
+    match repo.head() {
+        Ok(head) => Ok(Some(head.peel_to_tree()?)),
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => Ok(None),
+        Err(e) => Err(e.into()),
     }
 }
@@ -0,0 +1,137 @@
+use git2::{ErrorCode, Repository};
+
+/// A revision expression resolved to a commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedRevision {
+    /// Full commit id
+    pub id: String,
+    /// Commit subject
+    pub summary: String,
+}
+
+impl ResolvedRevision {
+    /// Abbreviated commit id
+    pub fn short_id(&self) -> &str {
+        self.id.get(..7).unwrap_or(&self.id)
+    }
+}
+
+/// Resolve any expression `git rev-parse` accepts (`HEAD~5`, `v1.2.0^`,
+/// `@{u}`, a hash prefix) to a commit, or explain why it doesn't name one
+pub fn resolve_revision(repo: &Repository, spec: &str) -> Result<ResolvedRevision, String> {
+    let spec = spec.trim();
+    if spec.is_empty() {
+        return Err("Type a branch, tag, commit or expression like HEAD~3".to_string());
+    }
+    let object = repo
+        .revparse_single(spec)
+        .map_err(|e| describe_error(repo, spec, &e))?;
+    let commit = object
+        .peel_to_commit()
+        .map_err(|_| format!("{spec} is a {}, not a commit", kind_name(object.kind())))?;
+    Ok(ResolvedRevision {
+        id: commit.id().to_string(),
+        summary: commit.summary().unwrap_or_default().to_string(),
+    })
+}
+
+fn kind_name(kind: Option<git2::ObjectType>) -> &'static str {
+    match kind {
+        Some(git2::ObjectType::Tree) => "tree",
+        Some(git2::ObjectType::Blob) => "file",
+        Some(git2::ObjectType::Tag) => "tag",
+        _ => "object",
+    }
+}
+
+/// Readable reason `spec` didn't resolve
+fn describe_error(repo: &Repository, spec: &str, error: &git2::Error) -> String {
+    if error.code() == ErrorCode::Ambiguous {
+        return format!("{spec} matches more than one object; type more of the hash");
+    }
+    if spec.contains("@{u") || spec.contains("@{upstream}") || spec.contains("@{push}") {
+        return "The branch has no upstream; set one with git branch --set-upstream-to".to_string();
+    }
+    // Walking past the root commit: the part before the first ~ or ^ resolves
+    if let Some(at) = spec.find(['~', '^'])
+        && at > 0
+        && repo.revparse_single(&spec[..at]).is_ok()
+    {
+        return format!("{spec} goes back further than {}'s history", &spec[..at]);
+    }
+    if error.code() == ErrorCode::InvalidSpec {
+        return format!("{spec} isn't a valid revision");
+    }
+    format!("No branch, tag or commit named {spec}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(repo: &Repository, message: &str) -> git2::Oid {
+        let signature = git2::Signature::now("Ada", "ada@example.com").expect("signature");
+        let tree = repo
+            .find_tree(repo.index().expect("index").write_tree().expect("tree"))
+            .expect("tree");
+        let parents: Vec<git2::Commit> = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .expect("commit")
+    }
+
+    #[test]
+    fn test_resolve_revision() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let repo = Repository::init(dir.path()).expect("init");
+        let first = commit(&repo, "First");
+        commit(&repo, "Second");
+        let target = repo.find_object(first, None).expect("object");
+        repo.tag_lightweight("v1.0.0", &target, false).expect("tag");
+
+        let resolved = resolve_revision(&repo, "HEAD~1").expect("resolves");
+        assert_eq!(resolved.id, first.to_string());
+        assert_eq!(resolved.summary, "First");
+        let prefix = &first.to_string()[..8];
+        assert_eq!(
+            resolve_revision(&repo, prefix).map(|r| r.summary),
+            Ok("First".to_string())
+        );
+        assert!(resolve_revision(&repo, "v1.0.0").is_ok());
+
+        assert_eq!(
+            resolve_revision(&repo, "HEAD~5"),
+            Err("HEAD~5 goes back further than HEAD's history".to_string())
+        );
+        assert_eq!(
+            resolve_revision(&repo, "v1.0.0^"),
+            Err("v1.0.0^ goes back further than v1.0.0's history".to_string())
+        );
+        assert_eq!(
+            resolve_revision(&repo, "nope"),
+            Err("No branch, tag or commit named nope".to_string())
+        );
+        assert!(
+            resolve_revision(&repo, "@{u}")
+                .unwrap_err()
+                .contains("no upstream")
+        );
+        assert!(
+            resolve_revision(&repo, "HEAD^{tree}")
+                .unwrap_err()
+                .contains("tree")
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use git2::{Oid, Repository};
+
+/// One entry of `git stash list`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StashEntry {
+    /// Position in the stash list (`stash@{index}`)
+    pub index: usize,
+    /// Full id of the stash commit
+    pub id: String,
+    /// Stash message, such as `WIP on main: 1a2b3c4 Fix login` or `On main: half-done parser`
+    pub message: String,
+    pub time: DateTime<Utc>,
+    /// Whether the stash also saved untracked files
+    pub has_untracked: bool,
+}
+
+impl StashEntry {
+    /// `stash@{n}` selector for this entry
+    pub fn selector(&self) -> String {
+        format!("stash@{{{}}}", self.index)
+    }
+
+    pub fn short_id(&self) -> &str {
+        &self.id[..7.min(self.id.len())]
+    }
+
+    /// Branch the stash was made on, as recorded in its message
+    pub fn branch(&self) -> Option<&str> {
+        let rest = self
+            .message
+            .strip_prefix("WIP on ")
+            .or_else(|| self.message.strip_prefix("On "))?;
+        rest.split_once(": ").map(|(branch, _)| branch)
+    }
+
+    /// Message without the `On <branch>: ` prefix git adds
+    pub fn description(&self) -> &str {
+        match self.branch() {
+            Some(_) => self
+                .message
+                .split_once(": ")
+                .map_or(self.message.as_str(), |(_, rest)| rest),
+            None => &self.message,
+        }
+    }
+}
+
+/// Read the stash list, newest first
+pub fn list_stashes(repo: &mut Repository) -> Result<Vec<StashEntry>> {
+    let mut stashes = Vec::new();
+    repo.stash_foreach(|index, message, id| {
+        stashes.push((index, message.to_string(), *id));
+        true
+    })?;
+
+    stashes
+        .into_iter()
+        .map(|(index, message, id)| {
+            let commit = repo.find_commit(id)?;
+            Ok(StashEntry {
+                index,
+                id: id.to_string(),
+                message,
+                time: DateTime::from_timestamp(commit.time().seconds(), 0).unwrap_or_default(),
+                // Untracked files are saved as a third parent
+                has_untracked: commit.parent_count() > 2,
+            })
+        })
+        .collect()
+}
+
+/// Id of `stash@{index}`, checked against the id the caller listed
+///
+/// Stash indexes shift whenever a stash is created or dropped, so acting on
+/// a stale index could touch the wrong stash.
+pub fn stash_id(repo: &mut Repository, index: usize, expected: &str) -> Result<Oid> {
+    let mut found = None;
+    repo.stash_foreach(|i, _, id| {
+        if i == index {
+            found = Some(*id);
+        }
+        i < index
+    })?;
+    match found {
+        Some(id) if id.to_string() == expected => Ok(id),
+        _ => Err(anyhow!(
+            "The stash list changed since it was loaded; refresh and try again"
+        )),
+    }
+}
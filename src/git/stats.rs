@@ -0,0 +1,192 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+use super::log_record::parse_log;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Aggregate history of one file over the stats window
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileStats {
+    /// Commits that touched the file
+    pub commits: usize,
+    pub additions: usize,
+    pub deletions: usize,
+    /// Revert commits that touched the file
+    pub reverts: usize,
+    /// Commits per author
+    pub authors: HashMap<String, usize>,
+    /// Timestamp of the newest commit that touched the file
+    pub last_changed: i64,
+}
+
+impl FileStats {
+    /// Lines added and deleted
+    pub fn churn(&self) -> usize {
+        self.additions + self.deletions
+    }
+
+    /// Authors with the most commits first, ties broken by name
+    pub fn top_authors(&self, limit: usize) -> Vec<(&str, usize)> {
+        let mut authors: Vec<(&str, usize)> = self
+            .authors
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect();
+        authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        authors.truncate(limit);
+        authors
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.commits += other.commits;
+        self.additions += other.additions;
+        self.deletions += other.deletions;
+        self.reverts += other.reverts;
+        for (author, count) in &other.authors {
+            *self.authors.entry(author.clone()).or_default() += count;
+        }
+        self.last_changed = self.last_changed.max(other.last_changed);
+    }
+}
+
+/// Commit graph statistics over a window of recent days
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryStats {
+    pub window_days: u32,
+    /// Non-merge commits in the window
+    pub total_commits: usize,
+    /// Commits per week, oldest first
+    pub weekly_commits: Vec<usize>,
+    /// Commits that revert an earlier one
+    pub reverts: usize,
+    /// Per-file history, keyed by path
+    pub files: HashMap<String, FileStats>,
+}
+
+impl HistoryStats {
+    /// Aggregate output of `git log --format=%x1e%an%x1f%at%x1f%s --numstat`
+    ///
+    /// Commits older than `window_days` before `now` are ignored, and binary
+    /// files count as a change with no lines.
+    pub fn from_git_log(output: &str, window_days: u32, now: DateTime<Utc>) -> Self {
+        let days = i64::from(window_days.max(1));
+        let weeks = usize::try_from((days + 6) / 7).unwrap_or(1);
+        let mut stats = Self {
+            window_days,
+            weekly_commits: vec![0; weeks],
+            ..Self::default()
+        };
+
+        for record in parse_log(output) {
+            let Some(timestamp) = record.timestamp else {
+                continue;
+            };
+            // Future-dated commits (clock skew) count as today
+            let age = ((now.timestamp() - timestamp) / SECONDS_PER_DAY).max(0);
+            if age >= days {
+                continue;
+            }
+            let week = usize::try_from(age / 7).unwrap_or(0);
+            stats.weekly_commits[weeks - 1 - week] += 1;
+            stats.total_commits += 1;
+            let is_revert = record.subject.starts_with("Revert \"");
+            if is_revert {
+                stats.reverts += 1;
+            }
+
+            for changed in &record.files {
+                let file = stats.files.entry(changed.path.to_string()).or_default();
+                file.commits += 1;
+                file.additions += changed.additions;
+                file.deletions += changed.deletions;
+                if is_revert {
+                    file.reverts += 1;
+                }
+                *file.authors.entry(record.author.to_string()).or_default() += 1;
+                file.last_changed = file.last_changed.max(timestamp);
+            }
+        }
+        stats
+    }
+
+    /// Files with the most lines changed, ties broken by path
+    pub fn top_churn(&self, limit: usize) -> Vec<(&str, &FileStats)> {
+        self.ranked(limit, FileStats::churn)
+    }
+
+    /// Files touched by the most reverts, ties broken by path
+    pub fn most_reverted(&self, limit: usize) -> Vec<(&str, &FileStats)> {
+        self.ranked(limit, |file| file.reverts)
+    }
+
+    fn ranked(&self, limit: usize, key: impl Fn(&FileStats) -> usize) -> Vec<(&str, &FileStats)> {
+        let mut files: Vec<(&str, &FileStats)> = self
+            .files
+            .iter()
+            .filter(|(_, file)| key(file) > 0)
+            .map(|(path, file)| (path.as_str(), file))
+            .collect();
+        files.sort_by(|a, b| key(b.1).cmp(&key(a.1)).then_with(|| a.0.cmp(b.0)));
+        files.truncate(limit);
+        files
+    }
+
+    /// History of a file, or of every file under a directory
+    ///
+    /// Commits that touched several files under a directory count once per
+    /// file, so `commits` is an upper bound for directories.
+    pub fn for_path(&self, path: &str) -> Option<FileStats> {
+        let path = path.trim_start_matches("./").trim_end_matches('/');
+        let prefix = format!("{path}/");
+        let mut total: Option<FileStats> = None;
+        for (file_path, file) in &self.files {
+            if path.is_empty() || file_path == path || file_path.starts_with(&prefix) {
+                total.get_or_insert_with(FileStats::default).merge(file);
+            }
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_git_log() {
+        let now = DateTime::from_timestamp(100 * SECONDS_PER_DAY, 0).expect("timestamp");
+        let day = |d: i64| (100 - d) * SECONDS_PER_DAY;
+        let output = format!(
+            "\x1eAda\x1f{}\x1fAdd retry\n\n10\t2\tsrc/net.rs\n3\t0\tREADME.md\n\
+             \x1eBob\x1f{}\x1fRevert \"Add retry\"\n\n2\t10\tsrc/net.rs\n\
+             \x1eAda\x1f{}\x1fTune retry\n\n1\t1\tsrc/net.rs\n-\t-\tlogo.png\n\
+             \x1eAda\x1f{}\x1fToo old\n\n5\t5\tsrc/old.rs\n",
+            day(20),
+            day(3),
+            day(0),
+            day(40)
+        );
+        let stats = HistoryStats::from_git_log(&output, 30, now);
+
+        assert_eq!(stats.total_commits, 3);
+        assert_eq!(stats.weekly_commits, [0, 0, 1, 0, 2]);
+        assert_eq!(stats.reverts, 1);
+        assert!(!stats.files.contains_key("src/old.rs"));
+
+        let net = &stats.files["src/net.rs"];
+        assert_eq!((net.commits, net.churn(), net.reverts), (3, 26, 1));
+        assert_eq!(net.top_authors(5), [("Ada", 2), ("Bob", 1)]);
+        assert_eq!(net.last_changed, day(0));
+        assert_eq!(stats.files["logo.png"].churn(), 0);
+
+        let top: Vec<&str> = stats.top_churn(2).iter().map(|(p, _)| *p).collect();
+        assert_eq!(top, ["src/net.rs", "README.md"]);
+        assert_eq!(stats.most_reverted(5).len(), 1);
+
+        let src = stats.for_path("src/").expect("src has history");
+        assert_eq!(src.commits, 3);
+        assert!(stats.for_path("sr").is_none());
+        assert_eq!(stats.for_path("").expect("everything").commits, 5);
+    }
+}
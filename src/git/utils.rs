@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use regex::Regex;
-use std::path::Path;
-use std::process::{Command, Stdio};
+use std::path::PathBuf;
+use std::process::Stdio;
 
 use crate::log_debug;
 
@@ -11,7 +11,7 @@ use crate::log_debug;
 ///
 /// A Result containing a boolean indicating if inside a work tree or an error.
 pub fn is_inside_work_tree() -> Result<bool> {
-    let status = Command::new("git")
+    let status = crate::git::git_command()
         .args(["rev-parse", "--is-inside-work-tree"])
         .stdout(Stdio::null())
         .stderr(Stdio::null())
@@ -40,7 +40,7 @@ pub fn is_binary_diff(diff: &str) -> bool {
 ///
 /// A Result containing the output as a String or an error.
 pub fn run_git_command(args: &[&str]) -> Result<String> {
-    let output = Command::new("git")
+    let output = crate::git::git_command()
         .args(args)
         .output()
         .context("Failed to execute git command")?;
@@ -58,6 +58,16 @@ pub fn run_git_command(args: &[&str]) -> Result<String> {
     Ok(stdout.trim().to_string())
 }
 
+/// Normalizes a repository-relative path to forward slashes.
+///
+/// Git reports `/`-separated paths, but paths coming from the filesystem on
+/// Windows use backslashes. Normalizing keeps status lookups and file trees
+/// consistent across platforms.
+pub fn normalize_path(path: impl AsRef<str>) -> PathBuf {
+    let path = path.as_ref().replace('\\', "/");
+    PathBuf::from(path.strip_prefix("./").unwrap_or(&path))
+}
+
 /// Checks if a file should be excluded from analysis.
 ///
 /// Excludes common directories and files that don't contribute meaningfully
@@ -85,7 +95,8 @@ pub fn should_exclude_file(path: &str) -> bool {
         (String::from(r"\.min\.js$"), true),
     ];
 
-    let path = Path::new(path);
+    // Patterns are written with `/` separators
+    let path = normalize_path(path);
 
     for (pattern, is_extension) in exclude_patterns {
         let re = match Regex::new(&pattern) {
@@ -0,0 +1,135 @@
+//! Project vocabulary for prompts
+//!
+//! Teams have their own names for things: abbreviations, subsystem names,
+//! terms that mean something specific in this codebase. A repository can
+//! define them in `.git-iris/glossary.toml`:
+//!
+//! ```toml
+//! [terms]
+//! CAS = "Content-addressed store: the blob cache under src/cas/"
+//! "hot path" = "Request handling in src/server/ that must not allocate"
+//! ```
+//!
+//! Entries whose term appears in the changes are added to the prompt, so
+//! generated text uses the team's terminology correctly without sending the
+//! whole glossary every time.
+
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Glossary file, relative to the repository root
+pub const GLOSSARY_FILE: &str = ".git-iris/glossary.toml";
+
+/// Most entries added to one prompt
+const MAX_PROMPT_TERMS: usize = 40;
+
+/// Project terms and their definitions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Glossary {
+    #[serde(default)]
+    pub terms: BTreeMap<String, String>,
+}
+
+impl Glossary {
+    /// Read the repository glossary; `None` if the repository has none
+    pub fn load(repo_root: &Path) -> Result<Option<Self>> {
+        let path = repo_root.join(GLOSSARY_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let glossary = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(glossary))
+    }
+
+    /// Entries whose term appears in `text`, in glossary order
+    ///
+    /// Terms match whole words. All-caps terms (abbreviations) match case
+    /// sensitively, so `CAS` doesn't match `cas`; others ignore case.
+    pub fn relevant(&self, text: &str) -> Vec<(&str, &str)> {
+        self.terms
+            .iter()
+            .filter(|(term, definition)| !term.trim().is_empty() && !definition.trim().is_empty())
+            .filter(|(term, _)| term_pattern(term).is_some_and(|re| re.is_match(text)))
+            .map(|(term, definition)| (term.as_str(), definition.as_str()))
+            .take(MAX_PROMPT_TERMS)
+            .collect()
+    }
+
+    /// Prompt section with the entries relevant to `changes`, or nothing
+    pub fn prompt(&self, changes: &str) -> String {
+        let entries = self.relevant(changes);
+        if entries.is_empty() {
+            return String::new();
+        }
+        let mut prompt = String::from(
+            "\n\n## Project Glossary\n\nThese terms appear in the changes and have a \
+             specific meaning in this project. Use them as defined; don't expand \
+             abbreviations differently or rename the concepts.\n\n",
+        );
+        for (term, definition) in entries {
+            prompt.push_str(&format!("- **{}**: {}\n", term.trim(), definition.trim()));
+        }
+        prompt
+    }
+}
+
+/// Whole-word pattern for a glossary term
+fn term_pattern(term: &str) -> Option<Regex> {
+    let term = term.trim();
+    let abbreviation =
+        term.chars().any(char::is_uppercase) && !term.chars().any(char::is_lowercase);
+    let flags = if abbreviation { "" } else { "(?i)" };
+    Regex::new(&format!(r"{flags}\b{}\b", regex::escape(term))).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relevant_terms_match_whole_words() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join(".git-iris")).expect("mkdir");
+        std::fs::write(
+            dir.path().join(GLOSSARY_FILE),
+            "[terms]\n\
+             CAS = \"Content-addressed store\"\n\
+             \"hot path\" = \"Request handling that must not allocate\"\n\
+             Shard = \"A partition of the index\"\n\
+             empty = \"\"\n",
+        )
+        .expect("write");
+        let glossary = Glossary::load(dir.path()).expect("parses").expect("exists");
+
+        let diff = "+// Keep the Hot Path free of allocations\n+fn cast(cas: &Cas) {}\n";
+        assert_eq!(
+            glossary.relevant(diff),
+            [("hot path", "Request handling that must not allocate")]
+        );
+        let diff = "+    let blob = CAS::get(id); // shards are rebuilt nightly\n";
+        assert_eq!(
+            glossary
+                .relevant(diff)
+                .iter()
+                .map(|(t, _)| *t)
+                .collect::<Vec<_>>(),
+            ["CAS"]
+        );
+
+        let prompt = glossary.prompt("+ one shard per CAS bucket");
+        assert!(prompt.contains("## Project Glossary"));
+        assert!(prompt.contains("- **CAS**: Content-addressed store\n- **Shard**: A partition"));
+        assert!(glossary.prompt("+ unrelated").is_empty());
+        assert_eq!(
+            Glossary::load(&dir.path().join("nowhere")).expect("ok"),
+            None
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! Git hooks that run git-iris
+//!
+//! `git-iris hooks install` writes a `prepare-commit-msg` hook that fills in
+//! a generated message when `git commit` opens the editor, and optionally a
+//! `commit-msg` hook that checks the final message against `[commit_style]`.
+//! A hook already in place is moved aside to `<hook>.pre-iris` and called
+//! first, so installing wraps existing hooks instead of replacing them;
+//! uninstalling moves it back.
+
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Marker line identifying hooks git-iris wrote
+const MARKER: &str = "# Installed by `git-iris hooks install`";
+
+/// Suffix for a hook that was in place before git-iris wrapped it
+const WRAPPED_SUFFIX: &str = "pre-iris";
+
+/// A hook git-iris can install
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    /// Pre-fill the message `git commit` opens in the editor
+    PrepareCommitMsg,
+    /// Reject messages that break the configured commit style
+    CommitMsg,
+}
+
+impl HookKind {
+    /// Hook file name under the hooks directory
+    pub const fn file_name(self) -> &'static str {
+        match self {
+            Self::PrepareCommitMsg => "prepare-commit-msg",
+            Self::CommitMsg => "commit-msg",
+        }
+    }
+
+    /// Shell script for the hook
+    fn script(self) -> String {
+        let body = match self {
+            // $2 is empty for a plain `git commit` and "template" when
+            // commit.template is set; -m, -F, merges, squashes and amends
+            // already have a message worth keeping
+            Self::PrepareCommitMsg => {
+                r#"case "$2" in
+  ""|template) ;;
+  *) exit 0 ;;
+esac
+
+message=$(git-iris plumbing message 2>/dev/null) || exit 0
+[ -n "$message" ] || exit 0
+
+# Keep what git put in the file (the status comments) below the message
+{ printf '%s\n' "$message"; cat "$1"; } > "$1.iris" && mv "$1.iris" "$1"
+"#
+            }
+            Self::CommitMsg => "exec git-iris plumbing check-message \"$1\"\n",
+        };
+        format!(
+            "#!/bin/sh\n\
+             {MARKER}\n\
+             # Remove with `git-iris hooks install --uninstall`. Set GIT_IRIS_SKIP_HOOKS=1\n\
+             # to skip it for one commit.\n\n\
+             wrapped=\"$0.{WRAPPED_SUFFIX}\"\n\
+             if [ -x \"$wrapped\" ]; then\n  \"$wrapped\" \"$@\" || exit $?\n\
+             fi\n\n\
+             [ -z \"$GIT_IRIS_SKIP_HOOKS\" ] || exit 0\n\
+             command -v git-iris >/dev/null 2>&1 || exit 0\n\n\
+             {body}"
+        )
+    }
+}
+
+/// What installing a hook did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Installed {
+    /// No hook was there
+    Fresh,
+    /// An earlier git-iris hook was rewritten
+    Updated,
+    /// Another hook was moved to this path and is called first
+    Wrapped(PathBuf),
+}
+
+/// What uninstalling a hook did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Uninstalled {
+    /// The git-iris hook was removed
+    Removed,
+    /// The git-iris hook was removed and the wrapped hook put back
+    Restored,
+    /// The hook there isn't one git-iris wrote, so it was left alone
+    NotOurs,
+    /// No hook was installed
+    Missing,
+}
+
+/// Directory git runs hooks from, honoring `core.hooksPath`
+pub fn hooks_dir(repo: &git2::Repository) -> Result<PathBuf> {
+    let configured = repo
+        .config()
+        .ok()
+        .and_then(|config| config.get_path("core.hooksPath").ok());
+    Ok(match configured {
+        Some(path) if path.is_absolute() => path,
+        // Relative hooksPath is relative to the working tree
+        Some(path) => repo
+            .workdir()
+            .context("core.hooksPath is relative but the repository has no working tree")?
+            .join(path),
+        None => repo.path().join("hooks"),
+    })
+}
+
+fn is_ours(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| content.contains(MARKER))
+}
+
+fn wrapped_path(hook: &Path) -> PathBuf {
+    let mut name = hook.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{WRAPPED_SUFFIX}"));
+    hook.with_file_name(name)
+}
+
+/// Install a hook into `dir`, wrapping any hook already there
+pub fn install(dir: &Path, kind: HookKind) -> Result<Installed> {
+    crate::read_only::ensure_writable("Installing hooks")?;
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create hooks directory: {}", dir.display()))?;
+    let hook = dir.join(kind.file_name());
+
+    let installed = if is_ours(&hook) {
+        Installed::Updated
+    } else if hook.exists() {
+        let wrapped = wrapped_path(&hook);
+        if wrapped.exists() {
+            bail!(
+                "{} exists but wasn't written by git-iris, and {} is already taken",
+                hook.display(),
+                wrapped.display()
+            );
+        }
+        fs::rename(&hook, &wrapped)
+            .with_context(|| format!("Failed to move {} aside", hook.display()))?;
+        Installed::Wrapped(wrapped)
+    } else {
+        Installed::Fresh
+    };
+
+    fs::write(&hook, kind.script())
+        .with_context(|| format!("Failed to write {}", hook.display()))?;
+    make_executable(&hook)?;
+    Ok(installed)
+}
+
+/// Remove a git-iris hook from `dir`, restoring the hook it wrapped
+pub fn uninstall(dir: &Path, kind: HookKind) -> Result<Uninstalled> {
+    crate::read_only::ensure_writable("Removing hooks")?;
+    let hook = dir.join(kind.file_name());
+    if !hook.exists() {
+        return Ok(Uninstalled::Missing);
+    }
+    if !is_ours(&hook) {
+        return Ok(Uninstalled::NotOurs);
+    }
+
+    fs::remove_file(&hook).with_context(|| format!("Failed to remove {}", hook.display()))?;
+    let wrapped = wrapped_path(&hook);
+    if wrapped.exists() {
+        fs::rename(&wrapped, &hook)
+            .with_context(|| format!("Failed to restore {}", wrapped.display()))?;
+        return Ok(Uninstalled::Restored);
+    }
+    Ok(Uninstalled::Removed)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(0o755);
+    fs::set_permissions(path, permissions)
+        .with_context(|| format!("Failed to make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// The message part of a commit message file: everything but `#` comments
+/// and the diff `git commit --verbose` appends
+pub fn message_from_file(content: &str) -> String {
+    const SCISSORS: &str = "# ------------------------ >8 ------------------------";
+    content
+        .lines()
+        .take_while(|line| *line != SCISSORS)
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_wraps_and_uninstall_restores() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let hooks = dir.path().join("hooks");
+        let existing = "#!/bin/sh\necho lint\n";
+        fs::create_dir_all(&hooks).expect("mkdir");
+        fs::write(hooks.join("prepare-commit-msg"), existing).expect("write");
+
+        let installed = install(&hooks, HookKind::PrepareCommitMsg).expect("install");
+        assert_eq!(
+            installed,
+            Installed::Wrapped(hooks.join("prepare-commit-msg.pre-iris"))
+        );
+        let script = fs::read_to_string(hooks.join("prepare-commit-msg")).expect("read");
+        assert!(script.contains(MARKER));
+        assert!(script.contains("git-iris plumbing message"));
+        assert_eq!(
+            install(&hooks, HookKind::PrepareCommitMsg).expect("reinstall"),
+            Installed::Updated
+        );
+        assert_eq!(
+            install(&hooks, HookKind::CommitMsg).expect("install"),
+            Installed::Fresh
+        );
+
+        assert_eq!(
+            uninstall(&hooks, HookKind::PrepareCommitMsg).expect("uninstall"),
+            Uninstalled::Restored
+        );
+        assert_eq!(
+            fs::read_to_string(hooks.join("prepare-commit-msg")).expect("read"),
+            existing
+        );
+        assert_eq!(
+            uninstall(&hooks, HookKind::PrepareCommitMsg).expect("uninstall"),
+            Uninstalled::NotOurs
+        );
+        assert_eq!(
+            uninstall(&hooks, HookKind::CommitMsg).expect("uninstall"),
+            Uninstalled::Removed
+        );
+        assert_eq!(
+            uninstall(&hooks, HookKind::CommitMsg).expect("uninstall"),
+            Uninstalled::Missing
+        );
+    }
+
+    #[test]
+    fn test_message_from_file_drops_comments_and_diff() {
+        let content = "Add retry logic\n\nBody line\n# Please enter the commit message\n\
+                       # ------------------------ >8 ------------------------\n\
+                       diff --git a/x b/x\n";
+        assert_eq!(message_from_file(content), "Add retry logic\n\nBody line");
+    }
+}
@@ -1,5 +1,17 @@
+use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::git::GitRepo;
+use crate::log_debug;
+
+/// Directory (relative to the repository root) holding a repository's presets
+pub const PRESETS_DIR: &str = ".git-iris/presets";
+
+/// Emoji for custom presets that don't set one
+const DEFAULT_CUSTOM_EMOJI: &str = "✨";
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct InstructionPreset {
@@ -10,17 +22,50 @@ pub struct InstructionPreset {
     pub preset_type: PresetType, // New field to distinguish between commit and review presets
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Copy, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Copy, Default, clap::ValueEnum)]
 pub enum PresetType {
+    #[serde(alias = "commit")]
     Commit,
+    #[serde(alias = "review")]
     Review,
     #[default]
+    #[serde(alias = "both")]
     Both,
 }
 
+impl PresetType {
+    /// Lowercase name, as preset files spell it
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Commit => "commit",
+            Self::Review => "review",
+            Self::Both => "both",
+        }
+    }
+}
+
+/// A preset file: `<key>.toml` in a presets directory
+#[derive(Debug, Deserialize)]
+struct PresetFile {
+    /// Display name; the file name when missing
+    name: Option<String>,
+    #[serde(default)]
+    description: String,
+    instructions: String,
+    emoji: Option<String>,
+    #[serde(default, rename = "type")]
+    preset_type: PresetType,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct InstructionPresetLibrary {
     presets: HashMap<String, InstructionPreset>,
+    /// Files custom presets were loaded from, by key
+    #[serde(skip)]
+    sources: HashMap<String, PathBuf>,
+    /// Preset files that couldn't be loaded, with the reason
+    #[serde(skip)]
+    load_errors: Vec<(PathBuf, String)>,
 }
 
 impl Default for InstructionPresetLibrary {
@@ -375,7 +420,72 @@ impl InstructionPresetLibrary {
             },
         );
 
-        Self { presets }
+        presets.insert(
+            "dependencies".to_string(),
+            InstructionPreset {
+                name: "Dependency Updates".to_string(),
+                description: "Summarize dependency bumps from Dependabot or Renovate".to_string(),
+                instructions: "Treat the changes as a dependency update. Summarize each version jump and what it brings, read the changelogs of major bumps, and call out known breaking changes, deprecations, and security fixes. Note any code in the branch that adapts to the new versions and anything callers may still need to change.".to_string(),
+                emoji: "📦".to_string(),
+                preset_type: PresetType::Review,
+            },
+        );
+
+        Self {
+            presets,
+            sources: HashMap::new(),
+            load_errors: Vec::new(),
+        }
+    }
+
+    /// Built-in presets plus the custom presets in `dirs`, later directories
+    /// overriding earlier ones and custom presets overriding built-ins with
+    /// the same key
+    pub fn with_custom_presets(dirs: &[PathBuf]) -> Self {
+        let mut library = Self::new();
+        for dir in dirs {
+            library.load_dir(dir);
+        }
+        library
+    }
+
+    /// Load every `*.toml` file in `dir`, skipping (and recording) bad ones
+    fn load_dir(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(std::result::Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let Some(key) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let key = key.to_string();
+            match load_preset_file(&path, &key) {
+                Ok(preset) => {
+                    self.presets.insert(key.clone(), preset);
+                    self.sources.insert(key, path);
+                }
+                Err(e) => {
+                    log_debug!("Skipping preset {}: {:#}", path.display(), e);
+                    self.load_errors.push((path, format!("{e:#}")));
+                }
+            }
+        }
+    }
+
+    /// File a custom preset was loaded from; `None` for built-ins
+    pub fn preset_path(&self, key: &str) -> Option<&Path> {
+        self.sources.get(key).map(PathBuf::as_path)
+    }
+
+    /// Preset files that were skipped, with why
+    pub fn load_errors(&self) -> &[(PathBuf, String)] {
+        &self.load_errors
     }
 
     pub fn get_preset(&self, key: &str) -> Option<&InstructionPreset> {
@@ -413,8 +523,116 @@ impl InstructionPresetLibrary {
     }
 }
 
+/// Built-in presets merged with the user's and the current repository's
 pub fn get_instruction_preset_library() -> InstructionPresetLibrary {
-    InstructionPresetLibrary::new()
+    let repo_root = GitRepo::get_repo_root().ok();
+    InstructionPresetLibrary::with_custom_presets(&preset_dirs(repo_root.as_deref()))
+}
+
+/// Directories custom presets are loaded from, lowest precedence first:
+/// `~/.config/git-iris/presets/`, `$XDG_CONFIG_HOME/git-iris/presets/` (if
+/// different), then `.git-iris/presets/` in the repository
+pub fn preset_dirs(repo_root: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = user_presets_dir() {
+        dirs.push(dir);
+    }
+    if let Some(xdg_config) = dirs::config_dir() {
+        let xdg_path = xdg_config.join("git-iris/presets");
+        if !dirs.contains(&xdg_path) {
+            dirs.push(xdg_path);
+        }
+    }
+    if let Some(root) = repo_root {
+        dirs.push(root.join(PRESETS_DIR));
+    }
+    dirs
+}
+
+/// Directory `git-iris preset new --user` writes to
+pub fn user_presets_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config/git-iris/presets"))
+}
+
+fn load_preset_file(path: &Path, key: &str) -> Result<InstructionPreset> {
+    let content = fs::read_to_string(path)?;
+    let file: PresetFile = toml::from_str(&content).context("invalid preset file")?;
+    if file.instructions.trim().is_empty() {
+        bail!("`instructions` is empty");
+    }
+    Ok(InstructionPreset {
+        name: file.name.unwrap_or_else(|| key.to_string()),
+        description: file.description,
+        instructions: file.instructions.trim().to_string(),
+        emoji: file
+            .emoji
+            .unwrap_or_else(|| DEFAULT_CUSTOM_EMOJI.to_string()),
+        preset_type: file.preset_type,
+    })
+}
+
+/// Whether `key` can name a preset file
+pub fn is_valid_preset_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Contents of a new preset file, starting from `base` when given
+pub fn preset_file_template(
+    key: &str,
+    preset_type: PresetType,
+    base: Option<&InstructionPreset>,
+) -> String {
+    let (name, description, emoji, instructions) = match base {
+        Some(base) => (
+            base.name.clone(),
+            base.description.clone(),
+            base.emoji.clone(),
+            base.instructions.clone(),
+        ),
+        None => (
+            key.to_string(),
+            "What this style is for".to_string(),
+            DEFAULT_CUSTOM_EMOJI.to_string(),
+            "Describe how Iris should write: tone, structure, what to include and what to leave out."
+                .to_string(),
+        ),
+    };
+    let preset_type = base.map_or(preset_type, |base| base.preset_type);
+    format!(
+        "# git-iris instruction preset, used with --preset {key}\n\
+         name = {}\n\
+         description = {}\n\
+         emoji = {}\n\
+         # commit, review, or both\n\
+         type = \"{}\"\n\
+         instructions = \"\"\"\n{}\n\"\"\"\n",
+        toml_string(&name),
+        toml_string(&description),
+        toml_string(&emoji),
+        preset_type.as_str(),
+        instructions.trim().replace("\"\"\"", "\"\"\\\""),
+    )
+}
+
+/// Write `preset` to `path` as a preset file, creating its directory
+pub fn write_preset_file(path: &Path, key: &str, preset: &InstructionPreset) -> Result<()> {
+    crate::read_only::ensure_writable("Saving the preset")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(
+        path,
+        preset_file_template(key, preset.preset_type, Some(preset)),
+    )
+    .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// A basic TOML string
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
 }
 
 pub fn list_presets_formatted(library: &InstructionPresetLibrary) -> String {
@@ -444,8 +662,13 @@ pub fn list_presets_formatted_by_type(
                 PresetType::Review => "[R]",
                 PresetType::Both => "[B]",
             };
+            let custom = if library.preset_path(key).is_some() {
+                " [custom]"
+            } else {
+                ""
+            };
             format!(
-                "{} {} - {} - {} - {}",
+                "{} {} - {} - {} - {}{custom}",
                 type_indicator, key, preset.emoji, preset.name, preset.description
             )
         })
@@ -484,3 +707,57 @@ pub fn list_valid_presets_for_command_formatted(
         .collect::<Vec<String>>()
         .join("\n")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_presets_override_in_order() {
+        let root = tempfile::TempDir::new().expect("temp dir");
+        let user = root.path().join("user");
+        let repo = root.path().join("repo");
+        fs::create_dir_all(&user).expect("user dir");
+        fs::create_dir_all(&repo).expect("repo dir");
+        fs::write(
+            user.join("team.toml"),
+            "name = \"Team\"\ntype = \"commit\"\ninstructions = \"Use ticket prefixes.\"\n",
+        )
+        .expect("write");
+        fs::write(
+            repo.join("team.toml"),
+            preset_file_template("team", PresetType::Review, None),
+        )
+        .expect("write");
+        fs::write(repo.join("broken.toml"), "name = \"No instructions\"\n").expect("write");
+
+        let library = InstructionPresetLibrary::with_custom_presets(std::slice::from_ref(&user));
+        let team = library.get_preset("team").expect("user preset");
+        assert_eq!(team.preset_type, PresetType::Commit);
+        assert_eq!(team.emoji, DEFAULT_CUSTOM_EMOJI);
+
+        let library = InstructionPresetLibrary::with_custom_presets(&[user, repo.clone()]);
+        let team = library.get_preset("team").expect("repo preset");
+        assert_eq!(team.name, "team");
+        assert_eq!(team.preset_type, PresetType::Review);
+        assert_eq!(
+            library.preset_path("team"),
+            Some(repo.join("team.toml").as_path())
+        );
+        assert_eq!(library.load_errors().len(), 1);
+        assert!(library.get_preset("conventional").is_some());
+        assert_eq!(library.preset_path("conventional"), None);
+
+        let copied =
+            preset_file_template("mine", PresetType::Both, library.get_preset("conventional"));
+        let parsed: PresetFile = toml::from_str(&copied).expect("template parses");
+        assert_eq!(
+            parsed.instructions.trim(),
+            library
+                .get_preset("conventional")
+                .expect("built-in")
+                .instructions
+        );
+        assert!(!is_valid_preset_key("../team"));
+    }
+}
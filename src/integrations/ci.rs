@@ -0,0 +1,165 @@
+//! CI check status of a commit
+//!
+//! PR mode shows whether the checks on the branch's pushed head are pending,
+//! failing or passing before a pull request is opened. GitHub reports both
+//! check runs and commit statuses; GitLab reports pipeline job statuses.
+
+use anyhow::{Result, bail};
+
+use super::forge::Forge;
+use super::github::{self, GitHubConfig};
+use super::gitlab::{self, GitLabConfig};
+
+/// Where a single check stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckState {
+    Failing,
+    Pending,
+    Passing,
+}
+
+impl CheckState {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Failing => "failing",
+            Self::Pending => "pending",
+            Self::Passing => "passing",
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            Self::Failing => "✗",
+            Self::Pending => "●",
+            Self::Passing => "✓",
+        }
+    }
+}
+
+/// A CI check or job on a commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: String,
+    pub state: CheckState,
+    /// Page with the check's logs
+    pub url: Option<String>,
+}
+
+/// Checks reported for one commit, failing first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiStatus {
+    /// Full id of the commit the checks ran on
+    pub commit: String,
+    pub checks: Vec<Check>,
+}
+
+impl CiStatus {
+    pub fn new(commit: impl Into<String>, mut checks: Vec<Check>) -> Self {
+        checks.sort_by(|a, b| a.state.cmp(&b.state).then_with(|| a.name.cmp(&b.name)));
+        Self {
+            commit: commit.into(),
+            checks,
+        }
+    }
+
+    /// Overall state: failing if any check fails, pending if any is still
+    /// running, `None` when no checks were reported
+    pub fn state(&self) -> Option<CheckState> {
+        self.checks.iter().map(|c| c.state).min()
+    }
+
+    /// Checks in `state`
+    pub fn with_state(&self, state: CheckState) -> impl Iterator<Item = &Check> {
+        self.checks.iter().filter(move |c| c.state == state)
+    }
+
+    /// Counts by state, like "2 failing, 5 passing"
+    pub fn summary(&self) -> String {
+        if self.checks.is_empty() {
+            return "no checks".to_string();
+        }
+        [
+            CheckState::Failing,
+            CheckState::Pending,
+            CheckState::Passing,
+        ]
+        .into_iter()
+        .filter_map(|state| {
+            let count = self.with_state(state).count();
+            (count > 0).then(|| format!("{count} {}", state.label()))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+
+    /// Description section telling reviewers which checks failed and that
+    /// they may be flaky rather than caused by the change
+    pub fn to_markdown(&self) -> String {
+        let short = self.commit.get(..7).unwrap_or(&self.commit);
+        let mut out = format!(
+            "## CI Status\n\nWhen this pull request was opened, {} on `{short}`:\n\n",
+            self.summary()
+        );
+        for check in self.with_state(CheckState::Failing) {
+            match &check.url {
+                Some(url) => out.push_str(&format!("- ✗ [{}]({url})\n", check.name)),
+                None => out.push_str(&format!("- ✗ {}\n", check.name)),
+            }
+        }
+        out.push_str(
+            "\nThese failures may be flaky tests unrelated to this change; \
+             re-run them before treating them as regressions.\n",
+        );
+        out
+    }
+}
+
+/// Checks on `commit` of the repository `remote_url` points at
+pub async fn fetch_status(
+    github: &GitHubConfig,
+    gitlab: &GitLabConfig,
+    remote_url: &str,
+    commit: &str,
+) -> Result<CiStatus> {
+    let checks = match Forge::from_remote_url(remote_url, gitlab) {
+        Some(Forge::GitHub(repo)) => github::commit_checks(github, &repo, commit).await?,
+        Some(Forge::GitLab(project)) => gitlab::commit_checks(gitlab, &project, commit).await?,
+        None => bail!("Can't tell the forge repository from {remote_url}"),
+    };
+    Ok(CiStatus::new(commit, checks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &str, state: CheckState) -> Check {
+        Check {
+            name: name.to_string(),
+            state,
+            url: None,
+        }
+    }
+
+    #[test]
+    fn test_ci_status_summary() {
+        let status = CiStatus::new(
+            "abcdef1234",
+            vec![
+                check("lint", CheckState::Passing),
+                check("test (ubuntu)", CheckState::Failing),
+                check("build", CheckState::Pending),
+                check("docs", CheckState::Passing),
+            ],
+        );
+        assert_eq!(status.state(), Some(CheckState::Failing));
+        assert_eq!(status.checks[0].name, "test (ubuntu)");
+        assert_eq!(status.summary(), "1 failing, 1 pending, 2 passing");
+        let markdown = status.to_markdown();
+        assert!(markdown.contains("on `abcdef1`"));
+        assert!(markdown.contains("- ✗ test (ubuntu)\n"));
+        assert!(!markdown.contains("lint"));
+
+        assert_eq!(CiStatus::new("abc", Vec::new()).state(), None);
+    }
+}
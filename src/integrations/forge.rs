@@ -0,0 +1,189 @@
+//! The code forge a repository's `origin` lives on
+//!
+//! GitHub and GitLab are told apart by the remote URL: GitLab when its host
+//! contains "gitlab" or matches `[gitlab] api_url`, GitHub otherwise. Both
+//! publish each pull (merge) request's head under a ref of its own, which
+//! `review --pr` fetches to review a colleague's branch by number.
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use super::github::{self, GitHubConfig, RepoSlug};
+use super::gitlab::{self, GitLabConfig};
+use crate::git::{git_command, run_git_command};
+
+/// A repository on a code forge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Forge {
+    GitHub(RepoSlug),
+    /// Full project path, including any subgroups
+    GitLab(String),
+}
+
+impl Forge {
+    /// Forge and repository of a remote URL
+    pub fn from_remote_url(url: &str, gitlab: &GitLabConfig) -> Option<Self> {
+        let host = remote_host(url)?;
+        if host.contains("gitlab") || gitlab.host().is_some_and(|h| h == host) {
+            gitlab::project_path(url).map(Self::GitLab)
+        } else {
+            RepoSlug::from_remote_url(url).map(Self::GitHub)
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::GitHub(_) => "GitHub",
+            Self::GitLab(_) => "GitLab",
+        }
+    }
+
+    /// How the forge refers to request `number`, like "PR #12" or "MR !12"
+    pub fn label(&self, number: u64) -> String {
+        match self {
+            Self::GitHub(_) => format!("PR #{number}"),
+            Self::GitLab(_) => format!("MR !{number}"),
+        }
+    }
+
+    /// Ref holding the head of request `number` on the remote
+    pub fn pull_ref(&self, number: u64) -> String {
+        match self {
+            Self::GitHub(_) => format!("refs/pull/{number}/head"),
+            Self::GitLab(_) => format!("refs/merge-requests/{number}/head"),
+        }
+    }
+}
+
+/// Host of a remote URL, without user or port
+pub(crate) fn remote_host(url: &str) -> Option<&str> {
+    let url = url.trim();
+    let authority = if let Some((_, rest)) = url.split_once("://") {
+        rest.split('/').next()?
+    } else {
+        url.split_once(':')?.0
+    };
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+/// A pull or merge request fetched from `origin` for review
+#[derive(Debug, Clone)]
+pub struct FetchedPullRequest {
+    /// "PR #12" or "MR !12"
+    pub label: String,
+    /// Title, when the forge's API could be read
+    pub title: Option<String>,
+    /// Branch it merges into
+    pub base: String,
+    /// Where the request's branch forked from `base`
+    pub merge_base: String,
+    /// Head commit
+    pub head: String,
+    /// Why the API couldn't be read and `base` was guessed
+    pub warning: Option<String>,
+}
+
+/// Fetch request `number` of `origin` and the branch it merges into, for
+/// reviewing `merge_base..head`
+///
+/// The title and base branch come from the forge's API; without a token the
+/// base falls back to `origin`'s default branch. The commits are fetched
+/// into `FETCH_HEAD` only, so no branches or refs are created.
+pub async fn fetch_pull_request(
+    github: &GitHubConfig,
+    gitlab: &GitLabConfig,
+    number: u64,
+) -> Result<FetchedPullRequest> {
+    let url = run_git_command(&["config", "--get", "remote.origin.url"])
+        .context("--pr needs an origin remote")?;
+    let forge = Forge::from_remote_url(&url, gitlab)
+        .ok_or_else(|| anyhow!("Can't tell the GitHub or GitLab repository from {url}"))?;
+    let label = forge.label(number);
+
+    let details = match &forge {
+        Forge::GitHub(repo) => github::get_pull_request(github, repo, number)
+            .await
+            .map(|pr| (pr.title, pr.base.branch)),
+        Forge::GitLab(project) => gitlab::get_merge_request(gitlab, project, number)
+            .await
+            .map(|mr| (mr.title, mr.target_branch)),
+    };
+    let (title, base, warning) = match details {
+        Ok((title, base)) => (Some(title), base, None),
+        Err(e) => {
+            let base = default_branch();
+            let warning = format!(
+                "Couldn't read {label} from {}: {e:#}; reviewing against {base}",
+                forge.name()
+            );
+            (None, base, Some(warning))
+        }
+    };
+
+    let head = fetch(&forge.pull_ref(number))
+        .map_err(|e| anyhow!("Couldn't fetch {label} from origin: {e}"))?;
+    let base_tip = fetch(&base).map_err(|e| anyhow!("Couldn't fetch {base} from origin: {e}"))?;
+    let merge_base = run_git_command(&["merge-base", &base_tip, &head])
+        .with_context(|| format!("{label} shares no history with {base}"))?;
+
+    Ok(FetchedPullRequest {
+        label,
+        title,
+        base,
+        merge_base,
+        head,
+        warning,
+    })
+}
+
+/// Fetch `refspec` from `origin` into `FETCH_HEAD` and return its commit id
+fn fetch(refspec: &str) -> Result<String> {
+    let output = git_command()
+        .args(["fetch", "--no-tags", "--quiet", "origin", refspec])
+        .output()
+        .context("Failed to run git fetch")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    run_git_command(&["rev-parse", "FETCH_HEAD"])
+}
+
+/// `origin`'s default branch, or `main` when it isn't known
+fn default_branch() -> String {
+    run_git_command(&["symbolic-ref", "--short", "refs/remotes/origin/HEAD"])
+        .ok()
+        .and_then(|head| head.strip_prefix("origin/").map(str::to_string))
+        .unwrap_or_else(|| "main".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forge_from_remote_url() {
+        let gitlab = GitLabConfig::default();
+        let forge = Forge::from_remote_url("git@gitlab.com:group/sub/app.git", &gitlab);
+        assert_eq!(forge, Some(Forge::GitLab("group/sub/app".to_string())));
+        let forge = forge.expect("gitlab");
+        assert_eq!(forge.label(7), "MR !7");
+        assert_eq!(forge.pull_ref(7), "refs/merge-requests/7/head");
+
+        let forge = Forge::from_remote_url("https://github.com/hyperb1iss/git-iris", &gitlab)
+            .expect("github");
+        assert_eq!(forge.name(), "GitHub");
+        assert_eq!(forge.label(123), "PR #123");
+        assert_eq!(forge.pull_ref(123), "refs/pull/123/head");
+
+        let self_hosted = GitLabConfig {
+            api_url: "https://code.example.com/api/v4".to_string(),
+            ..GitLabConfig::default()
+        };
+        assert_eq!(
+            Forge::from_remote_url("ssh://git@code.example.com:2222/team/app.git", &self_hosted),
+            Some(Forge::GitLab("team/app".to_string()))
+        );
+        assert_eq!(remote_host("/srv/git/project"), None);
+    }
+}
@@ -0,0 +1,525 @@
+//! GitHub REST API
+//!
+//! Opens pull requests from a generated PR description and posts review
+//! findings as inline comments on an open pull request, and reads pull
+//! requests and the CI checks of a commit. The token comes from
+//! `[github] token` in the personal config, then `GITHUB_TOKEN` or `GH_TOKEN`;
+//! `api_url` points at a GitHub Enterprise server instead of github.com.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::ci::{Check, CheckState};
+
+/// Public GitHub API
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Environment variables checked for a token, in order
+pub const TOKEN_ENV_VARS: &[&str] = &["GITHUB_TOKEN", "GH_TOKEN"];
+
+/// Request timeout
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// `[github]` settings
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GitHubConfig {
+    /// Personal access token with pull request write access (personal config only)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub token: String,
+    /// API base URL (empty = api.github.com; personal config only)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub api_url: String,
+}
+
+impl GitHubConfig {
+    /// Whether nothing is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Configured token, else the first token environment variable set
+    pub fn resolve_token(&self) -> Option<String> {
+        if !self.token.is_empty() {
+            return Some(self.token.clone());
+        }
+        TOKEN_ENV_VARS
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|token| !token.is_empty())
+    }
+
+    /// API base URL without a trailing slash
+    pub fn api_url(&self) -> &str {
+        if self.api_url.is_empty() {
+            DEFAULT_API_URL
+        } else {
+            self.api_url.trim_end_matches('/')
+        }
+    }
+}
+
+/// Owner and name of a repository on GitHub
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoSlug {
+    pub owner: String,
+    pub name: String,
+}
+
+impl RepoSlug {
+    /// Parse a remote URL: `git@host:owner/repo.git`, `ssh://git@host/owner/repo`
+    /// or `https://host/owner/repo(.git)`
+    pub fn from_remote_url(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let path = if let Some((_, rest)) = url.split_once("://") {
+            // ssh://, https://, git://: drop the host (and any user or port)
+            rest.split_once('/')?.1
+        } else {
+            // scp-like syntax: user@host:owner/repo
+            url.split_once(':')?.1
+        };
+        let path = path.trim_end_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        let (owner, name) = path.rsplit_once('/')?;
+        // Enterprise servers can nest under a path prefix; the owner is the
+        // segment right before the name
+        let owner = owner.rsplit('/').next()?;
+        if owner.is_empty() || name.is_empty() {
+            return None;
+        }
+        Some(Self {
+            owner: owner.to_string(),
+            name: name.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for RepoSlug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.owner, self.name)
+    }
+}
+
+/// A pull request to open
+#[derive(Debug, Clone, Serialize)]
+pub struct NewPullRequest {
+    pub title: String,
+    pub body: String,
+    /// Branch with the changes
+    pub head: String,
+    /// Branch to merge into
+    pub base: String,
+}
+
+impl NewPullRequest {
+    /// Build from a generated markdown description: the first `# ` heading is
+    /// the title and the rest the body
+    pub fn from_markdown(markdown: &str, head: &str, base: &str) -> Self {
+        let markdown = markdown.trim();
+        let (title, body) = match markdown.split_once('\n') {
+            Some((first, rest)) => (first, rest.trim()),
+            None => (markdown, ""),
+        };
+        let title = title.trim_start_matches('#').trim();
+        Self {
+            title: if title.is_empty() {
+                head.to_string()
+            } else {
+                title.to_string()
+            },
+            body: body.to_string(),
+            head: head.to_string(),
+            base: base.strip_prefix("origin/").unwrap_or(base).to_string(),
+        }
+    }
+}
+
+/// A pull request GitHub created
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub html_url: String,
+}
+
+/// A branch end of a pull request
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestBranch {
+    #[serde(rename = "ref")]
+    pub branch: String,
+    pub sha: String,
+}
+
+/// An existing pull request with its branches
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestDetails {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    /// Branch it merges into
+    pub base: PullRequestBranch,
+    /// Branch with the changes
+    pub head: PullRequestBranch,
+}
+
+/// An inline review comment, anchored to lines on the new side of the diff
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ReviewComment {
+    pub path: String,
+    /// Last line the comment covers
+    pub line: usize,
+    /// First line, when the comment covers several
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<usize>,
+    /// Always `RIGHT`: findings cite the code after the change
+    pub side: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_side: Option<&'static str>,
+    /// Markdown body
+    pub body: String,
+}
+
+impl ReviewComment {
+    /// Comment on `start..=end` of `path`
+    pub fn new(path: impl Into<String>, start: usize, end: usize, body: impl Into<String>) -> Self {
+        let multi_line = start < end;
+        Self {
+            path: path.into(),
+            line: end,
+            start_line: multi_line.then_some(start),
+            side: "RIGHT",
+            start_side: multi_line.then_some("RIGHT"),
+            body: body.into(),
+        }
+    }
+}
+
+/// A pull request review to post
+#[derive(Debug, Clone, Serialize)]
+pub struct NewReview {
+    /// Commit the comments' line numbers refer to
+    pub commit_id: String,
+    /// Summary shown above the inline comments
+    pub body: String,
+    /// Always `COMMENT`: Iris neither approves nor requests changes
+    pub event: &'static str,
+    pub comments: Vec<ReviewComment>,
+}
+
+/// A review GitHub created
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostedReview {
+    pub html_url: String,
+}
+
+/// Client and token for API requests
+fn client(config: &GitHubConfig) -> Result<(reqwest::Client, String)> {
+    let Some(token) = config.resolve_token() else {
+        bail!(
+            "No GitHub token (set [github] token in the config or {})",
+            TOKEN_ENV_VARS.join(" / ")
+        );
+    };
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .user_agent(concat!("git-iris/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+    Ok((client, token))
+}
+
+/// Send a request with the API headers, failing on an error status
+async fn send(request: reqwest::RequestBuilder, token: &str) -> Result<reqwest::Response> {
+    let response = request
+        .bearer_auth(token)
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .context("Failed to reach GitHub")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        bail!("GitHub returned {status}: {}", error_message(&body));
+    }
+    Ok(response)
+}
+
+/// Open a pull request in `repo`
+pub async fn create_pull_request(
+    config: &GitHubConfig,
+    repo: &RepoSlug,
+    pr: &NewPullRequest,
+) -> Result<PullRequest> {
+    crate::read_only::ensure_writable("Opening a pull request")?;
+    let (client, token) = client(config)?;
+    let request = client
+        .post(format!("{}/repos/{repo}/pulls", config.api_url()))
+        .json(pr);
+    send(request, &token)
+        .await?
+        .json()
+        .await
+        .context("Invalid pull request response")
+}
+
+/// The open pull request from branch `head` of `repo`, if there is one
+pub async fn find_pull_request(
+    config: &GitHubConfig,
+    repo: &RepoSlug,
+    head: &str,
+) -> Result<Option<PullRequest>> {
+    let (client, token) = client(config)?;
+    let request = client
+        .get(format!("{}/repos/{repo}/pulls", config.api_url()))
+        .query(&[
+            ("state", "open"),
+            ("head", &format!("{}:{head}", repo.owner)),
+        ]);
+    let pulls: Vec<PullRequest> = send(request, &token)
+        .await?
+        .json()
+        .await
+        .context("Invalid pull request list")?;
+    Ok(pulls.into_iter().next())
+}
+
+/// Pull request `number` of `repo`
+pub async fn get_pull_request(
+    config: &GitHubConfig,
+    repo: &RepoSlug,
+    number: u64,
+) -> Result<PullRequestDetails> {
+    let (client, token) = client(config)?;
+    let request = client.get(format!("{}/repos/{repo}/pulls/{number}", config.api_url()));
+    send(request, &token)
+        .await?
+        .json()
+        .await
+        .context("Invalid pull request response")
+}
+
+/// Post a review with inline comments on pull request `number`
+pub async fn create_review(
+    config: &GitHubConfig,
+    repo: &RepoSlug,
+    number: u64,
+    review: &NewReview,
+) -> Result<PostedReview> {
+    crate::read_only::ensure_writable("Posting a review")?;
+    let (client, token) = client(config)?;
+    let request = client
+        .post(format!(
+            "{}/repos/{repo}/pulls/{number}/reviews",
+            config.api_url()
+        ))
+        .json(review);
+    send(request, &token)
+        .await?
+        .json()
+        .await
+        .context("Invalid review response")
+}
+
+/// A check run from GitHub Actions or another checks app
+#[derive(Debug, Deserialize)]
+struct CheckRun {
+    name: String,
+    /// `queued`, `in_progress` or `completed`
+    status: String,
+    /// Set once completed: `success`, `failure`, `neutral`, `skipped`, ...
+    conclusion: Option<String>,
+    html_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRuns {
+    check_runs: Vec<CheckRun>,
+}
+
+/// A commit status posted by an external CI service
+#[derive(Debug, Deserialize)]
+struct CommitStatus {
+    context: String,
+    /// `error`, `failure`, `pending` or `success`
+    state: String,
+    target_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CombinedStatus {
+    statuses: Vec<CommitStatus>,
+}
+
+impl From<CheckRun> for Check {
+    fn from(run: CheckRun) -> Self {
+        let state = match (run.status.as_str(), run.conclusion.as_deref()) {
+            ("completed", Some("success" | "neutral" | "skipped")) => CheckState::Passing,
+            ("completed", _) => CheckState::Failing,
+            _ => CheckState::Pending,
+        };
+        Self {
+            name: run.name,
+            state,
+            url: run.html_url,
+        }
+    }
+}
+
+impl From<CommitStatus> for Check {
+    fn from(status: CommitStatus) -> Self {
+        let state = match status.state.as_str() {
+            "success" => CheckState::Passing,
+            "pending" => CheckState::Pending,
+            _ => CheckState::Failing,
+        };
+        Self {
+            name: status.context,
+            state,
+            url: status.target_url.filter(|url| !url.is_empty()),
+        }
+    }
+}
+
+/// Check runs and commit statuses reported for `commit`
+pub async fn commit_checks(
+    config: &GitHubConfig,
+    repo: &RepoSlug,
+    commit: &str,
+) -> Result<Vec<Check>> {
+    let (client, token) = client(config)?;
+    let base = format!("{}/repos/{repo}/commits/{commit}", config.api_url());
+    let runs: CheckRuns = send(
+        client
+            .get(format!("{base}/check-runs"))
+            .query(&[("per_page", "100")]),
+        &token,
+    )
+    .await?
+    .json()
+    .await
+    .context("Invalid check runs response")?;
+    let combined: CombinedStatus = send(client.get(format!("{base}/status")), &token)
+        .await?
+        .json()
+        .await
+        .context("Invalid commit status response")?;
+
+    Ok(runs
+        .check_runs
+        .into_iter()
+        .map(Check::from)
+        .chain(combined.statuses.into_iter().map(Check::from))
+        .collect())
+}
+
+/// Readable error from a GitHub error body, including validation details
+/// such as "A pull request already exists"
+fn error_message(body: &serde_json::Value) -> String {
+    let message = body["message"].as_str().unwrap_or("request failed");
+    let details: Vec<&str> = body["errors"]
+        .as_array()
+        .map(|errors| {
+            errors
+                .iter()
+                .filter_map(|e| e["message"].as_str().or_else(|| e["code"].as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if details.is_empty() {
+        message.to_string()
+    } else {
+        format!("{message} ({})", details.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_slug_from_remote_urls() {
+        let expected = Some(RepoSlug {
+            owner: "hyperb1iss".to_string(),
+            name: "git-iris".to_string(),
+        });
+        for url in [
+            "git@github.com:hyperb1iss/git-iris.git",
+            "https://github.com/hyperb1iss/git-iris",
+            "https://github.com/hyperb1iss/git-iris.git/",
+            "ssh://git@github.com:22/hyperb1iss/git-iris.git",
+            "https://ghe.example.com/code/hyperb1iss/git-iris.git",
+        ] {
+            assert_eq!(RepoSlug::from_remote_url(url), expected, "{url}");
+        }
+        assert_eq!(RepoSlug::from_remote_url("/srv/git/project"), None);
+    }
+
+    #[test]
+    fn test_pull_request_from_markdown() {
+        let pr = NewPullRequest::from_markdown(
+            "# ✨ Add OAuth login\n\n## Summary\nAdds login.\n",
+            "feat/oauth",
+            "origin/main",
+        );
+        assert_eq!(pr.title, "✨ Add OAuth login");
+        assert_eq!(pr.body, "## Summary\nAdds login.");
+        assert_eq!(pr.base, "main");
+
+        let error = serde_json::json!({
+            "message": "Validation Failed",
+            "errors": [{"message": "A pull request already exists for o:feat/oauth."}]
+        });
+        assert_eq!(
+            error_message(&error),
+            "Validation Failed (A pull request already exists for o:feat/oauth.)"
+        );
+    }
+
+    #[test]
+    fn test_checks_from_runs_and_statuses() {
+        let runs: CheckRuns = serde_json::from_value(serde_json::json!({
+            "total_count": 3,
+            "check_runs": [
+                {"name": "test", "status": "completed", "conclusion": "failure", "html_url": "https://x/1"},
+                {"name": "lint", "status": "completed", "conclusion": "skipped", "html_url": null},
+                {"name": "build", "status": "in_progress", "conclusion": null}
+            ]
+        }))
+        .expect("parses");
+        let states: Vec<CheckState> = runs
+            .check_runs
+            .into_iter()
+            .map(|run| Check::from(run).state)
+            .collect();
+        assert_eq!(
+            states,
+            [
+                CheckState::Failing,
+                CheckState::Passing,
+                CheckState::Pending
+            ]
+        );
+
+        let commit_status: CommitStatus = serde_json::from_value(serde_json::json!({
+            "context": "ci/circleci", "state": "error", "target_url": ""
+        }))
+        .expect("parses");
+        let check = Check::from(commit_status);
+        assert_eq!(check.state, CheckState::Failing);
+        assert_eq!(check.url, None);
+    }
+
+    #[test]
+    fn test_review_comment_ranges() {
+        let single = serde_json::to_value(ReviewComment::new("src/lib.rs", 12, 12, "Fix"))
+            .expect("serializes");
+        assert_eq!(
+            single,
+            serde_json::json!({"path": "src/lib.rs", "line": 12, "side": "RIGHT", "body": "Fix"})
+        );
+
+        let range = ReviewComment::new("src/lib.rs", 10, 14, "Fix");
+        assert_eq!(range.start_line, Some(10));
+        assert_eq!(range.line, 14);
+        assert_eq!(range.start_side, Some("RIGHT"));
+    }
+}
@@ -0,0 +1,224 @@
+//! GitLab REST API
+//!
+//! Reads the pipeline job statuses of a commit for PR mode's CI status, and
+//! merge requests for `review --pr`. The
+//! token comes from `[gitlab] token` in the personal config, then
+//! `GITLAB_TOKEN`; `api_url` points at a self-managed server instead of
+//! gitlab.com.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::ci::{Check, CheckState};
+
+/// gitlab.com API
+pub const DEFAULT_API_URL: &str = "https://gitlab.com/api/v4";
+
+/// Environment variables checked for a token, in order
+pub const TOKEN_ENV_VARS: &[&str] = &["GITLAB_TOKEN"];
+
+/// Request timeout
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// `[gitlab]` settings
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GitLabConfig {
+    /// Personal access token with `read_api` scope (personal config only)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub token: String,
+    /// API base URL (empty = gitlab.com/api/v4; personal config only)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub api_url: String,
+}
+
+impl GitLabConfig {
+    /// Whether nothing is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Configured token, else the first token environment variable set
+    pub fn resolve_token(&self) -> Option<String> {
+        if !self.token.is_empty() {
+            return Some(self.token.clone());
+        }
+        TOKEN_ENV_VARS
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|token| !token.is_empty())
+    }
+
+    /// API base URL without a trailing slash
+    pub fn api_url(&self) -> &str {
+        if self.api_url.is_empty() {
+            DEFAULT_API_URL
+        } else {
+            self.api_url.trim_end_matches('/')
+        }
+    }
+
+    /// Host of a configured self-managed server
+    pub fn host(&self) -> Option<&str> {
+        if self.api_url.is_empty() {
+            return None;
+        }
+        super::forge::remote_host(&self.api_url)
+    }
+}
+
+/// Full project path (`group/subgroup/project`) of a remote URL
+pub fn project_path(url: &str) -> Option<String> {
+    let url = url.trim();
+    let path = if let Some((_, rest)) = url.split_once("://") {
+        rest.split_once('/')?.1
+    } else {
+        url.split_once(':')?.1
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    path.contains('/').then(|| path.to_string())
+}
+
+/// A job status reported for a commit
+#[derive(Debug, Deserialize)]
+struct CommitStatus {
+    name: String,
+    /// `created`, `pending`, `running`, `success`, `failed`, `canceled`,
+    /// `skipped`, `manual`, ...
+    status: String,
+    #[serde(default)]
+    allow_failure: bool,
+    target_url: Option<String>,
+}
+
+impl From<CommitStatus> for Check {
+    fn from(status: CommitStatus) -> Self {
+        let state = match status.status.as_str() {
+            "success" | "skipped" | "manual" => CheckState::Passing,
+            "failed" if status.allow_failure => CheckState::Passing,
+            "failed" | "canceled" => CheckState::Failing,
+            _ => CheckState::Pending,
+        };
+        Self {
+            name: status.name,
+            state,
+            url: status.target_url.filter(|url| !url.is_empty()),
+        }
+    }
+}
+
+/// A merge request as GitLab reports it
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergeRequest {
+    pub iid: u64,
+    pub title: String,
+    /// Branch it merges into
+    pub target_branch: String,
+    /// Head commit
+    pub sha: String,
+}
+
+/// Send a GET request for `path` under the project API, failing on an error
+/// status
+async fn get(config: &GitLabConfig, project: &str, path: &str) -> Result<reqwest::Response> {
+    let Some(token) = config.resolve_token() else {
+        bail!(
+            "No GitLab token (set [gitlab] token in the config or {})",
+            TOKEN_ENV_VARS.join(" / ")
+        );
+    };
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .user_agent(concat!("git-iris/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+    let response = client
+        .get(format!(
+            "{}/projects/{}/{path}",
+            config.api_url(),
+            project.replace('/', "%2F")
+        ))
+        .query(&[("per_page", "100")])
+        .header("PRIVATE-TOKEN", token)
+        .send()
+        .await
+        .context("Failed to reach GitLab")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["message"]
+            .as_str()
+            .or_else(|| body["error"].as_str())
+            .unwrap_or("request failed");
+        bail!("GitLab returned {status}: {message}");
+    }
+    Ok(response)
+}
+
+/// Latest job statuses of `commit` in `project`
+pub async fn commit_checks(
+    config: &GitLabConfig,
+    project: &str,
+    commit: &str,
+) -> Result<Vec<Check>> {
+    let statuses: Vec<CommitStatus> = get(
+        config,
+        project,
+        &format!("repository/commits/{commit}/statuses"),
+    )
+    .await?
+    .json()
+    .await
+    .context("Invalid commit status response")?;
+    Ok(statuses.into_iter().map(Check::from).collect())
+}
+
+/// Merge request `iid` of `project`
+pub async fn get_merge_request(
+    config: &GitLabConfig,
+    project: &str,
+    iid: u64,
+) -> Result<MergeRequest> {
+    get(config, project, &format!("merge_requests/{iid}"))
+        .await?
+        .json()
+        .await
+        .context("Invalid merge request response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_path_and_statuses() {
+        assert_eq!(
+            project_path("git@gitlab.com:group/sub/app.git").as_deref(),
+            Some("group/sub/app")
+        );
+        assert_eq!(
+            project_path("https://gitlab.example.com/team/app/").as_deref(),
+            Some("team/app")
+        );
+        assert_eq!(project_path("https://gitlab.com/app"), None);
+
+        let statuses: Vec<CommitStatus> = serde_json::from_value(serde_json::json!([
+            {"name": "test", "status": "failed", "allow_failure": false, "target_url": "https://x/1"},
+            {"name": "audit", "status": "failed", "allow_failure": true},
+            {"name": "deploy", "status": "running"}
+        ]))
+        .expect("parses");
+        let states: Vec<CheckState> = statuses
+            .into_iter()
+            .map(|status| Check::from(status).state)
+            .collect();
+        assert_eq!(
+            states,
+            [
+                CheckState::Failing,
+                CheckState::Passing,
+                CheckState::Pending
+            ]
+        );
+    }
+}
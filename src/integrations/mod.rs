@@ -0,0 +1,10 @@
+//! Integrations with code hosting services
+//!
+//! Each submodule talks to one service's API, so Studio and the CLI can act
+//! on generated content (open a pull request, post a review, check CI)
+//! without leaving git-iris.
+
+pub mod ci;
+pub mod forge;
+pub mod github;
+pub mod gitlab;
@@ -13,26 +13,57 @@
 #![allow(clippy::option_as_ref_cloned)] // .as_ref().cloned() is sometimes clearer
 #![allow(clippy::redundant_clone)] // Sometimes more explicit is clearer
 
+pub mod adr;
 pub mod agents;
+pub mod analytics;
+pub mod architecture;
+pub mod ask;
+pub mod bench;
 pub mod changelog;
 pub mod cli;
+pub mod clipboard;
 pub mod commands;
+pub mod commit_scaffold;
 pub mod common;
 pub mod companion;
 pub mod config;
+pub mod config_bundle;
 pub mod context;
+pub mod dependency_updates;
+pub mod doc_context;
+pub mod error;
 pub mod git;
 pub mod gitmoji;
+pub mod glossary;
+pub mod hooks;
 pub mod instruction_presets;
+pub mod integrations;
+pub mod lifecycle;
 pub mod logger;
+pub mod message_stats;
 pub mod messages;
+pub mod model_catalog;
+pub mod onboarding;
 pub mod output;
+pub mod outputs;
+pub mod plan;
+pub mod plumbing;
+pub mod pr_split;
 pub mod providers;
+pub mod read_only;
+pub mod recap;
+pub mod release_readiness;
+pub mod risk;
 pub mod services;
+pub mod spellcheck;
 pub mod studio;
 pub mod theme;
 pub mod types;
 pub mod ui;
+pub mod undo;
+pub mod update;
+pub mod upgrade_guide;
+pub mod usage;
 
 // Re-export important structs and functions for easier testing
 pub use config::Config;
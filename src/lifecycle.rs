@@ -0,0 +1,235 @@
+//! Lifecycle hooks around generations and commits
+//!
+//! `[hooks]` in the personal config names shell commands to run at four
+//! points: `before_generate`, `after_generate`, `before_commit` and
+//! `after_commit`. Each gets the content at hand on stdin (the generated
+//! output, the commit message) and details in `IRIS_*` environment
+//! variables. A failing `before_*` hook stops the operation, which makes it a
+//! place for custom checks; `after_*` hooks are for notifications, so their
+//! failures are only logged.
+//!
+//! Hooks run arbitrary commands, so a project's `.irisconfig` can't set them.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::error::IrisError;
+
+/// Longest hook output line quoted in an error
+const MAX_REASON_CHARS: usize = 200;
+
+/// A point in an operation where a hook can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    BeforeGenerate,
+    AfterGenerate,
+    BeforeCommit,
+    AfterCommit,
+}
+
+impl LifecycleEvent {
+    /// Config key and `IRIS_HOOK` value
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::BeforeGenerate => "before_generate",
+            Self::AfterGenerate => "after_generate",
+            Self::BeforeCommit => "before_commit",
+            Self::AfterCommit => "after_commit",
+        }
+    }
+}
+
+/// `[hooks]` settings: shell commands run around generations and commits
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LifecycleHooks {
+    /// Before the agent starts; a non-zero exit cancels the generation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before_generate: Option<String>,
+    /// After a generation succeeds, with the output on stdin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_generate: Option<String>,
+    /// Before committing, with the message on stdin; a non-zero exit cancels
+    /// the commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before_commit: Option<String>,
+    /// After a commit is made, with the message on stdin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_commit: Option<String>,
+}
+
+impl LifecycleHooks {
+    /// Whether no hook is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Command configured for an event
+    pub fn command(&self, event: LifecycleEvent) -> Option<&str> {
+        let command = match event {
+            LifecycleEvent::BeforeGenerate => &self.before_generate,
+            LifecycleEvent::AfterGenerate => &self.after_generate,
+            LifecycleEvent::BeforeCommit => &self.before_commit,
+            LifecycleEvent::AfterCommit => &self.after_commit,
+        };
+        command.as_deref().map(str::trim).filter(|c| !c.is_empty())
+    }
+
+    /// Run the hook for `event`, if one is configured
+    ///
+    /// `input` is written to the command's stdin and `env` is added to its
+    /// environment alongside `IRIS_HOOK`. Output is captured rather than
+    /// shown, so hooks don't disturb Studio; the last line of a failing
+    /// hook's output becomes part of the error.
+    pub fn run(
+        &self,
+        event: LifecycleEvent,
+        env: &[(&str, &str)],
+        input: &str,
+        dir: Option<&Path>,
+    ) -> Result<()> {
+        let Some(command) = self.command(event) else {
+            return Ok(());
+        };
+        crate::log_debug!("Running {} hook: {}", event.name(), command);
+
+        let mut shell = shell_command(command);
+        shell
+            .env("IRIS_HOOK", event.name())
+            .envs(env.iter().copied())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(dir) = dir {
+            shell.current_dir(dir);
+        }
+
+        let mut child = shell
+            .spawn()
+            .with_context(|| format!("Failed to run the {} hook", event.name()))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            let input = input.to_string();
+            // Hooks that ignore stdin close it early; that's not an error
+            std::thread::spawn(move || {
+                let _ = stdin.write_all(input.as_bytes());
+            });
+        }
+        let output = child.wait_with_output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        crate::log_debug!(
+            "{} hook exited with {}; stdout: {}; stderr: {}",
+            event.name(),
+            output.status,
+            stdout.trim(),
+            stderr.trim()
+        );
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let error = anyhow::Error::from(IrisError::HookFailed {
+            hook: event.name().to_string(),
+            code: output.status.code(),
+        });
+        let reason = stderr
+            .lines()
+            .chain(stdout.lines())
+            .map(str::trim)
+            .rfind(|line| !line.is_empty())
+            .map(|line| line.chars().take(MAX_REASON_CHARS).collect::<String>());
+        Err(match reason {
+            Some(reason) => error.context(format!("{} hook: {reason}", event.name())),
+            None => error,
+        })
+    }
+
+    /// Run an `after_*` hook, logging instead of returning a failure
+    pub fn notify(
+        &self,
+        event: LifecycleEvent,
+        env: &[(&str, &str)],
+        input: &str,
+        dir: Option<&Path>,
+    ) {
+        if let Err(e) = self.run(event, env, input, dir) {
+            tracing::warn!("{:#}", e);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(not(unix))]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hooks_get_input_and_can_abort() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let hooks = LifecycleHooks {
+            before_commit: Some(
+                "grep -q 'PROJ-' || { echo 'No ticket reference' >&2; exit 3; }".to_string(),
+            ),
+            after_commit: Some(
+                "cat > seen.txt; echo \"$IRIS_HOOK $IRIS_COMMIT\" >> seen.txt".to_string(),
+            ),
+            ..LifecycleHooks::default()
+        };
+
+        let error = hooks
+            .run(
+                LifecycleEvent::BeforeCommit,
+                &[],
+                "fix: tidy the parser",
+                Some(dir.path()),
+            )
+            .expect_err("message without a ticket");
+        assert_eq!(error.to_string(), "before_commit hook: No ticket reference");
+        assert_eq!(
+            IrisError::find(&error),
+            Some(IrisError::HookFailed {
+                hook: "before_commit".to_string(),
+                code: Some(3)
+            })
+        );
+        hooks
+            .run(
+                LifecycleEvent::BeforeCommit,
+                &[],
+                "PROJ-1: tidy the parser",
+                Some(dir.path()),
+            )
+            .expect("message with a ticket");
+
+        hooks.notify(
+            LifecycleEvent::AfterCommit,
+            &[("IRIS_COMMIT", "abc1234")],
+            "PROJ-1: tidy the parser\n",
+            Some(dir.path()),
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("seen.txt")).expect("hook output"),
+            "PROJ-1: tidy the parser\nafter_commit abc1234\n"
+        );
+
+        // Unconfigured events do nothing
+        hooks
+            .run(LifecycleEvent::BeforeGenerate, &[], "", None)
+            .expect("no hook");
+    }
+}
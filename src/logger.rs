@@ -21,6 +21,9 @@ static LOG_TO_STDOUT: std::sync::LazyLock<Mutex<bool>> =
     std::sync::LazyLock::new(|| Mutex::new(false));
 static VERBOSE_LOGGING: std::sync::LazyLock<Mutex<bool>> =
     std::sync::LazyLock::new(|| Mutex::new(false));
+/// Send console logs to stderr, keeping stdout for machine-readable output
+static LOG_TO_STDERR: std::sync::LazyLock<Mutex<bool>> =
+    std::sync::LazyLock::new(|| Mutex::new(false));
 
 /// Custom writer that writes to both file and stdout/stderr
 #[derive(Clone)]
@@ -34,9 +37,13 @@ impl Write for UnifiedWriter {
             let _ = file.flush();
         }
 
-        // Also write to stdout if enabled (for CLI debug mode)
+        // Also write to the console if enabled (for CLI debug mode)
         if *LOG_TO_STDOUT.lock() {
-            let _ = io::stdout().write_all(buf);
+            if *LOG_TO_STDERR.lock() {
+                let _ = io::stderr().write_all(buf);
+            } else {
+                let _ = io::stdout().write_all(buf);
+            }
         }
 
         Ok(buf.len())
@@ -48,6 +55,7 @@ impl Write for UnifiedWriter {
         }
         if *LOG_TO_STDOUT.lock() {
             let _ = io::stdout().flush();
+            let _ = io::stderr().flush();
         }
         Ok(())
     }
@@ -122,7 +130,11 @@ impl log::Log for GitIrisLogger {
             }
 
             if *LOG_TO_STDOUT.lock() {
-                print!("{message}");
+                if *LOG_TO_STDERR.lock() {
+                    eprint!("{message}");
+                } else {
+                    print!("{message}");
+                }
             }
         }
     }
@@ -239,6 +251,11 @@ pub fn set_log_to_stdout(enabled: bool) {
     *log_to_stdout = enabled;
 }
 
+/// Print console logs on stderr instead of stdout
+pub fn set_log_to_stderr(enabled: bool) {
+    *LOG_TO_STDERR.lock() = enabled;
+}
+
 // Macros for git-iris logging (maintains compatibility)
 #[macro_export]
 macro_rules! log_debug {
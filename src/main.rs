@@ -1,5 +1,6 @@
 use anyhow::Result;
 use git_iris::cli;
+use git_iris::error::IrisError;
 
 /// Main entry point for the application
 #[tokio::main]
@@ -9,7 +10,11 @@ async fn main() -> Result<()> {
         Ok(()) => Ok(()),
         Err(e) => {
             eprintln!("Error: {e}");
-            std::process::exit(1);
+            let error = IrisError::find(&e);
+            if let Some(hint) = error.as_ref().and_then(IrisError::remediation) {
+                eprintln!("Hint: {hint}");
+            }
+            std::process::exit(error.as_ref().map_or(1, IrisError::exit_code));
         }
     }
 }
@@ -0,0 +1,429 @@
+//! Commit message quality over existing history
+//!
+//! Scores past commit messages against the conventions of the active
+//! instruction preset: subject length, Conventional Commits compliance and
+//! emoji usage. The report shows how far a team has adopted its conventions
+//! and whose commits still drift from them.
+
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+use crate::config::Config;
+use crate::context::RecentCommit;
+
+/// Commit types allowed by the Conventional Commits preset
+const CONVENTIONAL_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+
+/// `type(scope)!: description`
+static CONVENTIONAL_SUBJECT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"^(?:{})(?:\([^()\s]+\))?!?: \S",
+        CONVENTIONAL_TYPES.join("|")
+    ))
+    .expect("valid regex")
+});
+
+/// Leading `:shortcode:` gitmoji
+static SHORTCODE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^:[a-z0-9_+-]+:").expect("valid regex"));
+
+/// Authors listed in the report
+const TOP_AUTHORS: usize = 10;
+
+/// Subject length buckets: upper bound (inclusive) and label
+const LENGTH_BUCKETS: &[(usize, &str)] = &[
+    (50, "≤ 50"),
+    (72, "51–72"),
+    (100, "73–100"),
+    (usize::MAX, "> 100"),
+];
+
+/// A convention a commit message breaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Violation {
+    LongSubject,
+    NotConventional,
+    Emoji,
+    TrailingPeriod,
+    MissingBlankLine,
+}
+
+impl Violation {
+    fn describe(self, rules: &MessageRules) -> String {
+        match self {
+            Self::LongSubject => format!("Subject over {} characters", rules.max_subject),
+            Self::NotConventional => "Not in Conventional Commits format".to_string(),
+            Self::Emoji => "Emoji in subject".to_string(),
+            Self::TrailingPeriod => "Subject ends with a period".to_string(),
+            Self::MissingBlankLine => "No blank line between subject and body".to_string(),
+        }
+    }
+}
+
+/// Conventions derived from the active preset
+#[derive(Debug, Clone)]
+pub struct MessageRules {
+    pub preset: String,
+    pub max_subject: usize,
+    /// Require Conventional Commits subjects
+    pub conventional: bool,
+    /// Flag emoji in subjects
+    pub forbid_emoji: bool,
+}
+
+impl MessageRules {
+    /// Rules for the configured preset and gitmoji settings
+    pub fn from_config(config: &Config) -> Self {
+        let preset = match config.get_effective_preset_name() {
+            "" => "default",
+            name => name,
+        };
+        let conventional = preset == "conventional";
+        Self {
+            preset: preset.to_string(),
+            max_subject: if conventional { 50 } else { 72 },
+            conventional,
+            forbid_emoji: conventional || config.ascii_only || !config.use_gitmoji,
+        }
+    }
+
+    /// Conventions `message` breaks
+    pub fn check(&self, message: &str) -> Vec<Violation> {
+        let mut lines = message.lines();
+        let subject = lines.next().unwrap_or_default().trim();
+        let mut violations = Vec::new();
+
+        if subject.chars().count() > self.max_subject {
+            violations.push(Violation::LongSubject);
+        }
+        if self.conventional && !is_conventional(subject) {
+            violations.push(Violation::NotConventional);
+        }
+        if self.forbid_emoji && has_emoji(subject) {
+            violations.push(Violation::Emoji);
+        }
+        if subject.ends_with('.') && !subject.ends_with("..") {
+            violations.push(Violation::TrailingPeriod);
+        }
+        if lines.next().is_some_and(|line| !line.trim().is_empty()) {
+            violations.push(Violation::MissingBlankLine);
+        }
+        violations
+    }
+
+    /// One-line summary of the rules for the report
+    fn summary(&self) -> String {
+        let mut parts = vec![format!("subjects up to {} characters", self.max_subject)];
+        if self.conventional {
+            parts.push("Conventional Commits".to_string());
+        }
+        if self.forbid_emoji {
+            parts.push("no emoji".to_string());
+        }
+        parts.push("no trailing period".to_string());
+        parts.push("blank line before the body".to_string());
+        parts.join(", ")
+    }
+}
+
+/// Per-author totals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorStats {
+    pub name: String,
+    pub commits: usize,
+    /// Commits breaking at least one convention
+    pub violating: usize,
+}
+
+/// Scores for a stretch of history
+#[derive(Debug, Clone)]
+pub struct MessageStats {
+    pub rules: MessageRules,
+    /// Commits scored (merges excluded)
+    pub total: usize,
+    /// Commits breaking no convention
+    pub compliant: usize,
+    pub conventional: usize,
+    pub with_emoji: usize,
+    /// Subject lengths in characters, sorted
+    pub lengths: Vec<usize>,
+    pub violations: BTreeMap<Violation, usize>,
+    /// Authors with violations, most first
+    pub authors: Vec<AuthorStats>,
+}
+
+impl MessageStats {
+    /// Score `commits`, skipping merge commits
+    pub fn from_commits(rules: MessageRules, commits: &[RecentCommit]) -> Self {
+        let mut stats = Self {
+            rules,
+            total: 0,
+            compliant: 0,
+            conventional: 0,
+            with_emoji: 0,
+            lengths: Vec::new(),
+            violations: BTreeMap::new(),
+            authors: Vec::new(),
+        };
+        let mut authors: HashMap<&str, AuthorStats> = HashMap::new();
+
+        for commit in commits {
+            let subject = commit.message.lines().next().unwrap_or_default().trim();
+            if is_merge(subject) {
+                continue;
+            }
+            let violations = stats.rules.check(&commit.message);
+
+            stats.total += 1;
+            stats.lengths.push(subject.chars().count());
+            stats.conventional += usize::from(is_conventional(subject));
+            stats.with_emoji += usize::from(has_emoji(subject));
+            stats.compliant += usize::from(violations.is_empty());
+            for violation in &violations {
+                *stats.violations.entry(*violation).or_default() += 1;
+            }
+
+            let author = authors
+                .entry(commit.author.as_str())
+                .or_insert_with(|| AuthorStats {
+                    name: commit.author.clone(),
+                    commits: 0,
+                    violating: 0,
+                });
+            author.commits += 1;
+            author.violating += usize::from(!violations.is_empty());
+        }
+
+        stats.lengths.sort_unstable();
+        stats.authors = authors.into_values().filter(|a| a.violating > 0).collect();
+        stats.authors.sort_by(|a, b| {
+            b.violating
+                .cmp(&a.violating)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        stats
+    }
+
+    /// Subject length at percentile `p` (0–100)
+    pub fn length_percentile(&self, p: usize) -> usize {
+        if self.lengths.is_empty() {
+            return 0;
+        }
+        let index = (self.lengths.len() - 1) * p / 100;
+        self.lengths[index]
+    }
+
+    /// Render the report as markdown
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# Commit Message Quality\n\n");
+        md.push_str(&format!(
+            "Scored {} commits against the `{}` preset: {}.\n\n",
+            self.total,
+            self.rules.preset,
+            self.rules.summary()
+        ));
+
+        md.push_str("## Summary\n\n| Metric | Value |\n| --- | --- |\n");
+        md.push_str(&format!(
+            "| Compliant commits | {} ({} of {}) |\n",
+            percent(self.compliant, self.total),
+            self.compliant,
+            self.total
+        ));
+        md.push_str(&format!(
+            "| Conventional Commits | {} |\n",
+            percent(self.conventional, self.total)
+        ));
+        md.push_str(&format!(
+            "| Emoji in subject | {} |\n",
+            percent(self.with_emoji, self.total)
+        ));
+        md.push_str(&format!(
+            "| Median subject length | {} |\n| 90th percentile length | {} |\n\n",
+            self.length_percentile(50),
+            self.length_percentile(90)
+        ));
+
+        md.push_str("## Subject Length\n\n| Characters | Commits | Share |\n| --- | --- | --- |\n");
+        let mut lower = 0;
+        for (upper, label) in LENGTH_BUCKETS {
+            let count = self
+                .lengths
+                .iter()
+                .filter(|len| **len > lower && *len <= upper)
+                .count();
+            md.push_str(&format!(
+                "| {label} | {count} | {} |\n",
+                percent(count, self.total)
+            ));
+            lower = *upper;
+        }
+
+        if !self.violations.is_empty() {
+            md.push_str("\n## Violations\n\n| Rule | Commits | Share |\n| --- | --- | --- |\n");
+            for (violation, count) in &self.violations {
+                md.push_str(&format!(
+                    "| {} | {count} | {} |\n",
+                    violation.describe(&self.rules),
+                    percent(*count, self.total)
+                ));
+            }
+        }
+
+        if !self.authors.is_empty() {
+            md.push_str(
+                "\n## Authors with Most Violations\n\n| Author | Commits | Violating | Rate |\n| --- | --- | --- | --- |\n",
+            );
+            for author in self.authors.iter().take(TOP_AUTHORS) {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    author.name,
+                    author.commits,
+                    author.violating,
+                    percent(author.violating, author.commits)
+                ));
+            }
+        }
+        md
+    }
+}
+
+/// Whether `subject` follows Conventional Commits, ignoring a leading emoji
+pub fn is_conventional(subject: &str) -> bool {
+    CONVENTIONAL_SUBJECT.is_match(strip_emoji(subject))
+}
+
+/// Whether `subject` contains an emoji or a `:shortcode:` gitmoji
+pub fn has_emoji(subject: &str) -> bool {
+    SHORTCODE.is_match(subject) || subject.chars().any(is_emoji)
+}
+
+fn strip_emoji(subject: &str) -> &str {
+    let rest = SHORTCODE
+        .find(subject)
+        .map_or(subject, |m| &subject[m.end()..]);
+    rest.trim_start_matches(|c: char| is_emoji(c) || c == '\u{fe0f}' || c.is_whitespace())
+}
+
+/// Pictographs and symbols used as emoji
+fn is_emoji(c: char) -> bool {
+    matches!(u32::from(c), 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x1F000..=0x1FAFF)
+}
+
+/// Subjects git writes for merges
+fn is_merge(subject: &str) -> bool {
+    subject.starts_with("Merge branch ")
+        || subject.starts_with("Merge pull request ")
+        || subject.starts_with("Merge remote-tracking branch ")
+        || subject.starts_with("Merge tag ")
+}
+
+fn percent(part: usize, total: usize) -> String {
+    if total == 0 {
+        return "–".to_string();
+    }
+    format!("{}%", (part * 100 + total / 2) / total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(conventional: bool) -> MessageRules {
+        MessageRules {
+            preset: if conventional {
+                "conventional"
+            } else {
+                "default"
+            }
+            .to_string(),
+            max_subject: if conventional { 50 } else { 72 },
+            conventional,
+            forbid_emoji: conventional,
+        }
+    }
+
+    fn commit(author: &str, message: &str) -> RecentCommit {
+        RecentCommit {
+            hash: String::new(),
+            message: message.to_string(),
+            author: author.to_string(),
+            timestamp: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_conventional_detection() {
+        assert!(is_conventional("feat(auth): add token rotation"));
+        assert!(is_conventional("fix!: drop legacy flag"));
+        assert!(is_conventional("✨ feat: add search"));
+        assert!(is_conventional(":sparkles: feat: add search"));
+        assert!(!is_conventional("Feat: add search"));
+        assert!(!is_conventional("feature: add search"));
+        assert!(!is_conventional("Add search"));
+    }
+
+    #[test]
+    fn test_check_against_conventional_preset() {
+        let conventional = rules(true);
+        assert!(
+            conventional
+                .check("fix(cli): handle empty input")
+                .is_empty()
+        );
+        assert_eq!(
+            conventional.check("🐛 Fix the crash when the config file is missing entirely."),
+            [
+                Violation::LongSubject,
+                Violation::NotConventional,
+                Violation::Emoji,
+                Violation::TrailingPeriod
+            ]
+        );
+        assert_eq!(
+            conventional.check("docs: fix typo\nin readme"),
+            [Violation::MissingBlankLine]
+        );
+        // The default preset allows emoji and free-form subjects
+        assert!(rules(false).check("✨ Add search").is_empty());
+    }
+
+    #[test]
+    fn test_stats_skip_merges_and_rank_authors() {
+        let commits = [
+            commit("Ada", "feat: add search"),
+            commit("Ada", "Add more search."),
+            commit("Grace", "update stuff"),
+            commit("Grace", "fixed things"),
+            commit("Grace", "Merge branch 'main' into feature"),
+        ];
+        let stats = MessageStats::from_commits(rules(true), &commits);
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.compliant, 1);
+        assert_eq!(stats.conventional, 1);
+        assert_eq!(stats.violations[&Violation::NotConventional], 3);
+        assert_eq!(stats.violations[&Violation::TrailingPeriod], 1);
+        assert_eq!(
+            stats.authors,
+            [
+                AuthorStats {
+                    name: "Grace".to_string(),
+                    commits: 2,
+                    violating: 2
+                },
+                AuthorStats {
+                    name: "Ada".to_string(),
+                    commits: 2,
+                    violating: 1
+                }
+            ]
+        );
+
+        let report = stats.to_markdown();
+        assert!(report.contains("| Compliant commits | 25% (1 of 4) |"));
+        assert!(report.contains("| Grace | 2 | 2 | 100% |"));
+    }
+}
@@ -0,0 +1,414 @@
+//! Model catalog and connection checks
+//!
+//! Fetches the models a provider offers from its list endpoint and caches the
+//! result on disk so pickers stay fast and work offline. Also runs a minimal
+//! completion to verify a provider/key/model combination before it's saved.
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::providers::{Provider, header_map, ollama_base_url};
+
+/// How long a cached model list is considered fresh
+const CACHE_TTL_HOURS: i64 = 24;
+
+/// Request timeout for provider API calls
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+
+/// Why a connection test failed
+#[derive(Debug, thiserror::Error)]
+pub enum ConnectionError {
+    #[error("No API key configured (set it in settings or {0})")]
+    MissingApiKey(&'static str),
+    #[error("API key rejected by {0}")]
+    InvalidKey(Provider),
+    #[error("Model not found: {0}")]
+    ModelNotFound(String),
+    #[error("Rate limited by {0}, try again shortly")]
+    RateLimited(Provider),
+    #[error("Network error: {0}")]
+    Network(String),
+    #[error("{provider} returned {status}: {message}")]
+    Api {
+        provider: Provider,
+        status: u16,
+        message: String,
+    },
+}
+
+/// Cached model list for one provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedModels {
+    fetched_at: DateTime<Utc>,
+    models: Vec<String>,
+}
+
+/// Resolve the API key for a provider: explicit key first, then the environment
+///
+/// Providers that don't need a key resolve to an empty one when none is set.
+pub fn resolve_api_key(provider: Provider, configured: Option<&str>) -> Option<String> {
+    configured
+        .filter(|key| !key.is_empty())
+        .map(str::to_string)
+        .or_else(|| provider.env_api_key())
+        .or_else(|| (!provider.requires_api_key()).then(String::new))
+}
+
+/// List models for a provider, using the on-disk cache when it is fresh
+///
+/// A stale cache is still returned if the fetch fails, so the picker has
+/// something to show without network access.
+pub async fn list_models(
+    provider: Provider,
+    api_key: &str,
+    headers: &[(String, String)],
+    refresh: bool,
+) -> Result<Vec<String>> {
+    let cached = load_cache(provider);
+    if !refresh
+        && let Some(cached) = &cached
+        && Utc::now() - cached.fetched_at < Duration::hours(CACHE_TTL_HOURS)
+    {
+        return Ok(cached.models.clone());
+    }
+
+    match fetch_models(provider, api_key, headers).await {
+        Ok(models) => {
+            if let Err(e) = save_cache(provider, &models) {
+                tracing::warn!("Failed to cache model list: {}", e);
+            }
+            Ok(models)
+        }
+        Err(e) => match cached {
+            Some(cached) if !cached.models.is_empty() => {
+                tracing::warn!("Model list fetch failed, using cache: {}", e);
+                Ok(cached.models)
+            }
+            _ => Err(e),
+        },
+    }
+}
+
+/// Fetch the model list from the provider's API, sending `headers` too
+pub async fn fetch_models(
+    provider: Provider,
+    api_key: &str,
+    headers: &[(String, String)],
+) -> Result<Vec<String>> {
+    if api_key.is_empty() && provider.requires_api_key() {
+        bail!(
+            "No API key for {} (set it in settings or {})",
+            provider,
+            provider.api_key_env()
+        );
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .default_headers(header_map(headers))
+        .build()?;
+
+    let request = match provider {
+        Provider::OpenAI => client
+            .get("https://api.openai.com/v1/models")
+            .bearer_auth(api_key),
+        Provider::Anthropic => client
+            .get("https://api.anthropic.com/v1/models")
+            .query(&[("limit", "1000")])
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01"),
+        Provider::Google => client
+            .get("https://generativelanguage.googleapis.com/v1beta/models")
+            .query(&[("key", api_key), ("pageSize", "1000")]),
+        // Installed models; the daemon is local, so this works offline
+        Provider::Ollama => with_optional_bearer(
+            client.get(format!("{}/api/tags", ollama_base_url())),
+            api_key,
+        ),
+    };
+
+    let response = request.send().await.with_context(|| {
+        if provider == Provider::Ollama {
+            format!(
+                "Failed to reach Ollama at {} (is `ollama serve` running?)",
+                ollama_base_url()
+            )
+        } else {
+            format!("Failed to reach {provider}")
+        }
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!("{provider} returned {status} when listing models");
+    }
+
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .context("Invalid model list response")?;
+    Ok(parse_models(provider, &body))
+}
+
+/// Send a one-token completion and report the round-trip latency
+pub async fn test_connection(
+    provider: Provider,
+    api_key: &str,
+    headers: &[(String, String)],
+    model: &str,
+) -> Result<std::time::Duration, ConnectionError> {
+    if api_key.is_empty() && provider.requires_api_key() {
+        return Err(ConnectionError::MissingApiKey(provider.api_key_env()));
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .default_headers(header_map(headers))
+        .build()
+        .map_err(|e| ConnectionError::Network(e.to_string()))?;
+
+    let request = match provider {
+        Provider::OpenAI => client
+            .post("https://api.openai.com/v1/chat/completions")
+            .bearer_auth(api_key)
+            .json(&serde_json::json!({
+                "model": model,
+                "messages": [{"role": "user", "content": "ping"}],
+                "max_completion_tokens": 16,
+            })),
+        Provider::Anthropic => client
+            .post("https://api.anthropic.com/v1/messages")
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01")
+            .json(&serde_json::json!({
+                "model": model,
+                "max_tokens": 1,
+                "messages": [{"role": "user", "content": "ping"}],
+            })),
+        Provider::Google => client
+            .post(format!(
+                "https://generativelanguage.googleapis.com/v1beta/models/{model}:generateContent"
+            ))
+            .query(&[("key", api_key)])
+            .json(&serde_json::json!({
+                "contents": [{"parts": [{"text": "ping"}]}],
+                "generationConfig": {"maxOutputTokens": 1},
+            })),
+        Provider::Ollama => with_optional_bearer(
+            client.post(format!("{}/api/chat", ollama_base_url())),
+            api_key,
+        )
+        .json(&serde_json::json!({
+            "model": model,
+            "messages": [{"role": "user", "content": "ping"}],
+            "stream": false,
+            "options": {"num_predict": 1},
+        })),
+    };
+
+    let started = Instant::now();
+    let response = request
+        .send()
+        .await
+        .map_err(|e| ConnectionError::Network(e.without_url().to_string()))?;
+    let elapsed = started.elapsed();
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(elapsed);
+    }
+
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    Err(classify_error(provider, model, status.as_u16(), &body))
+}
+
+/// Map a failed completion response to a precise error
+fn classify_error(
+    provider: Provider,
+    model: &str,
+    status: u16,
+    body: &serde_json::Value,
+) -> ConnectionError {
+    // Ollama reports errors as a bare `{"error": "..."}` string
+    let message = body
+        .pointer("/error/message")
+        .or_else(|| body.get("error"))
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let mentions_model = message.to_lowercase().contains("model");
+
+    match status {
+        401 | 403 => ConnectionError::InvalidKey(provider),
+        // Google reports bad keys as 400 INVALID_ARGUMENT
+        400 if message.contains("API key") => ConnectionError::InvalidKey(provider),
+        404 => ConnectionError::ModelNotFound(model.to_string()),
+        400 if mentions_model => ConnectionError::ModelNotFound(model.to_string()),
+        429 => ConnectionError::RateLimited(provider),
+        _ => ConnectionError::Api {
+            provider,
+            status,
+            message,
+        },
+    }
+}
+
+/// Extract model identifiers from a list endpoint response, sorted and deduplicated
+fn parse_models(provider: Provider, body: &serde_json::Value) -> Vec<String> {
+    let (array_key, id_key) = match provider {
+        Provider::OpenAI | Provider::Anthropic => ("data", "id"),
+        Provider::Google | Provider::Ollama => ("models", "name"),
+    };
+
+    let mut models: Vec<String> = body
+        .get(array_key)
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|entry| match provider {
+            // Only models that can generate text are useful here
+            Provider::Google => entry
+                .get("supportedGenerationMethods")
+                .and_then(serde_json::Value::as_array)
+                .is_none_or(|methods| methods.iter().any(|m| m == "generateContent")),
+            Provider::OpenAI | Provider::Anthropic | Provider::Ollama => true,
+        })
+        .filter_map(|entry| entry.get(id_key)?.as_str())
+        .map(|id| id.strip_prefix("models/").unwrap_or(id).to_string())
+        // Gemma and other open models on the same API can't call tools
+        .filter(|id| provider != Provider::Google || id.starts_with("gemini-"))
+        .collect();
+
+    models.sort();
+    models.dedup();
+    models
+}
+
+/// Attach a bearer token if one is set (Ollama behind an authenticating proxy)
+fn with_optional_bearer(
+    request: reqwest::RequestBuilder,
+    api_key: &str,
+) -> reqwest::RequestBuilder {
+    if api_key.is_empty() {
+        request
+    } else {
+        request.bearer_auth(api_key)
+    }
+}
+
+/// Cache file location for a provider
+fn cache_path(provider: Provider) -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| {
+        dir.join("git-iris")
+            .join("models")
+            .join(format!("{}.json", provider.name()))
+    })
+}
+
+fn load_cache(provider: Provider) -> Option<CachedModels> {
+    let content = std::fs::read_to_string(cache_path(provider)?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_cache(provider: Provider, models: &[String]) -> Result<()> {
+    let path = cache_path(provider).context("No cache directory available")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let cached = CachedModels {
+        fetched_at: Utc::now(),
+        models: models.to_vec(),
+    };
+    std::fs::write(&path, serde_json::to_string_pretty(&cached)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_openai_models() {
+        let body = json!({
+            "data": [{"id": "gpt-5.1"}, {"id": "gpt-4o"}, {"id": "gpt-5.1"}]
+        });
+        assert_eq!(
+            parse_models(Provider::OpenAI, &body),
+            vec!["gpt-4o", "gpt-5.1"]
+        );
+    }
+
+    #[test]
+    fn test_parse_google_models_strips_prefix_and_filters() {
+        let body = json!({
+            "models": [
+                {"name": "models/gemini-2.5-flash", "supportedGenerationMethods": ["generateContent"]},
+                {"name": "models/gemini-1.5-pro", "supportedGenerationMethods": ["generateContent"]},
+                {"name": "models/gemma-3-27b-it", "supportedGenerationMethods": ["generateContent"]},
+                {"name": "models/text-embedding-004", "supportedGenerationMethods": ["embedContent"]}
+            ]
+        });
+        assert_eq!(
+            parse_models(Provider::Google, &body),
+            vec!["gemini-1.5-pro", "gemini-2.5-flash"]
+        );
+    }
+
+    #[test]
+    fn test_parse_ollama_models() {
+        let body = json!({
+            "models": [
+                {"name": "qwen2.5-coder:7b", "size": 4_683_087_332_u64},
+                {"name": "llama3.1:latest"}
+            ]
+        });
+        assert_eq!(
+            parse_models(Provider::Ollama, &body),
+            vec!["llama3.1:latest", "qwen2.5-coder:7b"]
+        );
+        let body = json!({"error": "model \"mistral\" not found, try pulling it first"});
+        assert!(matches!(
+            classify_error(Provider::Ollama, "mistral", 404, &body),
+            ConnectionError::ModelNotFound(_)
+        ));
+        assert_eq!(resolve_api_key(Provider::Ollama, None).as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_parse_unexpected_shape() {
+        assert!(parse_models(Provider::Anthropic, &json!({"error": "nope"})).is_empty());
+    }
+
+    #[test]
+    fn test_classify_errors() {
+        let body = json!({"error": {"message": "The model `gpt-9` does not exist"}});
+        assert!(matches!(
+            classify_error(Provider::OpenAI, "gpt-9", 400, &body),
+            ConnectionError::ModelNotFound(_)
+        ));
+        assert!(matches!(
+            classify_error(Provider::Anthropic, "claude", 401, &json!({})),
+            ConnectionError::InvalidKey(Provider::Anthropic)
+        ));
+        let body = json!({"error": {"message": "API key not valid. Please pass a valid API key."}});
+        assert!(matches!(
+            classify_error(Provider::Google, "gemini", 400, &body),
+            ConnectionError::InvalidKey(Provider::Google)
+        ));
+        assert!(matches!(
+            classify_error(Provider::OpenAI, "gpt", 500, &json!({})),
+            ConnectionError::Api { status: 500, .. }
+        ));
+    }
+
+    #[test]
+    fn test_resolve_api_key_prefers_configured() {
+        assert_eq!(
+            resolve_api_key(Provider::OpenAI, Some("sk-test")).as_deref(),
+            Some("sk-test")
+        );
+    }
+}
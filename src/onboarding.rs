@@ -0,0 +1,496 @@
+//! Onboarding report for newcomers to a repository
+//!
+//! Facts come straight from the repository: the directory layout, build and
+//! test commands declared in manifests, recent churn, who works on each
+//! subsystem, and which files to read first. Iris adds a written overview on
+//! top when an AI summary is requested.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use crate::git::{GitRepo, parse_log};
+use crate::pr_split::directory_key;
+
+/// Directories shown in the structure overview
+const MAX_DIRECTORIES: usize = 12;
+
+/// Files listed as churn hot spots
+const MAX_HOT_SPOTS: usize = 10;
+
+/// Subsystems listed with their contributors
+const MAX_SUBSYSTEMS: usize = 10;
+
+/// Contributors listed per subsystem
+const MAX_CONTRIBUTORS: usize = 3;
+
+/// Suggested first files
+const MAX_FIRST_FILES: usize = 8;
+
+/// Docs worth reading before any code, in reading order
+const INTRO_DOCS: &[&str] = &[
+    "README.md",
+    "README.rst",
+    "README",
+    "CONTRIBUTING.md",
+    "AGENTS.md",
+    "CLAUDE.md",
+    "ARCHITECTURE.md",
+    "docs/README.md",
+];
+
+/// Common program entry points
+const ENTRY_POINTS: &[&str] = &[
+    "src/main.rs",
+    "src/lib.rs",
+    "main.go",
+    "src/index.ts",
+    "src/index.js",
+    "index.ts",
+    "index.js",
+    "src/main.py",
+    "main.py",
+    "app.py",
+    "manage.py",
+    "src/main.c",
+    "src/main.cpp",
+];
+
+/// A top-level directory and how many tracked files it holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectorySummary {
+    pub path: String,
+    pub files: usize,
+}
+
+/// A command newcomers need, with where it was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectCommand {
+    /// `Build`, `Test`, `Lint`, `Run`, …
+    pub purpose: String,
+    pub command: String,
+    /// File that declares the command
+    pub source: String,
+}
+
+/// Recent activity in one subsystem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subsystem {
+    pub path: String,
+    pub commits: usize,
+    /// Authors with their commit counts, most active first
+    pub contributors: Vec<(String, usize)>,
+}
+
+/// Commit counts per file and per subsystem author from `git log`
+#[derive(Debug, Default)]
+pub struct Activity {
+    pub files: HashMap<String, usize>,
+    pub subsystems: HashMap<String, HashMap<String, usize>>,
+}
+
+impl Activity {
+    /// Count commits per file, and per author in each subsystem, from
+    /// `git log --format=%x1e%an --name-only` output
+    ///
+    /// Several files changed in one subsystem still make one commit there.
+    pub fn from_git_log(output: &str) -> Self {
+        let mut activity = Self::default();
+        for record in parse_log(output) {
+            let mut touched: Vec<String> = Vec::new();
+            for file in record.files {
+                *activity.files.entry(file.path.to_string()).or_default() += 1;
+                let key = directory_key(file.path);
+                if !touched.contains(&key) {
+                    touched.push(key);
+                }
+            }
+            for key in touched {
+                *activity
+                    .subsystems
+                    .entry(key)
+                    .or_default()
+                    .entry(record.author.to_string())
+                    .or_default() += 1;
+            }
+        }
+        activity
+    }
+
+    /// Most frequently changed files, most active first
+    pub fn hot_spots(&self, limit: usize) -> Vec<(String, usize)> {
+        let mut files: Vec<(String, usize)> = self
+            .files
+            .iter()
+            .map(|(path, commits)| (path.clone(), *commits))
+            .collect();
+        files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        files.truncate(limit);
+        files
+    }
+
+    /// Busiest subsystems with their top contributors
+    pub fn subsystems(&self, limit: usize) -> Vec<Subsystem> {
+        let mut subsystems: Vec<Subsystem> = self
+            .subsystems
+            .iter()
+            .map(|(path, authors)| {
+                let mut contributors: Vec<(String, usize)> = authors
+                    .iter()
+                    .map(|(name, commits)| (name.clone(), *commits))
+                    .collect();
+                contributors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                let commits = contributors.iter().map(|(_, c)| c).sum();
+                contributors.truncate(MAX_CONTRIBUTORS);
+                Subsystem {
+                    path: path.clone(),
+                    commits,
+                    contributors,
+                }
+            })
+            .collect();
+        subsystems.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.path.cmp(&b.path)));
+        subsystems.truncate(limit);
+        subsystems
+    }
+}
+
+/// Everything a newcomer report is built from
+#[derive(Debug)]
+pub struct OnboardingReport {
+    pub project: String,
+    pub tracked_files: usize,
+    pub structure: Vec<DirectorySummary>,
+    pub commands: Vec<ProjectCommand>,
+    pub hot_spots: Vec<(String, usize)>,
+    pub subsystems: Vec<Subsystem>,
+    /// Files to read first, with why
+    pub first_files: Vec<(String, &'static str)>,
+    /// Size of the activity window in days
+    pub window_days: u32,
+}
+
+impl OnboardingReport {
+    /// Gather facts from the repository and the last `window_days` of history
+    pub fn build(repo: &GitRepo, window_days: u32) -> Result<Self> {
+        let root = repo.repo_path();
+        let files = repo.get_all_tracked_files()?;
+        let mut activity = Activity::from_git_log(&recent_log(root, window_days)?);
+        let tracked: HashSet<&String> = files.iter().collect();
+        activity.files.retain(|path, _| tracked.contains(path));
+        let hot_spots = activity.hot_spots(MAX_HOT_SPOTS);
+
+        Ok(Self {
+            project: root
+                .file_name()
+                .map_or_else(|| "repository".to_string(), |n| n.to_string_lossy().into()),
+            tracked_files: files.len(),
+            structure: summarize_structure(&files),
+            commands: detect_commands(root, &files),
+            first_files: suggest_first_files(&files, &hot_spots),
+            subsystems: activity.subsystems(MAX_SUBSYSTEMS),
+            hot_spots,
+            window_days,
+        })
+    }
+
+    /// Markdown report, optionally led by Iris's overview
+    pub fn to_markdown(&self, overview: Option<&str>) -> String {
+        let mut out = format!("# Onboarding: {}\n\n", self.project);
+        if let Some(overview) = overview.map(str::trim).filter(|o| !o.is_empty()) {
+            out.push_str(&format!("## Overview\n\n{overview}\n\n"));
+        }
+
+        out.push_str(&format!(
+            "## Project Structure\n\n{} tracked files.\n\n",
+            self.tracked_files
+        ));
+        for dir in &self.structure {
+            let noun = if dir.files == 1 { "file" } else { "files" };
+            out.push_str(&format!("- `{}` ({} {noun})\n", dir.path, dir.files));
+        }
+
+        out.push_str("\n## Build and Test\n\n");
+        if self.commands.is_empty() {
+            out.push_str("No build or test commands found in the usual manifests.\n");
+        } else {
+            out.push_str("| Purpose | Command | From |\n| --- | --- | --- |\n");
+            for cmd in &self.commands {
+                out.push_str(&format!(
+                    "| {} | `{}` | `{}` |\n",
+                    cmd.purpose, cmd.command, cmd.source
+                ));
+            }
+        }
+
+        out.push_str(&format!(
+            "\n## Hot Areas (last {} days)\n\n",
+            self.window_days
+        ));
+        if self.hot_spots.is_empty() {
+            out.push_str("No commits in this window.\n");
+        }
+        for (path, commits) in &self.hot_spots {
+            out.push_str(&format!("- `{path}`: {commits} commits\n"));
+        }
+
+        if !self.subsystems.is_empty() {
+            out.push_str("\n## Key Contributors\n\n| Subsystem | Commits | Contributors |\n| --- | --- | --- |\n");
+            for subsystem in &self.subsystems {
+                let contributors = subsystem
+                    .contributors
+                    .iter()
+                    .map(|(name, commits)| format!("{name} ({commits})"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                out.push_str(&format!(
+                    "| `{}` | {} | {} |\n",
+                    subsystem.path, subsystem.commits, contributors
+                ));
+            }
+        }
+
+        out.push_str("\n## Read First\n\n");
+        for (i, (path, reason)) in self.first_files.iter().enumerate() {
+            out.push_str(&format!("{}. `{path}`: {reason}\n", i + 1));
+        }
+        out
+    }
+
+    /// Task prompt handing the facts to Iris
+    pub fn to_prompt(&self) -> String {
+        format!(
+            "Write the overview for a newcomer onboarding report on `{}`. \
+             These facts were computed from the repository and are shown to the reader \
+             after your overview:\n\n{}",
+            self.project,
+            self.to_markdown(None)
+        )
+    }
+}
+
+/// Run `git log` over the activity window
+pub(crate) fn recent_log(root: &Path, window_days: u32) -> Result<String> {
+    let since = format!("--since={window_days} days ago");
+    let output = crate::git::git_command()
+        .arg("-C")
+        .arg(root)
+        .args([
+            "log",
+            "--no-merges",
+            &since,
+            "--format=%x1e%an",
+            "--name-only",
+        ])
+        .output()
+        .context("Failed to run git log")?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Top-level directories by size, with root files counted together
+fn summarize_structure(files: &[String]) -> Vec<DirectorySummary> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for file in files {
+        let key = file
+            .split_once('/')
+            .map_or_else(|| "(root files)".to_string(), |(dir, _)| format!("{dir}/"));
+        *counts.entry(key).or_default() += 1;
+    }
+    let mut dirs: Vec<DirectorySummary> = counts
+        .into_iter()
+        .map(|(path, files)| DirectorySummary { path, files })
+        .collect();
+    dirs.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.path.cmp(&b.path)));
+    dirs.truncate(MAX_DIRECTORIES);
+    dirs
+}
+
+/// Build, test and lint commands declared by the project's tooling
+pub(crate) fn detect_commands(root: &Path, files: &[String]) -> Vec<ProjectCommand> {
+    let has = |name: &str| files.iter().any(|f| f == name);
+    let mut commands = Vec::new();
+    let mut push = |purpose: &str, command: String, source: &str| {
+        commands.push(ProjectCommand {
+            purpose: purpose.to_string(),
+            command,
+            source: source.to_string(),
+        });
+    };
+
+    if has("Cargo.toml") {
+        push("Build", "cargo build".into(), "Cargo.toml");
+        push("Test", "cargo test".into(), "Cargo.toml");
+        push("Lint", "cargo clippy --all-targets".into(), "Cargo.toml");
+    }
+    if has("package.json")
+        && let Some(scripts) = read_package_scripts(&root.join("package.json"))
+    {
+        let runner = if has("pnpm-lock.yaml") {
+            "pnpm"
+        } else if has("yarn.lock") {
+            "yarn"
+        } else {
+            "npm run"
+        };
+        for (script, purpose) in [
+            ("build", "Build"),
+            ("test", "Test"),
+            ("lint", "Lint"),
+            ("dev", "Run"),
+            ("start", "Run"),
+        ] {
+            if scripts.iter().any(|s| s == script) {
+                push(purpose, format!("{runner} {script}"), "package.json");
+            }
+        }
+    }
+    if has("go.mod") {
+        push("Build", "go build ./...".into(), "go.mod");
+        push("Test", "go test ./...".into(), "go.mod");
+    }
+    if has("pyproject.toml") {
+        push("Install", "pip install -e .".into(), "pyproject.toml");
+        push("Test", "pytest".into(), "pyproject.toml");
+    }
+    for (file, runner) in [("Makefile", "make"), ("justfile", "just")] {
+        if !has(file) {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(root.join(file)) else {
+            continue;
+        };
+        for target in ["build", "test", "lint", "check", "run"] {
+            if declares_target(&content, target) {
+                push(&capitalize(target), format!("{runner} {target}"), file);
+            }
+        }
+    }
+    commands
+}
+
+/// Script names from `package.json`
+fn read_package_scripts(path: &Path) -> Option<Vec<String>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    Some(json.get("scripts")?.as_object()?.keys().cloned().collect())
+}
+
+/// Whether a Makefile or justfile defines `target`
+fn declares_target(content: &str, target: &str) -> bool {
+    content.lines().any(|line| {
+        line.strip_prefix(target)
+            .is_some_and(|rest| rest.starts_with(':') || rest.starts_with(" :"))
+    })
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+/// Tracked files that are common program entry points
+pub(crate) fn entry_points(files: &[String]) -> Vec<String> {
+    ENTRY_POINTS
+        .iter()
+        .filter(|entry| files.iter().any(|f| f == *entry))
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Docs first, then entry points, then the busiest files
+fn suggest_first_files(
+    files: &[String],
+    hot_spots: &[(String, usize)],
+) -> Vec<(String, &'static str)> {
+    let mut suggested: Vec<(String, &'static str)> = Vec::new();
+    let mut add = |path: &str, reason: &'static str| {
+        if files.iter().any(|f| f == path) && !suggested.iter().any(|(p, _)| p == path) {
+            suggested.push((path.to_string(), reason));
+        }
+    };
+
+    for doc in INTRO_DOCS {
+        // AGENTS.md and CLAUDE.md are often the same file behind a symlink
+        if *doc == "CLAUDE.md" && files.iter().any(|f| f == "AGENTS.md") {
+            continue;
+        }
+        add(doc, "project overview and conventions");
+    }
+    for entry in entry_points(files) {
+        add(&entry, "entry point");
+    }
+    for (path, _) in hot_spots {
+        add(path, "changes most often");
+    }
+    suggested.truncate(MAX_FIRST_FILES);
+    suggested
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_groups_authors_by_subsystem() {
+        let log = "\x1eAda\nsrc/agents/iris.rs\nsrc/agents/setup.rs\n\n\
+                   \x1eGrace\nsrc/agents/iris.rs\ndocs/index.md\n\n\
+                   \x1eAda\nsrc/studio/app/mod.rs\n";
+        let activity = Activity::from_git_log(log);
+
+        assert_eq!(
+            activity.hot_spots(1),
+            [("src/agents/iris.rs".to_string(), 2)]
+        );
+        let subsystems = activity.subsystems(10);
+        assert_eq!(subsystems[0].path, "src/agents");
+        assert_eq!(subsystems[0].commits, 2);
+        assert_eq!(
+            subsystems[0].contributors,
+            [("Ada".to_string(), 1), ("Grace".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_structure_and_first_files() {
+        let files: Vec<String> = [
+            "README.md",
+            "Cargo.toml",
+            "src/main.rs",
+            "src/lib.rs",
+            "src/git/mod.rs",
+            "docs/index.md",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+        let structure = summarize_structure(&files);
+        assert_eq!(
+            structure[0],
+            DirectorySummary {
+                path: "src/".to_string(),
+                files: 3
+            }
+        );
+
+        let hot = [("src/git/mod.rs".to_string(), 4)];
+        let first: Vec<String> = suggest_first_files(&files, &hot)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(
+            first,
+            ["README.md", "src/main.rs", "src/lib.rs", "src/git/mod.rs"]
+        );
+    }
+
+    #[test]
+    fn test_declares_target() {
+        let makefile = "build:\n\tcargo build\ntest : build\n\tcargo test\n";
+        assert!(declares_target(makefile, "build"));
+        assert!(declares_target(makefile, "test"));
+        assert!(!declares_target(makefile, "lint"));
+    }
+}
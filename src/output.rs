@@ -1,8 +1,87 @@
-//! Git output formatting utilities
+//! Output formatting utilities
+//!
+//! Formats git results for the terminal, and prints command results as JSON
+//! for `--output json`. In JSON mode stdout carries only the result; messages,
+//! spinners and logs go to stderr.
 
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::agents::StructuredResponse;
 use crate::git::CommitResult;
 use git2::FileMode;
-use std::fmt::Write;
+
+/// Version of the `--output json` envelope
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// Whether command results are printed as JSON
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// How commands print their results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Formatted for reading in the terminal
+    #[default]
+    Text,
+    /// One JSON document on stdout, everything else on stderr
+    Json,
+}
+
+/// Switch result output to `format` for the rest of the process
+pub fn set_output_format(format: OutputFormat) {
+    let json = format == OutputFormat::Json;
+    JSON_OUTPUT.store(json, Ordering::Relaxed);
+    if json {
+        crate::ui::set_messages_to_stderr(true);
+        crate::logger::set_log_to_stderr(true);
+    }
+}
+
+/// Whether results are printed as JSON
+pub fn is_json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// What `--output json` prints: the command and its result
+#[derive(Debug, Serialize)]
+struct JsonEnvelope<'a, T: Serialize> {
+    schema_version: u32,
+    command: &'a str,
+    result: &'a T,
+}
+
+/// Print `result` of `command` as the JSON envelope on stdout
+pub fn print_json<T: Serialize>(command: &str, result: &T) -> Result<()> {
+    println!("{}", json_envelope(command, result)?);
+    Ok(())
+}
+
+fn json_envelope<T: Serialize>(command: &str, result: &T) -> Result<String> {
+    let envelope = JsonEnvelope {
+        schema_version: JSON_SCHEMA_VERSION,
+        command,
+        result,
+    };
+    Ok(serde_json::to_string_pretty(&envelope)?)
+}
+
+/// Print an agent response as the JSON envelope, unwrapped to the
+/// generated type (`GeneratedMessage`, `MarkdownReview`, …)
+pub fn print_response_json(command: &str, response: &StructuredResponse) -> Result<()> {
+    match response {
+        StructuredResponse::CommitMessage(message) => print_json(command, message),
+        StructuredResponse::PullRequest(pr) => print_json(command, pr),
+        StructuredResponse::Changelog(changelog) => print_json(command, changelog),
+        StructuredResponse::ReleaseNotes(notes) => print_json(command, notes),
+        StructuredResponse::MarkdownReview(review) => print_json(command, review),
+        StructuredResponse::SemanticBlame(text) | StructuredResponse::PlainText(text) => {
+            print_json(command, text)
+        }
+    }
+}
 
 /// Formats a commit result into a human-readable string
 pub fn format_commit_result(result: &CommitResult, message: &str) -> String {
@@ -49,3 +128,25 @@ fn format_file_mode(mode: FileMode) -> String {
     }
     .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::GeneratedMessage;
+
+    #[test]
+    fn test_json_envelope() {
+        let message = GeneratedMessage {
+            emoji: None,
+            title: "Add retries".to_string(),
+            message: "Retry failed fetches".to_string(),
+            completion_message: None,
+            variant: None,
+        };
+        let json = json_envelope("gen", &message).expect("serialize");
+        let value: serde_json::Value = serde_json::from_str(&json).expect("valid JSON");
+        assert_eq!(value["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(value["command"], "gen");
+        assert_eq!(value["result"]["title"], "Add retries");
+    }
+}
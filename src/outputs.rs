@@ -0,0 +1,157 @@
+//! Files generated artifacts are saved to
+//!
+//! `[outputs]` maps each artifact to a path relative to the repository root,
+//! used by `--write` and Studio's save action. Paths may contain
+//! `{version}`, `{branch}` and `{date}`, so release notes and reviews each
+//! get their own file. Missing directories are created. A changelog is
+//! merged into its file like `changelog --update` does; release notes and
+//! reviews replace the file.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Version used when a path needs `{version}` and none is known
+const UNRELEASED: &str = "unreleased";
+
+/// Branch used when a path needs `{branch}` and HEAD is detached
+const DETACHED: &str = "detached";
+
+/// Something Iris generates that can be saved to a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Artifact {
+    Changelog,
+    ReleaseNotes,
+    Review,
+}
+
+impl Artifact {
+    /// Display name
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Changelog => "Changelog",
+            Self::ReleaseNotes => "Release notes",
+            Self::Review => "Review",
+        }
+    }
+}
+
+/// `[outputs]` settings: where each artifact is written
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct OutputsConfig {
+    pub changelog: String,
+    pub release_notes: String,
+    pub review: String,
+}
+
+impl Default for OutputsConfig {
+    fn default() -> Self {
+        Self {
+            changelog: "CHANGELOG.md".to_string(),
+            release_notes: "docs/releases/{version}.md".to_string(),
+            review: ".reviews/{branch}.md".to_string(),
+        }
+    }
+}
+
+impl OutputsConfig {
+    /// Whether the settings are all defaults
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Configured path template for `artifact`
+    pub fn template(&self, artifact: Artifact) -> &str {
+        match artifact {
+            Artifact::Changelog => &self.changelog,
+            Artifact::ReleaseNotes => &self.release_notes,
+            Artifact::Review => &self.review,
+        }
+    }
+
+    /// Path for `artifact` with its placeholders filled in
+    pub fn path(&self, artifact: Artifact, version: Option<&str>, branch: Option<&str>) -> String {
+        let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        fill_placeholders(self.template(artifact), version, branch, &date)
+    }
+}
+
+/// Replace `{version}`, `{branch}` and `{date}` in `template`
+///
+/// Slashes in the version and branch become dashes, so `feature/login`
+/// names a file rather than a directory.
+fn fill_placeholders(
+    template: &str,
+    version: Option<&str>,
+    branch: Option<&str>,
+    date: &str,
+) -> String {
+    let segment = |value: Option<&str>, fallback: &str| {
+        let value = value.map(str::trim).filter(|v| !v.is_empty());
+        value.unwrap_or(fallback).replace(['/', '\\'], "-")
+    };
+    template
+        .replace("{version}", &segment(version, UNRELEASED))
+        .replace("{branch}", &segment(branch, DETACHED))
+        .replace("{date}", date)
+}
+
+/// Create the directories `path` needs
+pub fn ensure_parent_dir(path: &Path) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    Ok(())
+}
+
+/// Write `content` to `path`, replacing the file and creating directories
+pub fn write_artifact(artifact: Artifact, path: &Path, content: &str) -> Result<()> {
+    crate::read_only::ensure_writable(&format!("Writing the {}", artifact.label()))?;
+    ensure_parent_dir(path)?;
+    let mut content = content.trim_end().to_string();
+    content.push('\n');
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_paths() {
+        let outputs = OutputsConfig::default();
+        assert_eq!(
+            outputs.path(Artifact::ReleaseNotes, Some("v1.2.0"), None),
+            "docs/releases/v1.2.0.md"
+        );
+        assert_eq!(
+            outputs.path(Artifact::ReleaseNotes, None, None),
+            "docs/releases/unreleased.md"
+        );
+        assert_eq!(
+            outputs.path(Artifact::Review, None, Some("feature/login")),
+            ".reviews/feature-login.md"
+        );
+        assert_eq!(
+            outputs.path(Artifact::Review, None, None),
+            ".reviews/detached.md"
+        );
+        assert_eq!(
+            fill_placeholders(
+                "notes/{date}-{version}.md",
+                Some(" 2.0 "),
+                None,
+                "2026-01-31"
+            ),
+            "notes/2026-01-31-2.0.md"
+        );
+
+        let custom: OutputsConfig = toml::from_str("review = \"reviews/{branch}.md\"").unwrap();
+        assert_eq!(custom.changelog, "CHANGELOG.md");
+        assert_eq!(custom.template(Artifact::Review), "reviews/{branch}.md");
+        assert!(!custom.is_default());
+    }
+}
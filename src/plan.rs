@@ -0,0 +1,387 @@
+//! Work plans drafted before any code is written
+//!
+//! `git-iris plan "<task>"` asks Iris to turn a task description into a
+//! branch name, a sequence of commits and the files they'll likely touch. The
+//! plan is saved with the companion data; commits that land afterwards are
+//! matched against it by subject and files, so `git-iris plan` with no task and
+//! Studio's dashboard show it as a checklist that fills in as work lands.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use git2::{Repository, Sort};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Similarity at which a landed commit counts as a planned one
+const MATCH_THRESHOLD: f64 = 0.5;
+
+/// A commit the plan expects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedCommit {
+    pub subject: String,
+    /// Files the commit is expected to touch
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+/// A file the plan expects to touch, and why
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedFile {
+    pub path: String,
+    #[serde(default)]
+    pub note: String,
+}
+
+/// A drafted plan for one task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Plan {
+    /// The task as the developer described it
+    pub task: String,
+    pub title: String,
+    /// Suggested branch name
+    pub branch: String,
+    pub commits: Vec<PlannedCommit>,
+    pub files: Vec<PlannedFile>,
+    /// `HEAD` when the plan was made; later commits count as progress
+    pub base_commit: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Plan {
+    /// Parse the plan Iris drafted
+    ///
+    /// Expects a `# Title`, a `Branch:` line, and `## Commits` and `## Files`
+    /// lists whose items lead with a backticked subject or path, followed by
+    /// ` — ` and the files or a note.
+    pub fn from_markdown(task: &str, markdown: &str, base_commit: &str) -> Result<Self> {
+        let mut title = String::new();
+        let mut branch = String::new();
+        let mut commits = Vec::new();
+        let mut files = Vec::new();
+        let mut section = "";
+
+        for line in markdown.lines().map(str::trim) {
+            if let Some(heading) = line.strip_prefix("## ") {
+                section = if heading.to_lowercase().contains("commit") {
+                    "commits"
+                } else if heading.to_lowercase().contains("file") {
+                    "files"
+                } else {
+                    ""
+                };
+                continue;
+            }
+            if let Some(heading) = line.strip_prefix("# ") {
+                title = heading.trim().to_string();
+                continue;
+            }
+            if let Some(name) = line
+                .strip_prefix("Branch:")
+                .or_else(|| line.strip_prefix("**Branch:**"))
+            {
+                branch = name.trim().trim_matches('`').to_string();
+                continue;
+            }
+            let Some(item) = list_item(line) else {
+                continue;
+            };
+            let (lead, rest) = split_item(item);
+            match section {
+                "commits" if !lead.is_empty() => commits.push(PlannedCommit {
+                    subject: lead,
+                    files: backticked(rest),
+                }),
+                "files" if !lead.is_empty() => files.push(PlannedFile {
+                    path: lead,
+                    note: rest.trim().to_string(),
+                }),
+                _ => {}
+            }
+        }
+
+        if commits.is_empty() {
+            anyhow::bail!("The drafted plan lists no commits");
+        }
+        if title.is_empty() {
+            title = task.lines().next().unwrap_or_default().trim().to_string();
+        }
+        Ok(Self {
+            task: task.trim().to_string(),
+            title,
+            branch,
+            commits,
+            files,
+            base_commit: base_commit.to_string(),
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Which planned commits have landed, as the short hash of the matching
+    /// commit, in plan order
+    ///
+    /// `landed` is oldest first. Each landed commit checks off the best
+    /// matching planned commit that isn't checked off yet.
+    pub fn progress(&self, landed: &[LandedCommit]) -> Vec<Option<String>> {
+        let mut done: Vec<Option<String>> = vec![None; self.commits.len()];
+        for commit in landed {
+            let best = self
+                .commits
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| done[*i].is_none())
+                .map(|(i, planned)| (i, similarity(planned, commit)))
+                .filter(|(_, score)| *score >= MATCH_THRESHOLD)
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((i, _)) = best {
+                done[i] = Some(commit.short_hash.clone());
+            }
+        }
+        done
+    }
+
+    /// Plain-text checklist, one planned commit per line
+    pub fn checklist(&self, progress: &[Option<String>]) -> String {
+        let mut out = format!("{}\n", self.title);
+        if !self.branch.is_empty() {
+            out.push_str(&format!("Branch: {}\n", self.branch));
+        }
+        out.push('\n');
+        for (commit, landed) in self.commits.iter().zip(progress) {
+            match landed {
+                Some(hash) => out.push_str(&format!("  [x] {} ({hash})\n", commit.subject)),
+                None => out.push_str(&format!("  [ ] {}\n", commit.subject)),
+            }
+        }
+        if !self.files.is_empty() {
+            out.push_str("\nFiles:\n");
+            for file in &self.files {
+                if file.note.is_empty() {
+                    out.push_str(&format!("  {}\n", file.path));
+                } else {
+                    out.push_str(&format!("  {} — {}\n", file.path, file.note));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// A commit made since the plan was drafted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LandedCommit {
+    pub short_hash: String,
+    pub subject: String,
+    pub files: Vec<String>,
+}
+
+/// A plan and which of its commits have landed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanProgress {
+    pub plan: Plan,
+    /// Short hash of the matching commit for each planned commit
+    pub landed: Vec<Option<String>>,
+}
+
+impl PlanProgress {
+    /// Check `plan` against the commits in `repo` since it was made
+    pub fn load(repo: &Repository, plan: Plan) -> Result<Self> {
+        let landed = plan.progress(&landed_commits(repo, &plan.base_commit)?);
+        Ok(Self { plan, landed })
+    }
+
+    pub fn done(&self) -> usize {
+        self.landed.iter().filter(|l| l.is_some()).count()
+    }
+}
+
+/// Non-merge commits reachable from `HEAD` but not `base`, oldest first
+pub fn landed_commits(repo: &Repository, base: &str) -> Result<Vec<LandedCommit>> {
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    walk.push_head()?;
+    if let Ok(base) = repo.revparse_single(base) {
+        walk.hide(base.id())?;
+    }
+
+    let mut commits = Vec::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        let tree = commit.tree()?;
+        let parent = commit.parent(0).ok().map(|p| p.tree()).transpose()?;
+        let diff = repo
+            .diff_tree_to_tree(parent.as_ref(), Some(&tree), None)
+            .context("Failed to diff commit")?;
+        let files = diff
+            .deltas()
+            .filter_map(|d| d.new_file().path().or_else(|| d.old_file().path()))
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        let id = commit.id().to_string();
+        commits.push(LandedCommit {
+            short_hash: id[..7.min(id.len())].to_string(),
+            subject: commit.summary().unwrap_or_default().to_string(),
+            files,
+        });
+    }
+    Ok(commits)
+}
+
+/// How well a landed commit matches a planned one, from 0 to 1
+///
+/// Averages subject word overlap and file overlap; if the plan named no
+/// files for the commit, only the subject counts.
+fn similarity(planned: &PlannedCommit, landed: &LandedCommit) -> f64 {
+    let words = jaccard(
+        &subject_words(&planned.subject),
+        &subject_words(&landed.subject),
+    );
+    if planned.files.is_empty() {
+        return words;
+    }
+    let planned_files: HashSet<String> = planned.files.iter().cloned().collect();
+    let landed_files: HashSet<String> = landed.files.iter().cloned().collect();
+    f64::midpoint(words, jaccard(&planned_files, &landed_files))
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    let shared = u32::try_from(a.intersection(b).count()).unwrap_or(u32::MAX);
+    f64::from(shared) / f64::from(u32::try_from(union).unwrap_or(u32::MAX))
+}
+
+/// Lowercased words of a subject, without the conventional commit prefix
+/// and short filler words
+fn subject_words(subject: &str) -> HashSet<String> {
+    let description = subject
+        .split_once(": ")
+        .filter(|(prefix, _)| !prefix.contains(' '))
+        .map_or(subject, |(_, rest)| rest);
+    description
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() > 2)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Text of a markdown list item, without the marker or checkbox
+fn list_item(line: &str) -> Option<&str> {
+    let item = line
+        .strip_prefix(['-', '*'])
+        .or_else(|| {
+            let (number, rest) = line.split_once(". ")?;
+            number.chars().all(|c| c.is_ascii_digit()).then_some(rest)
+        })?
+        .trim_start();
+    Some(
+        item.strip_prefix("[ ]")
+            .or_else(|| item.strip_prefix("[x]"))
+            .unwrap_or(item)
+            .trim_start(),
+    )
+}
+
+/// The leading backticked (or plain) text of an item and what follows it
+fn split_item(item: &str) -> (String, &str) {
+    if let Some(quoted) = item.strip_prefix('`')
+        && let Some((lead, rest)) = quoted.split_once('`')
+    {
+        let rest = rest.trim_start().trim_start_matches(['—', '–', '-', ':']);
+        return (lead.trim().to_string(), rest);
+    }
+    for separator in [" — ", " – ", " - "] {
+        if let Some((lead, rest)) = item.split_once(separator) {
+            return (lead.trim().to_string(), rest);
+        }
+    }
+    (item.trim().to_string(), "")
+}
+
+/// Every backticked span in `text`
+fn backticked(text: &str) -> Vec<String> {
+    text.split('`')
+        .skip(1)
+        .step_by(2)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Task prompt for planning `task`
+pub fn prompt(task: &str) -> String {
+    format!(
+        "Plan the work for this task before any code is written.\n\n## Task\n{}\n",
+        task.trim()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DRAFT: &str = "# Add retries to the HTTP client\n\n\
+        Branch: `feat/http-retries`\n\n\
+        ## Commits\n\n\
+        - [ ] `feat(http): add RetryPolicy type` — `src/http/retry.rs`, `src/http/mod.rs`\n\
+        - [ ] `feat(http): retry idempotent requests` — `src/http/client.rs`\n\
+        - [ ] `docs: document retry settings` — `docs/http.md`\n\n\
+        ## Files\n\n\
+        - `src/http/retry.rs` — new module for the policy\n\
+        - `src/http/client.rs` — wraps send() in the policy\n";
+
+    #[test]
+    fn test_from_markdown() {
+        let plan = Plan::from_markdown("Add retries", DRAFT, "abc1234").expect("plan");
+        assert_eq!(plan.title, "Add retries to the HTTP client");
+        assert_eq!(plan.branch, "feat/http-retries");
+        assert_eq!(plan.commits.len(), 3);
+        assert_eq!(plan.commits[0].subject, "feat(http): add RetryPolicy type");
+        assert_eq!(
+            plan.commits[0].files,
+            ["src/http/retry.rs", "src/http/mod.rs"]
+        );
+        assert_eq!(plan.files[1].note, "wraps send() in the policy");
+        assert!(Plan::from_markdown("x", "# Nothing planned", "abc").is_err());
+    }
+
+    #[test]
+    fn test_progress_matches_landed_commits() {
+        let plan = Plan::from_markdown("Add retries", DRAFT, "abc1234").expect("plan");
+        let landed = |hash: &str, subject: &str, files: &[&str]| LandedCommit {
+            short_hash: hash.to_string(),
+            subject: subject.to_string(),
+            files: files.iter().map(ToString::to_string).collect(),
+        };
+        let progress = plan.progress(&[
+            landed("1111111", "Fix typo in README", &["README.md"]),
+            landed(
+                "2222222",
+                "feat(http): add a RetryPolicy type",
+                &["src/http/retry.rs", "src/http/mod.rs"],
+            ),
+            landed(
+                "3333333",
+                "feat(http): retry idempotent requests on 503",
+                &["src/http/client.rs"],
+            ),
+        ]);
+        assert_eq!(
+            progress,
+            [
+                Some("2222222".to_string()),
+                Some("3333333".to_string()),
+                None
+            ]
+        );
+
+        let checklist = plan.checklist(&progress);
+        assert!(checklist.contains("  [x] feat(http): add RetryPolicy type (2222222)\n"));
+        assert!(checklist.contains("  [ ] docs: document retry settings\n"));
+    }
+}
@@ -0,0 +1,246 @@
+//! Plumbing commands: a stable scripting interface
+//!
+//! `git-iris plumbing …` is meant to be called from lazygit custom commands,
+//! editor plugins and other TUIs. Output is only the result on stdout: no
+//! banner, spinners or color. Errors go to stderr with a non-zero exit code.
+//! The JSON shapes below are versioned by [`SCHEMA_VERSION`] and only change
+//! additively within a version.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+
+use crate::adr::{AdrDraft, next_number, prompt};
+use crate::agents::{IrisAgentService, StructuredResponse, TaskContext};
+use crate::common::CommonParams;
+use crate::error::IrisError;
+use crate::types::format_commit_message;
+
+/// Version of the JSON output schema
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// `plumbing message --json` output
+#[derive(Debug, Serialize)]
+pub struct MessageOutput {
+    pub schema_version: u32,
+    /// Full commit message, ready for `git commit -F -`
+    pub message: String,
+    /// Subject line without emoji
+    pub title: String,
+    /// Message body
+    pub body: String,
+    /// Gitmoji, if one was chosen
+    pub emoji: Option<String>,
+    pub provider: String,
+    pub model: String,
+}
+
+/// `plumbing review --json` output
+#[derive(Debug, Serialize)]
+pub struct ReviewOutput {
+    pub schema_version: u32,
+    /// Review as markdown
+    pub review: String,
+    pub provider: String,
+    pub model: String,
+}
+
+/// `plumbing adr --json` output
+#[derive(Debug, Serialize)]
+pub struct AdrOutput {
+    pub schema_version: u32,
+    /// Path of the written record
+    pub path: String,
+    pub number: u32,
+    pub title: String,
+    /// Record as markdown
+    pub content: String,
+    pub provider: String,
+    pub model: String,
+}
+
+/// Turn off everything that would pollute stdout
+pub(crate) fn quiet_output() {
+    crate::ui::set_quiet_mode(true);
+    crate::logger::set_log_to_stdout(false);
+    colored::control::set_override(false);
+}
+
+/// Generate a commit message for the staged changes
+pub async fn handle_message(
+    common: CommonParams,
+    json: bool,
+    repository_url: Option<String>,
+) -> Result<()> {
+    quiet_output();
+
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    if let Some(repo) = service.git_repo()
+        && repo.get_git_info(service.config())?.staged_files.is_empty()
+    {
+        return Err(IrisError::NothingStaged.into());
+    }
+
+    let response = service
+        .execute_task("commit", TaskContext::for_gen())
+        .await?;
+    let StructuredResponse::CommitMessage(generated) = response else {
+        bail!("Expected commit message response");
+    };
+    let message = format_commit_message(&generated);
+
+    if json {
+        let output = MessageOutput {
+            schema_version: SCHEMA_VERSION,
+            message,
+            title: generated.title,
+            body: generated.message,
+            emoji: generated.emoji,
+            provider: service.provider().to_string(),
+            model: service.model().to_string(),
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!("{}", message.trim_end());
+    }
+    Ok(())
+}
+
+/// Review staged changes, a commit, or a branch range
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_review(
+    common: CommonParams,
+    json: bool,
+    include_unstaged: bool,
+    commit: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    repository_url: Option<String>,
+) -> Result<()> {
+    quiet_output();
+
+    let context = TaskContext::for_review(commit, from, to, include_unstaged)?;
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    let review = match service.execute_task("review", context).await? {
+        // Raw markdown; Display would apply terminal styling
+        StructuredResponse::MarkdownReview(review) => review.content,
+        other => other.to_string(),
+    };
+
+    if json {
+        let output = ReviewOutput {
+            schema_version: SCHEMA_VERSION,
+            review,
+            provider: service.provider().to_string(),
+            model: service.model().to_string(),
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!("{}", review.trim_end());
+    }
+    Ok(())
+}
+
+/// Draft an architecture decision record and write it to `dir`
+pub async fn handle_adr(
+    common: CommonParams,
+    json: bool,
+    from: &str,
+    to: &str,
+    title: Option<String>,
+    dir: &str,
+    decision: &str,
+    repository_url: Option<String>,
+) -> Result<()> {
+    quiet_output();
+
+    let service = IrisAgentService::from_common_params(&common, repository_url)?;
+    let dir = service
+        .git_repo()
+        .context("Drafting an ADR needs a git repository")?
+        .repo_path()
+        .join(dir);
+    let response = service
+        .execute_task_with_prompt("adr", &prompt(from, to, decision, title.as_deref()))
+        .await?;
+    let draft =
+        AdrDraft::from_markdown(next_number(&dir), &response.to_string(), title.as_deref())?;
+    let path = draft.write(&dir)?;
+
+    if json {
+        let output = AdrOutput {
+            schema_version: SCHEMA_VERSION,
+            path: path.display().to_string(),
+            number: draft.number,
+            title: draft.title,
+            content: draft.content,
+            provider: service.provider().to_string(),
+            model: service.model().to_string(),
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+/// Check a commit message file against `[commit_style]`
+///
+/// Used by the `commit-msg` hook. `#` comments are ignored; violations are
+/// printed to stderr and fail the command so git aborts the commit.
+pub fn handle_check_message(file: &str) -> Result<()> {
+    quiet_output();
+
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read commit message file: {file}"))?;
+    let message = crate::hooks::message_from_file(&content);
+    if message.is_empty() {
+        // git rejects empty messages itself
+        return Ok(());
+    }
+
+    let config = crate::config::Config::load()?;
+    let violations = config.commit_style.check(&message);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    for violation in &violations {
+        eprintln!("{violation}");
+    }
+    bail!(
+        "Commit message breaks {} [commit_style] rule(s); set GIT_IRIS_SKIP_HOOKS=1 to commit anyway",
+        violations.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_schema_is_stable() {
+        let output = MessageOutput {
+            schema_version: SCHEMA_VERSION,
+            message: "feat: add plumbing".to_string(),
+            title: "add plumbing".to_string(),
+            body: String::new(),
+            emoji: None,
+            provider: "openai".to_string(),
+            model: "gpt-5.1".to_string(),
+        };
+        let value = serde_json::to_value(&output).expect("serialize");
+        let mut keys: Vec<_> = value.as_object().expect("object").keys().cloned().collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            [
+                "body",
+                "emoji",
+                "message",
+                "model",
+                "provider",
+                "schema_version",
+                "title"
+            ]
+        );
+    }
+}
@@ -0,0 +1,301 @@
+//! Suggestions for splitting a large branch into several PRs
+//!
+//! Changed files are grouped by code owner (from `CODEOWNERS`) and, where no
+//! owner matches, by directory. Each group becomes a proposed PR listing its
+//! files and the commits that touch them, so reviewers only see code they own.
+
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::risk::ChangedFile;
+
+/// Where `CODEOWNERS` files live, in the order GitHub checks them
+const CODEOWNERS_LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Label of the group collecting files that didn't fit elsewhere
+const OTHER_LABEL: &str = "other changes";
+
+/// Most PRs we propose; smaller groups past this are folded together
+const MAX_PROPOSED: usize = 6;
+
+/// Branches below both limits are fine as a single PR
+pub const SPLIT_MIN_FILES: usize = 15;
+pub const SPLIT_MIN_LINES: usize = 500;
+
+/// Parsed `CODEOWNERS` rules
+#[derive(Debug, Default)]
+pub struct CodeOwners {
+    rules: Vec<(Regex, Vec<String>)>,
+}
+
+impl CodeOwners {
+    /// Load the repository's `CODEOWNERS` file, if it has one
+    pub fn load(root: &Path) -> Option<Self> {
+        CODEOWNERS_LOCATIONS
+            .iter()
+            .map(|location| root.join(location))
+            .find(|path| path.is_file())
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|content| Self::parse(&content))
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .map(str::trim)
+            // Skip comments and GitLab section headers
+            .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('['))
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let pattern = pattern_regex(parts.next()?)?;
+                let owners: Vec<String> = parts
+                    .take_while(|part| !part.starts_with('#'))
+                    .map(String::from)
+                    .collect();
+                Some((pattern, owners))
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Owners of `path`; the last matching rule wins, as on GitHub
+    pub fn owners(&self, path: &str) -> Option<&[String]> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|(pattern, _)| pattern.is_match(path))
+            .map(|(_, owners)| owners.as_slice())
+            .filter(|owners| !owners.is_empty())
+    }
+}
+
+/// A commit and the files it touches
+#[derive(Debug, Clone)]
+pub struct CommitFiles {
+    /// `abc1234 subject`
+    pub summary: String,
+    pub paths: Vec<String>,
+}
+
+/// One suggested PR
+#[derive(Debug, Clone)]
+pub struct ProposedPr {
+    /// Owners or directory the group is built around
+    pub label: String,
+    /// Code owners, when the group came from `CODEOWNERS`
+    pub owners: Vec<String>,
+    pub files: Vec<String>,
+    /// Commits touching these files; ones that also touch other groups are marked
+    pub commits: Vec<String>,
+    pub lines: usize,
+}
+
+impl ProposedPr {
+    /// Task prompt asking for a description of just this part of the branch
+    pub fn to_prompt(&self, from: &str, to: &str, part: usize, total: usize) -> String {
+        let mut prompt = format!(
+            "Generate a pull request description for part {part} of {total} of a split of the \
+             branch {from}..{to}. This PR groups the changes {}. Describe only the files and \
+             commits listed below; use `git_diff` with from=\"{from}\" and to=\"{to}\" and \
+             ignore files outside this list.\n\n## Files\n",
+            if !self.owners.is_empty() {
+                format!("owned by {}", self.owners.join(", "))
+            } else if self.label == OTHER_LABEL {
+                "that did not fit the other parts".to_string()
+            } else {
+                format!("under `{}`", self.label)
+            }
+        );
+        for file in &self.files {
+            prompt.push_str(&format!("- {file}\n"));
+        }
+        if !self.commits.is_empty() {
+            prompt.push_str("\n## Commits\n");
+            for commit in &self.commits {
+                prompt.push_str(&format!("- {commit}\n"));
+            }
+        }
+        prompt
+    }
+}
+
+/// Whether a branch is large enough that splitting is worth suggesting
+pub fn should_split(files: &[ChangedFile]) -> bool {
+    let lines: usize = files.iter().map(|f| f.additions + f.deletions).sum();
+    files.len() >= SPLIT_MIN_FILES || lines >= SPLIT_MIN_LINES
+}
+
+/// Group changed files into proposed PRs
+pub fn plan_split(
+    files: &[ChangedFile],
+    commits: &[CommitFiles],
+    owners: Option<&CodeOwners>,
+) -> Vec<ProposedPr> {
+    let mut groups: BTreeMap<String, ProposedPr> = BTreeMap::new();
+    for file in files {
+        let file_owners = owners.and_then(|o| o.owners(&file.path));
+        let label = file_owners.map_or_else(|| directory_key(&file.path), |o| o.join(" "));
+        let group = groups.entry(label.clone()).or_insert_with(|| ProposedPr {
+            label,
+            owners: file_owners.map(<[String]>::to_vec).unwrap_or_default(),
+            files: Vec::new(),
+            commits: Vec::new(),
+            lines: 0,
+        });
+        group.files.push(file.path.clone());
+        group.lines += file.additions + file.deletions;
+    }
+
+    let mut proposed: Vec<ProposedPr> = groups.into_values().collect();
+    proposed.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.label.cmp(&b.label)));
+
+    if proposed.len() > MAX_PROPOSED {
+        let rest = proposed.split_off(MAX_PROPOSED - 1);
+        proposed.push(ProposedPr {
+            label: OTHER_LABEL.to_string(),
+            owners: Vec::new(),
+            files: rest.iter().flat_map(|p| p.files.clone()).collect(),
+            commits: Vec::new(),
+            lines: rest.iter().map(|p| p.lines).sum(),
+        });
+    }
+
+    for commit in commits {
+        let touched: Vec<usize> = proposed
+            .iter()
+            .enumerate()
+            .filter(|(_, pr)| commit.paths.iter().any(|p| pr.files.contains(p)))
+            .map(|(i, _)| i)
+            .collect();
+        let entry = if touched.len() > 1 {
+            format!("{} (spans {} PRs)", commit.summary, touched.len())
+        } else {
+            commit.summary.clone()
+        };
+        for i in touched {
+            proposed[i].commits.push(entry.clone());
+        }
+    }
+
+    proposed
+}
+
+/// Directory used to group files without an owner
+///
+/// Files nested at least two directories deep group by their first two
+/// directories (`src/auth`), shallower files by their top-level directory.
+pub(crate) fn directory_key(path: &str) -> String {
+    let dirs: Vec<&str> = path.split('/').collect();
+    match dirs.len() {
+        0 | 1 => "(root)".to_string(),
+        2 => dirs[0].to_string(),
+        _ => format!("{}/{}", dirs[0], dirs[1]),
+    }
+}
+
+/// Convert a `CODEOWNERS` pattern (gitignore syntax) to a regex
+fn pattern_regex(pattern: &str) -> Option<Regex> {
+    let anchored = pattern.starts_with('/') || pattern.trim_end_matches('/').contains('/');
+    let directory = pattern.ends_with('/');
+    let body = pattern.trim_matches('/');
+    if body.is_empty() {
+        return None;
+    }
+
+    let mut re = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                re.push_str(".*");
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push_str(if directory { "/.*$" } else { "(?:/.*)?$" });
+    Regex::new(&re).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEOWNERS: &str = "\
+# Default owners
+*               @org/core
+/src/auth/      @org/security
+*.md            @org/docs # docs team
+/src/auth/README.md
+";
+
+    #[test]
+    fn test_codeowners_last_match_wins() {
+        let owners = CodeOwners::parse(CODEOWNERS);
+        assert_eq!(
+            owners.owners("src/auth/token.rs"),
+            Some(&["@org/security".to_string()][..])
+        );
+        assert_eq!(
+            owners.owners("docs/guide.md"),
+            Some(&["@org/docs".to_string()][..])
+        );
+        assert_eq!(
+            owners.owners("src/main.rs"),
+            Some(&["@org/core".to_string()][..])
+        );
+        // A rule without owners leaves the file unowned
+        assert_eq!(owners.owners("src/auth/README.md"), None);
+    }
+
+    #[test]
+    fn test_plan_groups_by_owner_then_directory() {
+        let owners = CodeOwners::parse("/src/auth/ @org/security\n");
+        let files = [
+            ChangedFile::new("src/auth/token.rs", 120, 10),
+            ChangedFile::new("src/auth/session.rs", 40, 0),
+            ChangedFile::new("src/ui/panel.rs", 30, 5),
+            ChangedFile::new("README.md", 4, 1),
+        ];
+        let commits = [
+            CommitFiles {
+                summary: "abc1234 feat: token rotation".to_string(),
+                paths: vec!["src/auth/token.rs".to_string()],
+            },
+            CommitFiles {
+                summary: "def5678 feat: show session in panel".to_string(),
+                paths: vec![
+                    "src/auth/session.rs".to_string(),
+                    "src/ui/panel.rs".to_string(),
+                ],
+            },
+        ];
+
+        let plan = plan_split(&files, &commits, Some(&owners));
+        let labels: Vec<_> = plan.iter().map(|p| p.label.as_str()).collect();
+        assert_eq!(labels, ["@org/security", "src/ui", "(root)"]);
+        assert_eq!(plan[0].lines, 170);
+        assert_eq!(
+            plan[0].commits,
+            [
+                "abc1234 feat: token rotation",
+                "def5678 feat: show session in panel (spans 2 PRs)"
+            ]
+        );
+        assert!(plan[2].commits.is_empty());
+    }
+
+    #[test]
+    fn test_plan_folds_small_groups() {
+        let files: Vec<ChangedFile> = (0..10)
+            .map(|i| ChangedFile::new(format!("pkg{i}/lib.rs"), 10 + i, 0))
+            .collect();
+        let plan = plan_split(&files, &[], None);
+        assert_eq!(plan.len(), MAX_PROPOSED);
+        assert_eq!(plan[MAX_PROPOSED - 1].label, OTHER_LABEL);
+        assert_eq!(plan[MAX_PROPOSED - 1].files.len(), 5);
+    }
+}
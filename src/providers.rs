@@ -7,6 +7,9 @@ use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
 
+/// Where a local Ollama daemon listens unless `OLLAMA_HOST` says otherwise
+pub const OLLAMA_DEFAULT_URL: &str = "http://localhost:11434";
+
 /// Supported LLM providers
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -15,11 +18,18 @@ pub enum Provider {
     OpenAI,
     Anthropic,
     Google,
+    /// Local models served by an Ollama daemon
+    Ollama,
 }
 
 impl Provider {
     /// All available providers
-    pub const ALL: &'static [Provider] = &[Provider::OpenAI, Provider::Anthropic, Provider::Google];
+    pub const ALL: &'static [Provider] = &[
+        Provider::OpenAI,
+        Provider::Anthropic,
+        Provider::Google,
+        Provider::Ollama,
+    ];
 
     /// Provider name as used in config files and CLI
     pub const fn name(&self) -> &'static str {
@@ -27,6 +37,7 @@ impl Provider {
             Self::OpenAI => "openai",
             Self::Anthropic => "anthropic",
             Self::Google => "google",
+            Self::Ollama => "ollama",
         }
     }
 
@@ -36,6 +47,7 @@ impl Provider {
             Self::OpenAI => "gpt-5.1",
             Self::Anthropic => "claude-sonnet-4-5-20250929",
             Self::Google => "gemini-3-pro-preview",
+            Self::Ollama => "qwen2.5-coder:14b",
         }
     }
 
@@ -45,6 +57,7 @@ impl Provider {
             Self::OpenAI => "gpt-5.1-mini",
             Self::Anthropic => "claude-haiku-4-5-20251001",
             Self::Google => "gemini-2.5-flash",
+            Self::Ollama => "qwen2.5-coder:7b",
         }
     }
 
@@ -54,6 +67,7 @@ impl Provider {
             Self::OpenAI => 128_000,
             Self::Anthropic => 200_000,
             Self::Google => 1_000_000,
+            Self::Ollama => 32_768,
         }
     }
 
@@ -63,9 +77,29 @@ impl Provider {
             Self::OpenAI => "OPENAI_API_KEY",
             Self::Anthropic => "ANTHROPIC_API_KEY",
             Self::Google => "GOOGLE_API_KEY",
+            // Only needed behind an authenticating proxy
+            Self::Ollama => "OLLAMA_API_KEY",
         }
     }
 
+    /// API key from the environment
+    ///
+    /// Google also reads `GEMINI_API_KEY`, the name Google's own tools use.
+    pub fn env_api_key(&self) -> Option<String> {
+        let aliases: &[&str] = match self {
+            Self::Google => &["GEMINI_API_KEY"],
+            _ => &[],
+        };
+        std::iter::once(self.api_key_env())
+            .chain(aliases.iter().copied())
+            .find_map(|name| std::env::var(name).ok().filter(|key| !key.is_empty()))
+    }
+
+    /// Whether requests need an API key; local providers run without one
+    pub const fn requires_api_key(&self) -> bool {
+        !matches!(self, Self::Ollama)
+    }
+
     /// Get all provider names as strings
     pub fn all_names() -> Vec<&'static str> {
         Self::ALL.iter().map(Self::name).collect()
@@ -101,14 +135,55 @@ impl fmt::Display for Provider {
 /// Provider configuration error
 #[derive(Debug, thiserror::Error)]
 pub enum ProviderError {
-    #[error("Unknown provider: {0}. Supported: openai, anthropic, google")]
+    #[error("Unknown provider: {0}. Supported: openai, anthropic, google, ollama")]
     Unknown(String),
     #[error("API key required for provider: {0}")]
     MissingApiKey(String),
 }
 
+/// Base URL of the Ollama daemon
+///
+/// Honors `OLLAMA_HOST` the way the Ollama CLI does, so `0.0.0.0:11434` and
+/// `http://gpu-box:11434` both work.
+pub fn ollama_base_url() -> String {
+    std::env::var("OLLAMA_HOST")
+        .ok()
+        .map(|host| host.trim().trim_end_matches('/').to_string())
+        .filter(|host| !host.is_empty())
+        .map_or_else(
+            || OLLAMA_DEFAULT_URL.to_string(),
+            |host| {
+                if host.contains("://") {
+                    host
+                } else {
+                    format!("http://{host}")
+                }
+            },
+        )
+}
+
+/// Request headers as a header map
+///
+/// Headers with an invalid name or value are skipped with a warning.
+pub fn header_map(headers: &[(String, String)]) -> reqwest::header::HeaderMap {
+    use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        if let (Ok(header), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            map.insert(header, value);
+        } else {
+            tracing::warn!("Skipping invalid HTTP header '{}'", name);
+        }
+    }
+    map
+}
+
 /// Per-provider configuration
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProviderConfig {
     /// API key (loaded from env or config)
     #[serde(default, skip_serializing_if = "String::is_empty")]
@@ -125,6 +200,16 @@ pub struct ProviderConfig {
     /// Additional provider-specific params
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub additional_params: HashMap<String, String>,
+    /// `OpenAI` organization ID, sent as `OpenAI-Organization`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    /// `OpenAI` project ID, sent as `OpenAI-Project`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Extra HTTP headers sent with every request, such as the workspace
+    /// header an Anthropic gateway uses for billing
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
 }
 
 impl ProviderConfig {
@@ -136,6 +221,9 @@ impl ProviderConfig {
             fast_model: Some(provider.default_fast_model().to_string()),
             token_limit: None,
             additional_params: HashMap::new(),
+            organization: None,
+            project: None,
+            headers: HashMap::new(),
         }
     }
 
@@ -165,6 +253,37 @@ impl ProviderConfig {
     pub fn has_api_key(&self) -> bool {
         !self.api_key.is_empty()
     }
+
+    /// HTTP headers to send with every request to `provider`
+    ///
+    /// The `OpenAI` organization and project fall back to `OPENAI_ORG_ID` and
+    /// `OPENAI_PROJECT_ID`, the variables the `OpenAI` SDKs read, and are ignored
+    /// for other providers. Extra headers follow, sorted by name.
+    pub fn request_headers(&self, provider: Provider) -> Vec<(String, String)> {
+        let mut headers = Vec::new();
+        if provider == Provider::OpenAI {
+            let setting = |value: &Option<String>, env: &str| {
+                value
+                    .clone()
+                    .or_else(|| std::env::var(env).ok())
+                    .filter(|value| !value.trim().is_empty())
+            };
+            if let Some(organization) = setting(&self.organization, "OPENAI_ORG_ID") {
+                headers.push(("OpenAI-Organization".to_string(), organization));
+            }
+            if let Some(project) = setting(&self.project, "OPENAI_PROJECT_ID") {
+                headers.push(("OpenAI-Project".to_string(), project));
+            }
+        }
+        let mut extra: Vec<(String, String)> = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        extra.sort();
+        headers.extend(extra);
+        headers
+    }
 }
 
 #[cfg(test)]
@@ -187,6 +306,9 @@ mod tests {
         assert_eq!(Provider::OpenAI.default_model(), "gpt-5.1");
         assert_eq!(Provider::Anthropic.context_window(), 200_000);
         assert_eq!(Provider::Google.api_key_env(), "GOOGLE_API_KEY");
+        assert_eq!("ollama".parse::<Provider>().ok(), Some(Provider::Ollama));
+        assert!(!Provider::Ollama.requires_api_key());
+        assert!(Provider::OpenAI.requires_api_key());
     }
 
     #[test]
@@ -198,4 +320,38 @@ mod tests {
             Some("claude-haiku-4-5-20251001")
         );
     }
+
+    #[test]
+    fn test_request_headers() {
+        let config = ProviderConfig {
+            organization: Some("org-acme".to_string()),
+            project: Some("proj_billing".to_string()),
+            headers: [
+                ("x-team".to_string(), "platform".to_string()),
+                ("anthropic-workspace".to_string(), "wrkspc_1".to_string()),
+            ]
+            .into(),
+            ..ProviderConfig::default()
+        };
+        let names = |provider| -> Vec<String> {
+            config
+                .request_headers(provider)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect()
+        };
+        assert_eq!(
+            names(Provider::OpenAI),
+            [
+                "OpenAI-Organization",
+                "OpenAI-Project",
+                "anthropic-workspace",
+                "x-team"
+            ]
+        );
+        assert_eq!(
+            names(Provider::Anthropic),
+            ["anthropic-workspace", "x-team"]
+        );
+    }
 }
@@ -0,0 +1,38 @@
+//! Read-only mode
+//!
+//! `--read-only` keeps generation and analysis available but refuses every
+//! operation that changes something: staging, commits and amends, stash,
+//! rebase and reflog recovery, writing files such as changelogs, hooks and
+//! configuration, self-updates, and creating pull requests or reviews on
+//! GitHub. Each of those calls [`ensure_writable`] first, so the CLI, Studio
+//! and agent tools are covered alike. Caches git-iris keeps for itself, like
+//! the usage ledger and Studio sessions, are still written.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::IrisError;
+
+/// Whether mutating operations are refused
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Turn read-only mode on or off for the rest of the process
+pub fn set_read_only(enabled: bool) {
+    READ_ONLY.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether read-only mode is on
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+/// Fail with [`IrisError::ReadOnly`] when read-only mode is on
+///
+/// `action` names the operation, like "Committing".
+pub fn ensure_writable(action: &str) -> Result<(), IrisError> {
+    if is_read_only() {
+        return Err(IrisError::ReadOnly {
+            action: action.to_string(),
+        });
+    }
+    Ok(())
+}
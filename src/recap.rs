@@ -0,0 +1,234 @@
+//! "What was I doing?" recaps
+//!
+//! Gathers what git-iris remembers about the current branch (the companion
+//! session's recently touched files and commits, notes, last focus and
+//! message drafts), recent `HEAD` reflog entries and the uncommitted diff,
+//! then hands them to Iris for a short narrative of where work stopped.
+//! `git-iris recap` prints it; Studio shows one by itself when the branch
+//! was left alone for longer than [`IDLE_GAP_HOURS`].
+
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+
+use crate::companion::{BranchMemory, CompanionStorage, SessionState};
+use crate::git::{GitRepo, ReflogEntry};
+
+/// Hours without activity on a branch after which Studio opens with a recap
+pub const IDLE_GAP_HOURS: i64 = 12;
+
+/// Reflog entries included unless asked otherwise
+pub const DEFAULT_REFLOG_ENTRIES: usize = 15;
+
+/// Recently touched files listed
+const MAX_RECENT_FILES: usize = 10;
+
+/// Diff tokens sent; the start of each change says enough to place it
+const DIFF_BUDGET: usize = 8_000;
+
+/// Everything known about recent work on the branch
+#[derive(Debug, Clone, Default)]
+pub struct RecapFacts {
+    pub branch: String,
+    /// Last Studio visit or session activity, whichever is later
+    pub last_active: Option<DateTime<Utc>>,
+    /// Recently touched files, newest first, with their touch counts
+    pub recent_files: Vec<(String, u32)>,
+    /// Commits made during the companion session
+    pub session_commits: usize,
+    pub notes: Vec<String>,
+    /// `path:line` last focused in Studio
+    pub focus: Option<String>,
+    /// Most recent commit message draft that wasn't committed
+    pub last_draft: Option<String>,
+    /// Recent `HEAD` reflog entries, newest first
+    pub reflog: Vec<ReflogEntry>,
+    /// Staged, unstaged and untracked changes against `HEAD`
+    pub diff: String,
+    pub diff_truncated: bool,
+}
+
+impl RecapFacts {
+    /// Collect companion memory, the last `reflog_limit` reflog entries and
+    /// the uncommitted diff for the current branch
+    pub fn gather(repo: &GitRepo, reflog_limit: usize) -> Result<Self> {
+        let branch = repo
+            .get_current_branch()
+            .unwrap_or_else(|_| "HEAD".to_string());
+        let storage = CompanionStorage::new(repo.repo_path()).ok();
+        let session = storage
+            .as_ref()
+            .and_then(|s| s.load_session(&branch).ok().flatten());
+        let memory = storage
+            .as_ref()
+            .and_then(|s| s.load_branch_memory(&branch).ok().flatten());
+        let diff = repo.get_worktree_diff_full(true)?;
+        let (diff, _, diff_truncated) = crate::doc_context::truncate_to_tokens(&diff, DIFF_BUDGET);
+
+        let mut facts = Self {
+            branch,
+            reflog: repo.get_reflog(reflog_limit).unwrap_or_default(),
+            diff,
+            diff_truncated,
+            ..Self::default()
+        };
+        facts.add_memory(session.as_ref(), memory.as_ref());
+        Ok(facts)
+    }
+
+    /// Fill in what the companion remembers
+    fn add_memory(&mut self, session: Option<&SessionState>, memory: Option<&BranchMemory>) {
+        if let Some(session) = session {
+            self.last_active = Some(session.last_activity);
+            self.recent_files = session
+                .recent_files()
+                .into_iter()
+                .take(MAX_RECENT_FILES)
+                .map(|f| (f.path.display().to_string(), f.touch_count))
+                .collect();
+            self.session_commits = session.commits_made.len();
+        }
+        if let Some(memory) = memory {
+            self.last_active = self.last_active.max(Some(memory.last_visited));
+            self.notes.clone_from(&memory.notes);
+            self.focus = memory
+                .last_focus
+                .as_ref()
+                .map(|f| format!("{}:{}", f.path.display(), f.line));
+            self.last_draft = memory
+                .message_drafts
+                .last()
+                .map(|draft| draft.message.trim().to_string());
+        }
+    }
+
+    /// Whether there's nothing to recap
+    pub fn is_empty(&self) -> bool {
+        self.recent_files.is_empty()
+            && self.notes.is_empty()
+            && self.focus.is_none()
+            && self.reflog.is_empty()
+            && self.diff.trim().is_empty()
+    }
+
+    /// Markdown listing of the facts, shown with `--no-ai`
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Recap: {}\n\n", self.branch);
+        if let Some(at) = self.last_active {
+            out.push_str(&format!("Last active: {}\n\n", local_time(at)));
+        }
+
+        if !self.recent_files.is_empty() {
+            out.push_str("## Recently Touched Files\n\n");
+            for (path, touches) in &self.recent_files {
+                out.push_str(&format!("- `{path}` ({touches}×)\n"));
+            }
+            out.push('\n');
+        }
+        if self.session_commits > 0 {
+            out.push_str(&format!(
+                "Commits made in the last session: {}\n\n",
+                self.session_commits
+            ));
+        }
+        if let Some(focus) = &self.focus {
+            out.push_str(&format!("Last focus: `{focus}`\n\n"));
+        }
+        if !self.notes.is_empty() {
+            out.push_str("## Notes\n\n");
+            for note in &self.notes {
+                out.push_str(&format!("- {note}\n"));
+            }
+            out.push('\n');
+        }
+        if let Some(draft) = &self.last_draft {
+            out.push_str(&format!("## Last Message Draft\n\n```\n{draft}\n```\n\n"));
+        }
+        if !self.reflog.is_empty() {
+            out.push_str("## Recent HEAD Moves\n\n");
+            for entry in &self.reflog {
+                out.push_str(&format!("- {} {entry}\n", local_time(entry.time)));
+            }
+            out.push('\n');
+        }
+
+        out.push_str("## Uncommitted Changes\n\n");
+        if self.diff.trim().is_empty() {
+            out.push_str("None; the working tree is clean.\n");
+        } else {
+            out.push_str(&format!("```diff\n{}\n```\n", self.diff.trim_end()));
+            if self.diff_truncated {
+                out.push_str("(diff cut short)\n");
+            }
+        }
+        out
+    }
+
+    /// Task prompt handing the facts to Iris
+    pub fn to_prompt(&self) -> String {
+        format!(
+            "It is now {}. Recap where the developer left off on branch `{}`.\n\n{}",
+            local_time(Utc::now()),
+            self.branch,
+            self.to_markdown()
+        )
+    }
+}
+
+/// Whether `last_active` is long enough ago for Studio to open with a recap
+pub fn is_idle_gap(last_active: DateTime<Utc>) -> bool {
+    Utc::now() - last_active > chrono::Duration::hours(IDLE_GAP_HOURS)
+}
+
+fn local_time(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local)
+        .format("%a %Y-%m-%d %H:%M")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::companion::{DraftKind, MessageDraft};
+
+    #[test]
+    fn test_recap_facts_from_memory() {
+        let mut session = SessionState::new("/repo".into(), "feature/providers".to_string());
+        session.touch_file("src/providers.rs".into());
+        session.touch_file("src/providers.rs".into());
+        session.touch_file("tests/providers.rs".into());
+        let mut memory = BranchMemory::new("feature/providers".to_string());
+        memory.add_note("switch fallback order".to_string());
+        memory.set_focus("src/providers.rs".into(), 120);
+        memory.record_message_draft(MessageDraft::new(
+            "refactor: split provider config".to_string(),
+            DraftKind::Generated,
+        ));
+
+        let mut facts = RecapFacts {
+            branch: "feature/providers".to_string(),
+            ..RecapFacts::default()
+        };
+        assert!(facts.is_empty());
+        facts.add_memory(Some(&session), Some(&memory));
+        assert!(!facts.is_empty());
+        assert_eq!(facts.recent_files.len(), 2);
+        assert!(
+            facts
+                .recent_files
+                .contains(&("src/providers.rs".to_string(), 2))
+        );
+        assert_eq!(facts.focus.as_deref(), Some("src/providers.rs:120"));
+
+        let markdown = facts.to_markdown();
+        assert!(markdown.contains("Last focus: `src/providers.rs:120`"));
+        assert!(markdown.contains("- switch fallback order"));
+        assert!(markdown.contains("refactor: split provider config"));
+        assert!(markdown.contains("the working tree is clean"));
+        assert!(facts.to_prompt().contains("branch `feature/providers`"));
+
+        assert!(!is_idle_gap(Utc::now()));
+        assert!(is_idle_gap(
+            Utc::now() - chrono::Duration::hours(IDLE_GAP_HOURS + 1)
+        ));
+    }
+}
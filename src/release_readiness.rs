@@ -0,0 +1,485 @@
+//! Release readiness checklist
+//!
+//! Inspects a range (by default, everything since the last tag) and computes
+//! pass/fail checks from git data alone: migrations, docs, changelog, version
+//! bump and breaking-change call-outs. The checklist is then handed to the
+//! `release_readiness` capability, which adds commentary on top.
+
+use anyhow::{Result, bail};
+use regex::Regex;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::context::StagedFile;
+use crate::git::{GitRepo, detect_breaking_changes, is_breaking_message};
+
+/// Files whose `version` field marks a release
+const VERSION_FILES: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "setup.cfg",
+    "build.gradle",
+    "build.gradle.kts",
+    "pom.xml",
+    "mix.exs",
+    "VERSION",
+    "version.txt",
+];
+
+static VERSION_VALUE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)version\W*\s*[=:(>]\s*["']?v?(\d+\.\d+[\w.+-]*)"#)
+        .expect("valid version regex")
+});
+
+static BARE_VERSION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*v?(\d+\.\d+[\w.+-]*)\s*$").expect("valid version regex"));
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    /// Needs a human look but doesn't block the release
+    Warn,
+    Fail,
+}
+
+/// One item on the checklist
+#[derive(Debug, Clone, Serialize)]
+pub struct ReleaseCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+/// Release readiness for a range of commits
+#[derive(Debug, Clone, Serialize)]
+pub struct ReleaseChecklist {
+    pub from: String,
+    pub to: String,
+    /// Commit subjects in the range, oldest first
+    pub commits: Vec<String>,
+    pub files_changed: usize,
+    pub checks: Vec<ReleaseCheck>,
+}
+
+impl ReleaseChecklist {
+    /// Build the checklist for `from..to`, defaulting `from` to the last tag
+    pub fn build(repo: &GitRepo, from: Option<String>, to: &str) -> Result<Self> {
+        let from = match from {
+            Some(from) => from,
+            None => match repo.get_latest_tag(to)? {
+                Some(tag) => tag,
+                None => bail!("No tags found before {to}; pass --from to choose a starting point"),
+            },
+        };
+
+        let files = repo.get_commit_range_files(&from, to)?;
+        let mut messages =
+            repo.get_commits_between_with_callback(&from, to, |commit| Ok(commit.message.clone()))?;
+        messages.reverse();
+
+        Ok(Self {
+            checks: evaluate(&files, &messages),
+            commits: messages
+                .iter()
+                .map(|m| m.lines().next().unwrap_or_default().to_string())
+                .collect(),
+            files_changed: files.len(),
+            from,
+            to: to.to_string(),
+        })
+    }
+
+    /// Whether no check failed
+    pub fn is_ready(&self) -> bool {
+        self.checks.iter().all(|c| c.status != CheckStatus::Fail)
+    }
+
+    /// Markdown task list of the checks
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "## Release Readiness: {}..{}\n\n{} commits, {} files changed\n\n",
+            self.from,
+            self.to,
+            self.commits.len(),
+            self.files_changed
+        );
+        for check in &self.checks {
+            let (mark, suffix) = match check.status {
+                CheckStatus::Pass => ("x", ""),
+                CheckStatus::Warn => (" ", " (warning)"),
+                CheckStatus::Fail => (" ", ""),
+            };
+            out.push_str(&format!(
+                "- [{mark}] **{}**{suffix}: {}\n",
+                check.name, check.detail
+            ));
+        }
+        out
+    }
+
+    /// Task prompt for the commentary capability
+    pub fn to_prompt(&self) -> String {
+        let commits = self
+            .commits
+            .iter()
+            .map(|c| format!("- {c}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "Assess release readiness for the range {}..{}.\n\n{}\n## Commits\n{}",
+            self.from,
+            self.to,
+            self.to_markdown(),
+            commits
+        )
+    }
+}
+
+/// Compute the checks from changed files and commit messages
+fn evaluate(files: &[StagedFile], messages: &[String]) -> Vec<ReleaseCheck> {
+    vec![
+        check_migrations(files),
+        check_docs(files),
+        check_changelog(files),
+        check_version(files),
+        check_breaking(files, messages),
+    ]
+}
+
+fn check_migrations(files: &[StagedFile]) -> ReleaseCheck {
+    let migrations: Vec<&str> = files
+        .iter()
+        .map(|f| f.path.as_str())
+        .filter(|p| is_migration(p))
+        .collect();
+    let (status, detail) = if migrations.is_empty() {
+        (CheckStatus::Pass, "No migrations in this range".to_string())
+    } else {
+        (
+            CheckStatus::Warn,
+            format!(
+                "{} migration file(s), confirm ordering and rollback: {}",
+                migrations.len(),
+                list(&migrations)
+            ),
+        )
+    };
+    ReleaseCheck {
+        name: "Migrations",
+        status,
+        detail,
+    }
+}
+
+fn check_docs(files: &[StagedFile]) -> ReleaseCheck {
+    let docs: Vec<&str> = files
+        .iter()
+        .map(|f| f.path.as_str())
+        .filter(|p| is_doc(p) && !is_changelog(p))
+        .collect();
+    let code_changes = files
+        .iter()
+        .any(|f| !is_doc(&f.path) && !is_test(&f.path) && !is_changelog(&f.path));
+
+    let (status, detail) = if !docs.is_empty() {
+        (
+            CheckStatus::Pass,
+            format!("{} doc file(s) updated: {}", docs.len(), list(&docs)),
+        )
+    } else if code_changes {
+        (
+            CheckStatus::Warn,
+            "Code changed but no documentation was touched".to_string(),
+        )
+    } else {
+        (
+            CheckStatus::Pass,
+            "No code changes needing docs".to_string(),
+        )
+    };
+    ReleaseCheck {
+        name: "Docs touched",
+        status,
+        detail,
+    }
+}
+
+fn check_changelog(files: &[StagedFile]) -> ReleaseCheck {
+    let changelogs: Vec<&str> = files
+        .iter()
+        .map(|f| f.path.as_str())
+        .filter(|p| is_changelog(p))
+        .collect();
+    let (status, detail) = if changelogs.is_empty() {
+        (
+            CheckStatus::Fail,
+            "No changelog updated (try `git-iris changelog --update`)".to_string(),
+        )
+    } else {
+        (CheckStatus::Pass, format!("Updated {}", list(&changelogs)))
+    };
+    ReleaseCheck {
+        name: "Changelog updated",
+        status,
+        detail,
+    }
+}
+
+fn check_version(files: &[StagedFile]) -> ReleaseCheck {
+    let bump = files
+        .iter()
+        .filter(|f| is_version_file(&f.path))
+        .find_map(|f| version_change(&f.diff).map(|(old, new)| (f.path.as_str(), old, new)));
+    let (status, detail) = match bump {
+        Some((path, Some(old), new)) => (CheckStatus::Pass, format!("{path}: {old} -> {new}")),
+        Some((path, None, new)) => (CheckStatus::Pass, format!("{path}: {new}")),
+        None => (
+            CheckStatus::Fail,
+            "No version change found in a package manifest".to_string(),
+        ),
+    };
+    ReleaseCheck {
+        name: "Version bumped",
+        status,
+        detail,
+    }
+}
+
+fn check_breaking(files: &[StagedFile], messages: &[String]) -> ReleaseCheck {
+    let detected = detect_breaking_changes(files);
+    let marked = messages.iter().filter(|m| is_breaking_message(m)).count();
+    let in_changelog = files.iter().filter(|f| is_changelog(&f.path)).any(|f| {
+        f.diff
+            .lines()
+            .any(|line| line.starts_with('+') && line.to_lowercase().contains("breaking"))
+    });
+
+    let (status, detail) = if detected.is_empty() && marked == 0 {
+        (
+            CheckStatus::Pass,
+            "No breaking changes detected".to_string(),
+        )
+    } else if !detected.is_empty() && marked == 0 {
+        (
+            CheckStatus::Fail,
+            format!(
+                "Public API removed but no commit is marked breaking: {}",
+                detected
+                    .iter()
+                    .take(3)
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+        )
+    } else if in_changelog {
+        (
+            CheckStatus::Pass,
+            format!(
+                "{} breaking change(s), called out in the changelog",
+                detected.len().max(marked)
+            ),
+        )
+    } else {
+        (
+            CheckStatus::Fail,
+            format!(
+                "{} breaking change(s) not called out in the changelog",
+                detected.len().max(marked)
+            ),
+        )
+    };
+    ReleaseCheck {
+        name: "Breaking changes called out",
+        status,
+        detail,
+    }
+}
+
+/// Old and new version from a manifest diff
+fn version_change(diff: &str) -> Option<(Option<String>, String)> {
+    let mut old = None;
+    let mut new = None;
+    for line in diff.lines() {
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        let Some((sign, content)) = line.split_at_checked(1) else {
+            continue;
+        };
+        let version = VERSION_VALUE
+            .captures(content)
+            .or_else(|| BARE_VERSION.captures(content))
+            .and_then(|caps| caps.get(1))
+            .map(|m| m.as_str().to_string());
+        match (sign, version) {
+            ("-", Some(v)) if old.is_none() => old = Some(v),
+            ("+", Some(v)) if new.is_none() => new = Some(v),
+            _ => {}
+        }
+    }
+    new.filter(|n| old.as_ref() != Some(n)).map(|n| (old, n))
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(path)
+}
+
+fn is_version_file(path: &str) -> bool {
+    let name = file_name(path);
+    VERSION_FILES.contains(&name)
+        || Path::new(name)
+            .extension()
+            .is_some_and(|ext| ext == "gemspec")
+}
+
+fn is_changelog(path: &str) -> bool {
+    let stem = Path::new(path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default()
+        .to_uppercase();
+    ["CHANGELOG", "CHANGES", "HISTORY", "RELEASE_NOTES", "NEWS"].contains(&stem.as_str())
+}
+
+fn is_doc(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    lower.starts_with("docs/")
+        || lower.contains("/docs/")
+        || [".md", ".mdx", ".rst", ".adoc", ".txt"]
+            .iter()
+            .any(|ext| lower.ends_with(ext))
+}
+
+fn is_test(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    lower.starts_with("tests/")
+        || lower.contains("/tests/")
+        || lower.contains("/__tests__/")
+        || lower.contains(".test.")
+        || lower.contains(".spec.")
+        || lower.contains("_test.")
+}
+
+fn is_migration(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    lower.contains("migrations/")
+        || lower.contains("migrate/")
+        || Path::new(&lower)
+            .extension()
+            .is_some_and(|ext| ext == "sql")
+}
+
+fn list(paths: &[&str]) -> String {
+    const SHOWN: usize = 3;
+    let shown = paths
+        .iter()
+        .take(SHOWN)
+        .map(|p| format!("`{p}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if paths.len() > SHOWN {
+        format!("{shown} and {} more", paths.len() - SHOWN)
+    } else {
+        shown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ChangeType;
+
+    fn file(path: &str, diff: &str) -> StagedFile {
+        StagedFile {
+            path: path.to_string(),
+            change_type: ChangeType::Modified,
+            diff: diff.to_string(),
+            content: None,
+            content_excluded: false,
+        }
+    }
+
+    fn status(checks: &[ReleaseCheck], name: &str) -> CheckStatus {
+        checks
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| c.status)
+            .expect("check present")
+    }
+
+    #[test]
+    fn test_ready_release() {
+        let files = [
+            file("src/lib.rs", "+pub fn added() {}\n"),
+            file("docs/usage.md", "+Use `added`.\n"),
+            file("CHANGELOG.md", "+## [1.3.0]\n+- Added `added`\n"),
+            file("Cargo.toml", "-version = \"1.2.0\"\n+version = \"1.3.0\"\n"),
+        ];
+        let checks = evaluate(&files, &["feat: add added".to_string()]);
+        assert!(checks.iter().all(|c| c.status == CheckStatus::Pass));
+        let version = checks
+            .iter()
+            .find(|c| c.name == "Version bumped")
+            .expect("version check");
+        assert_eq!(version.detail, "Cargo.toml: 1.2.0 -> 1.3.0");
+    }
+
+    #[test]
+    fn test_missing_changelog_version_and_breaking_callout() {
+        let files = [
+            file("src/lib.rs", "-pub fn removed() {}\n"),
+            file(
+                "db/migrations/002_add_users.sql",
+                "+CREATE TABLE users();\n",
+            ),
+            file("package.json", "   \"name\": \"app\",\n"),
+        ];
+        let checks = evaluate(&files, &["refactor: drop removed".to_string()]);
+        assert_eq!(status(&checks, "Migrations"), CheckStatus::Warn);
+        assert_eq!(status(&checks, "Docs touched"), CheckStatus::Warn);
+        assert_eq!(status(&checks, "Changelog updated"), CheckStatus::Fail);
+        assert_eq!(status(&checks, "Version bumped"), CheckStatus::Fail);
+        assert_eq!(
+            status(&checks, "Breaking changes called out"),
+            CheckStatus::Fail
+        );
+    }
+
+    #[test]
+    fn test_markdown_checklist() {
+        let checklist = ReleaseChecklist {
+            from: "v1.2.0".to_string(),
+            to: "HEAD".to_string(),
+            commits: vec!["feat: x".to_string()],
+            files_changed: 1,
+            checks: vec![
+                ReleaseCheck {
+                    name: "Changelog updated",
+                    status: CheckStatus::Pass,
+                    detail: "Updated `CHANGELOG.md`".to_string(),
+                },
+                ReleaseCheck {
+                    name: "Version bumped",
+                    status: CheckStatus::Fail,
+                    detail: "No version change found in a package manifest".to_string(),
+                },
+            ],
+        };
+        assert!(!checklist.is_ready());
+        assert_eq!(
+            checklist.to_markdown(),
+            "## Release Readiness: v1.2.0..HEAD\n\n1 commits, 1 files changed\n\n\
+             - [x] **Changelog updated**: Updated `CHANGELOG.md`\n\
+             - [ ] **Version bumped**: No version change found in a package manifest\n"
+        );
+    }
+}
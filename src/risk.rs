@@ -0,0 +1,449 @@
+//! Deterministic risk scoring for commits and branches
+//!
+//! The score (0–100) adds up a few signals that are cheap to compute and easy
+//! to explain: how big the change is, whether it touches the repository's
+//! `critical_paths`, whether changed source files are covered according to an
+//! lcov report, and how often the touched files have been reverted before.
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::context::StagedFile;
+use crate::git::GitRepo;
+
+/// How far back to look for reverts
+const REVERT_HISTORY_LIMIT: usize = 1000;
+
+/// Files below this line coverage count as untested
+const COVERAGE_THRESHOLD: f64 = 0.5;
+
+/// Where coverage tools usually write lcov reports
+const LCOV_LOCATIONS: &[&str] = &[
+    "lcov.info",
+    "coverage/lcov.info",
+    "target/lcov.info",
+    "target/llvm-cov/lcov.info",
+    "target/coverage/lcov.info",
+];
+
+/// Extensions treated as source code for coverage checks
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "ts", "tsx", "js", "jsx", "mjs", "py", "go", "java", "kt", "rb", "php", "c", "cc", "cpp",
+    "h", "hpp", "cs", "swift", "scala",
+];
+
+/// A changed file with its line counts
+#[derive(Debug, Clone)]
+pub struct ChangedFile {
+    pub path: String,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+impl ChangedFile {
+    pub fn new(path: impl Into<String>, additions: usize, deletions: usize) -> Self {
+        Self {
+            path: path.into(),
+            additions,
+            deletions,
+        }
+    }
+
+    /// Count added and removed lines in a staged file's diff
+    pub fn from_staged(file: &StagedFile) -> Self {
+        let (additions, deletions) = file
+            .diff
+            .lines()
+            .filter(|line| !line.starts_with("+++") && !line.starts_with("---"))
+            .fold((0, 0), |(add, del), line| match line.as_bytes().first() {
+                Some(b'+') => (add + 1, del),
+                Some(b'-') => (add, del + 1),
+                _ => (add, del),
+            });
+        Self::new(file.path.clone(), additions, deletions)
+    }
+}
+
+/// Coarse risk bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl RiskLevel {
+    fn from_score(score: u8) -> Self {
+        match score {
+            0..30 => Self::Low,
+            30..60 => Self::Medium,
+            _ => Self::High,
+        }
+    }
+}
+
+impl fmt::Display for RiskLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Medium => write!(f, "medium"),
+            Self::High => write!(f, "high"),
+        }
+    }
+}
+
+/// One contribution to the score
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiskFactor {
+    /// Short name (`Size`, `Critical paths`, …)
+    pub label: &'static str,
+    /// Points added to the score
+    pub points: u8,
+    /// Human-readable explanation
+    pub detail: String,
+}
+
+/// Risk score with the factors that produced it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiskReport {
+    pub score: u8,
+    pub level: RiskLevel,
+    pub factors: Vec<RiskFactor>,
+}
+
+impl RiskReport {
+    /// Compact badge for panel titles
+    pub fn badge(&self) -> String {
+        format!("Risk {} {}", self.level, self.score)
+    }
+
+    /// Markdown section for PR descriptions
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "## Risk Assessment\n\n**{}** risk (score {}/100)\n",
+            capitalize(&self.level.to_string()),
+            self.score
+        );
+        if !self.factors.is_empty() {
+            out.push('\n');
+            for factor in &self.factors {
+                out.push_str(&format!(
+                    "- {} (+{}): {}\n",
+                    factor.label, factor.points, factor.detail
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Signals gathered from the repository for scoring
+#[derive(Debug, Default)]
+pub struct RiskContext {
+    critical_paths: Vec<Regex>,
+    /// Line coverage ratio by file path, if an lcov report exists
+    coverage: Option<HashMap<String, f64>>,
+    /// Reverts per touched path
+    reverts: HashMap<String, usize>,
+}
+
+impl RiskContext {
+    /// Gather critical paths from config, coverage and revert history
+    ///
+    /// Repository lookups are best-effort; missing data just contributes nothing.
+    pub fn load(config: &Config, repo: Option<&GitRepo>, files: &[ChangedFile]) -> Self {
+        let mut context = Self::default().with_critical_paths(&config.critical_paths);
+        if let Some(repo) = repo {
+            context.coverage = load_lcov(repo.repo_path());
+            let paths: Vec<String> = files.iter().map(|f| f.path.clone()).collect();
+            context.reverts = repo
+                .count_reverts_touching(&paths, REVERT_HISTORY_LIMIT)
+                .unwrap_or_else(|e| {
+                    crate::log_debug!("Revert history unavailable: {}", e);
+                    HashMap::new()
+                });
+        }
+        context
+    }
+
+    fn with_critical_paths(mut self, patterns: &[String]) -> Self {
+        self.critical_paths = patterns.iter().filter_map(|p| glob_to_regex(p)).collect();
+        self
+    }
+
+    /// Score a set of changed files
+    pub fn assess(&self, files: &[ChangedFile]) -> RiskReport {
+        let mut factors = Vec::new();
+
+        let lines: usize = files.iter().map(|f| f.additions + f.deletions).sum();
+        let size_points = match lines {
+            0..50 => 0,
+            50..200 => 10,
+            200..500 => 20,
+            500..1000 => 30,
+            _ => 40,
+        } + if files.len() > 20 { 10 } else { 0 };
+        if size_points > 0 {
+            factors.push(RiskFactor {
+                label: "Size",
+                points: size_points,
+                detail: format!("{lines} lines across {} files", files.len()),
+            });
+        }
+
+        let critical: Vec<&str> = files
+            .iter()
+            .filter(|f| self.critical_paths.iter().any(|re| re.is_match(&f.path)))
+            .map(|f| f.path.as_str())
+            .collect();
+        if !critical.is_empty() {
+            factors.push(RiskFactor {
+                label: "Critical paths",
+                points: points_for(critical.len(), 15, 30),
+                detail: list_paths(&critical),
+            });
+        }
+
+        if let Some(coverage) = &self.coverage {
+            let untested: Vec<&str> = files
+                .iter()
+                .filter(|f| f.additions > 0 && is_source(&f.path))
+                .filter(|f| {
+                    coverage_for(coverage, &f.path).is_none_or(|ratio| ratio < COVERAGE_THRESHOLD)
+                })
+                .map(|f| f.path.as_str())
+                .collect();
+            if !untested.is_empty() {
+                factors.push(RiskFactor {
+                    label: "Test coverage",
+                    points: points_for(untested.len(), 5, 20),
+                    detail: format!("low or no coverage: {}", list_paths(&untested)),
+                });
+            }
+        }
+
+        let reverts: usize = files.iter().filter_map(|f| self.reverts.get(&f.path)).sum();
+        if reverts > 0 {
+            let mut reverted: Vec<&str> = files
+                .iter()
+                .filter(|f| self.reverts.contains_key(&f.path))
+                .map(|f| f.path.as_str())
+                .collect();
+            reverted.sort_unstable();
+            factors.push(RiskFactor {
+                label: "Revert history",
+                points: points_for(reverts, 5, 20),
+                detail: format!("{reverts} past reverts touching {}", list_paths(&reverted)),
+            });
+        }
+
+        let score = factors
+            .iter()
+            .map(|f| u32::from(f.points))
+            .sum::<u32>()
+            .min(100);
+        let score = u8::try_from(score).unwrap_or(100);
+        RiskReport {
+            score,
+            level: RiskLevel::from_score(score),
+            factors,
+        }
+    }
+}
+
+/// Score changed files against the repository
+pub fn assess(config: &Config, repo: Option<&GitRepo>, files: &[ChangedFile]) -> RiskReport {
+    RiskContext::load(config, repo, files).assess(files)
+}
+
+fn points_for(count: usize, each: u8, cap: u8) -> u8 {
+    u8::try_from(count)
+        .unwrap_or(u8::MAX)
+        .saturating_mul(each)
+        .min(cap)
+}
+
+fn list_paths(paths: &[&str]) -> String {
+    const SHOWN: usize = 3;
+    let shown = paths
+        .iter()
+        .take(SHOWN)
+        .map(|p| format!("`{p}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if paths.len() > SHOWN {
+        format!("{shown} and {} more", paths.len() - SHOWN)
+    } else {
+        shown
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+fn is_source(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext))
+}
+
+/// Convert a path pattern (`critical_paths`, `[context.docs]`) to a regex
+///
+/// `**` matches across directories, `*` and `?` within one path segment.
+/// A pattern without wildcards matches that path and everything below it.
+pub(crate) fn glob_to_regex(pattern: &str) -> Option<Regex> {
+    let pattern = pattern.trim().trim_start_matches("./");
+    if pattern.is_empty() {
+        return None;
+    }
+    if !pattern.contains(['*', '?']) {
+        let prefix = regex::escape(pattern.trim_end_matches('/'));
+        return Regex::new(&format!("^{prefix}(/|$)")).ok();
+    }
+
+    let mut re = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    Regex::new(&re).ok()
+}
+
+/// Find the first lcov report and read per-file line coverage
+fn load_lcov(root: &Path) -> Option<HashMap<String, f64>> {
+    LCOV_LOCATIONS
+        .iter()
+        .map(|location| root.join(location))
+        .find(|path| path.is_file())
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|content| parse_lcov(&content, root))
+}
+
+fn parse_lcov(content: &str, root: &Path) -> HashMap<String, f64> {
+    let root = root.to_string_lossy().replace('\\', "/");
+    let mut coverage = HashMap::new();
+    let (mut file, mut found, mut hit) = (None::<String>, 0u32, 0u32);
+
+    for line in content.lines().map(str::trim) {
+        if let Some(path) = line.strip_prefix("SF:") {
+            let path = path.replace('\\', "/");
+            let relative = path
+                .strip_prefix(&root)
+                .map_or(path.as_str(), |p| p.trim_start_matches('/'));
+            file = Some(relative.to_string());
+            (found, hit) = (0, 0);
+        } else if let Some(n) = line.strip_prefix("LF:") {
+            found = n.parse().unwrap_or(0);
+        } else if let Some(n) = line.strip_prefix("LH:") {
+            hit = n.parse().unwrap_or(0);
+        } else if line == "end_of_record"
+            && let Some(path) = file.take()
+        {
+            let ratio = if found == 0 {
+                1.0
+            } else {
+                f64::from(hit) / f64::from(found)
+            };
+            coverage.insert(path, ratio);
+        }
+    }
+    coverage
+}
+
+/// Coverage for a repo-relative path; lcov paths may carry extra prefixes
+fn coverage_for(coverage: &HashMap<String, f64>, path: &str) -> Option<f64> {
+    coverage.get(path).copied().or_else(|| {
+        coverage
+            .iter()
+            .find(|(file, _)| file.ends_with(&format!("/{path}")))
+            .map(|(_, ratio)| *ratio)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_patterns() {
+        let dir = glob_to_regex("src/auth/").expect("regex");
+        assert!(dir.is_match("src/auth/token.rs"));
+        assert!(!dir.is_match("src/authz.rs"));
+
+        let sql = glob_to_regex("**/*.sql").expect("regex");
+        assert!(sql.is_match("schema.sql"));
+        assert!(sql.is_match("db/migrations/001.sql"));
+
+        let single = glob_to_regex("config/*.toml").expect("regex");
+        assert!(single.is_match("config/app.toml"));
+        assert!(!single.is_match("config/nested/app.toml"));
+    }
+
+    #[test]
+    fn test_parse_lcov_relative_paths() {
+        let lcov = "SF:/repo/src/lib.rs\nLF:10\nLH:9\nend_of_record\nSF:src/new.rs\nLF:4\nLH:0\nend_of_record\n";
+        let coverage = parse_lcov(lcov, Path::new("/repo"));
+        assert_eq!(coverage.get("src/lib.rs"), Some(&0.9));
+        assert_eq!(coverage.get("src/new.rs"), Some(&0.0));
+    }
+
+    #[test]
+    fn test_assess_combines_factors() {
+        let mut context = RiskContext::default().with_critical_paths(&["src/auth".to_string()]);
+        context.coverage = Some(HashMap::from([("src/auth/login.rs".to_string(), 0.2)]));
+        context.reverts = HashMap::from([("src/auth/login.rs".to_string(), 2)]);
+
+        let files = [
+            ChangedFile::new("src/auth/login.rs", 150, 60),
+            ChangedFile::new("README.md", 5, 0),
+        ];
+        let report = context.assess(&files);
+        let points: Vec<_> = report.factors.iter().map(|f| (f.label, f.points)).collect();
+        assert_eq!(
+            points,
+            [
+                ("Size", 20),
+                ("Critical paths", 15),
+                ("Test coverage", 5),
+                ("Revert history", 10)
+            ]
+        );
+        assert_eq!(report.score, 50);
+        assert_eq!(report.level, RiskLevel::Medium);
+        assert!(
+            report
+                .to_markdown()
+                .starts_with("## Risk Assessment\n\n**Medium** risk (score 50/100)")
+        );
+    }
+
+    #[test]
+    fn test_small_change_is_low_risk() {
+        let report = RiskContext::default().assess(&[ChangedFile::new("src/lib.rs", 3, 1)]);
+        assert_eq!(report.score, 0);
+        assert_eq!(report.level, RiskLevel::Low);
+        assert!(report.factors.is_empty());
+    }
+}
@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use crate::git::{CommitResult, GitRepo};
 use crate::gitmoji::process_commit_message;
+use crate::lifecycle::{LifecycleEvent, LifecycleHooks};
 use crate::log_debug;
 
 /// Service for performing git commit operations
@@ -15,6 +16,7 @@ use crate::log_debug;
 /// This service handles:
 /// - Creating commits with optional hook verification
 /// - Pre-commit hook execution
+/// - `before_commit`/`after_commit` lifecycle hooks
 /// - Remote repository detection
 ///
 /// It does NOT handle:
@@ -25,6 +27,7 @@ pub struct GitCommitService {
     repo: Arc<GitRepo>,
     use_gitmoji: bool,
     verify: bool,
+    hooks: LifecycleHooks,
 }
 
 impl GitCommitService {
@@ -39,9 +42,17 @@ impl GitCommitService {
             repo,
             use_gitmoji,
             verify,
+            hooks: LifecycleHooks::default(),
         }
     }
 
+    /// Run the configured `before_commit`/`after_commit` hooks around
+    /// commits (skipped along with the git hooks when verify is off)
+    pub fn with_lifecycle_hooks(mut self, hooks: LifecycleHooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
     /// Create from an existing `GitRepo` (convenience constructor)
     pub fn from_repo(repo: GitRepo, use_gitmoji: bool, verify: bool) -> Self {
         Self::new(Arc::new(repo), use_gitmoji, verify)
@@ -71,14 +82,42 @@ impl GitCommitService {
         }
     }
 
+    /// Run the `before_commit` lifecycle hook with the final message
+    fn before_commit(&self, message: &str, amend: bool) -> Result<()> {
+        self.hooks.run(
+            LifecycleEvent::BeforeCommit,
+            &[
+                ("IRIS_COMMIT_MESSAGE", message),
+                ("IRIS_AMEND", if amend { "1" } else { "0" }),
+            ],
+            message,
+            Some(self.repo.repo_path()),
+        )
+    }
+
+    /// Run the `after_commit` lifecycle hook; failures are only logged
+    fn after_commit(&self, message: &str, result: &CommitResult, amend: bool) {
+        self.hooks.notify(
+            LifecycleEvent::AfterCommit,
+            &[
+                ("IRIS_COMMIT_MESSAGE", message),
+                ("IRIS_AMEND", if amend { "1" } else { "0" }),
+                ("IRIS_COMMIT", &result.commit_hash),
+                ("IRIS_BRANCH", &result.branch),
+            ],
+            message,
+            Some(self.repo.repo_path()),
+        );
+    }
+
     /// Perform a commit with the given message
     ///
     /// This method:
     /// 1. Validates the repository is not remote
     /// 2. Processes the message (applies gitmoji if enabled)
-    /// 3. Runs pre-commit hook (if verify is enabled)
+    /// 3. Runs pre-commit and `before_commit` hooks (if verify is enabled)
     /// 4. Creates the commit
-    /// 5. Runs post-commit hook (if verify is enabled)
+    /// 5. Runs post-commit and `after_commit` hooks (if verify is enabled)
     ///
     /// # Arguments
     /// * `message` - The commit message to use
@@ -105,6 +144,7 @@ impl GitCommitService {
             return Err(e);
         }
         log_debug!("Pre-commit hook executed successfully");
+        self.before_commit(&processed_message, false)?;
 
         // Perform the commit
         match self.repo.commit(&processed_message) {
@@ -114,6 +154,7 @@ impl GitCommitService {
                 if let Err(e) = self.repo.execute_hook("post-commit") {
                     log_debug!("Post-commit hook failed: {}", e);
                 }
+                self.after_commit(&processed_message, &result, false);
                 log_debug!("Commit performed successfully");
                 Ok(result)
             }
@@ -129,16 +170,16 @@ impl GitCommitService {
     /// This method:
     /// 1. Validates the repository is not remote
     /// 2. Processes the message (applies gitmoji if enabled)
-    /// 3. Runs pre-commit hook (if verify is enabled)
-    /// 4. Amends the commit (replaces HEAD)
-    /// 5. Runs post-commit hook (if verify is enabled)
+    /// 3. Runs pre-commit and `before_commit` hooks (if verify is enabled)
+    /// 4. Amends the commit (replaces HEAD, keeping its author and author date)
+    /// 5. Runs post-commit and `after_commit` hooks (if verify is enabled)
     ///
     /// # Arguments
     /// * `message` - The new commit message
     ///
     /// # Returns
     /// The result of the amend operation
-    pub fn perform_amend(&self, message: &str) -> Result<CommitResult> {
+    pub fn amend_commit(&self, message: &str) -> Result<CommitResult> {
         if self.is_remote() {
             return Err(anyhow::anyhow!(
                 "Cannot amend a commit in a remote repository"
@@ -160,6 +201,7 @@ impl GitCommitService {
             return Err(e);
         }
         log_debug!("Pre-commit hook executed successfully");
+        self.before_commit(&processed_message, true)?;
 
         // Perform the amend
         match self.repo.amend_commit(&processed_message) {
@@ -169,6 +211,7 @@ impl GitCommitService {
                 if let Err(e) = self.repo.execute_hook("post-commit") {
                     log_debug!("Post-commit hook failed: {}", e);
                 }
+                self.after_commit(&processed_message, &result, true);
                 log_debug!("Amend performed successfully");
                 Ok(result)
             }
@@ -0,0 +1,237 @@
+//! Deterministic spell-check for generated and hand-edited text
+//!
+//! Flags words from a local list of known misspellings, each with its
+//! correction, so it never needs a network call or the LLM. Code is skipped:
+//! anything in backticks or fenced blocks, URLs, paths, and identifier-shaped
+//! tokens (`snake_case`, `camelCase`, `ALLCAPS`, words with digits).
+//!
+//! A repository can list words that should never be flagged in
+//! `.git-iris/dictionary.txt`, one per line.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Repository dictionary of words to accept (relative to the repo root)
+pub const DICTIONARY_FILE: &str = ".git-iris/dictionary.txt";
+
+/// Embedded misspelling list in `wrong->right` form
+const MISSPELLINGS: &str = include_str!("misspellings.txt");
+
+static CORRECTIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    MISSPELLINGS
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once("->"))
+        .collect()
+});
+
+/// A suspect word and its suggested correction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Misspelling {
+    /// The word as written
+    pub word: String,
+    /// Suggested replacement, matching the original's capitalization
+    pub suggestion: String,
+    /// Zero-based line index
+    pub line: usize,
+    /// Byte offset of the word within its line
+    pub column: usize,
+}
+
+/// Spell checker with repository-specific accepted words
+#[derive(Debug, Clone, Default)]
+pub struct SpellChecker {
+    accepted: HashSet<String>,
+}
+
+impl SpellChecker {
+    /// Load the checker, reading the repository dictionary if there is one
+    pub fn load(repo_root: Option<&Path>) -> Self {
+        let accepted = repo_root
+            .and_then(|root| std::fs::read_to_string(root.join(DICTIONARY_FILE)).ok())
+            .map(|content| {
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_lowercase)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { accepted }
+    }
+
+    /// Find suspect words in `text`
+    pub fn check(&self, text: &str) -> Vec<Misspelling> {
+        let mut found = Vec::new();
+        let mut in_fence = false;
+
+        for (line_idx, line) in text.lines().enumerate() {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+                continue;
+            }
+            if in_fence {
+                continue;
+            }
+
+            for (column, token) in prose_tokens(line) {
+                let Some(word) = clean_word(token) else {
+                    continue;
+                };
+                let lower = word.to_lowercase();
+                if self.accepted.contains(&lower) {
+                    continue;
+                }
+                if let Some(correction) = CORRECTIONS.get(lower.as_str()) {
+                    let offset = token.find(word).unwrap_or(0);
+                    found.push(Misspelling {
+                        word: word.to_string(),
+                        suggestion: match_case(word, correction),
+                        line: line_idx,
+                        column: column + offset,
+                    });
+                }
+            }
+        }
+        found
+    }
+
+    /// Replace every suspect word in `text` with its suggestion
+    pub fn fix(&self, text: &str) -> (String, usize) {
+        let misspellings = self.check(text);
+        if misspellings.is_empty() {
+            return (text.to_string(), 0);
+        }
+
+        let mut lines: Vec<String> = text.lines().map(String::from).collect();
+        // Replace right-to-left so earlier offsets stay valid
+        for m in misspellings.iter().rev() {
+            if let Some(line) = lines.get_mut(m.line) {
+                line.replace_range(m.column..m.column + m.word.len(), &m.suggestion);
+            }
+        }
+
+        let mut fixed = lines.join("\n");
+        if text.ends_with('\n') {
+            fixed.push('\n');
+        }
+        (fixed, misspellings.len())
+    }
+}
+
+/// Whitespace-separated tokens outside inline code spans, with byte offsets
+fn prose_tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut in_code = false;
+    let mut start = None;
+
+    for (i, c) in line.char_indices() {
+        if c == '`' {
+            if let Some(s) = start.take()
+                && !in_code
+            {
+                tokens.push((s, &line[s..i]));
+            }
+            in_code = !in_code;
+        } else if c.is_whitespace() {
+            if let Some(s) = start.take()
+                && !in_code
+            {
+                tokens.push((s, &line[s..i]));
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(s) = start
+        && !in_code
+    {
+        tokens.push((s, &line[s..]));
+    }
+    tokens
+}
+
+/// Strip surrounding punctuation and reject tokens that look like code
+fn clean_word(token: &str) -> Option<&str> {
+    if token.contains("://") || token.contains('/') || token.contains('\\') {
+        return None;
+    }
+    let word = token.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'');
+    let word = word.trim_matches('\'');
+    if word.len() < 2 || !word.chars().all(|c| c.is_alphabetic() || c == '\'') {
+        // Digits, underscores, dots and other symbols mean an identifier
+        return None;
+    }
+
+    // camelCase, PascalCase with inner capitals, or ALLCAPS
+    let inner_upper = word.chars().skip(1).any(char::is_uppercase);
+    if inner_upper {
+        return None;
+    }
+    Some(word)
+}
+
+/// Apply the capitalization of `original` to `correction`
+fn match_case(original: &str, correction: &str) -> String {
+    if original.chars().next().is_some_and(char::is_uppercase) {
+        let mut chars = correction.chars();
+        chars.next().map_or_else(String::new, |first| {
+            first.to_uppercase().chain(chars).collect()
+        })
+    } else {
+        correction.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_flags_misspellings_with_suggestions() {
+        let checker = SpellChecker::default();
+        let found = checker.check("Fix teh parser\n\nRecieve events untill shutdown.");
+        let pairs: Vec<_> = found
+            .iter()
+            .map(|m| (m.word.as_str(), m.suggestion.as_str(), m.line, m.column))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("teh", "the", 0, 4),
+                ("Recieve", "Receive", 2, 0),
+                ("untill", "until", 2, 15)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_skips_code_tokens() {
+        let checker = SpellChecker::default();
+        let text = "Rename `recieve` to recieve_msg in teh_mod and tehValue\n\
+                    ```\nlet teh = 1;\n```\nsee https://example.com/teh and src/teh.rs";
+        assert!(checker.check(text).is_empty());
+    }
+
+    #[test]
+    fn test_fix_replaces_all() {
+        let checker = SpellChecker::default();
+        let (fixed, count) = checker.fix("Teh seperate modules\nare definately done\n");
+        assert_eq!(fixed, "The separate modules\nare definitely done\n");
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_repository_dictionary() {
+        let root = TempDir::new().expect("temp dir");
+        std::fs::create_dir_all(root.path().join(".git-iris")).expect("dir");
+        std::fs::write(root.path().join(DICTIONARY_FILE), "# project words\nTeh\n").expect("write");
+        let checker = SpellChecker::load(Some(root.path()));
+        assert!(checker.check("teh").is_empty());
+        assert_eq!(checker.check("recieve").len(), 1);
+    }
+}
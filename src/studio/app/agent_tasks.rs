@@ -2,10 +2,12 @@
 //!
 //! Contains all async task spawning functions for Iris agent operations.
 
+use crate::error::Explained;
 use crate::types::GeneratedMessage;
 
 use super::{ChatUpdateType, IrisTaskResult, StudioApp};
 use crate::studio::events::{BlameInfo, SemanticBlameResult, TaskType};
+use crate::studio::utils::truncate_chars;
 
 impl StudioApp {
     // ═══════════════════════════════════════════════════════════════════════════════
@@ -14,7 +16,7 @@ impl StudioApp {
 
     /// Spawn a task for chat query - uses Iris agent with chat capability
     pub(super) fn spawn_chat_query(
-        &self,
+        &mut self,
         message: String,
         context: crate::studio::events::ChatContext,
     ) {
@@ -48,11 +50,15 @@ impl StudioApp {
         let tx_status = self.iris_result_tx.clone();
         let tx_updates = self.iris_result_tx.clone();
         let mode = context.mode;
+        let code_section = context.code_context.clone().unwrap_or_default();
 
         // Extract conversation history (convert VecDeque → Vec)
         let chat_history: Vec<ChatMessage> =
             self.state.chat_state.messages.iter().cloned().collect();
 
+        // Explicitly pinned files/hunks travel with every question
+        let pinned_section = self.state.chat_state.pinned_prompt_section();
+
         // Use context content if provided, otherwise extract from state
         let current_content = context
             .current_content
@@ -64,7 +70,7 @@ impl StudioApp {
         let cancel_updates = cancel_token.clone();
 
         // Spawn a status polling task (polls global state, so still uses interval)
-        tokio::spawn(async move {
+        self.tasks.spawn_for(TaskType::Chat, async move {
             use crate::agents::status::IrisPhase;
             let mut last_tool: Option<String> = None;
             let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(100));
@@ -96,7 +102,7 @@ impl StudioApp {
         });
 
         // Spawn a task to listen for content updates from tools (uses select! for zero latency)
-        tokio::spawn(async move {
+        self.tasks.spawn_for(TaskType::Chat, async move {
             loop {
                 tokio::select! {
                     () = cancel_updates.cancelled() => break,
@@ -114,6 +120,7 @@ impl StudioApp {
                                     title,
                                     message,
                                     completion_message: None,
+                                    variant: None,
                                 })
                             }
                             ContentUpdate::PR { content } => {
@@ -131,7 +138,7 @@ impl StudioApp {
             }
         });
 
-        tokio::spawn(async move {
+        self.tasks.spawn_for(TaskType::Chat, async move {
             // Build comprehensive context (universal chat across all modes)
             let mode_context = format!(
                 "Current Mode: {:?}\nYou are Iris, a helpful git assistant. You have access to all generated content across modes and can help with commit messages, PR descriptions, code reviews, changelogs, and release notes.",
@@ -174,9 +181,22 @@ You have tools to update content. When the user asks you to modify, change, upda
 
 Simply call the appropriate tool with the new content. Do NOT echo back the full content in your response - the tool will update it directly.";
 
+            // Selected code (with enclosing item) goes right before the request
+            let code_section = if code_section.is_empty() {
+                String::new()
+            } else {
+                format!("\n{}", code_section)
+            };
+
             let prompt = format!(
-                "{}{}{}{}\n\n## Current Request\nUser: {}",
-                mode_context, content_section, history_str, update_instructions, message
+                "{}{}{}{}{}{}\n\n## Current Request\nUser: {}",
+                mode_context,
+                content_section,
+                pinned_section,
+                history_str,
+                update_instructions,
+                code_section,
+                message
             );
 
             // Execute with streaming and content update tools
@@ -210,7 +230,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
                 Err(e) => {
                     let _ = tx.send(IrisTaskResult::ChatResponse(format!(
                         "I encountered an error: {}",
-                        e
+                        Explained(&e)
                     )));
                 }
             }
@@ -236,44 +256,28 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
         // Code review
         let review = &self.state.modes.review.review_content;
         if !review.is_empty() {
-            let preview = if review.len() > 500 {
-                format!("{}...", &review[..500])
-            } else {
-                review.clone()
-            };
+            let preview = truncate_chars(review, 500);
             sections.push(format!("## Code Review\n{}", preview));
         }
 
         // PR description
         let pr = &self.state.modes.pr.pr_content;
         if !pr.is_empty() {
-            let preview = if pr.len() > 500 {
-                format!("{}...", &pr[..500])
-            } else {
-                pr.clone()
-            };
+            let preview = truncate_chars(pr, 500);
             sections.push(format!("## PR Description\n{}", preview));
         }
 
         // Changelog
         let cl = &self.state.modes.changelog.changelog_content;
         if !cl.is_empty() {
-            let preview = if cl.len() > 500 {
-                format!("{}...", &cl[..500])
-            } else {
-                cl.clone()
-            };
+            let preview = truncate_chars(cl, 500);
             sections.push(format!("## Changelog\n{}", preview));
         }
 
         // Release notes
         let rn = &self.state.modes.release_notes.release_notes_content;
         if !rn.is_empty() {
-            let preview = if rn.len() > 500 {
-                format!("{}...", &rn[..500])
-            } else {
-                rn.clone()
-            };
+            let preview = truncate_chars(rn, 500);
             sections.push(format!("## Release Notes\n{}", preview));
         }
 
@@ -289,7 +293,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
     // ═══════════════════════════════════════════════════════════════════════════════
 
     /// Spawn a task for code review generation with streaming
-    pub(super) fn spawn_review_generation(&self, from_ref: String, to_ref: String) {
+    pub(super) fn spawn_review_generation(&mut self, from_ref: String, to_ref: String) {
         use super::super::events::AgentTask;
         use crate::agents::{StructuredResponse, TaskContext};
 
@@ -312,7 +316,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
         let tx = self.iris_result_tx.clone();
         let streaming_tx = tx.clone();
 
-        tokio::spawn(async move {
+        self.tasks.spawn_for(TaskType::Review, async move {
             // Use review context with specified refs
             let context = match TaskContext::for_review(None, Some(from_ref), Some(to_ref), false) {
                 Ok(ctx) => ctx,
@@ -358,7 +362,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
                 Err(e) => {
                     let _ = tx.send(IrisTaskResult::Error {
                         task_type: TaskType::Review,
-                        error: format!("Review error: {}", e),
+                        error: format!("Review error: {}", Explained(&e)),
                     });
                 }
             }
@@ -370,7 +374,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
     // ═══════════════════════════════════════════════════════════════════════════════
 
     /// Spawn a task for PR description generation with streaming
-    pub(super) fn spawn_pr_generation(&self, base_branch: String, to_ref: &str) {
+    pub(super) fn spawn_pr_generation(&mut self, base_branch: String, to_ref: &str) {
         use super::super::events::AgentTask;
         use crate::agents::{StructuredResponse, TaskContext};
 
@@ -393,7 +397,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
         let tx = self.iris_result_tx.clone();
         let streaming_tx = tx.clone();
 
-        tokio::spawn(async move {
+        self.tasks.spawn_for(TaskType::PR, async move {
             // Build context for PR (comparing current branch to base)
             let context = TaskContext::for_pr(Some(base_branch), None);
 
@@ -425,7 +429,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
                 Err(e) => {
                     let _ = tx.send(IrisTaskResult::Error {
                         task_type: TaskType::PR,
-                        error: format!("PR error: {}", e),
+                        error: format!("PR error: {}", Explained(&e)),
                     });
                 }
             }
@@ -437,7 +441,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
     // ═══════════════════════════════════════════════════════════════════════════════
 
     /// Spawn a task for changelog generation with streaming
-    pub(super) fn spawn_changelog_generation(&self, from_ref: String, to_ref: String) {
+    pub(super) fn spawn_changelog_generation(&mut self, from_ref: String, to_ref: String) {
         use super::super::events::AgentTask;
         use crate::agents::{StructuredResponse, TaskContext};
 
@@ -460,7 +464,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
         let tx = self.iris_result_tx.clone();
         let streaming_tx = tx.clone();
 
-        tokio::spawn(async move {
+        self.tasks.spawn_for(TaskType::Changelog, async move {
             // Build context for changelog (comparing two refs, date auto-set to today)
             let context = TaskContext::for_changelog(from_ref, Some(to_ref), None, None);
 
@@ -495,7 +499,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
                 Err(e) => {
                     let _ = tx.send(IrisTaskResult::Error {
                         task_type: TaskType::Changelog,
-                        error: format!("Changelog error: {}", e),
+                        error: format!("Changelog error: {}", Explained(&e)),
                     });
                 }
             }
@@ -507,7 +511,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
     // ═══════════════════════════════════════════════════════════════════════════════
 
     /// Spawn a task for release notes generation with streaming
-    pub(super) fn spawn_release_notes_generation(&self, from_ref: String, to_ref: String) {
+    pub(super) fn spawn_release_notes_generation(&mut self, from_ref: String, to_ref: String) {
         use super::super::events::AgentTask;
         use crate::agents::{StructuredResponse, TaskContext};
 
@@ -530,7 +534,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
         let tx = self.iris_result_tx.clone();
         let streaming_tx = tx.clone();
 
-        tokio::spawn(async move {
+        self.tasks.spawn_for(TaskType::ReleaseNotes, async move {
             // Build context for release notes (comparing two refs, date auto-set to today)
             let context = TaskContext::for_changelog(from_ref, Some(to_ref), None, None);
 
@@ -555,17 +559,20 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
                         task_type: TaskType::ReleaseNotes,
                     });
 
-                    let release_notes_text = match response {
-                        StructuredResponse::ReleaseNotes(rn) => rn.content,
-                        StructuredResponse::PlainText(text) => text,
-                        other => other.to_string(),
+                    let (release_notes_text, suggested_version) = match response {
+                        StructuredResponse::ReleaseNotes(rn) => (rn.content, rn.suggested_version),
+                        StructuredResponse::PlainText(text) => (text, None),
+                        other => (other.to_string(), None),
                     };
-                    let _ = tx.send(IrisTaskResult::ReleaseNotesContent(release_notes_text));
+                    let _ = tx.send(IrisTaskResult::ReleaseNotesContent {
+                        content: release_notes_text,
+                        suggested_version,
+                    });
                 }
                 Err(e) => {
                     let _ = tx.send(IrisTaskResult::Error {
                         task_type: TaskType::ReleaseNotes,
-                        error: format!("Release notes error: {}", e),
+                        error: format!("Release notes error: {}", Explained(&e)),
                     });
                 }
             }
@@ -578,7 +585,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
 
     /// Spawn a task to generate a commit message
     pub(super) fn spawn_commit_generation(
-        &self,
+        &mut self,
         instructions: Option<String>,
         preset: String,
         use_gitmoji: bool,
@@ -596,10 +603,16 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
             return;
         };
 
+        let variants = super::super::variants::plan(
+            self.state.config.variant_count,
+            &self.state.config.variant_presets,
+            &preset,
+        );
+
         // Spawn dynamic status messages
         let task = AgentTask::Commit {
             instructions: instructions.clone(),
-            preset: preset.clone(),
+            preset,
             use_gitmoji,
             amend,
         };
@@ -618,7 +631,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
 
         let tx = self.iris_result_tx.clone();
 
-        tokio::spawn(async move {
+        self.tasks.spawn_for(TaskType::Commit, async move {
             // Use amend context if amending, otherwise standard commit context
             let context = if amend {
                 TaskContext::for_amend(original_message)
@@ -626,43 +639,53 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
                 TaskContext::for_gen()
             };
 
-            // Execute commit capability with style overrides
-            let preset_opt = if preset == "default" {
-                None
-            } else {
-                Some(preset.as_str())
-            };
-
-            match agent
-                .execute_task_with_style(
+            // Request every variant at once
+            let labeled = variants.len() > 1;
+            let requests = variants.iter().map(|variant| {
+                // Execute commit capability with style overrides
+                let preset_opt = (variant.preset != "default").then_some(variant.preset.as_str());
+                agent.execute_task_with_style(
                     "commit",
-                    context,
+                    context.clone(),
                     preset_opt,
                     Some(use_gitmoji),
                     instructions.as_deref(),
+                    variant.temperature,
                 )
-                .await
-            {
-                Ok(response) => {
-                    // Extract message from response
-                    match response {
-                        StructuredResponse::CommitMessage(msg) => {
-                            let _ = tx.send(IrisTaskResult::CommitMessages(vec![msg]));
-                        }
-                        _ => {
-                            let _ = tx.send(IrisTaskResult::Error {
-                                task_type: TaskType::Commit,
-                                error: "Unexpected response type from agent".to_string(),
-                            });
+            });
+            let responses = futures::future::join_all(requests).await;
+
+            let mut messages = Vec::new();
+            let mut first_error = None;
+            for (variant, response) in variants.iter().zip(responses) {
+                match response {
+                    Ok(StructuredResponse::CommitMessage(mut msg)) => {
+                        if labeled {
+                            msg.variant = Some(variant.label());
                         }
+                        messages.push(msg);
+                    }
+                    Ok(_) => {
+                        first_error.get_or_insert_with(|| {
+                            "Unexpected response type from agent".to_string()
+                        });
+                    }
+                    Err(e) => {
+                        tracing::warn!("Commit variant {} failed: {}", variant.label(), e);
+                        first_error
+                            .get_or_insert_with(|| format!("Agent error: {}", Explained(&e)));
                     }
                 }
-                Err(e) => {
-                    let _ = tx.send(IrisTaskResult::Error {
-                        task_type: TaskType::Commit,
-                        error: format!("Agent error: {}", e),
-                    });
-                }
+            }
+
+            // Some variants are better than none; only report failure if all failed
+            if messages.is_empty() {
+                let _ = tx.send(IrisTaskResult::Error {
+                    task_type: TaskType::Commit,
+                    error: first_error.unwrap_or_else(|| "No commit message generated".to_string()),
+                });
+            } else {
+                let _ = tx.send(IrisTaskResult::CommitMessages(messages));
             }
         });
     }
@@ -675,7 +698,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
     /// All blocking I/O (file read, git blame) runs in a background task to avoid
     /// blocking the UI event loop.
     pub(super) fn gather_blame_and_spawn(
-        &self,
+        &mut self,
         file: &std::path::Path,
         start_line: usize,
         end_line: usize,
@@ -705,11 +728,10 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
         let file = file.to_path_buf();
         let repo_path = repo.repo_path().clone();
 
-        tokio::spawn(async move {
+        self.tasks.spawn_for(TaskType::SemanticBlame, async move {
             // Run blocking I/O in spawn_blocking to avoid blocking the tokio runtime
             let blame_result = tokio::task::spawn_blocking(move || {
                 use std::fs;
-                use std::process::Command;
 
                 // Read file content
                 let content = fs::read_to_string(&file)?;
@@ -723,7 +745,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
                 let code_content = lines[(start_line - 1)..end].join("\n");
 
                 // Run git blame
-                let output = Command::new("git")
+                let output = crate::git::git_command()
                     .args([
                         "-C",
                         &repo_path.to_string_lossy(),
@@ -818,7 +840,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
                 Err(e) => {
                     let _ = tx.send(IrisTaskResult::Error {
                         task_type: TaskType::SemanticBlame,
-                        error: format!("Semantic blame error: {}", e),
+                        error: format!("Semantic blame error: {}", Explained(&e)),
                     });
                 }
             }
@@ -827,7 +849,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
 
     /// Spawn the semantic blame agent to explain why the code exists.
     /// Used when blame info is already collected (e.g., from `AgentTask::SemanticBlame`).
-    pub(super) fn spawn_semantic_blame(&self, blame_info: BlameInfo) {
+    pub(super) fn spawn_semantic_blame(&mut self, blame_info: BlameInfo) {
         use super::super::events::AgentTask;
         use crate::agents::StructuredResponse;
 
@@ -848,7 +870,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
 
         let tx = self.iris_result_tx.clone();
 
-        tokio::spawn(async move {
+        self.tasks.spawn_for(TaskType::SemanticBlame, async move {
             // Build context with blame info
             let context_text = format!(
                 "File: {}\nLines: {}-{}\nCommit: {} by {} on {}\nMessage: {}\n\nCode:\n{}",
@@ -891,7 +913,7 @@ Simply call the appropriate tool with the new content. Do NOT echo back the full
                 Err(e) => {
                     let _ = tx.send(IrisTaskResult::Error {
                         task_type: TaskType::SemanticBlame,
-                        error: format!("Semantic blame error: {}", e),
+                        error: format!("Semantic blame error: {}", Explained(&e)),
                     });
                 }
             }
@@ -0,0 +1,283 @@
+//! Picking up config edits made while Studio runs
+//!
+//! The personal config and the repository's `.irisconfig` are checked every
+//! couple of seconds. When either file changes — edited by hand, or saved by
+//! another git-iris process — the config is loaded again and the settings that
+//! differ from the previous load are copied onto Studio's running config.
+//! Copying only what changed on disk keeps session overrides such as
+//! `--provider` or `--preset` in place until the file itself touches that
+//! setting.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::Result;
+
+use crate::config::Config;
+
+/// How often the config files are checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A setting that's applied to a running Studio when it changes on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadedSetting {
+    Provider,
+    ProviderSettings,
+    Preset,
+    Instructions,
+    Gitmoji,
+    Theme,
+}
+
+impl ReloadedSetting {
+    const ALL: [Self; 6] = [
+        Self::Provider,
+        Self::ProviderSettings,
+        Self::Preset,
+        Self::Instructions,
+        Self::Gitmoji,
+        Self::Theme,
+    ];
+
+    fn differs(self, old: &Config, new: &Config) -> bool {
+        match self {
+            Self::Provider => old.default_provider != new.default_provider,
+            Self::ProviderSettings => old.providers != new.providers,
+            Self::Preset => old.instruction_preset != new.instruction_preset,
+            Self::Instructions => old.instructions != new.instructions,
+            Self::Gitmoji => old.use_gitmoji != new.use_gitmoji,
+            Self::Theme => old.theme != new.theme,
+        }
+    }
+
+    fn copy(self, from: &Config, to: &mut Config) {
+        match self {
+            Self::Provider => to.default_provider.clone_from(&from.default_provider),
+            Self::ProviderSettings => to.providers.clone_from(&from.providers),
+            Self::Preset => to.instruction_preset.clone_from(&from.instruction_preset),
+            Self::Instructions => to.instructions.clone_from(&from.instructions),
+            Self::Gitmoji => to.use_gitmoji = from.use_gitmoji,
+            Self::Theme => to.theme.clone_from(&from.theme),
+        }
+    }
+
+    /// What changed, for the notification; never includes API keys
+    fn describe(self, old: &Config, new: &Config) -> String {
+        match self {
+            Self::Provider => format!(
+                "provider {} → {}",
+                old.default_provider, new.default_provider
+            ),
+            Self::ProviderSettings => {
+                let mut names: Vec<&String> = old
+                    .providers
+                    .keys()
+                    .chain(new.providers.keys())
+                    .filter(|name| old.providers.get(*name) != new.providers.get(*name))
+                    .collect();
+                names.sort();
+                names.dedup();
+                names
+                    .into_iter()
+                    .map(|name| {
+                        let old_model = old.providers.get(name).map(|p| p.model.as_str());
+                        let new_model = new.providers.get(name).map(|p| p.model.as_str());
+                        match (old_model, new_model) {
+                            (Some(old_model), Some(new_model)) if old_model != new_model => {
+                                format!("{name} model {old_model} → {new_model}")
+                            }
+                            _ => format!("{name} settings"),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+            Self::Preset => format!(
+                "preset {} → {}",
+                old.instruction_preset, new.instruction_preset
+            ),
+            Self::Instructions => "instructions".to_string(),
+            Self::Gitmoji => format!("gitmoji {}", if new.use_gitmoji { "on" } else { "off" }),
+            Self::Theme => format!("theme {} → {}", theme_name(old), theme_name(new)),
+        }
+    }
+}
+
+fn theme_name(config: &Config) -> &str {
+    if config.theme.is_empty() {
+        "default"
+    } else {
+        &config.theme
+    }
+}
+
+/// Settings that changed between two loads of the config files
+pub struct ConfigReload {
+    previous: Config,
+    current: Config,
+    pub changes: Vec<ReloadedSetting>,
+}
+
+impl ConfigReload {
+    fn between(previous: Config, current: Config) -> Self {
+        let changes = ReloadedSetting::ALL
+            .into_iter()
+            .filter(|setting| setting.differs(&previous, &current))
+            .collect();
+        Self {
+            previous,
+            current,
+            changes,
+        }
+    }
+
+    /// Copy the changed settings onto a running config
+    pub fn apply_to(&self, config: &mut Config) {
+        for setting in &self.changes {
+            setting.copy(&self.current, config);
+        }
+    }
+
+    /// One-line summary for a notification
+    pub fn summary(&self) -> String {
+        let changes: Vec<String> = self
+            .changes
+            .iter()
+            .map(|setting| setting.describe(&self.previous, &self.current))
+            .collect();
+        format!("Config reloaded: {}", changes.join(", "))
+    }
+}
+
+/// Watches the config files for changes made outside this Studio
+pub struct ConfigWatcher {
+    paths: Vec<PathBuf>,
+    modified: Vec<Option<SystemTime>>,
+    /// The config as last loaded from disk, before session overrides
+    on_disk: Config,
+    last_check: Instant,
+}
+
+impl ConfigWatcher {
+    /// Start watching; `running` stands in for the files if they can't be
+    /// read now
+    pub fn new(running: &Config) -> Self {
+        let paths = [Config::get_config_path(), Config::get_project_config_path()]
+            .into_iter()
+            .filter_map(Result::ok)
+            .collect();
+        let mut watcher = Self {
+            paths,
+            modified: Vec::new(),
+            on_disk: running.clone(),
+            last_check: Instant::now(),
+        };
+        watcher.modified = watcher.stamps();
+        if let Ok(config) = load() {
+            watcher.on_disk = config;
+        }
+        watcher
+    }
+
+    fn stamps(&self) -> Vec<Option<SystemTime>> {
+        self.paths
+            .iter()
+            .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+            .collect()
+    }
+
+    /// Reload the config if a file changed since the last check
+    ///
+    /// Returns `None` while nothing relevant changed, and the load error if
+    /// a changed file no longer parses.
+    pub fn poll(&mut self) -> Option<Result<ConfigReload>> {
+        if self.last_check.elapsed() < CHECK_INTERVAL {
+            return None;
+        }
+        self.last_check = Instant::now();
+        let stamps = self.stamps();
+        if stamps == self.modified {
+            return None;
+        }
+        self.modified = stamps;
+
+        let config = match load() {
+            Ok(config) => config,
+            Err(e) => return Some(Err(e)),
+        };
+        let previous = std::mem::replace(&mut self.on_disk, config.clone());
+        let reload = ConfigReload::between(previous, config);
+        (!reload.changes.is_empty()).then_some(Ok(reload))
+    }
+
+    /// Take the files as they are now without reporting a change, after
+    /// Studio has written them itself
+    pub fn sync(&mut self) {
+        self.modified = self.stamps();
+        if let Ok(config) = load() {
+            self.on_disk = config;
+        }
+    }
+}
+
+/// Load the config like [`Config::load`], but fail on an unreadable
+/// `.irisconfig` instead of ignoring it
+fn load() -> Result<Config> {
+    let mut config = Config::load_personal()?;
+    if Config::get_project_config_path().is_ok_and(|path| path.exists()) {
+        config.merge_with_project_config(Config::load_project_config()?);
+    }
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::ProviderConfig;
+
+    #[test]
+    fn test_reload_applies_only_what_changed_on_disk() {
+        let before = Config {
+            default_provider: "openai".to_string(),
+            providers: [(
+                "anthropic".to_string(),
+                ProviderConfig {
+                    model: "claude-sonnet-4-5".to_string(),
+                    ..ProviderConfig::default()
+                },
+            )]
+            .into(),
+            ..Config::default()
+        };
+
+        let mut after = before.clone();
+        after.theme = "nord".to_string();
+        after.use_gitmoji = !before.use_gitmoji;
+        if let Some(anthropic) = after.providers.get_mut("anthropic") {
+            anthropic.model = "claude-opus-4-1".to_string();
+            anthropic.api_key = "sk-secret".to_string();
+        }
+
+        let reload = ConfigReload::between(before.clone(), after);
+        assert_eq!(
+            reload.changes,
+            vec![
+                ReloadedSetting::ProviderSettings,
+                ReloadedSetting::Gitmoji,
+                ReloadedSetting::Theme
+            ]
+        );
+        let summary = reload.summary();
+        assert!(summary.contains("anthropic model claude-sonnet-4-5 → claude-opus-4-1"));
+        assert!(summary.contains("theme default → nord"));
+        assert!(!summary.contains("sk-secret"));
+
+        // A session override of an unchanged setting survives the reload
+        let mut running = before;
+        running.default_provider = "anthropic".to_string();
+        reload.apply_to(&mut running);
+        assert_eq!(running.default_provider, "anthropic");
+        assert_eq!(running.theme, "nord");
+        assert_eq!(running.providers["anthropic"].api_key, "sk-secret");
+    }
+}
@@ -3,6 +3,9 @@
 //! Event loop and rendering coordination.
 
 mod agent_tasks;
+mod config_reload;
+mod session_export;
+mod tasks;
 
 use anyhow::{Result, anyhow};
 use crossterm::event::{
@@ -26,25 +29,34 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 
 use crate::agents::IrisAgentService;
-use crate::config::Config;
-use crate::git::GitRepo;
+use crate::config::{AutoTrigger, Config, Tone, Verbosity};
+use crate::error::{Explained, IrisError};
+use crate::git::{GitRepo, normalize_path};
+use crate::outputs::Artifact;
+use crate::providers::Provider;
 use crate::services::GitCommitService;
-use crate::types::GeneratedMessage;
+use crate::types::{GeneratedMessage, SuggestedVersion};
 
 use super::components::{DiffHunk, DiffLine, FileDiff, FileGitStatus, parse_diff};
 use super::events::{
-    AgentResult, ContentPayload, ContentType, SemanticBlameResult, SideEffect, StudioEvent,
-    TaskType,
+    AgentResult, ContentPayload, ContentType, SemanticBlameResult, SideEffect, StashAction,
+    StudioEvent, TaskType,
 };
 use super::history::History;
-use super::layout::{LayoutAreas, calculate_layout, get_mode_layout};
+use super::layout::{LayoutAreas, calculate_layout, get_mode_layout, is_too_small};
 use super::reducer::reduce;
 use super::render::{
-    render_changelog_panel, render_commit_panel, render_companion_status_bar, render_explore_panel,
-    render_modal, render_pr_panel, render_release_notes_panel, render_review_panel,
+    render_changelog_panel, render_commit_panel, render_companion_status_bar,
+    render_dashboard_panel, render_explore_panel, render_modal, render_panel_switcher,
+    render_pr_panel, render_rebase_panel, render_release_notes_panel, render_review_panel,
+    render_stash_panel, render_too_small,
+};
+use super::state::{
+    CommitQueueState, GitStatus, IrisStatus, Modal, Mode, Notification, PanelId, StudioState,
 };
-use super::state::{GitStatus, IrisStatus, Mode, Notification, PanelId, StudioState};
 use super::theme;
+use config_reload::{ConfigWatcher, ReloadedSetting};
+use tasks::TaskSupervisor;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Async Task Results
@@ -60,8 +72,11 @@ pub enum IrisTaskResult {
     PRContent(String),
     /// Generated changelog (markdown)
     ChangelogContent(String),
-    /// Generated release notes (markdown)
-    ReleaseNotesContent(String),
+    /// Generated release notes (markdown) and the suggested next version
+    ReleaseNotesContent {
+        content: String,
+        suggested_version: Option<SuggestedVersion>,
+    },
     /// Chat response from Iris
     ChatResponse(String),
     /// Chat-triggered update to current content
@@ -93,6 +108,61 @@ pub enum IrisTaskResult {
     GlobalLogLoaded {
         entries: Vec<crate::studio::state::FileLogEntry>,
     },
+    /// Heat map data computed for the explore file tree
+    HeatMapLoaded(std::sync::Arc<super::components::HeatMapData>),
+    /// Dashboard activity statistics computed
+    DashboardLoaded(std::sync::Arc<super::state::DashboardData>),
+    /// Provider model list fetched for the model picker
+    ModelsLoaded {
+        provider: String,
+        result: Result<Vec<String>, String>,
+    },
+    /// Pickaxe search results for the search modal
+    PickaxeLoaded {
+        query: String,
+        kind: crate::git::PickaxeKind,
+        result: Result<Vec<crate::git::PickaxeHit>, String>,
+    },
+    /// Iris's answer to a semantic code search
+    SemanticSearchLoaded {
+        query: String,
+        result: Result<String, String>,
+    },
+    /// Settings connection test finished (latency in ms or error)
+    ConnectionTested(Result<u128, String>),
+    /// GitHub pull request opened (number and URL) or failed
+    PullRequestCreated(Result<(u64, String), String>),
+    /// CI checks on the pushed head of a branch, or why they couldn't be read
+    CiStatusLoaded {
+        head: String,
+        result: Result<crate::integrations::ci::CiStatus, String>,
+    },
+    /// Review posted (pull request number and review URL) or failed
+    ReviewPosted(Result<(u64, String), String>),
+    /// Description of the changes about to be stashed, or why there is none
+    StashDescribed(Result<String, String>),
+    /// Recap of recent work on a branch after an idle gap
+    RecapReady {
+        branch: String,
+        result: Result<String, String>,
+    },
+    /// Rebase todo list suggested by Iris
+    RebaseSuggested(Result<String, String>),
+    /// Architecture overview file for `HEAD`
+    RepoOverviewReady(Result<std::path::PathBuf, String>),
+    /// Whole-file blame regions
+    FileBlameLoaded {
+        file: std::path::PathBuf,
+        result: Result<Vec<crate::git::BlameRegion>, String>,
+        explaining: bool,
+    },
+    /// Iris's reasons for the commits in a whole-file blame
+    FileBlameExplained {
+        file: std::path::PathBuf,
+        result: Result<String, String>,
+    },
+    /// A newer git-iris release was found at startup
+    UpdateAvailable(Box<crate::update::Release>),
     /// Git status loaded (async initialization)
     GitStatusLoaded(Box<GitStatusData>),
     /// Companion service initialized (async)
@@ -117,6 +187,8 @@ pub struct CompanionInitData {
     pub service: crate::companion::CompanionService,
     /// Display data for the UI
     pub display: super::state::CompanionSessionDisplay,
+    /// What to recap when the branch was left idle for a long time
+    pub recap: Option<crate::recap::RecapFacts>,
 }
 
 /// Type of content update triggered by chat
@@ -158,8 +230,10 @@ pub struct StudioApp {
     last_click: Option<(std::time::Instant, u16, u16)>,
     /// Drag selection start info (panel, line number) for code view selection
     drag_start: Option<(PanelId, usize)>,
-    /// Background task handles to abort on exit
-    background_tasks: Vec<tokio::task::JoinHandle<()>>,
+    /// Spawned tasks, torn down on cancel and on exit
+    tasks: TaskSupervisor,
+    /// Picks up config edits made outside this Studio
+    config_watcher: ConfigWatcher,
 }
 
 impl StudioApp {
@@ -179,6 +253,7 @@ impl StudioApp {
             History::new()
         };
 
+        let config_watcher = ConfigWatcher::new(&config);
         let state = StudioState::new(config, repo);
         let (iris_result_tx, iris_result_rx) = mpsc::unbounded_channel();
 
@@ -194,7 +269,8 @@ impl StudioApp {
             explicit_mode_set: false,
             last_click: None,
             drag_start: None,
-            background_tasks: Vec::new(),
+            tasks: TaskSupervisor::default(),
+            config_watcher,
         }
     }
 
@@ -232,11 +308,23 @@ impl StudioApp {
                 SideEffect::Quit => return Some(ExitResult::Quit),
 
                 SideEffect::ExecuteCommit { message } => {
-                    return Some(self.perform_commit(&message));
+                    if self.validate_commit_message(&message) {
+                        return Some(self.perform_commit(&message));
+                    }
                 }
 
                 SideEffect::ExecuteAmend { message } => {
-                    return Some(self.perform_amend(&message));
+                    if self.validate_commit_message(&message) {
+                        return Some(self.perform_amend(&message));
+                    }
+                }
+
+                SideEffect::QueueCommit { message, files } => {
+                    self.queue_commit(message, files);
+                }
+
+                SideEffect::RunCommitQueue => {
+                    self.start_commit_queue();
                 }
 
                 SideEffect::Redraw => {
@@ -255,6 +343,10 @@ impl StudioApp {
                     self.unstage_file(&path.to_string_lossy());
                 }
 
+                SideEffect::GitStageFiles(paths) => {
+                    self.stage_files(&paths);
+                }
+
                 SideEffect::GitStageAll => {
                     self.stage_all();
                 }
@@ -263,25 +355,38 @@ impl StudioApp {
                     self.unstage_all();
                 }
 
+                SideEffect::UndoLastCommit => {
+                    self.undo_last_commit();
+                }
+
                 SideEffect::SaveSettings => {
                     self.save_settings();
                 }
 
-                SideEffect::CopyToClipboard(text) => match arboard::Clipboard::new() {
-                    Ok(mut clipboard) => {
-                        if let Err(e) = clipboard.set_text(&text) {
-                            self.state
-                                .notify(Notification::error(format!("Failed to copy: {e}")));
-                        } else {
+                SideEffect::SavePreset => {
+                    self.save_preset();
+                }
+
+                SideEffect::SaveArtifact(artifact) => {
+                    self.save_artifact(artifact);
+                }
+
+                SideEffect::ReconfigureAgent => {
+                    self.reconfigure_agent();
+                }
+
+                SideEffect::CopyToClipboard(text) => {
+                    match crate::clipboard::copy_text(&text, self.state.config.clipboard_osc52) {
+                        Ok(_) => {
                             self.state
                                 .notify(Notification::success("Copied to clipboard"));
                         }
+                        Err(e) => {
+                            self.state
+                                .notify(Notification::error(format!("Failed to copy: {e}")));
+                        }
                     }
-                    Err(e) => {
-                        self.state
-                            .notify(Notification::error(format!("Clipboard unavailable: {e}")));
-                    }
-                },
+                }
 
                 SideEffect::ShowNotification {
                     level,
@@ -302,7 +407,12 @@ impl StudioApp {
                 }
 
                 SideEffect::SpawnAgent { task } => {
+                    if let Some(provider) = self.state.degraded {
+                        self.run_offline(&task, provider);
+                        continue;
+                    }
                     // Status messages are now spawned inside each spawn_*_generation method
+                    self.tasks.begin(task.task_type());
                     match task {
                         AgentTask::Commit {
                             instructions,
@@ -310,6 +420,8 @@ impl StudioApp {
                             use_gitmoji,
                             amend,
                         } => {
+                            let commit = &mut self.state.modes.commit;
+                            commit.generated_for = commit.staged_fingerprint;
                             self.spawn_commit_generation(instructions, preset, use_gitmoji, amend);
                         }
                         AgentTask::Review { from_ref, to_ref } => {
@@ -336,14 +448,27 @@ impl StudioApp {
                     }
                 }
 
+                SideEffect::CancelTask(task_type) => {
+                    self.tasks.cancel(task_type);
+                }
+
                 SideEffect::GatherBlameAndSpawnAgent {
                     file,
                     start_line,
                     end_line,
                 } => {
+                    if let Some(provider) = self.state.degraded {
+                        self.run_offline_task_type(TaskType::SemanticBlame, provider);
+                        continue;
+                    }
+                    self.tasks.begin(TaskType::SemanticBlame);
                     self.gather_blame_and_spawn(&file, start_line, end_line);
                 }
 
+                SideEffect::AutoGenerate(mode) => {
+                    self.auto_generate(mode);
+                }
+
                 SideEffect::LoadData {
                     data_type,
                     from_ref,
@@ -369,6 +494,15 @@ impl StudioApp {
                         DataType::ExploreFiles => {
                             self.update_explore_file_tree();
                         }
+                        DataType::Stashes => {
+                            self.update_stash_data();
+                        }
+                        DataType::RebasePlan => {
+                            self.load_rebase_plan();
+                        }
+                        DataType::RebaseDiff => {
+                            self.load_rebase_diff();
+                        }
                     }
                 }
 
@@ -379,6 +513,100 @@ impl StudioApp {
                 SideEffect::LoadGlobalLog => {
                     self.load_global_log();
                 }
+
+                SideEffect::PickaxeSearch { query, kind } => {
+                    self.pickaxe_search(query, kind);
+                }
+
+                SideEffect::SemanticSearch { query } => {
+                    self.semantic_search(query);
+                }
+
+                SideEffect::ExplainRepo => {
+                    self.explain_repo();
+                }
+
+                SideEffect::LoadFileBlame(file) => {
+                    self.load_file_blame(file);
+                }
+
+                SideEffect::LoadHeatMap { window_days } => {
+                    self.load_heat_map(window_days);
+                }
+
+                SideEffect::LoadDashboard { window_days } => {
+                    self.load_dashboard(window_days);
+                }
+
+                SideEffect::ExportSession => {
+                    self.export_session();
+                }
+
+                SideEffect::OpenHistorySearch => {
+                    self.open_history_search();
+                }
+
+                SideEffect::ShowContextReport => {
+                    self.show_context_report();
+                }
+
+                SideEffect::LoadModels {
+                    provider,
+                    api_key,
+                    refresh,
+                } => {
+                    self.load_models(provider, api_key, refresh);
+                }
+
+                SideEffect::TestConnection {
+                    provider,
+                    model,
+                    api_key,
+                } => {
+                    self.test_connection(&provider, model, api_key);
+                }
+
+                SideEffect::LoadCiStatus => self.load_ci_status(),
+
+                SideEffect::CreatePullRequest {
+                    markdown,
+                    head,
+                    base,
+                } => {
+                    self.create_pull_request(&markdown, &head, &base);
+                }
+
+                SideEffect::PostReview {
+                    head,
+                    to_ref,
+                    body,
+                    comments,
+                } => {
+                    self.post_review(&head, &to_ref, body, comments);
+                }
+
+                SideEffect::RunStashAction { action, index, id } => {
+                    self.run_stash_action(action, index, &id);
+                }
+
+                SideEffect::CreateStash {
+                    message,
+                    include_untracked,
+                } => {
+                    self.create_stash(message.as_deref(), include_untracked);
+                }
+
+                SideEffect::DescribeStash { include_untracked } => {
+                    self.describe_stash(include_untracked);
+                }
+
+                SideEffect::SuggestRebase => {
+                    self.suggest_rebase();
+                }
+
+                SideEffect::RunRebase => {
+                    self.run_rebase();
+                }
             }
         }
         None
@@ -396,14 +624,14 @@ impl StudioApp {
                 .map(|f| {
                     f.staged_files
                         .iter()
-                        .map(|s| s.path.clone().into())
+                        .map(|s| normalize_path(&s.path))
                         .collect()
                 })
                 .unwrap_or_default();
 
             let modified_files: Vec<std::path::PathBuf> = unstaged
                 .as_ref()
-                .map(|f| f.iter().map(|s| s.path.clone().into()).collect())
+                .map(|f| f.iter().map(|s| normalize_path(&s.path)).collect())
                 .unwrap_or_default();
 
             // Get untracked files
@@ -411,7 +639,7 @@ impl StudioApp {
                 .get_untracked_files()
                 .unwrap_or_default()
                 .into_iter()
-                .map(std::path::PathBuf::from)
+                .map(normalize_path)
                 .collect();
 
             // Get ahead/behind counts
@@ -432,11 +660,24 @@ impl StudioApp {
 
             // Update file trees for components (explore tree is lazy-loaded on mode switch)
             self.update_commit_file_tree();
+            let unstaged = self.state.git_status.unstaged_files();
+            self.state
+                .modes
+                .commit
+                .message_editor
+                .set_unstaged_files(unstaged);
             self.update_review_file_tree();
 
             // Load diffs into diff view
             self.load_staged_diffs(files_info.as_ref());
 
+            // Regenerate for the new staged changes if Commit mode asks for it
+            if self.state.active_mode == Mode::Commit
+                && self.state.auto_trigger(Mode::Commit) == AutoTrigger::OnStagedChange
+            {
+                self.auto_generate(Mode::Commit);
+            }
+
             // Sync initial file selection with diff view
             if let Some(path) = self.state.modes.commit.file_tree.selected_path() {
                 self.state.modes.commit.diff_view.select_file_by_path(&path);
@@ -452,7 +693,9 @@ impl StudioApp {
 
         // Get a proper unified diff with all headers using git
         if let Ok(diff_text) = repo.get_staged_diff_full() {
+            self.state.modes.commit.staged_fingerprint = Some(diff_fingerprint(&diff_text));
             let diffs = parse_diff(&diff_text);
+            self.state.modes.commit.risk = self.assess_risk(&diffs);
             self.state.modes.commit.diff_view.set_diffs(diffs);
         } else {
             // Fallback: Build synthetic diff from file info
@@ -490,6 +733,7 @@ impl StudioApp {
                 }
                 diffs.push(file_diff);
             }
+            self.state.modes.commit.risk = self.assess_risk(&diffs);
             self.state.modes.commit.diff_view.set_diffs(diffs);
         }
     }
@@ -499,7 +743,7 @@ impl StudioApp {
         // Get all tracked files from the repository
         let Some(repo) = &self.state.repo else { return };
         let all_files: Vec<std::path::PathBuf> = match repo.get_all_tracked_files() {
-            Ok(files) => files.into_iter().map(std::path::PathBuf::from).collect(),
+            Ok(files) => files.into_iter().map(normalize_path).collect(),
             Err(e) => {
                 eprintln!("Failed to get tracked files: {}", e);
                 return;
@@ -521,9 +765,19 @@ impl StudioApp {
         if !all_files.is_empty() {
             let tree_state = super::components::FileTreeState::from_paths(&all_files, &statuses);
             self.state.modes.explore.file_tree = tree_state;
+            self.state.modes.explore.sync_heat_overlay();
 
             // Initialize selected file (content only - file log loads via event system)
-            if let Some(entry) = self.state.modes.explore.file_tree.selected_entry()
+            if let Some(document) = self.state.modes.explore.document.take() {
+                if let Err(e) = self.state.modes.explore.code_view.load_file(&document) {
+                    tracing::warn!("Failed to load document: {}", e);
+                }
+                if let Some(line) = self.state.modes.explore.document_line.take() {
+                    self.state.modes.explore.code_view.show_line(line);
+                    self.state.modes.explore.current_line = line;
+                }
+                self.state.modes.explore.current_file = Some(document);
+            } else if let Some(entry) = self.state.modes.explore.file_tree.selected_entry()
                 && !entry.is_dir
             {
                 let path = entry.path.clone();
@@ -539,7 +793,7 @@ impl StudioApp {
     }
 
     /// Load git log for a specific file (async)
-    fn load_file_log(&self, path: &std::path::Path) {
+    fn load_file_log(&mut self, path: &std::path::Path) {
         use crate::studio::state::FileLogEntry;
 
         let Some(repo) = &self.state.repo else {
@@ -550,11 +804,9 @@ impl StudioApp {
         let file = path.to_path_buf();
         let repo_path = repo.repo_path().clone();
 
-        tokio::spawn(async move {
+        self.tasks.spawn(async move {
             let file_for_result = file.clone();
             let result = tokio::task::spawn_blocking(move || {
-                use std::process::Command;
-
                 // Make file path relative to repo root
                 let relative_path = file
                     .strip_prefix(&repo_path)
@@ -564,7 +816,7 @@ impl StudioApp {
 
                 // Run git log for the specific file with format:
                 // %H = full hash, %h = short hash, %s = subject, %an = author, %ar = relative time
-                let output = Command::new("git")
+                let output = crate::git::git_command()
                     .args([
                         "-C",
                         repo_path.to_str().unwrap_or("."),
@@ -643,7 +895,7 @@ impl StudioApp {
     }
 
     /// Load global commit log (not file-specific)
-    fn load_global_log(&self) {
+    fn load_global_log(&mut self) {
         use crate::studio::state::FileLogEntry;
 
         let Some(repo) = &self.state.repo else {
@@ -653,12 +905,10 @@ impl StudioApp {
         let tx = self.iris_result_tx.clone();
         let repo_path = repo.repo_path().clone();
 
-        tokio::spawn(async move {
+        self.tasks.spawn(async move {
             let result = tokio::task::spawn_blocking(move || {
-                use std::process::Command;
-
                 // Run git log for the whole repo
-                let output = Command::new("git")
+                let output = crate::git::git_command()
                     .args([
                         "-C",
                         repo_path.to_str().unwrap_or("."),
@@ -738,178 +988,989 @@ impl StudioApp {
         });
     }
 
-    /// Load git status asynchronously (for fast TUI startup)
-    fn load_git_status_async(&self) {
+    /// Run `git log -S/-G` for the pickaxe search modal (async)
+    fn pickaxe_search(&mut self, query: String, kind: crate::git::PickaxeKind) {
+        /// Commits searched, newest first
+        const PICKAXE_LIMIT: usize = 100;
+
+        let tx = self.iris_result_tx.clone();
         let Some(repo) = &self.state.repo else {
+            let _ = tx.send(IrisTaskResult::PickaxeLoaded {
+                query,
+                kind,
+                result: Err("Repository not available".to_string()),
+            });
             return;
         };
-
-        let tx = self.iris_result_tx.clone();
         let repo_path = repo.repo_path().clone();
 
-        tokio::spawn(async move {
+        self.tasks.spawn(async move {
+            let search = query.clone();
             let result = tokio::task::spawn_blocking(move || {
-                use crate::git::GitRepo;
+                crate::git::pickaxe_search(&repo_path, &search, kind, PICKAXE_LIMIT)
+            })
+            .await;
 
-                // Open repo once and gather all data
-                let repo = GitRepo::new(&repo_path)?;
+            let result = match result {
+                Ok(result) => result.map_err(|e| e.to_string()),
+                Err(e) => Err(format!("Search task panicked: {e}")),
+            };
+            let _ = tx.send(IrisTaskResult::PickaxeLoaded {
+                query,
+                kind,
+                result,
+            });
+        });
+    }
 
-                let branch = repo.get_current_branch().unwrap_or_default();
-                let files_info = repo.extract_files_info(false).ok();
-                let unstaged = repo.get_unstaged_files().ok();
-                let untracked = repo.get_untracked_files().unwrap_or_default();
-                let (commits_ahead, commits_behind) = repo.get_ahead_behind();
-                let staged_diff = repo.get_staged_diff_full().ok();
+    /// Have Iris find the code that answers a plain-language question
+    fn semantic_search(&mut self, query: String) {
+        let tx = self.iris_result_tx.clone();
+        let fail = |query: String, error: &str| {
+            let _ = tx.send(IrisTaskResult::SemanticSearchLoaded {
+                query,
+                result: Err(error.to_string()),
+            });
+        };
+        if self.state.degraded.is_some() {
+            fail(query, "Iris is offline");
+            return;
+        }
+        let Some(service) = self.agent_service.clone() else {
+            fail(query, "Iris is not available");
+            return;
+        };
 
-                let staged_files: Vec<std::path::PathBuf> = files_info
-                    .as_ref()
-                    .map(|f| {
-                        f.staged_files
-                            .iter()
-                            .map(|s| s.path.clone().into())
-                            .collect()
-                    })
-                    .unwrap_or_default();
+        self.tasks.spawn(async move {
+            let result = service
+                .execute_task_with_prompt("semantic_search", &format!("Question: {query}"))
+                .await
+                .map(|response| response.to_string())
+                .map_err(|e| e.to_string());
+            let _ = tx.send(IrisTaskResult::SemanticSearchLoaded { query, result });
+        });
+    }
 
-                let modified_files: Vec<std::path::PathBuf> = unstaged
-                    .as_ref()
-                    .map(|f| f.iter().map(|s| s.path.clone().into()).collect())
-                    .unwrap_or_default();
+    /// Find the architecture overview of `HEAD` in the cache, or have Iris
+    /// write one
+    fn explain_repo(&mut self) {
+        let tx = self.iris_result_tx.clone();
+        let fail = |error: &str| {
+            let _ = tx.send(IrisTaskResult::RepoOverviewReady(Err(error.to_string())));
+        };
+        if self.state.degraded.is_some() {
+            fail("Iris is offline");
+            return;
+        }
+        let (Some(service), Some(repo)) = (self.agent_service.clone(), self.state.repo.clone())
+        else {
+            fail("Iris is not available");
+            return;
+        };
 
-                let untracked_files: Vec<std::path::PathBuf> = untracked
-                    .into_iter()
-                    .map(std::path::PathBuf::from)
-                    .collect();
+        self.tasks.spawn(async move {
+            let result = crate::architecture::cached_overview(&repo, false, |prompt| async move {
+                let response = service
+                    .execute_task_with_prompt("explain_repo", &prompt)
+                    .await?;
+                Ok(response.to_string())
+            })
+            .await
+            .map(|(_, path)| path)
+            .map_err(|e| e.to_string());
+            let _ = tx.send(IrisTaskResult::RepoOverviewReady(result));
+        });
+    }
 
-                Ok::<_, anyhow::Error>(GitStatusData {
-                    branch,
-                    staged_files,
-                    modified_files,
-                    untracked_files,
-                    commits_ahead,
-                    commits_behind,
-                    staged_diff,
-                })
+    /// Blame every line of `file`, then ask Iris why each commit wrote its
+    /// lines. The blame is sent first so the gutter shows without waiting.
+    fn load_file_blame(&mut self, file: std::path::PathBuf) {
+        use super::components::FileBlame;
+
+        let tx = self.iris_result_tx.clone();
+        let Some(repo) = &self.state.repo else {
+            let _ = tx.send(IrisTaskResult::FileBlameLoaded {
+                file,
+                result: Err("Repository not available".to_string()),
+                explaining: false,
+            });
+            return;
+        };
+        let repo_path = repo.repo_path().clone();
+        let service = self
+            .agent_service
+            .clone()
+            .filter(|_| self.state.degraded.is_none());
+
+        self.tasks.spawn(async move {
+            let blame_file = file.clone();
+            let blame_root = repo_path.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let regions = crate::git::blame_file(&blame_root, &blame_file)?;
+                let lines: Vec<String> = std::fs::read_to_string(&blame_file)?
+                    .lines()
+                    .map(String::from)
+                    .collect();
+                Ok::<_, anyhow::Error>((regions, lines))
             })
-            .await;
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|result| result.map_err(|e| e.to_string()));
 
-            match result {
-                Ok(Ok(data)) => {
-                    let _ = tx.send(IrisTaskResult::GitStatusLoaded(Box::new(data)));
-                }
-                Ok(Err(e)) => {
-                    tracing::warn!("Failed to load git status: {}", e);
-                }
+            let (regions, lines) = match result {
+                Ok(blamed) => blamed,
                 Err(e) => {
-                    tracing::warn!("Git status task panicked: {}", e);
+                    let _ = tx.send(IrisTaskResult::FileBlameLoaded {
+                        file,
+                        result: Err(e),
+                        explaining: false,
+                    });
+                    return;
                 }
+            };
+            let blame = FileBlame::new(regions.clone(), false);
+            let explaining = service.is_some() && blame.regions.iter().any(|r| !r.is_uncommitted());
+            let _ = tx.send(IrisTaskResult::FileBlameLoaded {
+                file: file.clone(),
+                result: Ok(regions),
+                explaining,
+            });
+
+            if let Some(service) = service.filter(|_| explaining) {
+                let relative = file.strip_prefix(&repo_path).unwrap_or(&file);
+                let result = service
+                    .execute_task_with_prompt("file_blame", &blame.to_prompt(relative, &lines))
+                    .await
+                    .map(|response| response.to_string())
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(IrisTaskResult::FileBlameExplained { file, result });
             }
         });
     }
 
-    /// Load companion service asynchronously for fast TUI startup
-    fn load_companion_async(&mut self) {
+    /// Compute per-path churn and ownership from recent history (async)
+    fn load_heat_map(&mut self, window_days: u32) {
+        use super::components::HeatMapData;
+
         let Some(repo) = &self.state.repo else {
             return;
         };
 
         let tx = self.iris_result_tx.clone();
         let repo_path = repo.repo_path().clone();
-        let branch = repo
-            .get_current_branch()
-            .unwrap_or_else(|_| "main".to_string());
 
-        let handle = tokio::spawn(async move {
+        self.tasks.spawn(async move {
             let result = tokio::task::spawn_blocking(move || {
-                use super::state::CompanionSessionDisplay;
-                use crate::companion::{BranchMemory, CompanionService};
-
-                // Create companion service (this is the slow part - file watcher setup)
-                let service = CompanionService::new(repo_path, &branch)?;
-
-                // Load or create branch memory
-                let mut branch_mem = service
-                    .load_branch_memory(&branch)
-                    .ok()
-                    .flatten()
-                    .unwrap_or_else(|| BranchMemory::new(branch.clone()));
-
-                // Get welcome message before recording visit
-                let welcome = branch_mem.welcome_message();
-
-                // Record this visit
-                branch_mem.record_visit();
+                let since = format!("--since={} days ago", window_days);
+                let output = crate::git::git_command()
+                    .args([
+                        "-C",
+                        repo_path.to_str().unwrap_or("."),
+                        "log",
+                        "--no-merges",
+                        &since,
+                        "--format=%x1e%an",
+                        "--name-only",
+                    ])
+                    .output()?;
 
-                // Save updated branch memory
-                if let Err(e) = service.save_branch_memory(&branch_mem) {
-                    tracing::warn!("Failed to save branch memory: {}", e);
+                if !output.status.success() {
+                    return Ok(HeatMapData {
+                        window_days,
+                        ..HeatMapData::default()
+                    });
                 }
 
-                let display = CompanionSessionDisplay {
-                    watcher_active: service.has_watcher(),
-                    welcome_message: welcome.clone(),
-                    welcome_shown_at: welcome.map(|_| std::time::Instant::now()),
-                    ..Default::default()
-                };
-
-                Ok::<_, anyhow::Error>(CompanionInitData { service, display })
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                Ok::<_, std::io::Error>(HeatMapData::from_git_log(&stdout, window_days))
             })
             .await;
 
-            match result {
-                Ok(Ok(data)) => {
-                    let _ = tx.send(IrisTaskResult::CompanionReady(Box::new(data)));
-                }
+            // Always report back (empty on failure) so the loading flag clears
+            let data = match result {
+                Ok(Ok(data)) => data,
                 Ok(Err(e)) => {
-                    tracing::warn!("Failed to initialize companion: {}", e);
+                    tracing::warn!("Failed to load heat map: {}", e);
+                    HeatMapData::default()
                 }
                 Err(e) => {
-                    tracing::warn!("Companion init task panicked: {}", e);
+                    tracing::warn!("Heat map task panicked: {}", e);
+                    HeatMapData::default()
                 }
-            }
+            };
+            let _ = tx.send(IrisTaskResult::HeatMapLoaded(std::sync::Arc::new(data)));
         });
-
-        // Store handle so we can abort on exit
-        self.background_tasks.push(handle);
     }
 
-    /// Run the TUI application
-    pub fn run(&mut self) -> Result<ExitResult> {
-        // Install panic hook to ensure terminal is restored on panic
-        let original_hook = std::panic::take_hook();
-        std::panic::set_hook(Box::new(move |panic_info| {
-            // Try to restore terminal
-            let _ = disable_raw_mode();
-            let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
-            // Print panic info to stderr
-            eprintln!("\n\n=== PANIC ===\n{}\n", panic_info);
-            original_hook(panic_info);
-        }));
+    /// Compute Dashboard statistics: activity, stash count and latest session
+    fn load_dashboard(&mut self, window_days: u32) {
+        use super::state::{DashboardData, SessionSummary, WorktreeSession};
+        use crate::companion::CompanionStorage;
+        use crate::plan::PlanProgress;
 
-        // Setup terminal
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+        let Some(repo) = &self.state.repo else {
+            return;
+        };
 
-        // Run main loop
-        let result = self.main_loop(&mut terminal);
+        let tx = self.iris_result_tx.clone();
+        let repo_path = repo.repo_path().clone();
 
-        // Cleanup terminal
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
+        self.tasks.spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                let since = format!("--since={} days ago", window_days);
+                let output = crate::git::git_command()
+                    .args([
+                        "-C",
+                        repo_path.to_str().unwrap_or("."),
+                        "log",
+                        "--no-merges",
+                        &since,
+                        "--format=%x1e%an%x1f%at",
+                        "--name-only",
+                    ])
+                    .output()?;
 
-        result
-    }
+                let mut data = if output.status.success() {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    DashboardData::from_git_log(&stdout, window_days, chrono::Utc::now())
+                } else {
+                    DashboardData {
+                        window_days,
+                        ..DashboardData::default()
+                    }
+                };
 
-    fn main_loop(
-        &mut self,
+                let storage = CompanionStorage::new(&repo_path).ok();
+                if let Ok(mut git) = crate::git::open_repository(&repo_path) {
+                    let _ = git.stash_foreach(|_, _, _| {
+                        data.stash_count += 1;
+                        true
+                    });
+                    data.plan = storage
+                        .as_ref()
+                        .and_then(|storage| storage.load_plan().ok().flatten())
+                        .and_then(|plan| PlanProgress::load(&git, plan).ok());
+                }
+                if let Some(storage) = &storage {
+                    data.last_session = storage
+                        .load_transcript()
+                        .ok()
+                        .flatten()
+                        .map(|transcript| SessionSummary::from_transcript(&transcript));
+                    data.other_worktrees = storage
+                        .other_worktree_sessions()
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|session| WorktreeSession::new(session, &repo_path))
+                        .collect();
+                }
+
+                Ok::<_, std::io::Error>(data)
+            })
+            .await;
+
+            // Always report back (empty on failure) so the loading flag clears
+            let data = match result {
+                Ok(Ok(data)) => data,
+                Ok(Err(e)) => {
+                    tracing::warn!("Failed to load dashboard: {}", e);
+                    DashboardData::default()
+                }
+                Err(e) => {
+                    tracing::warn!("Dashboard task panicked: {}", e);
+                    DashboardData::default()
+                }
+            };
+            let _ = tx.send(IrisTaskResult::DashboardLoaded(std::sync::Arc::new(data)));
+        });
+    }
+
+    /// Fetch the provider's model list for the model picker
+    fn load_models(&mut self, provider_name: String, api_key: Option<String>, refresh: bool) {
+        use crate::model_catalog;
+        use crate::providers::Provider;
+
+        let tx = self.iris_result_tx.clone();
+        let provider = match provider_name.parse::<Provider>() {
+            Ok(provider) => provider,
+            Err(e) => {
+                let _ = tx.send(IrisTaskResult::ModelsLoaded {
+                    provider: provider_name,
+                    result: Err(e.to_string()),
+                });
+                return;
+            }
+        };
+
+        let configured = api_key.or_else(|| {
+            self.state
+                .config
+                .get_provider_config(&provider_name)
+                .map(|p| p.api_key.clone())
+        });
+        let api_key = model_catalog::resolve_api_key(provider, configured.as_deref());
+        let headers = self.state.config.request_headers(provider);
+
+        self.tasks.spawn(async move {
+            let result = match api_key {
+                Some(key) => model_catalog::list_models(provider, &key, &headers, refresh)
+                    .await
+                    .map_err(|e| e.to_string()),
+                None => Err(format!("No API key (set {})", provider.api_key_env())),
+            };
+            let _ = tx.send(IrisTaskResult::ModelsLoaded {
+                provider: provider_name,
+                result,
+            });
+        });
+    }
+
+    /// Verify the provider, key and model entered in settings
+    fn test_connection(&mut self, provider_name: &str, model: String, api_key: Option<String>) {
+        use crate::model_catalog;
+        use crate::providers::Provider;
+
+        let tx = self.iris_result_tx.clone();
+        let provider = match provider_name.parse::<Provider>() {
+            Ok(provider) => provider,
+            Err(e) => {
+                let _ = tx.send(IrisTaskResult::ConnectionTested(Err(e.to_string())));
+                return;
+            }
+        };
+
+        let configured = api_key.or_else(|| {
+            self.state
+                .config
+                .get_provider_config(provider_name)
+                .map(|p| p.api_key.clone())
+        });
+        let api_key =
+            model_catalog::resolve_api_key(provider, configured.as_deref()).unwrap_or_default();
+        let headers = self.state.config.request_headers(provider);
+        let model = if model.is_empty() {
+            provider.default_model().to_string()
+        } else {
+            model
+        };
+
+        self.tasks.spawn(async move {
+            let result = model_catalog::test_connection(provider, &api_key, &headers, &model)
+                .await
+                .map(|latency| latency.as_millis())
+                .map_err(|e| e.to_string());
+            let _ = tx.send(IrisTaskResult::ConnectionTested(result));
+        });
+    }
+
+    /// Open a pull request on GitHub for `head` into `base`
+    fn create_pull_request(&mut self, markdown: &str, head: &str, base: &str) {
+        use crate::integrations::github::{self, NewPullRequest, RepoSlug};
+
+        let tx = self.iris_result_tx.clone();
+        let fail = |error: String| {
+            let _ = tx.send(IrisTaskResult::PullRequestCreated(Err(error)));
+        };
+        let Some(repo) = self.state.repo.as_ref().and_then(|r| r.open_repo().ok()) else {
+            fail("No repository".to_string());
+            return;
+        };
+        let Some(url) = repo
+            .find_remote("origin")
+            .ok()
+            .and_then(|remote| remote.url().map(str::to_string))
+        else {
+            fail("No origin remote".to_string());
+            return;
+        };
+        let Some(slug) = RepoSlug::from_remote_url(&url) else {
+            fail(format!("Can't tell the GitHub repository from {url}"));
+            return;
+        };
+        // GitHub can only open a PR for a branch it has
+        if repo
+            .find_branch(&format!("origin/{head}"), git2::BranchType::Remote)
+            .is_err()
+        {
+            fail(format!("Push {head} to origin first"));
+            return;
+        }
+
+        let pr = NewPullRequest::from_markdown(markdown, head, base);
+        let config = self.state.config.github.clone();
+        self.tasks.spawn(async move {
+            let result = github::create_pull_request(&config, &slug, &pr)
+                .await
+                .map(|created| (created.number, created.html_url))
+                .map_err(|e| e.to_string());
+            let _ = tx.send(IrisTaskResult::PullRequestCreated(result));
+        });
+    }
+
+    /// Read the CI checks on the pushed head of PR mode's target branch
+    fn load_ci_status(&mut self) {
+        use crate::integrations::ci;
+
+        let pr = &mut self.state.modes.pr;
+        pr.ci_loading = false;
+        let Some(head) = pr.head_branch(&self.state.git_status.branch) else {
+            pr.ci = None;
+            return;
+        };
+        let Some(repo) = self.state.repo.as_ref().and_then(|r| r.open_repo().ok()) else {
+            return;
+        };
+        let url = repo
+            .find_remote("origin")
+            .ok()
+            .and_then(|remote| remote.url().map(str::to_string));
+        // The forge only ran checks on what was pushed
+        let pushed = repo
+            .find_branch(&format!("origin/{head}"), git2::BranchType::Remote)
+            .and_then(|branch| branch.get().peel_to_commit());
+        let (url, commit) = match (url, pushed) {
+            (Some(url), Ok(commit)) => (url, commit.id().to_string()),
+            (None, _) => {
+                pr.ci = Some(Err("No origin remote".to_string()));
+                return;
+            }
+            (_, Err(_)) => {
+                pr.ci = Some(Err(format!("{head} isn't pushed to origin")));
+                return;
+            }
+        };
+
+        pr.ci_loading = true;
+        let tx = self.iris_result_tx.clone();
+        let github = self.state.config.github.clone();
+        let gitlab = self.state.config.gitlab.clone();
+        self.tasks.spawn(async move {
+            let result = ci::fetch_status(&github, &gitlab, &url, &commit)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(IrisTaskResult::CiStatusLoaded { head, result });
+        });
+    }
+
+    /// Post review comments to the open pull request from `head`
+    fn post_review(
+        &mut self,
+        head: &str,
+        to_ref: &str,
+        body: String,
+        comments: Vec<crate::integrations::github::ReviewComment>,
+    ) {
+        use crate::integrations::github::{self, NewReview, RepoSlug};
+
+        let tx = self.iris_result_tx.clone();
+        let fail = |error: String| {
+            let _ = tx.send(IrisTaskResult::ReviewPosted(Err(error)));
+        };
+        let Some(repo) = self.state.repo.as_ref().and_then(|r| r.open_repo().ok()) else {
+            fail("No repository".to_string());
+            return;
+        };
+        let Some(url) = repo
+            .find_remote("origin")
+            .ok()
+            .and_then(|remote| remote.url().map(str::to_string))
+        else {
+            fail("No origin remote".to_string());
+            return;
+        };
+        let Some(slug) = RepoSlug::from_remote_url(&url) else {
+            fail(format!("Can't tell the GitHub repository from {url}"));
+            return;
+        };
+        // Line numbers only match the pull request if GitHub has the
+        // reviewed commit at the head of the branch
+        let reviewed = repo
+            .revparse_single(to_ref)
+            .and_then(|object| object.peel_to_commit());
+        let pushed = repo
+            .find_branch(&format!("origin/{head}"), git2::BranchType::Remote)
+            .and_then(|branch| branch.get().peel_to_commit());
+        let commit_id = match (reviewed, pushed) {
+            (Ok(reviewed), Ok(pushed)) if reviewed.id() == pushed.id() => reviewed.id().to_string(),
+            (Ok(_), Ok(_)) => {
+                fail(format!(
+                    "origin/{head} isn't at the reviewed commit; push {head} first"
+                ));
+                return;
+            }
+            (Err(e), _) => {
+                fail(format!("Can't resolve {to_ref}: {}", e.message()));
+                return;
+            }
+            (_, Err(_)) => {
+                fail(format!("Push {head} to origin first"));
+                return;
+            }
+        };
+
+        let review = NewReview {
+            commit_id,
+            body,
+            event: "COMMENT",
+            comments,
+        };
+        let config = self.state.config.github.clone();
+        let head = head.to_string();
+        self.tasks.spawn(async move {
+            let result = async {
+                let Some(pr) = github::find_pull_request(&config, &slug, &head).await? else {
+                    anyhow::bail!("No open pull request for {head}");
+                };
+                let posted = github::create_review(&config, &slug, pr.number, &review).await?;
+                Ok((pr.number, posted.html_url))
+            }
+            .await
+            .map_err(|e| e.to_string());
+            let _ = tx.send(IrisTaskResult::ReviewPosted(result));
+        });
+    }
+
+    /// Apply, pop or drop a stash, then reload the stash list and status
+    fn run_stash_action(&mut self, action: StashAction, index: usize, id: &str) {
+        let Some(repo) = self.state.repo.clone() else {
+            self.state
+                .notify(Notification::error("No repository available"));
+            return;
+        };
+        let (result, verb, done) = match action {
+            StashAction::Apply => (repo.apply_stash(index, id), "apply", "Applied"),
+            StashAction::Pop => (repo.pop_stash(index, id), "pop", "Popped"),
+            StashAction::Drop => (repo.drop_stash(index, id), "drop", "Dropped"),
+        };
+        match result {
+            Ok(()) => {
+                self.state
+                    .notify(Notification::success(format!("{done} stash@{{{index}}}")));
+            }
+            Err(e) => {
+                self.state.notify(Notification::error(format!(
+                    "Couldn't {verb} stash@{{{index}}}: {e}"
+                )));
+            }
+        }
+        self.update_stash_data();
+        let _ = self.refresh_git_status();
+    }
+
+    /// Stash local changes and select the new stash
+    fn create_stash(&mut self, message: Option<&str>, include_untracked: bool) {
+        let Some(repo) = self.state.repo.clone() else {
+            self.state
+                .notify(Notification::error("No repository available"));
+            return;
+        };
+        match repo.create_stash(message, include_untracked) {
+            Ok(entry) => {
+                self.state.notify(Notification::success(format!(
+                    "Stashed as {}: {}",
+                    entry.selector(),
+                    entry.description()
+                )));
+                self.load_stash_list();
+                self.state.modes.stash.selected = 0;
+                self.load_stash_diff();
+                self.state.mark_dirty();
+                let _ = self.refresh_git_status();
+            }
+            Err(e) => {
+                self.state
+                    .notify(Notification::error(format!("Couldn't stash: {e}")));
+            }
+        }
+    }
+
+    /// Have Iris describe the changes a new stash would save
+    fn describe_stash(&mut self, include_untracked: bool) {
+        /// Diff tokens sent; the start of a change says enough to name it
+        const DIFF_BUDGET: usize = 6_000;
+
+        let tx = self.iris_result_tx.clone();
+        let fail = |error: &str| {
+            let _ = tx.send(IrisTaskResult::StashDescribed(Err(error.to_string())));
+        };
+        if self.state.degraded.is_some() {
+            fail("Iris is offline");
+            return;
+        }
+        let (Some(repo), Some(service)) = (self.state.repo.clone(), self.agent_service.clone())
+        else {
+            fail("Iris is not available");
+            return;
+        };
+        let diff = match repo.get_worktree_diff_full(include_untracked) {
+            Ok(diff) => diff,
+            Err(e) => {
+                fail(&e.to_string());
+                return;
+            }
+        };
+        let (diff, _, truncated) = crate::doc_context::truncate_to_tokens(&diff, DIFF_BUDGET);
+        let prompt = format!(
+            "Branch: {}\n\n```diff\n{diff}\n```{}",
+            self.state.git_status.branch,
+            if truncated { "\n(diff cut short)" } else { "" }
+        );
+
+        self.tasks.spawn(async move {
+            let result = service
+                .execute_task_with_prompt("stash", &prompt)
+                .await
+                .map(|response| {
+                    let text = response.to_string();
+                    text.lines().next().unwrap_or_default().trim().to_string()
+                })
+                .map_err(|e| e.to_string());
+            let _ = tx.send(IrisTaskResult::StashDescribed(result));
+        });
+    }
+
+    /// Have Iris recap recent work on the branch after a long idle gap
+    fn recap(&mut self, facts: crate::recap::RecapFacts) {
+        let Some(service) = self.agent_service.clone() else {
+            return;
+        };
+        if self.state.degraded.is_some() {
+            return;
+        }
+        let tx = self.iris_result_tx.clone();
+        self.tasks.spawn(async move {
+            let result = service
+                .execute_task_with_prompt("recap", &facts.to_prompt())
+                .await
+                .map(|response| response.to_string())
+                .map_err(|e| e.to_string());
+            let _ = tx.send(IrisTaskResult::RecapReady {
+                branch: facts.branch,
+                result,
+            });
+        });
+    }
+
+    /// Have Iris suggest a rebase plan for the current branch
+    fn suggest_rebase(&mut self) {
+        let tx = self.iris_result_tx.clone();
+        let fail = |error: &str| {
+            let _ = tx.send(IrisTaskResult::RebaseSuggested(Err(error.to_string())));
+        };
+        if self.state.degraded.is_some() {
+            fail("Iris is offline");
+            return;
+        }
+        let Some(service) = self.agent_service.clone() else {
+            fail("Iris is not available");
+            return;
+        };
+        let Some(plan) = &self.state.modes.rebase.plan else {
+            fail("no plan loaded");
+            return;
+        };
+        let prompt = plan.prompt();
+
+        self.tasks.spawn(async move {
+            let result = service
+                .execute_task_with_prompt("rebase", &prompt)
+                .await
+                .map(|response| response.to_string())
+                .map_err(|e| e.to_string());
+            let _ = tx.send(IrisTaskResult::RebaseSuggested(result));
+        });
+    }
+
+    /// Run the rebase plan, then reload the branch and status
+    fn run_rebase(&mut self) {
+        let (Some(repo), Some(plan)) = (self.state.repo.clone(), &self.state.modes.rebase.plan)
+        else {
+            return;
+        };
+        match repo.execute_rebase(plan) {
+            Ok(outcome) => {
+                let old_head = &outcome.old_head[..7.min(outcome.old_head.len())];
+                self.state.notify(Notification::success(format!(
+                    "Rebased into {} commits · undo with git reset --hard {old_head}",
+                    outcome.commits
+                )));
+                self.load_rebase_plan();
+                let _ = self.refresh_git_status();
+            }
+            Err(e) => {
+                self.state
+                    .notify(Notification::error(format!("Rebase failed: {e}")));
+            }
+        }
+    }
+
+    /// Load git status asynchronously (for fast TUI startup)
+    fn load_git_status_async(&mut self) {
+        let Some(repo) = &self.state.repo else {
+            return;
+        };
+
+        let tx = self.iris_result_tx.clone();
+        let repo_path = repo.repo_path().clone();
+
+        self.tasks.spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                use crate::git::GitRepo;
+
+                // Open repo once and gather all data
+                let repo = GitRepo::new(&repo_path)?;
+
+                let branch = repo.get_current_branch().unwrap_or_default();
+                let files_info = repo.extract_files_info(false).ok();
+                let unstaged = repo.get_unstaged_files().ok();
+                let untracked = repo.get_untracked_files().unwrap_or_default();
+                let (commits_ahead, commits_behind) = repo.get_ahead_behind();
+                let staged_diff = repo.get_staged_diff_full().ok();
+
+                let staged_files: Vec<std::path::PathBuf> = files_info
+                    .as_ref()
+                    .map(|f| {
+                        f.staged_files
+                            .iter()
+                            .map(|s| normalize_path(&s.path))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                let modified_files: Vec<std::path::PathBuf> = unstaged
+                    .as_ref()
+                    .map(|f| f.iter().map(|s| normalize_path(&s.path)).collect())
+                    .unwrap_or_default();
+
+                let untracked_files: Vec<std::path::PathBuf> =
+                    untracked.into_iter().map(normalize_path).collect();
+
+                Ok::<_, anyhow::Error>(GitStatusData {
+                    branch,
+                    staged_files,
+                    modified_files,
+                    untracked_files,
+                    commits_ahead,
+                    commits_behind,
+                    staged_diff,
+                })
+            })
+            .await;
+
+            match result {
+                Ok(Ok(data)) => {
+                    let _ = tx.send(IrisTaskResult::GitStatusLoaded(Box::new(data)));
+                }
+                Ok(Err(e)) => {
+                    tracing::warn!("Failed to load git status: {}", e);
+                }
+                Err(e) => {
+                    tracing::warn!("Git status task panicked: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Note the active mode in the local analytics file, when enabled
+    fn record_mode_usage(&self) {
+        if self.state.config.local_analytics {
+            crate::analytics::record(&crate::analytics::UsageEvent::mode(
+                self.state.active_mode.display_name(),
+            ));
+        }
+    }
+
+    /// Look for a newer git-iris release when `check_for_updates` is on
+    ///
+    /// Failures (offline, rate limited) are only logged; this never gets in
+    /// the way.
+    fn check_for_update_async(&mut self) {
+        if !self.state.config.check_for_updates {
+            return;
+        }
+        let tx = self.iris_result_tx.clone();
+        self.tasks.spawn(async move {
+            match crate::update::check_for_update().await {
+                Ok(Some(release)) => {
+                    let _ = tx.send(IrisTaskResult::UpdateAvailable(Box::new(release)));
+                }
+                Ok(None) => {}
+                Err(e) => tracing::debug!("Update check skipped: {}", e),
+            }
+        });
+    }
+
+    /// Explain what works without a provider when Studio starts without an
+    /// API key
+    fn announce_degraded_mode(&mut self) {
+        let Some(provider) = self.state.degraded else {
+            return;
+        };
+        self.state.notify(Notification::warning(format!(
+            "No API key for {provider}: AI features are off. Staging, diffs and history work, and commit messages are scaffolded from the staged files. Press S to add a key."
+        )));
+    }
+
+    /// Stand-in for an agent task while no provider is configured: commit
+    /// messages are scaffolded from the staged files, every other task
+    /// reports what's missing
+    fn run_offline(&mut self, task: &super::events::AgentTask, provider: Provider) {
+        use super::events::AgentTask;
+
+        if let AgentTask::Commit { .. } = task {
+            self.scaffold_commit_message();
+            return;
+        }
+        self.run_offline_task_type(task.task_type(), provider);
+    }
+
+    /// Report that `task_type` needs a provider
+    fn run_offline_task_type(&self, task_type: TaskType, provider: Provider) {
+        let error: anyhow::Error = IrisError::MissingApiKey { provider }.into();
+        let _ = self.iris_result_tx.send(IrisTaskResult::Error {
+            task_type,
+            error: format!("{}; press S to add one", Explained(&error)),
+        });
+    }
+
+    /// Draft a Conventional Commits message from the staged files alone
+    fn scaffold_commit_message(&mut self) {
+        let Some(repo) = self.state.repo.clone() else {
+            return;
+        };
+        let config = self.state.config.clone();
+        let tx = self.iris_result_tx.clone();
+        self.tasks.spawn(async move {
+            let staged = tokio::task::spawn_blocking(move || {
+                repo.get_git_info(&config)
+                    .map(|info| info.staged_files)
+                    .unwrap_or_default()
+            })
+            .await
+            .unwrap_or_default();
+            let result = match crate::commit_scaffold::scaffold(&staged) {
+                Some(message) => IrisTaskResult::CommitMessages(vec![message]),
+                None => IrisTaskResult::Error {
+                    task_type: TaskType::Commit,
+                    error: "Nothing staged to scaffold a message from".to_string(),
+                },
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Load companion service asynchronously for fast TUI startup
+    fn load_companion_async(&mut self) {
+        let Some(repo) = &self.state.repo else {
+            return;
+        };
+
+        let tx = self.iris_result_tx.clone();
+        let repo = Arc::clone(repo);
+        let repo_path = repo.repo_path().clone();
+        let branch = repo
+            .get_current_branch()
+            .unwrap_or_else(|_| "main".to_string());
+
+        self.tasks.spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                use super::state::CompanionSessionDisplay;
+                use crate::companion::{BranchMemory, CompanionService};
+
+                // Create companion service (this is the slow part - file watcher setup)
+                let service = CompanionService::new(repo_path, &branch)?;
+
+                // Load or create branch memory
+                let stored = service.load_branch_memory(&branch).ok().flatten();
+                let returning = stored.is_some();
+                let mut branch_mem = stored.unwrap_or_else(|| BranchMemory::new(branch.clone()));
+
+                // Get welcome message before recording visit
+                let welcome = branch_mem.welcome_message();
+
+                // Gather the recap before this visit counts as activity
+                let last_active = branch_mem
+                    .last_visited
+                    .max(service.session().read().last_activity);
+                let recap = (returning && crate::recap::is_idle_gap(last_active))
+                    .then(|| {
+                        crate::recap::RecapFacts::gather(
+                            &repo,
+                            crate::recap::DEFAULT_REFLOG_ENTRIES,
+                        )
+                        .ok()
+                    })
+                    .flatten();
+
+                // Record this visit
+                branch_mem.record_visit();
+
+                // Save updated branch memory
+                if let Err(e) = service.save_branch_memory(&branch_mem) {
+                    tracing::warn!("Failed to save branch memory: {}", e);
+                }
+
+                let display = CompanionSessionDisplay {
+                    watcher_active: service.has_watcher(),
+                    welcome_message: welcome.clone(),
+                    welcome_shown_at: welcome.map(|_| std::time::Instant::now()),
+                    ..Default::default()
+                };
+
+                Ok::<_, anyhow::Error>(CompanionInitData {
+                    service,
+                    display,
+                    recap,
+                })
+            })
+            .await;
+
+            match result {
+                Ok(Ok(data)) => {
+                    let _ = tx.send(IrisTaskResult::CompanionReady(Box::new(data)));
+                }
+                Ok(Err(e)) => {
+                    tracing::warn!("Failed to initialize companion: {}", e);
+                }
+                Err(e) => {
+                    tracing::warn!("Companion init task panicked: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Run the TUI application
+    pub fn run(&mut self) -> Result<ExitResult> {
+        // Install panic hook to ensure terminal is restored on panic
+        let original_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            // Try to restore terminal
+            restore_terminal();
+            // Print panic info to stderr
+            eprintln!("\n\n=== PANIC ===\n{}\n", panic_info);
+            original_hook(panic_info);
+        }));
+
+        // Setup terminal
+        let mut terminal = match setup_terminal() {
+            Ok(terminal) => terminal,
+            Err(e) => {
+                restore_terminal();
+                return Err(e);
+            }
+        };
+
+        // Run main loop
+        let result = self.main_loop(&mut terminal);
+
+        // Cleanup terminal
+        restore_terminal();
+
+        result
+    }
+
+    fn main_loop(
+        &mut self,
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<ExitResult> {
         // Start async git status loading for fast TUI startup
@@ -919,6 +1980,10 @@ impl StudioApp {
         // Start async companion initialization (file watcher setup is slow)
         self.load_companion_async();
 
+        self.check_for_update_async();
+        self.announce_degraded_mode();
+        self.record_mode_usage();
+
         // Note: Auto-generation happens in apply_git_status_data() after async load completes
 
         loop {
@@ -929,14 +1994,28 @@ impl StudioApp {
 
             // Check for completed Iris tasks
             self.check_iris_results();
+            self.tasks.reap();
+
+            // Make the next queued commit, one per pass so progress redraws
+            if let Some(result) = self.step_commit_queue() {
+                self.save_session_transcript();
+                return Ok(result);
+            }
 
             // Poll companion events (file watcher)
             self.check_companion_events();
+            self.check_config_changes();
 
             // Process any queued events through reducer
+            let mode_before = self.state.active_mode;
             if let Some(result) = self.process_events() {
+                // Keep the latest transcript around for `git-iris export-session`
+                self.save_session_transcript();
                 return Ok(result);
             }
+            if self.state.active_mode != mode_before {
+                self.record_mode_usage();
+            }
 
             // Render if dirty
             if self.state.check_dirty() {
@@ -946,12 +2025,10 @@ impl StudioApp {
             // Poll for events with timeout for animations
             if event::poll(Duration::from_millis(50))? {
                 match event::read()? {
-                    Event::Key(key) => {
-                        // Only handle key press events
-                        if key.kind == KeyEventKind::Press {
-                            // Push to event queue - reducer will handle via existing handlers
-                            self.push_event(StudioEvent::KeyPressed(key));
-                        }
+                    // Only handle key press events
+                    Event::Key(key) if key.kind == KeyEventKind::Press => {
+                        // Push to event queue - reducer will handle via existing handlers
+                        self.push_event(StudioEvent::KeyPressed(key));
                     }
                     Event::Mouse(mouse) => {
                         match mouse.kind {
@@ -1065,12 +2142,68 @@ impl StudioApp {
         }
     }
 
+    /// Apply config edits made outside this Studio: the running config,
+    /// theme and agent service pick up whatever changed on disk
+    fn check_config_changes(&mut self) {
+        let reload = match self.config_watcher.poll() {
+            None => return,
+            Some(Ok(reload)) => reload,
+            Some(Err(e)) => {
+                self.state
+                    .notify(Notification::error(format!("Config not reloaded: {e:#}")));
+                return;
+            }
+        };
+        reload.apply_to(&mut self.state.config);
+        self.state.degraded = self.state.config.missing_api_key();
+
+        if reload.changes.contains(&ReloadedSetting::Theme) {
+            let theme = &self.state.config.theme;
+            if theme.is_empty() {
+                crate::theme::set_theme(crate::theme::Theme::builtin_neon());
+            } else if let Err(e) = crate::theme::load_theme_by_name(theme) {
+                self.state.notify(Notification::warning(format!(
+                    "Theme '{theme}' not loaded: {e}"
+                )));
+            }
+        }
+        if reload
+            .changes
+            .iter()
+            .any(|setting| *setting != ReloadedSetting::Theme)
+        {
+            self.reconfigure_agent();
+        }
+
+        self.state.notify(Notification::info(reload.summary()));
+        self.state.mark_dirty();
+    }
+
+    /// Point the agent service at Studio's running config
+    fn reconfigure_agent(&mut self) {
+        let Some(service) = &self.agent_service else {
+            return;
+        };
+        match service.reconfigured(self.state.config.clone()) {
+            Ok(service) => self.agent_service = Some(Arc::new(service)),
+            Err(e) => self
+                .state
+                .notify(Notification::error(format!("Provider not switched: {e}"))),
+        }
+    }
+
     /// Check for completed Iris task results
     /// Convert async Iris results to events and push to queue
     fn check_iris_results(&mut self) {
+        for violation in crate::agents::policy::take_violations() {
+            self.state.notify(Notification::warning(format!(
+                "Tool policy refused {violation}"
+            )));
+        }
         while let Ok(result) = self.iris_result_rx.try_recv() {
             let event = match result {
                 IrisTaskResult::CommitMessages(messages) => {
+                    self.record_context_report(TaskType::Commit);
                     // Use completion_message from agent if available, otherwise spawn generation
                     if let Some(msg) = messages.first().and_then(|m| m.completion_message.clone()) {
                         tracing::info!("Using agent completion_message: {:?}", msg);
@@ -1090,6 +2223,7 @@ impl StudioApp {
                 }
 
                 IrisTaskResult::ReviewContent(content) => {
+                    self.record_context_report(TaskType::Review);
                     // Extract first line as hint
                     let hint = content.lines().next().map(|l| l.chars().take(60).collect());
                     self.spawn_completion_message("review", hint);
@@ -1100,6 +2234,7 @@ impl StudioApp {
                 }
 
                 IrisTaskResult::PRContent(content) => {
+                    self.record_context_report(TaskType::PR);
                     let hint = content.lines().next().map(|l| l.chars().take(60).collect());
                     self.spawn_completion_message("pr", hint);
                     StudioEvent::AgentComplete {
@@ -1109,6 +2244,7 @@ impl StudioApp {
                 }
 
                 IrisTaskResult::ChangelogContent(content) => {
+                    self.record_context_report(TaskType::Changelog);
                     let hint = content.lines().next().map(|l| l.chars().take(60).collect());
                     self.spawn_completion_message("changelog", hint);
                     StudioEvent::AgentComplete {
@@ -1117,12 +2253,19 @@ impl StudioApp {
                     }
                 }
 
-                IrisTaskResult::ReleaseNotesContent(content) => {
+                IrisTaskResult::ReleaseNotesContent {
+                    content,
+                    suggested_version,
+                } => {
+                    self.record_context_report(TaskType::ReleaseNotes);
                     let hint = content.lines().next().map(|l| l.chars().take(60).collect());
                     self.spawn_completion_message("release_notes", hint);
                     StudioEvent::AgentComplete {
                         task_type: TaskType::ReleaseNotes,
-                        result: AgentResult::ReleaseNotesContent(content),
+                        result: AgentResult::ReleaseNotesContent {
+                            content,
+                            suggested_version,
+                        },
                     }
                 }
 
@@ -1208,6 +2351,70 @@ impl StudioApp {
                     StudioEvent::GlobalLogLoaded { entries }
                 }
 
+                IrisTaskResult::HeatMapLoaded(data) => StudioEvent::HeatMapLoaded { data },
+
+                IrisTaskResult::DashboardLoaded(data) => StudioEvent::DashboardLoaded { data },
+
+                IrisTaskResult::ModelsLoaded { provider, result } => {
+                    StudioEvent::ModelsLoaded { provider, result }
+                }
+
+                IrisTaskResult::PickaxeLoaded {
+                    query,
+                    kind,
+                    result,
+                } => StudioEvent::PickaxeLoaded {
+                    query,
+                    kind,
+                    result,
+                },
+
+                IrisTaskResult::SemanticSearchLoaded { query, result } => {
+                    StudioEvent::SemanticSearchLoaded { query, result }
+                }
+
+                IrisTaskResult::ConnectionTested(result) => {
+                    StudioEvent::ConnectionTested { result }
+                }
+
+                IrisTaskResult::PullRequestCreated(result) => {
+                    StudioEvent::PullRequestCreated { result }
+                }
+
+                IrisTaskResult::CiStatusLoaded { head, result } => {
+                    StudioEvent::CiStatusLoaded { head, result }
+                }
+
+                IrisTaskResult::ReviewPosted(result) => StudioEvent::ReviewPosted { result },
+
+                IrisTaskResult::StashDescribed(result) => StudioEvent::StashDescribed { result },
+                IrisTaskResult::RepoOverviewReady(result) => {
+                    StudioEvent::RepoOverviewReady { result }
+                }
+                IrisTaskResult::FileBlameLoaded {
+                    file,
+                    result,
+                    explaining,
+                } => StudioEvent::FileBlameLoaded {
+                    file,
+                    result,
+                    explaining,
+                },
+                IrisTaskResult::FileBlameExplained { file, result } => {
+                    StudioEvent::FileBlameExplained { file, result }
+                }
+                IrisTaskResult::RecapReady { branch, result } => {
+                    StudioEvent::RecapReady { branch, result }
+                }
+
+                IrisTaskResult::RebaseSuggested(result) => StudioEvent::RebaseSuggested { result },
+
+                IrisTaskResult::UpdateAvailable(release) => {
+                    self.state
+                        .notify(Notification::info(crate::update::update_notice(&release)));
+                    continue;
+                }
+
                 IrisTaskResult::GitStatusLoaded(data) => {
                     // Apply git status data directly (not through reducer)
                     self.apply_git_status_data(*data);
@@ -1216,10 +2423,14 @@ impl StudioApp {
 
                 IrisTaskResult::CompanionReady(data) => {
                     // Apply companion data directly
+                    let data = *data;
                     self.state.companion = Some(data.service);
                     self.state.companion_display = data.display;
                     self.state.mark_dirty();
                     tracing::info!("Companion service initialized asynchronously");
+                    if let Some(facts) = data.recap.filter(|facts| !facts.is_empty()) {
+                        self.recap(facts);
+                    }
                     continue; // Already handled
                 }
             };
@@ -1248,11 +2459,19 @@ impl StudioApp {
 
         // Update file trees
         self.update_commit_file_tree();
+        let unstaged = self.state.git_status.unstaged_files();
+        self.state
+            .modes
+            .commit
+            .message_editor
+            .set_unstaged_files(unstaged);
         self.update_review_file_tree();
 
         // Load diffs from staged diff text
         if let Some(diff_text) = data.staged_diff {
+            self.state.modes.commit.staged_fingerprint = Some(diff_fingerprint(&diff_text));
             let diffs = parse_diff(&diff_text);
+            self.state.modes.commit.risk = self.assess_risk(&diffs);
             self.state.modes.commit.diff_view.set_diffs(diffs);
         }
 
@@ -1261,6 +2480,19 @@ impl StudioApp {
             self.state.modes.commit.diff_view.select_file_by_path(&path);
         }
 
+        // A message left by `git-iris undo` beats generating a new one
+        if self.state.modes.commit.messages.is_empty()
+            && let Some(repo) = &self.state.repo
+            && let Some(draft) = crate::undo::take_draft(repo, &self.state.git_status.branch)
+        {
+            self.state.load_commit_draft(&draft);
+            self.explicit_mode_set = true;
+            self.state.notify(Notification::info(format!(
+                "Restored the message of undone commit {}",
+                draft.short_hash()
+            )));
+        }
+
         // If no explicit mode was set, switch to suggested mode
         if !self.explicit_mode_set {
             let suggested = self.state.suggest_initial_mode();
@@ -1269,58 +2501,75 @@ impl StudioApp {
             }
         }
 
-        // Auto-generate based on mode
+        // Load the mode's data, then generate if its trigger allows
         match self.state.active_mode {
-            Mode::Commit => {
-                if self.state.git_status.has_staged() {
-                    self.auto_generate_commit();
-                }
-            }
-            Mode::Review => {
-                self.update_review_data(None, None);
-                self.auto_generate_review();
-            }
-            Mode::PR => {
-                self.update_pr_data(None, None);
-                self.auto_generate_pr();
-            }
-            Mode::Changelog => {
-                self.update_changelog_data(None, None);
-                self.auto_generate_changelog();
-            }
-            Mode::ReleaseNotes => {
-                self.update_release_notes_data(None, None);
-                self.auto_generate_release_notes();
-            }
+            Mode::Commit => {}
+            Mode::Review => self.update_review_data(None, None),
+            Mode::PR => self.update_pr_data(None, None),
+            Mode::Changelog => self.update_changelog_data(None, None),
+            Mode::ReleaseNotes => self.update_release_notes_data(None, None),
             Mode::Explore => {
                 self.update_explore_file_tree();
             }
+            Mode::Dashboard => {
+                self.state.modes.dashboard.loading = true;
+                self.load_dashboard(super::state::DashboardState::WINDOW_DAYS);
+            }
+            Mode::Stash => self.update_stash_data(),
+            Mode::Rebase => self.load_rebase_plan(),
         }
+        self.auto_generate(self.state.active_mode);
 
         self.state.mark_dirty();
     }
 
+    /// Status message generator for the configured personality pack
+    ///
+    /// Packs that never call the model work without an agent service.
+    fn status_generator(&self) -> Option<crate::agents::StatusMessageGenerator> {
+        use crate::agents::StatusMessageGenerator;
+
+        let personality = self.state.config.status_personality;
+        let generator = match self.agent_service.as_ref() {
+            Some(agent) => {
+                tracing::info!(
+                    "Status generator using provider={}, fast_model={}, personality={}",
+                    agent.provider(),
+                    agent.fast_model(),
+                    personality
+                );
+                let headers = agent
+                    .provider()
+                    .parse()
+                    .map(|provider| agent.config().request_headers(provider))
+                    .unwrap_or_default();
+                StatusMessageGenerator::new(agent.provider(), agent.fast_model())
+                    .with_headers(headers)
+            }
+            None if !personality.uses_model() => StatusMessageGenerator::new("", ""),
+            None => return None,
+        };
+        Some(generator.with_personality(personality))
+    }
+
     /// Spawn fire-and-forget status message generation using the fast model
     ///
     /// This spawns an async task that generates witty status messages while
     /// the user waits for the main agent task to complete. Messages are
     /// sent via the result channel and displayed in the status bar.
-    fn spawn_status_messages(&self, task: &super::events::AgentTask) {
-        use crate::agents::{StatusContext, StatusMessageGenerator};
+    fn spawn_status_messages(&mut self, task: &super::events::AgentTask) {
+        use crate::agents::{StatusContext, StatusPersonality};
 
         tracing::info!("spawn_status_messages called for task: {:?}", task);
 
-        let Some(agent) = self.agent_service.as_ref() else {
+        if self.state.config.status_personality == StatusPersonality::Silent {
+            return;
+        }
+        let Some(status_gen) = self.status_generator() else {
             tracing::warn!("No agent service available for status messages");
             return;
         };
 
-        tracing::info!(
-            "Status generator using provider={}, fast_model={}",
-            agent.provider(),
-            agent.fast_model()
-        );
-
         // Build context from task type
         let (task_type, activity) = match task {
             super::events::AgentTask::Commit { amend, .. } => {
@@ -1422,9 +2671,8 @@ impl StudioApp {
 
         // Fire-and-forget: spawn ONE generation attempt
         let tx = self.iris_result_tx.clone();
-        let status_gen = StatusMessageGenerator::new(agent.provider(), agent.fast_model());
 
-        tokio::spawn(async move {
+        self.tasks.spawn_for(task.task_type(), async move {
             tracing::info!("Status message starting for task: {}", context.task_type);
             let start = std::time::Instant::now();
             match tokio::time::timeout(
@@ -1455,12 +2703,56 @@ impl StudioApp {
         });
     }
 
+    /// Copy the service's context size report for a finished task into history
+    fn record_context_report(&mut self, task_type: TaskType) {
+        let report = self
+            .agent_service
+            .as_ref()
+            .and_then(|service| service.context_report(&task_type.to_string()));
+        if let Some(report) = report {
+            self.history.record_context_report(report);
+        }
+        for warning in crate::context::take_limit_warnings() {
+            self.state.notify(Notification::warning(warning));
+        }
+    }
+
+    /// Open the token breakdown of the latest generation, preferring the
+    /// active mode's
+    fn show_context_report(&mut self) {
+        let capability = match self.state.active_mode {
+            Mode::Commit => Some(TaskType::Commit),
+            Mode::Review => Some(TaskType::Review),
+            Mode::PR => Some(TaskType::PR),
+            Mode::Changelog => Some(TaskType::Changelog),
+            Mode::ReleaseNotes => Some(TaskType::ReleaseNotes),
+            Mode::Explore | Mode::Dashboard | Mode::Stash | Mode::Rebase => None,
+        }
+        .map(|task_type| task_type.to_string());
+        let report = self
+            .history
+            .latest_context_report(capability.as_deref())
+            .or_else(|| self.history.latest_context_report(None));
+        match report {
+            Some(report) => {
+                self.state.modal = Some(Modal::ContextReport(Box::new(report.clone())));
+                self.state.mark_dirty();
+            }
+            None => self.state.notify(Notification::info(
+                "No generations yet this session; `git-iris trace` shows earlier ones",
+            )),
+        }
+    }
+
     /// Spawn completion message generation using the fast model
     /// This generates a clever completion message based on the content that was just generated.
-    fn spawn_completion_message(&self, task_type: &str, content_hint: Option<String>) {
-        use crate::agents::{StatusContext, StatusMessageGenerator};
+    fn spawn_completion_message(&mut self, task_type: &str, content_hint: Option<String>) {
+        use crate::agents::{StatusContext, StatusPersonality};
 
-        let Some(agent) = self.agent_service.as_ref() else {
+        if self.state.config.status_personality == StatusPersonality::Silent {
+            return;
+        }
+        let Some(status_gen) = self.status_generator() else {
             return;
         };
 
@@ -1479,11 +2771,10 @@ impl StudioApp {
         }
 
         let tx = self.iris_result_tx.clone();
-        let status_gen = StatusMessageGenerator::new(agent.provider(), agent.fast_model());
 
-        tokio::spawn(async move {
+        self.tasks.spawn(async move {
             match tokio::time::timeout(
-                std::time::Duration::from_millis(2000),
+                std::time::Duration::from_secs(2),
                 status_gen.generate_completion(&context),
             )
             .await
@@ -1499,15 +2790,44 @@ impl StudioApp {
         });
     }
 
-    /// Auto-generate commit message on app start
+    /// Generate in `mode` if its `[auto_generate]` trigger and the status bar
+    /// toggle allow it and there's something to generate from
+    fn auto_generate(&mut self, mode: Mode) {
+        if self.state.auto_trigger(mode) == AutoTrigger::Manual {
+            return;
+        }
+        // Without a provider only the commit scaffold can run unasked
+        if self.state.degraded.is_some() && mode != Mode::Commit {
+            return;
+        }
+        match mode {
+            Mode::Commit => self.auto_generate_commit(),
+            Mode::Review => self.auto_generate_review(),
+            Mode::PR => self.auto_generate_pr(),
+            Mode::Changelog => self.auto_generate_changelog(),
+            Mode::ReleaseNotes => self.auto_generate_release_notes(),
+            Mode::Explore | Mode::Dashboard | Mode::Stash | Mode::Rebase => {}
+        }
+    }
+
+    /// Auto-generate commit message for the staged changes
     fn auto_generate_commit(&mut self) {
-        // Don't regenerate if we already have messages
-        if !self.state.modes.commit.messages.is_empty() {
+        let commit = &self.state.modes.commit;
+        if !self.state.git_status.has_staged() || commit.generating {
+            return;
+        }
+        // Don't regenerate if we already have messages, unless they were
+        // generated for other staged changes and haven't been edited
+        let stale = self.state.auto_trigger(Mode::Commit) == AutoTrigger::OnStagedChange
+            && commit.generated_for != commit.staged_fingerprint
+            && !commit.message_editor.is_modified();
+        if !commit.messages.is_empty() && !stale {
             return;
         }
 
         self.state.set_iris_thinking("Analyzing changes...");
         self.state.modes.commit.generating = true;
+        self.state.modes.commit.generated_for = self.state.modes.commit.staged_fingerprint;
         let preset = self.state.modes.commit.preset.clone();
         let use_gitmoji = self.state.modes.commit.use_gitmoji;
         let amend = self.state.modes.commit.amend_mode;
@@ -1805,34 +3125,158 @@ impl StudioApp {
         }
     }
 
+    /// Enforce `ascii_only` before committing, keeping Studio open on failure
+    fn validate_commit_message(&mut self, message: &str) -> bool {
+        if !self.state.config.ascii_only {
+            return true;
+        }
+        match crate::types::check_ascii(message) {
+            Ok(()) => true,
+            Err(e) => {
+                self.state.notify(Notification::error(e.to_string()));
+                false
+            }
+        }
+    }
+
     fn perform_commit(&mut self, message: &str) -> ExitResult {
-        if let Some(service) = &self.commit_service {
-            match service.perform_commit(message) {
-                Ok(result) => {
-                    // Record commit in companion
-                    self.state
-                        .companion_record_commit(result.commit_hash.clone());
+        match self.commit_staged(message) {
+            Ok(output) => ExitResult::Committed(output),
+            Err(e) => ExitResult::Error(e),
+        }
+    }
 
-                    // Also update branch memory commit count
-                    self.update_branch_commit_count(&result.branch);
+    /// Commit the staged changes, returning the `git commit`-style summary
+    fn commit_staged(&mut self, message: &str) -> Result<String, String> {
+        let Some(service) = &self.commit_service else {
+            return Err("Commit service not available".to_string());
+        };
+        let result = service.perform_commit(message).map_err(|e| e.to_string())?;
+        // Record commit in companion
+        self.state
+            .companion_record_commit(result.commit_hash.clone());
+        crate::undo::record_commit(service.repo());
+
+        // Also update branch memory commit count
+        self.update_branch_commit_count(&result.branch);
+        // Plan progress and activity include the new commit
+        self.state.modes.dashboard.loaded_at = None;
+
+        Ok(crate::output::format_commit_result(&result, message))
+    }
 
-                    let output = crate::output::format_commit_result(&result, message);
-                    ExitResult::Committed(output)
+    /// Queue `message` with the changes staged to `files` right now
+    fn queue_commit(&mut self, message: String, files: Vec<std::path::PathBuf>) {
+        let patch = match &self.state.repo {
+            Some(repo) => repo.staged_patch(&files).map_err(|e| e.to_string()),
+            None => Err("No repository available".to_string()),
+        };
+        match patch {
+            Ok(patch) => {
+                let count = files.len();
+                self.state.modes.commit.queue.push(message, files, patch);
+                self.state.notify(Notification::success(format!(
+                    "Queued commit {} ({count} file{}); unstage with U and stage the next set, Q reviews the queue",
+                    self.state.modes.commit.queue.entries.len(),
+                    if count == 1 { "" } else { "s" }
+                )));
+            }
+            Err(e) => {
+                self.state
+                    .notify(Notification::error(format!("Can't queue: {e}")));
+            }
+        }
+        self.state.mark_dirty();
+    }
+
+    /// Check the queued messages and start committing them
+    fn start_commit_queue(&mut self) {
+        let messages: Vec<String> = self
+            .state
+            .modes
+            .commit
+            .queue
+            .entries
+            .iter()
+            .map(|entry| entry.message.clone())
+            .collect();
+        if messages.is_empty() || !messages.iter().all(|m| self.validate_commit_message(m)) {
+            return;
+        }
+        self.state.modes.commit.queue.start();
+        self.state.set_iris_thinking(format!(
+            "Committing 1 of {}...",
+            self.state.modes.commit.queue.run_total
+        ));
+        self.state.mark_dirty();
+    }
+
+    /// Make the next queued commit: stage exactly its changes, then commit
+    ///
+    /// Returns the combined output once the whole queue is committed. On the
+    /// first failure the run stops with that entry and the rest still
+    /// queued, and whatever its changes left staged stays staged.
+    fn step_commit_queue(&mut self) -> Option<ExitResult> {
+        let entry = self.state.modes.commit.queue.next()?.clone();
+        let staged = match &self.state.repo {
+            Some(repo) => stage_only(repo, &entry.patch),
+            None => Err("No repository available".to_string()),
+        };
+        match staged.and_then(|()| self.commit_staged(&entry.message)) {
+            Ok(output) => {
+                let queue = &mut self.state.modes.commit.queue;
+                queue.complete_next(output);
+                if queue.finished() {
+                    let output = queue.committed.join("\n");
+                    self.state.modes.commit.queue = CommitQueueState::default();
+                    return Some(ExitResult::Committed(output));
                 }
-                Err(e) => ExitResult::Error(e.to_string()),
+                let progress = format!(
+                    "Committing {} of {}...",
+                    queue.committed.len() + 1,
+                    queue.run_total
+                );
+                self.state.set_iris_thinking(progress);
+            }
+            Err(e) => {
+                let queue = &mut self.state.modes.commit.queue;
+                let (done, left) = (queue.committed.len(), queue.entries.len());
+                queue.fail_next(e.clone());
+                self.state.set_iris_idle();
+                self.state.notify(Notification::error(format!(
+                    "Queue stopped at \"{}\": {e} ({done} committed, {left} left in the queue)",
+                    entry.subject()
+                )));
+                let _ = self.refresh_git_status();
             }
-        } else {
-            ExitResult::Error("Commit service not available".to_string())
         }
+        self.state.mark_dirty();
+        None
     }
 
     fn perform_amend(&mut self, message: &str) -> ExitResult {
+        // The commit being replaced, for the branch's message drift record
+        let original_commit = self
+            .state
+            .repo
+            .as_ref()
+            .and_then(|repo| repo.head_commit_id().ok());
         if let Some(service) = &self.commit_service {
-            match service.perform_amend(message) {
+            match service.amend_commit(message) {
                 Ok(result) => {
                     // Record amend in companion (still counts as commit activity)
                     self.state
                         .companion_record_commit(result.commit_hash.clone());
+                    if let (Some(repo), Some(commit), Some(original)) = (
+                        self.state.repo.clone(),
+                        original_commit,
+                        self.state.modes.commit.original_message.clone(),
+                    ) {
+                        match crate::companion::record_amend(&repo, commit, original) {
+                            Ok(revision) => self.history.record_message_revision(revision),
+                            Err(e) => tracing::warn!("Failed to record message revision: {}", e),
+                        }
+                    }
 
                     let output = crate::output::format_commit_result(&result, message);
                     ExitResult::Amended(output)
@@ -1844,6 +3288,31 @@ impl StudioApp {
         }
     }
 
+    /// Soft-reset the last commit git-iris made and edit its message again
+    fn undo_last_commit(&mut self) {
+        let Some(repo) = self.state.repo.clone() else {
+            self.state
+                .notify(Notification::error("No repository available"));
+            return;
+        };
+        match crate::undo::undo_last_commit(&repo, false) {
+            Ok(undone) => {
+                self.state.load_commit_draft(&undone.record);
+                self.state.notify(Notification::success(format!(
+                    "Undid {}; {} file{} staged again",
+                    undone.record.short_hash(),
+                    undone.files.len(),
+                    if undone.files.len() == 1 { "" } else { "s" }
+                )));
+                let _ = self.refresh_git_status();
+            }
+            Err(e) => {
+                self.state
+                    .notify(Notification::error(format!("Can't undo: {e}")));
+            }
+        }
+    }
+
     /// Update branch memory commit count
     fn update_branch_commit_count(&self, branch: &str) {
         if let Some(ref companion) = self.state.companion {
@@ -1866,10 +3335,24 @@ impl StudioApp {
     // ═══════════════════════════════════════════════════════════════════════════
 
     fn render(&mut self, frame: &mut Frame) {
-        let areas = calculate_layout(frame.area(), self.state.active_mode);
+        // Below the minimum size panels would overlap, so explain instead
+        if is_too_small(frame.area()) {
+            render_too_small(frame);
+            self.last_layout = None;
+            return;
+        }
+
+        let areas = calculate_layout(
+            frame.area(),
+            self.state.active_mode,
+            self.state.focused_panel,
+        );
 
         self.render_header(frame, areas.header);
         self.render_tabs(frame, areas.tabs);
+        if let Some(switcher) = areas.switcher {
+            render_panel_switcher(frame, switcher, &self.state, &areas);
+        }
         self.render_panels(frame, &areas);
 
         // Render companion status bar for explore mode
@@ -1945,6 +3428,32 @@ impl StudioApp {
             ));
         }
 
+        // Output length (Ctrl+O) and tone (Ctrl+Y) of the active mode, right-aligned
+        if let Some((verbosity, tone)) = self.state.output_style() {
+            let style_for = |is_default: bool| {
+                if is_default {
+                    theme::dimmed()
+                } else {
+                    Style::default().fg(theme::accent_secondary())
+                }
+            };
+            let right = [
+                Span::styled(
+                    verbosity.to_string(),
+                    style_for(verbosity == Verbosity::default()),
+                ),
+                Span::styled(" · ", theme::dimmed()),
+                Span::styled(tone.to_string(), style_for(tone == Tone::default())),
+                Span::raw(" "),
+            ];
+            let used = Line::from(spans.clone()).width() + Line::from(right.to_vec()).width();
+            let padding = usize::from(area.width).saturating_sub(used);
+            if padding > 0 {
+                spans.push(Span::raw(" ".repeat(padding)));
+                spans.extend(right);
+            }
+        }
+
         let line = Line::from(spans);
         let header = Paragraph::new(line);
         frame.render_widget(header, area);
@@ -2027,6 +3536,10 @@ impl StudioApp {
         let panel_areas: Vec<_> = areas.panels.clone();
 
         for (i, panel_area) in panel_areas.iter().enumerate() {
+            // Panels hidden by a narrow layout have no area
+            if panel_area.area() == 0 {
+                continue;
+            }
             if let Some(&panel_id) = panel_ids.get(i) {
                 self.render_panel_content(frame, *panel_area, panel_id);
             }
@@ -2043,6 +3556,9 @@ impl StudioApp {
             Mode::ReleaseNotes => {
                 render_release_notes_panel(&mut self.state, frame, area, panel_id);
             }
+            Mode::Dashboard => render_dashboard_panel(&self.state, frame, area, panel_id),
+            Mode::Stash => render_stash_panel(&mut self.state, frame, area, panel_id),
+            Mode::Rebase => render_rebase_panel(&mut self.state, frame, area, panel_id),
         }
     }
 
@@ -2070,7 +3586,7 @@ impl StudioApp {
                 return;
             };
             match repo.get_all_tracked_files() {
-                Ok(files) => files.into_iter().map(std::path::PathBuf::from).collect(),
+                Ok(files) => files.into_iter().map(normalize_path).collect(),
                 Err(e) => {
                     eprintln!("Failed to get tracked files: {}", e);
                     return;
@@ -2134,6 +3650,7 @@ impl StudioApp {
         // Get staged diff first, then unstaged
         if let Ok(diff_text) = repo.get_staged_diff_full() {
             let diffs = parse_diff(&diff_text);
+            self.state.modes.review.risk = self.assess_risk(&diffs);
             self.state.modes.review.diff_view.set_diffs(diffs);
         }
 
@@ -2143,6 +3660,29 @@ impl StudioApp {
         }
     }
 
+    /// Score a set of diffs for the Commit and Review panel titles
+    fn assess_risk(&self, diffs: &[FileDiff]) -> Option<crate::risk::RiskReport> {
+        if diffs.is_empty() {
+            return None;
+        }
+        let files: Vec<crate::risk::ChangedFile> = diffs
+            .iter()
+            .map(|diff| {
+                let (additions, deletions) = diff.lines_changed();
+                crate::risk::ChangedFile::new(
+                    diff.path.to_string_lossy().replace('\\', "/"),
+                    additions,
+                    deletions,
+                )
+            })
+            .collect();
+        Some(crate::risk::assess(
+            &self.state.config,
+            self.state.repo.as_deref(),
+            &files,
+        ))
+    }
+
     /// Update PR mode data - load commits and diff between refs
     pub fn update_pr_data(&mut self, from_ref: Option<String>, to_ref: Option<String>) {
         use super::state::PrCommit;
@@ -2189,6 +3729,7 @@ impl StudioApp {
             }
         }
 
+        self.load_ci_status();
         self.state.mark_dirty();
     }
 
@@ -2207,13 +3748,14 @@ impl StudioApp {
         match repo.get_ref_diff_full(&from, &to) {
             Ok(diff_text) => {
                 let diffs = parse_diff(&diff_text);
-                self.state.modes.review.diff_view.set_diffs(diffs.clone());
+                self.state.modes.review.risk = self.assess_risk(&diffs);
 
                 // Also update file tree from the diff files
                 let files: Vec<std::path::PathBuf> = diffs
                     .iter()
                     .map(|d| std::path::PathBuf::from(&d.path))
                     .collect();
+                self.state.modes.review.diff_view.set_diffs(diffs);
                 let statuses: Vec<_> = files
                     .iter()
                     .map(|p| (p.clone(), FileGitStatus::Modified))
@@ -2231,6 +3773,81 @@ impl StudioApp {
         self.state.mark_dirty();
     }
 
+    /// Update Stash mode data - reload the stash list, and the selected
+    /// stash's diff if it changed
+    pub fn update_stash_data(&mut self) {
+        self.load_stash_list();
+        self.load_stash_diff();
+        self.state.mark_dirty();
+    }
+
+    /// Load the rebase plan for the current branch, and the selected commit's diff
+    pub fn load_rebase_plan(&mut self) {
+        let Some(repo) = self.state.repo.clone() else {
+            return;
+        };
+        let rebase = &mut self.state.modes.rebase;
+        rebase.set_plan(repo.rebase_plan(&rebase.base).map_err(|e| e.to_string()));
+        rebase.shown = None;
+        self.load_rebase_diff();
+        self.state.mark_dirty();
+    }
+
+    fn load_rebase_diff(&mut self) {
+        let rebase = &self.state.modes.rebase;
+        if !rebase.needs_diff() {
+            return;
+        }
+        let (Some(repo), Some(step)) = (self.state.repo.clone(), rebase.selected_step()) else {
+            return;
+        };
+        let id = step.commit.id.clone();
+        match repo.get_ref_diff_full(&format!("{id}^"), &id) {
+            Ok(diff) => self.state.modes.rebase.show_diff(Some(id), &diff),
+            Err(e) => {
+                self.state.notify(Notification::warning(format!(
+                    "Could not load {}: {e}",
+                    &id[..7]
+                )));
+            }
+        }
+        self.state.mark_dirty();
+    }
+
+    fn load_stash_list(&mut self) {
+        let Some(repo) = self.state.repo.clone() else {
+            return;
+        };
+        match repo.list_stashes() {
+            Ok(entries) => self.state.modes.stash.set_entries(entries),
+            Err(e) => {
+                self.state.notify(Notification::warning(format!(
+                    "Could not list stashes: {e}"
+                )));
+            }
+        }
+    }
+
+    fn load_stash_diff(&mut self) {
+        let stash = &self.state.modes.stash;
+        if !stash.needs_diff() {
+            return;
+        }
+        let (Some(repo), Some(entry)) = (self.state.repo.clone(), stash.selected_entry().cloned())
+        else {
+            return;
+        };
+        match repo.get_stash_diff(entry.index, &entry.id) {
+            Ok(diff) => self.state.modes.stash.show_diff(Some(entry.id), &diff),
+            Err(e) => {
+                self.state.notify(Notification::warning(format!(
+                    "Could not load {}: {e}",
+                    entry.selector()
+                )));
+            }
+        }
+    }
+
     /// Update Changelog mode data - load commits and diff between `from_ref` and `to_ref`
     pub fn update_changelog_data(&mut self, from_ref: Option<String>, to_ref: Option<String>) {
         use super::state::ChangelogCommit;
@@ -2359,6 +3976,42 @@ impl StudioApp {
         self.state.mark_dirty();
     }
 
+    /// Stage several files with one status refresh
+    fn stage_files(&mut self, paths: &[std::path::PathBuf]) {
+        let Some(repo) = &self.state.repo else {
+            self.state
+                .notify(Notification::error("No repository available"));
+            return;
+        };
+
+        let mut staged = 0;
+        let mut failed = Vec::new();
+        for path in paths {
+            match repo.stage_file(path) {
+                Ok(()) => staged += 1,
+                Err(e) => failed.push(format!("{}: {e}", path.display())),
+            }
+        }
+        for path in paths {
+            self.state.companion_touch_file(path.clone());
+        }
+
+        if failed.is_empty() {
+            self.state.notify(Notification::success(format!(
+                "Staged {staged} file{}",
+                if staged == 1 { "" } else { "s" }
+            )));
+        } else {
+            self.state.notify(Notification::error(format!(
+                "Failed to stage {}",
+                failed.join(", ")
+            )));
+        }
+        let _ = self.refresh_git_status();
+        self.state.update_companion_display();
+        self.state.mark_dirty();
+    }
+
     /// Unstage a single file
     fn unstage_file(&mut self, path: &str) {
         let Some(repo) = &self.state.repo else {
@@ -2453,7 +4106,7 @@ impl StudioApp {
 
     /// Save settings from the settings modal to config file
     fn save_settings(&mut self) {
-        use crate::studio::state::Modal;
+        use crate::studio::state::{Modal, SettingsScope};
 
         let settings = if let Some(Modal::Settings(s)) = &self.state.modal {
             s.clone()
@@ -2466,36 +4119,31 @@ impl StudioApp {
             return;
         }
 
-        // Update config
-        let mut config = self.state.config.clone();
-        config.default_provider.clone_from(&settings.provider);
-        config.use_gitmoji = settings.use_gitmoji;
-        config
-            .instruction_preset
-            .clone_from(&settings.instruction_preset);
-        config
-            .instructions
-            .clone_from(&settings.custom_instructions);
-        config.theme.clone_from(&settings.theme);
-
-        // Update provider config
-        if let Some(provider_config) = config.providers.get_mut(&settings.provider) {
-            provider_config.model.clone_from(&settings.model);
-            if let Some(api_key) = &settings.api_key_actual {
-                provider_config.api_key.clone_from(api_key);
-            }
-        }
+        let result = write_settings(&settings);
 
-        // Save to file
-        match config.save() {
+        match result {
             Ok(()) => {
-                self.state.config = config;
-                // Clear the modified flag
+                settings.apply_changes(&mut self.state.config, true);
+                self.state.degraded = self.state.config.missing_api_key();
+                self.config_watcher.sync();
+                let scope = settings.scope;
+                // Clear the modified flag and refresh value origins
                 if let Some(Modal::Settings(s)) = &mut self.state.modal {
                     s.modified = false;
+                    s.changed.clear();
+                    s.project_config = Config::load_project_config().ok();
                     s.error = None;
                 }
-                self.state.notify(Notification::success("Settings saved"));
+                let message = match scope {
+                    SettingsScope::Global => "Settings saved globally".to_string(),
+                    SettingsScope::Repository => {
+                        format!(
+                            "Settings saved to {}",
+                            crate::config::PROJECT_CONFIG_FILENAME
+                        )
+                    }
+                };
+                self.state.notify(Notification::success(message));
             }
             Err(e) => {
                 if let Some(Modal::Settings(s)) = &mut self.state.modal {
@@ -2508,6 +4156,112 @@ impl StudioApp {
         self.state.mark_dirty();
     }
 
+    /// Write the preset editor's preset and return to settings with it selected
+    fn save_preset(&mut self) {
+        let Some(Modal::PresetEditor(editor)) = &mut self.state.modal else {
+            return;
+        };
+        let (key, preset) = match editor.to_preset() {
+            Ok(saved) => saved,
+            Err(e) => {
+                editor.error = Some(e);
+                self.state.mark_dirty();
+                return;
+            }
+        };
+        let Some(path) = editor.save_path(&key) else {
+            editor.error = Some("Unable to determine home directory".to_string());
+            self.state.mark_dirty();
+            return;
+        };
+        if editor.editing.is_none() && path.exists() {
+            editor.error = Some(format!("{} already exists", path.display()));
+            self.state.mark_dirty();
+            return;
+        }
+
+        match crate::instruction_presets::write_preset_file(&path, &key, &preset) {
+            Ok(()) => {
+                if let Some(Modal::PresetEditor(editor)) = self.state.modal.take() {
+                    self.state.modal = Some(Modal::Settings(Box::new(
+                        editor.into_settings(Some(key.clone())),
+                    )));
+                }
+                self.state.notify(Notification::success(format!(
+                    "Saved preset {} {} to {}",
+                    preset.emoji,
+                    key,
+                    path.display()
+                )));
+            }
+            Err(e) => editor.error = Some(format!("{e:#}")),
+        }
+        self.state.mark_dirty();
+    }
+
+    /// Save the generated changelog, release notes or review to the file
+    /// `[outputs]` names for it
+    fn save_artifact(&mut self, artifact: Artifact) {
+        let Some(repo) = self.state.repo.clone() else {
+            self.state
+                .notify(Notification::error("Saving needs a repository"));
+            return;
+        };
+        let modes = &self.state.modes;
+        let outputs = &self.state.config.outputs;
+        let (content, path) = match artifact {
+            Artifact::Changelog => (
+                modes.changelog.changelog_content.clone(),
+                outputs.path(artifact, None, None),
+            ),
+            Artifact::ReleaseNotes => {
+                let notes = &modes.release_notes;
+                let version = notes
+                    .suggested_version
+                    .as_ref()
+                    .and_then(|suggestion| suggestion.next.as_deref())
+                    .or_else(|| (notes.to_ref != "HEAD").then_some(notes.to_ref.as_str()));
+                (
+                    notes.release_notes_content.clone(),
+                    outputs.path(artifact, version, None),
+                )
+            }
+            Artifact::Review => (
+                modes.review.review_content.clone(),
+                outputs.path(artifact, None, Some(&self.state.git_status.branch)),
+            ),
+        };
+        if content.trim().is_empty() {
+            self.state.notify(Notification::warning(format!(
+                "No {} to save yet",
+                artifact.label().to_lowercase()
+            )));
+            return;
+        }
+
+        let full_path = repo.repo_path().join(&path);
+        let result = match artifact {
+            Artifact::Changelog => crate::changelog::ChangelogGenerator::update_changelog_file(
+                &content,
+                &full_path.to_string_lossy(),
+                &repo,
+                &self.state.modes.changelog.to_ref,
+                None,
+            ),
+            _ => crate::outputs::write_artifact(artifact, &full_path, &content),
+        };
+        match result {
+            Ok(()) => self.state.notify(Notification::success(format!(
+                "{} saved to {path}",
+                artifact.label()
+            ))),
+            Err(e) => self.state.notify(Notification::error(format!(
+                "Failed to save {}: {e:#}",
+                artifact.label().to_lowercase()
+            ))),
+        }
+    }
+
     fn render_status(&self, frame: &mut Frame, area: Rect) {
         let mut spans = Vec::new();
 
@@ -2528,6 +4282,9 @@ impl StudioApp {
 
         // Right-align Iris status
         let iris_status = match &self.state.iris_status {
+            IrisStatus::Idle if self.state.degraded.is_some() => {
+                Span::styled("Iris: offline", theme::warning())
+            }
             IrisStatus::Idle => Span::styled("Iris: ready", theme::dimmed()),
             IrisStatus::Thinking { task, .. } => {
                 let spinner = self.state.iris_status.spinner_char().unwrap_or('◎');
@@ -2542,9 +4299,46 @@ impl StudioApp {
             IrisStatus::Error(msg) => Span::styled(format!("Error: {}", msg), theme::error()),
         };
 
+        // Name the provider when a fallback answered instead of the primary one
+        let fallback = self
+            .agent_service
+            .as_ref()
+            .map(|service| (service.active_provider(), service.provider()))
+            .filter(|(active, primary)| active != primary)
+            .map(|(active, _)| active);
+        let iris_status = match fallback {
+            Some(provider) => Span::styled(
+                format!("{} · via {provider}", iris_status.content),
+                iris_status.style,
+            ),
+            None => iris_status,
+        };
+
+        // Auto-generate toggle (Ctrl+A), left of the Iris status
+        let auto = if self.state.auto_generate {
+            Span::styled("auto ", Style::default().fg(theme::accent_secondary()))
+        } else {
+            Span::styled("manual ", theme::dimmed())
+        };
+
+        // Session token usage and estimated cost, once Iris has been called
+        let usage = self
+            .agent_service
+            .as_ref()
+            .map(|service| service.session_usage())
+            .filter(|totals| totals.calls > 0)
+            .map(|totals| Span::styled(format!("{} ", totals.status_label()), theme::dimmed()));
+
+        // Started with --read-only: staging, commits and file writes are refused
+        let read_only =
+            crate::read_only::is_read_only().then(|| Span::styled("read-only ", theme::warning()));
+
         // Calculate spacing (use saturating_sub to avoid overflow on narrow terminals)
-        let left_len: usize = spans.iter().map(|s| s.content.len()).sum();
-        let right_len = iris_status.content.len();
+        let left_len: usize = spans.iter().map(Span::width).sum();
+        let right_len = read_only.as_ref().map_or(0, Span::width)
+            + usage.as_ref().map_or(0, Span::width)
+            + auto.width()
+            + iris_status.width();
         let padding = (area.width as usize)
             .saturating_sub(left_len)
             .saturating_sub(right_len)
@@ -2552,6 +4346,9 @@ impl StudioApp {
         let padding_str = " ".repeat(padding.max(1));
 
         spans.push(Span::raw(padding_str));
+        spans.extend(read_only);
+        spans.extend(usage);
+        spans.push(auto);
         spans.push(iris_status);
 
         let status = Paragraph::new(Line::from(spans));
@@ -2571,10 +4368,15 @@ impl StudioApp {
                 ),
                 PanelId::Right => format!("{} · [↑↓]scroll [n/p]file []/[]hunk", base),
             },
+            Mode::PR if self.state.focused_panel == PanelId::Center => {
+                format!("{} · [↑↓]scroll [y]copy [o]open PR [r]generate", base)
+            }
             Mode::Review | Mode::PR | Mode::Changelog | Mode::ReleaseNotes => {
                 match self.state.focused_panel {
                     PanelId::Left => format!("{} · [f/t]set refs [r]generate", base),
-                    PanelId::Center => format!("{} · [↑↓]scroll [y]copy [r]generate", base),
+                    PanelId::Center => {
+                        format!("{} · [↑↓]scroll [y]copy [w]save [r]generate", base)
+                    }
                     PanelId::Right => format!("{} · [↑↓]scroll", base),
                 }
             }
@@ -2585,10 +4387,43 @@ impl StudioApp {
                 }
                 PanelId::Right => format!("{} · [c]chat", base),
             },
+            Mode::Dashboard => format!("{} · [↑↓]nav [Enter]explore [r]refresh", base),
+            Mode::Stash => match self.state.focused_panel {
+                PanelId::Right => format!("{} · [↑↓]scroll [n/p]file [a]apply [d]drop", base),
+                _ => format!(
+                    "{} · [↑↓]nav [a]apply [p]pop [d]drop [s/u]stash [r]refresh",
+                    base
+                ),
+            },
+            Mode::Rebase => match self.state.focused_panel {
+                PanelId::Left => format!(
+                    "{} · [p/s/f/d]action [e]reword [J/K]move [r]suggest [x]run [b]base",
+                    base
+                ),
+                _ => format!("{} · [↑↓]scroll [r]suggest [x]run", base),
+            },
         }
     }
 }
 
+/// Hash of a diff, to tell whether the staged changes changed
+fn diff_fingerprint(diff_text: &str) -> u64 {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+    let mut hasher = DefaultHasher::new();
+    diff_text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Reset the index and stage exactly `patch`, for one queued commit
+fn stage_only(repo: &GitRepo, patch: &[u8]) -> Result<(), String> {
+    repo.stage_patch(patch).map_err(|e| format!("{e:#}"))?;
+    let diff = repo.get_staged_diff_full().map_err(|e| e.to_string())?;
+    if diff.trim().is_empty() {
+        return Err("nothing to commit, its changes are already committed".to_string());
+    }
+    Ok(())
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Exit Result
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -2608,17 +4443,73 @@ pub enum ExitResult {
 
 impl Drop for StudioApp {
     fn drop(&mut self) {
-        // Abort all background tasks to prevent hanging on exit
-        for handle in self.background_tasks.drain(..) {
-            handle.abort();
-        }
+        // Stop all background tasks to prevent hanging on exit
+        self.tasks.shutdown();
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Terminal Setup
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Enter raw mode and the alternate screen
+fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
+    // Legacy Windows consoles (conhost without VT processing) can't render the UI
+    #[cfg(windows)]
+    if !crossterm::ansi_support::supports_ansi() {
+        tracing::warn!("Console does not support ANSI sequences; use Windows Terminal for Studio");
     }
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    Ok(Terminal::new(CrosstermBackend::new(stdout))?)
+}
+
+/// Restore the terminal, ignoring errors so it's safe from the panic hook
+///
+/// Mouse capture is released first: on Windows it changes the console input
+/// mode, and leaving it set breaks text selection in the parent shell.
+fn restore_terminal() {
+    let mut stdout = io::stdout();
+    let _ = execute!(stdout, DisableMouseCapture);
+    let _ = disable_raw_mode();
+    let _ = execute!(stdout, LeaveAlternateScreen, crossterm::cursor::Show);
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Public Entry Point
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Write the changed settings fields to the chosen scope.
+///
+/// API keys always go to the personal config, never into a project file.
+fn write_settings(settings: &super::state::SettingsState) -> Result<()> {
+    use super::state::SettingsScope;
+
+    let mut personal = Config::load_personal()?;
+    match settings.scope {
+        SettingsScope::Global => settings.apply_changes(&mut personal, true),
+        SettingsScope::Repository => {
+            let mut project =
+                Config::load_project_config().unwrap_or_else(|_| Config::empty_project_config());
+            settings.apply_changes(&mut project, false);
+            project.save_as_project_config()?;
+
+            let Some(api_key) = &settings.api_key_actual else {
+                return Ok(());
+            };
+            personal
+                .providers
+                .entry(settings.provider.clone())
+                .or_default()
+                .api_key
+                .clone_from(api_key);
+        }
+    }
+    personal.save()
+}
+
 /// Run Iris Studio
 pub fn run_studio(
     config: Config,
@@ -2629,17 +4520,7 @@ pub fn run_studio(
     from_ref: Option<String>,
     to_ref: Option<String>,
 ) -> Result<()> {
-    // Enable file logging for debugging (TUI owns stdout, so logs go to file only)
-    // Only set up default log file if one wasn't specified via CLI (-l --log-file)
-    if !crate::logger::has_log_file()
-        && let Err(e) = crate::logger::set_log_file(crate::cli::LOG_FILE)
-    {
-        eprintln!("Warning: Could not set up log file: {}", e);
-    }
-    // Disable stdout logging - TUI owns the terminal, but debug goes to file
-    crate::logger::set_log_to_stdout(false);
-    tracing::info!("Iris Studio starting");
-
+    setup_studio_logging();
     let mut app = StudioApp::new(config, repo, commit_service, agent_service);
 
     // Set initial mode if specified
@@ -2647,12 +4528,13 @@ pub fn run_studio(
         app.set_initial_mode(mode);
     }
 
-    // Set comparison refs if specified (applies to Review, PR, Changelog, and Release Notes modes)
+    // Set comparison refs if specified (applies to Review, PR, Changelog, Release Notes and Rebase modes)
     if let Some(from) = from_ref {
         app.state.modes.review.from_ref = from.clone();
         app.state.modes.pr.base_branch = from.clone();
         app.state.modes.changelog.from_ref = from.clone();
-        app.state.modes.release_notes.from_ref = from;
+        app.state.modes.release_notes.from_ref = from.clone();
+        app.state.modes.rebase.base = from;
     }
     if let Some(to) = to_ref {
         app.state.modes.review.to_ref = to.clone();
@@ -2661,6 +4543,37 @@ pub fn run_studio(
         app.state.modes.release_notes.to_ref = to;
     }
 
+    run_app(app)
+}
+
+/// Run Iris Studio in Explore mode with `document` open in the code view
+pub fn run_studio_with_document(
+    config: Config,
+    repo: Option<Arc<GitRepo>>,
+    agent_service: Option<Arc<IrisAgentService>>,
+    document: std::path::PathBuf,
+) -> Result<()> {
+    setup_studio_logging();
+    let mut app = StudioApp::new(config, repo, None, agent_service);
+    app.set_initial_mode(Mode::Explore);
+    app.state.modes.explore.document = Some(document);
+    run_app(app)
+}
+
+fn setup_studio_logging() {
+    // Enable file logging for debugging (TUI owns stdout, so logs go to file only)
+    // Only set up default log file if one wasn't specified via CLI (-l --log-file)
+    if !crate::logger::has_log_file()
+        && let Err(e) = crate::logger::set_log_file(crate::cli::LOG_FILE)
+    {
+        eprintln!("Warning: Could not set up log file: {}", e);
+    }
+    // Disable stdout logging - TUI owns the terminal, but debug goes to file
+    crate::logger::set_log_to_stdout(false);
+    tracing::info!("Iris Studio starting");
+}
+
+fn run_app(mut app: StudioApp) -> Result<()> {
     // Run the app
     match app.run()? {
         ExitResult::Quit => {
@@ -2669,6 +4582,9 @@ pub fn run_studio(
         }
         ExitResult::Committed(message) => {
             println!("{message}");
+            crate::ui::print_info(
+                "Changed your mind? `git-iris undo` restores this commit as a draft",
+            );
             Ok(())
         }
         ExitResult::Amended(message) => {
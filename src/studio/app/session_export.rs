@@ -0,0 +1,209 @@
+//! Session export for Iris Studio
+//!
+//! Builds a `SessionTranscript` from the chat thread, the latest generated
+//! artifacts and the diff they were based on, and writes shareable reports.
+
+use std::fmt::Write as _;
+
+use crate::companion::{
+    CompanionStorage, HistoryKind, ReportFormat, SessionTranscript, TranscriptArtifact,
+    TranscriptMessage,
+};
+use crate::studio::components::DiffViewState;
+use crate::studio::state::{ChatRole, HistorySearchState, Modal, Mode, Notification};
+use crate::types::format_commit_message;
+
+use super::StudioApp;
+
+impl StudioApp {
+    /// Snapshot the current session into a transcript
+    pub(super) fn session_transcript(&self) -> Option<SessionTranscript> {
+        let repo = self.state.repo.as_ref()?;
+        let modes = &self.state.modes;
+        let mut transcript = SessionTranscript::new(
+            self.history.session_id(),
+            repo.repo_path().clone(),
+            self.state.git_status.branch.clone(),
+            self.history.metadata.created_at,
+        );
+
+        transcript.messages = self
+            .state
+            .chat_state
+            .messages
+            .iter()
+            .map(|msg| TranscriptMessage {
+                role: match msg.role {
+                    ChatRole::User => "user",
+                    ChatRole::Iris => "iris",
+                }
+                .to_string(),
+                content: msg.content.to_string(),
+            })
+            .collect();
+
+        if let Some(msg) = modes.commit.messages.get(modes.commit.current_index) {
+            transcript.artifacts.push(TranscriptArtifact {
+                kind: HistoryKind::Commit,
+                content: format_commit_message(msg),
+            });
+        }
+
+        let ranged = [
+            (
+                HistoryKind::Review,
+                "Review",
+                &modes.review.review_content,
+                &modes.review.from_ref,
+                &modes.review.to_ref,
+            ),
+            (
+                HistoryKind::Pr,
+                "PR",
+                &modes.pr.pr_content,
+                &modes.pr.base_branch,
+                &modes.pr.to_ref,
+            ),
+            (
+                HistoryKind::Changelog,
+                "Changelog",
+                &modes.changelog.changelog_content,
+                &modes.changelog.from_ref,
+                &modes.changelog.to_ref,
+            ),
+            (
+                HistoryKind::ReleaseNotes,
+                "Release Notes",
+                &modes.release_notes.release_notes_content,
+                &modes.release_notes.from_ref,
+                &modes.release_notes.to_ref,
+            ),
+        ];
+        for (kind, ref_label, content, from, to) in ranged {
+            if content.trim().is_empty() {
+                continue;
+            }
+            transcript.artifacts.push(TranscriptArtifact {
+                kind,
+                content: content.to_string(),
+            });
+            transcript
+                .refs
+                .push((ref_label.to_string(), format!("{from}..{to}")));
+        }
+
+        // Prefer the diff of the active mode, falling back to staged changes
+        let diff_view = match self.state.active_mode {
+            Mode::Review => &modes.review.diff_view,
+            Mode::PR => &modes.pr.diff_view,
+            Mode::Changelog => &modes.changelog.diff_view,
+            Mode::ReleaseNotes => &modes.release_notes.diff_view,
+            Mode::Stash => &modes.stash.diff_view,
+            Mode::Rebase => &modes.rebase.diff_view,
+            Mode::Commit | Mode::Explore | Mode::Dashboard => &modes.commit.diff_view,
+        };
+        let diff_view = if diff_view.file_count() == 0 {
+            &modes.commit.diff_view
+        } else {
+            diff_view
+        };
+        transcript.set_diff(&diff_view_patch(diff_view));
+
+        Some(transcript)
+    }
+
+    /// Persist the transcript so `git-iris export-session` can render it later
+    pub(super) fn save_session_transcript(&self) {
+        let Some(transcript) = self.session_transcript() else {
+            return;
+        };
+        if transcript.is_empty() {
+            return;
+        }
+        let result = CompanionStorage::new(&transcript.repo_path)
+            .and_then(|storage| storage.save_transcript(&transcript));
+        if let Err(e) = result {
+            tracing::warn!("Failed to save session transcript: {}", e);
+        }
+    }
+
+    /// Export the current session as a markdown report
+    pub(super) fn export_session(&mut self) {
+        let Some(transcript) = self.session_transcript() else {
+            self.state
+                .notify(Notification::warning("No repository to export from"));
+            return;
+        };
+        if transcript.is_empty() {
+            self.state
+                .notify(Notification::warning("Nothing to export yet"));
+            return;
+        }
+
+        let format = ReportFormat::Markdown;
+        let file_name = format!(
+            "session-{}.{}",
+            transcript.exported_at.format("%Y%m%d-%H%M%S"),
+            format.extension()
+        );
+        let result = CompanionStorage::new(&transcript.repo_path).and_then(|storage| {
+            storage.save_transcript(&transcript)?;
+            storage.write_report(&file_name, &transcript.render(format))
+        });
+
+        match result {
+            Ok(path) => self.state.notify(Notification::success(format!(
+                "Session exported to {}",
+                path.display()
+            ))),
+            Err(e) => self
+                .state
+                .notify(Notification::error(format!("Export failed: {e}"))),
+        }
+    }
+}
+
+impl StudioApp {
+    /// Open the history search modal over archived sessions plus this one
+    pub(super) fn open_history_search(&mut self) {
+        let mut sessions = Vec::new();
+        if let Some(current) = self.session_transcript().filter(|t| !t.is_empty()) {
+            sessions.push(current);
+        }
+
+        if let Some(repo) = &self.state.repo {
+            match CompanionStorage::new(repo.repo_path()).and_then(|s| s.load_history()) {
+                Ok(archived) => {
+                    let current_id = self.history.session_id();
+                    sessions.extend(archived.into_iter().filter(|s| s.session_id != current_id));
+                }
+                Err(e) => tracing::warn!("Failed to load session history: {}", e),
+            }
+        }
+
+        self.state.modal = Some(Modal::HistorySearch(Box::new(HistorySearchState::new(
+            sessions,
+        ))));
+        self.state.mark_dirty();
+    }
+}
+
+/// Render every file in a diff view back to unified diff text
+fn diff_view_patch(diff_view: &DiffViewState) -> String {
+    let mut out = String::new();
+    for diff in diff_view.diffs() {
+        let path = diff.path.display();
+        let old_path = diff
+            .old_path
+            .as_ref()
+            .map_or_else(|| path.to_string(), |p| p.display().to_string());
+        let _ = writeln!(out, "--- a/{old_path}\n+++ b/{path}");
+        if diff.is_binary {
+            out.push_str("Binary file changed\n");
+            continue;
+        }
+        out.push_str(&diff.to_patch());
+        out.push('\n');
+    }
+    out
+}
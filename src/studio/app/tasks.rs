@@ -0,0 +1,173 @@
+//! Supervision of Studio's background tasks
+//!
+//! Every task Studio spawns — git loads, generations, status pollers,
+//! content-update listeners — goes through a [`TaskSupervisor`] owned by the
+//! app. Tasks live in one `JoinSet` and each runs under a cancellation token:
+//! a generation's token covers the generation itself and its helpers, so
+//! cancelling it (or starting the same kind again) stops all of them, and the
+//! root token stops everything when Studio exits.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use crate::studio::events::TaskType;
+
+/// Owns Studio's spawned tasks so they can be cancelled and torn down
+#[derive(Default)]
+pub struct TaskSupervisor {
+    tasks: JoinSet<()>,
+    /// Parent of every task's token; cancelled on shutdown
+    shutdown: CancellationToken,
+    /// Token of the current run of each kind of generation
+    runs: HashMap<TaskType, CancellationToken>,
+}
+
+impl TaskSupervisor {
+    /// Spawn a task that runs until it finishes or Studio exits
+    pub fn spawn<F>(&mut self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let token = self.shutdown.child_token();
+        self.spawn_with(token, future);
+    }
+
+    /// Spawn a task belonging to the current run of `task_type`
+    pub fn spawn_for<F>(&mut self, task_type: TaskType, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let token = self
+            .runs
+            .entry(task_type)
+            .or_insert_with(|| self.shutdown.child_token())
+            .clone();
+        self.spawn_with(token, future);
+    }
+
+    fn spawn_with<F>(&mut self, token: CancellationToken, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks.spawn(async move {
+            tokio::select! {
+                () = token.cancelled() => {}
+                () = future => {}
+            }
+        });
+    }
+
+    /// Start a new run of `task_type`, cancelling whatever is left of the
+    /// previous one so a superseded generation can't report back
+    pub fn begin(&mut self, task_type: TaskType) {
+        if let Some(previous) = self.runs.insert(task_type, self.shutdown.child_token()) {
+            previous.cancel();
+        }
+    }
+
+    /// Cancel the current run of `task_type`; returns false if there was
+    /// none to cancel
+    pub fn cancel(&mut self, task_type: TaskType) -> bool {
+        self.runs.remove(&task_type).is_some_and(|token| {
+            let active = !token.is_cancelled();
+            token.cancel();
+            active
+        })
+    }
+
+    /// Collect finished tasks, logging any that panicked
+    pub fn reap(&mut self) {
+        while let Some(result) = self.tasks.try_join_next() {
+            if let Err(e) = result
+                && e.is_panic()
+            {
+                tracing::warn!("Background task panicked: {}", e);
+            }
+        }
+    }
+
+    /// Number of tasks not yet collected
+    #[cfg(test)]
+    fn outstanding(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Cancel every task and abort any that don't stop at the next await
+    pub fn shutdown(&mut self) {
+        self.shutdown.cancel();
+        self.tasks.abort_all();
+    }
+}
+
+impl Drop for TaskSupervisor {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    /// A task that never finishes, flagging when it's dropped
+    fn pending(dropped: &Arc<AtomicBool>) -> impl Future<Output = ()> + Send + 'static {
+        struct Flag(Arc<AtomicBool>);
+        impl Drop for Flag {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+        let flag = Flag(Arc::clone(dropped));
+        async move {
+            let _flag = flag;
+            std::future::pending::<()>().await;
+        }
+    }
+
+    async fn settle(supervisor: &mut TaskSupervisor) {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        supervisor.reap();
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_a_run_and_its_helpers() {
+        let mut supervisor = TaskSupervisor::default();
+        let review = Arc::new(AtomicBool::new(false));
+        let review_helper = Arc::new(AtomicBool::new(false));
+        let background = Arc::new(AtomicBool::new(false));
+
+        supervisor.begin(TaskType::Review);
+        supervisor.spawn_for(TaskType::Review, pending(&review));
+        supervisor.spawn_for(TaskType::Review, pending(&review_helper));
+        supervisor.spawn(pending(&background));
+        settle(&mut supervisor).await;
+        assert_eq!(supervisor.outstanding(), 3);
+
+        assert!(supervisor.cancel(TaskType::Review));
+        assert!(!supervisor.cancel(TaskType::Review));
+        settle(&mut supervisor).await;
+        assert!(review.load(Ordering::SeqCst));
+        assert!(review_helper.load(Ordering::SeqCst));
+        assert!(!background.load(Ordering::SeqCst));
+        assert_eq!(supervisor.outstanding(), 1);
+
+        // A new run replaces the old one
+        let first = Arc::new(AtomicBool::new(false));
+        supervisor.begin(TaskType::Commit);
+        supervisor.spawn_for(TaskType::Commit, pending(&first));
+        supervisor.begin(TaskType::Commit);
+        settle(&mut supervisor).await;
+        assert!(first.load(Ordering::SeqCst));
+
+        supervisor.shutdown();
+        settle(&mut supervisor).await;
+        assert!(background.load(Ordering::SeqCst));
+        assert_eq!(supervisor.outstanding(), 0);
+    }
+}
@@ -0,0 +1,460 @@
+//! Code context expansion for Explore selections
+//!
+//! When asking Iris about selected lines, the raw selection is rarely enough.
+//! This module widens a selection to its enclosing item (function, struct,
+//! class, ...) using lightweight, language-agnostic heuristics and collects the
+//! file's import lines, so chat answers can reference the surrounding code.
+
+use std::path::Path;
+
+/// Maximum number of lines included for the enclosing block
+const MAX_BLOCK_LINES: usize = 160;
+
+/// Lines of surrounding context kept when the block is too large or not found
+const FALLBACK_CONTEXT_LINES: usize = 8;
+
+/// Maximum number of import lines included
+const MAX_IMPORT_LINES: usize = 40;
+
+/// Keywords that introduce a definition in common languages
+const ITEM_KEYWORDS: &[&str] = &[
+    "fn",
+    "struct",
+    "enum",
+    "impl",
+    "trait",
+    "mod",
+    "union",
+    "macro_rules!",
+    "class",
+    "def",
+    "function",
+    "interface",
+    "func",
+    "type",
+    "namespace",
+];
+
+/// Modifiers that may precede an item keyword
+const ITEM_MODIFIERS: &[&str] = &[
+    "pub",
+    "pub(crate)",
+    "pub(super)",
+    "async",
+    "const",
+    "unsafe",
+    "extern",
+    "export",
+    "default",
+    "static",
+    "private",
+    "public",
+    "protected",
+    "abstract",
+    "final",
+    "override",
+    "open",
+    "internal",
+    "inline",
+    "virtual",
+];
+
+/// Line prefixes that mark an import statement
+const IMPORT_PREFIXES: &[&str] = &[
+    "use ",
+    "pub use ",
+    "pub(crate) use ",
+    "extern crate ",
+    "import ",
+    "from ",
+    "#include",
+    "package ",
+    "using ",
+    "require ",
+];
+
+/// Selected code widened with its surrounding context
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeContext {
+    /// File the selection belongs to
+    pub file: String,
+    /// Selected range (1-indexed, inclusive)
+    pub selection: (usize, usize),
+    /// Header line of the enclosing item, if one was found
+    pub enclosing_item: Option<String>,
+    /// Range of the included block (1-indexed, inclusive)
+    pub block_range: (usize, usize),
+    /// Lines of the included block
+    pub block: Vec<String>,
+    /// Import lines from the top of the file
+    pub imports: Vec<String>,
+}
+
+impl CodeContext {
+    /// Expand a 1-indexed, inclusive selection within `lines`
+    pub fn expand(path: &Path, lines: &[String], start: usize, end: usize) -> Option<Self> {
+        if lines.is_empty() {
+            return None;
+        }
+        let (start, end) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        let start_idx = start.max(1).min(lines.len()) - 1;
+        let end_idx = end.max(1).min(lines.len()) - 1;
+
+        let enclosing = find_enclosing_item(lines, start_idx, end_idx);
+        let (block_start, block_end, enclosing_item) = match enclosing {
+            Some((header, item_end)) if item_end - header < MAX_BLOCK_LINES => (
+                include_leading_attributes(lines, header),
+                item_end,
+                Some(lines[header].trim().to_string()),
+            ),
+            Some((header, _)) => {
+                let (s, e) = window(lines.len(), start_idx, end_idx);
+                (s, e, Some(lines[header].trim().to_string()))
+            }
+            None => {
+                let (s, e) = window(lines.len(), start_idx, end_idx);
+                (s, e, None)
+            }
+        };
+
+        Some(Self {
+            file: path.display().to_string(),
+            selection: (start_idx + 1, end_idx + 1),
+            enclosing_item,
+            block_range: (block_start + 1, block_end + 1),
+            block: lines[block_start..=block_end].to_vec(),
+            imports: collect_imports(lines, block_start),
+        })
+    }
+
+    /// Render as a markdown section for the chat prompt
+    ///
+    /// Selected lines are prefixed with `>` so the model can tell them apart
+    /// from the surrounding context.
+    pub fn to_prompt_section(&self) -> String {
+        let lang = language_hint(&self.file);
+        let mut out = format!(
+            "## Selected Code\nFile: {} (lines {}-{})\n",
+            self.file, self.selection.0, self.selection.1
+        );
+        if let Some(item) = &self.enclosing_item {
+            out.push_str(&format!(
+                "Enclosing item: `{}` (lines {}-{})\n",
+                item, self.block_range.0, self.block_range.1
+            ));
+        }
+
+        if !self.imports.is_empty() {
+            out.push_str(&format!(
+                "\n### Imports\n```{}\n{}\n```\n",
+                lang,
+                self.imports.join("\n")
+            ));
+        }
+
+        out.push_str(&format!("\n### Code\n```{}\n", lang));
+        for (offset, line) in self.block.iter().enumerate() {
+            let line_no = self.block_range.0 + offset;
+            let marker = if (self.selection.0..=self.selection.1).contains(&line_no) {
+                '>'
+            } else {
+                ' '
+            };
+            out.push_str(&format!("{}{:>5} | {}\n", marker, line_no, line));
+        }
+        out.push_str("```\n");
+        out
+    }
+}
+
+/// Fixed window of context around a selection (0-indexed, inclusive)
+fn window(len: usize, start_idx: usize, end_idx: usize) -> (usize, usize) {
+    (
+        start_idx.saturating_sub(FALLBACK_CONTEXT_LINES),
+        (end_idx + FALLBACK_CONTEXT_LINES).min(len - 1),
+    )
+}
+
+/// Leading whitespace width (tabs count as 4)
+fn indent_of(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Whether a line looks like the header of a definition
+fn is_item_header(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("//") || trimmed.starts_with('#') || trimmed.starts_with('*') {
+        return false;
+    }
+    for token in trimmed.split_whitespace() {
+        if ITEM_KEYWORDS.contains(&token) {
+            return true;
+        }
+        // Keyword glued to generics or a paren, e.g. `impl<T>` or `func(`
+        if ITEM_KEYWORDS
+            .iter()
+            .any(|kw| token.starts_with(kw) && token[kw.len()..].starts_with(['<', '(']))
+        {
+            return true;
+        }
+        if !ITEM_MODIFIERS.contains(&token) && !token.starts_with("pub(") {
+            return false;
+        }
+    }
+    false
+}
+
+/// Find the innermost item enclosing the selection
+///
+/// Returns `(header_idx, end_idx)`, both 0-indexed.
+fn find_enclosing_item(
+    lines: &[String],
+    start_idx: usize,
+    end_idx: usize,
+) -> Option<(usize, usize)> {
+    let mut max_indent = usize::MAX;
+    for idx in (0..=start_idx).rev() {
+        let line = &lines[idx];
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indent = indent_of(line);
+        // Only headers that are outdented relative to what we've passed can enclose it
+        if indent <= max_indent
+            && is_item_header(line)
+            && let Some(item_end) = find_item_end(lines, idx)
+            && item_end >= end_idx
+        {
+            return Some((idx, item_end));
+        }
+        if idx != start_idx {
+            max_indent = max_indent.min(indent);
+        }
+    }
+    None
+}
+
+/// Find the last line of an item starting at `header_idx`
+fn find_item_end(lines: &[String], header_idx: usize) -> Option<usize> {
+    let header = lines[header_idx].trim_end();
+
+    // Indentation-based (Python-style `def foo():`)
+    if header.ends_with(':') && !header.contains('{') {
+        let base = indent_of(header);
+        let mut last = header_idx;
+        for (idx, line) in lines.iter().enumerate().skip(header_idx + 1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if indent_of(line) <= base {
+                break;
+            }
+            last = idx;
+        }
+        return (last > header_idx).then_some(last);
+    }
+
+    // Brace-based
+    let mut depth: i64 = 0;
+    let mut opened = false;
+    for (idx, line) in lines.iter().enumerate().skip(header_idx) {
+        for c in strip_strings(line).chars() {
+            match c {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if opened && depth <= 0 {
+            return Some(idx);
+        }
+        // A declaration that ends before any brace (e.g. `struct Foo;`)
+        if !opened && line.trim_end().ends_with(';') {
+            return Some(idx);
+        }
+    }
+    None
+}
+
+/// Drop string/char literal contents and line comments so braces inside them are ignored
+fn strip_strings(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_str: Option<char> = None;
+    let mut escaped = false;
+    let mut prev = '\0';
+    for c in line.chars() {
+        if let Some(quote) = in_str {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote {
+                in_str = None;
+            }
+        } else if c == '"' || c == '`' {
+            in_str = Some(c);
+        } else if c == '/' && prev == '/' {
+            out.pop();
+            break;
+        } else {
+            out.push(c);
+        }
+        prev = c;
+    }
+    out
+}
+
+/// Extend the block upward to include doc comments and attributes
+fn include_leading_attributes(lines: &[String], item_start: usize) -> usize {
+    let mut start = item_start;
+    while start > 0 {
+        let prev = lines[start - 1].trim_start();
+        if prev.starts_with("///")
+            || prev.starts_with("//!")
+            || prev.starts_with("#[")
+            || prev.starts_with('@')
+            || prev.starts_with("/**")
+            || prev.starts_with('*')
+        {
+            start -= 1;
+        } else {
+            break;
+        }
+    }
+    start
+}
+
+/// Collect import statements appearing before `limit_idx`
+fn collect_imports(lines: &[String], limit_idx: usize) -> Vec<String> {
+    let mut imports = Vec::new();
+    let mut continuing = false;
+    for line in lines.iter().take(limit_idx) {
+        let trimmed = line.trim();
+        if continuing {
+            imports.push(line.clone());
+            // Multi-line `use foo::{ ... };`, `import ( ... )` or `from x import ( ... )`
+            if trimmed.ends_with(';') || trimmed == ")" || trimmed.ends_with("})") {
+                continuing = false;
+            }
+        } else if IMPORT_PREFIXES.iter().any(|p| trimmed.starts_with(p)) {
+            imports.push(line.clone());
+            continuing = trimmed.ends_with('{') || trimmed.ends_with('(');
+        }
+        if imports.len() >= MAX_IMPORT_LINES {
+            break;
+        }
+    }
+    imports
+}
+
+/// Markdown code fence language for a file path
+fn language_hint(file: &str) -> &'static str {
+    match Path::new(file)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+    {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "mjs" | "cjs" | "jsx" => "javascript",
+        "ts" | "tsx" | "mts" | "cts" => "typescript",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "c" | "h" => "c",
+        "cpp" | "cc" | "cxx" | "hpp" | "hxx" => "cpp",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "swift" => "swift",
+        "php" => "php",
+        "sh" | "bash" | "zsh" => "bash",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(src: &str) -> Vec<String> {
+        src.lines().map(str::to_string).collect()
+    }
+
+    const RUST_SRC: &str = r#"use std::path::Path;
+use crate::{
+    a::B,
+    c::D,
+};
+
+/// Does a thing
+#[inline]
+pub fn outer(x: u32) -> u32 {
+    let s = "{ not a brace";
+    if x > 1 {
+        x + 1
+    } else {
+        0
+    }
+}
+
+fn other() {}
+"#;
+
+    #[test]
+    fn test_expands_to_enclosing_function() {
+        let src = lines(RUST_SRC);
+        let ctx = CodeContext::expand(Path::new("src/lib.rs"), &src, 12, 12).expect("context");
+
+        assert_eq!(
+            ctx.enclosing_item.as_deref(),
+            Some("pub fn outer(x: u32) -> u32 {")
+        );
+        // Doc comment and attribute are pulled in, closing brace is the end
+        assert_eq!(ctx.block_range, (7, 16));
+        assert_eq!(ctx.imports.len(), 5);
+        assert!(ctx.imports[0].starts_with("use std::path::Path"));
+    }
+
+    #[test]
+    fn test_prompt_section_marks_selection() {
+        let src = lines(RUST_SRC);
+        let ctx = CodeContext::expand(Path::new("src/lib.rs"), &src, 12, 12).expect("context");
+        let section = ctx.to_prompt_section();
+
+        assert!(section.contains("File: src/lib.rs (lines 12-12)"));
+        assert!(section.contains("```rust"));
+        assert!(section.contains(">   12 |         x + 1"));
+        assert!(section.contains("    11 |     if x > 1 {"));
+    }
+
+    #[test]
+    fn test_python_indentation_block() {
+        let src = lines(
+            "import os\nfrom x import y\n\nclass Foo:\n    def bar(self):\n        return 1\n\n    def baz(self):\n        return 2\n",
+        );
+        let ctx = CodeContext::expand(Path::new("a.py"), &src, 6, 6).expect("context");
+
+        assert_eq!(ctx.enclosing_item.as_deref(), Some("def bar(self):"));
+        assert_eq!(ctx.block_range, (5, 6));
+        assert_eq!(ctx.imports, vec!["import os", "from x import y"]);
+    }
+
+    #[test]
+    fn test_falls_back_to_window_without_item() {
+        let src = lines("a\nb\nc\nd\ne");
+        let ctx = CodeContext::expand(Path::new("notes.txt"), &src, 3, 3).expect("context");
+
+        assert_eq!(ctx.enclosing_item, None);
+        assert_eq!(ctx.block_range, (1, 5));
+    }
+}
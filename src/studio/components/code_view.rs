@@ -13,9 +13,10 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use unicode_width::UnicodeWidthStr;
 
+use super::file_blame::{self, FileBlame, GUTTER_WIDTH};
 use super::syntax::SyntaxHighlighter;
 use crate::studio::theme;
-use crate::studio::utils::expand_tabs;
+use crate::studio::utils::{expand_tabs, truncate_width};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Code View State
@@ -34,6 +35,8 @@ pub struct CodeViewState {
     selected_line: usize,
     /// Selection range for multi-line selection (start, end) 1-indexed
     selection: Option<(usize, usize)>,
+    /// Whole-file blame shown in the gutter
+    blame: Option<FileBlame>,
 }
 
 impl CodeViewState {
@@ -50,9 +53,25 @@ impl CodeViewState {
         self.scroll_offset = 0;
         self.selected_line = 1;
         self.selection = None;
+        self.blame = None;
         Ok(())
     }
 
+    /// Whole-file blame shown in the gutter
+    pub fn blame(&self) -> Option<&FileBlame> {
+        self.blame.as_ref()
+    }
+
+    /// Mutable whole-file blame, to fill in Iris's reasons
+    pub fn blame_mut(&mut self) -> Option<&mut FileBlame> {
+        self.blame.as_mut()
+    }
+
+    /// Show blame in the gutter, or hide it with `None`
+    pub fn set_blame(&mut self, blame: Option<FileBlame>) {
+        self.blame = blame;
+    }
+
     /// Get current file path
     pub fn current_file(&self) -> Option<&Path> {
         self.current_file.as_deref()
@@ -113,6 +132,13 @@ impl CodeViewState {
         self.scroll_offset = (self.scroll_offset + amount).min(max_offset);
     }
 
+    /// Select a line (1-indexed) and scroll it near the top, keeping a few
+    /// lines of context above it
+    pub fn show_line(&mut self, line: usize) {
+        self.set_selected_line(line);
+        self.scroll_to_line(line.saturating_sub(3).max(1), 1);
+    }
+
     /// Scroll to make a specific line visible (1-indexed)
     pub fn scroll_to_line(&mut self, line: usize, visible_height: usize) {
         if line == 0 || self.lines.is_empty() {
@@ -188,7 +214,7 @@ pub fn render_code_view(
     title: &str,
     focused: bool,
 ) {
-    let block = Block::default()
+    let mut block = Block::default()
         .title(format!(" {} ", title))
         .borders(Borders::ALL)
         .border_style(if focused {
@@ -196,6 +222,17 @@ pub fn render_code_view(
         } else {
             theme::unfocused_border()
         });
+    // The selected line's commit and why go in the bottom border
+    if let Some(annotation) = state
+        .blame()
+        .and_then(|blame| file_blame::annotation(blame, state.selected_line))
+    {
+        let width = area.width.saturating_sub(4) as usize;
+        block = block.title_bottom(Line::styled(
+            format!(" {} ", truncate_width(&annotation, width.saturating_sub(2))),
+            theme::dimmed(),
+        ));
+    }
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -226,15 +263,24 @@ pub fn render_code_view(
         .skip(scroll_offset)
         .take(visible_height)
         .map(|(idx, content)| {
-            render_code_line(
+            let gutter = state
+                .blame()
+                .map(|blame| file_blame::gutter_spans(blame, idx + 1));
+            let width =
+                (inner.width as usize).saturating_sub(gutter.as_ref().map_or(0, |_| GUTTER_WIDTH));
+            let mut line = render_code_line(
                 idx + 1, // 1-indexed line number
                 content,
                 line_num_width,
-                inner.width as usize,
+                width,
                 state.selected_line,
                 state.selection(),
                 highlighter.as_ref(),
-            )
+            );
+            if let Some(gutter) = gutter {
+                line.spans.splice(0..0, gutter);
+            }
+            line
         })
         .collect();
 
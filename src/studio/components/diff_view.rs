@@ -11,7 +11,9 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
 };
+use std::ops::Range;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Diff Types
@@ -71,62 +73,70 @@ impl DiffLineType {
 pub struct DiffLine {
     /// Type of line
     pub line_type: DiffLineType,
-    /// Line content (without prefix)
-    pub content: String,
+    /// Line content (without prefix), shared with cached copies
+    pub content: Arc<str>,
     /// Old line number (for context and removed)
     pub old_line_num: Option<usize>,
     /// New line number (for context and added)
     pub new_line_num: Option<usize>,
+    /// Byte ranges of `content` that differ from the paired line on the
+    /// other side of the change; empty means the whole line is highlighted
+    pub emphasis: Vec<Range<usize>>,
 }
 
 impl DiffLine {
     /// Create a context line
-    pub fn context(content: impl Into<String>, old_num: usize, new_num: usize) -> Self {
+    pub fn context(content: impl Into<Arc<str>>, old_num: usize, new_num: usize) -> Self {
         Self {
             line_type: DiffLineType::Context,
             content: content.into(),
             old_line_num: Some(old_num),
             new_line_num: Some(new_num),
+            emphasis: Vec::new(),
         }
     }
 
     /// Create an added line
-    pub fn added(content: impl Into<String>, new_num: usize) -> Self {
+    pub fn added(content: impl Into<Arc<str>>, new_num: usize) -> Self {
         Self {
             line_type: DiffLineType::Added,
             content: content.into(),
             old_line_num: None,
             new_line_num: Some(new_num),
+            emphasis: Vec::new(),
         }
     }
 
     /// Create a removed line
-    pub fn removed(content: impl Into<String>, old_num: usize) -> Self {
+    pub fn removed(content: impl Into<Arc<str>>, old_num: usize) -> Self {
         Self {
             line_type: DiffLineType::Removed,
             content: content.into(),
             old_line_num: Some(old_num),
             new_line_num: None,
+            emphasis: Vec::new(),
         }
     }
 
     /// Create a hunk header line
-    pub fn hunk_header(content: impl Into<String>) -> Self {
+    pub fn hunk_header(content: impl Into<Arc<str>>) -> Self {
         Self {
             line_type: DiffLineType::HunkHeader,
             content: content.into(),
             old_line_num: None,
             new_line_num: None,
+            emphasis: Vec::new(),
         }
     }
 
     /// Create a file header line
-    pub fn file_header(content: impl Into<String>) -> Self {
+    pub fn file_header(content: impl Into<Arc<str>>) -> Self {
         Self {
             line_type: DiffLineType::FileHeader,
             content: content.into(),
             old_line_num: None,
             new_line_num: None,
+            emphasis: Vec::new(),
         }
     }
 }
@@ -152,6 +162,19 @@ pub struct DiffHunk {
     pub new_count: usize,
 }
 
+impl DiffHunk {
+    /// Render back to unified diff text
+    pub fn to_patch(&self) -> String {
+        let mut out = self.header.clone();
+        for line in &self.lines {
+            out.push('\n');
+            out.push_str(line.line_type.prefix());
+            out.push_str(&line.content);
+        }
+        out
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // File Diff
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -202,6 +225,15 @@ impl FileDiff {
         (added, removed)
     }
 
+    /// Render back to unified diff text (hunks only)
+    pub fn to_patch(&self) -> String {
+        self.hunks
+            .iter()
+            .map(DiffHunk::to_patch)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Get all lines for display
     pub fn all_lines(&self) -> Vec<DiffLine> {
         let mut lines = Vec::new();
@@ -223,15 +255,16 @@ impl FileDiff {
         if self.is_binary {
             lines.push(DiffLine {
                 line_type: DiffLineType::Empty,
-                content: "Binary file".to_string(),
+                content: "Binary file".into(),
                 old_line_num: None,
                 new_line_num: None,
+                emphasis: Vec::new(),
             });
             return lines;
         }
 
         for hunk in &self.hunks {
-            lines.push(DiffLine::hunk_header(&hunk.header));
+            lines.push(DiffLine::hunk_header(hunk.header.as_str()));
             lines.extend(hunk.lines.clone());
         }
 
@@ -380,6 +413,41 @@ impl DiffViewState {
         }
     }
 
+    /// Scroll to the hunk containing `line` of the new file, or the last hunk
+    /// before it (returns true if the file has hunks)
+    pub fn scroll_to_new_line(&mut self, line: usize) -> bool {
+        let mut target = None;
+        for (i, diff_line) in self.cached_lines.iter().enumerate() {
+            if diff_line.line_type == DiffLineType::HunkHeader {
+                // The last header before the first line at or past `line`
+                target = Some(i);
+            } else if diff_line.new_line_num.is_some_and(|n| n >= line) {
+                break;
+            }
+        }
+        match target {
+            Some(i) => {
+                self.scroll_offset = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Index of the hunk at the top of the view, with the hunk itself
+    pub fn current_hunk(&self) -> Option<(usize, &DiffHunk)> {
+        let diff = self.current_diff()?;
+        let headers_passed = self
+            .cached_lines
+            .iter()
+            .take(self.scroll_offset + 1)
+            .filter(|l| l.line_type == DiffLineType::HunkHeader)
+            .count();
+        // Before the first hunk header, the first hunk is the nearest one
+        let idx = headers_passed.saturating_sub(1);
+        diff.hunks.get(idx).map(|hunk| (idx, hunk))
+    }
+
     /// Get cached lines
     pub fn lines(&self) -> &[DiffLine] {
         &self.cached_lines
@@ -406,6 +474,11 @@ impl DiffViewState {
         false
     }
 
+    /// Get all file diffs
+    pub fn diffs(&self) -> &[FileDiff] {
+        &self.diffs
+    }
+
     /// Get all file paths in the diff
     pub fn file_paths(&self) -> Vec<&std::path::Path> {
         self.diffs.iter().map(|d| d.path.as_path()).collect()
@@ -526,9 +599,151 @@ pub fn parse_diff(diff_text: &str) -> Vec<FileDiff> {
         diffs.push(diff);
     }
 
+    for hunk in diffs.iter_mut().flat_map(|diff| diff.hunks.iter_mut()) {
+        highlight_word_changes(&mut hunk.lines);
+    }
     diffs
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// Word Diff
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Lines with more tokens than this keep whole-line coloring
+const MAX_WORD_DIFF_TOKENS: usize = 256;
+
+/// Changed byte ranges within one line
+type Emphasis = Vec<Range<usize>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Word,
+    Space,
+    Punct,
+}
+
+impl CharClass {
+    fn of(ch: char) -> Self {
+        if ch.is_alphanumeric() || ch == '_' {
+            Self::Word
+        } else if ch.is_whitespace() {
+            Self::Space
+        } else {
+            Self::Punct
+        }
+    }
+}
+
+/// Split a line into words, runs of whitespace and single punctuation
+/// characters, as byte ranges
+fn tokenize(line: &str) -> Vec<Range<usize>> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some((start, ch)) = chars.next() {
+        let class = CharClass::of(ch);
+        let mut end = start + ch.len_utf8();
+        if class != CharClass::Punct {
+            while let Some(&(i, next)) = chars.peek()
+                && CharClass::of(next) == class
+            {
+                end = i + next.len_utf8();
+                chars.next();
+            }
+        }
+        tokens.push(start..end);
+    }
+    tokens
+}
+
+/// Extend the last range when `range` follows on from it
+fn push_merged(ranges: &mut Vec<Range<usize>>, range: Range<usize>) {
+    match ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => ranges.push(range),
+    }
+}
+
+/// Byte ranges of `old` and `new` outside their longest common run of
+/// tokens, or `None` when the lines share too little for word highlighting
+/// to say more than whole-line coloring does
+fn word_changes(old: &str, new: &str) -> Option<(Emphasis, Emphasis)> {
+    let a = tokenize(old);
+    let b = tokenize(new);
+    if a.len() > MAX_WORD_DIFF_TOKENS || b.len() > MAX_WORD_DIFF_TOKENS {
+        return None;
+    }
+    let same = |i: usize, j: usize| old[a[i].clone()] == new[b[j].clone()];
+
+    // common[i][j]: length of the longest common subsequence of a[i..], b[j..]
+    let mut common = vec![vec![0_usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            common[i][j] = if same(i, j) {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut old_changed = Vec::new();
+    let mut new_changed = Vec::new();
+    let mut shared = 0;
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if same(i, j) {
+            shared += a[i].len();
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            push_merged(&mut old_changed, a[i].clone());
+            i += 1;
+        } else {
+            push_merged(&mut new_changed, b[j].clone());
+            j += 1;
+        }
+    }
+    for range in &a[i..] {
+        push_merged(&mut old_changed, range.clone());
+    }
+    for range in &b[j..] {
+        push_merged(&mut new_changed, range.clone());
+    }
+
+    // Mostly rewritten lines read better as plain removed/added lines
+    (shared * 5 >= old.len().max(new.len()) * 2).then_some((old_changed, new_changed))
+}
+
+/// Pair each run of removed lines with the added lines that follow it and
+/// mark the words that changed between them
+fn highlight_word_changes(lines: &mut [DiffLine]) {
+    let mut start = 0;
+    while start < lines.len() {
+        let removed = lines[start..]
+            .iter()
+            .take_while(|line| line.line_type == DiffLineType::Removed)
+            .count();
+        if removed == 0 {
+            start += 1;
+            continue;
+        }
+        let added = lines[start + removed..]
+            .iter()
+            .take_while(|line| line.line_type == DiffLineType::Added)
+            .count();
+        for offset in 0..removed.min(added) {
+            let (old, new) = (start + offset, start + removed + offset);
+            if let Some((old_changed, new_changed)) =
+                word_changes(&lines[old].content, &lines[new].content)
+            {
+                lines[old].emphasis = old_changed;
+                lines[new].emphasis = new_changed;
+            }
+        }
+        start += removed + added;
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Rendering
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -638,28 +853,67 @@ fn render_diff_line(line: &DiffLine, line_num_width: usize, width: usize) -> Lin
                 _ => theme::dimmed(),
             };
 
-            // Expand tabs to spaces for proper width calculation and rendering
-            let expanded_content = expand_tabs(&line.content, 4);
-
             // Calculate available width for content
             // Format: "XXXX │ XXXX +content"
             let fixed_width = line_num_width * 2 + 6; // " │ " (3) + " " (1) + prefix (1) + padding (1)
             let max_content = width.saturating_sub(fixed_width);
-            let truncated = truncate_width(&expanded_content, max_content);
 
-            Line::from(vec![
+            let mut spans = vec![
                 Span::styled(old_num, theme::dimmed()),
                 Span::styled(" │ ", theme::dimmed()),
                 Span::styled(new_num, theme::dimmed()),
                 Span::raw(" "),
                 Span::styled(prefix, prefix_style),
-                Span::styled(truncated, style),
-            ])
+            ];
+            spans.extend(content_spans(line, max_content, style));
+            Line::from(spans)
         }
         DiffLineType::Empty => Line::from(""),
     }
 }
 
+/// A line's content fitted to `max_width`, with its changed words
+/// emphasized
+fn content_spans(line: &DiffLine, max_width: usize, style: Style) -> Vec<Span<'static>> {
+    // Expand tabs to spaces for proper width calculation and rendering
+    let expanded = expand_tabs(&line.content, 4);
+    let truncated = truncate_width(&expanded, max_width);
+    let word_style = match line.line_type {
+        DiffLineType::Added => theme::diff_added_word(),
+        DiffLineType::Removed => theme::diff_removed_word(),
+        _ => return vec![Span::styled(truncated, style)],
+    };
+    if line.emphasis.is_empty() {
+        return vec![Span::styled(truncated, style)];
+    }
+
+    // Length of the content that made it past truncation, before any ellipsis
+    let shown = truncated
+        .strip_suffix('…')
+        .filter(|_| truncated.len() != expanded.len())
+        .map_or(truncated.len(), str::len);
+    // Expansion works left to right, so a content offset maps to the length
+    // of its expanded prefix
+    let at = |offset: usize| expand_tabs(&line.content[..offset], 4).len().min(shown);
+
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for range in &line.emphasis {
+        let (start, end) = (at(range.start), at(range.end));
+        if start > pos {
+            spans.push(Span::styled(truncated[pos..start].to_string(), style));
+        }
+        if end > start {
+            spans.push(Span::styled(truncated[start..end].to_string(), word_style));
+        }
+        pos = pos.max(end);
+    }
+    if pos < truncated.len() {
+        spans.push(Span::styled(truncated[pos..].to_string(), style));
+    }
+    spans
+}
+
 /// Render a compact summary of changes
 pub fn render_diff_summary(diff: &FileDiff) -> Line<'static> {
     let (added, removed) = diff.lines_changed();
@@ -687,3 +941,40 @@ pub fn render_diff_summary(diff: &FileDiff) -> Line<'static> {
         ),
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changed(line: &DiffLine) -> Vec<&str> {
+        line.emphasis
+            .iter()
+            .map(|range| &line.content[range.clone()])
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_diff_marks_changed_words() {
+        let diffs = parse_diff(
+            "diff --git a/src/lib.rs b/src/lib.rs\n\
+             @@ -1,4 +1,4 @@\n\
+             -let timeout = Duration::from_secs(30);\n\
+             -fn old_name() {}\n\
+             +let timeout = Duration::from_millis(30);\n\
+             +struct Completely { different: bool }\n\
+             \x20unchanged\n",
+        );
+        let lines = &diffs[0].hunks[0].lines;
+
+        assert_eq!(changed(&lines[0]), vec!["from_secs"]);
+        assert_eq!(changed(&lines[2]), vec!["from_millis"]);
+        // Rewritten lines keep whole-line coloring
+        assert!(lines[1].emphasis.is_empty());
+        assert!(lines[3].emphasis.is_empty());
+        assert!(lines[4].emphasis.is_empty());
+
+        let (old, new) = word_changes("a(b, c)", "a(b, c, d)").expect("similar lines");
+        assert!(old.is_empty());
+        assert_eq!(new, vec![6..9]);
+    }
+}
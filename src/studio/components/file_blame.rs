@@ -0,0 +1,264 @@
+//! Whole-file blame gutter for the code view
+//!
+//! Blames every line of the open file. The gutter names the commit at the
+//! start of each region, and a bar colored by the commit's age turns the file
+//! into a heat map: red for the newest changes, green for the oldest. Iris
+//! then gives each commit a one-line "why", shown in the code view's bottom
+//! border for the selected line.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use ratatui::style::Style;
+use ratatui::text::Span;
+
+use crate::git::BlameRegion;
+use crate::studio::theme;
+
+/// Columns the gutter takes: bar, short id and a space
+pub const GUTTER_WIDTH: usize = 9;
+
+/// Commits Iris explains, those covering the most lines first
+const MAX_EXPLAINED_COMMITS: usize = 25;
+
+/// Lines of code shown to Iris per commit
+const SAMPLE_LINES: usize = 6;
+
+/// Blame for every line of a file, with Iris's reason for each commit
+#[derive(Debug, Clone, Default)]
+pub struct FileBlame {
+    /// Regions in line order
+    pub regions: Vec<BlameRegion>,
+    /// One-line reasons by full commit id
+    pub whys: HashMap<String, String>,
+    /// Whether Iris is still explaining the commits
+    pub explaining: bool,
+    oldest: i64,
+    newest: i64,
+}
+
+impl FileBlame {
+    /// Blame from `regions`, waiting for Iris's reasons if `explaining`
+    pub fn new(regions: Vec<BlameRegion>, explaining: bool) -> Self {
+        let committed = regions.iter().filter(|r| !r.is_uncommitted());
+        let oldest = committed.clone().map(|r| r.time).min().unwrap_or(0);
+        let newest = committed.map(|r| r.time).max().unwrap_or(0);
+        Self {
+            regions,
+            whys: HashMap::new(),
+            explaining,
+            oldest,
+            newest,
+        }
+    }
+
+    /// Region holding `line` (1-based)
+    pub fn region_at(&self, line: usize) -> Option<&BlameRegion> {
+        let idx = self.regions.partition_point(|r| r.end < line);
+        self.regions.get(idx).filter(|r| r.contains(line))
+    }
+
+    /// How recent a region is within the file, 0 (oldest) to 100 (newest
+    /// or uncommitted)
+    pub fn heat(&self, region: &BlameRegion) -> i64 {
+        if region.is_uncommitted() || self.newest <= self.oldest {
+            return 100;
+        }
+        (region.time - self.oldest) * 100 / (self.newest - self.oldest)
+    }
+
+    /// Iris's reason for the region's commit
+    pub fn why(&self, region: &BlameRegion) -> Option<&str> {
+        self.whys.get(&region.commit).map(String::as_str)
+    }
+
+    /// Apply Iris's answer, returning why it failed
+    pub fn set_whys(&mut self, result: Result<String, String>) -> Option<String> {
+        self.explaining = false;
+        match result {
+            Ok(answer) => {
+                self.whys = parse_whys(&answer, &self.explained_commits());
+                None
+            }
+            Err(e) => Some(e),
+        }
+    }
+
+    /// Commits worth explaining, covering the most lines first
+    fn explained_commits(&self) -> Vec<&BlameRegion> {
+        let mut lines: HashMap<&str, (usize, &BlameRegion)> = HashMap::new();
+        for region in self.regions.iter().filter(|r| !r.is_uncommitted()) {
+            let entry = lines.entry(&region.commit).or_insert((0, region));
+            entry.0 += region.len();
+            if region.len() > entry.1.len() {
+                entry.1 = region;
+            }
+        }
+        let mut commits: Vec<(usize, &BlameRegion)> = lines.into_values().collect();
+        commits.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.time.cmp(&a.1.time)));
+        commits
+            .into_iter()
+            .take(MAX_EXPLAINED_COMMITS)
+            .map(|(_, region)| region)
+            .collect()
+    }
+
+    /// Task prompt listing each commit with a sample of the lines it wrote
+    pub fn to_prompt(&self, file: &Path, lines: &[String]) -> String {
+        let mut prompt = format!(
+            "File: {}\nExplain why each of these commits wrote its lines of the file.\n",
+            file.display()
+        );
+        for region in self.explained_commits() {
+            let ranges: Vec<String> = self
+                .regions
+                .iter()
+                .filter(|r| r.commit == region.commit)
+                .map(|r| format!("{}-{}", r.start, r.end))
+                .collect();
+            let sample_end = region.end.min(region.start + SAMPLE_LINES - 1);
+            let sample = lines
+                .get(region.start - 1..sample_end.min(lines.len()))
+                .unwrap_or_default()
+                .join("\n");
+            prompt.push_str(&format!(
+                "\n## {} by {} on {}\nSubject: {}\nLines: {}\n```\n{sample}\n```\n",
+                region.short_id(),
+                region.author,
+                format_date(region.time),
+                region.summary,
+                ranges.join(", "),
+            ));
+        }
+        prompt
+    }
+}
+
+/// Parse `<short id> - <why>` lines, keyed by the matching full commit id
+fn parse_whys(text: &str, commits: &[&BlameRegion]) -> HashMap<String, String> {
+    let mut whys = HashMap::new();
+    for line in text.lines().map(str::trim) {
+        let line = line.trim_start_matches(['-', '*', ' ']);
+        let Some((id, why)) = line.split_once(" - ").or_else(|| line.split_once(" — ")) else {
+            continue;
+        };
+        let id = id.trim().trim_matches('`');
+        if id.len() < 7 {
+            continue;
+        }
+        if let Some(region) = commits.iter().find(|r| r.commit.starts_with(id)) {
+            whys.insert(region.commit.clone(), why.trim().to_string());
+        }
+    }
+    whys
+}
+
+/// Author date as `YYYY-MM-DD`
+fn format_date(time: i64) -> String {
+    chrono::DateTime::from_timestamp(time, 0)
+        .map_or_else(String::new, |dt| dt.format("%Y-%m-%d").to_string())
+}
+
+/// Gutter cells for `line`: a heat bar, and the commit id where a region starts
+pub fn gutter_spans(blame: &FileBlame, line: usize) -> Vec<Span<'static>> {
+    let Some(region) = blame.region_at(line) else {
+        return vec![Span::raw(" ".repeat(GUTTER_WIDTH))];
+    };
+    let heat = blame.heat(region);
+    let color = if heat >= 75 {
+        theme::error_color()
+    } else if heat >= 50 {
+        theme::accent_tertiary()
+    } else if heat >= 25 {
+        theme::warning_color()
+    } else {
+        theme::success_color()
+    };
+    let label = if region.start != line {
+        String::new()
+    } else if region.is_uncommitted() {
+        "working".to_string()
+    } else {
+        region.short_id().to_string()
+    };
+    vec![
+        Span::styled("▌", Style::default().fg(color)),
+        Span::styled(
+            format!("{label:<width$}", width = GUTTER_WIDTH - 2),
+            theme::dimmed(),
+        ),
+        Span::raw(" "),
+    ]
+}
+
+/// Bottom border text for the region holding `line`
+pub fn annotation(blame: &FileBlame, line: usize) -> Option<String> {
+    let region = blame.region_at(line)?;
+    if region.is_uncommitted() {
+        return Some("not committed yet".to_string());
+    }
+    let why = match blame.why(region) {
+        Some(why) => why.to_string(),
+        None if blame.explaining => "asking Iris why…".to_string(),
+        None => region.summary.clone(),
+    };
+    Some(format!(
+        "{} · {} · {} · {why}",
+        region.short_id(),
+        region.author,
+        format_date(region.time)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(start: usize, end: usize, commit: char, time: i64) -> BlameRegion {
+        BlameRegion {
+            start,
+            end,
+            commit: commit.to_string().repeat(40),
+            author: "Ada".to_string(),
+            time,
+            summary: format!("commit {commit}"),
+        }
+    }
+
+    #[test]
+    fn test_file_blame_heat_and_whys() {
+        let mut blame = FileBlame::new(
+            vec![
+                region(1, 4, 'a', 1_000),
+                region(5, 5, 'b', 3_000),
+                region(6, 9, 'a', 1_000),
+                region(10, 10, '0', 9_000),
+            ],
+            true,
+        );
+        assert_eq!(blame.region_at(7).map(|r| r.start), Some(6));
+        assert!(blame.region_at(11).is_none());
+        assert_eq!(blame.heat(&blame.regions[0]), 0);
+        assert_eq!(blame.heat(&blame.regions[1]), 100);
+        assert_eq!(blame.heat(&blame.regions[3]), 100);
+
+        let lines: Vec<String> = (1..=10).map(|n| format!("line {n}")).collect();
+        let prompt = blame.to_prompt(Path::new("src/a.rs"), &lines);
+        assert!(prompt.contains("Lines: 1-4, 6-9"));
+        assert!(prompt.find("## aaaaaaa") < prompt.find("## bbbbbbb"));
+        assert!(!prompt.contains("## 0000000"));
+
+        assert_eq!(
+            annotation(&blame, 5).as_deref(),
+            Some("bbbbbbb · Ada · 1970-01-01 · asking Iris why…")
+        );
+        let error = blame.set_whys(Ok("aaaaaaa - adds the parser\n\
+             - `bbbbbbbb` - fixes the off-by-one\n\
+             ccccccc - not in this file\n"
+            .to_string()));
+        assert!(error.is_none());
+        assert_eq!(blame.whys.len(), 2);
+        assert_eq!(blame.why(&blame.regions[2]), Some("adds the parser"));
+        assert_eq!(annotation(&blame, 10).as_deref(), Some("not committed yet"));
+    }
+}
@@ -9,10 +9,12 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use unicode_width::UnicodeWidthStr;
 
+use crate::git::parse_log;
 use crate::studio::theme;
 use crate::studio::utils::truncate_width;
 
@@ -61,6 +63,135 @@ impl FileGitStatus {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// Heat Map Overlay
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Which heat map overlay (if any) is drawn over the tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeatMapMode {
+    #[default]
+    Off,
+    /// Colorize by number of recent commits touching the path
+    Churn,
+    /// Colorize by the author with the most recent commits to the path
+    Ownership,
+}
+
+impl HeatMapMode {
+    /// Cycle Off → Churn → Ownership → Off
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Churn,
+            Self::Churn => Self::Ownership,
+            Self::Ownership => Self::Off,
+        }
+    }
+
+    /// Short label for titles and notifications
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Churn => "churn",
+            Self::Ownership => "ownership",
+        }
+    }
+}
+
+/// Recent activity for a single path (file or directory)
+#[derive(Debug, Clone, Default)]
+pub struct FileHeat {
+    /// Commits touching this path (or anything below it) within the window
+    pub commits: usize,
+    /// Author with the most commits to this path
+    pub primary_author: Option<String>,
+}
+
+/// Churn and ownership data computed from `git log`
+#[derive(Debug, Clone, Default)]
+pub struct HeatMapData {
+    /// Heat per path; directories aggregate their descendants
+    pub files: HashMap<PathBuf, FileHeat>,
+    /// Highest commit count of any single file (for scaling)
+    pub max_commits: usize,
+    /// Authors ordered by total commits, most active first
+    pub authors: Vec<String>,
+    /// Size of the window in days
+    pub window_days: u32,
+}
+
+impl HeatMapData {
+    /// Aggregate output of `git log --format=%x1e%an --name-only`
+    pub fn from_git_log(output: &str, window_days: u32) -> Self {
+        let mut per_path: HashMap<PathBuf, HashMap<String, usize>> = HashMap::new();
+        let mut totals: HashMap<String, usize> = HashMap::new();
+
+        for record in parse_log(output) {
+            let author = record.author;
+            *totals.entry(author.to_string()).or_default() += 1;
+
+            // Changed files and their directories, so a directory sees the commit once
+            let mut touched: HashSet<PathBuf> = HashSet::new();
+            for file in record.files {
+                let path = PathBuf::from(file.path);
+                for ancestor in path.ancestors() {
+                    if ancestor.as_os_str().is_empty() {
+                        break;
+                    }
+                    touched.insert(ancestor.to_path_buf());
+                }
+            }
+            for path in touched {
+                *per_path
+                    .entry(path)
+                    .or_default()
+                    .entry(author.to_string())
+                    .or_default() += 1;
+            }
+        }
+
+        let mut authors: Vec<(String, usize)> = totals.into_iter().collect();
+        authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let files: HashMap<PathBuf, FileHeat> = per_path
+            .into_iter()
+            .map(|(path, by_author)| {
+                let commits = by_author.values().sum();
+                let primary_author = by_author
+                    .into_iter()
+                    .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+                    .map(|(name, _)| name);
+                (
+                    path,
+                    FileHeat {
+                        commits,
+                        primary_author,
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            max_commits: files.values().map(|h| h.commits).max().unwrap_or(0),
+            files,
+            authors: authors.into_iter().map(|(name, _)| name).collect(),
+            window_days,
+        }
+    }
+
+    /// Rank of an author by activity (0 = most active)
+    pub fn author_rank(&self, author: &str) -> Option<usize> {
+        self.authors.iter().position(|a| a == author)
+    }
+}
+
+/// Heat map data paired with the overlay being shown
+#[derive(Debug, Clone)]
+struct HeatOverlay {
+    mode: HeatMapMode,
+    data: Arc<HeatMapData>,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Tree Node
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -166,6 +297,8 @@ pub struct FileTreeState {
     flat_cache: Vec<FlatEntry>,
     /// Cache is dirty flag
     cache_dirty: bool,
+    /// Optional churn/ownership overlay
+    heat_overlay: Option<HeatOverlay>,
 }
 
 impl Default for FileTreeState {
@@ -184,6 +317,7 @@ impl FileTreeState {
             scroll_offset: 0,
             flat_cache: Vec::new(),
             cache_dirty: true,
+            heat_overlay: None,
         }
     }
 
@@ -200,6 +334,21 @@ impl FileTreeState {
         self.scroll_offset = 0;
     }
 
+    /// Show a heat map overlay, or clear it with `HeatMapMode::Off` / `None` data
+    pub fn set_heat_overlay(&mut self, mode: HeatMapMode, data: Option<Arc<HeatMapData>>) {
+        self.heat_overlay = match (mode, data) {
+            (HeatMapMode::Off, _) | (_, None) => None,
+            (mode, Some(data)) => Some(HeatOverlay { mode, data }),
+        };
+    }
+
+    /// Currently displayed heat map mode
+    pub fn heat_map_mode(&self) -> HeatMapMode {
+        self.heat_overlay
+            .as_ref()
+            .map_or(HeatMapMode::Off, |overlay| overlay.mode)
+    }
+
     /// Build tree from a list of file paths
     pub fn from_paths(paths: &[PathBuf], git_statuses: &[(PathBuf, FileGitStatus)]) -> Self {
         let mut state = Self::new();
@@ -307,6 +456,23 @@ impl FileTreeState {
         }
     }
 
+    /// Select a file, expanding its parent directories (returns true if found)
+    pub fn select_path(&mut self, path: &Path) -> bool {
+        for ancestor in path.ancestors().skip(1) {
+            if !ancestor.as_os_str().is_empty() {
+                self.expanded.insert(ancestor.to_path_buf());
+            }
+        }
+        self.cache_dirty = true;
+        match self.flat_view().iter().position(|e| e.path == path) {
+            Some(index) => {
+                self.selected = index;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Jump to first item
     pub fn select_first(&mut self) {
         self.selected = 0;
@@ -559,14 +725,45 @@ pub fn render_file_tree(
     title: &str,
     focused: bool,
 ) {
-    let block = Block::default()
-        .title(format!(" {} ", title))
+    let title = match &state.heat_overlay {
+        Some(overlay) => format!(
+            " {} · {} {}d ",
+            title,
+            overlay.mode.label(),
+            overlay.data.window_days
+        ),
+        None => format!(" {} ", title),
+    };
+
+    // Selected entry's heat details go in the bottom border
+    let selected_path = state.selected_path();
+    let selected_heat = state.heat_overlay.as_ref().and_then(|overlay| {
+        let path = selected_path?;
+        let heat = overlay.data.files.get(&path)?;
+        let commits = format!(
+            "{} commit{}",
+            heat.commits,
+            if heat.commits == 1 { "" } else { "s" }
+        );
+        Some(match &heat.primary_author {
+            Some(author) if overlay.mode == HeatMapMode::Ownership => {
+                format!(" {} · {} ", author, commits)
+            }
+            _ => format!(" {} ", commits),
+        })
+    });
+
+    let mut block = Block::default()
+        .title(title)
         .borders(Borders::ALL)
         .border_style(if focused {
             theme::focused_border()
         } else {
             theme::unfocused_border()
         });
+    if let Some(details) = selected_heat {
+        block = block.title_bottom(Line::styled(details, theme::dimmed()));
+    }
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -585,6 +782,7 @@ pub fn render_file_tree(
     // Now get the flat view
     let flat = state.flat_view().to_vec(); // Clone to avoid borrow issues
     let flat_len = flat.len();
+    let overlay = state.heat_overlay.as_ref();
 
     let lines: Vec<Line> = flat
         .iter()
@@ -593,7 +791,7 @@ pub fn render_file_tree(
         .take(visible_height)
         .map(|(i, entry)| {
             let is_selected = i == selected;
-            render_entry(entry, is_selected, inner.width as usize)
+            render_entry(entry, is_selected, inner.width as usize, overlay)
         })
         .collect();
 
@@ -620,7 +818,12 @@ pub fn render_file_tree(
 }
 
 /// Render a single tree entry
-fn render_entry(entry: &FlatEntry, is_selected: bool, width: usize) -> Line<'static> {
+fn render_entry(
+    entry: &FlatEntry,
+    is_selected: bool,
+    width: usize,
+    overlay: Option<&HeatOverlay>,
+) -> Line<'static> {
     let indent = "  ".repeat(entry.depth);
 
     // Icon with nice Unicode symbols
@@ -699,22 +902,75 @@ fn render_entry(entry: &FlatEntry, is_selected: bool, width: usize) -> Line<'sta
         }
     };
 
+    // Heat cell (2 columns) when an overlay is active
+    let heat_span = overlay.map(|overlay| heat_cell(&entry.path, overlay));
+
     // Calculate available width for name using unicode width
-    // Format: status (1) + ">" (1) + " " (1) + indent + icon (1) + " " (1) + name
-    let fixed_width = 1 + 1 + 1 + indent.width() + 1 + 1;
+    // Format: status (1) + ">" (1) + " " (1) + [heat (2)] + indent + icon (1) + " " (1) + name
+    let heat_width = if heat_span.is_some() { 2 } else { 0 };
+    let fixed_width = 1 + 1 + 1 + heat_width + indent.width() + 1 + 1;
     let max_name_width = width.saturating_sub(fixed_width);
 
     // Truncate name if needed (using unicode width)
     let display_name = truncate_width(&entry.name, max_name_width);
 
-    Line::from(vec![
+    let mut spans = vec![
         Span::styled(status_indicator, status_style),
         Span::styled(marker, marker_style),
         Span::raw(" "),
+    ];
+    spans.extend(heat_span);
+    spans.extend([
         Span::raw(indent),
         Span::styled(format!("{} ", icon), icon_style),
         Span::styled(display_name, name_style),
-    ])
+    ]);
+    Line::from(spans)
+}
+
+/// Bar glyphs for churn intensity, coolest first
+const CHURN_BARS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+/// Render the heat cell for a path
+fn heat_cell(path: &Path, overlay: &HeatOverlay) -> Span<'static> {
+    let Some(heat) = overlay.data.files.get(path).filter(|h| h.commits > 0) else {
+        return Span::styled("· ", theme::dimmed());
+    };
+
+    match overlay.mode {
+        HeatMapMode::Off => Span::raw("  "),
+        HeatMapMode::Churn => {
+            let max = overlay.data.max_commits.max(1);
+            // Directories aggregate children and may exceed the file maximum
+            let percent = heat.commits.min(max) * 100 / max;
+            let idx = (percent * (CHURN_BARS.len() - 1) + 50) / 100;
+            let color = if percent >= 75 {
+                theme::error_color()
+            } else if percent >= 50 {
+                theme::accent_tertiary()
+            } else if percent >= 25 {
+                theme::warning_color()
+            } else {
+                theme::success_color()
+            };
+            Span::styled(format!("{} ", CHURN_BARS[idx]), Style::default().fg(color))
+        }
+        HeatMapMode::Ownership => {
+            let rank = heat
+                .primary_author
+                .as_deref()
+                .and_then(|a| overlay.data.author_rank(a));
+            let color = match rank {
+                Some(0) => theme::accent_primary(),
+                Some(1) => theme::accent_secondary(),
+                Some(2) => theme::accent_tertiary(),
+                Some(3) => theme::success_color(),
+                Some(4) => theme::warning_color(),
+                _ => theme::text_muted_color(),
+            };
+            Span::styled("■ ", Style::default().fg(color))
+        }
+    }
 }
 
 /// Get icon for file based on extension (Unicode symbols, no emoji)
@@ -797,3 +1053,54 @@ fn get_file_icon(name: &str) -> &'static str {
         _ => "◦",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "\x1eAlice\n\nsrc/main.rs\nsrc/lib.rs\n\x1eBob\n\nsrc/main.rs\n\x1eAlice\n\nsrc/main.rs\nREADME.md\n";
+
+    #[test]
+    fn test_heat_map_counts_files_and_directories() {
+        let data = HeatMapData::from_git_log(LOG, 30);
+
+        assert_eq!(data.window_days, 30);
+        assert_eq!(data.files[Path::new("src/main.rs")].commits, 3);
+        assert_eq!(data.files[Path::new("src/lib.rs")].commits, 1);
+        assert_eq!(data.files[Path::new("README.md")].commits, 1);
+        // Directory counts each commit once, even when several children changed
+        assert_eq!(data.files[Path::new("src")].commits, 3);
+        assert_eq!(data.max_commits, 3);
+    }
+
+    #[test]
+    fn test_heat_map_ownership() {
+        let data = HeatMapData::from_git_log(LOG, 30);
+
+        assert_eq!(data.authors, vec!["Alice".to_string(), "Bob".to_string()]);
+        assert_eq!(
+            data.files[Path::new("src/main.rs")]
+                .primary_author
+                .as_deref(),
+            Some("Alice")
+        );
+        assert_eq!(data.author_rank("Bob"), Some(1));
+        assert_eq!(data.author_rank("Carol"), None);
+    }
+
+    #[test]
+    fn test_heat_overlay_mode() {
+        let mut tree = FileTreeState::new();
+        assert_eq!(tree.heat_map_mode(), HeatMapMode::Off);
+
+        let data = Arc::new(HeatMapData::from_git_log(LOG, 30));
+        tree.set_heat_overlay(HeatMapMode::Churn, Some(Arc::clone(&data)));
+        assert_eq!(tree.heat_map_mode(), HeatMapMode::Churn);
+
+        // Without data there is nothing to draw
+        tree.set_heat_overlay(HeatMapMode::Ownership, None);
+        assert_eq!(tree.heat_map_mode(), HeatMapMode::Off);
+
+        assert_eq!(HeatMapMode::Ownership.next(), HeatMapMode::Off);
+    }
+}
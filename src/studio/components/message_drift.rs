@@ -0,0 +1,329 @@
+//! Message drift view for amends
+//!
+//! Shows the commit message being replaced next to its replacement with a
+//! word-level diff: removed words are struck through on the left, added words
+//! highlighted on the right. Earlier amends on the branch are listed below, so
+//! message churn stays visible while deciding on the next one.
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::companion::MessageRevision;
+use crate::studio::theme;
+use crate::studio::utils::truncate_width;
+use unicode_width::UnicodeWidthStr;
+
+/// Most earlier amends listed under the comparison
+const MAX_LISTED_REVISIONS: usize = 5;
+
+/// Size limit for the diff table; bigger messages show as wholly replaced
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+/// One token of a word- or line-level diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordChange<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Split text into words, whitespace runs and single newlines
+fn tokens(text: &str) -> Vec<&str> {
+    #[derive(PartialEq)]
+    enum Class {
+        Newline,
+        Space,
+        Word,
+    }
+    let class = |c: char| {
+        if c == '\n' {
+            Class::Newline
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Word
+        }
+    };
+
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut current: Option<Class> = None;
+    for (i, c) in text.char_indices() {
+        let next = class(c);
+        if current
+            .as_ref()
+            .is_some_and(|cur| *cur != next || next == Class::Newline)
+        {
+            tokens.push(&text[start..i]);
+            start = i;
+        }
+        current = Some(next);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+/// Word-level diff of `old` against `new`, like `git diff --word-diff`
+///
+/// Uses a longest-common-subsequence table over word, whitespace and newline
+/// tokens. Commit messages are short; anything too big for the table is
+/// reported as wholly replaced.
+pub fn word_diff<'a>(old: &'a str, new: &'a str) -> Vec<WordChange<'a>> {
+    lcs_diff(&tokens(old), &tokens(new))
+}
+
+/// Line-level diff of `old` against `new`, for content longer than a
+/// commit message
+pub fn line_diff<'a>(old: &'a str, new: &'a str) -> Vec<WordChange<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    lcs_diff(&old, &new)
+}
+
+fn lcs_diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<WordChange<'a>> {
+    if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+        return old
+            .iter()
+            .copied()
+            .map(WordChange::Removed)
+            .chain(new.iter().copied().map(WordChange::Added))
+            .collect();
+    }
+
+    // lcs[i][j]: common tokens between old[i..] and new[j..]
+    let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            changes.push(WordChange::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            changes.push(WordChange::Removed(old[i]));
+            i += 1;
+        } else {
+            changes.push(WordChange::Added(new[j]));
+            j += 1;
+        }
+    }
+    changes.extend(old[i..].iter().copied().map(WordChange::Removed));
+    changes.extend(new[j..].iter().copied().map(WordChange::Added));
+    changes
+}
+
+/// Lines for one side of the comparison: the original (`revised == false`)
+/// with removals marked, or the revision with additions marked
+fn side_lines(changes: &[WordChange<'_>], revised: bool) -> Vec<Line<'static>> {
+    let same = Style::default().fg(theme::text_primary_color());
+    let removed = Style::default()
+        .fg(theme::error_color())
+        .add_modifier(Modifier::CROSSED_OUT);
+    let added = Style::default()
+        .fg(theme::success_color())
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = Vec::new();
+    let mut spans = Vec::new();
+    for change in changes {
+        let (text, style) = match (*change, revised) {
+            (WordChange::Same(text), _) => (text, same),
+            (WordChange::Removed(text), false) => (text, removed),
+            (WordChange::Added(text), true) => (text, added),
+            _ => continue,
+        };
+        if text == "\n" {
+            lines.push(Line::from(std::mem::take(&mut spans)));
+        } else {
+            spans.push(Span::styled(text.to_string(), style));
+        }
+    }
+    lines.push(Line::from(spans));
+    lines
+}
+
+/// One line per earlier amend: hashes, age and the subject change
+fn revision_lines(revisions: &[MessageRevision], width: usize) -> Vec<Line<'static>> {
+    let now = chrono::Utc::now();
+    revisions
+        .iter()
+        .rev()
+        .take(MAX_LISTED_REVISIONS)
+        .map(|revision| {
+            let age = now - revision.recorded_at;
+            let age = if age.num_days() > 0 {
+                format!("{}d", age.num_days())
+            } else if age.num_hours() > 0 {
+                format!("{}h", age.num_hours())
+            } else {
+                format!("{}m", age.num_minutes())
+            };
+            let short = |hash: &str| hash.chars().take(7).collect::<String>();
+            let subject = |message: &str| message.lines().next().unwrap_or_default().to_string();
+            let change = if revision.subject_changed() {
+                format!(
+                    "{} → {}",
+                    subject(&revision.original),
+                    subject(&revision.revised)
+                )
+            } else {
+                format!("{} (body only)", subject(&revision.revised))
+            };
+            let prefix = format!(
+                "{} → {} {age:>3} ",
+                short(&revision.original_commit),
+                short(&revision.amended_commit)
+            );
+            let remaining = width.saturating_sub(prefix.width());
+            Line::from(vec![
+                Span::styled(prefix, theme::commit_hash()),
+                Span::styled(
+                    truncate_width(&change, remaining),
+                    Style::default().fg(theme::text_secondary_color()),
+                ),
+            ])
+        })
+        .collect()
+}
+
+/// Render the original message beside its replacement, with the branch's
+/// earlier amends underneath
+pub fn render_message_drift(
+    frame: &mut Frame,
+    area: Rect,
+    original: &str,
+    revised: &str,
+    revisions: &[MessageRevision],
+    focused: bool,
+) {
+    let title = if revisions.is_empty() {
+        " Message Drift ".to_string()
+    } else {
+        format!(
+            " Message Drift · amended {}× on this branch ",
+            revisions.len()
+        )
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(if focused {
+            theme::focused_border()
+        } else {
+            theme::unfocused_border()
+        });
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.height == 0 || inner.width == 0 {
+        return;
+    }
+
+    let listed = revisions.len().min(MAX_LISTED_REVISIONS);
+    let history_height = if listed == 0 {
+        0
+    } else {
+        u16::try_from(listed + 1).unwrap_or(u16::MAX)
+    };
+    let [compare, history] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(history_height)]).areas(inner);
+    let [left, right] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(compare);
+
+    let changes = word_diff(original.trim_end(), revised.trim_end());
+    let heading = |text: &'static str| {
+        Line::from(Span::styled(
+            text,
+            Style::default()
+                .fg(theme::text_muted_color())
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    for (side, label, is_revised) in [(left, "Original", false), (right, "Revised", true)] {
+        let mut lines = vec![heading(label)];
+        lines.extend(side_lines(&changes, is_revised));
+        let block = if is_revised {
+            Block::default()
+        } else {
+            Block::default()
+                .borders(Borders::RIGHT)
+                .border_style(theme::unfocused_border())
+        };
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            side,
+        );
+    }
+
+    if listed > 0 {
+        let mut lines = vec![heading("Earlier amends on this branch")];
+        lines.extend(revision_lines(revisions, usize::from(history.width)));
+        frame.render_widget(Paragraph::new(lines), history);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_diff_marks_changed_words() {
+        let changes = word_diff(
+            "Fix parser crash\n\nHandle empty input",
+            "Fix TOML parser crash\n\nHandle empty files",
+        );
+        let removed: Vec<&str> = changes
+            .iter()
+            .filter_map(|c| match c {
+                WordChange::Removed(t) => Some(*t),
+                _ => None,
+            })
+            .collect();
+        let added: Vec<&str> = changes
+            .iter()
+            .filter_map(|c| match c {
+                WordChange::Added(t) => Some(*t),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(removed, ["input"]);
+        assert_eq!(added, ["TOML", " ", "files"]);
+
+        let original = side_lines(&changes, false);
+        let revised = side_lines(&changes, true);
+        assert_eq!(original.len(), 3);
+        assert_eq!(revised[0].to_string(), "Fix TOML parser crash");
+        assert_eq!(original[2].to_string(), "Handle empty input");
+        let lines = line_diff("# Title\nold line\nkept", "# Title\nnew line\nkept");
+        assert_eq!(
+            lines,
+            [
+                WordChange::Same("# Title"),
+                WordChange::Removed("old line"),
+                WordChange::Added("new line"),
+                WordChange::Same("kept"),
+            ]
+        );
+        assert!(
+            word_diff("same", "same")
+                .iter()
+                .all(|c| matches!(c, WordChange::Same(_)))
+        );
+    }
+}
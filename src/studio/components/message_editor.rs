@@ -2,16 +2,21 @@
 //!
 //! Text editor for commit messages using tui-textarea.
 
+use crate::spellcheck::{Misspelling, SpellChecker};
 use crate::studio::theme;
-use crate::studio::utils::truncate_width;
-use crate::types::GeneratedMessage;
+use crate::studio::utils::{referenced_paths, truncate_width};
+use crate::types::{CommitStyle, GeneratedMessage, StyleViolation};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
 use tui_textarea::TextArea;
+use unicode_width::UnicodeWidthStr;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Message Editor State
@@ -29,6 +34,18 @@ pub struct MessageEditorState {
     edit_mode: bool,
     /// Original message (for reset)
     original_message: String,
+    /// Spell checker, if enabled
+    spell_checker: Option<Arc<SpellChecker>>,
+    /// Suspect words in the current text
+    misspellings: Vec<Misspelling>,
+    /// House style rules for commit messages
+    commit_style: CommitStyle,
+    /// Style rules the current text breaks
+    style_violations: Vec<StyleViolation>,
+    /// Modified and untracked files not yet staged
+    unstaged_files: Vec<PathBuf>,
+    /// Unstaged files the current text mentions
+    referenced_unstaged: Vec<PathBuf>,
 }
 
 impl Default for MessageEditorState {
@@ -50,9 +67,89 @@ impl MessageEditorState {
             selected_message: 0,
             edit_mode: false,
             original_message: String::new(),
+            spell_checker: None,
+            misspellings: Vec::new(),
+            commit_style: CommitStyle::default(),
+            style_violations: Vec::new(),
+            unstaged_files: Vec::new(),
+            referenced_unstaged: Vec::new(),
         }
     }
 
+    /// Enable or disable spell-checking
+    pub fn set_spell_checker(&mut self, checker: Option<Arc<SpellChecker>>) {
+        self.spell_checker = checker;
+        self.refresh_spelling();
+    }
+
+    /// Suspect words in the current text
+    pub fn misspellings(&self) -> &[Misspelling] {
+        &self.misspellings
+    }
+
+    /// Set the commit style rules checked while editing
+    pub fn set_commit_style(&mut self, style: CommitStyle) {
+        self.commit_style = style;
+        self.refresh_spelling();
+    }
+
+    /// Style rules the current text breaks
+    pub fn style_violations(&self) -> &[StyleViolation] {
+        &self.style_violations
+    }
+
+    /// Set the files the message may mention without them being staged
+    pub fn set_unstaged_files(&mut self, files: Vec<PathBuf>) {
+        self.unstaged_files = files;
+        self.refresh_spelling();
+    }
+
+    /// Unstaged files the current text mentions
+    pub fn referenced_unstaged(&self) -> &[PathBuf] {
+        &self.referenced_unstaged
+    }
+
+    /// Apply every suggested correction, returning how many words changed
+    pub fn fix_spelling(&mut self) -> usize {
+        let Some(checker) = self.spell_checker.clone() else {
+            return 0;
+        };
+        let (fixed, count) = checker.fix(&self.get_message());
+        if count > 0 {
+            let edit_mode = self.edit_mode;
+            self.set_text(&fixed);
+            self.edit_mode = edit_mode;
+        }
+        count
+    }
+
+    /// Re-run the spell checker, style rules and file references,
+    /// highlighting suspect words while editing
+    fn refresh_spelling(&mut self) {
+        self.style_violations = self.commit_style.check(&self.get_message());
+        self.referenced_unstaged = referenced_paths(&self.get_message(), &self.unstaged_files);
+        self.misspellings = self
+            .spell_checker
+            .as_ref()
+            .map(|checker| checker.check(&self.get_message()))
+            .unwrap_or_default();
+
+        let words: HashSet<String> = self
+            .misspellings
+            .iter()
+            .map(|m| regex::escape(&m.word))
+            .collect();
+        let pattern = if words.is_empty() {
+            String::new()
+        } else {
+            let mut words: Vec<_> = words.into_iter().collect();
+            words.sort();
+            format!(r"\b({})\b", words.join("|"))
+        };
+        // Escaped words always form a valid pattern; an empty one clears it
+        let _ = self.textarea.set_search_pattern(pattern);
+    }
+
     /// Set generated messages (replaces all existing)
     pub fn set_messages(&mut self, messages: Vec<GeneratedMessage>) {
         self.generated_messages = messages;
@@ -81,11 +178,22 @@ impl MessageEditorState {
         self.original_message.clone_from(&full_message);
 
         // Clear and set new content
-        self.textarea = TextArea::from(full_message.lines().map(String::from).collect::<Vec<_>>());
+        self.set_text(&full_message);
+    }
+
+    /// Replace the textarea content
+    fn set_text(&mut self, text: &str) {
+        self.textarea = TextArea::from(text.lines().map(String::from).collect::<Vec<_>>());
         self.textarea
             .set_cursor_line_style(Style::default().bg(theme::bg_highlight_color()));
         self.textarea
             .set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
+        self.textarea.set_search_style(
+            Style::default()
+                .fg(theme::error_color())
+                .add_modifier(Modifier::UNDERLINED),
+        );
+        self.refresh_spelling();
     }
 
     /// Get current message count
@@ -141,16 +249,8 @@ impl MessageEditorState {
 
     /// Reset to original message
     pub fn reset(&mut self) {
-        self.textarea = TextArea::from(
-            self.original_message
-                .lines()
-                .map(String::from)
-                .collect::<Vec<_>>(),
-        );
-        self.textarea
-            .set_cursor_line_style(Style::default().bg(theme::bg_highlight_color()));
-        self.textarea
-            .set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
+        let original = self.original_message.clone();
+        self.set_text(&original);
         self.edit_mode = false;
     }
 
@@ -159,6 +259,9 @@ impl MessageEditorState {
         self.generated_messages.clear();
         self.selected_message = 0;
         self.original_message.clear();
+        self.misspellings.clear();
+        self.style_violations.clear();
+        self.referenced_unstaged.clear();
         self.textarea = TextArea::default();
         self.textarea
             .set_cursor_line_style(Style::default().bg(theme::bg_highlight_color()));
@@ -189,7 +292,9 @@ impl MessageEditorState {
             true
         } else {
             // Forward to textarea
-            self.textarea.input(key);
+            if self.textarea.input(key) {
+                self.refresh_spelling();
+            }
             true
         }
     }
@@ -240,9 +345,14 @@ pub fn render_message_editor(
     status_message: Option<&str>,
 ) {
     // Build title with message count indicator
+    let variant = state
+        .current_generated()
+        .and_then(|msg| msg.variant.as_deref())
+        .map(|label| format!(" · {label}"))
+        .unwrap_or_default();
     let count_indicator = if state.message_count() > 1 {
         format!(
-            " ({}/{})",
+            " ({}/{}{variant})",
             state.selected_index() + 1,
             state.message_count()
         )
@@ -251,8 +361,16 @@ pub fn render_message_editor(
     };
 
     let mode_indicator = if state.is_editing() { " [EDITING]" } else { "" };
+    let style_indicator = match state.style_violations().len() {
+        0 => String::new(),
+        1 => " [1 style issue]".to_string(),
+        n => format!(" [{n} style issues]"),
+    };
 
-    let full_title = format!(" {}{}{} ", title, count_indicator, mode_indicator);
+    let full_title = format!(
+        " {}{}{}{} ",
+        title, count_indicator, mode_indicator, style_indicator
+    );
 
     let block = Block::default()
         .title(full_title)
@@ -331,34 +449,34 @@ fn render_message_view(frame: &mut Frame, area: Rect, state: &MessageEditorState
 
     let width = area.width as usize;
     let mut lines = Vec::new();
+    let suspects: HashSet<&str> = state
+        .misspellings()
+        .iter()
+        .map(|m| m.word.as_str())
+        .collect();
 
     // Emoji and title (truncated to fit)
     let emoji = msg.emoji.as_deref().unwrap_or("");
     let title_width = if emoji.is_empty() {
         width
     } else {
-        width.saturating_sub(emoji.chars().count() + 1)
+        width.saturating_sub(emoji.width() + 1)
     };
     let title = truncate_width(&msg.title, title_width);
 
+    let title_style = Style::default()
+        .fg(theme::text_primary_color())
+        .add_modifier(Modifier::BOLD);
     if emoji.is_empty() {
-        lines.push(Line::from(Span::styled(
-            title,
-            Style::default()
-                .fg(theme::text_primary_color())
-                .add_modifier(Modifier::BOLD),
+        lines.push(Line::from(highlight_suspects(
+            &title,
+            title_style,
+            &suspects,
         )));
     } else {
-        lines.push(Line::from(vec![
-            Span::styled(emoji, Style::default()),
-            Span::raw(" "),
-            Span::styled(
-                title,
-                Style::default()
-                    .fg(theme::text_primary_color())
-                    .add_modifier(Modifier::BOLD),
-            ),
-        ]));
+        let mut spans = vec![Span::styled(emoji, Style::default()), Span::raw(" ")];
+        spans.extend(highlight_suspects(&title, title_style, &suspects));
+        lines.push(Line::from(spans));
     }
 
     // Empty line
@@ -367,12 +485,72 @@ fn render_message_view(frame: &mut Frame, area: Rect, state: &MessageEditorState
     // Body (truncated lines)
     for body_line in msg.message.lines() {
         let truncated = truncate_width(body_line, width);
-        lines.push(Line::from(Span::styled(
-            truncated,
+        lines.push(Line::from(highlight_suspects(
+            &truncated,
             Style::default().fg(theme::text_primary_color()),
+            &suspects,
         )));
     }
 
+    // Spelling suggestions
+    if !state.misspellings().is_empty() {
+        let suggestions: Vec<String> = state
+            .misspellings()
+            .iter()
+            .map(|m| format!("{} → {}", m.word, m.suggestion))
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Spelling: ", Style::default().fg(theme::warning_color())),
+            Span::styled(
+                truncate_width(&suggestions.join(", "), width.saturating_sub(18)),
+                theme::dimmed(),
+            ),
+            Span::styled("  z", Style::default().fg(theme::accent_secondary())),
+            Span::styled(" fix", theme::dimmed()),
+        ]));
+    }
+
+    // House style violations
+    if !state.style_violations().is_empty() {
+        let violations: Vec<String> = state
+            .style_violations()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        if state.misspellings().is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(vec![
+            Span::styled("Style: ", Style::default().fg(theme::warning_color())),
+            Span::styled(
+                truncate_width(&violations.join("; "), width.saturating_sub(7)),
+                theme::dimmed(),
+            ),
+        ]));
+    }
+
+    // Files the message mentions that won't be in the commit
+    if !state.referenced_unstaged().is_empty() {
+        let files: Vec<String> = state
+            .referenced_unstaged()
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect();
+        if state.misspellings().is_empty() && state.style_violations().is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(vec![
+            Span::styled("Unstaged: ", Style::default().fg(theme::warning_color())),
+            Span::styled(
+                truncate_width(&files.join(", "), width.saturating_sub(18)),
+                theme::dimmed(),
+            ),
+            Span::styled("  s", Style::default().fg(theme::accent_secondary())),
+            Span::styled(" stage", theme::dimmed()),
+        ]));
+    }
+
     // Help hints at bottom
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
@@ -388,13 +566,56 @@ fn render_message_view(frame: &mut Frame, area: Rect, state: &MessageEditorState
     frame.render_widget(paragraph, area);
 }
 
+/// Split text into spans, underlining words flagged by the spell checker
+fn highlight_suspects(text: &str, base: Style, suspects: &HashSet<&str>) -> Vec<Span<'static>> {
+    if suspects.is_empty() {
+        return vec![Span::styled(text.to_string(), base)];
+    }
+
+    let suspect_style = base
+        .fg(theme::error_color())
+        .add_modifier(Modifier::UNDERLINED);
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut word = String::new();
+
+    let flush_word = |word: &mut String, plain: &mut String, spans: &mut Vec<Span<'static>>| {
+        if word.is_empty() {
+            return;
+        }
+        if suspects.contains(word.as_str()) {
+            if !plain.is_empty() {
+                spans.push(Span::styled(std::mem::take(plain), base));
+            }
+            spans.push(Span::styled(std::mem::take(word), suspect_style));
+        } else {
+            plain.push_str(word);
+            word.clear();
+        }
+    };
+
+    for c in text.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+        } else {
+            flush_word(&mut word, &mut plain, &mut spans);
+            plain.push(c);
+        }
+    }
+    flush_word(&mut word, &mut plain, &mut spans);
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
 /// Render a compact message preview (for lists)
 pub fn render_message_preview(msg: &GeneratedMessage, width: usize) -> Line<'static> {
     let emoji = msg.emoji.as_deref().unwrap_or("");
     let title_width = if emoji.is_empty() {
         width
     } else {
-        width.saturating_sub(emoji.chars().count() + 1)
+        width.saturating_sub(emoji.width() + 1)
     };
     let title = truncate_width(&msg.title, title_width);
 
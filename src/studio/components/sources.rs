@@ -0,0 +1,60 @@
+//! Sources footer for agent answers
+//!
+//! One line per cited file or commit: `✓` when it checked out against the
+//! repository, `⚠` with the reason when it didn't.
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::studio::theme;
+use crate::studio::utils::truncate_width;
+use crate::types::CheckedCitation;
+
+/// Footer lines for `sources`, highlighting `selected`
+///
+/// Returns nothing when there are no sources, so callers can append the
+/// result unconditionally.
+pub fn source_lines(
+    sources: &[CheckedCitation],
+    selected: Option<usize>,
+    width: usize,
+) -> Vec<Line<'static>> {
+    if sources.is_empty() {
+        return Vec::new();
+    }
+    let unverified = sources.iter().filter(|s| !s.is_verified()).count();
+    let mut header = format!("Sources ({})", sources.len());
+    if unverified > 0 {
+        header.push_str(&format!(" · {unverified} unverified"));
+    }
+    let mut lines = vec![Line::from(Span::styled(
+        header,
+        Style::default()
+            .fg(theme::text_muted_color())
+            .add_modifier(Modifier::BOLD),
+    ))];
+
+    for (i, source) in sources.iter().enumerate() {
+        let (marker, marker_style) = if source.is_verified() {
+            ("✓ ", Style::default().fg(theme::success_color()))
+        } else {
+            ("⚠ ", Style::default().fg(theme::warning_color()))
+        };
+        let mut text = source.citation.to_string();
+        if let Some(problem) = &source.problem {
+            text.push_str(&format!(" — {problem}"));
+        } else if let Some(summary) = &source.summary {
+            text.push_str(&format!(" {summary}"));
+        }
+        let text_style = if Some(i) == selected {
+            theme::selected()
+        } else {
+            Style::default().fg(theme::text_secondary_color())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(marker, marker_style),
+            Span::styled(truncate_width(&text, width.saturating_sub(2)), text_style),
+        ]));
+    }
+    lines
+}
@@ -11,7 +11,8 @@ use std::time::Instant;
 
 use crossterm::event::{KeyEvent, MouseEvent};
 
-use crate::types::GeneratedMessage;
+use crate::outputs::Artifact;
+use crate::types::{GeneratedMessage, SuggestedVersion};
 
 use super::state::{Mode, PanelId};
 
@@ -172,6 +173,82 @@ pub enum StudioEvent {
     /// Toggle between file log and global log
     ToggleGlobalLog,
 
+    /// Heat map data (churn/ownership) loaded for the explore file tree
+    HeatMapLoaded {
+        data: std::sync::Arc<crate::studio::components::HeatMapData>,
+    },
+
+    /// Activity statistics loaded for Dashboard mode
+    DashboardLoaded {
+        data: std::sync::Arc<crate::studio::state::DashboardData>,
+    },
+
+    /// Provider model list fetched for the model picker
+    ModelsLoaded {
+        provider: String,
+        result: Result<Vec<String>, String>,
+    },
+
+    /// Pickaxe search finished for `query`
+    PickaxeLoaded {
+        query: String,
+        kind: crate::git::PickaxeKind,
+        result: Result<Vec<crate::git::PickaxeHit>, String>,
+    },
+
+    /// Iris answered a semantic code search for `query`
+    SemanticSearchLoaded {
+        query: String,
+        result: Result<String, String>,
+    },
+
+    /// Settings connection test finished (latency in ms or error)
+    ConnectionTested { result: Result<u128, String> },
+
+    /// GitHub pull request opened (number and URL) or failed
+    PullRequestCreated {
+        result: Result<(u64, String), String>,
+    },
+
+    /// CI checks read for the pushed head of `head`
+    CiStatusLoaded {
+        head: String,
+        result: Result<crate::integrations::ci::CiStatus, String>,
+    },
+
+    /// Review posted to a pull request (number and review URL) or failed
+    ReviewPosted {
+        result: Result<(u64, String), String>,
+    },
+
+    /// Iris described the changes about to be stashed, or failed to
+    StashDescribed { result: Result<String, String> },
+
+    /// Iris recapped recent work on `branch` after an idle gap, or failed to
+    RecapReady {
+        branch: String,
+        result: Result<String, String>,
+    },
+
+    /// Iris suggested a rebase todo list, or failed to
+    RebaseSuggested { result: Result<String, String> },
+
+    /// Architecture overview written (or found in the cache) at `path`
+    RepoOverviewReady { result: Result<PathBuf, String> },
+
+    /// Every line of `file` blamed; `explaining` if Iris's reasons will follow
+    FileBlameLoaded {
+        file: PathBuf,
+        result: Result<Vec<crate::git::BlameRegion>, String>,
+        explaining: bool,
+    },
+
+    /// Iris explained the commits in the whole-file blame of `file`
+    FileBlameExplained {
+        file: PathBuf,
+        result: Result<String, String>,
+    },
+
     // ─────────────────────────────────────────────────────────────────────────
     // Modal Events
     // ─────────────────────────────────────────────────────────────────────────
@@ -305,8 +382,11 @@ pub enum AgentResult {
     /// Changelog generated
     ChangelogContent(String),
 
-    /// Release notes generated
-    ReleaseNotesContent(String),
+    /// Release notes generated, with the suggested next version
+    ReleaseNotesContent {
+        content: String,
+        suggested_version: Option<SuggestedVersion>,
+    },
 
     /// Chat response
     ChatResponse(String),
@@ -378,6 +458,23 @@ pub enum DataType {
     ReleaseNotesCommits,
     /// Explore mode file tree (lazy-loaded on mode switch)
     ExploreFiles,
+    /// Stash list and the selected stash's diff
+    Stashes,
+    /// Rebase plan for the current branch, discarding any edits
+    RebasePlan,
+    /// Diff of the commit selected in the rebase plan
+    RebaseDiff,
+}
+
+/// What to do with an existing stash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StashAction {
+    /// Apply it and keep it
+    Apply,
+    /// Apply it and remove it from the list
+    Pop,
+    /// Remove it without applying
+    Drop,
 }
 
 /// Modal types
@@ -435,6 +532,9 @@ pub enum SideEffect {
     /// Spawn an agent task
     SpawnAgent { task: AgentTask },
 
+    /// Generate in a mode if its auto-generate trigger allows
+    AutoGenerate(Mode),
+
     /// Load data asynchronously
     LoadData {
         data_type: DataType,
@@ -454,9 +554,24 @@ pub enum SideEffect {
     /// Unstage all files
     GitUnstageAll,
 
+    /// Stage several files at once
+    GitStageFiles(Vec<PathBuf>),
+
+    /// Soft-reset the last commit git-iris made
+    UndoLastCommit,
+
     /// Save settings to config
     SaveSettings,
 
+    /// Save the preset in the preset editor to its file
+    SavePreset,
+
+    /// Save the current mode's generated content to its `[outputs]` path
+    SaveArtifact(Artifact),
+
+    /// Rebuild the agent service from Studio's running config
+    ReconfigureAgent,
+
     /// Refresh git status
     RefreshGitStatus,
 
@@ -469,6 +584,15 @@ pub enum SideEffect {
     /// Execute git commit --amend
     ExecuteAmend { message: String },
 
+    /// Queue a commit of the changes staged to `files`
+    QueueCommit {
+        message: String,
+        files: Vec<PathBuf>,
+    },
+
+    /// Make the queued commits one after another
+    RunCommitQueue,
+
     /// Show notification (if needs timing/animation)
     #[allow(dead_code)] // Kept for future use - handled in executor but not yet constructed
     ShowNotification {
@@ -484,6 +608,9 @@ pub enum SideEffect {
     /// Quit the application
     Quit,
 
+    /// Stop a running generation and the tasks helping it
+    CancelTask(TaskType),
+
     /// Gather blame info for a file region and spawn semantic blame agent
     GatherBlameAndSpawnAgent {
         file: PathBuf,
@@ -496,6 +623,102 @@ pub enum SideEffect {
 
     /// Load global commit log (not file-specific)
     LoadGlobalLog,
+
+    /// Find the commits that added or removed a string or regex
+    PickaxeSearch {
+        query: String,
+        kind: crate::git::PickaxeKind,
+    },
+
+    /// Ask Iris where the code answering a plain-language question is
+    SemanticSearch { query: String },
+
+    /// Open the repository's architecture overview, writing it if needed
+    ExplainRepo,
+
+    /// Blame every line of a file, then have Iris explain each commit
+    LoadFileBlame(PathBuf),
+
+    /// Compute churn/ownership heat map data from recent history
+    LoadHeatMap { window_days: u32 },
+
+    /// Compute Dashboard activity statistics from recent history
+    LoadDashboard { window_days: u32 },
+
+    /// Export chat, artifacts and diff as a shareable session report
+    ExportSession,
+
+    /// Load archived sessions and open the history search modal
+    OpenHistorySearch,
+
+    /// Show the token breakdown of the latest generation
+    ShowContextReport,
+
+    /// Fetch the provider's model list for the model picker
+    LoadModels {
+        provider: String,
+        /// Key entered in settings but not yet saved
+        api_key: Option<String>,
+        /// Bypass the on-disk cache
+        refresh: bool,
+    },
+
+    /// Read the CI checks on the pushed head of PR mode's target branch
+    LoadCiStatus,
+
+    /// Open a pull request on GitHub from a generated description
+    CreatePullRequest {
+        /// PR description markdown; its first heading becomes the title
+        markdown: String,
+        /// Branch with the changes
+        head: String,
+        /// Branch to merge into
+        base: String,
+    },
+
+    /// Post review findings to the open pull request of `head` on GitHub
+    PostReview {
+        /// Branch whose pull request gets the review
+        head: String,
+        /// Revision the comments' line numbers refer to
+        to_ref: String,
+        /// Summary shown above the inline comments
+        body: String,
+        comments: Vec<crate::integrations::github::ReviewComment>,
+    },
+
+    /// Apply, pop or drop a stash
+    RunStashAction {
+        action: StashAction,
+        /// Position in the stash list
+        index: usize,
+        /// Stash commit id, to catch a list that changed since it was read
+        id: String,
+    },
+
+    /// Stash local changes
+    CreateStash {
+        /// Stash message; git's default is used when `None`
+        message: Option<String>,
+        include_untracked: bool,
+    },
+
+    /// Ask Iris to describe the changes a new stash would save
+    DescribeStash { include_untracked: bool },
+
+    /// Ask Iris to suggest a plan for the rebase
+    SuggestRebase,
+
+    /// Run the rebase plan
+    RunRebase,
+
+    /// Send a minimal completion to verify provider, key and model
+    TestConnection {
+        provider: String,
+        model: String,
+        /// Key entered in settings but not yet saved
+        api_key: Option<String>,
+    },
 }
 
 /// Blame information gathered from git
@@ -555,6 +778,21 @@ pub enum AgentTask {
     },
 }
 
+impl AgentTask {
+    /// Kind of task, for results and cancellation
+    pub fn task_type(&self) -> TaskType {
+        match self {
+            Self::Commit { .. } => TaskType::Commit,
+            Self::Review { .. } => TaskType::Review,
+            Self::PR { .. } => TaskType::PR,
+            Self::Changelog { .. } => TaskType::Changelog,
+            Self::ReleaseNotes { .. } => TaskType::ReleaseNotes,
+            Self::Chat { .. } => TaskType::Chat,
+            Self::SemanticBlame { .. } => TaskType::SemanticBlame,
+        }
+    }
+}
+
 /// Context for chat messages
 #[derive(Debug, Clone, Default)]
 pub struct ChatContext {
@@ -565,6 +803,8 @@ pub struct ChatContext {
     /// Diff summary for context
     #[allow(dead_code)] // Kept for future use - will provide diff context to chat
     pub diff_summary: Option<String>,
+    /// Explore selection expanded to its enclosing item and imports
+    pub code_context: Option<String>,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
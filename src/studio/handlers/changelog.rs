@@ -1,7 +1,9 @@
 //! Changelog mode key handling for Iris Studio
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::sync::Arc;
 
+use crate::outputs::Artifact;
 use crate::studio::events::SideEffect;
 use crate::studio::state::{Modal, PanelId, RefSelectorTarget, StudioState};
 
@@ -57,6 +59,7 @@ fn handle_commits_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
                 refs: state.get_branch_refs(),
                 selected: 0,
                 target: RefSelectorTarget::ChangelogFrom,
+                revision: None,
             });
             state.mark_dirty();
             vec![]
@@ -68,6 +71,7 @@ fn handle_commits_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
                 refs: state.get_branch_refs(),
                 selected: 0,
                 target: RefSelectorTarget::ChangelogTo,
+                revision: None,
             });
             state.mark_dirty();
             vec![]
@@ -201,9 +205,11 @@ fn handle_output_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
             }
             vec![]
         }
+        // Save to the configured [outputs] file
+        KeyCode::Char('w') => vec![SideEffect::SaveArtifact(Artifact::Changelog)],
         // Reset
         KeyCode::Char('R') => {
-            state.modes.changelog.changelog_content.clear();
+            state.modes.changelog.changelog_content = Arc::default();
             state.modes.changelog.changelog_scroll = 0;
             state.mark_dirty();
             vec![]
@@ -2,6 +2,7 @@
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+use crate::companion::DraftKind;
 use crate::studio::events::SideEffect;
 use crate::studio::state::{EmojiMode, Modal, PanelId, StudioState};
 
@@ -14,6 +15,39 @@ pub fn handle_commit_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffe
         return handle_editing_key(state, key);
     }
 
+    // Undo the last git-iris commit from any panel
+    if key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        state.modal = Some(Modal::Confirm {
+            message: "Undo the last git-iris commit? Its changes stay staged and its message \
+                      returns to the editor."
+                .to_string(),
+            action: "undo_commit".to_string(),
+        });
+        state.mark_dirty();
+        return vec![];
+    }
+
+    // Browse messages generated or edited earlier on this branch
+    if key.code == KeyCode::Char('H') {
+        state.open_message_history();
+        return vec![];
+    }
+
+    // Review and run the commit queue
+    if key.code == KeyCode::Char('Q') {
+        state.modes.commit.queue.move_selection(0);
+        state.modal = Some(Modal::CommitQueue);
+        state.mark_dirty();
+        return vec![];
+    }
+
+    // Switch the right panel between the message drift and the diff
+    if key.code == KeyCode::Char('D') && state.modes.commit.amend_mode {
+        state.modes.commit.show_drift = !state.modes.commit.show_drift;
+        state.mark_dirty();
+        return vec![];
+    }
+
     match state.focused_panel {
         PanelId::Left => handle_files_key(state, key),
         PanelId::Center => handle_message_key(state, key),
@@ -26,6 +60,10 @@ fn handle_editing_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
     if state.modes.commit.message_editor.handle_key(key) {
         // Sync editing state from component
         state.modes.commit.editing_message = state.modes.commit.message_editor.is_editing();
+        if !state.modes.commit.editing_message && state.modes.commit.message_editor.is_modified() {
+            let message = state.modes.commit.message_editor.get_message();
+            state.record_message_draft(&message, DraftKind::Edited);
+        }
         state.mark_dirty();
     }
     vec![]
@@ -288,27 +326,16 @@ fn handle_message_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
 
         // Toggle amend mode
         KeyCode::Char('A') => {
-            state.modes.commit.amend_mode = !state.modes.commit.amend_mode;
+            state.set_amend_mode(!state.modes.commit.amend_mode);
             if state.modes.commit.amend_mode {
-                // Load original message from HEAD
-                if let Some(repo) = &state.repo
-                    && let Ok(msg) = repo.get_head_commit_message()
-                {
-                    state.modes.commit.original_message = Some(msg);
-                }
                 state.notify(crate::studio::state::Notification::info(
-                    "Amend mode: ON - will replace previous commit".to_string(),
+                    "Amend mode: ON - editing HEAD's message; Enter amends it".to_string(),
                 ));
             } else {
-                state.modes.commit.original_message = None;
                 state.notify(crate::studio::state::Notification::info(
                     "Amend mode: OFF".to_string(),
                 ));
             }
-            // Clear messages when toggling amend mode (they need regeneration)
-            state.modes.commit.messages.clear();
-            state.modes.commit.message_editor.clear();
-            state.mark_dirty();
             vec![]
         }
 
@@ -324,6 +351,24 @@ fn handle_message_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
             }
         }
 
+        // Queue the message with the staged files, to commit later with the rest
+        KeyCode::Char('b') => {
+            let message = state.modes.commit.message_editor.get_message();
+            let files = state.git_status.staged_files.clone();
+            let warning = if message.trim().is_empty() {
+                "Write or generate a message before queueing"
+            } else if files.is_empty() {
+                "Stage the files for this commit before queueing"
+            } else if state.modes.commit.queue.running {
+                "The commit queue is running"
+            } else {
+                return vec![SideEffect::QueueCommit { message, files }];
+            };
+            state.notify(crate::studio::state::Notification::warning(warning));
+            state.mark_dirty();
+            vec![]
+        }
+
         // Navigate between generated messages (arrow keys only, n/p reserved for other uses)
         KeyCode::Right => {
             state.modes.commit.message_editor.next_message();
@@ -339,6 +384,45 @@ fn handle_message_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
             vec![]
         }
 
+        // Apply spelling suggestions
+        KeyCode::Char('z') => {
+            let fixed = state.modes.commit.message_editor.fix_spelling();
+            if fixed > 0 {
+                state.notify(crate::studio::state::Notification::success(format!(
+                    "Fixed {fixed} misspelled word{}",
+                    if fixed == 1 { "" } else { "s" }
+                )));
+            } else if state.spell_checker.is_some() {
+                state.notify(crate::studio::state::Notification::info(
+                    "No misspellings found",
+                ));
+            }
+            state.mark_dirty();
+            vec![]
+        }
+
+        // Stage the unstaged files the message mentions
+        KeyCode::Char('s') => {
+            let paths = state.modes.commit.message_editor.referenced_unstaged();
+            if paths.is_empty() {
+                state.notify(crate::studio::state::Notification::info(
+                    "The message doesn't mention any unstaged files",
+                ));
+            } else {
+                let list: Vec<String> =
+                    paths.iter().map(|p| format!("  {}", p.display())).collect();
+                state.modal = Some(Modal::Confirm {
+                    message: format!(
+                        "Stage files referenced by this message?\n{}",
+                        list.join("\n")
+                    ),
+                    action: "stage_referenced".to_string(),
+                });
+            }
+            state.mark_dirty();
+            vec![]
+        }
+
         // Copy to clipboard
         KeyCode::Char('y') => {
             let message = state.modes.commit.message_editor.get_message();
@@ -0,0 +1,61 @@
+//! Dashboard mode key handling for Iris Studio
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::studio::events::SideEffect;
+use crate::studio::state::{DashboardState, Notification, StudioState};
+
+use super::open_in_explore;
+
+/// Handle key events in Dashboard mode
+pub fn handle_dashboard_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    match key.code {
+        KeyCode::Char('r') => {
+            state.notify(Notification::info("Refreshing activity..."));
+            load_dashboard(state, true)
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.modes.dashboard.move_selection(1);
+            state.mark_dirty();
+            vec![]
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.modes.dashboard.move_selection(-1);
+            state.mark_dirty();
+            vec![]
+        }
+        KeyCode::Home | KeyCode::Char('g') => {
+            state.modes.dashboard.selected_file = 0;
+            state.mark_dirty();
+            vec![]
+        }
+        KeyCode::End | KeyCode::Char('G') => {
+            state.modes.dashboard.move_selection(isize::MAX);
+            state.mark_dirty();
+            vec![]
+        }
+        KeyCode::Enter => open_selected_file(state),
+        _ => vec![],
+    }
+}
+
+/// Load Dashboard statistics if the cache is stale (or always, with `force`)
+pub fn load_dashboard(state: &mut StudioState, force: bool) -> Vec<SideEffect> {
+    let dashboard = &mut state.modes.dashboard;
+    if dashboard.loading || !(force || dashboard.needs_load()) {
+        return vec![];
+    }
+    dashboard.loading = true;
+    state.mark_dirty();
+    vec![SideEffect::LoadDashboard {
+        window_days: DashboardState::WINDOW_DAYS,
+    }]
+}
+
+/// Show the selected hot file in Explore mode
+fn open_selected_file(state: &mut StudioState) -> Vec<SideEffect> {
+    let Some(path) = state.modes.dashboard.selected_path().cloned() else {
+        return vec![];
+    };
+    open_in_explore(state, path, None)
+}
@@ -1,13 +1,20 @@
 //! Explore mode key handling for Iris Studio
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::Path;
 
+use crate::studio::components::HeatMapMode;
 use crate::studio::events::SideEffect;
-use crate::studio::state::{Notification, PanelId, StudioState};
+use crate::studio::state::{ExploreState, Modal, Notification, PanelId, PickaxeState, StudioState};
+
+use super::open_in_explore;
 
 /// Default visible height for code view navigation (will be adjusted by actual render)
 const DEFAULT_VISIBLE_HEIGHT: usize = 30;
 
+/// Search results that fit in the left panel (two rows each)
+const SEARCH_VISIBLE_RESULTS: usize = 12;
+
 /// Handle key events in Explore mode
 pub fn handle_explore_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
     // Global explore mode keys (work in any panel)
@@ -35,14 +42,120 @@ pub fn handle_explore_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEff
         return vec![];
     }
 
+    // Cycle file tree heat map: off → churn → ownership
+    if let KeyCode::Char('H') = key.code {
+        return cycle_heat_map(state);
+    }
+
+    // Search history for when a string was added or removed
+    if let KeyCode::Char('p') = key.code {
+        open_pickaxe(state);
+        return vec![];
+    }
+
+    // Ask Iris where something happens in the code
+    if let KeyCode::Char('s') = key.code {
+        let input = state
+            .modes
+            .explore
+            .search
+            .as_ref()
+            .map(|search| search.query.clone())
+            .unwrap_or_default();
+        state.modal = Some(Modal::SemanticSearch { input });
+        state.mark_dirty();
+        return vec![];
+    }
+
+    // Open the architecture overview of the repository
+    if let KeyCode::Char('A') = key.code {
+        state.mark_dirty();
+        if state.modes.explore.overview_loading {
+            state.notify(Notification::info("Already mapping the architecture..."));
+            return vec![];
+        }
+        state.modes.explore.overview_loading = true;
+        state.set_iris_thinking("Mapping the architecture...");
+        return vec![SideEffect::ExplainRepo];
+    }
+
     // Panel-specific keys
     match state.focused_panel {
+        PanelId::Left if state.modes.explore.search.is_some() => {
+            handle_search_results_key(state, key)
+        }
         PanelId::Left => handle_file_tree_key(state, key),
         PanelId::Center => handle_code_view_key(state, key),
         PanelId::Right => handle_context_key(state, key),
     }
 }
 
+/// Advance the heat map overlay, loading history data if the cache is stale
+fn cycle_heat_map(state: &mut StudioState) -> Vec<SideEffect> {
+    let explore = &mut state.modes.explore;
+    explore.heat_map_mode = explore.heat_map_mode.next();
+    explore.sync_heat_overlay();
+
+    let mode = explore.heat_map_mode;
+    let mut effects = Vec::new();
+    if mode == HeatMapMode::Off {
+        state.notify(Notification::info("Heat map off"));
+    } else {
+        if explore.heat_map_needs_load() && !explore.heat_map_loading {
+            explore.heat_map_loading = true;
+            effects.push(SideEffect::LoadHeatMap {
+                window_days: ExploreState::HEAT_MAP_WINDOW_DAYS,
+            });
+        }
+        let msg = if explore.heat_map_loading {
+            format!("Heat map: {} (loading history...)", mode.label())
+        } else {
+            format!("Heat map: {} (H to cycle)", mode.label())
+        };
+        state.notify(Notification::info(msg));
+    }
+    state.mark_dirty();
+    effects
+}
+
+/// Open the pickaxe search, pre-filled with a single selected line
+fn open_pickaxe(state: &mut StudioState) {
+    let explore = &state.modes.explore;
+    let query = match explore.selection {
+        Some((start, end)) if start == end => explore
+            .code_view
+            .lines()
+            .get(start.saturating_sub(1))
+            .map(|line| line.trim().to_string())
+            .unwrap_or_default(),
+        _ => String::new(),
+    };
+    state.modal = Some(Modal::Pickaxe(Box::new(PickaxeState::new(query))));
+    state.mark_dirty();
+}
+
+/// Show or hide the whole-file blame gutter for the open file
+fn toggle_file_blame(state: &mut StudioState) -> Vec<SideEffect> {
+    state.mark_dirty();
+    let explore = &mut state.modes.explore;
+    if explore.code_view.blame().is_some() {
+        explore.code_view.set_blame(None);
+        state.notify(Notification::info("Blame hidden"));
+        return vec![];
+    }
+    if explore.file_blame_loading {
+        state.notify(Notification::info("Already blaming..."));
+        return vec![];
+    }
+    let Some(file) = explore.code_view.current_file().map(Path::to_path_buf) else {
+        state.notify(Notification::warning("No file open"));
+        return vec![];
+    };
+    explore.file_blame_loading = true;
+    state.notify(Notification::info("Blaming every line..."));
+    vec![SideEffect::LoadFileBlame(file)]
+}
+
 /// Load the selected file into the code view and trigger file log loading
 fn load_selected_file(state: &mut StudioState) -> Vec<SideEffect> {
     if let Some(entry) = state.modes.explore.file_tree.selected_entry()
@@ -133,6 +246,42 @@ fn handle_file_tree_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffec
     }
 }
 
+/// Keys for the semantic search results that replace the file tree
+fn handle_search_results_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let Some(search) = &mut state.modes.explore.search else {
+        return vec![];
+    };
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => search.select_next(SEARCH_VISIBLE_RESULTS),
+        KeyCode::Char('k') | KeyCode::Up => search.select_prev(),
+        // Show the selected place in the code view
+        KeyCode::Enter => {
+            let Some(result) = search.selected_result().cloned() else {
+                return vec![];
+            };
+            let effects = open_in_explore(state, result.path, result.line);
+            if let (Some(start), Some(end)) = (result.line, result.end_line) {
+                let explore = &mut state.modes.explore;
+                explore.selection = Some((start, end));
+                explore.code_view.set_selection(start, end);
+            }
+            state.mark_dirty();
+            return effects;
+        }
+        // Back to the file tree
+        KeyCode::Esc => {
+            let was_loading = search.loading;
+            state.modes.explore.search = None;
+            if was_loading {
+                state.set_iris_idle();
+            }
+        }
+        _ => return vec![],
+    }
+    state.mark_dirty();
+    vec![]
+}
+
 /// Update selection range based on anchor and current line
 fn update_visual_selection(state: &mut StudioState) {
     if let Some(anchor) = state.modes.explore.selection_anchor {
@@ -271,12 +420,8 @@ fn handle_code_view_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffec
             vec![]
         }
 
-        // Heat map toggle
-        KeyCode::Char('H') => {
-            state.modes.explore.show_heat_map = !state.modes.explore.show_heat_map;
-            state.mark_dirty();
-            vec![]
-        }
+        // Blame the whole file as a gutter heat map, or hide it
+        KeyCode::Char('b') => toggle_file_blame(state),
 
         // Ask "why" about current line - semantic blame
         KeyCode::Char('w') => {
@@ -5,25 +5,32 @@
 
 mod changelog;
 mod commit;
+mod dashboard;
 mod explore;
 mod modals;
 mod pr;
+mod rebase;
 mod release_notes;
 mod review;
+mod stash;
 
-use arboard::Clipboard;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::PathBuf;
 
+use crate::config::AutoTrigger;
 use crate::studio::events::{AgentTask, ChatContext, DataType, SideEffect};
-use crate::studio::state::{Modal, Mode, Notification, SettingsState, StudioState};
+use crate::studio::state::{Modal, Mode, Notification, PanelId, SettingsState, StudioState};
 
 pub use changelog::handle_changelog_key;
 pub use commit::handle_commit_key;
+pub use dashboard::{handle_dashboard_key, load_dashboard};
 pub use explore::handle_explore_key;
 pub use modals::handle_modal_key;
 pub use pr::handle_pr_key;
+pub use rebase::{handle_rebase_key, load_rebase_diff, load_rebase_plan};
 pub use release_notes::handle_release_notes_key;
 pub use review::handle_review_key;
+pub use stash::{handle_stash_key, load_stashes};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Main Event Handler
@@ -49,6 +56,9 @@ pub fn handle_key_event(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffec
         Mode::PR => handle_pr_key(state, key),
         Mode::Changelog => handle_changelog_key(state, key),
         Mode::ReleaseNotes => handle_release_notes_key(state, key),
+        Mode::Dashboard => handle_dashboard_key(state, key),
+        Mode::Stash => handle_stash_key(state, key),
+        Mode::Rebase => handle_rebase_key(state, key),
     }
 }
 
@@ -76,6 +86,12 @@ fn handle_global_key(state: &mut StudioState, key: KeyEvent) -> Option<Vec<SideE
             Some(vec![])
         }
 
+        // Pin focused file/selection/hunk into the chat context basket
+        KeyCode::Char('+') if !is_editing(state) => {
+            toggle_focused_pin(state);
+            Some(vec![])
+        }
+
         // Mode switching (Shift+letter)
         KeyCode::Char('E') if key.modifiers.contains(KeyModifiers::SHIFT) => {
             Some(switch_mode(state, Mode::Explore))
@@ -92,9 +108,51 @@ fn handle_global_key(state: &mut StudioState, key: KeyEvent) -> Option<Vec<SideE
         KeyCode::Char('L') if key.modifiers.contains(KeyModifiers::SHIFT) => {
             Some(switch_mode(state, Mode::Changelog))
         }
-        KeyCode::Char('N') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+        // In the review output, N steps back through findings instead
+        KeyCode::Char('N')
+            if key.modifiers.contains(KeyModifiers::SHIFT) && !is_reading_review(state) =>
+        {
             Some(switch_mode(state, Mode::ReleaseNotes))
         }
+        KeyCode::Char('D') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            Some(switch_mode(state, Mode::Dashboard))
+        }
+        KeyCode::Char('Z') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            Some(switch_mode(state, Mode::Stash))
+        }
+        KeyCode::Char('B') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            Some(switch_mode(state, Mode::Rebase))
+        }
+
+        // Search generated history across sessions
+        KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(vec![SideEffect::OpenHistorySearch])
+        }
+
+        // Status bar auto-generate toggle
+        KeyCode::Char('a')
+            if key.modifiers.contains(KeyModifiers::CONTROL) && !is_editing(state) =>
+        {
+            state.toggle_auto_generate();
+            Some(vec![])
+        }
+
+        // Output length (Ctrl+O) and tone (Ctrl+Y) of the active mode
+        KeyCode::Char(c @ ('o' | 'y'))
+            if key.modifiers.contains(KeyModifiers::CONTROL) && !is_editing(state) =>
+        {
+            Some(cycle_output_style(state, c == 'y'))
+        }
+
+        // Token breakdown of the latest generation
+        KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(vec![SideEffect::ShowContextReport])
+        }
+
+        // Export chat, artifacts and diff as a shareable report
+        KeyCode::Char('X') if key.modifiers.contains(KeyModifiers::SHIFT) && !is_editing(state) => {
+            Some(vec![SideEffect::ExportSession])
+        }
 
         // Settings
         KeyCode::Char('S') if key.modifiers.contains(KeyModifiers::SHIFT) => {
@@ -119,6 +177,11 @@ fn handle_global_key(state: &mut StudioState, key: KeyEvent) -> Option<Vec<SideE
             if state.modal.is_some() {
                 state.close_modal();
                 Some(vec![])
+            } else if let Some(task_type) = state.active_generation()
+                && !is_editing(state)
+            {
+                state.cancel_generation(task_type);
+                Some(vec![SideEffect::CancelTask(task_type)])
             } else {
                 // Mode-specific escape handling
                 None
@@ -129,6 +192,37 @@ fn handle_global_key(state: &mut StudioState, key: KeyEvent) -> Option<Vec<SideE
     }
 }
 
+/// Step the active mode's length or tone for this session
+fn cycle_output_style(state: &mut StudioState, tone: bool) -> Vec<SideEffect> {
+    let changed = if tone {
+        state.cycle_tone()
+    } else {
+        state.cycle_verbosity()
+    };
+    if changed {
+        vec![SideEffect::ReconfigureAgent]
+    } else {
+        vec![]
+    }
+}
+
+/// Pin (or unpin) whatever is focused into the chat context basket
+fn toggle_focused_pin(state: &mut StudioState) {
+    let Some(pin) = state.focused_pin() else {
+        state.notify(Notification::warning("Nothing to pin here"));
+        return;
+    };
+    let label = pin.label.clone();
+    let pinned = state.chat_state.toggle_pin(pin);
+    let count = state.chat_state.pinned.len();
+    let msg = if pinned {
+        format!("Pinned {} to chat ({} pinned)", label, count)
+    } else {
+        format!("Unpinned {} ({} pinned)", label, count)
+    };
+    state.notify(Notification::info(msg));
+}
+
 /// Switch mode and return appropriate data loading effect
 fn switch_mode(state: &mut StudioState, mode: Mode) -> Vec<SideEffect> {
     if state.active_mode == mode {
@@ -137,7 +231,7 @@ fn switch_mode(state: &mut StudioState, mode: Mode) -> Vec<SideEffect> {
 
     state.switch_mode(mode);
 
-    match mode {
+    let mut effects = match mode {
         Mode::Commit => vec![SideEffect::LoadData {
             data_type: DataType::CommitDiff,
             from_ref: None,
@@ -163,8 +257,18 @@ fn switch_mode(state: &mut StudioState, mode: Mode) -> Vec<SideEffect> {
             from_ref: Some(state.modes.release_notes.from_ref.clone()),
             to_ref: Some(state.modes.release_notes.to_ref.clone()),
         }],
+        Mode::Dashboard => load_dashboard(state, false),
+        Mode::Stash => vec![load_stashes()],
+        // Keep an edited plan; R reloads it
+        Mode::Rebase if state.modes.rebase.plan.is_some() => vec![],
+        Mode::Rebase => vec![load_rebase_plan()],
         Mode::Explore => vec![],
+    };
+    // After the data loads, so generation sees the fresh diff or commits
+    if state.auto_trigger(mode) != AutoTrigger::Manual {
+        effects.push(SideEffect::AutoGenerate(mode));
     }
+    effects
 }
 
 /// Check if we're in an editing state (text input mode)
@@ -175,6 +279,11 @@ pub fn is_editing(state: &StudioState) -> bool {
     }
 }
 
+/// Check if the Review output panel has focus, where `n`/`N` walk findings
+fn is_reading_review(state: &StudioState) -> bool {
+    state.active_mode == Mode::Review && state.focused_panel == PanelId::Center
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Keybinding Descriptions
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -189,6 +298,10 @@ pub fn get_keybindings(mode: Mode) -> Vec<(&'static str, &'static str)> {
         ("Tab", "Next panel"),
         ("S-Tab", "Previous panel"),
         ("/", "Search"),
+        ("+", "Pin to chat"),
+        ("X", "Export session"),
+        ("^F", "Search history"),
+        ("^A", "Toggle auto-generate"),
         ("E", "Explore mode"),
         ("C", "Commit mode"),
     ];
@@ -202,7 +315,9 @@ pub fn get_keybindings(mode: Mode) -> Vec<(&'static str, &'static str)> {
                 ("g/G", "First/last"),
                 ("Enter", "Open/select"),
                 ("w", "Ask why"),
-                ("H", "Toggle heat map"),
+                ("H", "Cycle heat map"),
+                ("s", "Search by meaning"),
+                ("p", "Find when text changed"),
                 ("o", "Open in editor"),
             ]);
         }
@@ -227,25 +342,65 @@ pub fn get_keybindings(mode: Mode) -> Vec<(&'static str, &'static str)> {
     bindings
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// Navigation
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Show a file in Explore mode, selecting `line` (1-based) if given
+pub fn open_in_explore(
+    state: &mut StudioState,
+    path: PathBuf,
+    line: Option<usize>,
+) -> Vec<SideEffect> {
+    if !path.exists() {
+        state.notify(Notification::warning(format!(
+            "{} no longer exists",
+            path.display()
+        )));
+        return vec![];
+    }
+
+    state.switch_mode(Mode::Explore);
+    state.focused_panel = PanelId::Center;
+    let explore = &mut state.modes.explore;
+    if explore.file_tree.is_empty() {
+        // The tree loads lazily; show the file once it has
+        explore.document = Some(path);
+        explore.document_line = line;
+        return vec![SideEffect::LoadData {
+            data_type: DataType::ExploreFiles,
+            from_ref: None,
+            to_ref: None,
+        }];
+    }
+
+    explore.current_file = Some(path.clone());
+    if let Err(e) = explore.code_view.load_file(&path) {
+        state.notify(Notification::warning(format!("Could not load file: {}", e)));
+        return vec![];
+    }
+    if let Some(line) = line {
+        explore.code_view.show_line(line);
+        explore.current_line = line;
+    }
+    explore.file_log_loading = true;
+    vec![SideEffect::LoadFileLog(path)]
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Clipboard Utilities
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Copy text to the system clipboard and notify the user
 pub fn copy_to_clipboard(state: &mut StudioState, content: &str, description: &str) {
-    match Clipboard::new() {
-        Ok(mut clipboard) => match clipboard.set_text(content) {
-            Ok(()) => {
-                state.notify(Notification::success(format!(
-                    "{description} copied to clipboard"
-                )));
-            }
-            Err(e) => {
-                state.notify(Notification::error(format!("Failed to copy: {e}")));
-            }
-        },
+    match crate::clipboard::copy_text(content, state.config.clipboard_osc52) {
+        Ok(_) => {
+            state.notify(Notification::success(format!(
+                "{description} copied to clipboard"
+            )));
+        }
         Err(e) => {
-            state.notify(Notification::error(format!("Clipboard unavailable: {e}")));
+            state.notify(Notification::error(format!("Failed to copy: {e}")));
         }
     }
     state.mark_dirty();
@@ -314,12 +469,13 @@ pub fn spawn_release_notes_task(state: &StudioState) -> SideEffect {
 }
 
 /// Create a chat agent task
-pub fn spawn_chat_task(message: String, mode: Mode) -> SideEffect {
+pub fn spawn_chat_task(message: String, state: &StudioState) -> SideEffect {
     SideEffect::SpawnAgent {
         task: AgentTask::Chat {
             message,
             context: ChatContext {
-                mode,
+                mode: state.active_mode,
+                code_context: state.explore_code_context(),
                 ..Default::default()
             },
         },
@@ -1,9 +1,9 @@
 //! Chat modal key handler
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::studio::events::SideEffect;
-use crate::studio::state::{Modal, StudioState};
+use crate::studio::state::{Modal, Notification, StudioState};
 
 use super::super::spawn_chat_task;
 
@@ -17,7 +17,6 @@ pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
     // Get state needed before potential mutation
     let current_input = state.chat_state.input.clone();
     let is_responding = state.chat_state.is_responding;
-    let mode = state.active_mode;
 
     match key.code {
         KeyCode::Esc => {
@@ -30,11 +29,19 @@ pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
                 state.chat_state.add_user_message(&current_input);
                 state.chat_state.is_responding = true;
                 state.mark_dirty();
-                vec![spawn_chat_task(current_input, mode)]
+                vec![spawn_chat_task(current_input, state)]
             } else {
                 vec![]
             }
         }
+        // Drop the most recent pin
+        KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            if let Some(pin) = state.chat_state.pop_pin() {
+                state.notify(Notification::info(format!("Unpinned {}", pin.label)));
+            }
+            state.mark_dirty();
+            vec![]
+        }
         KeyCode::Char(c) => {
             state.chat_state.input.push(c);
             state.mark_dirty();
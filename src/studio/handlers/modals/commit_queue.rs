@@ -0,0 +1,52 @@
+//! Commit queue modal key handler
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::studio::events::SideEffect;
+use crate::studio::state::{Notification, StudioState};
+
+/// Handle key events in the commit queue modal
+pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let queue = &mut state.modes.commit.queue;
+    // The queue can't change under a run; it stops on its own at a failure
+    if queue.running {
+        return vec![];
+    }
+
+    let effects = match key.code {
+        KeyCode::Esc => {
+            state.close_modal();
+            return vec![];
+        }
+        KeyCode::Enter if !queue.is_empty() => vec![SideEffect::RunCommitQueue],
+        KeyCode::Char('j') | KeyCode::Down => {
+            queue.move_selection(1);
+            vec![]
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            queue.move_selection(-1);
+            vec![]
+        }
+        KeyCode::Char('J') => {
+            queue.move_selected(1);
+            vec![]
+        }
+        KeyCode::Char('K') => {
+            queue.move_selected(-1);
+            vec![]
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            if let Some(removed) = queue.remove_selected() {
+                state.notify(Notification::info(format!(
+                    "Removed \"{}\" from the queue",
+                    removed.subject()
+                )));
+            }
+            vec![]
+        }
+        _ => return vec![],
+    };
+
+    state.mark_dirty();
+    effects
+}
@@ -2,7 +2,7 @@
 
 use crossterm::event::{KeyCode, KeyEvent};
 
-use crate::studio::events::SideEffect;
+use crate::studio::events::{SideEffect, StashAction};
 use crate::studio::state::{Modal, StudioState};
 
 /// Handle key events in confirm modal
@@ -48,6 +48,28 @@ pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
                         vec![]
                     }
                 }
+                "undo_commit" => vec![SideEffect::UndoLastCommit],
+                "drop_stash" => state
+                    .modes
+                    .stash
+                    .selected_entry()
+                    .map(|entry| SideEffect::RunStashAction {
+                        action: StashAction::Drop,
+                        index: entry.index,
+                        id: entry.id.clone(),
+                    })
+                    .into_iter()
+                    .collect(),
+                "run_rebase" => vec![SideEffect::RunRebase],
+                "open_pr_with_ci" => super::super::pr::create_pull_request(state, true),
+                "stage_referenced" => {
+                    let paths = state.modes.commit.message_editor.referenced_unstaged();
+                    if paths.is_empty() {
+                        vec![]
+                    } else {
+                        vec![SideEffect::GitStageFiles(paths.to_vec())]
+                    }
+                }
                 "quit" => vec![SideEffect::Quit],
                 _ => vec![],
             }
@@ -0,0 +1,38 @@
+//! History search modal key handler
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::studio::events::SideEffect;
+use crate::studio::state::{Modal, StudioState};
+
+/// Handle key events in the history search modal
+pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let Some(Modal::HistorySearch(search)) = &mut state.modal else {
+        return vec![];
+    };
+
+    match key.code {
+        KeyCode::Esc => {
+            state.close_modal();
+            return vec![];
+        }
+        KeyCode::Enter => {
+            // Copy the selected entry so it can be reused
+            let content = search.selected_hit().map(|hit| hit.content.clone());
+            state.close_modal();
+            return content
+                .map(|content| vec![SideEffect::CopyToClipboard(content)])
+                .unwrap_or_default();
+        }
+        KeyCode::Up => search.select_prev(),
+        KeyCode::Down => search.select_next(),
+        KeyCode::Tab => search.cycle_kind(),
+        KeyCode::BackTab => search.cycle_window(),
+        KeyCode::Backspace => search.pop_char(),
+        KeyCode::Char(c) => search.push_char(c),
+        _ => return vec![],
+    }
+
+    state.mark_dirty();
+    vec![]
+}
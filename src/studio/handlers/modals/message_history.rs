@@ -0,0 +1,34 @@
+//! Message history modal key handler
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::studio::events::SideEffect;
+use crate::studio::state::{Modal, StudioState};
+
+/// Handle key events in the message history modal
+pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let Some(Modal::MessageHistory(history)) = &mut state.modal else {
+        return vec![];
+    };
+
+    match key.code {
+        KeyCode::Esc => {
+            state.close_modal();
+            return vec![];
+        }
+        KeyCode::Enter => {
+            let message = history.selected_draft().map(|draft| draft.message.clone());
+            state.close_modal();
+            if let Some(message) = message {
+                state.restore_message_draft(&message);
+            }
+            return vec![];
+        }
+        KeyCode::Char('k') | KeyCode::Up => history.select_prev(),
+        KeyCode::Char('j') | KeyCode::Down => history.select_next(),
+        _ => return vec![],
+    }
+
+    state.mark_dirty();
+    vec![]
+}
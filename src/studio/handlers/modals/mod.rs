@@ -4,13 +4,23 @@
 
 mod chat;
 mod commit_count;
+mod commit_queue;
 mod confirm;
 mod emoji_selector;
+mod history_search;
 mod instructions;
+mod message_history;
+mod model_picker;
+mod pickaxe;
+mod preset_editor;
 mod preset_selector;
+mod rebase_reword;
 mod ref_selector;
+mod review_export;
 mod search;
+mod semantic_search;
 mod settings;
+mod stash_create;
 mod theme_selector;
 
 use crossterm::event::KeyEvent;
@@ -21,8 +31,8 @@ use crate::studio::state::{Modal, StudioState};
 /// Handle key events when a modal is open
 pub fn handle_modal_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
     match &state.modal {
-        Some(Modal::Help) => {
-            // Any key closes help
+        Some(Modal::Help | Modal::ContextReport(_) | Modal::Recap { .. }) => {
+            // Any key closes help and reports
             state.close_modal();
             vec![]
         }
@@ -36,6 +46,16 @@ pub fn handle_modal_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffec
         Some(Modal::Settings(_)) => settings::handle(state, key),
         Some(Modal::ThemeSelector { .. }) => theme_selector::handle(state, key),
         Some(Modal::CommitCount { .. }) => commit_count::handle(state, key),
+        Some(Modal::CommitQueue) => commit_queue::handle(state, key),
+        Some(Modal::HistorySearch(_)) => history_search::handle(state, key),
+        Some(Modal::ModelPicker(_)) => model_picker::handle(state, key),
+        Some(Modal::PresetEditor(_)) => preset_editor::handle(state, key),
+        Some(Modal::Pickaxe(_)) => pickaxe::handle(state, key),
+        Some(Modal::SemanticSearch { .. }) => semantic_search::handle(state, key),
+        Some(Modal::MessageHistory(_)) => message_history::handle(state, key),
+        Some(Modal::ReviewExport(_)) => review_export::handle(state, key),
+        Some(Modal::StashCreate(_)) => stash_create::handle(state, key),
+        Some(Modal::RebaseReword(_)) => rebase_reword::handle(state, key),
         None => vec![],
     }
 }
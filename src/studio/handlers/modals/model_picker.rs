@@ -0,0 +1,50 @@
+//! Model picker modal key handler
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::studio::events::SideEffect;
+use crate::studio::state::{Modal, StudioState};
+
+/// Visible rows in the model list
+pub const VISIBLE_ITEMS: usize = 14;
+
+/// Handle key events in the model picker modal
+pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let Some(Modal::ModelPicker(picker)) = &mut state.modal else {
+        return vec![];
+    };
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Enter => {
+            let Some(Modal::ModelPicker(picker)) = state.modal.take() else {
+                return vec![];
+            };
+            // Enter picks the highlighted model, or the typed name as a manual entry
+            let choice = if key.code == KeyCode::Enter {
+                picker.choice()
+            } else {
+                None
+            };
+            state.modal = Some(Modal::Settings(Box::new(picker.into_settings(choice))));
+        }
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            picker.loading = true;
+            picker.error = None;
+            let effect = SideEffect::LoadModels {
+                provider: picker.provider.clone(),
+                api_key: picker.settings.api_key_actual.clone(),
+                refresh: true,
+            };
+            state.mark_dirty();
+            return vec![effect];
+        }
+        KeyCode::Up => picker.select_prev(),
+        KeyCode::Down => picker.select_next(VISIBLE_ITEMS),
+        KeyCode::Backspace => picker.pop_char(),
+        KeyCode::Char(c) => picker.push_char(c),
+        _ => return vec![],
+    }
+
+    state.mark_dirty();
+    vec![]
+}
@@ -0,0 +1,86 @@
+//! Pickaxe search modal key handler
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::git::PickaxeHit;
+use crate::studio::events::{AgentTask, BlameInfo, SideEffect};
+use crate::studio::state::{Modal, Notification, StudioState};
+
+/// Handle key events in the pickaxe search modal
+pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let Some(Modal::Pickaxe(search)) = &mut state.modal else {
+        return vec![];
+    };
+
+    match key.code {
+        KeyCode::Esc => {
+            state.close_modal();
+            return vec![];
+        }
+        // Search when the query changed, otherwise explain the selected hit
+        KeyCode::Enter => {
+            if search.query.is_empty() || search.loading {
+                return vec![];
+            }
+            if !search.is_current() {
+                search.begin_search();
+                let effect = SideEffect::PickaxeSearch {
+                    query: search.query.clone(),
+                    kind: search.kind,
+                };
+                state.mark_dirty();
+                return vec![effect];
+            }
+            let Some(hit) = search.selected_hit().cloned() else {
+                return vec![];
+            };
+            let blame_info = blame_info_for(&hit, &search.query, search.kind.flag());
+            state.close_modal();
+            return explain(state, blame_info);
+        }
+        KeyCode::Up => search.select_prev(),
+        KeyCode::Down => search.select_next(),
+        KeyCode::Tab => search.toggle_kind(),
+        KeyCode::Backspace => search.pop_char(),
+        KeyCode::Char(c) => search.push_char(c),
+        _ => return vec![],
+    }
+
+    state.mark_dirty();
+    vec![]
+}
+
+/// Ask the semantic blame agent why `blame_info`'s commit made its change
+fn explain(state: &mut StudioState, blame_info: BlameInfo) -> Vec<SideEffect> {
+    if state.modes.explore.blame_loading {
+        state.notify(Notification::info("Already analyzing..."));
+        state.mark_dirty();
+        return vec![];
+    }
+    state.modes.explore.blame_loading = true;
+    state.set_iris_thinking("Analyzing code history...");
+    state.mark_dirty();
+    vec![SideEffect::SpawnAgent {
+        task: AgentTask::SemanticBlame { blame_info },
+    }]
+}
+
+/// Blame info pointing the agent at the commit that added or removed the match
+fn blame_info_for(hit: &PickaxeHit, query: &str, flag: &str) -> BlameInfo {
+    let start_line = hit.line.unwrap_or(0);
+    let code_content = format!(
+        "# git log {flag}{query:?}: {} in this commit\n{}",
+        hit.change().label(),
+        hit.lines.join("\n")
+    );
+    BlameInfo {
+        file: hit.file.clone(),
+        start_line,
+        end_line: start_line,
+        commit_hash: hit.commit.clone(),
+        author: hit.author.clone(),
+        commit_date: hit.date.clone(),
+        commit_message: hit.subject.clone(),
+        code_content,
+    }
+}
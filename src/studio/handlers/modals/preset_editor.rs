@@ -0,0 +1,38 @@
+//! Preset editor modal key handler
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::studio::events::SideEffect;
+use crate::studio::state::{Modal, PresetEditorField, StudioState};
+
+/// Handle key events in the preset editor modal
+pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let Some(Modal::PresetEditor(editor)) = &mut state.modal else {
+        return vec![];
+    };
+
+    match key.code {
+        KeyCode::Esc => {
+            let Some(Modal::PresetEditor(editor)) = state.modal.take() else {
+                return vec![];
+            };
+            state.modal = Some(Modal::Settings(Box::new(editor.into_settings(None))));
+        }
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            return vec![SideEffect::SavePreset];
+        }
+        KeyCode::Tab => editor.next_field(),
+        KeyCode::BackTab => editor.prev_field(),
+        // New line in the instructions, next field elsewhere
+        KeyCode::Enter if editor.field == PresetEditorField::Instructions => editor.newline(),
+        KeyCode::Enter => editor.next_field(),
+        KeyCode::Left if editor.field == PresetEditorField::Type => editor.cycle_type(false),
+        KeyCode::Right if editor.field == PresetEditorField::Type => editor.cycle_type(true),
+        KeyCode::Backspace => editor.pop_char(),
+        KeyCode::Char(c) => editor.push_char(c),
+        _ => return vec![],
+    }
+
+    state.mark_dirty();
+    vec![]
+}
@@ -0,0 +1,34 @@
+//! Reword modal key handler for Rebase mode
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::studio::events::SideEffect;
+use crate::studio::state::{Modal, StudioState};
+
+/// Handle key events in the reword modal
+pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let Some(Modal::RebaseReword(reword)) = &mut state.modal else {
+        return vec![];
+    };
+
+    match key.code {
+        KeyCode::Esc => {
+            state.close_modal();
+            return vec![];
+        }
+        KeyCode::Enter => {
+            let subject = std::mem::take(&mut reword.subject);
+            state.modes.rebase.reword(&subject);
+            state.close_modal();
+            return vec![];
+        }
+        KeyCode::Char(c) => reword.subject.push(c),
+        KeyCode::Backspace => {
+            reword.subject.pop();
+        }
+        _ => return vec![],
+    }
+
+    state.mark_dirty();
+    vec![]
+}
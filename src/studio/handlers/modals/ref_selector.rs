@@ -2,13 +2,40 @@
 
 use crossterm::event::{KeyCode, KeyEvent};
 
+use crate::git::ResolvedRevision;
 use crate::studio::events::SideEffect;
 use crate::studio::state::{Modal, Notification, RefSelectorTarget, StudioState};
 
 use super::super::{
-    reload_changelog_data, reload_pr_data, reload_release_notes_data, reload_review_data,
+    load_rebase_plan, reload_changelog_data, reload_pr_data, reload_release_notes_data,
+    reload_review_data,
 };
 
+/// Resolve `spec` against the open repository
+fn resolve(state: &StudioState, spec: &str) -> Result<ResolvedRevision, String> {
+    match &state.repo {
+        Some(repo) => repo.resolve_revision(spec),
+        None => Err("No repository to resolve revisions in".to_string()),
+    }
+}
+
+/// Check the typed text as a revision while no listed ref matches it, so
+/// the modal can show what it resolves to or why it doesn't
+fn validate_input(state: &mut StudioState) {
+    let Some(Modal::RefSelector { input, refs, .. }) = &state.modal else {
+        return;
+    };
+    let needle = input.to_lowercase();
+    let listed = refs.iter().any(|r| r.to_lowercase().contains(&needle));
+    let revision = (!input.is_empty() && !listed).then(|| resolve(state, input));
+    if let Some(Modal::RefSelector {
+        revision: current, ..
+    }) = &mut state.modal
+    {
+        *current = revision;
+    }
+}
+
 /// Handle key events in ref selector modal
 pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
     // Get current state for filtering
@@ -17,6 +44,7 @@ pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
         refs,
         selected,
         target,
+        ..
     }) = &state.modal
     {
         (input.clone(), refs.clone(), *selected, *target)
@@ -43,19 +71,24 @@ pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
                 Review,
                 Changelog,
                 ReleaseNotes,
+                Rebase,
             }
 
-            // Determine which ref to use:
-            // 1. If there's a matching filtered ref, use that
-            // 2. Otherwise, if input is not empty, use it as a custom ref (e.g., HEAD~5)
-            let ref_to_use: Option<String> =
-                filtered.get(selected).map(|s| (*s).clone()).or_else(|| {
-                    if input.is_empty() {
-                        None
-                    } else {
-                        Some(input.clone())
+            // Use the highlighted listed ref; when none matches, the typed
+            // text as a revision expression (HEAD~5, v1.2.0^, @{u}, a hash)
+            let ref_to_use: Option<String> = if let Some(listed) = filtered.get(selected) {
+                Some((*listed).clone())
+            } else if input.is_empty() {
+                None
+            } else {
+                match resolve(state, &input) {
+                    Ok(_) => Some(input.trim().to_string()),
+                    Err(e) => {
+                        state.notify(Notification::warning(e));
+                        return vec![];
                     }
-                });
+                }
+            };
 
             let reload_type = if let Some(ref_value) = ref_to_use {
                 let (label, reload) = match target {
@@ -91,6 +124,10 @@ pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
                         state.modes.release_notes.to_ref.clone_from(&ref_value);
                         ("Release Notes to", ReloadType::ReleaseNotes)
                     }
+                    RefSelectorTarget::RebaseBase => {
+                        state.modes.rebase.base.clone_from(&ref_value);
+                        ("Rebase onto", ReloadType::Rebase)
+                    }
                 };
                 state.notify(Notification::info(format!("{label} set to {ref_value}")));
                 reload
@@ -103,6 +140,7 @@ pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
                 ReloadType::Review => vec![reload_review_data(state)],
                 ReloadType::Changelog => vec![reload_changelog_data(state)],
                 ReloadType::ReleaseNotes => vec![reload_release_notes_data(state)],
+                ReloadType::Rebase => vec![load_rebase_plan()],
                 ReloadType::None => vec![],
             }
         }
@@ -142,6 +180,7 @@ pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
                 input.push(c);
                 *selected = 0; // Reset selection on filter change
             }
+            validate_input(state);
             state.mark_dirty();
             vec![]
         }
@@ -153,6 +192,7 @@ pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
                 input.pop();
                 *selected = 0;
             }
+            validate_input(state);
             state.mark_dirty();
             vec![]
         }
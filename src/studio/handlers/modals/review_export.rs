@@ -0,0 +1,42 @@
+//! Review export modal key handler
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::studio::events::SideEffect;
+use crate::studio::state::{Modal, Notification, StudioState};
+
+/// Handle key events in the review export modal
+pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let Some(Modal::ReviewExport(export)) = &mut state.modal else {
+        return vec![];
+    };
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('n' | 'N') => {
+            state.close_modal();
+            return vec![];
+        }
+        KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+            let effect = SideEffect::PostReview {
+                head: export.head.clone(),
+                to_ref: export.to_ref.clone(),
+                body: export.summary(),
+                comments: export.comments(),
+            };
+            let head = export.head.clone();
+            state.close_modal();
+            state.modes.review.posting = true;
+            state.set_iris_thinking("Posting review to GitHub...");
+            state.notify(Notification::info(format!(
+                "Posting review to the pull request for {head}..."
+            )));
+            return vec![effect];
+        }
+        KeyCode::Char('k') | KeyCode::Up => export.scroll_up(),
+        KeyCode::Char('j') | KeyCode::Down => export.scroll_down(),
+        _ => return vec![],
+    }
+
+    state.mark_dirty();
+    vec![]
+}
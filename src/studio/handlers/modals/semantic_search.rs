@@ -0,0 +1,48 @@
+//! Semantic search question modal key handler
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::studio::events::SideEffect;
+use crate::studio::state::{Modal, Notification, PanelId, SemanticSearchState, StudioState};
+
+/// Handle key events in the semantic search modal
+pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let Some(Modal::SemanticSearch { input }) = &mut state.modal else {
+        return vec![];
+    };
+
+    match key.code {
+        KeyCode::Esc => state.close_modal(),
+        KeyCode::Enter => {
+            let query = input.trim().to_string();
+            if query.is_empty() {
+                return vec![];
+            }
+            state.close_modal();
+            if state
+                .modes
+                .explore
+                .search
+                .as_ref()
+                .is_some_and(|s| s.loading)
+            {
+                state.notify(Notification::info("Already searching..."));
+                state.mark_dirty();
+                return vec![];
+            }
+            state.modes.explore.search = Some(SemanticSearchState::new(query.clone()));
+            state.focused_panel = PanelId::Left;
+            state.set_iris_thinking("Searching the code...");
+            state.mark_dirty();
+            return vec![SideEffect::SemanticSearch { query }];
+        }
+        KeyCode::Char(c) => input.push(c),
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        _ => return vec![],
+    }
+
+    state.mark_dirty();
+    vec![]
+}
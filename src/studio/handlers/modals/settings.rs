@@ -2,8 +2,12 @@
 
 use crossterm::event::{KeyCode, KeyEvent};
 
+use crate::instruction_presets::get_instruction_preset_library;
 use crate::studio::events::SideEffect;
-use crate::studio::state::{Modal, SettingsField, StudioState};
+use crate::studio::state::{
+    ConnectionStatus, Modal, ModelPickerState, Notification, PresetEditorState, SettingsField,
+    StudioState,
+};
 
 /// Handle key events in settings modal
 pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
@@ -65,6 +69,54 @@ fn handle_navigation_mode(state: &mut StudioState, key: KeyEvent) -> Vec<SideEff
             // Save settings
             vec![SideEffect::SaveSettings]
         }
+        KeyCode::Char('t') => {
+            // Test the provider/key/model before saving
+            let Some(Modal::Settings(settings)) = &mut state.modal else {
+                return vec![];
+            };
+            settings.connection = Some(ConnectionStatus::Testing);
+            let effect = SideEffect::TestConnection {
+                provider: settings.provider.clone(),
+                model: settings.model.clone(),
+                api_key: settings.api_key_actual.clone(),
+            };
+            state.mark_dirty();
+            vec![effect]
+        }
+        // New preset, or edit the selected one, from the preset field
+        KeyCode::Char(c @ ('n' | 'e'))
+            if matches!(&state.modal, Some(Modal::Settings(settings))
+                if settings.current_field() == SettingsField::InstructionPreset) =>
+        {
+            let Some(Modal::Settings(settings)) = state.modal.take() else {
+                return vec![];
+            };
+            let editor = if c == 'n' {
+                PresetEditorState::new(*settings)
+            } else {
+                let library = get_instruction_preset_library();
+                let key = settings.instruction_preset.clone();
+                let Some(preset) = library.get_preset(&key) else {
+                    state.modal = Some(Modal::Settings(settings));
+                    state.notify(Notification::warning(format!("Unknown preset '{key}'")));
+                    state.mark_dirty();
+                    return vec![];
+                };
+                let source = library.preset_path(&key).map(std::path::Path::to_path_buf);
+                PresetEditorState::edit(*settings, &key, preset, source)
+            };
+            state.modal = Some(Modal::PresetEditor(Box::new(editor)));
+            state.mark_dirty();
+            vec![]
+        }
+        KeyCode::Tab => {
+            // Switch between saving globally and to this repository
+            if let Some(Modal::Settings(settings)) = &mut state.modal {
+                settings.toggle_scope();
+            }
+            state.mark_dirty();
+            vec![]
+        }
         KeyCode::Up | KeyCode::Char('k') => {
             if let Some(Modal::Settings(settings)) = &mut state.modal {
                 settings.select_prev();
@@ -100,6 +152,24 @@ fn handle_navigation_mode(state: &mut StudioState, key: KeyEvent) -> Vec<SideEff
                 state.mark_dirty();
                 return vec![];
             }
+            // Model field - open the model picker and fetch the provider's list
+            if let Some(Modal::Settings(settings)) = &state.modal
+                && settings.current_field() == SettingsField::Model
+            {
+                let Some(Modal::Settings(settings)) = state.modal.take() else {
+                    return vec![];
+                };
+                let effect = SideEffect::LoadModels {
+                    provider: settings.provider.clone(),
+                    api_key: settings.api_key_actual.clone(),
+                    refresh: false,
+                };
+                state.modal = Some(Modal::ModelPicker(Box::new(ModelPickerState::new(
+                    *settings,
+                ))));
+                state.mark_dirty();
+                return vec![effect];
+            }
             if let Some(Modal::Settings(settings)) = &mut state.modal {
                 settings.start_editing();
             }
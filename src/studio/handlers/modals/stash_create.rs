@@ -0,0 +1,55 @@
+//! New stash modal key handler
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::studio::events::SideEffect;
+use crate::studio::state::{Modal, StudioState};
+
+/// Handle key events in the new stash modal
+pub fn handle(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let Some(Modal::StashCreate(create)) = &mut state.modal else {
+        return vec![];
+    };
+
+    let effects = match key.code {
+        KeyCode::Esc => {
+            state.close_modal();
+            return vec![];
+        }
+        KeyCode::Enter => {
+            let message = create.message.trim();
+            let effect = SideEffect::CreateStash {
+                message: (!message.is_empty()).then(|| message.to_string()),
+                include_untracked: create.include_untracked,
+            };
+            state.close_modal();
+            return vec![effect];
+        }
+        // Untracked files change what's stashed, so describe it again
+        KeyCode::Tab => {
+            create.include_untracked = !create.include_untracked;
+            if create.edited {
+                vec![]
+            } else {
+                create.describing = true;
+                vec![SideEffect::DescribeStash {
+                    include_untracked: create.include_untracked,
+                }]
+            }
+        }
+        KeyCode::Char(c) => {
+            create.message.push(c);
+            create.edited = true;
+            vec![]
+        }
+        KeyCode::Backspace => {
+            create.message.pop();
+            create.edited = true;
+            vec![]
+        }
+        _ => return vec![],
+    };
+
+    state.mark_dirty();
+    effects
+}
@@ -1,9 +1,13 @@
 //! PR mode key handling for Iris Studio
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::sync::Arc;
 
+use crate::integrations::ci::CheckState;
 use crate::studio::events::SideEffect;
-use crate::studio::state::{CommitCountTarget, Modal, PanelId, RefSelectorTarget, StudioState};
+use crate::studio::state::{
+    CommitCountTarget, Modal, Notification, PanelId, RefSelectorTarget, StudioState,
+};
 
 use super::{copy_to_clipboard, spawn_pr_task};
 
@@ -57,6 +61,7 @@ fn handle_commits_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
                 refs: state.get_branch_refs(),
                 selected: 0,
                 target: RefSelectorTarget::PrFrom,
+                revision: None,
             });
             state.mark_dirty();
             vec![]
@@ -68,6 +73,7 @@ fn handle_commits_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
                 refs: state.get_branch_refs(),
                 selected: 0,
                 target: RefSelectorTarget::PrTo,
+                revision: None,
             });
             state.mark_dirty();
             vec![]
@@ -87,6 +93,12 @@ fn handle_commits_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
             state.modes.pr.generating = true;
             vec![spawn_pr_task(state)]
         }
+        // Refresh CI status
+        KeyCode::Char('c') => {
+            state.modes.pr.ci_loading = true;
+            state.mark_dirty();
+            vec![SideEffect::LoadCiStatus]
+        }
         _ => vec![],
     }
 }
@@ -156,6 +168,74 @@ fn handle_diff_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
     }
 }
 
+/// Open the generated description as a GitHub pull request from the target
+/// branch into the base branch, asking first when CI checks are failing
+fn open_pull_request(state: &mut StudioState) -> Vec<SideEffect> {
+    let pr = &state.modes.pr;
+    if pr.generating || pr.opening {
+        return vec![];
+    }
+    if pr.pr_content.is_empty() {
+        state.notify(Notification::warning(
+            "Generate a PR description first (press r)",
+        ));
+        return vec![];
+    }
+    let Some(head) = pr.head_branch(&state.git_status.branch) else {
+        state.notify(Notification::warning(
+            "Check out a branch (or set one with t) to open a pull request",
+        ));
+        return vec![];
+    };
+
+    if let Some(ci) = pr.ci_status()
+        && ci.state() == Some(CheckState::Failing)
+    {
+        let failing: Vec<&str> = ci
+            .with_state(CheckState::Failing)
+            .map(|check| check.name.as_str())
+            .collect();
+        state.modal = Some(Modal::Confirm {
+            message: format!(
+                "{} failing on {head}: {}\n\nOpen the pull request anyway, with a CI Status \
+                 section telling reviewers these may be flaky?",
+                if failing.len() == 1 {
+                    "1 check is".to_string()
+                } else {
+                    format!("{} checks are", failing.len())
+                },
+                failing.join(", ")
+            ),
+            action: "open_pr_with_ci".to_string(),
+        });
+        state.mark_dirty();
+        return vec![];
+    }
+    create_pull_request(state, false)
+}
+
+/// Start opening the pull request, appending the CI status section when
+/// `with_ci` is set
+pub(crate) fn create_pull_request(state: &mut StudioState, with_ci: bool) -> Vec<SideEffect> {
+    let pr = &state.modes.pr;
+    let Some(head) = pr.head_branch(&state.git_status.branch) else {
+        return vec![];
+    };
+    let mut markdown = pr.pr_content.to_string();
+    if with_ci && let Some(ci) = pr.ci_status() {
+        markdown = format!("{}\n\n{}", markdown.trim_end(), ci.to_markdown());
+    }
+
+    let effect = SideEffect::CreatePullRequest {
+        markdown,
+        head,
+        base: pr.base_branch.clone(),
+    };
+    state.modes.pr.opening = true;
+    state.set_iris_thinking("Opening pull request on GitHub...");
+    vec![effect]
+}
+
 fn handle_output_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
     let content_lines = state.modes.pr.pr_content.lines().count();
 
@@ -200,6 +280,8 @@ fn handle_output_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
             state.modes.pr.generating = true;
             vec![spawn_pr_task(state)]
         }
+        // Open the pull request on GitHub
+        KeyCode::Char('o') => open_pull_request(state),
         // Copy to clipboard
         KeyCode::Char('y') => {
             if !state.modes.pr.pr_content.is_empty() {
@@ -208,9 +290,26 @@ fn handle_output_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
             }
             vec![]
         }
+        // Apply spelling suggestions
+        KeyCode::Char('z') => {
+            if let Some(checker) = state.spell_checker.clone() {
+                let (fixed, count) = checker.fix(&state.modes.pr.pr_content);
+                if count > 0 {
+                    state.modes.pr.pr_content = fixed.into();
+                    state.notify(Notification::success(format!(
+                        "Fixed {count} misspelled word{}",
+                        if count == 1 { "" } else { "s" }
+                    )));
+                } else {
+                    state.notify(Notification::info("No misspellings found"));
+                }
+                state.mark_dirty();
+            }
+            vec![]
+        }
         // Reset
         KeyCode::Char('R') => {
-            state.modes.pr.pr_content.clear();
+            state.modes.pr.pr_content = Arc::default();
             state.modes.pr.pr_scroll = 0;
             state.mark_dirty();
             vec![]
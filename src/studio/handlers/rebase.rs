@@ -0,0 +1,214 @@
+//! Rebase mode key handling for Iris Studio
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::git::RebaseAction;
+use crate::studio::events::{DataType, SideEffect};
+use crate::studio::state::{
+    Modal, Notification, PanelId, RebaseRewordState, RefSelectorTarget, StudioState,
+};
+
+/// Handle key events in Rebase mode
+pub fn handle_rebase_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    match key.code {
+        KeyCode::Char('r') => return suggest(state),
+        KeyCode::Char('x') => return confirm_run(state),
+        KeyCode::Char('b') => {
+            let refs = state.get_branch_refs();
+            state.modal = Some(Modal::RefSelector {
+                input: String::new(),
+                refs,
+                selected: 0,
+                target: RefSelectorTarget::RebaseBase,
+                revision: None,
+            });
+            state.mark_dirty();
+            return vec![];
+        }
+        KeyCode::Char('R') => {
+            state.notify(Notification::info("Reloading the branch..."));
+            return vec![load_rebase_plan()];
+        }
+        _ => {}
+    }
+    match state.focused_panel {
+        PanelId::Left => handle_plan_key(state, key),
+        PanelId::Center => handle_details_key(state, key),
+        PanelId::Right => handle_diff_key(state, key),
+    }
+}
+
+/// Load the plan for the current branch from scratch
+pub fn load_rebase_plan() -> SideEffect {
+    SideEffect::LoadData {
+        data_type: DataType::RebasePlan,
+        from_ref: None,
+        to_ref: None,
+    }
+}
+
+/// Load the diff of the selected commit
+pub fn load_rebase_diff() -> SideEffect {
+    SideEffect::LoadData {
+        data_type: DataType::RebaseDiff,
+        from_ref: None,
+        to_ref: None,
+    }
+}
+
+fn handle_plan_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    if state.modes.rebase.suggesting && is_edit_key(key.code) {
+        state.notify(Notification::warning("Iris is still suggesting a plan"));
+        return vec![];
+    }
+    let rebase = &mut state.modes.rebase;
+    // Whether the selection moved to another commit
+    let moved = match key.code {
+        KeyCode::Char('j') | KeyCode::Down => rebase.move_selection(1),
+        KeyCode::Char('k') | KeyCode::Up => rebase.move_selection(-1),
+        KeyCode::Char('g') | KeyCode::Home => rebase.move_selection(isize::MIN),
+        KeyCode::Char('G') | KeyCode::End => rebase.move_selection(isize::MAX),
+        KeyCode::Char('J') => rebase.move_step(true),
+        KeyCode::Char('K') => rebase.move_step(false),
+        KeyCode::Char('p') => {
+            rebase.set_action(RebaseAction::Pick);
+            false
+        }
+        KeyCode::Char('s') => {
+            rebase.set_action(RebaseAction::Squash);
+            false
+        }
+        KeyCode::Char('f') => {
+            rebase.set_action(RebaseAction::Fixup);
+            false
+        }
+        KeyCode::Char('d') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            rebase.set_action(RebaseAction::Drop);
+            false
+        }
+        KeyCode::Char('e') => return open_reword(state),
+        KeyCode::Char('u') => {
+            if let Some(plan) = &mut rebase.plan {
+                plan.reset();
+            }
+            state.notify(Notification::info("Plan reset to pick every commit"));
+            true
+        }
+        KeyCode::Enter => {
+            state.focused_panel = PanelId::Right;
+            state.mark_dirty();
+            return vec![];
+        }
+        _ => return vec![],
+    };
+    state.mark_dirty();
+    if moved && state.modes.rebase.needs_diff() {
+        vec![load_rebase_diff()]
+    } else {
+        vec![]
+    }
+}
+
+/// Keys that change the plan rather than move around it
+fn is_edit_key(code: KeyCode) -> bool {
+    matches!(
+        code,
+        KeyCode::Char('J' | 'K' | 'p' | 's' | 'f' | 'd' | 'e' | 'u')
+    )
+}
+
+fn handle_details_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let rebase = &mut state.modes.rebase;
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => rebase.details_scroll += 1,
+        KeyCode::Char('k') | KeyCode::Up => {
+            rebase.details_scroll = rebase.details_scroll.saturating_sub(1);
+        }
+        KeyCode::Char('g') | KeyCode::Home => rebase.details_scroll = 0,
+        _ => return vec![],
+    }
+    state.mark_dirty();
+    vec![]
+}
+
+fn handle_diff_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let diff_view = &mut state.modes.rebase.diff_view;
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => diff_view.scroll_down(1),
+        KeyCode::Char('k') | KeyCode::Up => diff_view.scroll_up(1),
+        KeyCode::PageDown => diff_view.scroll_down(20),
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            diff_view.scroll_down(20);
+        }
+        KeyCode::PageUp => diff_view.scroll_up(20),
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            diff_view.scroll_up(20);
+        }
+        KeyCode::Char(']') => diff_view.next_hunk(),
+        KeyCode::Char('[') => diff_view.prev_hunk(),
+        KeyCode::Char('n') => diff_view.next_file(),
+        KeyCode::Char('p') => diff_view.prev_file(),
+        _ => return vec![],
+    }
+    state.mark_dirty();
+    vec![]
+}
+
+/// Open the reword modal for the selected step
+fn open_reword(state: &mut StudioState) -> Vec<SideEffect> {
+    let Some(step) = state.modes.rebase.selected_step() else {
+        return vec![];
+    };
+    state.modal = Some(Modal::RebaseReword(Box::new(RebaseRewordState {
+        short_id: step.commit.short_id().to_string(),
+        subject: step.subject().to_string(),
+    })));
+    state.mark_dirty();
+    vec![]
+}
+
+/// Ask Iris for a plan, replacing the current one when it arrives
+fn suggest(state: &mut StudioState) -> Vec<SideEffect> {
+    let rebase = &mut state.modes.rebase;
+    if rebase.suggesting {
+        return vec![];
+    }
+    if rebase.steps().is_empty() {
+        state.notify(Notification::warning("No commits to rebase"));
+        return vec![];
+    }
+    rebase.suggesting = true;
+    state.notify(Notification::info("Iris is planning the rebase..."));
+    vec![SideEffect::SuggestRebase]
+}
+
+/// Ask before running the plan, since it rewrites the branch
+fn confirm_run(state: &mut StudioState) -> Vec<SideEffect> {
+    let rebase = &state.modes.rebase;
+    let Some(plan) = &rebase.plan else {
+        return vec![];
+    };
+    if rebase.suggesting {
+        state.notify(Notification::warning("Iris is still suggesting a plan"));
+        return vec![];
+    }
+    if plan.is_unchanged() {
+        state.notify(Notification::info("The plan leaves the branch as it is"));
+        return vec![];
+    }
+    if let Err(e) = plan.validate() {
+        state.notify(Notification::warning(e.to_string()));
+        return vec![];
+    }
+    state.modal = Some(Modal::Confirm {
+        message: format!(
+            "Rewrite {} as {} commits on {}?",
+            plan.branch,
+            plan.result_count(),
+            plan.base
+        ),
+        action: "run_rebase".to_string(),
+    });
+    state.mark_dirty();
+    vec![]
+}
@@ -1,7 +1,9 @@
 //! Release Notes mode key handling for Iris Studio
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::sync::Arc;
 
+use crate::outputs::Artifact;
 use crate::studio::events::SideEffect;
 use crate::studio::state::{Modal, PanelId, RefSelectorTarget, StudioState};
 
@@ -63,6 +65,7 @@ fn handle_commits_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
                 refs: state.get_branch_refs(),
                 selected: 0,
                 target: RefSelectorTarget::ReleaseNotesFrom,
+                revision: None,
             });
             state.mark_dirty();
             vec![]
@@ -74,6 +77,7 @@ fn handle_commits_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
                 refs: state.get_branch_refs(),
                 selected: 0,
                 target: RefSelectorTarget::ReleaseNotesTo,
+                revision: None,
             });
             state.mark_dirty();
             vec![]
@@ -219,9 +223,12 @@ fn handle_output_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
             }
             vec![]
         }
+        // Save to the configured [outputs] file
+        KeyCode::Char('w') => vec![SideEffect::SaveArtifact(Artifact::ReleaseNotes)],
         // Reset
         KeyCode::Char('R') => {
-            state.modes.release_notes.release_notes_content.clear();
+            state.modes.release_notes.release_notes_content = Arc::default();
+            state.modes.release_notes.suggested_version = None;
             state.modes.release_notes.release_notes_scroll = 0;
             state.mark_dirty();
             vec![]
@@ -1,11 +1,17 @@
 //! Review mode key handling for Iris Studio
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::PathBuf;
+use std::sync::Arc;
 
+use crate::outputs::Artifact;
 use crate::studio::events::SideEffect;
-use crate::studio::state::{Modal, PanelId, RefSelectorTarget, StudioState};
+use crate::studio::state::{
+    Modal, Notification, PanelId, RefSelectorTarget, ReviewExportState, StudioState,
+};
+use crate::types::Citation;
 
-use super::{copy_to_clipboard, spawn_review_task};
+use super::{copy_to_clipboard, open_in_explore, spawn_review_task};
 
 /// Handle key events in Review mode
 pub fn handle_review_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
@@ -78,6 +84,7 @@ fn handle_files_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
                 refs: state.get_branch_refs(),
                 selected: 0,
                 target: RefSelectorTarget::ReviewFrom,
+                revision: None,
             });
             state.mark_dirty();
             vec![]
@@ -89,6 +96,7 @@ fn handle_files_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
                 refs: state.get_branch_refs(),
                 selected: 0,
                 target: RefSelectorTarget::ReviewTo,
+                revision: None,
             });
             state.mark_dirty();
             vec![]
@@ -188,9 +196,18 @@ fn handle_output_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
             state.modes.review.generating = true;
             vec![spawn_review_task(state)]
         }
+        // Jump between findings
+        KeyCode::Char('n') => jump_to_finding(state, true),
+        KeyCode::Char('N') => jump_to_finding(state, false),
+        // Select and open cited sources
+        KeyCode::Char(']') => select_source(state, true),
+        KeyCode::Char('[') => select_source(state, false),
+        KeyCode::Char('o') | KeyCode::Enter => open_selected_source(state),
+        // Post findings to the branch's pull request
+        KeyCode::Char('c') => open_review_export(state),
         // Reset review
         KeyCode::Char('R') => {
-            state.modes.review.review_content.clear();
+            state.modes.review.set_content(Arc::default());
             state.modes.review.review_scroll = 0;
             state.mark_dirty();
             vec![]
@@ -203,6 +220,128 @@ fn handle_output_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect>
             }
             vec![]
         }
+        // Save to the configured [outputs] file
+        KeyCode::Char('w') => vec![SideEffect::SaveArtifact(Artifact::Review)],
         _ => vec![],
     }
 }
+
+/// Move to the next or previous finding, selecting its file and hunk
+fn jump_to_finding(state: &mut StudioState, forward: bool) -> Vec<SideEffect> {
+    let review = &mut state.modes.review;
+    let Some(index) = review.jump_to_finding(forward) else {
+        if !review.review_content.is_empty() {
+            state.notify(Notification::info(
+                "No findings with a severity in this review",
+            ));
+        }
+        return vec![];
+    };
+    let finding = &review.findings[index];
+    let mut message = format!(
+        "Finding {}/{}: [{}] {}",
+        index + 1,
+        review.findings.len(),
+        finding.severity.label(),
+        finding.title
+    );
+    if let Some(location) = &finding.location
+        && review
+            .diff_view
+            .current_diff()
+            .is_none_or(|d| !d.path.ends_with(&location.path))
+    {
+        message.push_str(&format!(" ({} isn't in this diff)", location.path));
+    }
+    state.notify(Notification::info(message));
+    state.mark_dirty();
+    vec![]
+}
+
+/// List the findings as they'd be posted to the reviewed branch's pull
+/// request, for confirmation
+fn open_review_export(state: &mut StudioState) -> Vec<SideEffect> {
+    let review = &state.modes.review;
+    if review.generating || review.posting {
+        return vec![];
+    }
+    if review.findings.is_empty() {
+        let message = if review.review_content.is_empty() {
+            "Generate a review first (press r)"
+        } else {
+            "No findings with a severity in this review"
+        };
+        state.notify(Notification::warning(message));
+        return vec![];
+    }
+    let head = if review.to_ref == "HEAD" {
+        state.git_status.branch.clone()
+    } else {
+        review.to_ref.clone()
+    };
+    if head.is_empty() || head == "HEAD detached" {
+        state.notify(Notification::warning(
+            "Review a branch (set one with t) to post to its pull request",
+        ));
+        return vec![];
+    }
+
+    let export = ReviewExportState::new(
+        &review.findings,
+        review.diff_view.diffs(),
+        head,
+        &review.from_ref,
+        &review.to_ref,
+    );
+    state.modal = Some(Modal::ReviewExport(Box::new(export)));
+    state.mark_dirty();
+    vec![]
+}
+
+/// Move the sources footer selection
+fn select_source(state: &mut StudioState, forward: bool) -> Vec<SideEffect> {
+    let review = &mut state.modes.review;
+    let Some(index) = review.select_source(forward) else {
+        if !review.review_content.is_empty() {
+            state.notify(Notification::info("This review cites no sources"));
+        }
+        return vec![];
+    };
+    let source = &review.sources[index];
+    let mut message = format!(
+        "Source {}/{}: {}",
+        index + 1,
+        review.sources.len(),
+        source.citation
+    );
+    if let Some(problem) = &source.problem {
+        message.push_str(&format!(" (unverified: {problem})"));
+    }
+    state.notify(Notification::info(message));
+    state.mark_dirty();
+    vec![]
+}
+
+/// Open the selected source: files in Explore at the cited line, commits
+/// by copying their hash
+fn open_selected_source(state: &mut StudioState) -> Vec<SideEffect> {
+    let review = &state.modes.review;
+    let Some(source) = review.selected_source.and_then(|i| review.sources.get(i)) else {
+        return vec![];
+    };
+    if let Some(problem) = &source.problem {
+        let message = format!("Can't open {}: {problem}", source.citation);
+        state.notify(Notification::warning(message));
+        return vec![];
+    }
+    match source.citation.clone() {
+        Citation::File {
+            path, start_line, ..
+        } => open_in_explore(state, PathBuf::from(path), start_line),
+        Citation::Commit { hash } => {
+            let summary = source.summary.clone().unwrap_or_default();
+            copy_to_clipboard(state, &hash, &format!("Commit {hash} ({summary})"));
+            vec![]
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Stash mode key handling for Iris Studio
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::studio::events::{DataType, SideEffect, StashAction};
+use crate::studio::state::{Modal, Notification, PanelId, StashCreateState, StudioState};
+
+/// Handle key events in Stash mode
+pub fn handle_stash_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    // In the diff, p steps back through files as in the other modes
+    let pop_key = state.focused_panel != PanelId::Right;
+    match key.code {
+        KeyCode::Char('a') => return run_action(state, StashAction::Apply),
+        KeyCode::Char('p') if pop_key => return run_action(state, StashAction::Pop),
+        KeyCode::Char('d') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            return confirm_drop(state);
+        }
+        KeyCode::Char('s') => return open_create(state, false),
+        KeyCode::Char('u') => return open_create(state, true),
+        KeyCode::Char('r') => {
+            state.notify(Notification::info("Refreshing stashes..."));
+            return vec![load_stashes()];
+        }
+        _ => {}
+    }
+    match state.focused_panel {
+        PanelId::Left => handle_list_key(state, key),
+        PanelId::Center => handle_files_key(state, key),
+        PanelId::Right => handle_diff_key(state, key),
+    }
+}
+
+/// Reload the stash list, and the selected stash's diff if it changed
+pub fn load_stashes() -> SideEffect {
+    SideEffect::LoadData {
+        data_type: DataType::Stashes,
+        from_ref: None,
+        to_ref: None,
+    }
+}
+
+fn handle_list_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let stash = &mut state.modes.stash;
+    let moved = match key.code {
+        KeyCode::Char('j') | KeyCode::Down => stash.move_selection(1),
+        KeyCode::Char('k') | KeyCode::Up => stash.move_selection(-1),
+        KeyCode::Char('g') | KeyCode::Home => stash.move_selection(isize::MIN),
+        KeyCode::Char('G') | KeyCode::End => stash.move_selection(isize::MAX),
+        KeyCode::Enter => {
+            state.focused_panel = PanelId::Right;
+            state.mark_dirty();
+            return vec![];
+        }
+        _ => return vec![],
+    };
+    state.mark_dirty();
+    if moved { vec![load_stashes()] } else { vec![] }
+}
+
+/// Sync file tree selection with the diff view
+fn sync_file_selection(state: &mut StudioState) {
+    if let Some(path) = state.modes.stash.file_tree.selected_path() {
+        state.modes.stash.diff_view.select_file_by_path(&path);
+    }
+}
+
+fn handle_files_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let file_tree = &mut state.modes.stash.file_tree;
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => file_tree.select_next(),
+        KeyCode::Char('k') | KeyCode::Up => file_tree.select_prev(),
+        KeyCode::Char('h') | KeyCode::Left => file_tree.collapse(),
+        KeyCode::Char('l') | KeyCode::Right => file_tree.expand(),
+        KeyCode::Char('g') | KeyCode::Home => file_tree.select_first(),
+        KeyCode::Char('G') | KeyCode::End => file_tree.select_last(),
+        KeyCode::Enter => {
+            if file_tree.selected_entry().is_some_and(|entry| entry.is_dir) {
+                file_tree.toggle_expand();
+            } else {
+                state.focused_panel = PanelId::Right;
+            }
+        }
+        _ => return vec![],
+    }
+    sync_file_selection(state);
+    state.mark_dirty();
+    vec![]
+}
+
+fn handle_diff_key(state: &mut StudioState, key: KeyEvent) -> Vec<SideEffect> {
+    let diff_view = &mut state.modes.stash.diff_view;
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => diff_view.scroll_down(1),
+        KeyCode::Char('k') | KeyCode::Up => diff_view.scroll_up(1),
+        KeyCode::PageDown => diff_view.scroll_down(20),
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            diff_view.scroll_down(20);
+        }
+        KeyCode::PageUp => diff_view.scroll_up(20),
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            diff_view.scroll_up(20);
+        }
+        KeyCode::Char(']') => diff_view.next_hunk(),
+        KeyCode::Char('[') => diff_view.prev_hunk(),
+        KeyCode::Char('n') => diff_view.next_file(),
+        KeyCode::Char('p') => diff_view.prev_file(),
+        _ => return vec![],
+    }
+    state.mark_dirty();
+    vec![]
+}
+
+/// Apply or pop the selected stash
+fn run_action(state: &mut StudioState, action: StashAction) -> Vec<SideEffect> {
+    let Some(entry) = state.modes.stash.selected_entry() else {
+        state.notify(Notification::warning("No stash selected"));
+        return vec![];
+    };
+    vec![SideEffect::RunStashAction {
+        action,
+        index: entry.index,
+        id: entry.id.clone(),
+    }]
+}
+
+/// Ask before dropping the selected stash, since it can't be undone here
+fn confirm_drop(state: &mut StudioState) -> Vec<SideEffect> {
+    let Some(entry) = state.modes.stash.selected_entry() else {
+        state.notify(Notification::warning("No stash selected"));
+        return vec![];
+    };
+    state.modal = Some(Modal::Confirm {
+        message: format!("Drop {}: {}?", entry.selector(), entry.description()),
+        action: "drop_stash".to_string(),
+    });
+    state.mark_dirty();
+    vec![]
+}
+
+/// Open the new stash modal and have Iris describe the changes
+fn open_create(state: &mut StudioState, include_untracked: bool) -> Vec<SideEffect> {
+    let status = &state.git_status;
+    let untracked = if include_untracked {
+        status.untracked_count
+    } else {
+        0
+    };
+    if status.staged_count + status.modified_count + untracked == 0 {
+        state.notify(Notification::warning("No local changes to stash"));
+        return vec![];
+    }
+    state.modal = Some(Modal::StashCreate(Box::new(StashCreateState::new(
+        include_untracked,
+    ))));
+    state.mark_dirty();
+    vec![SideEffect::DescribeStash { include_untracked }]
+}
@@ -10,11 +10,14 @@
 
 use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
 
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::agents::ContextReport;
+use crate::companion::MessageRevision;
 use crate::types::GeneratedMessage;
 
 use super::events::{ContentType, EventSource, TaskType, TimestampedEvent};
@@ -29,6 +32,9 @@ const MAX_CHAT_MESSAGES: usize = 500;
 /// Max content versions per (mode, `content_type`) key
 const MAX_CONTENT_VERSIONS: usize = 50;
 
+/// Max context size reports in history
+const MAX_CONTEXT_REPORTS: usize = 50;
+
 use super::state::Mode;
 use super::utils::truncate_chars;
 
@@ -114,6 +120,12 @@ pub struct History {
     /// Each entry contains all versions of that content
     content_versions: HashMap<ContentKey, Vec<ContentVersion>>,
 
+    /// Token breakdowns of finished generations, oldest first
+    context_reports: Vec<ContextReport>,
+
+    /// Commit messages replaced by amends, oldest first
+    message_revisions: Vec<MessageRevision>,
+
     /// Generation counter for unique IDs
     next_id: u64,
 }
@@ -133,6 +145,8 @@ impl History {
             max_events: 1000,
             chat_messages: Vec::new(),
             content_versions: HashMap::new(),
+            context_reports: Vec::new(),
+            message_revisions: Vec::new(),
             next_id: 1,
         }
     }
@@ -145,6 +159,8 @@ impl History {
             max_events: 1000,
             chat_messages: Vec::new(),
             content_versions: HashMap::new(),
+            context_reports: Vec::new(),
+            message_revisions: Vec::new(),
             next_id: 1,
         }
     }
@@ -356,10 +372,48 @@ impl History {
         self.push_entry(entry);
     }
 
+    /// Record the token breakdown of a finished generation
+    pub fn record_context_report(&mut self, report: ContextReport) {
+        self.context_reports.push(report);
+        if self.context_reports.len() > MAX_CONTEXT_REPORTS {
+            self.context_reports.remove(0);
+        }
+    }
+
+    /// Record a commit message replaced by an amend
+    pub fn record_message_revision(&mut self, revision: MessageRevision) {
+        self.touch();
+        let entry = HistoryEntry {
+            id: self.next_id(),
+            timestamp: Instant::now(),
+            source: EventSource::User,
+            change: HistoryChange::MessageRevised {
+                commit: revision.amended_commit.clone(),
+                preview: truncate_chars(&revision.revised, 50),
+                previous_preview: truncate_chars(&revision.original, 50),
+            },
+        };
+        self.push_entry(entry);
+        self.message_revisions.push(revision);
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // Query Methods
     // ─────────────────────────────────────────────────────────────────────────
 
+    /// Latest context size report, for `capability` if given
+    pub fn latest_context_report(&self, capability: Option<&str>) -> Option<&ContextReport> {
+        self.context_reports
+            .iter()
+            .rev()
+            .find(|r| capability.is_none_or(|c| r.capability == c))
+    }
+
+    /// Commit messages replaced by amends this session, oldest first
+    pub fn message_revisions(&self) -> &[MessageRevision] {
+        &self.message_revisions
+    }
+
     /// Get all chat messages
     pub fn chat_messages(&self) -> &[ChatMessage] {
         &self.chat_messages
@@ -415,6 +469,8 @@ impl History {
         self.events.clear();
         self.chat_messages.clear();
         self.content_versions.clear();
+        self.context_reports.clear();
+        self.message_revisions.clear();
     }
 
     /// Clear chat messages only
@@ -485,6 +541,13 @@ pub enum HistoryChange {
 
     /// Agent task completed
     AgentTaskCompleted { task_type: TaskType, success: bool },
+
+    /// A commit message was replaced by an amend
+    MessageRevised {
+        commit: String,
+        preview: String,
+        previous_preview: String,
+    },
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -521,8 +584,8 @@ pub enum ContentData {
     /// Structured commit message
     Commit(GeneratedMessage),
 
-    /// Markdown content (PR, review, changelog, etc.)
-    Markdown(String),
+    /// Markdown content (PR, review, changelog, etc.), shared with mode state
+    Markdown(Arc<str>),
 }
 
 impl ContentData {
@@ -531,21 +594,13 @@ impl ContentData {
         match self {
             Self::Commit(msg) => {
                 let full = format!("{} {}", msg.emoji.as_deref().unwrap_or(""), msg.title);
-                if full.len() > max_len {
-                    format!("{}...", &full[..max_len])
-                } else {
-                    full
-                }
+                truncate_chars(&full, max_len)
             }
             Self::Markdown(content) => {
                 // Get first non-empty line
                 let first_line = content.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
 
-                if first_line.len() > max_len {
-                    format!("{}...", &first_line[..max_len])
-                } else {
-                    first_line.to_string()
-                }
+                truncate_chars(first_line, max_len)
             }
         }
     }
@@ -561,7 +616,7 @@ impl ContentData {
                     format!("{} {}\n\n{}", emoji, msg.title, msg.message)
                 }
             }
-            Self::Markdown(content) => content.clone(),
+            Self::Markdown(content) => content.to_string(),
         }
     }
 }
@@ -637,6 +692,7 @@ mod tests {
             title: "Add new feature".to_string(),
             message: "Implement the thing".to_string(),
             completion_message: None,
+            variant: None,
         };
 
         history.record_content(
@@ -658,6 +714,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_latest_context_report() {
+        let report = |capability: &str, output_tokens| ContextReport {
+            capability: capability.to_string(),
+            model: "test-model".to_string(),
+            created_at: Utc::now(),
+            system_tokens: 100,
+            task_tokens: 50,
+            docs_tokens: 0,
+            diff_tokens: 400,
+            analysis_tokens: 200,
+            output_tokens,
+            tool_calls: 3,
+            billed_input_tokens: 0,
+            omitted: Vec::new(),
+        };
+        let mut history = History::new();
+        assert!(history.latest_context_report(None).is_none());
+
+        history.record_context_report(report("commit", 1));
+        history.record_context_report(report("review", 2));
+        history.record_context_report(report("commit", 3));
+
+        let latest = |capability| {
+            history
+                .latest_context_report(capability)
+                .map(|r| r.output_tokens)
+        };
+        assert_eq!(latest(Some("commit")), Some(3));
+        assert_eq!(latest(Some("review")), Some(2));
+        assert_eq!(latest(Some("pr")), None);
+        assert_eq!(latest(None), Some(3));
+    }
+
+    #[test]
+    fn test_record_message_revision() {
+        let mut history = History::new();
+        history.record_message_revision(MessageRevision::new(
+            "aaa1111".to_string(),
+            "bbb2222".to_string(),
+            "Fix parser".to_string(),
+            "Fix off-by-one in the TOML parser".to_string(),
+        ));
+
+        assert_eq!(history.message_revisions().len(), 1);
+        assert!(history.message_revisions()[0].subject_changed());
+        assert!(matches!(
+            &history.events().last().map(|e| &e.change),
+            Some(HistoryChange::MessageRevised { commit, .. }) if commit == "bbb2222"
+        ));
+    }
+
     #[test]
     fn test_content_preview() {
         let msg = GeneratedMessage {
@@ -665,6 +773,7 @@ mod tests {
             title: "Fix the bug".to_string(),
             message: "Details here".to_string(),
             completion_message: None,
+            variant: None,
         };
 
         let data = ContentData::Commit(msg);
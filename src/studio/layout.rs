@@ -1,8 +1,9 @@
 //! Layout management for Iris Studio
 //!
-//! Panel layout calculations and constraints.
+//! Panel layout calculations and constraints, including the narrower
+//! arrangements used when three panels don't fit.
 
-#![allow(dead_code)] // Layout helpers are scaffolded for responsive features
+#![allow(dead_code)] // Panel metadata is kept for layouts that don't read it yet
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
@@ -54,6 +55,9 @@ pub fn get_mode_layout(mode: Mode) -> ModeLayout {
         Mode::PR => pr_layout(),
         Mode::Changelog => changelog_layout(),
         Mode::ReleaseNotes => release_notes_layout(),
+        Mode::Dashboard => dashboard_layout(),
+        Mode::Stash => stash_layout(),
+        Mode::Rebase => rebase_layout(),
     }
 }
 
@@ -237,10 +241,143 @@ fn release_notes_layout() -> ModeLayout {
     }
 }
 
+fn dashboard_layout() -> ModeLayout {
+    ModeLayout {
+        panels: vec![
+            PanelConfig {
+                id: PanelId::Left,
+                title: "Activity",
+                focusable: true,
+                min_width: 30,
+            },
+            PanelConfig {
+                id: PanelId::Center,
+                title: "Hot Files",
+                focusable: true,
+                min_width: 35,
+            },
+            PanelConfig {
+                id: PanelId::Right,
+                title: "Contributors",
+                focusable: true,
+                min_width: 25,
+            },
+        ],
+        constraints: vec![
+            Constraint::Percentage(40),
+            Constraint::Percentage(35),
+            Constraint::Percentage(25),
+        ],
+    }
+}
+
+fn stash_layout() -> ModeLayout {
+    ModeLayout {
+        panels: vec![
+            PanelConfig {
+                id: PanelId::Left,
+                title: "Stashes",
+                focusable: true,
+                min_width: 30,
+            },
+            PanelConfig {
+                id: PanelId::Center,
+                title: "Files",
+                focusable: true,
+                min_width: 20,
+            },
+            PanelConfig {
+                id: PanelId::Right,
+                title: "Diff",
+                focusable: true,
+                min_width: 40,
+            },
+        ],
+        constraints: vec![
+            Constraint::Percentage(30),
+            Constraint::Percentage(20),
+            Constraint::Percentage(50),
+        ],
+    }
+}
+
+fn rebase_layout() -> ModeLayout {
+    ModeLayout {
+        panels: vec![
+            PanelConfig {
+                id: PanelId::Left,
+                title: "Plan",
+                focusable: true,
+                min_width: 40,
+            },
+            PanelConfig {
+                id: PanelId::Center,
+                title: "Details",
+                focusable: true,
+                min_width: 25,
+            },
+            PanelConfig {
+                id: PanelId::Right,
+                title: "Diff",
+                focusable: true,
+                min_width: 40,
+            },
+        ],
+        constraints: vec![
+            Constraint::Percentage(40),
+            Constraint::Percentage(25),
+            Constraint::Percentage(35),
+        ],
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Layout Calculation
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Narrowest terminal Studio renders in
+pub const MIN_WIDTH: u16 = 40;
+
+/// Shortest terminal Studio renders in
+pub const MIN_HEIGHT: u16 = 12;
+
+/// How many panels fit side by side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arrangement {
+    /// All three panels
+    Three,
+    /// The focused panel and a neighbor
+    Two,
+    /// Only the focused panel
+    Single,
+}
+
+impl Arrangement {
+    /// Arrangement for a terminal `width` columns wide
+    pub fn for_width(width: u16) -> Self {
+        if is_wide_layout(width) {
+            Self::Three
+        } else if is_narrow_layout(width) {
+            Self::Single
+        } else {
+            Self::Two
+        }
+    }
+
+    /// Panels shown while `focused` has focus, left to right
+    ///
+    /// Two panels slide over the three so the focused one stays visible:
+    /// left and center, or center and right when the right panel has focus.
+    pub fn visible(self, focused: PanelId) -> Vec<PanelId> {
+        match (self, focused) {
+            (Self::Three, _) => vec![PanelId::Left, PanelId::Center, PanelId::Right],
+            (Self::Two, PanelId::Right) => vec![PanelId::Center, PanelId::Right],
+            (Self::Two, _) => vec![PanelId::Left, PanelId::Center],
+            (Self::Single, panel) => vec![panel],
+        }
+    }
+}
+
 /// Calculated layout areas
 #[derive(Debug, Clone)]
 pub struct LayoutAreas {
@@ -250,16 +387,28 @@ pub struct LayoutAreas {
     pub tabs: Rect,
     /// Main content area (panels)
     pub content: Rect,
-    /// Individual panel areas
+    /// Individual panel areas, left to right; hidden panels are empty
     pub panels: Vec<Rect>,
+    /// How many panels are shown
+    pub arrangement: Arrangement,
+    /// Row listing every panel when some are hidden
+    pub switcher: Option<Rect>,
     /// Companion status bar (explore mode only)
     pub companion_bar: Option<Rect>,
     /// Status bar area
     pub status: Rect,
 }
 
+/// Whether `area` is below the size Studio can render in
+pub fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
 /// Calculate layout areas for the given terminal size
-pub fn calculate_layout(area: Rect, mode: Mode) -> LayoutAreas {
+///
+/// Below 100 columns only the focused panel and a neighbor are shown, and
+/// below 80 only the focused panel, with a switcher row naming the others.
+pub fn calculate_layout(area: Rect, mode: Mode, focused: PanelId) -> LayoutAreas {
     // Explore mode gets an extra companion status bar
     let has_companion_bar = matches!(mode, Mode::Explore);
 
@@ -268,7 +417,7 @@ pub fn calculate_layout(area: Rect, mode: Mode) -> LayoutAreas {
         vec![
             Constraint::Length(1), // Header
             Constraint::Length(2), // Tabs
-            Constraint::Min(10),   // Content
+            Constraint::Min(6),    // Content
             Constraint::Length(1), // Companion bar
             Constraint::Length(1), // Status
         ]
@@ -276,7 +425,7 @@ pub fn calculate_layout(area: Rect, mode: Mode) -> LayoutAreas {
         vec![
             Constraint::Length(1), // Header
             Constraint::Length(2), // Tabs
-            Constraint::Min(10),   // Content
+            Constraint::Min(6),    // Content
             Constraint::Length(1), // Status
         ]
     };
@@ -304,27 +453,60 @@ pub fn calculate_layout(area: Rect, mode: Mode) -> LayoutAreas {
         )
     };
 
-    // Get mode-specific layout
-    let mode_layout = get_mode_layout(mode);
+    let arrangement = Arrangement::for_width(area.width);
+    let (switcher, panel_area) = if arrangement == Arrangement::Three {
+        (None, content)
+    } else {
+        let rows = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).split(content);
+        (Some(rows[0]), rows[1])
+    };
 
-    // Split content into panels
-    let panel_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints(mode_layout.constraints.clone())
-        .split(content);
+    // Split the visible panels by the mode's proportions
+    let mode_layout = get_mode_layout(mode);
+    let visible = arrangement.visible(focused);
+    let widths: Vec<Constraint> = visible
+        .iter()
+        .map(|id| {
+            let index = panel_index(*id);
+            let share = |c: &Constraint| match c {
+                Constraint::Percentage(p) => u32::from(*p),
+                _ => 1,
+            };
+            let total: u32 = visible
+                .iter()
+                .map(|v| share(&mode_layout.constraints[panel_index(*v)]))
+                .sum();
+            Constraint::Ratio(share(&mode_layout.constraints[index]), total.max(1))
+        })
+        .collect();
+    let chunks = Layout::horizontal(widths).split(panel_area);
 
-    let panels = panel_chunks.to_vec();
+    let mut panels = vec![Rect::default(); 3];
+    for (id, rect) in visible.iter().zip(chunks.iter()) {
+        panels[panel_index(*id)] = *rect;
+    }
 
     LayoutAreas {
         header,
         tabs,
         content,
         panels,
+        arrangement,
+        switcher,
         companion_bar,
         status,
     }
 }
 
+/// Position of a panel, left to right
+pub fn panel_index(id: PanelId) -> usize {
+    match id {
+        PanelId::Left => 0,
+        PanelId::Center => 1,
+        PanelId::Right => 2,
+    }
+}
+
 /// Calculate inner area for panel content (with border padding)
 pub fn panel_inner(area: Rect) -> Rect {
     // Account for border (1 char each side)
@@ -345,30 +527,40 @@ pub fn is_wide_layout(width: u16) -> bool {
     width >= 100
 }
 
-/// Check if terminal is too narrow for comfortable use
+/// Check if terminal is too narrow for two panels side by side
 pub fn is_narrow_layout(width: u16) -> bool {
     width < 80
 }
 
-/// Get appropriate layout constraints based on terminal width
-pub fn responsive_constraints(width: u16, mode: Mode) -> Vec<Constraint> {
-    let default = get_mode_layout(mode).constraints;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if is_narrow_layout(width) {
-        // Narrow: hide right panel, expand center
-        vec![
-            Constraint::Percentage(25),
-            Constraint::Percentage(75),
-            Constraint::Length(0),
-        ]
-    } else if !is_wide_layout(width) {
-        // Medium: slightly different ratios
-        vec![
-            Constraint::Percentage(22),
-            Constraint::Percentage(48),
-            Constraint::Percentage(30),
-        ]
-    } else {
-        default
+    fn widths(width: u16, focused: PanelId) -> Vec<u16> {
+        let layout = calculate_layout(Rect::new(0, 0, width, 40), Mode::Commit, focused);
+        layout.panels.iter().map(|rect| rect.width).collect()
+    }
+
+    #[test]
+    fn test_panels_follow_focus_as_width_shrinks() {
+        assert!(widths(120, PanelId::Left).iter().all(|w| *w > 0));
+
+        // Two panels slide to keep the focused one visible
+        let two = widths(90, PanelId::Right);
+        assert_eq!(two[0], 0);
+        assert_eq!(two[1] + two[2], 90);
+        assert_eq!(widths(90, PanelId::Left)[2], 0);
+
+        assert_eq!(widths(60, PanelId::Center), [0, 60, 0]);
+        let layout = calculate_layout(Rect::new(0, 0, 60, 40), Mode::Commit, PanelId::Center);
+        assert_eq!(layout.arrangement, Arrangement::Single);
+        assert!(layout.switcher.is_some());
+    }
+
+    #[test]
+    fn test_minimum_size() {
+        assert!(is_too_small(Rect::new(0, 0, 39, 40)));
+        assert!(is_too_small(Rect::new(0, 0, 120, 11)));
+        assert!(!is_too_small(Rect::new(0, 0, MIN_WIDTH, MIN_HEIGHT)));
     }
 }
@@ -16,6 +16,7 @@
 #![allow(clippy::trivially_copy_pass_by_ref)] // consistency with ratatui APIs
 
 mod app;
+mod code_context;
 mod events;
 mod handlers;
 mod history;
@@ -25,10 +26,11 @@ mod render;
 mod state;
 mod theme;
 pub mod utils;
+mod variants;
 
 // Submodules
 pub mod components;
 
 // Re-exports
-pub use app::{ExitResult, StudioApp, run_studio};
+pub use app::{ExitResult, StudioApp, run_studio, run_studio_with_document};
 pub use state::{Mode, StudioState};
@@ -2,9 +2,13 @@
 //!
 //! Handles events from the agent: progress updates, completions, errors, streaming.
 
+use std::sync::Arc;
+
 use super::super::events::{AgentResult, EventSource, TaskType};
 use super::super::history::{ChatRole, ContentData, History};
 use super::super::state::{Mode, Notification, StudioState};
+use crate::companion::DraftKind;
+use crate::studio::components::message_editor::format_message;
 use crate::studio::events::ContentType;
 
 /// Handle `AgentStarted` event
@@ -52,6 +56,9 @@ pub fn agent_complete(
                 .commit
                 .message_editor
                 .add_messages(messages.clone());
+            for msg in &messages {
+                state.record_message_draft(&format_message(msg), DraftKind::Generated);
+            }
 
             // Record in history
             if let Some(msg) = messages.first() {
@@ -66,7 +73,9 @@ pub fn agent_complete(
         }
 
         AgentResult::ReviewContent(content) => {
-            state.modes.review.review_content.clone_from(&content);
+            let content: Arc<str> = content.into();
+            state.modes.review.set_content(Arc::clone(&content));
+            state.modes.review.sources = state.verify_sources(&content);
             state.modes.review.generating = false;
 
             history.record_content(
@@ -79,7 +88,8 @@ pub fn agent_complete(
         }
 
         AgentResult::PRContent(content) => {
-            state.modes.pr.pr_content.clone_from(&content);
+            let content: Arc<str> = content.into();
+            state.modes.pr.pr_content = Arc::clone(&content);
             state.modes.pr.generating = false;
 
             history.record_content(
@@ -92,7 +102,8 @@ pub fn agent_complete(
         }
 
         AgentResult::ChangelogContent(content) => {
-            state.modes.changelog.changelog_content.clone_from(&content);
+            let content: Arc<str> = content.into();
+            state.modes.changelog.changelog_content = Arc::clone(&content);
             state.modes.changelog.generating = false;
 
             history.record_content(
@@ -104,12 +115,13 @@ pub fn agent_complete(
             );
         }
 
-        AgentResult::ReleaseNotesContent(content) => {
-            state
-                .modes
-                .release_notes
-                .release_notes_content
-                .clone_from(&content);
+        AgentResult::ReleaseNotesContent {
+            content,
+            suggested_version,
+        } => {
+            let content: Arc<str> = content.into();
+            state.modes.release_notes.release_notes_content = Arc::clone(&content);
+            state.modes.release_notes.suggested_version = suggested_version;
             state.modes.release_notes.generating = false;
 
             history.record_content(
@@ -126,10 +138,12 @@ pub fn agent_complete(
             history.add_chat_message(ChatRole::Iris, &response);
 
             // Update chat state
-            state.chat_state.add_iris_response(&response);
+            let sources = state.verify_sources(&response);
+            state.chat_state.add_iris_response(&response, sources);
         }
 
         AgentResult::SemanticBlame(result) => {
+            state.modes.explore.blame_sources = state.verify_sources(&result.explanation);
             state.modes.explore.semantic_blame = Some(result);
             state.modes.explore.blame_loading = false;
             state.notify(Notification::success("Blame analysis complete"));
@@ -149,20 +163,7 @@ pub fn agent_error(
     state.set_iris_error(error);
     history.record_agent_complete(task_type, false);
 
-    // Reset generating flags
-    match task_type {
-        TaskType::Commit => state.modes.commit.generating = false,
-        TaskType::Review => state.modes.review.generating = false,
-        TaskType::PR => state.modes.pr.generating = false,
-        TaskType::Changelog => state.modes.changelog.generating = false,
-        TaskType::ReleaseNotes => state.modes.release_notes.generating = false,
-        TaskType::Chat => {
-            state.chat_state.is_responding = false;
-        }
-        TaskType::SemanticBlame => {
-            state.modes.explore.blame_loading = false;
-        }
-    }
+    state.stop_generating(task_type);
 
     state.notify(Notification::error(format!(
         "{} failed: {}",
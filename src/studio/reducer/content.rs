@@ -2,16 +2,105 @@
 //!
 //! Handles tool-triggered content updates (from chat tools updating UI).
 
+use std::sync::Arc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::super::components::message_drift::line_diff;
+use super::super::components::message_editor::format_message;
 use super::super::events::{ContentPayload, ContentType, EventSource, SideEffect};
 use super::super::history::{ContentData, History};
-use super::super::state::{Mode, Notification, StudioState};
+use super::super::state::{Modal, Mode, Notification, ProposedUpdate, StudioState};
 
 /// Handle `UpdateContent` event (tool-triggered)
+///
+/// Empty or unchanged content is replaced directly. Anything else becomes a
+/// proposal the chat modal shows as a diff, so text the user may have edited
+/// is only replaced once they accept.
 pub fn update_content(
     state: &mut StudioState,
     history: &mut History,
     content_type: ContentType,
     content: ContentPayload,
+) {
+    let current = current_text(state, content_type);
+    let proposed = match &content {
+        ContentPayload::Commit(msg) => format_message(msg),
+        ContentPayload::Markdown(markdown) => markdown.clone(),
+    };
+    if current.trim().is_empty() || current.trim() == proposed.trim() {
+        apply_content(state, history, content_type, content);
+        return;
+    }
+
+    let proposal = ProposedUpdate {
+        content_type,
+        content,
+        current,
+        proposed,
+    };
+    if !matches!(state.modal, Some(Modal::Chat)) {
+        state.notify(Notification::info(format!(
+            "Iris proposed a new {}; open chat to review it",
+            proposal.label()
+        )));
+    }
+    state.chat_state.propose(proposal);
+    state.mark_dirty();
+}
+
+/// Accept or reject the proposal shown in the chat modal
+pub fn resolve_proposal(state: &mut StudioState, history: &mut History, accept: bool) {
+    let Some(proposal) = state.chat_state.take_proposal() else {
+        return;
+    };
+    let label = proposal.label();
+    if accept {
+        apply_content(state, history, proposal.content_type, proposal.content);
+        state.notify(Notification::success(format!("Applied the new {label}")));
+    } else {
+        state.notify(Notification::info(format!("Kept your {label}")));
+    }
+    state.mark_dirty();
+}
+
+/// Keys while a proposal is shown: accept, reject or scroll the diff
+pub fn proposal_key(state: &mut StudioState, history: &mut History, key: KeyEvent) {
+    let Some(proposal) = state.chat_state.proposals.front() else {
+        return;
+    };
+    let max_scroll = line_diff(&proposal.current, &proposal.proposed)
+        .len()
+        .saturating_sub(1);
+    let scroll = &mut state.chat_state.proposal_scroll;
+    match key.code {
+        KeyCode::Char('a' | 'y') | KeyCode::Enter => resolve_proposal(state, history, true),
+        KeyCode::Char('r' | 'n') | KeyCode::Esc => resolve_proposal(state, history, false),
+        KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => *scroll = (*scroll + 1).min(max_scroll),
+        KeyCode::PageUp => *scroll = scroll.saturating_sub(10),
+        KeyCode::PageDown => *scroll = (*scroll + 10).min(max_scroll),
+        _ => return,
+    }
+    state.mark_dirty();
+}
+
+/// The content an update would replace, as the user sees it
+fn current_text(state: &StudioState, content_type: ContentType) -> String {
+    match content_type {
+        ContentType::CommitMessage => state.modes.commit.message_editor.get_message(),
+        ContentType::PRDescription => state.modes.pr.pr_content.to_string(),
+        ContentType::CodeReview => state.modes.review.review_content.to_string(),
+        ContentType::Changelog => state.modes.changelog.changelog_content.to_string(),
+        ContentType::ReleaseNotes => state.modes.release_notes.release_notes_content.to_string(),
+    }
+}
+
+fn apply_content(
+    state: &mut StudioState,
+    history: &mut History,
+    content_type: ContentType,
+    content: ContentPayload,
 ) {
     match (content_type, content) {
         (ContentType::CommitMessage, ContentPayload::Commit(msg)) => {
@@ -43,7 +132,8 @@ pub fn update_content(
         }
 
         (ContentType::PRDescription, ContentPayload::Markdown(content)) => {
-            state.modes.pr.pr_content.clone_from(&content);
+            let content: Arc<str> = content.into();
+            state.modes.pr.pr_content = Arc::clone(&content);
 
             history.record_content(
                 Mode::PR,
@@ -55,7 +145,9 @@ pub fn update_content(
         }
 
         (ContentType::CodeReview, ContentPayload::Markdown(content)) => {
-            state.modes.review.review_content.clone_from(&content);
+            let content: Arc<str> = content.into();
+            state.modes.review.set_content(Arc::clone(&content));
+            state.modes.review.sources = state.verify_sources(&content);
 
             history.record_content(
                 Mode::Review,
@@ -67,7 +159,8 @@ pub fn update_content(
         }
 
         (ContentType::Changelog, ContentPayload::Markdown(content)) => {
-            state.modes.changelog.changelog_content.clone_from(&content);
+            let content: Arc<str> = content.into();
+            state.modes.changelog.changelog_content = Arc::clone(&content);
 
             history.record_content(
                 Mode::Changelog,
@@ -79,11 +172,8 @@ pub fn update_content(
         }
 
         (ContentType::ReleaseNotes, ContentPayload::Markdown(content)) => {
-            state
-                .modes
-                .release_notes
-                .release_notes_content
-                .clone_from(&content);
+            let content: Arc<str> = content.into();
+            state.modes.release_notes.release_notes_content = Arc::clone(&content);
 
             history.record_content(
                 Mode::ReleaseNotes,
@@ -121,3 +121,29 @@ pub fn toggle_global_log(state: &mut StudioState) -> Vec<SideEffect> {
     state.mark_dirty();
     vec![]
 }
+
+/// Handle `HeatMapLoaded` event
+pub fn heat_map_loaded(
+    state: &mut StudioState,
+    data: std::sync::Arc<crate::studio::components::HeatMapData>,
+) {
+    let explore = &mut state.modes.explore;
+    explore.heat_map_data = Some(data);
+    explore.heat_map_loaded_at = Some(std::time::Instant::now());
+    explore.heat_map_loading = false;
+    explore.sync_heat_overlay();
+    state.mark_dirty();
+}
+
+/// Handle `DashboardLoaded` event
+pub fn dashboard_loaded(
+    state: &mut StudioState,
+    data: std::sync::Arc<crate::studio::state::DashboardData>,
+) {
+    let dashboard = &mut state.modes.dashboard;
+    dashboard.data = Some(data);
+    dashboard.loaded_at = Some(std::time::Instant::now());
+    dashboard.loading = false;
+    dashboard.move_selection(0);
+    state.mark_dirty();
+}
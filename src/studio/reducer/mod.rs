@@ -17,11 +17,16 @@ mod ui;
 
 use crossterm::event::MouseEventKind;
 
+use crate::config::AutoTrigger;
+
+use super::components::{FileBlame, HeatMapMode};
 use super::events::{
     AgentTask, ChatContext, DataType, ModalType, ScrollDirection, SideEffect, StudioEvent, TaskType,
 };
 use super::history::{ChatRole, History};
-use super::state::{EmojiMode, Modal, Mode, StudioState};
+use super::state::{
+    ConnectionStatus, EmojiMode, ExploreState, Modal, Mode, Notification, StudioState,
+};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Reducer Function
@@ -110,6 +115,16 @@ pub fn reduce(
                             to_ref: Some(to),
                         });
                     }
+                    Mode::Dashboard => {
+                        effects.extend(super::handlers::load_dashboard(state, false));
+                    }
+                    Mode::Stash => effects.push(super::handlers::load_stashes()),
+                    Mode::Rebase => {
+                        // Keep an edited plan; R reloads it
+                        if state.modes.rebase.plan.is_none() {
+                            effects.push(super::handlers::load_rebase_plan());
+                        }
+                    }
                     Mode::Explore => {
                         // Load explore file tree if not already loaded
                         if state.modes.explore.file_tree.is_empty() {
@@ -121,6 +136,9 @@ pub fn reduce(
                         }
                     }
                 }
+                if state.auto_trigger(new_mode) != AutoTrigger::Manual {
+                    effects.push(SideEffect::AutoGenerate(new_mode));
+                }
             }
         }
 
@@ -229,6 +247,7 @@ pub fn reduce(
                 mode: state.active_mode,
                 current_content: get_current_content(state),
                 diff_summary: get_diff_summary(state),
+                code_context: state.explore_code_context(),
             };
 
             effects.push(SideEffect::SpawnAgent {
@@ -349,6 +368,208 @@ pub fn reduce(
             effects.extend(git::toggle_global_log(state));
         }
 
+        StudioEvent::HeatMapLoaded { data } => {
+            git::heat_map_loaded(state, data);
+        }
+
+        StudioEvent::DashboardLoaded { data } => {
+            git::dashboard_loaded(state, data);
+        }
+
+        StudioEvent::ModelsLoaded { provider, result } => {
+            if let Some(Modal::ModelPicker(picker)) = &mut state.modal
+                && picker.provider == provider
+            {
+                picker.set_models(result);
+                state.mark_dirty();
+            }
+        }
+
+        StudioEvent::PickaxeLoaded {
+            query,
+            kind,
+            result,
+        } => {
+            if let Some(Modal::Pickaxe(search)) = &mut state.modal {
+                search.set_results(&query, kind, result);
+                state.mark_dirty();
+            }
+        }
+
+        StudioEvent::SemanticSearchLoaded { query, result } => {
+            // Ignore answers to a question that was dismissed or replaced
+            if let Some(search) = &mut state.modes.explore.search
+                && search.query == query
+                && search.loading
+            {
+                search.set_results(result);
+                match (search.error.clone(), search.results.len()) {
+                    (Some(e), _) => state.set_iris_error(format!("Search failed: {e}")),
+                    (None, 0) => state.set_iris_complete("Nothing found."),
+                    (None, count) => {
+                        state.set_iris_complete(format!("Found {count} places."));
+                    }
+                }
+                state.mark_dirty();
+            }
+        }
+
+        StudioEvent::PullRequestCreated { result } => {
+            state.modes.pr.opening = false;
+            match result {
+                Ok((number, url)) => {
+                    state.set_iris_complete(format!("Opened PR #{number}"));
+                    let copied = crate::clipboard::copy_text(&url, state.config.clipboard_osc52);
+                    state.notify(Notification::success(if copied.is_ok() {
+                        format!("Opened PR #{number} (URL copied): {url}")
+                    } else {
+                        format!("Opened PR #{number}: {url}")
+                    }));
+                }
+                Err(e) => {
+                    state.set_iris_error(&e);
+                    state.notify(Notification::error(format!(
+                        "Couldn't open pull request: {e}"
+                    )));
+                }
+            }
+        }
+
+        StudioEvent::CiStatusLoaded { head, result } => {
+            let pr = &mut state.modes.pr;
+            // Drop answers for a branch the user has since moved away from
+            if pr.head_branch(&state.git_status.branch).as_deref() == Some(head.as_str()) {
+                pr.ci_loading = false;
+                pr.ci = Some(result);
+                state.mark_dirty();
+            }
+        }
+
+        StudioEvent::ReviewPosted { result } => {
+            state.modes.review.posting = false;
+            match result {
+                Ok((number, url)) => {
+                    state.set_iris_complete(format!("Posted review to PR #{number}"));
+                    state.notify(Notification::success(format!(
+                        "Posted review to PR #{number}: {url}"
+                    )));
+                }
+                Err(e) => {
+                    state.set_iris_error(&e);
+                    state.notify(Notification::error(format!("Couldn't post review: {e}")));
+                }
+            }
+        }
+
+        StudioEvent::StashDescribed { result } => {
+            // The modal may have been closed while Iris was writing
+            if let Some(Modal::StashCreate(create)) = &mut state.modal {
+                match result {
+                    Ok(description) => create.set_description(&description),
+                    Err(e) => {
+                        create.describing = false;
+                        state.notify(Notification::warning(format!("No stash description: {e}")));
+                    }
+                }
+                state.mark_dirty();
+            }
+        }
+
+        StudioEvent::RepoOverviewReady { result } => {
+            state.modes.explore.overview_loading = false;
+            match result {
+                Ok(path) => {
+                    state.set_iris_complete("Architecture overview ready.");
+                    effects.extend(super::handlers::open_in_explore(state, path, None));
+                }
+                Err(e) => state.set_iris_error(format!("Overview failed: {e}")),
+            }
+            state.mark_dirty();
+        }
+
+        StudioEvent::FileBlameLoaded {
+            file,
+            result,
+            explaining,
+        } => {
+            let explore = &mut state.modes.explore;
+            explore.file_blame_loading = false;
+            match result {
+                // Ignore blame for a file that's no longer open
+                Ok(regions) if explore.code_view.current_file() == Some(file.as_path()) => {
+                    explore
+                        .code_view
+                        .set_blame(Some(FileBlame::new(regions, explaining)));
+                    if explaining {
+                        state.set_iris_thinking("Asking why each commit wrote its lines...");
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => state.notify(Notification::error(format!("Blame failed: {e}"))),
+            }
+            state.mark_dirty();
+        }
+
+        StudioEvent::FileBlameExplained { file, result } => {
+            let code_view = &mut state.modes.explore.code_view;
+            let open = code_view.current_file() == Some(file.as_path());
+            let error = match code_view.blame_mut() {
+                Some(blame) if open => blame.set_whys(result),
+                _ => result.err(),
+            };
+            match error {
+                Some(e) => state.set_iris_error(format!("Blame failed: {e}")),
+                None => state.set_iris_complete("Blame ready."),
+            }
+            state.mark_dirty();
+        }
+
+        StudioEvent::RecapReady { branch, result } => match result {
+            // Don't cover something the user already opened
+            Ok(text) if state.modal.is_none() => {
+                state.modal = Some(Modal::Recap { branch, text });
+                state.mark_dirty();
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Recap failed: {e}"),
+        },
+
+        StudioEvent::RebaseSuggested { result } => {
+            let rebase = &mut state.modes.rebase;
+            rebase.suggesting = false;
+            let applied = match (result, &mut rebase.plan) {
+                (Ok(suggestion), Some(plan)) => plan
+                    .apply_suggestion(&suggestion)
+                    .map(|()| plan.result_count())
+                    .map_err(|e| e.to_string()),
+                (Ok(_), None) => Err("the plan was reloaded".to_string()),
+                (Err(e), _) => Err(e),
+            };
+            match applied {
+                Ok(count) => {
+                    rebase.selected = 0;
+                    state.notify(Notification::success(format!(
+                        "Iris suggested a plan with {count} commits"
+                    )));
+                    effects.push(super::handlers::load_rebase_diff());
+                }
+                Err(e) => {
+                    state.notify(Notification::warning(format!("No rebase suggestion: {e}")));
+                }
+            }
+            state.mark_dirty();
+        }
+
+        StudioEvent::ConnectionTested { result } => {
+            if let Some(Modal::Settings(settings)) = &mut state.modal {
+                settings.connection = Some(match result {
+                    Ok(ms) => ConnectionStatus::Ok(ms),
+                    Err(e) => ConnectionStatus::Failed(e),
+                });
+                state.mark_dirty();
+            }
+        }
+
         // ─────────────────────────────────────────────────────────────────────────
         // Modal Events
         // ─────────────────────────────────────────────────────────────────────────
@@ -489,6 +710,16 @@ pub fn reduce(
             // Refresh status first
             effects.push(SideEffect::RefreshGitStatus);
 
+            // New commits change churn - expire the heat map cache and reload if visible
+            let explore = &mut state.modes.explore;
+            explore.heat_map_loaded_at = None;
+            if explore.heat_map_mode != HeatMapMode::Off && !explore.heat_map_loading {
+                explore.heat_map_loading = true;
+                effects.push(SideEffect::LoadHeatMap {
+                    window_days: ExploreState::HEAT_MAP_WINDOW_DAYS,
+                });
+            }
+
             // Check if branch changed
             if let Some(repo) = &state.repo
                 && let Ok(new_branch) = repo.get_current_branch()
@@ -582,28 +813,28 @@ fn get_current_content(state: &StudioState) -> Option<String> {
             if state.modes.review.review_content.is_empty() {
                 None
             } else {
-                Some(state.modes.review.review_content.clone())
+                Some(state.modes.review.review_content.to_string())
             }
         }
         Mode::PR => {
             if state.modes.pr.pr_content.is_empty() {
                 None
             } else {
-                Some(state.modes.pr.pr_content.clone())
+                Some(state.modes.pr.pr_content.to_string())
             }
         }
         Mode::Changelog => {
             if state.modes.changelog.changelog_content.is_empty() {
                 None
             } else {
-                Some(state.modes.changelog.changelog_content.clone())
+                Some(state.modes.changelog.changelog_content.to_string())
             }
         }
         Mode::ReleaseNotes => {
             if state.modes.release_notes.release_notes_content.is_empty() {
                 None
             } else {
-                Some(state.modes.release_notes.release_notes_content.clone())
+                Some(state.modes.release_notes.release_notes_content.to_string())
             }
         }
         Mode::Explore => state
@@ -612,6 +843,18 @@ fn get_current_content(state: &StudioState) -> Option<String> {
             .current_file
             .as_ref()
             .map(|p| p.to_string_lossy().to_string()),
+        Mode::Dashboard => None,
+        Mode::Stash => state
+            .modes
+            .stash
+            .selected_entry()
+            .map(|entry| format!("{}: {}", entry.selector(), entry.message)),
+        Mode::Rebase => state
+            .modes
+            .rebase
+            .plan
+            .as_ref()
+            .map(crate::git::RebasePlan::todo),
     }
 }
 
@@ -673,10 +916,16 @@ fn get_diff_summary(state: &StudioState) -> Option<String> {
 fn reduce_key_event(
     state: &mut StudioState,
     key: crossterm::event::KeyEvent,
-    _history: &mut History,
+    history: &mut History,
 ) -> Vec<SideEffect> {
     use super::handlers::handle_key_event;
 
+    // A proposed content update takes the keys until it's accepted or rejected
+    if matches!(state.modal, Some(Modal::Chat)) && !state.chat_state.proposals.is_empty() {
+        content::proposal_key(state, history, key);
+        return Vec::new();
+    }
+
     // Handlers now return Vec<SideEffect> directly - no conversion needed!
     handle_key_event(state, key)
 }
@@ -757,6 +1006,94 @@ mod tests {
         assert!(!effects.is_empty()); // Should have LoadData effect
     }
 
+    #[test]
+    fn test_mode_switch_respects_auto_generate() {
+        let mut state = test_state();
+        let mut history = History::new();
+        state.config.auto_generate.review = AutoTrigger::Manual;
+
+        let effects = reduce(
+            &mut state,
+            StudioEvent::SwitchMode(Mode::Review),
+            &mut history,
+        );
+        assert!(
+            !effects
+                .iter()
+                .any(|e| matches!(e, SideEffect::AutoGenerate(_)))
+        );
+
+        let effects = reduce(&mut state, StudioEvent::SwitchMode(Mode::PR), &mut history);
+        assert!(matches!(
+            effects.last(),
+            Some(SideEffect::AutoGenerate(Mode::PR))
+        ));
+
+        // The status bar toggle turns every trigger off
+        state.toggle_auto_generate();
+        assert_eq!(state.auto_trigger(Mode::Commit), AutoTrigger::Manual);
+        let effects = reduce(
+            &mut state,
+            StudioEvent::SwitchMode(Mode::Changelog),
+            &mut history,
+        );
+        assert!(
+            !effects
+                .iter()
+                .any(|e| matches!(e, SideEffect::AutoGenerate(_)))
+        );
+    }
+
+    #[test]
+    fn test_pull_request_created_failure_clears_opening() {
+        let mut state = test_state();
+        let mut history = History::new();
+        state.modes.pr.opening = true;
+
+        let _ = reduce(
+            &mut state,
+            StudioEvent::PullRequestCreated {
+                result: Err("Push feat/x to origin first".to_string()),
+            },
+            &mut history,
+        );
+
+        assert!(!state.modes.pr.opening);
+        let notification = state.current_notification().expect("notified");
+        assert!(notification.message.contains("Push feat/x to origin first"));
+    }
+
+    #[test]
+    fn test_ci_status_only_applies_to_current_head() {
+        use crate::integrations::ci::CiStatus;
+
+        let mut state = test_state();
+        let mut history = History::new();
+        state.git_status.branch = "feat/x".to_string();
+        state.modes.pr.ci_loading = true;
+
+        let _ = reduce(
+            &mut state,
+            StudioEvent::CiStatusLoaded {
+                head: "feat/old".to_string(),
+                result: Err("stale".to_string()),
+            },
+            &mut history,
+        );
+        assert!(state.modes.pr.ci.is_none());
+
+        let _ = reduce(
+            &mut state,
+            StudioEvent::CiStatusLoaded {
+                head: "feat/x".to_string(),
+                result: Ok(CiStatus::new("abc1234", Vec::new())),
+            },
+            &mut history,
+        );
+        assert!(!state.modes.pr.ci_loading);
+        assert!(state.modes.pr.ci_status().is_some());
+    }
+
     #[test]
     fn test_focus_panel() {
         let mut state = test_state();
@@ -788,6 +1125,100 @@ mod tests {
         assert!(!state.notifications.is_empty());
     }
 
+    #[test]
+    fn test_heat_map_loaded_updates_cache_and_overlay() {
+        use crate::studio::components::HeatMapData;
+
+        let mut state = test_state();
+        let mut history = History::new();
+        state.modes.explore.heat_map_mode = HeatMapMode::Churn;
+        state.modes.explore.heat_map_loading = true;
+
+        let data = std::sync::Arc::new(HeatMapData::from_git_log("\x1eAlice\n\nsrc/lib.rs\n", 30));
+        let _ = reduce(
+            &mut state,
+            StudioEvent::HeatMapLoaded { data },
+            &mut history,
+        );
+
+        assert!(!state.modes.explore.heat_map_loading);
+        assert!(!state.modes.explore.heat_map_needs_load());
+        assert_eq!(
+            state.modes.explore.file_tree.heat_map_mode(),
+            HeatMapMode::Churn
+        );
+    }
+
+    #[test]
+    fn test_dashboard_loaded_caches_and_clamps_selection() {
+        use crate::studio::state::DashboardData;
+
+        let mut state = test_state();
+        let mut history = History::new();
+        state.modes.dashboard.loading = true;
+        state.modes.dashboard.selected_file = 5;
+
+        let output = format!(
+            "\x1eAda\x1f{}\n\nsrc/lib.rs\n",
+            chrono::Utc::now().timestamp()
+        );
+        let data =
+            std::sync::Arc::new(DashboardData::from_git_log(&output, 30, chrono::Utc::now()));
+        let _ = reduce(
+            &mut state,
+            StudioEvent::DashboardLoaded { data },
+            &mut history,
+        );
+
+        assert!(!state.modes.dashboard.loading);
+        assert!(!state.modes.dashboard.needs_load());
+        assert_eq!(
+            state.modes.dashboard.selected_path(),
+            Some(&std::path::PathBuf::from("src/lib.rs"))
+        );
+    }
+
+    #[test]
+    fn test_review_findings_navigation() {
+        use crate::studio::components::parse_diff;
+        use crate::studio::events::{AgentResult, TaskType};
+        use std::path::Path;
+
+        let mut state = test_state();
+        let mut history = History::new();
+        state.modes.review.diff_view.set_diffs(parse_diff(
+            "diff --git a/src/auth.rs b/src/auth.rs\n--- a/src/auth.rs\n+++ b/src/auth.rs\n\
+             @@ -1,2 +1,2 @@\n-old\n+new\n ctx\n\
+             @@ -40,2 +40,3 @@\n ctx\n+added\n ctx\n\
+             diff --git a/README.md b/README.md\n--- a/README.md\n+++ b/README.md\n\
+             @@ -1 +1 @@\n-a\n+b\n",
+        ));
+
+        let content = "## Issues\n\n- [HIGH] **Token leak in `auth.rs:41`**\n\n- [LOW] Typo\n";
+        let _ = reduce(
+            &mut state,
+            StudioEvent::AgentComplete {
+                task_type: TaskType::Review,
+                result: AgentResult::ReviewContent(content.to_string()),
+            },
+            &mut history,
+        );
+        let review = &mut state.modes.review;
+        assert_eq!(review.findings.len(), 2);
+
+        assert_eq!(review.jump_to_finding(true), Some(0));
+        assert_eq!(review.review_scroll, 2);
+        let diff = review.diff_view.current_diff().expect("diff selected");
+        assert_eq!(diff.path, Path::new("src/auth.rs"));
+        // Scrolled to the second hunk, which contains line 41
+        let top = &review.diff_view.lines()[review.diff_view.scroll_offset()];
+        assert!(top.content.starts_with("@@ -40"));
+
+        assert_eq!(review.jump_to_finding(true), Some(1));
+        assert_eq!(review.jump_to_finding(true), Some(0));
+        assert_eq!(review.jump_to_finding(false), Some(1));
+    }
+
     #[test]
     fn test_quit_produces_effect() {
         let mut state = test_state();
@@ -797,4 +1228,39 @@ mod tests {
 
         assert!(effects.iter().any(|e| matches!(e, SideEffect::Quit)));
     }
+
+    #[test]
+    fn test_tool_updates_wait_for_acceptance() {
+        use crate::studio::events::{ContentPayload, ContentType};
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut state = test_state();
+        let mut history = History::new();
+        let update = |content: &str| StudioEvent::UpdateContent {
+            content_type: ContentType::PRDescription,
+            content: ContentPayload::Markdown(content.to_string()),
+        };
+        let key = |code| StudioEvent::KeyPressed(KeyEvent::new(code, KeyModifiers::NONE));
+
+        // Nothing to overwrite yet, so the first update applies directly
+        let _ = reduce(&mut state, update("# First draft"), &mut history);
+        assert_eq!(&*state.modes.pr.pr_content, "# First draft");
+
+        state.modal = Some(Modal::Chat);
+        let _ = reduce(&mut state, update("# Second draft"), &mut history);
+        let _ = reduce(&mut state, update("# Third draft"), &mut history);
+        assert_eq!(&*state.modes.pr.pr_content, "# First draft");
+        assert_eq!(state.chat_state.proposals.len(), 1);
+
+        // Rejecting keeps the chat open and the content as it was
+        let _ = reduce(&mut state, key(KeyCode::Esc), &mut history);
+        assert!(state.chat_state.proposals.is_empty());
+        assert!(matches!(state.modal, Some(Modal::Chat)));
+        assert_eq!(&*state.modes.pr.pr_content, "# First draft");
+
+        let _ = reduce(&mut state, update("# Fourth draft"), &mut history);
+        let _ = reduce(&mut state, key(KeyCode::Char('a')), &mut history);
+        assert_eq!(&*state.modes.pr.pr_content, "# Fourth draft");
+        assert!(state.chat_state.input.is_empty());
+    }
 }
@@ -52,6 +52,7 @@ pub fn create_modal(state: &StudioState, modal_type: ModalType) -> Modal {
                 refs,
                 selected: 0,
                 target,
+                revision: None,
             }
         }
         ModalType::ConfirmCommit => {
@@ -153,13 +153,11 @@ pub fn apply_scroll(state: &mut StudioState, direction: ScrollDirection, amount:
             PanelId::Left => {
                 // Commits list navigation
                 match direction {
-                    ScrollDirection::Up => {
-                        if state.modes.pr.selected_commit > 0 {
-                            state.modes.pr.selected_commit =
-                                state.modes.pr.selected_commit.saturating_sub(amount);
-                            if state.modes.pr.selected_commit < state.modes.pr.commit_scroll {
-                                state.modes.pr.commit_scroll = state.modes.pr.selected_commit;
-                            }
+                    ScrollDirection::Up if state.modes.pr.selected_commit > 0 => {
+                        state.modes.pr.selected_commit =
+                            state.modes.pr.selected_commit.saturating_sub(amount);
+                        if state.modes.pr.selected_commit < state.modes.pr.commit_scroll {
+                            state.modes.pr.commit_scroll = state.modes.pr.selected_commit;
                         }
                     }
                     ScrollDirection::Down => {
@@ -201,16 +199,14 @@ pub fn apply_scroll(state: &mut StudioState, direction: ScrollDirection, amount:
             PanelId::Left => {
                 // Commits list navigation
                 match direction {
-                    ScrollDirection::Up => {
-                        if state.modes.changelog.selected_commit > 0 {
-                            state.modes.changelog.selected_commit =
-                                state.modes.changelog.selected_commit.saturating_sub(amount);
-                            if state.modes.changelog.selected_commit
-                                < state.modes.changelog.commit_scroll
-                            {
-                                state.modes.changelog.commit_scroll =
-                                    state.modes.changelog.selected_commit;
-                            }
+                    ScrollDirection::Up if state.modes.changelog.selected_commit > 0 => {
+                        state.modes.changelog.selected_commit =
+                            state.modes.changelog.selected_commit.saturating_sub(amount);
+                        if state.modes.changelog.selected_commit
+                            < state.modes.changelog.commit_scroll
+                        {
+                            state.modes.changelog.commit_scroll =
+                                state.modes.changelog.selected_commit;
                         }
                     }
                     ScrollDirection::Down => {
@@ -262,19 +258,17 @@ pub fn apply_scroll(state: &mut StudioState, direction: ScrollDirection, amount:
             PanelId::Left => {
                 // Commits list navigation
                 match direction {
-                    ScrollDirection::Up => {
-                        if state.modes.release_notes.selected_commit > 0 {
-                            state.modes.release_notes.selected_commit = state
-                                .modes
-                                .release_notes
-                                .selected_commit
-                                .saturating_sub(amount);
-                            if state.modes.release_notes.selected_commit
-                                < state.modes.release_notes.commit_scroll
-                            {
-                                state.modes.release_notes.commit_scroll =
-                                    state.modes.release_notes.selected_commit;
-                            }
+                    ScrollDirection::Up if state.modes.release_notes.selected_commit > 0 => {
+                        state.modes.release_notes.selected_commit = state
+                            .modes
+                            .release_notes
+                            .selected_commit
+                            .saturating_sub(amount);
+                        if state.modes.release_notes.selected_commit
+                            < state.modes.release_notes.commit_scroll
+                        {
+                            state.modes.release_notes.commit_scroll =
+                                state.modes.release_notes.selected_commit;
                         }
                     }
                     ScrollDirection::Down => {
@@ -323,6 +317,69 @@ pub fn apply_scroll(state: &mut StudioState, direction: ScrollDirection, amount:
                 }
             }
         },
+        Mode::Dashboard => {
+            // Scrolling moves through the hot files list
+            let delta = isize::try_from(amount).unwrap_or(isize::MAX);
+            match direction {
+                ScrollDirection::Up | ScrollDirection::PageUp => {
+                    state.modes.dashboard.move_selection(-delta);
+                }
+                ScrollDirection::Down | ScrollDirection::PageDown => {
+                    state.modes.dashboard.move_selection(delta);
+                }
+                ScrollDirection::Top => state.modes.dashboard.selected_file = 0,
+                ScrollDirection::Bottom => state.modes.dashboard.move_selection(isize::MAX),
+            }
+        }
+        Mode::Stash => match state.focused_panel {
+            // Selecting another stash loads its diff, which only keys trigger
+            PanelId::Left => {}
+            PanelId::Center => match direction {
+                ScrollDirection::Up | ScrollDirection::PageUp => {
+                    state.modes.stash.file_tree.page_up(amount);
+                }
+                ScrollDirection::Down | ScrollDirection::PageDown => {
+                    state.modes.stash.file_tree.page_down(amount);
+                }
+                ScrollDirection::Top => state.modes.stash.file_tree.select_first(),
+                ScrollDirection::Bottom => state.modes.stash.file_tree.select_last(),
+            },
+            PanelId::Right => match direction {
+                ScrollDirection::Up | ScrollDirection::PageUp => {
+                    state.modes.stash.diff_view.scroll_up(amount);
+                }
+                ScrollDirection::Down | ScrollDirection::PageDown => {
+                    state.modes.stash.diff_view.scroll_down(amount);
+                }
+                ScrollDirection::Top => state.modes.stash.diff_view.scroll_to_top(),
+                ScrollDirection::Bottom => state.modes.stash.diff_view.scroll_to_bottom(),
+            },
+        },
+        Mode::Rebase => match state.focused_panel {
+            // Selecting another commit loads its diff, which only keys trigger
+            PanelId::Left => {}
+            PanelId::Center => match direction {
+                ScrollDirection::Up | ScrollDirection::PageUp => {
+                    let rebase = &mut state.modes.rebase;
+                    rebase.details_scroll = rebase.details_scroll.saturating_sub(amount);
+                }
+                ScrollDirection::Down | ScrollDirection::PageDown => {
+                    state.modes.rebase.details_scroll += amount;
+                }
+                ScrollDirection::Top => state.modes.rebase.details_scroll = 0,
+                ScrollDirection::Bottom => {}
+            },
+            PanelId::Right => match direction {
+                ScrollDirection::Up | ScrollDirection::PageUp => {
+                    state.modes.rebase.diff_view.scroll_up(amount);
+                }
+                ScrollDirection::Down | ScrollDirection::PageDown => {
+                    state.modes.rebase.diff_view.scroll_down(amount);
+                }
+                ScrollDirection::Top => state.modes.rebase.diff_view.scroll_to_top(),
+                ScrollDirection::Bottom => state.modes.rebase.diff_view.scroll_to_bottom(),
+            },
+        },
     }
     state.mark_dirty();
 }
@@ -33,19 +33,5 @@ pub fn set_emoji(state: &mut StudioState, emoji: String) {
 
 /// Handle `ToggleAmendMode` event
 pub fn toggle_amend_mode(state: &mut StudioState) {
-    state.modes.commit.amend_mode = !state.modes.commit.amend_mode;
-    if state.modes.commit.amend_mode {
-        // Load original message from HEAD if repo is available
-        if let Some(repo) = &state.repo
-            && let Ok(msg) = repo.get_head_commit_message()
-        {
-            state.modes.commit.original_message = Some(msg);
-        }
-    } else {
-        state.modes.commit.original_message = None;
-    }
-    // Clear messages when toggling amend mode
-    state.modes.commit.messages.clear();
-    state.modes.commit.message_editor.clear();
-    state.mark_dirty();
+    state.set_amend_mode(!state.modes.commit.amend_mode);
 }
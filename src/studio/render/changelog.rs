@@ -16,11 +16,9 @@ fn scrollable_title(base_title: &str, scroll: usize, total_lines: usize, visible
         format!(" {} ", base_title)
     } else {
         let max_scroll = total_lines.saturating_sub(visible);
-        let percent = if max_scroll == 0 {
-            100
-        } else {
-            ((scroll.min(max_scroll)) * 100) / max_scroll
-        };
+        let percent = (scroll.min(max_scroll) * 100)
+            .checked_div(max_scroll)
+            .unwrap_or(100);
         format!(
             " {} ({}/{}) {}% ",
             base_title,
@@ -101,11 +99,11 @@ pub fn render_changelog_panel(
             let visible_height = area.height.saturating_sub(2) as usize;
 
             // Prefer streaming content if available, then final content
-            let content_to_display = state.modes.changelog.streaming_content.as_ref().or(
+            let content_to_display = state.modes.changelog.streaming_content.as_deref().or(
                 if state.modes.changelog.changelog_content.is_empty() {
                     None
                 } else {
-                    Some(&state.modes.changelog.changelog_content)
+                    Some(&*state.modes.changelog.changelog_content)
                 },
             );
 
@@ -135,7 +133,7 @@ pub fn render_changelog_panel(
                     .lines()
                     .skip(state.modes.changelog.changelog_scroll)
                     .take(inner.height as usize)
-                    .map(|line| Line::from(line.to_string()))
+                    .map(Line::from)
                     .collect();
                 let paragraph = Paragraph::new(lines);
                 frame.render_widget(paragraph, inner);
@@ -5,9 +5,13 @@ use ratatui::prelude::Stylize;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 
+use crate::studio::components::source_lines;
 use crate::studio::components::syntax::SyntaxHighlighter;
-use crate::studio::state::{ChatRole, ChatState};
+use crate::studio::state::{ChatRole, ChatState, PinnedContext};
 use crate::studio::theme;
+use crate::studio::utils::{split_at_width, truncate_width};
+use crate::types::without_sources;
+use unicode_width::UnicodeWidthStr;
 
 /// Render chat messages into formatted lines
 pub fn render_messages(
@@ -38,9 +42,19 @@ pub fn render_messages(
 
         lines.push(Line::from(Span::styled(prefix, prefix_style)));
 
-        // Parse and render message content with markdown-like formatting
-        let formatted_lines = format_markdown(&msg.content, content_width, content_style);
+        // Parse and render message content with markdown-like formatting;
+        // checked sources replace the raw section
+        let body = if msg.sources.is_empty() {
+            &msg.content
+        } else {
+            without_sources(&msg.content)
+        };
+        let formatted_lines = format_markdown(body, content_width, content_style);
         lines.extend(formatted_lines);
+        if !msg.sources.is_empty() {
+            lines.push(Line::from(""));
+            lines.extend(source_lines(&msg.sources, None, content_width));
+        }
 
         // Add separator between messages (except last)
         if msg_idx < chat_state.messages.len() - 1 {
@@ -401,18 +415,16 @@ pub fn format_markdown(content: &str, max_width: usize, base_style: Style) -> Ve
                     flush_line(&mut lines, &mut current_spans, list_depth);
                     lines.push(Line::from("")); // Space after table
                 }
-                TagEnd::TableHead | TagEnd::TableRow => {
-                    // Render the row
-                    if !table_row.is_empty() {
-                        let row_text = table_row.join(" │ ");
-                        flush_line(&mut lines, &mut current_spans, list_depth);
-                        current_spans.push(Span::styled(
-                            format!("  │ {} │", row_text),
-                            Style::default().fg(theme::text_secondary_color()),
-                        ));
-                        flush_line(&mut lines, &mut current_spans, list_depth);
-                        table_row.clear();
-                    }
+                // Render the row
+                TagEnd::TableHead | TagEnd::TableRow if !table_row.is_empty() => {
+                    let row_text = table_row.join(" │ ");
+                    flush_line(&mut lines, &mut current_spans, list_depth);
+                    current_spans.push(Span::styled(
+                        format!("  │ {} │", row_text),
+                        Style::default().fg(theme::text_secondary_color()),
+                    ));
+                    flush_line(&mut lines, &mut current_spans, list_depth);
+                    table_row.clear();
                 }
                 TagEnd::TableCell => {
                     // Cell completed - handled in text
@@ -566,11 +578,7 @@ fn render_code_block(
     let highlighter = SyntaxHighlighter::for_extension(lang);
 
     for code_line in code_lines {
-        let truncated = if code_line.len() > max_width.saturating_sub(4) {
-            format!("{}…", &code_line[..max_width.saturating_sub(5)])
-        } else {
-            code_line.clone()
-        };
+        let truncated = truncate_width(code_line, max_width.saturating_sub(4));
 
         // Build spans for this line
         let mut line_spans = vec![Span::styled(
@@ -624,7 +632,7 @@ fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
             word.to_string()
         };
         // Handle words longer than max_width by breaking them
-        if word_to_add.len() > max_width {
+        if word_to_add.width() > max_width {
             // Push current line if not empty
             if !current_line.is_empty() {
                 lines.push(current_line);
@@ -632,8 +640,8 @@ fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
             }
             // Break the long word into chunks
             let mut remaining = word_to_add.as_str();
-            while remaining.len() > max_width {
-                let (chunk, rest) = remaining.split_at(max_width);
+            while remaining.width() > max_width {
+                let (chunk, rest) = split_at_width(remaining, max_width);
                 lines.push(chunk.to_string());
                 remaining = rest;
             }
@@ -642,7 +650,7 @@ fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
             }
         } else if current_line.is_empty() {
             current_line = word_to_add;
-        } else if current_line.len() + 1 + word_to_add.len() <= max_width {
+        } else if current_line.width() + 1 + word_to_add.width() <= max_width {
             current_line.push(' ');
             current_line.push_str(&word_to_add);
         } else {
@@ -684,6 +692,25 @@ pub fn render_input_line(input: &str, cursor_visible: bool) -> Line<'static> {
     ])
 }
 
+/// Render pinned context items as a row of chips
+pub fn render_pin_chips(pinned: &[PinnedContext]) -> Line<'static> {
+    let mut spans = Vec::with_capacity(pinned.len() * 2 + 1);
+    for pin in pinned {
+        spans.push(Span::styled(
+            format!(" ◆ {} ", truncate_width(&pin.label, 32)),
+            Style::default()
+                .fg(theme::accent_secondary())
+                .bg(theme::bg_highlight_color()),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    spans.push(Span::styled(
+        "^X unpin",
+        Style::default().fg(theme::text_dim_color()),
+    ));
+    Line::from(spans)
+}
+
 /// Render the help footer
 pub fn help_footer() -> Line<'static> {
     Line::from(" [Enter] send · [Esc] close · [↑↓] scroll ").fg(theme::text_dim_color())
@@ -3,7 +3,9 @@
 use ratatui::Frame;
 use ratatui::layout::Rect;
 
-use crate::studio::components::{render_diff_view, render_file_tree, render_message_editor};
+use crate::studio::components::{
+    render_diff_view, render_file_tree, render_message_drift, render_message_editor,
+};
 use crate::studio::state::{EmojiMode, PanelId, StudioState};
 
 /// Render a panel in Commit mode
@@ -21,7 +23,7 @@ pub fn render_commit_panel(
             let staged = state.git_status.staged_count;
             let modified = state.git_status.modified_count;
 
-            let title = if state.modes.commit.show_all_files {
+            let mut title = if state.modes.commit.show_all_files {
                 format!("All Files · ✓{}", staged)
             } else if modified > 0 {
                 format!("Changes ✓{} ○{}", staged, modified)
@@ -30,6 +32,11 @@ pub fn render_commit_panel(
             } else {
                 "Changes".to_string()
             };
+            if staged > 0
+                && let Some(risk) = &state.modes.commit.risk
+            {
+                title = format!("{} · {}", title, risk.badge());
+            }
 
             render_file_tree(
                 frame,
@@ -70,6 +77,26 @@ pub fn render_commit_panel(
             );
         }
         PanelId::Right => {
+            // While amending, compare HEAD's message with the one in the editor
+            let commit = &state.modes.commit;
+            if commit.amend_mode
+                && commit.show_drift
+                && let Some(original) = &commit.original_message
+            {
+                let revised = commit.message_editor.get_message();
+                if !revised.is_empty() {
+                    render_message_drift(
+                        frame,
+                        area,
+                        original,
+                        &revised,
+                        &commit.branch_revisions,
+                        is_focused,
+                    );
+                    return;
+                }
+            }
+
             // Render diff view for selected file
             let title = state.modes.commit.file_tree.selected_path().map_or_else(
                 || "Changes".to_string(),
@@ -0,0 +1,303 @@
+//! Dashboard mode rendering for Iris Studio
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Sparkline};
+
+use crate::plan::PlanProgress;
+use crate::studio::state::{DashboardData, PanelId, SessionSummary, StudioState, WorktreeSession};
+use crate::studio::theme;
+use crate::studio::utils::{time_ago, truncate_width};
+
+/// Width of the count bars in the hot files and contributors lists
+const BAR_WIDTH: usize = 10;
+
+/// Render a panel in Dashboard mode
+pub fn render_dashboard_panel(
+    state: &StudioState,
+    frame: &mut Frame,
+    area: Rect,
+    panel_id: PanelId,
+) {
+    let is_focused = panel_id == state.focused_panel;
+    let title = match panel_id {
+        PanelId::Left => " Activity ",
+        PanelId::Center => " Hot Files [Enter:explore] ",
+        PanelId::Right => " Contributors ",
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(if is_focused {
+            theme::focused_border()
+        } else {
+            theme::unfocused_border()
+        });
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let dashboard = &state.modes.dashboard;
+    let Some(data) = dashboard.data.as_deref() else {
+        let hint = if dashboard.loading {
+            "Loading activity..."
+        } else {
+            "Press r to load activity"
+        };
+        frame.render_widget(Paragraph::new(Line::styled(hint, theme::dimmed())), inner);
+        return;
+    };
+
+    match panel_id {
+        PanelId::Left => render_activity(state, data, frame, inner),
+        PanelId::Center => render_hot_files(data, dashboard.selected_file, frame, inner),
+        PanelId::Right => render_contributors(data, frame, inner),
+    }
+}
+
+/// Sparkline of daily commits, work in progress and the last session
+fn render_activity(state: &StudioState, data: &DashboardData, frame: &mut Frame, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(4),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let summary = format!(
+        "{} commits in {} days",
+        data.total_commits, data.window_days
+    );
+    frame.render_widget(
+        Paragraph::new(Line::styled(summary, theme::keyword())),
+        chunks[0],
+    );
+
+    // Most recent days on the right, trimmed to fit the panel
+    let width = usize::from(chunks[1].width);
+    let days = &data.daily_commits[data.daily_commits.len().saturating_sub(width)..];
+    frame.render_widget(
+        Sparkline::default()
+            .data(days)
+            .style(Style::default().fg(theme::accent_secondary())),
+        chunks[1],
+    );
+
+    let status = &state.git_status;
+    let mut lines = vec![
+        Line::default(),
+        section_header("Work in Progress"),
+        Line::from(vec![
+            Span::styled(
+                format!("  {} staged", status.staged_count),
+                theme::git_staged(),
+            ),
+            Span::styled(" · ", theme::dimmed()),
+            Span::styled(
+                format!("{} modified", status.modified_count),
+                theme::git_modified(),
+            ),
+            Span::styled(" · ", theme::dimmed()),
+            Span::styled(
+                format!("{} untracked", status.untracked_count),
+                theme::git_untracked(),
+            ),
+        ]),
+        Line::styled(
+            format!("  {} stashed", data.stash_count),
+            if data.stash_count > 0 {
+                theme::warning()
+            } else {
+                theme::dimmed()
+            },
+        ),
+        Line::default(),
+        section_header("Last Session"),
+    ];
+    match &data.last_session {
+        Some(session) => lines.extend(session_lines(session)),
+        None => lines.push(Line::styled("  No saved sessions", theme::dimmed())),
+    }
+    lines.extend(data.other_worktrees.iter().map(worktree_line));
+    if let Some(plan) = &data.plan {
+        lines.push(Line::default());
+        lines.extend(plan_lines(plan, usize::from(chunks[2].width)));
+    }
+
+    frame.render_widget(Paragraph::new(lines), chunks[2]);
+}
+
+/// The saved plan as a checklist of its commits
+fn plan_lines(progress: &PlanProgress, width: usize) -> Vec<Line<'static>> {
+    let plan = &progress.plan;
+    let mut lines = vec![
+        section_header(&format!(
+            "Plan · {}/{}",
+            progress.done(),
+            plan.commits.len()
+        )),
+        Line::styled(
+            format!("  {}", truncate_width(&plan.title, width.saturating_sub(2))),
+            theme::keyword(),
+        ),
+    ];
+    for (commit, landed) in plan.commits.iter().zip(&progress.landed) {
+        let (mark, style) = match landed {
+            Some(_) => ("  ✓ ", theme::success()),
+            None => ("  ☐ ", theme::dimmed()),
+        };
+        let subject = truncate_width(&commit.subject, width.saturating_sub(4));
+        lines.push(Line::from(vec![
+            Span::styled(mark, style),
+            Span::styled(
+                subject,
+                if landed.is_some() {
+                    theme::dimmed()
+                } else {
+                    Style::default().fg(theme::text_primary_color())
+                },
+            ),
+        ]));
+    }
+    lines
+}
+
+fn section_header(title: &str) -> Line<'static> {
+    Line::styled(
+        title.to_string(),
+        Style::default()
+            .fg(theme::accent_primary())
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+fn session_lines(session: &SessionSummary) -> Vec<Line<'static>> {
+    let minutes = (session.ended_at - session.started_at).num_minutes();
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!("  {}", session.branch), theme::keyword()),
+            Span::styled(
+                format!(" · {}", time_ago(session.ended_at)),
+                theme::timestamp(),
+            ),
+        ]),
+        Line::styled(
+            format!(
+                "  {} min · {} chat messages",
+                minutes.max(1),
+                session.chat_messages
+            ),
+            theme::dimmed(),
+        ),
+    ];
+    if !session.artifacts.is_empty() {
+        let artifacts: Vec<&str> = session.artifacts.iter().map(|k| k.short_label()).collect();
+        lines.push(Line::styled(
+            format!("  Generated: {}", artifacts.join(", ")),
+            theme::dimmed(),
+        ));
+    }
+    lines
+}
+
+/// A session in progress in another worktree
+fn worktree_line(session: &WorktreeSession) -> Line<'static> {
+    Line::from(vec![
+        Span::styled("  also ", theme::dimmed()),
+        Span::styled(session.branch.clone(), theme::keyword()),
+        Span::styled(
+            format!(" in progress in {} worktree", session.worktree),
+            theme::dimmed(),
+        ),
+        Span::styled(
+            format!(" · {}", time_ago(session.last_activity)),
+            theme::timestamp(),
+        ),
+    ])
+}
+
+/// Bar proportional to `count`, `BAR_WIDTH` cells at `max`
+fn bar(count: usize, max: usize) -> String {
+    let filled = (count * BAR_WIDTH).div_ceil(max.max(1));
+    format!("{:<width$}", "█".repeat(filled), width = BAR_WIDTH)
+}
+
+fn render_hot_files(data: &DashboardData, selected: usize, frame: &mut Frame, area: Rect) {
+    if data.hot_files.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::styled("No changes in this window", theme::dimmed())),
+            area,
+        );
+        return;
+    }
+
+    let visible = usize::from(area.height).max(1);
+    let offset = selected.saturating_sub(visible - 1);
+    let max = data.hot_files[0].1;
+    let path_width = usize::from(area.width).saturating_sub(BAR_WIDTH + 8);
+
+    let lines: Vec<Line> = data
+        .hot_files
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, (path, count))| {
+            let is_selected = i == selected;
+            let marker = if is_selected { "▸ " } else { "  " };
+            let line = Line::from(vec![
+                Span::styled(marker, theme::keyword()),
+                Span::styled(format!("{count:>3} "), theme::dimmed()),
+                Span::styled(
+                    bar(*count, max),
+                    Style::default().fg(theme::accent_tertiary()),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    truncate_width(&path.display().to_string(), path_width),
+                    theme::file_path(),
+                ),
+            ]);
+            if is_selected {
+                line.style(theme::selected())
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn render_contributors(data: &DashboardData, frame: &mut Frame, area: Rect) {
+    let Some(max) = data.contributors.first().map(|(_, count)| *count) else {
+        frame.render_widget(
+            Paragraph::new(Line::styled("No commits in this window", theme::dimmed())),
+            area,
+        );
+        return;
+    };
+
+    let name_width = usize::from(area.width).saturating_sub(6);
+    let lines: Vec<Line> = data
+        .contributors
+        .iter()
+        .flat_map(|(name, count)| {
+            [
+                Line::from(vec![
+                    Span::styled(truncate_width(name, name_width), theme::author()),
+                    Span::styled(format!(" {count}"), theme::dimmed()),
+                ]),
+                Line::styled(
+                    bar(*count, max),
+                    Style::default().fg(theme::accent_secondary()),
+                ),
+            ]
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
@@ -8,9 +8,11 @@ use ratatui::widgets::{
     Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
 };
 
-use crate::studio::components::{render_code_view, render_file_tree};
-use crate::studio::state::{PanelId, StudioState};
+use crate::studio::components::{render_code_view, render_file_tree, source_lines};
+use crate::studio::state::{PanelId, SemanticSearchState, StudioState};
 use crate::studio::theme;
+use crate::studio::utils::truncate_width;
+use crate::types::{CheckedCitation, without_sources};
 
 /// Render a panel in Explore mode
 pub fn render_explore_panel(
@@ -22,6 +24,11 @@ pub fn render_explore_panel(
     let is_focused = panel_id == state.focused_panel;
 
     match panel_id {
+        PanelId::Left if state.modes.explore.search.is_some() => {
+            if let Some(search) = &state.modes.explore.search {
+                render_search_results(frame, area, search, is_focused);
+            }
+        }
         PanelId::Left => {
             // File tree
             render_file_tree(
@@ -57,7 +64,13 @@ pub fn render_explore_panel(
             if state.modes.explore.blame_loading {
                 render_blame_loading(frame, area, is_focused);
             } else if let Some(ref blame) = state.modes.explore.semantic_blame {
-                render_semantic_blame_panel(frame, area, blame, is_focused);
+                render_semantic_blame_panel(
+                    frame,
+                    area,
+                    blame,
+                    &state.modes.explore.blame_sources,
+                    is_focused,
+                );
             } else {
                 render_file_log_panel(frame, area, state, is_focused);
             }
@@ -65,6 +78,84 @@ pub fn render_explore_panel(
     }
 }
 
+/// Render semantic search results in place of the file tree
+fn render_search_results(
+    frame: &mut Frame,
+    area: Rect,
+    search: &SemanticSearchState,
+    is_focused: bool,
+) {
+    let block = Block::default()
+        .title(format!(" Search: {} (Esc: files) ", search.query))
+        .borders(Borders::ALL)
+        .border_style(if is_focused {
+            theme::focused_border()
+        } else {
+            theme::unfocused_border()
+        });
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.height == 0 || inner.width == 0 {
+        return;
+    }
+
+    let width = inner.width as usize;
+    let message = if search.loading {
+        Some(Span::styled(
+            "Iris is searching the code...",
+            theme::dimmed(),
+        ))
+    } else if let Some(error) = &search.error {
+        Some(Span::styled(
+            truncate_width(error, width),
+            Style::default().fg(theme::error_color()),
+        ))
+    } else if search.results.is_empty() {
+        let note = search.note.as_deref().unwrap_or("Nothing found");
+        Some(Span::styled(truncate_width(note, width), theme::dimmed()))
+    } else {
+        None
+    };
+    if let Some(message) = message {
+        frame.render_widget(
+            Paragraph::new(Line::from(message)).wrap(Wrap { trim: true }),
+            inner,
+        );
+        return;
+    }
+
+    let visible = (inner.height as usize / 2).max(1);
+    let lines: Vec<Line> = search
+        .results
+        .iter()
+        .enumerate()
+        .skip(search.scroll)
+        .take(visible)
+        .flat_map(|(i, result)| {
+            let is_selected = i == search.selected;
+            let location_style = if is_selected {
+                Style::default()
+                    .fg(theme::accent_secondary())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::text_primary_color())
+            };
+            let prefix = if is_selected { "▸ " } else { "  " };
+            [
+                Line::from(Span::styled(
+                    truncate_width(&format!("{prefix}{}", result.location()), width),
+                    location_style,
+                )),
+                Line::from(Span::styled(
+                    truncate_width(&format!("  {}", result.reason), width),
+                    theme::dimmed(),
+                )),
+            ]
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
 /// Render the file log panel (git history for selected file)
 fn render_file_log_panel(frame: &mut Frame, area: Rect, state: &mut StudioState, is_focused: bool) {
     let show_global = state.modes.explore.show_global_log;
@@ -184,7 +275,7 @@ fn render_file_log_panel(frame: &mut Frame, area: Rect, state: &mut StudioState,
 
         // Calculate message width: panel - marker(2) - hash(7) - space(1)
         let msg_width = panel_width.saturating_sub(10);
-        let truncated_msg = truncate_str(&entry.message, msg_width);
+        let truncated_msg = truncate_width(&entry.message, msg_width);
 
         lines.push(Line::from(vec![
             Span::styled(marker, marker_style.bg(bg.unwrap_or(Color::Reset))),
@@ -297,6 +388,7 @@ fn render_semantic_blame_panel(
     frame: &mut Frame,
     area: Rect,
     blame: &crate::studio::events::SemanticBlameResult,
+    sources: &[CheckedCitation],
     is_focused: bool,
 ) {
     let block = Block::default()
@@ -310,7 +402,7 @@ fn render_semantic_blame_panel(
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    render_semantic_blame(frame, inner, blame);
+    render_semantic_blame(frame, inner, blame, sources);
 }
 
 /// Render the companion status bar (compact, at bottom of explore mode)
@@ -426,20 +518,12 @@ pub fn render_companion_status_bar(frame: &mut Frame, area: Rect, state: &Studio
     frame.render_widget(paragraph, area);
 }
 
-/// Truncate a string to max length with ellipsis
-fn truncate_str(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}…", &s[..max_len.saturating_sub(1)])
-    }
-}
-
 /// Render semantic blame result in the context panel
 fn render_semantic_blame(
     frame: &mut Frame,
     area: Rect,
     blame: &crate::studio::events::SemanticBlameResult,
+    sources: &[CheckedCitation],
 ) {
     use ratatui::layout::{Constraint, Layout};
 
@@ -495,8 +579,16 @@ fn render_semantic_blame(
     let header = Paragraph::new(header_lines);
     frame.render_widget(header, chunks[0]);
 
-    // Body: explanation with markdown rendering
-    let lines = render_markdown_lines(&blame.explanation);
+    // Body: explanation with markdown rendering, then the checked sources
+    let mut lines = if sources.is_empty() {
+        render_markdown_lines(&blame.explanation)
+    } else {
+        render_markdown_lines(without_sources(&blame.explanation))
+    };
+    if !sources.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(source_lines(sources, None, usize::from(chunks[1].width)));
+    }
     let explanation = Paragraph::new(lines).wrap(Wrap { trim: true });
     frame.render_widget(explanation, chunks[1]);
 }
@@ -0,0 +1,62 @@
+//! Panel switcher and minimum size screen for narrow terminals
+
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+use crate::studio::layout::{LayoutAreas, MIN_HEIGHT, MIN_WIDTH, get_mode_layout, panel_index};
+use crate::studio::state::StudioState;
+use crate::studio::theme;
+
+/// Render the row naming every panel of the mode, visible ones bright and
+/// the focused one highlighted
+pub fn render_panel_switcher(
+    frame: &mut Frame,
+    area: Rect,
+    state: &StudioState,
+    areas: &LayoutAreas,
+) {
+    let mut spans = vec![Span::raw(" ")];
+    for panel in get_mode_layout(state.active_mode).panels {
+        let style = if panel.id == state.focused_panel {
+            Style::default()
+                .fg(theme::accent_primary())
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else if areas.panels[panel_index(panel.id)].area() > 0 {
+            Style::default().fg(theme::text_primary_color())
+        } else {
+            theme::dimmed()
+        };
+        spans.push(Span::styled(format!(" {} ", panel.title), style));
+        spans.push(Span::raw(" "));
+    }
+    spans.push(Span::styled("Tab switch panel", theme::dimmed()));
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Render a notice in place of Studio when the terminal is below the
+/// minimum size
+pub fn render_too_small(frame: &mut Frame) {
+    let area = frame.area();
+    let lines = vec![
+        Line::styled(
+            "Terminal too small",
+            Style::default()
+                .fg(theme::warning_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::styled(
+            format!(
+                "{}×{}, need at least {MIN_WIDTH}×{MIN_HEIGHT}",
+                area.width, area.height
+            ),
+            theme::dimmed(),
+        ),
+    ];
+    let [middle] = Layout::vertical([Constraint::Length(2)])
+        .flex(Flex::Center)
+        .areas(area);
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), middle);
+}
@@ -5,16 +5,24 @@
 mod changelog;
 mod chat;
 mod commit;
+mod dashboard;
 mod explore;
+mod layout;
 mod modals;
 mod pr;
+mod rebase;
 mod release_notes;
 mod review;
+mod stash;
 
 pub use changelog::render_changelog_panel;
 pub use commit::render_commit_panel;
+pub use dashboard::render_dashboard_panel;
 pub use explore::{render_companion_status_bar, render_explore_panel};
+pub use layout::{render_panel_switcher, render_too_small};
 pub use modals::render_modal;
 pub use pr::render_pr_panel;
+pub use rebase::render_rebase_panel;
 pub use release_notes::render_release_notes_panel;
 pub use review::render_review_panel;
+pub use stash::render_stash_panel;
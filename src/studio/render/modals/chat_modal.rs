@@ -2,11 +2,14 @@
 
 use ratatui::Frame;
 use ratatui::layout::Rect;
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use std::time::Instant;
 
 use super::super::chat;
-use crate::studio::state::ChatState;
+use crate::studio::components::message_drift::{WordChange, line_diff};
+use crate::studio::state::{ChatState, ProposedUpdate};
 use crate::studio::theme;
 
 pub fn render(frame: &mut Frame, area: Rect, chat_state: &ChatState, last_render: Instant) {
@@ -18,8 +21,9 @@ pub fn render(frame: &mut Frame, area: Rect, chat_state: &ChatState, last_render
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Split inner area: messages area and input area
-    let input_height = 3u16;
+    // Split inner area: messages area and input area (plus a chip row when pinned)
+    let has_pins = !chat_state.pinned.is_empty();
+    let input_height = if has_pins { 4u16 } else { 3u16 };
     let messages_height = inner.height.saturating_sub(input_height);
     let content_width = inner.width.saturating_sub(2) as usize;
 
@@ -60,5 +64,80 @@ pub fn render(frame: &mut Frame, area: Rect, chat_state: &ChatState, last_render
 
     let cursor_visible = last_render.elapsed().as_millis() % 1000 < 500;
     let input_line = chat::render_input_line(&chat_state.input, cursor_visible);
-    frame.render_widget(Paragraph::new(input_line), input_inner);
+    if has_pins {
+        let chips = chat::render_pin_chips(&chat_state.pinned);
+        frame.render_widget(Paragraph::new(vec![chips, input_line]), input_inner);
+    } else {
+        frame.render_widget(Paragraph::new(input_line), input_inner);
+    }
+
+    if let Some(proposal) = chat_state.proposals.front() {
+        let queued = chat_state.proposals.len() - 1;
+        render_proposal(
+            frame,
+            messages_area,
+            proposal,
+            queued,
+            chat_state.proposal_scroll,
+        );
+    }
+}
+
+/// Popover over the conversation diffing the current content against a
+/// tool's proposed replacement
+fn render_proposal(
+    frame: &mut Frame,
+    area: Rect,
+    proposal: &ProposedUpdate,
+    queued: usize,
+    scroll: usize,
+) {
+    let area = Rect::new(
+        area.x + 1,
+        area.y,
+        area.width.saturating_sub(2),
+        area.height,
+    );
+    if area.height < 3 {
+        return;
+    }
+    frame.render_widget(Clear, area);
+
+    let title = if queued > 0 {
+        format!(" Replace the {}? ({queued} more) ", proposal.label())
+    } else {
+        format!(" Replace the {}? ", proposal.label())
+    };
+    let footer = Line::from(" [a/Enter] accept · [r/Esc] keep mine · [↑↓] scroll ")
+        .style(Style::default().fg(theme::text_dim_color()));
+    let block = Block::default()
+        .title(title)
+        .title_bottom(footer)
+        .borders(Borders::ALL)
+        .border_style(theme::focused_border());
+
+    let same = Style::default().fg(theme::text_secondary_color());
+    let removed = Style::default().fg(theme::error_color());
+    let added = Style::default()
+        .fg(theme::success_color())
+        .add_modifier(Modifier::BOLD);
+    let lines: Vec<Line> = line_diff(&proposal.current, &proposal.proposed)
+        .into_iter()
+        .map(|change| {
+            let (marker, text, style) = match change {
+                WordChange::Same(text) => (' ', text, same),
+                WordChange::Removed(text) => ('-', text, removed),
+                WordChange::Added(text) => ('+', text, added),
+            };
+            Line::from(Span::styled(format!("{marker} {text}"), style))
+        })
+        .collect();
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0))
+            .wrap(ratatui::widgets::Wrap { trim: false }),
+        area,
+    );
 }
@@ -0,0 +1,135 @@
+//! Commit queue modal rendering
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::studio::state::CommitQueueState;
+use crate::studio::theme;
+use crate::studio::utils::truncate_width;
+
+pub fn render(frame: &mut Frame, area: Rect, queue: &CommitQueueState) {
+    let block = Block::default()
+        .title(" Commit Queue ")
+        .borders(Borders::ALL)
+        .border_style(theme::focused_border());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let status = if queue.running {
+        Line::from(Span::styled(
+            format!(
+                "Committing {} of {}...",
+                queue.committed.len() + 1,
+                queue.run_total
+            ),
+            Style::default()
+                .fg(theme::accent_primary())
+                .add_modifier(Modifier::BOLD),
+        ))
+    } else if let Some(error) = &queue.error {
+        Line::from(Span::styled(
+            format!("Stopped after {} commits: {error}", queue.committed.len()),
+            theme::error(),
+        ))
+    } else if queue.is_empty() {
+        Line::from(Span::styled(
+            "Nothing queued. Stage files, write a message and press b in the message panel.",
+            theme::dimmed(),
+        ))
+    } else {
+        Line::from(Span::styled(
+            format!(
+                "{} commit{} queued, made top to bottom",
+                queue.entries.len(),
+                if queue.entries.len() == 1 { "" } else { "s" }
+            ),
+            Style::default().fg(theme::text_primary_color()),
+        ))
+    };
+    frame.render_widget(Paragraph::new(status), chunks[0]);
+
+    let width = chunks[1].width as usize;
+    let mut lines = Vec::new();
+    for (i, entry) in queue.entries.iter().enumerate() {
+        let selected = i == queue.selected && !queue.running;
+        let next = i == 0 && queue.running;
+        let marker = if next {
+            "▸ "
+        } else if selected {
+            "> "
+        } else {
+            "  "
+        };
+        let files = format!(
+            " ({} file{})",
+            entry.files.len(),
+            if entry.files.len() == 1 { "" } else { "s" }
+        );
+        let subject_width = width.saturating_sub(marker.len() + files.len() + 4);
+        let subject_style = if selected {
+            theme::selected()
+        } else {
+            Style::default().fg(theme::text_primary_color())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(marker, Style::default().fg(theme::accent_secondary())),
+            Span::styled(format!("{:>2}. ", i + 1), theme::dimmed()),
+            Span::styled(
+                truncate_width(entry.subject(), subject_width),
+                subject_style,
+            ),
+            Span::styled(files, theme::dimmed()),
+        ]));
+        // The selected entry lists its files
+        if selected {
+            for file in &entry.files {
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "       {}",
+                        truncate_width(&file.to_string_lossy(), width.saturating_sub(7))
+                    ),
+                    theme::file_path(),
+                )));
+            }
+        }
+    }
+    // Keep the selection in view
+    let height = chunks[1].height as usize;
+    let selected_line = queue.selected.min(lines.len());
+    let scroll = selected_line.saturating_sub(height.saturating_sub(1) / 2);
+    let scroll = u16::try_from(scroll).unwrap_or(u16::MAX);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), chunks[1]);
+
+    let footer = if queue.running {
+        Line::from(Span::styled(
+            "Stops at the first commit that fails",
+            theme::dimmed(),
+        ))
+    } else {
+        Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme::accent_secondary())),
+            Span::styled(" commit all  ", theme::dimmed()),
+            Span::styled("j/k", Style::default().fg(theme::accent_secondary())),
+            Span::styled(" select  ", theme::dimmed()),
+            Span::styled("J/K", Style::default().fg(theme::accent_secondary())),
+            Span::styled(" move  ", theme::dimmed()),
+            Span::styled("d", Style::default().fg(theme::accent_secondary())),
+            Span::styled(" remove  ", theme::dimmed()),
+            Span::styled("Esc", Style::default().fg(theme::accent_secondary())),
+            Span::styled(" close", theme::dimmed()),
+        ])
+    };
+    frame.render_widget(Paragraph::new(footer), chunks[2]);
+}
@@ -3,7 +3,7 @@
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::text::Line;
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
 use crate::studio::theme;
 
@@ -15,11 +15,9 @@ pub fn render(frame: &mut Frame, area: Rect, message: &str) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let lines = vec![
-        Line::from(message),
-        Line::from(""),
-        Line::from("Press y/n to confirm"),
-    ];
-    let paragraph = Paragraph::new(lines);
+    let mut lines: Vec<Line> = message.lines().map(Line::from).collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from("Press y/n to confirm"));
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
     frame.render_widget(paragraph, inner);
 }
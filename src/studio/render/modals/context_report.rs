@@ -0,0 +1,94 @@
+//! Context size report modal rendering
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::agents::ContextReport;
+use crate::agents::context_report::group_digits;
+use crate::studio::theme;
+
+/// Width of the share bars
+const BAR_WIDTH: usize = 20;
+
+pub fn render(frame: &mut Frame, area: Rect, report: &ContextReport) {
+    let block = Block::default()
+        .title(format!(" Context: {} ", report.capability))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::accent_secondary()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let total = report.input_tokens().max(1);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!(" {}", report.model), theme::keyword()),
+            Span::styled(
+                format!(
+                    " · {}",
+                    report
+                        .created_at
+                        .with_timezone(&chrono::Local)
+                        .format("%H:%M:%S")
+                ),
+                theme::timestamp(),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    for (label, tokens) in report.sections() {
+        let filled = (tokens * BAR_WIDTH).div_ceil(total);
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {label:<14}"), theme::dimmed()),
+            Span::raw(format!("{:>9}", group_digits(tokens))),
+            Span::styled(format!(" {:>3}% ", tokens * 100 / total), theme::dimmed()),
+            Span::styled(
+                "█".repeat(filled),
+                Style::default().fg(theme::accent_tertiary()),
+            ),
+        ]));
+    }
+
+    let total_style = Style::default()
+        .fg(theme::accent_primary())
+        .add_modifier(Modifier::BOLD);
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(format!(" {:<14}", "Input"), total_style),
+        Span::styled(
+            format!("{:>9}", group_digits(report.input_tokens())),
+            total_style,
+        ),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(format!(" {:<14}", "Output"), total_style),
+        Span::styled(
+            format!("{:>9}", group_digits(report.output_tokens)),
+            total_style,
+        ),
+    ]));
+    if report.billed_input_tokens > 0 {
+        let billed = usize::try_from(report.billed_input_tokens).unwrap_or(usize::MAX);
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {:<14}", "Billed input"), theme::dimmed()),
+            Span::raw(format!("{:>9}", group_digits(billed))),
+            Span::styled(
+                format!(
+                    "  {} tool call{}",
+                    report.tool_calls,
+                    if report.tool_calls == 1 { "" } else { "s" }
+                ),
+                theme::dimmed(),
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        " git-iris trace lists earlier generations · any key closes",
+        theme::dimmed(),
+    ));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
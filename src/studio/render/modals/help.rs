@@ -27,21 +27,55 @@ pub fn render(frame: &mut Frame, area: Rect) {
         Line::from("  Shift+S    Settings             Shift+E  Explore mode"),
         Line::from("  Shift+C    Commit mode          Shift+R  Review mode"),
         Line::from("  Shift+P    PR mode              Shift+L  Changelog mode"),
+        Line::from("  +          Pin to chat context  Shift+X  Export session"),
+        Line::from("  Shift+D    Dashboard            Ctrl+F   Search history"),
+        Line::from("  Ctrl+T     Context size report  Ctrl+A   Toggle auto-generate"),
+        Line::from("  Esc        Cancel generation    Shift+Z  Stash mode"),
+        Line::from("  Shift+B    Rebase mode          Ctrl+O   Cycle output length"),
+        Line::from("  Ctrl+Y     Cycle tone"),
         Line::from(""),
         Line::from(Span::styled("Navigation (all modes)", section_style)),
         Line::from("  j/k        Down/up              g/G  Top/bottom"),
         Line::from("  h/l        Collapse/expand      Enter Select"),
         Line::from(""),
+        Line::from(Span::styled("Explore", section_style)),
+        Line::from("  w          Ask why              p   Find when text changed"),
+        Line::from("  v          Visual selection     H   Cycle heat map"),
+        Line::from("  s          Search by meaning    A   Architecture overview"),
+        Line::from("  b          Blame whole file"),
+        Line::from(""),
         Line::from(Span::styled("Commit Mode", section_style)),
         Line::from("  r          Generate message     i   With instructions"),
         Line::from("  e          Edit message         n/p Cycle alternatives"),
         Line::from("  p          Select preset        g   Select emoji"),
         Line::from("  E          Toggle emoji         y   Copy message"),
-        Line::from("  Enter      Commit changes"),
+        Line::from("  Enter      Commit changes       z   Fix spelling"),
+        Line::from("  Ctrl+Z     Undo last commit     s   Stage mentioned files"),
+        Line::from("  A          Toggle amend mode    D   Message drift/diff"),
+        Line::from("  H          Message history      b   Queue commit"),
+        Line::from("  Q          Commit queue"),
         Line::from(""),
         Line::from(Span::styled("Review / PR / Changelog", section_style)),
         Line::from("  f          Select from ref      t   Select to ref"),
         Line::from("  r          Generate             R   Reset"),
+        Line::from("  w          Save to file (Review, Changelog, Release notes)"),
+        Line::from("  n/N        Next/prev finding (Review)"),
+        Line::from("  [/]  o     Select/open source (Review)"),
+        Line::from("  c          Post findings to GitHub PR (Review)"),
+        Line::from("  o          Open pull request on GitHub (PR)"),
+        Line::from("  c          Refresh CI status of the branch (PR commits)"),
+        Line::from(""),
+        Line::from(Span::styled("Dashboard", section_style)),
+        Line::from("  r          Refresh activity     Enter Explore hot file"),
+        Line::from(""),
+        Line::from(Span::styled("Stash", section_style)),
+        Line::from("  s/u        Stash (u: +untracked) a   Apply"),
+        Line::from("  p          Pop                  d   Drop"),
+        Line::from(""),
+        Line::from(Span::styled("Rebase", section_style)),
+        Line::from("  p/s/f/d    Pick/squash/fix/drop e   Reword"),
+        Line::from("  J/K        Move commit          r   Suggest plan"),
+        Line::from("  x          Run rebase           b   Pick base"),
         Line::from(""),
         Line::from(Span::styled("Press any key to close", theme::dimmed())),
     ];
@@ -0,0 +1,151 @@
+//! History search modal rendering
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::studio::state::HistorySearchState;
+use crate::studio::theme;
+use crate::studio::utils::{pad_width, truncate_width};
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(frame: &mut Frame, area: Rect, search: &HistorySearchState) {
+    let block = Block::default()
+        .title(" Search History ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::accent_secondary()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Percentage(50),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    render_filters(frame, chunks[0], search);
+    render_results(frame, chunks[1], search);
+    render_preview(frame, chunks[2], search);
+
+    let footer = Line::from(vec![
+        Span::styled("↑↓", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" navigate  ", theme::dimmed()),
+        Span::styled("Tab", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" kind  ", theme::dimmed()),
+        Span::styled("S-Tab", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" date  ", theme::dimmed()),
+        Span::styled("Enter", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" copy  ", theme::dimmed()),
+        Span::styled("Esc", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" close", theme::dimmed()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[3]);
+}
+
+fn render_filters(frame: &mut Frame, area: Rect, search: &HistorySearchState) {
+    let chip = Style::default()
+        .fg(theme::accent_tertiary())
+        .add_modifier(Modifier::BOLD);
+    let kind = search.kind.map_or("all", |k| k.short_label());
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Query: ", theme::dimmed()),
+            Span::styled(
+                search.query.as_str(),
+                Style::default().fg(theme::text_primary_color()),
+            ),
+            Span::styled("█", Style::default().fg(theme::accent_secondary())),
+        ]),
+        Line::from(vec![
+            Span::styled("Kind ", theme::dimmed()),
+            Span::styled(kind, chip),
+            Span::styled("  Date ", theme::dimmed()),
+            Span::styled(search.window.label(), chip),
+            Span::styled(
+                format!(
+                    "  {} matches in {} sessions",
+                    search.results.len(),
+                    search.session_count()
+                ),
+                theme::dimmed(),
+            ),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn render_results(frame: &mut Frame, area: Rect, search: &HistorySearchState) {
+    let visible = area.height as usize;
+    if search.results.is_empty() {
+        let msg = if search.session_count() == 0 {
+            "No saved sessions yet"
+        } else {
+            "No matches"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(msg, theme::dimmed()))),
+            area,
+        );
+        return;
+    }
+
+    let scroll = if search.selected >= visible {
+        search.selected - visible + 1
+    } else {
+        0
+    };
+
+    let lines: Vec<Line> = search
+        .results
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible)
+        .map(|(i, hit)| {
+            let is_selected = i == search.selected;
+            let prefix = if is_selected { "▸ " } else { "  " };
+            let meta = format!(
+                "{}{} {:<9} {} ",
+                prefix,
+                hit.date.format("%m-%d %H:%M"),
+                hit.kind.short_label(),
+                pad_width(&truncate_width(&hit.branch, 16), 16)
+            );
+            let remaining = (area.width as usize).saturating_sub(meta.width());
+            let text_style = if is_selected {
+                Style::default()
+                    .fg(theme::accent_secondary())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::text_primary_color())
+            };
+            Line::from(vec![
+                Span::styled(meta, theme::dimmed()),
+                Span::styled(truncate_width(&hit.snippet, remaining), text_style),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn render_preview(frame: &mut Frame, area: Rect, search: &HistorySearchState) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(theme::text_dim_color()));
+    let Some(hit) = search.selected_hit() else {
+        frame.render_widget(block, area);
+        return;
+    };
+    let preview = Paragraph::new(hit.content.as_str())
+        .style(Style::default().fg(theme::text_secondary_color()))
+        .wrap(Wrap { trim: false })
+        .block(block.title(format!(" {} ", hit.kind.label())));
+    frame.render_widget(preview, area);
+}
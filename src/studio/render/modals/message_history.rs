@@ -0,0 +1,109 @@
+//! Message history modal rendering
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::companion::DraftKind;
+use crate::studio::state::MessageHistoryState;
+use crate::studio::theme;
+use crate::studio::utils::truncate_width;
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(frame: &mut Frame, area: Rect, history: &MessageHistoryState) {
+    let block = Block::default()
+        .title(" Message History ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::accent_secondary()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(45),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    render_list(frame, chunks[0], history);
+    render_preview(frame, chunks[1], history);
+
+    let footer = Line::from(vec![
+        Span::styled("↑↓", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" navigate  ", theme::dimmed()),
+        Span::styled("Enter", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" restore  ", theme::dimmed()),
+        Span::styled("Esc", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" close", theme::dimmed()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[2]);
+}
+
+fn kind_label(kind: DraftKind) -> &'static str {
+    match kind {
+        DraftKind::Generated => "generated",
+        DraftKind::Edited => "edited",
+    }
+}
+
+fn render_list(frame: &mut Frame, area: Rect, history: &MessageHistoryState) {
+    let visible = area.height as usize;
+    let scroll = if history.selected >= visible {
+        history.selected - visible + 1
+    } else {
+        0
+    };
+
+    let lines: Vec<Line> = history
+        .drafts
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible)
+        .map(|(i, draft)| {
+            let is_selected = i == history.selected;
+            let prefix = if is_selected { "▸ " } else { "  " };
+            let meta = format!(
+                "{}{} {:<9} ",
+                prefix,
+                draft
+                    .recorded_at
+                    .with_timezone(&chrono::Local)
+                    .format("%m-%d %H:%M"),
+                kind_label(draft.kind)
+            );
+            let remaining = (area.width as usize).saturating_sub(meta.width());
+            let text_style = if is_selected {
+                Style::default()
+                    .fg(theme::accent_secondary())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::text_primary_color())
+            };
+            Line::from(vec![
+                Span::styled(meta, theme::dimmed()),
+                Span::styled(truncate_width(draft.subject(), remaining), text_style),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn render_preview(frame: &mut Frame, area: Rect, history: &MessageHistoryState) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(theme::text_dim_color()));
+    let Some(draft) = history.selected_draft() else {
+        frame.render_widget(block, area);
+        return;
+    };
+    let preview = Paragraph::new(draft.message.as_str())
+        .style(Style::default().fg(theme::text_secondary_color()))
+        .wrap(Wrap { trim: false })
+        .block(block);
+    frame.render_widget(preview, area);
+}
@@ -4,20 +4,33 @@
 
 mod chat_modal;
 mod commit_count;
+mod commit_queue;
 mod confirm;
+mod context_report;
 mod emoji_selector;
 mod help;
+mod history_search;
 mod instructions;
+mod message_history;
+mod model_picker;
+mod pickaxe;
+mod preset_editor;
 mod preset_selector;
+mod rebase_reword;
+mod recap;
 mod ref_selector;
+mod review_export;
 mod search;
+mod semantic_search;
 mod settings;
+mod stash_create;
 mod theme_selector;
 
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::widgets::Clear;
 use std::time::Instant;
+use unicode_width::UnicodeWidthStr;
 
 use crate::studio::state::{Modal, StudioState};
 
@@ -32,14 +45,23 @@ fn modal_size(modal: &Modal, area: Rect) -> (u16, u16) {
             (area.width * 4 / 5).max(80).min(max_width),
             (area.height * 4 / 5).min(max_height),
         ),
-        // Help modal uses available height (40 lines or less)
-        Modal::Help => (70.min(max_width), 40.min(max_height)),
+        // Help modal uses available height (50 lines or less)
+        Modal::Help => (70.min(max_width), 50.min(max_height)),
         // Instructions modal is compact
         Modal::Instructions { .. } => (60.min(max_width), 8.min(max_height)),
         // Search modal with results
         Modal::Search { .. } => (60.min(max_width), 15.min(max_height)),
-        // Confirm modal is minimal
-        Modal::Confirm { .. } => (60.min(max_width), 6.min(max_height)),
+        // Confirm modal is minimal, growing to fit (wrapped) message lines
+        Modal::Confirm { message, .. } => {
+            let width = 60.min(max_width);
+            let inner = usize::from(width.saturating_sub(2)).max(1);
+            let lines: usize = message
+                .lines()
+                .map(|line| line.width().div_ceil(inner).max(1))
+                .sum();
+            let height = u16::try_from(lines + 4).unwrap_or(u16::MAX);
+            (width, height.max(6).min(max_height))
+        }
         // RefSelector sizes based on content when possible
         Modal::RefSelector { refs, .. } => {
             let needed_width = refs
@@ -62,14 +84,53 @@ fn modal_size(modal: &Modal, area: Rect) -> (u16, u16) {
         // Emoji selector grid
         Modal::EmojiSelector { .. } => (55.min(max_width), 26.min(max_height)),
         // Settings modal - full width for fields, compact preview strip
-        Modal::Settings(_) => (70.min(max_width), 24.min(max_height)),
+        Modal::Settings(_) => (70.min(max_width), 34.min(max_height)),
         // Theme selector modal - spacious for preview and list
         Modal::ThemeSelector { themes, .. } => {
             let list_height = (themes.len() as u16 + 8).min(28);
             (75.min(max_width), list_height.min(max_height))
         }
+        // Model picker - filter, list and footer
+        Modal::ModelPicker(_) => (60.min(max_width), 20.min(max_height)),
+        // Preset editor - short fields above a tall instructions box
+        Modal::PresetEditor(_) => (76.min(max_width), 26.min(max_height)),
+        // History search - list plus preview
+        Modal::HistorySearch(_) => (
+            (area.width * 4 / 5).max(80).min(max_width),
+            (area.height * 4 / 5).min(max_height),
+        ),
+        // Pickaxe search - query, results and the matched lines
+        Modal::Pickaxe(_) => (
+            (area.width * 4 / 5).max(80).min(max_width),
+            (area.height * 3 / 4).min(max_height),
+        ),
+        // Semantic search question - a single input line
+        Modal::SemanticSearch { .. } => (70.min(max_width), 8.min(max_height)),
+        // Message history - list plus preview
+        Modal::MessageHistory(_) => (
+            (area.width * 3 / 4).max(70).min(max_width),
+            (area.height * 3 / 4).min(max_height),
+        ),
         // Commit count picker - compact
         Modal::CommitCount { .. } => (45.min(max_width), 9.min(max_height)),
+        // Commit queue - one line per commit plus the selected one's files
+        Modal::CommitQueue => (
+            (area.width * 3 / 4).max(70).min(max_width),
+            22.min(max_height),
+        ),
+        // Context report - one line per section plus totals
+        Modal::ContextReport(_) => (62.min(max_width), 17.min(max_height)),
+        // Recap - a short paragraph and a few bullets
+        Modal::Recap { .. } => (76.min(max_width), 18.min(max_height)),
+        // Review export - one line per finding plus header and footer
+        Modal::ReviewExport(export) => {
+            let height = u16::try_from(export.findings.len() + 7).unwrap_or(u16::MAX);
+            (80.min(max_width), height.clamp(10, 24).min(max_height))
+        }
+        // New stash - message input and the untracked toggle
+        Modal::StashCreate(_) => (70.min(max_width), 10.min(max_height)),
+        // Reword - a single subject line
+        Modal::RebaseReword(_) => (80.min(max_width), 8.min(max_height)),
     }
 }
 
@@ -101,13 +162,24 @@ pub fn render_modal(state: &StudioState, frame: &mut Frame, last_render: Instant
             search::render(frame, modal_area, query, results, *selected);
         }
         Modal::Confirm { message, .. } => confirm::render(frame, modal_area, message),
+        Modal::ContextReport(report) => context_report::render(frame, modal_area, report),
+        Modal::Recap { branch, text } => recap::render(frame, modal_area, branch, text),
         Modal::Chat => chat_modal::render(frame, modal_area, &state.chat_state, last_render),
         Modal::RefSelector {
             input,
             refs,
             selected,
             target,
-        } => ref_selector::render(frame, modal_area, input, refs, *selected, *target),
+            revision,
+        } => ref_selector::render(
+            frame,
+            modal_area,
+            input,
+            refs,
+            *selected,
+            *target,
+            revision.as_ref(),
+        ),
         Modal::PresetSelector {
             input,
             presets,
@@ -127,6 +199,16 @@ pub fn render_modal(state: &StudioState, frame: &mut Frame, last_render: Instant
             selected,
             scroll,
         } => theme_selector::render(frame, modal_area, input, themes, *selected, *scroll),
+        Modal::HistorySearch(search) => history_search::render(frame, modal_area, search),
+        Modal::ModelPicker(picker) => model_picker::render(frame, modal_area, picker),
+        Modal::PresetEditor(editor) => preset_editor::render(frame, modal_area, editor),
+        Modal::Pickaxe(search) => pickaxe::render(frame, modal_area, search),
+        Modal::SemanticSearch { input } => semantic_search::render(frame, modal_area, input),
+        Modal::MessageHistory(history) => message_history::render(frame, modal_area, history),
+        Modal::ReviewExport(export) => review_export::render(frame, modal_area, export),
+        Modal::StashCreate(create) => stash_create::render(frame, modal_area, create),
+        Modal::RebaseReword(reword) => rebase_reword::render(frame, modal_area, reword),
+        Modal::CommitQueue => commit_queue::render(frame, modal_area, &state.modes.commit.queue),
         Modal::CommitCount { input, target } => {
             commit_count::render(frame, modal_area, input, *target);
         }
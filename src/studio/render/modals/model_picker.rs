@@ -0,0 +1,115 @@
+//! Model picker modal rendering
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::studio::state::ModelPickerState;
+use crate::studio::theme;
+use crate::studio::utils::truncate_width;
+
+pub fn render(frame: &mut Frame, area: Rect, picker: &ModelPickerState) {
+    let block = Block::default()
+        .title(format!(" Select Model · {} ", picker.provider))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::accent_primary()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let input_line = Line::from(vec![
+        Span::styled("  Filter: ", theme::dimmed()),
+        Span::styled(
+            picker.input.as_str(),
+            Style::default().fg(theme::text_primary_color()),
+        ),
+        Span::styled("█", Style::default().fg(theme::accent_secondary())),
+    ]);
+    frame.render_widget(Paragraph::new(input_line), chunks[0]);
+
+    render_list(frame, chunks[1], picker);
+
+    let footer = Line::from(vec![
+        Span::styled("  ↑↓", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" navigate  ", theme::dimmed()),
+        Span::styled("Enter", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" select  ", theme::dimmed()),
+        Span::styled("Ctrl+R", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" refresh  ", theme::dimmed()),
+        Span::styled("Esc", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" back", theme::dimmed()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[2]);
+}
+
+fn render_list(frame: &mut Frame, area: Rect, picker: &ModelPickerState) {
+    let filtered = picker.filtered();
+
+    if filtered.is_empty() {
+        let mut lines = Vec::new();
+        if picker.loading {
+            lines.push(Line::from(Span::styled(
+                "  Fetching models…",
+                theme::dimmed(),
+            )));
+        } else if let Some(error) = &picker.error {
+            lines.push(Line::from(Span::styled(
+                format!("  {error}"),
+                theme::error(),
+            )));
+        } else if !picker.models.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  No matching models",
+                theme::dimmed(),
+            )));
+        }
+        let typed = picker.input.trim();
+        let hint = if typed.is_empty() {
+            "  Type a model name and press Enter to set it manually".to_string()
+        } else {
+            format!("  Enter to use \"{typed}\"")
+        };
+        lines.push(Line::from(Span::styled(hint, theme::dimmed())));
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+        return;
+    }
+
+    let width = area.width as usize;
+    let lines: Vec<Line> = filtered
+        .iter()
+        .enumerate()
+        .skip(picker.scroll)
+        .take(area.height as usize)
+        .map(|(i, model)| {
+            let is_selected = i == picker.selected;
+            let is_current = *model == picker.settings.model;
+            let prefix = if is_selected { "  > " } else { "    " };
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme::accent_secondary())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::text_primary_color())
+            };
+            let mut spans = vec![
+                Span::styled(prefix, style),
+                Span::styled(truncate_width(model, width.saturating_sub(14)), style),
+            ];
+            if is_current {
+                spans.push(Span::styled(" (current)", theme::dimmed()));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
@@ -0,0 +1,189 @@
+//! Pickaxe search modal rendering
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::git::PickaxeChange;
+use crate::studio::state::PickaxeState;
+use crate::studio::theme;
+use crate::studio::utils::{pad_width, truncate_width};
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(frame: &mut Frame, area: Rect, search: &PickaxeState) {
+    let block = Block::default()
+        .title(" When Was This Introduced? ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::accent_secondary()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Percentage(55),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    render_query(frame, chunks[0], search);
+    render_results(frame, chunks[1], search);
+    render_preview(frame, chunks[2], search);
+
+    let enter_action = if search.is_current() {
+        " explain why  "
+    } else {
+        " search  "
+    };
+    let footer = Line::from(vec![
+        Span::styled("↑↓", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" navigate  ", theme::dimmed()),
+        Span::styled("Tab", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" string/regex  ", theme::dimmed()),
+        Span::styled("Enter", Style::default().fg(theme::accent_secondary())),
+        Span::styled(enter_action, theme::dimmed()),
+        Span::styled("Esc", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" close", theme::dimmed()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[3]);
+}
+
+fn render_query(frame: &mut Frame, area: Rect, search: &PickaxeState) {
+    let chip = Style::default()
+        .fg(theme::accent_tertiary())
+        .add_modifier(Modifier::BOLD);
+
+    let status = if search.loading {
+        Span::styled("  searching...", theme::dimmed())
+    } else if let Some(error) = &search.error {
+        Span::styled(
+            format!("  {error}"),
+            Style::default().fg(theme::error_color()),
+        )
+    } else if search.is_current() {
+        Span::styled(
+            format!("  {} matches", search.results.len()),
+            theme::dimmed(),
+        )
+    } else {
+        Span::styled("  Enter to search", theme::dimmed())
+    };
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Find: ", theme::dimmed()),
+            Span::styled(
+                search.query.as_str(),
+                Style::default().fg(theme::text_primary_color()),
+            ),
+            Span::styled("█", Style::default().fg(theme::accent_secondary())),
+        ]),
+        Line::from(vec![
+            Span::styled("Match ", theme::dimmed()),
+            Span::styled(search.kind.label(), chip),
+            Span::styled(
+                format!(" (git log {})", search.kind.flag()),
+                theme::dimmed(),
+            ),
+            status,
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn render_results(frame: &mut Frame, area: Rect, search: &PickaxeState) {
+    let visible = area.height as usize;
+    if search.results.is_empty() {
+        let msg = if search.is_current() && !search.loading && search.error.is_none() {
+            "No commits added or removed it"
+        } else {
+            ""
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(msg, theme::dimmed()))),
+            area,
+        );
+        return;
+    }
+
+    let scroll = if search.selected >= visible {
+        search.selected - visible + 1
+    } else {
+        0
+    };
+
+    let lines: Vec<Line> = search
+        .results
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible)
+        .map(|(i, hit)| {
+            let is_selected = i == search.selected;
+            let prefix = if is_selected { "▸ " } else { "  " };
+            let change_style = Style::default().fg(match hit.change() {
+                PickaxeChange::Added => theme::success_color(),
+                PickaxeChange::Removed => theme::error_color(),
+                PickaxeChange::Modified => theme::warning_color(),
+            });
+            let meta = format!("{}{} {} ", prefix, hit.short_hash, hit.date);
+            let change = format!("{} ", pad_width(hit.change().label(), 7));
+            let file = format!(
+                "{} ",
+                pad_width(&truncate_width(&hit.file.to_string_lossy(), 28), 28)
+            );
+            let remaining =
+                (area.width as usize).saturating_sub(meta.width() + change.width() + file.width());
+            let text_style = if is_selected {
+                Style::default()
+                    .fg(theme::accent_secondary())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::text_primary_color())
+            };
+            Line::from(vec![
+                Span::styled(meta, theme::dimmed()),
+                Span::styled(change, change_style),
+                Span::styled(file, Style::default().fg(theme::text_secondary_color())),
+                Span::styled(truncate_width(&hit.subject, remaining), text_style),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn render_preview(frame: &mut Frame, area: Rect, search: &PickaxeState) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(theme::text_dim_color()));
+    let Some(hit) = search.selected_hit() else {
+        frame.render_widget(block, area);
+        return;
+    };
+
+    let width = area.width as usize;
+    let lines: Vec<Line> = hit
+        .lines
+        .iter()
+        .map(|line| {
+            let color = if line.starts_with('+') {
+                theme::success_color()
+            } else {
+                theme::error_color()
+            };
+            Line::from(Span::styled(
+                truncate_width(line, width),
+                Style::default().fg(color),
+            ))
+        })
+        .collect();
+    let title = format!(
+        " {} by {}, {} ",
+        hit.short_hash, hit.author, hit.relative_time
+    );
+    frame.render_widget(Paragraph::new(lines).block(block.title(title)), area);
+}
@@ -0,0 +1,161 @@
+//! Preset editor modal rendering
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use unicode_width::UnicodeWidthStr;
+
+use crate::studio::state::{PresetEditorField, PresetEditorState};
+use crate::studio::theme;
+use crate::studio::utils::truncate_width;
+
+pub fn render(frame: &mut Frame, area: Rect, editor: &PresetEditorState) {
+    let title = match &editor.editing {
+        Some(key) => format!(" Edit Preset · {key} "),
+        None => " New Preset ".to_string(),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::accent_primary()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(5),
+            Constraint::Min(4),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let type_value = format!("◀ {} ▶", editor.preset_type.as_str());
+    let fields = [
+        (PresetEditorField::Key, editor.key.as_str()),
+        (PresetEditorField::Name, editor.name.as_str()),
+        (PresetEditorField::Emoji, editor.emoji.as_str()),
+        (PresetEditorField::Type, type_value.as_str()),
+    ];
+    let lines: Vec<Line> = fields
+        .iter()
+        .map(|(field, value)| field_line(editor, *field, value))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    render_instructions(frame, chunks[1], editor);
+
+    let status = if let Some(error) = &editor.error {
+        Span::styled(
+            format!("  {error}"),
+            Style::default().fg(theme::error_color()),
+        )
+    } else {
+        let path = editor
+            .save_path(editor.key.trim())
+            .map_or_else(String::new, |path| path.display().to_string());
+        Span::styled(
+            truncate_width(&format!("  Saves to {path}"), chunks[2].width as usize),
+            theme::dimmed(),
+        )
+    };
+    frame.render_widget(Paragraph::new(Line::from(status)), chunks[2]);
+
+    let footer = Line::from(vec![
+        Span::styled("  Tab", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" next field  ", theme::dimmed()),
+        Span::styled("←→", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" type  ", theme::dimmed()),
+        Span::styled(
+            "Ctrl+S",
+            Style::default()
+                .fg(theme::success_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" save  ", theme::dimmed()),
+        Span::styled("Esc", Style::default().fg(theme::warning_color())),
+        Span::styled(" back", theme::dimmed()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[3]);
+}
+
+/// One short field: label, value and a cursor when focused
+fn field_line<'a>(
+    editor: &PresetEditorState,
+    field: PresetEditorField,
+    value: &'a str,
+) -> Line<'a> {
+    let focused = editor.field == field;
+    let fixed = field == PresetEditorField::Key && editor.editing.is_some();
+    let label_style = if focused {
+        Style::default()
+            .fg(theme::accent_primary())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        theme::dimmed()
+    };
+    let value_style = if fixed {
+        theme::dimmed()
+    } else {
+        Style::default().fg(theme::text_primary_color())
+    };
+
+    let mut spans = vec![
+        Span::styled(if focused { "▸ " } else { "  " }, label_style),
+        Span::styled(format!("{:<14}", field.label()), label_style),
+        Span::styled(value, value_style),
+    ];
+    if focused && field != PresetEditorField::Type {
+        spans.push(Span::styled(
+            "█",
+            Style::default().fg(theme::accent_secondary()),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// The instructions box, scrolled so the end stays visible
+fn render_instructions(frame: &mut Frame, area: Rect, editor: &PresetEditorState) {
+    let focused = editor.field == PresetEditorField::Instructions;
+    let border = if focused {
+        Style::default().fg(theme::accent_primary())
+    } else {
+        Style::default().fg(theme::text_dim_color())
+    };
+    let block = Block::default()
+        .title(" Instructions ")
+        .borders(Borders::ALL)
+        .border_style(border);
+    let inner = block.inner(area);
+
+    let mut text = editor.instructions.clone();
+    if focused {
+        text.push('█');
+    }
+    let width = usize::from(inner.width).max(1);
+    let wrapped: usize = text
+        .split('\n')
+        .map(|line| line.width().div_ceil(width).max(1))
+        .sum();
+    let scroll = wrapped.saturating_sub(usize::from(inner.height));
+
+    let lines: Vec<Line> = text
+        .split('\n')
+        .map(|line| {
+            Line::from(Span::styled(
+                line.to_string(),
+                Style::default().fg(theme::text_primary_color()),
+            ))
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0))
+            .block(block),
+        area,
+    );
+}
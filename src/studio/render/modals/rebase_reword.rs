@@ -0,0 +1,41 @@
+//! Reword modal rendering for Rebase mode
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::studio::state::RebaseRewordState;
+use crate::studio::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, reword: &RebaseRewordState) {
+    let block = Block::default()
+        .title(format!(" Reword {} ", reword.short_id))
+        .borders(Borders::ALL)
+        .border_style(theme::focused_border());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines = vec![
+        Line::from(Span::styled(
+            "New subject line (the body is kept):",
+            theme::dimmed(),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme::accent_primary())),
+            Span::styled(
+                reword.subject.as_str(),
+                Style::default().fg(theme::text_primary_color()),
+            ),
+            Span::styled("█", Style::default().fg(theme::accent_secondary())),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Enter save · Esc cancel · empty keeps the original",
+            theme::dimmed(),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines), inner);
+}
@@ -0,0 +1,31 @@
+//! Recap modal rendering
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::studio::render::chat::format_markdown;
+use crate::studio::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, branch: &str, text: &str) {
+    let block = Block::default()
+        .title(format!(" Welcome back · {branch} "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::accent_primary()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let style = Style::default().fg(theme::text_primary_color());
+    let lines = format_markdown(text, usize::from(chunks[0].width), style);
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+    let footer = Line::from(Span::styled("  Press any key to continue", theme::dimmed()));
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
@@ -6,6 +6,7 @@ use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
+use crate::git::ResolvedRevision;
 use crate::studio::state::RefSelectorTarget;
 use crate::studio::theme;
 
@@ -16,6 +17,7 @@ pub fn render(
     refs: &[String],
     selected: usize,
     target: RefSelectorTarget,
+    revision: Option<&Result<ResolvedRevision, String>>,
 ) {
     let title = match target {
         RefSelectorTarget::ReviewFrom => " Select Review From Ref ",
@@ -26,6 +28,7 @@ pub fn render(
         RefSelectorTarget::ChangelogTo => " Select Changelog To ",
         RefSelectorTarget::ReleaseNotesFrom => " Select Release Notes From ",
         RefSelectorTarget::ReleaseNotesTo => " Select Release Notes To ",
+        RefSelectorTarget::RebaseBase => " Rebase Onto ",
     };
 
     let block = Block::default()
@@ -75,16 +78,29 @@ pub fn render(
                 theme::dimmed(),
             )));
         } else {
-            // Show that the custom input will be used
-            lines.push(Line::from(vec![
-                Span::styled("▸ ", Style::default().fg(theme::success_color())),
-                Span::styled(
-                    format!("Use custom ref: {input}"),
-                    Style::default()
-                        .fg(theme::success_color())
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]));
+            // The typed text is used as a revision expression
+            match revision {
+                Some(Ok(resolved)) => lines.push(Line::from(vec![
+                    Span::styled("▸ ", Style::default().fg(theme::success_color())),
+                    Span::styled(
+                        format!("Use {}", input.trim()),
+                        Style::default()
+                            .fg(theme::success_color())
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(" → ", theme::dimmed()),
+                    Span::styled(resolved.short_id().to_string(), theme::commit_hash()),
+                    Span::styled(format!(" {}", resolved.summary), theme::dimmed()),
+                ])),
+                Some(Err(e)) => lines.push(Line::from(vec![
+                    Span::styled("✗ ", Style::default().fg(theme::error_color())),
+                    Span::styled(e.clone(), Style::default().fg(theme::error_color())),
+                ])),
+                None => lines.push(Line::from(Span::styled(
+                    format!("Use {input}"),
+                    theme::dimmed(),
+                ))),
+            }
         }
     }
 
@@ -99,7 +115,7 @@ pub fn render(
         Span::styled(" cancel", theme::dimmed()),
     ]));
     lines.push(Line::from(Span::styled(
-        "Type any ref: branch, tag, HEAD~N, commit hash",
+        "Or any revision: tag, HEAD~N, v1.2.0^, @{u}, commit hash",
         theme::dimmed(),
     )));
 
@@ -0,0 +1,112 @@
+//! Review export modal rendering
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::studio::state::{ExportedFinding, ReviewExportState};
+use crate::studio::theme;
+use crate::studio::utils::truncate_width;
+use crate::types::Severity;
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(frame: &mut Frame, area: Rect, export: &ReviewExportState) {
+    let block = Block::default()
+        .title(" Post Review to GitHub ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::accent_secondary()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let inline = export.inline_count();
+    let in_summary = export.findings.len() - inline;
+    let header = vec![
+        Line::from(vec![
+            Span::styled("Pull request for ", theme::dimmed()),
+            Span::styled(
+                export.head.clone(),
+                Style::default()
+                    .fg(theme::accent_primary())
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(Span::styled(
+            format!(
+                "{inline} inline comment{}, {in_summary} in the summary",
+                if inline == 1 { "" } else { "s" }
+            ),
+            Style::default().fg(theme::text_primary_color()),
+        )),
+        Line::from(Span::styled(
+            format!("Line numbers refer to {}; push it first", export.to_ref),
+            theme::dimmed(),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+
+    let width = chunks[1].width as usize;
+    let lines: Vec<Line> = export
+        .findings
+        .iter()
+        .skip(export.scroll)
+        .take(chunks[1].height as usize)
+        .map(|finding| finding_line(finding, width))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    let footer = Line::from(vec![
+        Span::styled("y/Enter", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" post  ", theme::dimmed()),
+        Span::styled("↑↓", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" scroll  ", theme::dimmed()),
+        Span::styled("Esc", Style::default().fg(theme::accent_secondary())),
+        Span::styled(" cancel", theme::dimmed()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[2]);
+}
+
+fn severity_style(severity: Severity) -> Style {
+    match severity {
+        Severity::Critical | Severity::High => theme::error(),
+        Severity::Medium => theme::warning(),
+        Severity::Low => theme::dimmed(),
+    }
+}
+
+/// Badge, where the comment goes, and title
+fn finding_line(finding: &ExportedFinding, width: usize) -> Line<'static> {
+    let badge = format!("{:<10}", format!("[{}]", finding.severity.label()));
+    let target = match &finding.comment {
+        Some(comment) => match comment.start_line {
+            Some(start) => format!("{}:{start}-{}", comment.path, comment.line),
+            None => format!("{}:{}", comment.path, comment.line),
+        },
+        None => "summary".to_string(),
+    };
+    let target = format!("{} ", truncate_width(&target, 30));
+    let remaining = width.saturating_sub(badge.width() + target.width());
+    let target_style = if finding.comment.is_some() {
+        Style::default().fg(theme::accent_tertiary())
+    } else {
+        theme::dimmed()
+    };
+    Line::from(vec![
+        Span::styled(badge, severity_style(finding.severity)),
+        Span::styled(target, target_style),
+        Span::styled(
+            truncate_width(&finding.title, remaining),
+            Style::default().fg(theme::text_primary_color()),
+        ),
+    ])
+}
@@ -0,0 +1,37 @@
+//! Semantic search question modal rendering
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::studio::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, input: &str) {
+    let block = Block::default()
+        .title(" Search by Meaning ")
+        .borders(Borders::ALL)
+        .border_style(theme::focused_border());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines = vec![
+        Line::from(Span::styled(
+            "Ask where something happens, e.g. \"where do we rotate API keys\":",
+            theme::dimmed(),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme::accent_primary())),
+            Span::styled(input, Style::default().fg(theme::text_primary_color())),
+            Span::styled("█", Style::default().fg(theme::accent_secondary())),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Press Enter to search, Esc to cancel",
+            theme::dimmed(),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines), inner);
+}
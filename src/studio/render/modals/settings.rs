@@ -6,9 +6,10 @@ use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
-use crate::studio::state::{SettingsField, SettingsSection, SettingsState};
+use crate::studio::state::{ConnectionStatus, SettingsField, SettingsSection, SettingsState};
 use crate::theme;
 use crate::theme::adapters::ratatui::{ThemeColorExt, ToRatatuiColor};
+use unicode_width::UnicodeWidthStr;
 
 /// Unicode box drawing characters for visual polish
 const BOX_HORIZONTAL: &str = "─";
@@ -18,12 +19,12 @@ pub fn render(frame: &mut Frame, area: Rect, state: &SettingsState) {
 
     let t = theme::current();
 
-    // Title with modification indicator
-    let title = if state.modified {
-        " Settings * "
-    } else {
-        " Settings "
-    };
+    // Title with save scope and modification indicator
+    let title = format!(
+        " Settings · {}{} ",
+        state.scope.display_name(),
+        if state.modified { " *" } else { "" }
+    );
 
     let block = Block::default()
         .title(title)
@@ -57,6 +58,7 @@ fn render_settings_fields(frame: &mut Frame, area: Rect, state: &SettingsState)
     let t = theme::current();
     let mut lines = Vec::new();
     let mut current_section: Option<SettingsSection> = None;
+    let mut selected_line = 0;
 
     for (idx, field) in SettingsField::all().iter().enumerate() {
         let section = field.section();
@@ -75,7 +77,7 @@ fn render_settings_fields(frame: &mut Frame, area: Rect, state: &SettingsState)
                     .add_modifier(Modifier::BOLD),
             )));
             lines.push(Line::from(Span::styled(
-                BOX_HORIZONTAL.repeat(section_name.len()),
+                BOX_HORIZONTAL.repeat(section_name.width()),
                 Style::default().fg(t.ratatui_color("text.dim")),
             )));
 
@@ -83,6 +85,9 @@ fn render_settings_fields(frame: &mut Frame, area: Rect, state: &SettingsState)
         }
 
         let is_selected = idx == state.selected_field;
+        if is_selected {
+            selected_line = lines.len();
+        }
         let value = state.get_field_value(*field);
 
         // Styles based on selection
@@ -124,24 +129,46 @@ fn render_settings_fields(frame: &mut Frame, area: Rect, state: &SettingsState)
             ""
         };
 
+        // Where the effective value comes from
+        let (origin, origin_color) = if state.is_project_value(*field) {
+            ("repo ", "accent.tertiary")
+        } else {
+            ("global ", "text.dim")
+        };
+
         // Build row with fixed-width label
         let label_width = 14;
         let label = format!("  {:width$}", field.display_name(), width = label_width);
         let value_text = format!("{}{}", display_value, suffix);
 
-        // Pad to fill background highlight
-        let padding_len = area
-            .width
-            .saturating_sub(label.len() as u16 + value_text.len() as u16 + 1);
+        // Pad to fill background highlight, leaving room for the origin tag
+        let padding_len = area.width.saturating_sub(
+            u16::try_from(label.width() + value_text.width() + origin.width()).unwrap_or(u16::MAX),
+        );
         let padding = " ".repeat(padding_len as usize);
 
         lines.push(Line::from(vec![
             Span::styled(label, label_style),
             Span::styled(value_text, value_style),
             Span::styled(padding, row_style),
+            Span::styled(origin, row_style.fg(t.ratatui_color(origin_color))),
         ]));
     }
 
+    // Connection test result
+    if let Some(status) = &state.connection {
+        let (text, color) = match status {
+            ConnectionStatus::Testing => ("  Testing connection…".to_string(), "text.muted"),
+            ConnectionStatus::Ok(ms) => (format!("  ✓ Connected in {ms} ms"), "success"),
+            ConnectionStatus::Failed(reason) => (format!("  ✗ {reason}"), "error"),
+        };
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            text,
+            Style::default().fg(t.ratatui_color(color)),
+        )));
+    }
+
     // Error message
     if let Some(error) = &state.error {
         lines.push(Line::from(""));
@@ -151,7 +178,10 @@ fn render_settings_fields(frame: &mut Frame, area: Rect, state: &SettingsState)
         )));
     }
 
-    frame.render_widget(Paragraph::new(lines), area);
+    // Keep the selected row visible on short terminals
+    let height = area.height as usize;
+    let scroll = (selected_line + 1).saturating_sub(height);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), area);
 }
 
 #[allow(clippy::cast_precision_loss)]
@@ -221,6 +251,39 @@ fn render_footer(frame: &mut Frame, area: Rect, state: &SettingsState) {
                 Style::default().fg(t.ratatui_color("text.muted")),
             ),
         ])
+    } else if state.current_field() == SettingsField::InstructionPreset {
+        Line::from(vec![
+            Span::styled(
+                "  ←→",
+                Style::default().fg(t.ratatui_color("accent.primary")),
+            ),
+            Span::styled(
+                " cycle  ",
+                Style::default().fg(t.ratatui_color("text.muted")),
+            ),
+            Span::styled("n", Style::default().fg(t.ratatui_color("accent.primary"))),
+            Span::styled(
+                " new preset  ",
+                Style::default().fg(t.ratatui_color("text.muted")),
+            ),
+            Span::styled("e", Style::default().fg(t.ratatui_color("accent.primary"))),
+            Span::styled(
+                " edit preset  ",
+                Style::default().fg(t.ratatui_color("text.muted")),
+            ),
+            Span::styled(
+                "s",
+                Style::default()
+                    .fg(t.ratatui_color("success"))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                " save  ",
+                Style::default().fg(t.ratatui_color("text.muted")),
+            ),
+            Span::styled("Esc", Style::default().fg(t.ratatui_color("warning"))),
+            Span::styled(" close", Style::default().fg(t.ratatui_color("text.muted"))),
+        ])
     } else {
         Line::from(vec![
             Span::styled(
@@ -241,6 +304,19 @@ fn render_footer(frame: &mut Frame, area: Rect, state: &SettingsState) {
                 " edit  ",
                 Style::default().fg(t.ratatui_color("text.muted")),
             ),
+            Span::styled(
+                "Tab",
+                Style::default().fg(t.ratatui_color("accent.primary")),
+            ),
+            Span::styled(
+                " scope  ",
+                Style::default().fg(t.ratatui_color("text.muted")),
+            ),
+            Span::styled("t", Style::default().fg(t.ratatui_color("accent.primary"))),
+            Span::styled(
+                " test  ",
+                Style::default().fg(t.ratatui_color("text.muted")),
+            ),
             Span::styled(
                 "s",
                 Style::default()
@@ -0,0 +1,56 @@
+//! New stash modal rendering
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::studio::state::StashCreateState;
+use crate::studio::theme;
+
+pub fn render(frame: &mut Frame, area: Rect, create: &StashCreateState) {
+    let block = Block::default()
+        .title(" Stash Changes ")
+        .borders(Borders::ALL)
+        .border_style(theme::focused_border());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let status = if create.describing {
+        Span::styled("Iris is describing your changes...", theme::dimmed())
+    } else if create.message.is_empty() {
+        Span::styled(
+            "Empty message: git names it \"WIP on <branch>\"",
+            theme::dimmed(),
+        )
+    } else {
+        Span::styled("Stash message:", theme::dimmed())
+    };
+    let untracked = if create.include_untracked {
+        Span::styled("[x] include untracked files", theme::git_untracked())
+    } else {
+        Span::styled("[ ] include untracked files", theme::dimmed())
+    };
+
+    let lines = vec![
+        Line::from(status),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme::accent_primary())),
+            Span::styled(
+                create.message.as_str(),
+                Style::default().fg(theme::text_primary_color()),
+            ),
+            Span::styled("█", Style::default().fg(theme::accent_secondary())),
+        ]),
+        Line::from(""),
+        Line::from(untracked),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Enter stash · Tab toggle untracked · Esc cancel",
+            theme::dimmed(),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines), inner);
+}
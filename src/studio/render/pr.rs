@@ -1,7 +1,7 @@
 //! PR mode rendering for Iris Studio
 
 use ratatui::Frame;
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 
@@ -9,21 +9,79 @@ use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 #[allow(unused_imports)]
 use std::time::SystemTime;
 
+use crate::integrations::ci::CheckState;
 use crate::studio::components::render_diff_view;
-use crate::studio::state::{PanelId, StudioState};
+use crate::studio::state::{PanelId, PrState, StudioState};
 use crate::studio::theme;
 
+/// Most rows the CI status takes under the commit list
+const MAX_CI_ROWS: usize = 8;
+
+fn check_style(state: CheckState) -> Style {
+    match state {
+        CheckState::Failing => theme::error(),
+        CheckState::Pending => theme::warning(),
+        CheckState::Passing => theme::success(),
+    }
+}
+
+/// CI status lines: the overall state, then each check, failing first
+fn ci_lines(pr: &PrState) -> Vec<Line<'static>> {
+    if pr.ci_loading {
+        return vec![Line::styled("CI: checking…", theme::dimmed())];
+    }
+    let status = match &pr.ci {
+        None => return Vec::new(),
+        Some(Err(e)) => return vec![Line::styled(format!("CI: {e}"), theme::dimmed())],
+        Some(Ok(status)) => status,
+    };
+    let Some(overall) = status.state() else {
+        return vec![Line::styled("CI: no checks reported [c]", theme::dimmed())];
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled("CI ", theme::dimmed()),
+        Span::styled(
+            format!("{} {}", overall.icon(), overall.label()),
+            check_style(overall).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!(" · {} [c]", status.summary()), theme::dimmed()),
+    ])];
+    // Leave a row for the overflow note when the checks don't all fit
+    let shown = if status.checks.len() < MAX_CI_ROWS {
+        status.checks.len()
+    } else {
+        MAX_CI_ROWS - 2
+    };
+    for check in status.checks.iter().take(shown) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} ", check.state.icon()),
+                check_style(check.state),
+            ),
+            Span::styled(
+                check.name.clone(),
+                Style::default().fg(theme::text_dim_color()),
+            ),
+        ]));
+    }
+    if status.checks.len() > shown {
+        lines.push(Line::styled(
+            format!("  … {} more", status.checks.len() - shown),
+            theme::dimmed(),
+        ));
+    }
+    lines
+}
+
 /// Create a panel title with scroll position indicator
 fn scrollable_title(base_title: &str, scroll: usize, total_lines: usize, visible: usize) -> String {
     if total_lines <= visible {
         format!(" {} ", base_title)
     } else {
         let max_scroll = total_lines.saturating_sub(visible);
-        let percent = if max_scroll == 0 {
-            100
-        } else {
-            ((scroll.min(max_scroll)) * 100) / max_scroll
-        };
+        let percent = (scroll.min(max_scroll) * 100)
+            .checked_div(max_scroll)
+            .unwrap_or(100);
         format!(
             " {} ({}/{}) {}% ",
             base_title,
@@ -58,6 +116,14 @@ pub fn render_pr_panel(state: &mut StudioState, frame: &mut Frame, area: Rect, p
             let inner = block.inner(area);
             frame.render_widget(block, area);
 
+            let ci = ci_lines(&state.modes.pr);
+            let [inner, ci_area] = Layout::vertical([
+                Constraint::Min(1),
+                Constraint::Length(ci.len().min(MAX_CI_ROWS) as u16),
+            ])
+            .areas(inner);
+            frame.render_widget(Paragraph::new(ci), ci_area);
+
             if state.modes.pr.commits.is_empty() {
                 let text = Paragraph::new("No commits to show").style(theme::dimmed());
                 frame.render_widget(text, inner);
@@ -97,11 +163,11 @@ pub fn render_pr_panel(state: &mut StudioState, frame: &mut Frame, area: Rect, p
             let visible_height = area.height.saturating_sub(2) as usize;
 
             // Prefer streaming content if available, then final content
-            let content_to_display = state.modes.pr.streaming_content.as_ref().or(
+            let content_to_display = state.modes.pr.streaming_content.as_deref().or(
                 if state.modes.pr.pr_content.is_empty() {
                     None
                 } else {
-                    Some(&state.modes.pr.pr_content)
+                    Some(&*state.modes.pr.pr_content)
                 },
             );
 
@@ -131,7 +197,7 @@ pub fn render_pr_panel(state: &mut StudioState, frame: &mut Frame, area: Rect, p
                     .lines()
                     .skip(state.modes.pr.pr_scroll)
                     .take(inner.height as usize)
-                    .map(|line| Line::from(line.to_string()))
+                    .map(Line::from)
                     .collect();
                 let paragraph = Paragraph::new(lines);
                 frame.render_widget(paragraph, inner);
@@ -0,0 +1,222 @@
+//! Rebase mode rendering for Iris Studio
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::git::{RebaseAction, RebaseStep};
+use crate::studio::components::render_diff_view;
+use crate::studio::state::{PanelId, RebaseState, StudioState};
+use crate::studio::theme;
+use crate::studio::utils::truncate_width;
+use unicode_width::UnicodeWidthStr;
+
+/// Render a panel in Rebase mode
+pub fn render_rebase_panel(
+    state: &mut StudioState,
+    frame: &mut Frame,
+    area: Rect,
+    panel_id: PanelId,
+) {
+    let is_focused = panel_id == state.focused_panel;
+    let rebase = &state.modes.rebase;
+
+    match panel_id {
+        PanelId::Left => render_plan(rebase, frame, area, is_focused),
+        PanelId::Center => render_details(rebase, frame, area, is_focused),
+        PanelId::Right => {
+            let title = rebase.selected_step().map_or_else(
+                || "Diff".to_string(),
+                |step| step.commit.short_id().to_string(),
+            );
+            render_diff_view(frame, area, &rebase.diff_view, &title, is_focused);
+        }
+    }
+}
+
+fn block(title: String, focused: bool) -> Block<'static> {
+    Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(if focused {
+            theme::focused_border()
+        } else {
+            theme::unfocused_border()
+        })
+}
+
+fn action_style(action: RebaseAction) -> Style {
+    match action {
+        RebaseAction::Pick => Style::default().fg(theme::text_secondary_color()),
+        RebaseAction::Reword => Style::default().fg(theme::accent_secondary()),
+        RebaseAction::Squash | RebaseAction::Fixup => Style::default().fg(theme::warning_color()),
+        RebaseAction::Drop => theme::error(),
+    }
+}
+
+/// The todo list: one line per commit, folded commits indented under the
+/// commit they join
+fn render_plan(rebase: &RebaseState, frame: &mut Frame, area: Rect, focused: bool) {
+    let title = rebase.plan.as_ref().map_or_else(
+        || " Plan ".to_string(),
+        |plan| {
+            format!(
+                " {} onto {} ({} → {}) ",
+                plan.branch,
+                plan.base,
+                plan.steps.len(),
+                plan.result_count()
+            )
+        },
+    );
+    let block = block(title, focused);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(plan) = &rebase.plan else {
+        let hint = rebase
+            .error
+            .as_deref()
+            .unwrap_or("Loading the branch...")
+            .to_string();
+        frame.render_widget(
+            Paragraph::new(Line::styled(hint, theme::dimmed())).wrap(Wrap { trim: true }),
+            inner,
+        );
+        return;
+    };
+    if plan.steps.is_empty() {
+        let hint = format!(
+            "No commits on {} since {} · b to pick another base",
+            plan.branch, plan.base
+        );
+        frame.render_widget(Paragraph::new(Line::styled(hint, theme::dimmed())), inner);
+        return;
+    }
+
+    let mut lines = Vec::new();
+    if rebase.suggesting {
+        lines.push(Line::styled(
+            "Iris is planning the rebase...",
+            theme::dimmed(),
+        ));
+    }
+    let visible = usize::from(inner.height).saturating_sub(lines.len()).max(1);
+    let offset = rebase.selected.saturating_sub(visible - 1);
+    let width = usize::from(inner.width);
+
+    for (i, step) in plan.steps.iter().enumerate().skip(offset).take(visible) {
+        let is_selected = i == rebase.selected;
+        let marker = if is_selected { "▸ " } else { "  " };
+        let indent = if step.action.is_fold() { "  ↳ " } else { "" };
+        let prefix_width = 2 + indent.width() + 7 + 1 + 7 + 1;
+        let subject_style = if step.action == RebaseAction::Drop {
+            theme::dimmed().add_modifier(Modifier::CROSSED_OUT)
+        } else {
+            Style::default().fg(theme::text_primary_color())
+        };
+        let line = Line::from(vec![
+            Span::styled(marker, theme::keyword()),
+            Span::styled(indent, theme::dimmed()),
+            Span::styled(
+                format!("{:<7}", step.action.as_str()),
+                action_style(step.action),
+            ),
+            Span::raw(" "),
+            Span::styled(step.commit.short_id().to_string(), theme::commit_hash()),
+            Span::raw(" "),
+            Span::styled(
+                truncate_width(step.subject(), width.saturating_sub(prefix_width)),
+                subject_style,
+            ),
+        ]);
+        lines.push(if is_selected {
+            line.style(theme::selected())
+        } else {
+            line
+        });
+    }
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// The selected step: what happens to the commit, why, and its message
+fn render_details(rebase: &RebaseState, frame: &mut Frame, area: Rect, focused: bool) {
+    let block = block(" Details ".to_string(), focused);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(step) = rebase.selected_step() else {
+        return;
+    };
+    let lines = details_lines(step);
+    let scroll = u16::try_from(rebase.details_scroll).unwrap_or(u16::MAX);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0)),
+        inner,
+    );
+}
+
+fn details_lines(step: &RebaseStep) -> Vec<Line<'static>> {
+    let commit = &step.commit;
+    let section = Style::default()
+        .fg(theme::accent_primary())
+        .add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(step.action.as_str().to_string(), action_style(step.action)),
+            Span::raw(" "),
+            Span::styled(commit.short_id().to_string(), theme::commit_hash()),
+            Span::styled(format!(" by {}", commit.author), theme::author()),
+        ]),
+        Line::from(""),
+    ];
+
+    if let Some(subject) = step
+        .new_subject
+        .as_ref()
+        .filter(|_| step.action == RebaseAction::Reword)
+    {
+        lines.push(Line::styled(subject.clone(), theme::success()));
+        lines.push(Line::styled(
+            format!("was: {}", commit.summary),
+            theme::dimmed(),
+        ));
+    } else {
+        lines.push(Line::styled(
+            commit.summary.clone(),
+            Style::default().fg(theme::text_primary_color()),
+        ));
+    }
+    let body = commit
+        .message
+        .split_once('\n')
+        .map_or("", |(_, body)| body)
+        .trim();
+    if !body.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(body.lines().map(|line| Line::from(line.to_string())));
+    }
+
+    if let Some(rationale) = &step.rationale {
+        lines.push(Line::from(""));
+        lines.push(Line::styled("Why", section));
+        lines.push(Line::from(rationale.clone()));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        format!("Files ({})", commit.files.len()),
+        section,
+    ));
+    lines.extend(
+        commit
+            .files
+            .iter()
+            .map(|file| Line::styled(file.clone(), theme::file_path())),
+    );
+    lines
+}
@@ -1,10 +1,10 @@
 //! Release Notes mode rendering for Iris Studio
 
 use ratatui::Frame;
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
 
 use crate::studio::components::render_diff_view;
 use crate::studio::state::{PanelId, StudioState};
@@ -16,11 +16,9 @@ fn scrollable_title(base_title: &str, scroll: usize, total_lines: usize, visible
         format!(" {} ", base_title)
     } else {
         let max_scroll = total_lines.saturating_sub(visible);
-        let percent = if max_scroll == 0 {
-            100
-        } else {
-            ((scroll.min(max_scroll)) * 100) / max_scroll
-        };
+        let percent = (scroll.min(max_scroll) * 100)
+            .checked_div(max_scroll)
+            .unwrap_or(100);
         format!(
             " {} ({}/{}) {}% ",
             base_title,
@@ -101,11 +99,11 @@ pub fn render_release_notes_panel(
             let visible_height = area.height.saturating_sub(2) as usize;
 
             // Prefer streaming content if available, then final content
-            let content_to_display = state.modes.release_notes.streaming_content.as_ref().or(
+            let content_to_display = state.modes.release_notes.streaming_content.as_deref().or(
                 if state.modes.release_notes.release_notes_content.is_empty() {
                     None
                 } else {
-                    Some(&state.modes.release_notes.release_notes_content)
+                    Some(&*state.modes.release_notes.release_notes_content)
                 },
             );
 
@@ -126,16 +124,53 @@ pub fn render_release_notes_panel(
                 } else {
                     theme::unfocused_border()
                 });
-            let inner = block.inner(area);
+            let mut inner = block.inner(area);
             frame.render_widget(block, area);
 
+            // Suggested version above the finished notes
+            if let Some(suggestion) = &state.modes.release_notes.suggested_version
+                && state.modes.release_notes.streaming_content.is_none()
+                && inner.height > 4
+            {
+                let [banner, rest] =
+                    Layout::vertical([Constraint::Length(3), Constraint::Min(1)]).areas(inner);
+                let next = match (&suggestion.current, &suggestion.next) {
+                    (Some(current), Some(next)) => format!("{current} → {next}"),
+                    _ => "Next version".to_string(),
+                };
+                let lines = vec![
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{next} "),
+                            Style::default()
+                                .fg(theme::accent_secondary())
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(format!("({} bump)", suggestion.bump), theme::dimmed()),
+                    ]),
+                    Line::from(Span::styled(
+                        suggestion.justification.clone(),
+                        Style::default().fg(theme::text_secondary_color()),
+                    )),
+                ];
+                frame.render_widget(
+                    Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+                        Block::default()
+                            .borders(Borders::BOTTOM)
+                            .border_style(theme::unfocused_border()),
+                    ),
+                    banner,
+                );
+                inner = rest;
+            }
+
             if let Some(content) = content_to_display {
                 // Render content with scroll
                 let lines: Vec<Line> = content
                     .lines()
                     .skip(state.modes.release_notes.release_notes_scroll)
                     .take(inner.height as usize)
-                    .map(|line| Line::from(line.to_string()))
+                    .map(Line::from)
                     .collect();
                 let paragraph = Paragraph::new(lines);
                 frame.render_widget(paragraph, inner);
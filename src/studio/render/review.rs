@@ -5,9 +5,10 @@ use ratatui::layout::Rect;
 use ratatui::text::Line;
 use ratatui::widgets::{Block, Borders, Paragraph};
 
-use crate::studio::components::{render_diff_view, render_file_tree};
+use crate::studio::components::{render_diff_view, render_file_tree, source_lines};
 use crate::studio::state::{PanelId, StudioState};
 use crate::studio::theme;
+use crate::types::sources_start;
 
 /// Create a panel title with scroll position indicator
 fn scrollable_title(base_title: &str, scroll: usize, total_lines: usize, visible: usize) -> String {
@@ -15,11 +16,9 @@ fn scrollable_title(base_title: &str, scroll: usize, total_lines: usize, visible
         format!(" {} ", base_title)
     } else {
         let max_scroll = total_lines.saturating_sub(visible);
-        let percent = if max_scroll == 0 {
-            100
-        } else {
-            ((scroll.min(max_scroll)) * 100) / max_scroll
-        };
+        let percent = (scroll.min(max_scroll) * 100)
+            .checked_div(max_scroll)
+            .unwrap_or(100);
         format!(
             " {} ({}/{}) {}% ",
             base_title,
@@ -42,11 +41,15 @@ pub fn render_review_panel(
     match panel_id {
         PanelId::Left => {
             // Render changed files using FileTree component
+            let title = state.modes.review.risk.as_ref().map_or_else(
+                || "Changed Files".to_string(),
+                |risk| format!("Changed Files · {}", risk.badge()),
+            );
             render_file_tree(
                 frame,
                 area,
                 &mut state.modes.review.file_tree,
-                "Changed Files",
+                &title,
                 is_focused,
             );
         }
@@ -55,17 +58,35 @@ pub fn render_review_panel(
             let visible_height = area.height.saturating_sub(2) as usize; // -2 for borders
 
             // Prefer streaming content if available, then final content
-            let content_to_display = state.modes.review.streaming_content.as_ref().or(
+            let content_to_display = state.modes.review.streaming_content.as_deref().or(
                 if state.modes.review.review_content.is_empty() {
                     None
                 } else {
-                    Some(&state.modes.review.review_content)
+                    Some(&*state.modes.review.review_content)
                 },
             );
 
-            let total_lines = content_to_display.map_or(0, |c| c.lines().count());
+            let review = &state.modes.review;
+            // Checked sources replace the raw section in a footer
+            let body_lines = content_to_display.map_or(0, |c| {
+                let total = c.lines().count();
+                if review.sources.is_empty() {
+                    total
+                } else {
+                    sources_start(c).unwrap_or(total)
+                }
+            });
+            let total_lines = body_lines;
+            let mut base_title = match (review.findings.len(), review.selected_finding) {
+                (0, _) => "Review [y:copy]".to_string(),
+                (count, Some(i)) => format!("Review · finding {}/{count} [n/N y:copy]", i + 1),
+                (count, None) => format!("Review · {count} findings [n/N y:copy]"),
+            };
+            if !review.sources.is_empty() {
+                base_title = base_title.replacen(" [", " [[/] o:source ", 1);
+            }
             let title = scrollable_title(
-                "Review [y:copy]",
+                &base_title,
                 state.modes.review.review_scroll,
                 total_lines,
                 visible_height,
@@ -80,16 +101,50 @@ pub fn render_review_panel(
                 } else {
                     theme::unfocused_border()
                 });
-            let inner = block.inner(area);
+            let mut inner = block.inner(area);
             frame.render_widget(block, area);
 
+            let footer = source_lines(
+                &review.sources,
+                review.selected_source,
+                usize::from(inner.width),
+            );
+            if !footer.is_empty() && inner.height > 6 {
+                let height = u16::try_from(footer.len() + 1)
+                    .unwrap_or(u16::MAX)
+                    .min(inner.height / 3);
+                let footer_area = Rect {
+                    y: inner.y + inner.height - height,
+                    height,
+                    ..inner
+                };
+                inner.height -= height;
+                let separator =
+                    Line::from("─".repeat(usize::from(inner.width))).style(theme::dimmed());
+                let footer: Vec<Line> = std::iter::once(separator).chain(footer).collect();
+                frame.render_widget(Paragraph::new(footer), footer_area);
+            }
+
             if let Some(content) = content_to_display {
-                // Render content with scroll
+                // Render content with scroll, highlighting the current finding
+                let selected_line = review
+                    .selected_finding
+                    .and_then(|i| review.findings.get(i))
+                    .map(|f| f.line);
                 let lines: Vec<Line> = content
                     .lines()
+                    .enumerate()
+                    .take(body_lines)
                     .skip(state.modes.review.review_scroll)
                     .take(inner.height as usize)
-                    .map(|line| Line::from(line.to_string()))
+                    .map(|(i, line)| {
+                        let line = Line::from(line);
+                        if Some(i) == selected_line {
+                            line.style(theme::selected())
+                        } else {
+                            line
+                        }
+                    })
                     .collect();
                 let paragraph = Paragraph::new(lines);
                 frame.render_widget(paragraph, inner);
@@ -0,0 +1,109 @@
+//! Stash mode rendering for Iris Studio
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::git::StashEntry;
+use crate::studio::components::{render_diff_view, render_file_tree};
+use crate::studio::state::{PanelId, StashState, StudioState};
+use crate::studio::theme;
+use crate::studio::utils::{time_ago, truncate_width};
+
+/// Render a panel in Stash mode
+pub fn render_stash_panel(
+    state: &mut StudioState,
+    frame: &mut Frame,
+    area: Rect,
+    panel_id: PanelId,
+) {
+    let is_focused = panel_id == state.focused_panel;
+    let stash = &mut state.modes.stash;
+
+    match panel_id {
+        PanelId::Left => render_stash_list(stash, frame, area, is_focused),
+        PanelId::Center => {
+            render_file_tree(frame, area, &mut stash.file_tree, "Files", is_focused);
+        }
+        PanelId::Right => {
+            let title = stash
+                .selected_entry()
+                .map_or_else(|| "Diff".to_string(), StashEntry::selector);
+            render_diff_view(frame, area, &stash.diff_view, &title, is_focused);
+        }
+    }
+}
+
+/// Stashes, newest first: selector, age and branch, then the description
+fn render_stash_list(stash: &StashState, frame: &mut Frame, area: Rect, focused: bool) {
+    let block = Block::default()
+        .title(format!(" Stashes ({}) ", stash.entries.len()))
+        .borders(Borders::ALL)
+        .border_style(if focused {
+            theme::focused_border()
+        } else {
+            theme::unfocused_border()
+        });
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if stash.entries.is_empty() {
+        let hint = if stash.loaded {
+            "No stashes · s to stash your changes"
+        } else {
+            "Loading stashes..."
+        };
+        frame.render_widget(Paragraph::new(Line::styled(hint, theme::dimmed())), inner);
+        return;
+    }
+
+    // Two lines per stash
+    let visible = (usize::from(inner.height) / 2).max(1);
+    let offset = stash.selected.saturating_sub(visible - 1);
+    let width = usize::from(inner.width).saturating_sub(2);
+
+    let lines: Vec<Line> = stash
+        .entries
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .flat_map(|(i, entry)| {
+            let is_selected = i == stash.selected;
+            let marker = if is_selected { "▸ " } else { "  " };
+            let mut header = vec![
+                Span::styled(marker, theme::keyword()),
+                Span::styled(
+                    entry.selector(),
+                    Style::default().fg(theme::accent_primary()),
+                ),
+                Span::styled(format!(" {}", time_ago(entry.time)), theme::dimmed()),
+            ];
+            if let Some(branch) = entry.branch() {
+                header.push(Span::styled(format!(" · {branch}"), theme::dimmed()));
+            }
+            if entry.has_untracked {
+                header.push(Span::styled(" +untracked", theme::git_untracked()));
+            }
+            let lines = [
+                Line::from(header),
+                Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(
+                        truncate_width(entry.description(), width),
+                        Style::default().fg(theme::text_primary_color()),
+                    ),
+                ]),
+            ];
+            if is_selected {
+                lines.map(|line| line.style(theme::selected()))
+            } else {
+                lines
+            }
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
@@ -3,6 +3,10 @@
 //! Contains the chat interface state, message history, and related types.
 
 use std::collections::VecDeque;
+use std::sync::Arc;
+
+use crate::studio::events::{ContentPayload, ContentType};
+use crate::types::CheckedCitation;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Constants
@@ -14,6 +18,12 @@ const MAX_CHAT_MESSAGES: usize = 500;
 /// Maximum tool history entries per response
 const MAX_TOOL_HISTORY: usize = 20;
 
+/// Maximum pinned context items in the chat basket
+const MAX_PINNED: usize = 8;
+
+/// Maximum characters kept per pinned item
+const MAX_PIN_CHARS: usize = 12_000;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Chat Types
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -29,21 +39,74 @@ pub enum ChatRole {
 #[derive(Debug, Clone)]
 pub struct ChatMessage {
     pub role: ChatRole,
-    pub content: String,
+    pub content: Arc<str>,
+    /// Sources the message cites, checked against the repository
+    pub sources: Vec<CheckedCitation>,
 }
 
 impl ChatMessage {
-    pub fn user(content: impl Into<String>) -> Self {
+    pub fn user(content: impl Into<Arc<str>>) -> Self {
         Self {
             role: ChatRole::User,
             content: content.into(),
+            sources: Vec::new(),
         }
     }
 
-    pub fn iris(content: impl Into<String>) -> Self {
+    pub fn iris(content: impl Into<Arc<str>>) -> Self {
         Self {
             role: ChatRole::Iris,
             content: content.into(),
+            sources: Vec::new(),
+        }
+    }
+}
+
+/// A file, selection, or hunk pinned into the chat context basket
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedContext {
+    /// Short label shown as a chip (also identifies the pin)
+    pub label: String,
+    /// Code or diff text sent to Iris
+    pub content: String,
+}
+
+impl PinnedContext {
+    /// Create a pin, truncating oversized content
+    pub fn new(label: impl Into<String>, content: &str) -> Self {
+        let content = if content.chars().count() > MAX_PIN_CHARS {
+            let truncated: String = content.chars().take(MAX_PIN_CHARS).collect();
+            format!("{}\n... (truncated)", truncated)
+        } else {
+            content.to_string()
+        };
+        Self {
+            label: label.into(),
+            content,
+        }
+    }
+}
+
+/// Content a chat tool wants to replace, waiting for the user to accept it
+#[derive(Debug, Clone)]
+pub struct ProposedUpdate {
+    pub content_type: ContentType,
+    pub content: ContentPayload,
+    /// The content it would replace, as shown in Studio
+    pub current: String,
+    /// The proposed content as text
+    pub proposed: String,
+}
+
+impl ProposedUpdate {
+    /// What is being replaced, for titles and notifications
+    pub fn label(&self) -> &'static str {
+        match self.content_type {
+            ContentType::CommitMessage => "commit message",
+            ContentType::PRDescription => "PR description",
+            ContentType::CodeReview => "review",
+            ContentType::Changelog => "changelog",
+            ContentType::ReleaseNotes => "release notes",
         }
     }
 }
@@ -73,6 +136,12 @@ pub struct ChatState {
     pub tool_history: VecDeque<String>,
     /// Error message to display (cleared on next user input)
     pub error: Option<String>,
+    /// Explicitly pinned context sent with every question
+    pub pinned: Vec<PinnedContext>,
+    /// Tool updates awaiting accept/reject, oldest first (one per content type)
+    pub proposals: VecDeque<ProposedUpdate>,
+    /// Scroll offset of the proposal diff
+    pub proposal_scroll: usize,
 }
 
 impl Default for ChatState {
@@ -87,6 +156,9 @@ impl Default for ChatState {
             current_tool: None,
             tool_history: VecDeque::new(),
             error: None,
+            pinned: Vec::new(),
+            proposals: VecDeque::new(),
+            proposal_scroll: 0,
         }
     }
 }
@@ -148,8 +220,11 @@ impl ChatState {
     }
 
     /// Add or update Iris response and auto-scroll to bottom
-    pub fn add_iris_response(&mut self, content: &str) {
-        self.messages.push_back(ChatMessage::iris(content));
+    pub fn add_iris_response(&mut self, content: &str, sources: Vec<CheckedCitation>) {
+        self.messages.push_back(ChatMessage {
+            sources,
+            ..ChatMessage::iris(content)
+        });
         self.trim_messages();
         self.is_responding = false;
         self.streaming_response = None;
@@ -194,7 +269,62 @@ impl ChatState {
         }
     }
 
-    /// Clear the chat history
+    /// Pin an item, or unpin it if one with the same label exists
+    ///
+    /// Returns `true` if the item is now pinned. When the basket is full the
+    /// oldest pin is dropped.
+    pub fn toggle_pin(&mut self, pin: PinnedContext) -> bool {
+        if let Some(pos) = self.pinned.iter().position(|p| p.label == pin.label) {
+            self.pinned.remove(pos);
+            return false;
+        }
+        self.pinned.push(pin);
+        if self.pinned.len() > MAX_PINNED {
+            self.pinned.remove(0);
+        }
+        true
+    }
+
+    /// Remove the most recently pinned item
+    pub fn pop_pin(&mut self) -> Option<PinnedContext> {
+        self.pinned.pop()
+    }
+
+    /// Render pinned items as a prompt section (empty if nothing is pinned)
+    pub fn pinned_prompt_section(&self) -> String {
+        if self.pinned.is_empty() {
+            return String::new();
+        }
+        let mut out = String::from(
+            "\n## Pinned Context\nThe user pinned these items; treat them as the primary subject of their questions.\n",
+        );
+        for pin in &self.pinned {
+            out.push_str(&format!("\n### {}\n```\n{}\n```\n", pin.label, pin.content));
+        }
+        out
+    }
+
+    /// Queue a proposed update, replacing an older one for the same content
+    pub fn propose(&mut self, proposal: ProposedUpdate) {
+        if let Some(existing) = self
+            .proposals
+            .iter_mut()
+            .find(|p| p.content_type == proposal.content_type)
+        {
+            *existing = proposal;
+        } else {
+            self.proposals.push_back(proposal);
+        }
+        self.proposal_scroll = 0;
+    }
+
+    /// Take the proposal shown to the user
+    pub fn take_proposal(&mut self) -> Option<ProposedUpdate> {
+        self.proposal_scroll = 0;
+        self.proposals.pop_front()
+    }
+
+    /// Clear the chat history (pinned context is kept)
     pub fn clear(&mut self) {
         self.messages.clear();
         self.input.clear();
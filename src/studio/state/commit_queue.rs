@@ -0,0 +1,184 @@
+//! Commit queue state for Iris Studio
+//!
+//! Several small commits can be prepared before any of them is made: each
+//! entry pairs a message with the changes that were staged when it was
+//! queued. Running the queue stages exactly those changes on top of the
+//! commits before it and commits them in order, one per frame so progress
+//! shows, and stops at the first failure with that entry and the ones after it
+//! still queued. Edits made after queueing stay in the working tree.
+
+use std::path::PathBuf;
+
+/// A commit waiting in the queue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedCommit {
+    pub message: String,
+    /// Files staged for this commit, relative to the repository root
+    pub files: Vec<PathBuf>,
+    /// Their staged changes when queued, from [`GitRepo::staged_patch`]
+    ///
+    /// [`GitRepo::staged_patch`]: crate::git::GitRepo::staged_patch
+    pub patch: Vec<u8>,
+}
+
+impl QueuedCommit {
+    /// First line of the message
+    pub fn subject(&self) -> &str {
+        self.message.lines().next().unwrap_or_default()
+    }
+}
+
+/// Commits prepared in Commit mode, first to be made first
+#[derive(Debug, Clone, Default)]
+pub struct CommitQueueState {
+    pub entries: Vec<QueuedCommit>,
+    /// Selected entry in the queue modal
+    pub selected: usize,
+    /// Whether the queue is being committed
+    pub running: bool,
+    /// Entries in the current run, for "n of m" progress
+    pub run_total: usize,
+    /// `git commit`-style summaries of the commits made in the current run
+    pub committed: Vec<String>,
+    /// Why the last run stopped, shown on the entry it stopped at
+    pub error: Option<String>,
+}
+
+impl CommitQueueState {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Queue a commit at the end
+    pub fn push(&mut self, message: String, files: Vec<PathBuf>, patch: Vec<u8>) {
+        self.entries.push(QueuedCommit {
+            message,
+            files,
+            patch,
+        });
+        self.error = None;
+    }
+
+    /// Move the selection, clamped to the queue
+    pub fn move_selection(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.entries.len().saturating_sub(1));
+    }
+
+    /// Move the selected entry up or down the queue, keeping it selected
+    pub fn move_selected(&mut self, delta: isize) {
+        let target = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.entries.len().saturating_sub(1));
+        if target != self.selected {
+            self.entries.swap(self.selected, target);
+            self.selected = target;
+        }
+    }
+
+    /// Drop the selected entry
+    pub fn remove_selected(&mut self) -> Option<QueuedCommit> {
+        if self.selected >= self.entries.len() {
+            return None;
+        }
+        let removed = self.entries.remove(self.selected);
+        self.move_selection(0);
+        self.error = None;
+        Some(removed)
+    }
+
+    /// Start committing the queue from the top
+    pub fn start(&mut self) {
+        self.running = !self.entries.is_empty();
+        self.run_total = self.entries.len();
+        self.committed.clear();
+        self.error = None;
+        self.selected = 0;
+    }
+
+    /// The entry to commit next while running
+    pub fn next(&self) -> Option<&QueuedCommit> {
+        self.running.then(|| self.entries.first()).flatten()
+    }
+
+    /// Record that the next entry was committed
+    pub fn complete_next(&mut self, summary: String) {
+        if self.running && !self.entries.is_empty() {
+            self.entries.remove(0);
+            self.committed.push(summary);
+            self.running = !self.entries.is_empty();
+        }
+    }
+
+    /// Stop the run at the next entry, leaving it and the rest queued
+    pub fn fail_next(&mut self, error: String) {
+        self.running = false;
+        self.error = Some(error);
+    }
+
+    /// Whether every entry of a started run has been committed
+    pub fn finished(&self) -> bool {
+        !self.running && self.error.is_none() && self.entries.is_empty() && self.run_total > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue() -> CommitQueueState {
+        let mut queue = CommitQueueState::default();
+        queue.push(
+            "feat: a\n\nBody".to_string(),
+            vec![PathBuf::from("a.rs")],
+            Vec::new(),
+        );
+        queue.push(
+            "fix: b".to_string(),
+            vec![PathBuf::from("b.rs")],
+            Vec::new(),
+        );
+        queue.push(
+            "docs: c".to_string(),
+            vec![PathBuf::from("c.md")],
+            Vec::new(),
+        );
+        queue
+    }
+
+    #[test]
+    fn test_reorder_and_remove() {
+        let mut queue = queue();
+        queue.move_selected(1);
+        assert_eq!(queue.selected, 1);
+        assert_eq!(queue.entries[1].subject(), "feat: a");
+        queue.move_selected(5);
+        assert_eq!(queue.entries[2].subject(), "feat: a");
+        assert_eq!(
+            queue.remove_selected().map(|e| e.files),
+            Some(vec![PathBuf::from("a.rs")])
+        );
+        assert_eq!(queue.selected, 1);
+    }
+
+    #[test]
+    fn test_run_stops_at_failure() {
+        let mut queue = queue();
+        queue.start();
+        assert_eq!(queue.next().map(QueuedCommit::subject), Some("feat: a"));
+        queue.complete_next("[main abc1234] feat: a".to_string());
+        queue.fail_next("pre-commit hook failed".to_string());
+        assert_eq!(queue.next(), None);
+        assert_eq!(queue.entries.len(), 2);
+        assert!(!queue.finished());
+
+        queue.start();
+        queue.complete_next("b".to_string());
+        queue.complete_next("c".to_string());
+        assert!(queue.finished());
+        assert_eq!(queue.committed.len(), 2);
+    }
+}
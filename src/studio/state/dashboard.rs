@@ -0,0 +1,259 @@
+//! Dashboard mode state for Iris Studio
+//!
+//! Repository activity at a glance: daily commits, top contributors, the
+//! hottest files, work in progress, the latest companion session, sessions in
+//! the repository's other worktrees and progress on the saved work plan. The
+//! statistics come from one `git log` pass and are cached for a few minutes.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::companion::{HistoryKind, SessionState, SessionTranscript};
+use crate::git::parse_log;
+use crate::plan::PlanProgress;
+
+/// Contributors shown on the dashboard
+const MAX_CONTRIBUTORS: usize = 8;
+
+/// Hot files shown on the dashboard
+const MAX_HOT_FILES: usize = 20;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Summary of the most recent saved companion session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionSummary {
+    pub branch: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// Chat messages exchanged
+    pub chat_messages: usize,
+    /// Artifacts generated, in session order
+    pub artifacts: Vec<HistoryKind>,
+}
+
+impl SessionSummary {
+    pub fn from_transcript(transcript: &SessionTranscript) -> Self {
+        Self {
+            branch: transcript.branch.clone(),
+            started_at: transcript.started_at,
+            ended_at: transcript.exported_at,
+            chat_messages: transcript.messages.len(),
+            artifacts: transcript.artifacts.iter().map(|a| a.kind).collect(),
+        }
+    }
+}
+
+/// A companion session in progress in another worktree of the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeSession {
+    pub branch: String,
+    /// The worktree, relative to this one when they share an ancestor
+    pub worktree: String,
+    pub last_activity: DateTime<Utc>,
+}
+
+impl WorktreeSession {
+    pub fn new(session: &SessionState, current_worktree: &Path) -> Self {
+        Self {
+            branch: session.branch.clone(),
+            worktree: relative_path(&session.repo_path, current_worktree),
+            last_activity: session.last_activity,
+        }
+    }
+}
+
+/// `path` as seen from the directory `from`, e.g. `../hotfix`
+fn relative_path(path: &Path, from: &Path) -> String {
+    let path: Vec<Component> = path.components().collect();
+    let from: Vec<Component> = from.components().collect();
+    let shared = path.iter().zip(&from).take_while(|(a, b)| a == b).count();
+    if shared == 0 {
+        return path.iter().collect::<PathBuf>().display().to_string();
+    }
+    let mut relative: PathBuf =
+        std::iter::repeat_n(Component::ParentDir, from.len() - shared).collect();
+    relative.extend(&path[shared..]);
+    relative.display().to_string()
+}
+
+/// Activity statistics computed from history
+#[derive(Debug, Clone, Default)]
+pub struct DashboardData {
+    /// Commits per day, oldest first, one entry per day of the window
+    pub daily_commits: Vec<u64>,
+    /// Commits in the window
+    pub total_commits: usize,
+    /// Authors and their commit counts, most active first
+    pub contributors: Vec<(String, usize)>,
+    /// Paths and the number of commits touching them, most changed first
+    pub hot_files: Vec<(PathBuf, usize)>,
+    /// Entries in `git stash list`
+    pub stash_count: usize,
+    /// Latest saved companion session
+    pub last_session: Option<SessionSummary>,
+    /// Sessions in progress in other worktrees, newest first
+    pub other_worktrees: Vec<WorktreeSession>,
+    /// Saved work plan and which of its commits have landed
+    pub plan: Option<PlanProgress>,
+    /// Size of the window in days
+    pub window_days: u32,
+}
+
+impl DashboardData {
+    /// Aggregate output of `git log --format=%x1e%an%x1f%at --name-only`
+    ///
+    /// Commits older than `window_days` before `now` are ignored.
+    pub fn from_git_log(output: &str, window_days: u32, now: DateTime<Utc>) -> Self {
+        let days = window_days.max(1) as usize;
+        let mut daily_commits = vec![0; days];
+        let mut authors: HashMap<&str, usize> = HashMap::new();
+        let mut files: HashMap<&str, usize> = HashMap::new();
+        let mut total_commits = 0;
+
+        for record in parse_log(output) {
+            let Some(timestamp) = record.timestamp else {
+                continue;
+            };
+            // Future-dated commits (clock skew) count as today
+            let age = usize::try_from((now.timestamp() - timestamp) / SECONDS_PER_DAY).unwrap_or(0);
+            if age >= days {
+                continue;
+            }
+            daily_commits[days - 1 - age] += 1;
+            total_commits += 1;
+            *authors.entry(record.author).or_default() += 1;
+            for file in record.files {
+                *files.entry(file.path).or_default() += 1;
+            }
+        }
+
+        Self {
+            daily_commits,
+            total_commits,
+            contributors: ranked(authors, MAX_CONTRIBUTORS),
+            hot_files: ranked(files, MAX_HOT_FILES),
+            stash_count: 0,
+            last_session: None,
+            other_worktrees: Vec::new(),
+            plan: None,
+            window_days,
+        }
+    }
+}
+
+/// Top `limit` entries by count, ties broken by name
+fn ranked<T: for<'a> From<&'a str>>(counts: HashMap<&str, usize>, limit: usize) -> Vec<(T, usize)> {
+    let mut entries: Vec<(&str, usize)> = counts.into_iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    entries
+        .into_iter()
+        .take(limit)
+        .map(|(name, count)| (T::from(name), count))
+        .collect()
+}
+
+/// State for Dashboard mode
+#[derive(Debug, Default)]
+pub struct DashboardState {
+    /// Cached activity statistics
+    pub data: Option<Arc<DashboardData>>,
+    /// When the cached statistics were computed
+    pub loaded_at: Option<Instant>,
+    /// Whether statistics are loading
+    pub loading: bool,
+    /// Selected index in the hot files list
+    pub selected_file: usize,
+}
+
+impl DashboardState {
+    /// Number of days of history shown
+    pub const WINDOW_DAYS: u32 = 30;
+
+    /// How long cached statistics stay fresh
+    const TTL: Duration = Duration::from_mins(5);
+
+    /// Whether the cached statistics are missing or stale
+    pub fn needs_load(&self) -> bool {
+        self.data.is_none() || self.loaded_at.is_none_or(|at| at.elapsed() > Self::TTL)
+    }
+
+    /// Path of the selected hot file
+    pub fn selected_path(&self) -> Option<&PathBuf> {
+        self.data
+            .as_ref()?
+            .hot_files
+            .get(self.selected_file)
+            .map(|(path, _)| path)
+    }
+
+    /// Move the hot file selection, clamped to the list
+    pub fn move_selection(&mut self, delta: isize) {
+        let len = self.data.as_ref().map_or(0, |d| d.hot_files.len());
+        self.selected_file = self
+            .selected_file
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_git_log_buckets_by_day() {
+        let now = DateTime::from_timestamp(10 * SECONDS_PER_DAY, 0).expect("timestamp");
+        let day = |d: i64| (10 - d) * SECONDS_PER_DAY - 60;
+        let output = format!(
+            "\x1eAda\x1f{}\n\nsrc/lib.rs\nsrc/main.rs\n\
+             \x1eGrace\x1f{}\n\nsrc/lib.rs\n\
+             \x1eAda\x1f{}\n\nREADME.md\n\
+             \x1eAda\x1f{}\n\nold.rs\n",
+            day(0),
+            day(0),
+            day(2),
+            day(40)
+        );
+
+        let data = DashboardData::from_git_log(&output, 7, now);
+        assert_eq!(data.daily_commits, [0, 0, 0, 0, 1, 0, 2]);
+        assert_eq!(data.total_commits, 3);
+        assert_eq!(
+            data.contributors,
+            [("Ada".to_string(), 2), ("Grace".to_string(), 1)]
+        );
+        assert_eq!(data.hot_files[0], (PathBuf::from("src/lib.rs"), 2));
+        assert!(data.hot_files.iter().all(|(p, _)| p != "old.rs"));
+    }
+
+    #[test]
+    fn test_selection_clamps_to_hot_files() {
+        let mut state = DashboardState {
+            data: Some(Arc::new(DashboardData {
+                hot_files: vec![(PathBuf::from("a.rs"), 3), (PathBuf::from("b.rs"), 1)],
+                ..DashboardData::default()
+            })),
+            ..DashboardState::default()
+        };
+        state.move_selection(5);
+        assert_eq!(state.selected_path(), Some(&PathBuf::from("b.rs")));
+        state.move_selection(-5);
+        assert_eq!(state.selected_path(), Some(&PathBuf::from("a.rs")));
+        assert!(state.needs_load());
+    }
+
+    #[test]
+    fn test_relative_worktree_path() {
+        let from = Path::new("/work/app");
+        assert_eq!(relative_path(Path::new("/work/hotfix"), from), "../hotfix");
+        assert_eq!(
+            relative_path(Path::new("/work/app/.worktrees/pr-12"), from),
+            ".worktrees/pr-12"
+        );
+        assert_eq!(relative_path(Path::new("hotfix"), from), "hotfix");
+    }
+}
@@ -0,0 +1,207 @@
+//! History search modal state for Iris Studio
+//!
+//! Full-text search over archived sessions (commit messages, reviews, PRs,
+//! changelogs, release notes and chats) with kind and date filters.
+
+use chrono::{Duration, Utc};
+
+use crate::companion::{HistoryFilter, HistoryKind, SearchHit, SessionTranscript, search_history};
+
+/// Date window for history search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateWindow {
+    #[default]
+    All,
+    Today,
+    Week,
+    Month,
+}
+
+impl DateWindow {
+    /// Cycle to the next window
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::Today,
+            Self::Today => Self::Week,
+            Self::Week => Self::Month,
+            Self::Month => Self::All,
+        }
+    }
+
+    /// Display label
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "any time",
+            Self::Today => "24h",
+            Self::Week => "7 days",
+            Self::Month => "30 days",
+        }
+    }
+
+    /// Length of the window, `None` for unbounded
+    fn duration(self) -> Option<Duration> {
+        match self {
+            Self::All => None,
+            Self::Today => Some(Duration::days(1)),
+            Self::Week => Some(Duration::days(7)),
+            Self::Month => Some(Duration::days(30)),
+        }
+    }
+}
+
+/// State for the history search modal
+#[derive(Debug, Clone, Default)]
+pub struct HistorySearchState {
+    /// Current query text
+    pub query: String,
+    /// Kind filter (`None` = everything)
+    pub kind: Option<HistoryKind>,
+    /// Date filter
+    pub window: DateWindow,
+    /// Sessions being searched, newest first
+    sessions: Vec<SessionTranscript>,
+    /// Hits for the current filters
+    pub results: Vec<SearchHit>,
+    /// Selected result index
+    pub selected: usize,
+}
+
+impl HistorySearchState {
+    /// Create search state over the given sessions
+    pub fn new(sessions: Vec<SessionTranscript>) -> Self {
+        let mut state = Self {
+            sessions,
+            ..Self::default()
+        };
+        state.refresh();
+        state
+    }
+
+    /// Number of sessions being searched
+    pub fn session_count(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Re-run the search with the current filters
+    pub fn refresh(&mut self) {
+        let filter = HistoryFilter {
+            query: self.query.clone(),
+            kind: self.kind,
+            since: self.window.duration().map(|d| Utc::now() - d),
+        };
+        self.results = search_history(&self.sessions, &filter);
+        self.selected = 0;
+    }
+
+    /// Append a character to the query
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.refresh();
+    }
+
+    /// Remove the last query character
+    pub fn pop_char(&mut self) {
+        if self.query.pop().is_some() {
+            self.refresh();
+        }
+    }
+
+    /// Cycle the kind filter: all → commit → … → chat → all
+    pub fn cycle_kind(&mut self) {
+        self.kind = match self.kind {
+            None => Some(HistoryKind::ALL[0]),
+            Some(kind) => HistoryKind::ALL
+                .iter()
+                .position(|k| *k == kind)
+                .and_then(|i| HistoryKind::ALL.get(i + 1))
+                .copied(),
+        };
+        self.refresh();
+    }
+
+    /// Cycle the date window
+    pub fn cycle_window(&mut self) {
+        self.window = self.window.next();
+        self.refresh();
+    }
+
+    /// Move the selection down
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.results.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Move the selection up
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Currently selected hit
+    pub fn selected_hit(&self) -> Option<&SearchHit> {
+        self.results.get(self.selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::companion::{TranscriptArtifact, TranscriptMessage};
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn session() -> SessionTranscript {
+        let mut t = SessionTranscript::new(
+            Uuid::new_v4(),
+            PathBuf::from("/repo"),
+            "main".to_string(),
+            Utc::now(),
+        );
+        t.artifacts.push(TranscriptArtifact {
+            kind: HistoryKind::Commit,
+            content: "Fix login redirect".to_string(),
+        });
+        t.messages.push(TranscriptMessage {
+            role: "user".to_string(),
+            content: "why does login redirect twice?".to_string(),
+        });
+        t
+    }
+
+    #[test]
+    fn test_query_and_kind_filter() {
+        let mut state = HistorySearchState::new(vec![session()]);
+        assert_eq!(state.results.len(), 2);
+
+        for c in "redirect".chars() {
+            state.push_char(c);
+        }
+        assert_eq!(state.results.len(), 2);
+
+        state.cycle_kind();
+        assert_eq!(state.kind, Some(HistoryKind::Commit));
+        assert_eq!(state.results.len(), 1);
+
+        // Cycling past the last kind returns to "all"
+        for _ in 0..HistoryKind::ALL.len() {
+            state.cycle_kind();
+        }
+        assert_eq!(state.kind, None);
+        assert_eq!(state.results.len(), 2);
+    }
+
+    #[test]
+    fn test_date_window_excludes_old_sessions() {
+        let mut old = session();
+        old.exported_at = Utc::now() - Duration::days(10);
+        let mut state = HistorySearchState::new(vec![old, session()]);
+        assert_eq!(state.results.len(), 4);
+
+        state.cycle_window(); // 24h
+        assert_eq!(state.results.len(), 2);
+        state.cycle_window(); // 7 days
+        assert_eq!(state.results.len(), 2);
+        state.cycle_window(); // 30 days
+        assert_eq!(state.results.len(), 4);
+    }
+}
@@ -0,0 +1,43 @@
+//! Message history modal state for Iris Studio
+//!
+//! Lists the commit messages generated or edited on the current branch so
+//! one can be brought back after regenerating over it.
+
+use crate::companion::MessageDraft;
+
+/// State for the message history modal
+#[derive(Debug, Clone, Default)]
+pub struct MessageHistoryState {
+    /// Drafts, newest first
+    pub drafts: Vec<MessageDraft>,
+    /// Selected draft index
+    pub selected: usize,
+}
+
+impl MessageHistoryState {
+    /// Create the modal state from a branch's drafts, oldest first
+    pub fn new(mut drafts: Vec<MessageDraft>) -> Self {
+        drafts.reverse();
+        Self {
+            drafts,
+            selected: 0,
+        }
+    }
+
+    /// Move the selection down
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.drafts.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Move the selection up
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Currently selected draft
+    pub fn selected_draft(&self) -> Option<&MessageDraft> {
+        self.drafts.get(self.selected)
+    }
+}
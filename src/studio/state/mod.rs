@@ -3,17 +3,45 @@
 //! Centralized state for all modes and shared data.
 
 mod chat;
+mod commit_queue;
+mod dashboard;
+mod history_search;
+mod message_history;
+mod model_picker;
 mod modes;
-
-pub use chat::{ChatMessage, ChatRole, ChatState, truncate_preview};
-pub use modes::{ChangelogCommit, FileLogEntry, ModeStates, PrCommit};
-
-use crate::agents::StatusMessageBatch;
-use crate::companion::CompanionService;
-use crate::config::Config;
+mod pickaxe;
+mod preset_editor;
+mod rebase;
+mod review_export;
+mod semantic_search;
+mod stash;
+
+pub use chat::{ChatMessage, ChatRole, ChatState, PinnedContext, ProposedUpdate, truncate_preview};
+pub use commit_queue::CommitQueueState;
+pub use dashboard::{DashboardData, DashboardState, SessionSummary, WorktreeSession};
+pub use history_search::HistorySearchState;
+pub use message_history::MessageHistoryState;
+pub use model_picker::ModelPickerState;
+pub use modes::{ChangelogCommit, ExploreState, FileLogEntry, ModeStates, PrCommit, PrState};
+pub use pickaxe::PickaxeState;
+pub use preset_editor::{PresetEditorField, PresetEditorState};
+pub use rebase::{RebaseRewordState, RebaseState};
+pub use review_export::{ExportedFinding, ReviewExportState};
+pub use semantic_search::SemanticSearchState;
+pub use stash::{StashCreateState, StashState};
+
+use super::code_context::CodeContext;
+use super::events::TaskType;
+use crate::agents::{ContextReport, StatusMessageBatch, StatusPersonality};
+use crate::companion::{CompanionService, DraftKind, MessageDraft};
+use crate::config::{
+    AutoTrigger, Config, GENERATION_CAPABILITIES, GenerationParams, Tone, Verbosity,
+};
 use crate::git::GitRepo;
-use crate::types::format_commit_message;
-use std::collections::VecDeque;
+use crate::providers::Provider;
+use crate::spellcheck::SpellChecker;
+use crate::types::{CheckedCitation, GeneratedMessage, format_commit_message, verify_sources};
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -37,6 +65,12 @@ pub enum Mode {
     Changelog,
     /// Release Notes mode - release documentation
     ReleaseNotes,
+    /// Dashboard mode - repository activity at a glance
+    Dashboard,
+    /// Stash mode - browse, apply and create stashes
+    Stash,
+    /// Rebase mode - plan and run an interactive rebase
+    Rebase,
 }
 
 impl Mode {
@@ -49,6 +83,9 @@ impl Mode {
             Mode::PR => "PR",
             Mode::Changelog => "Changelog",
             Mode::ReleaseNotes => "Release",
+            Mode::Dashboard => "Dashboard",
+            Mode::Stash => "Stash",
+            Mode::Rebase => "Rebase",
         }
     }
 
@@ -61,6 +98,24 @@ impl Mode {
             Mode::PR => 'P',
             Mode::Changelog => 'L',
             Mode::ReleaseNotes => 'N',
+            Mode::Dashboard => 'D',
+            Mode::Stash => 'Z',
+            Mode::Rebase => 'B',
+        }
+    }
+
+    /// Capability whose generation params apply to this mode's output
+    pub fn capability(self) -> Option<&'static str> {
+        match self {
+            Mode::Explore => Some("semantic_blame"),
+            Mode::Commit => Some("commit"),
+            Mode::Review => Some("review"),
+            Mode::PR => Some("pr"),
+            Mode::Changelog => Some("changelog"),
+            Mode::ReleaseNotes => Some("release_notes"),
+            Mode::Dashboard => None,
+            Mode::Stash => Some("stash"),
+            Mode::Rebase => Some("rebase"),
         }
     }
 
@@ -74,6 +129,9 @@ impl Mode {
                 | Mode::PR
                 | Mode::Changelog
                 | Mode::ReleaseNotes
+                | Mode::Dashboard
+                | Mode::Stash
+                | Mode::Rebase
         )
     }
 
@@ -86,6 +144,9 @@ impl Mode {
             Mode::PR,
             Mode::Changelog,
             Mode::ReleaseNotes,
+            Mode::Dashboard,
+            Mode::Stash,
+            Mode::Rebase,
         ]
     }
 }
@@ -167,6 +228,15 @@ impl GitStatus {
     pub fn has_staged(&self) -> bool {
         self.staged_count > 0
     }
+
+    /// Modified and untracked files, which a commit would leave out
+    pub fn unstaged_files(&self) -> Vec<PathBuf> {
+        self.modified_files
+            .iter()
+            .chain(&self.untracked_files)
+            .cloned()
+            .collect()
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -295,6 +365,9 @@ pub enum Modal {
         selected: usize,
         /// Target mode (which mode to update)
         target: RefSelectorTarget,
+        /// What the typed expression resolves to, checked while no listed
+        /// ref matches it
+        revision: Option<Result<crate::git::ResolvedRevision, String>>,
     },
     /// Preset selector for commit style
     PresetSelector {
@@ -331,6 +404,31 @@ pub enum Modal {
         /// Scroll offset for long lists
         scroll: usize,
     },
+    /// Model picker opened from settings, listing the provider's models
+    ModelPicker(Box<ModelPickerState>),
+    /// Instruction preset editor opened from settings
+    PresetEditor(Box<PresetEditorState>),
+    /// Full-text search over archived session history
+    HistorySearch(Box<HistorySearchState>),
+    /// `git log -S/-G` search for when a string was added or removed
+    Pickaxe(Box<PickaxeState>),
+    /// Plain-language question for a semantic code search in Explore
+    SemanticSearch { input: String },
+    /// Commit messages generated or edited on the current branch
+    MessageHistory(Box<MessageHistoryState>),
+    /// Token breakdown of a generation
+    ContextReport(Box<ContextReport>),
+    /// Iris's recap of where work stopped, shown after a long idle gap
+    Recap { branch: String, text: String },
+    /// Review findings about to be posted to a GitHub pull request
+    ReviewExport(Box<ReviewExportState>),
+    /// Commits prepared for making in one go (state lives in
+    /// `modes.commit.queue`)
+    CommitQueue,
+    /// Message and options for a new stash
+    StashCreate(Box<StashCreateState>),
+    /// New subject for a commit in the rebase plan
+    RebaseReword(Box<RebaseRewordState>),
     /// Quick commit count picker for PR mode ("last N commits")
     CommitCount {
         /// Current input (number as string)
@@ -363,6 +461,7 @@ pub enum SettingsSection {
     Provider,
     Appearance,
     Behavior,
+    Advanced,
 }
 
 impl SettingsSection {
@@ -372,10 +471,42 @@ impl SettingsSection {
             SettingsSection::Provider => "Provider",
             SettingsSection::Appearance => "Appearance",
             SettingsSection::Behavior => "Behavior",
+            SettingsSection::Advanced => "Advanced",
         }
     }
 }
 
+/// Where settings are written when saved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SettingsScope {
+    /// Personal config (~/.config/git-iris/config.toml)
+    #[default]
+    Global,
+    /// Project config (`.irisconfig` in the repository root)
+    Repository,
+}
+
+impl SettingsScope {
+    /// Get the display name for this scope
+    pub fn display_name(self) -> &'static str {
+        match self {
+            SettingsScope::Global => "Global",
+            SettingsScope::Repository => "This repository",
+        }
+    }
+}
+
+/// Result of the settings "Test connection" action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    /// Test request in flight
+    Testing,
+    /// Round trip succeeded in the given number of milliseconds
+    Ok(u128),
+    /// Test failed with a user-facing reason
+    Failed(String),
+}
+
 /// Field being edited in settings
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SettingsField {
@@ -383,9 +514,18 @@ pub enum SettingsField {
     Model,
     ApiKey,
     Theme,
+    StatusPersonality,
     UseGitmoji,
     InstructionPreset,
     CustomInstructions,
+    GenerationCapability,
+    Verbosity,
+    Tone,
+    Temperature,
+    TopP,
+    MaxTokens,
+    PresencePenalty,
+    FrequencyPenalty,
 }
 
 impl SettingsField {
@@ -396,9 +536,18 @@ impl SettingsField {
             SettingsField::Model,
             SettingsField::ApiKey,
             SettingsField::Theme,
+            SettingsField::StatusPersonality,
             SettingsField::UseGitmoji,
             SettingsField::InstructionPreset,
             SettingsField::CustomInstructions,
+            SettingsField::GenerationCapability,
+            SettingsField::Verbosity,
+            SettingsField::Tone,
+            SettingsField::Temperature,
+            SettingsField::TopP,
+            SettingsField::MaxTokens,
+            SettingsField::PresencePenalty,
+            SettingsField::FrequencyPenalty,
         ]
     }
 
@@ -409,22 +558,51 @@ impl SettingsField {
             SettingsField::Model => "Model",
             SettingsField::ApiKey => "API Key",
             SettingsField::Theme => "Theme",
+            SettingsField::StatusPersonality => "Personality",
             SettingsField::UseGitmoji => "Gitmoji",
             SettingsField::InstructionPreset => "Preset",
             SettingsField::CustomInstructions => "Instructions",
+            SettingsField::GenerationCapability => "Capability",
+            SettingsField::Verbosity => "Length",
+            SettingsField::Tone => "Tone",
+            SettingsField::Temperature => "Temperature",
+            SettingsField::TopP => "Top P",
+            SettingsField::MaxTokens => "Max Tokens",
+            SettingsField::PresencePenalty => "Presence Pen.",
+            SettingsField::FrequencyPenalty => "Frequency Pen.",
         }
     }
 
+    /// Whether this field edits a generation parameter of the selected capability
+    pub fn is_generation_param(self) -> bool {
+        matches!(
+            self,
+            SettingsField::Temperature
+                | SettingsField::TopP
+                | SettingsField::MaxTokens
+                | SettingsField::PresencePenalty
+                | SettingsField::FrequencyPenalty
+        )
+    }
+
     /// Get which section this field belongs to
     pub fn section(&self) -> SettingsSection {
         match self {
             SettingsField::Provider | SettingsField::Model | SettingsField::ApiKey => {
                 SettingsSection::Provider
             }
-            SettingsField::Theme => SettingsSection::Appearance,
+            SettingsField::Theme | SettingsField::StatusPersonality => SettingsSection::Appearance,
             SettingsField::UseGitmoji
             | SettingsField::InstructionPreset
             | SettingsField::CustomInstructions => SettingsSection::Behavior,
+            SettingsField::GenerationCapability
+            | SettingsField::Verbosity
+            | SettingsField::Tone
+            | SettingsField::Temperature
+            | SettingsField::TopP
+            | SettingsField::MaxTokens
+            | SettingsField::PresencePenalty
+            | SettingsField::FrequencyPenalty => SettingsSection::Advanced,
         }
     }
 }
@@ -463,6 +641,8 @@ pub struct SettingsState {
     pub api_key_actual: Option<String>,
     /// Current theme identifier
     pub theme: String,
+    /// Status message personality pack
+    pub status_personality: StatusPersonality,
     /// Use gitmoji
     pub use_gitmoji: bool,
     /// Instruction preset
@@ -477,6 +657,20 @@ pub struct SettingsState {
     pub available_presets: Vec<String>,
     /// Whether config was modified
     pub modified: bool,
+    /// Fields changed since the modal opened (only these are written on save)
+    pub changed: Vec<SettingsField>,
+    /// Where changes are written on save
+    pub scope: SettingsScope,
+    /// Project config overlay, if the repository has one
+    pub project_config: Option<Config>,
+    /// Outcome of the last connection test
+    pub connection: Option<ConnectionStatus>,
+    /// Generation params per capability
+    pub generation: HashMap<String, GenerationParams>,
+    /// Capability whose params the Advanced section edits
+    pub generation_capability: String,
+    /// Capabilities whose params were edited
+    pub generation_changed: Vec<String>,
     /// Error message if any
     pub error: Option<String>,
 }
@@ -484,8 +678,6 @@ pub struct SettingsState {
 impl SettingsState {
     /// Create settings state from current config
     pub fn from_config(config: &Config) -> Self {
-        use crate::instruction_presets::get_instruction_preset_library;
-        use crate::providers::Provider;
         use crate::theme;
 
         let provider = config.default_provider.clone();
@@ -530,12 +722,7 @@ impl SettingsState {
             .find(|t| t.display_name == current_theme.meta.name)
             .map_or_else(|| "silkcircuit-neon".to_string(), |t| t.id.clone());
 
-        let preset_library = get_instruction_preset_library();
-        let available_presets: Vec<String> = preset_library
-            .list_presets()
-            .iter()
-            .map(|(key, _)| (*key).clone())
-            .collect();
+        let available_presets = preset_keys();
 
         Self {
             selected_field: 0,
@@ -546,6 +733,7 @@ impl SettingsState {
             api_key_display,
             api_key_actual: None, // Only set when user enters a new key
             theme: theme_id,
+            status_personality: config.status_personality,
             use_gitmoji: config.use_gitmoji,
             instruction_preset: config.instruction_preset.clone(),
             custom_instructions: config
@@ -556,20 +744,157 @@ impl SettingsState {
             available_themes,
             available_presets,
             modified: false,
+            changed: Vec::new(),
+            scope: SettingsScope::Global,
+            project_config: Config::load_project_config().ok(),
+            connection: None,
+            generation: config.generation.clone(),
+            generation_capability: GENERATION_CAPABILITIES[0].to_string(),
+            generation_changed: Vec::new(),
             error: None,
         }
     }
 
+    /// Record that a field was changed
+    fn mark_changed(&mut self, field: SettingsField) {
+        self.modified = true;
+        // A previous connection test no longer reflects the credentials
+        if field.section() == SettingsSection::Provider {
+            self.connection = None;
+        }
+        if !self.changed.contains(&field) {
+            self.changed.push(field);
+        }
+    }
+
+    /// Set the model (from the model picker)
+    pub fn set_model(&mut self, model: String) {
+        if model != self.model {
+            self.model = model;
+            self.mark_changed(SettingsField::Model);
+        }
+    }
+
+    /// Reload the preset list and select `key` (from the preset editor)
+    pub fn set_preset(&mut self, key: String) {
+        self.available_presets = preset_keys();
+        if key != self.instruction_preset {
+            self.instruction_preset = key;
+            self.mark_changed(SettingsField::InstructionPreset);
+        }
+    }
+
+    /// Toggle between saving globally and to the repository
+    pub fn toggle_scope(&mut self) {
+        self.scope = match self.scope {
+            SettingsScope::Global if Config::get_project_config_path().is_ok() => {
+                SettingsScope::Repository
+            }
+            SettingsScope::Global => {
+                self.error = Some("Not in a git repository".to_string());
+                return;
+            }
+            SettingsScope::Repository => SettingsScope::Global,
+        };
+        self.error = None;
+    }
+
+    /// Whether the effective value of a field comes from the project config.
+    ///
+    /// Mirrors the precedence rules in `Config::merge_with_project_config`.
+    pub fn is_project_value(&self, field: SettingsField) -> bool {
+        use crate::providers::Provider;
+
+        let Some(project) = &self.project_config else {
+            return false;
+        };
+        match field {
+            SettingsField::Provider => {
+                !project.default_provider.is_empty()
+                    && project.default_provider != Provider::default().name()
+            }
+            SettingsField::Model => project
+                .providers
+                .get(&self.provider)
+                .is_some_and(|p| !p.model.is_empty()),
+            SettingsField::ApiKey => false,
+            SettingsField::Theme => !project.theme.is_empty(),
+            SettingsField::StatusPersonality => !project.status_personality.is_default(),
+            SettingsField::UseGitmoji | SettingsField::CustomInstructions => true,
+            SettingsField::InstructionPreset => project.instruction_preset != "default",
+            SettingsField::GenerationCapability => false,
+            _ => project
+                .generation
+                .get(&self.generation_capability)
+                .is_some_and(|params| param_value(params, field).is_some()),
+        }
+    }
+
+    /// Write the changed fields into a config.
+    ///
+    /// API keys are only written when `include_api_key` is set, so they never
+    /// end up in a project config.
+    pub fn apply_changes(&self, config: &mut Config, include_api_key: bool) {
+        for field in &self.changed {
+            match field {
+                SettingsField::Provider => config.default_provider.clone_from(&self.provider),
+                SettingsField::Model => config
+                    .providers
+                    .entry(self.provider.clone())
+                    .or_default()
+                    .model
+                    .clone_from(&self.model),
+                SettingsField::ApiKey => {
+                    if include_api_key && let Some(api_key) = &self.api_key_actual {
+                        config
+                            .providers
+                            .entry(self.provider.clone())
+                            .or_default()
+                            .api_key
+                            .clone_from(api_key);
+                    }
+                }
+                SettingsField::Theme => config.theme.clone_from(&self.theme),
+                SettingsField::StatusPersonality => {
+                    config.status_personality = self.status_personality;
+                }
+                SettingsField::UseGitmoji => config.use_gitmoji = self.use_gitmoji,
+                SettingsField::InstructionPreset => config
+                    .instruction_preset
+                    .clone_from(&self.instruction_preset),
+                SettingsField::CustomInstructions => {
+                    config.instructions.clone_from(&self.custom_instructions);
+                }
+                SettingsField::GenerationCapability => {}
+                _ => {
+                    for capability in &self.generation_changed {
+                        match self.generation.get(capability) {
+                            Some(params) if !params.is_empty() => {
+                                config.generation.insert(capability.clone(), params.clone());
+                            }
+                            _ => {
+                                config.generation.remove(capability);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     /// Mask an API key for display
     fn mask_api_key(key: &str) -> String {
         if key.is_empty() {
             "(not set)".to_string()
         } else {
-            let len = key.len();
+            let chars: Vec<char> = key.chars().collect();
+            let len = chars.len();
             if len <= 8 {
                 "*".repeat(len)
             } else {
-                format!("{}...{}", &key[..4], &key[len - 4..])
+                let head: String = chars[..4].iter().collect();
+                let tail: String = chars[len - 4..].iter().collect();
+                format!("{head}...{tail}")
             }
         }
     }
@@ -605,6 +930,7 @@ impl SettingsState {
                 .iter()
                 .find(|t| t.id == self.theme)
                 .map_or_else(|| self.theme.clone(), |t| t.display_name.clone()),
+            SettingsField::StatusPersonality => self.status_personality.to_string(),
             SettingsField::UseGitmoji => {
                 if self.use_gitmoji {
                     "yes".to_string()
@@ -613,6 +939,32 @@ impl SettingsState {
                 }
             }
             SettingsField::InstructionPreset => self.instruction_preset.clone(),
+            SettingsField::GenerationCapability => self.generation_capability.clone(),
+            SettingsField::Verbosity
+            | SettingsField::Tone
+            | SettingsField::Temperature
+            | SettingsField::TopP
+            | SettingsField::MaxTokens
+            | SettingsField::PresencePenalty
+            | SettingsField::FrequencyPenalty => {
+                let own = self
+                    .generation
+                    .get(&self.generation_capability)
+                    .and_then(|params| param_value(params, field));
+                let inherited = self
+                    .generation
+                    .get("default")
+                    .and_then(|params| param_value(params, field));
+                match (own, inherited) {
+                    (Some(value), _) => value,
+                    (None, Some(value)) => format!("{value} (default)"),
+                    (None, None) if field == SettingsField::Verbosity => {
+                        Verbosity::default().to_string()
+                    }
+                    (None, None) if field == SettingsField::Tone => Tone::default().to_string(),
+                    (None, None) => "(provider default)".to_string(),
+                }
+            }
             SettingsField::CustomInstructions => {
                 if self.custom_instructions.is_empty() {
                     "(none)".to_string()
@@ -662,7 +1014,7 @@ impl SettingsState {
                         idx - 1
                     };
                     self.provider = self.available_providers[next].clone();
-                    self.modified = true;
+                    self.mark_changed(field);
                 }
             }
             SettingsField::Theme => {
@@ -679,14 +1031,28 @@ impl SettingsState {
                         idx - 1
                     };
                     self.theme = self.available_themes[next].id.clone();
-                    self.modified = true;
+                    self.mark_changed(field);
                     // Apply theme immediately for live preview
                     let _ = crate::theme::load_theme_by_name(&self.theme);
                 }
             }
+            SettingsField::StatusPersonality => {
+                let all = StatusPersonality::ALL;
+                let idx = all
+                    .iter()
+                    .position(|p| *p == self.status_personality)
+                    .unwrap_or(0);
+                let next = if forward {
+                    (idx + 1) % all.len()
+                } else {
+                    (idx + all.len() - 1) % all.len()
+                };
+                self.status_personality = all[next];
+                self.mark_changed(field);
+            }
             SettingsField::UseGitmoji => {
                 self.use_gitmoji = !self.use_gitmoji;
-                self.modified = true;
+                self.mark_changed(field);
             }
             SettingsField::InstructionPreset => {
                 if let Some(idx) = self
@@ -702,8 +1068,36 @@ impl SettingsState {
                         idx - 1
                     };
                     self.instruction_preset = self.available_presets[next].clone();
-                    self.modified = true;
+                    self.mark_changed(field);
+                }
+            }
+            SettingsField::GenerationCapability => {
+                // Selecting which capability to edit isn't itself a change
+                let len = GENERATION_CAPABILITIES.len();
+                let idx = GENERATION_CAPABILITIES
+                    .iter()
+                    .position(|c| *c == self.generation_capability)
+                    .unwrap_or(0);
+                let next = if forward {
+                    (idx + 1) % len
+                } else {
+                    (idx + len - 1) % len
+                };
+                self.generation_capability = GENERATION_CAPABILITIES[next].to_string();
+            }
+            SettingsField::Verbosity | SettingsField::Tone => {
+                // Cycles through the levels and back to unset (inherit)
+                let capability = self.generation_capability.clone();
+                let params = self.generation.entry(capability.clone()).or_default();
+                if field == SettingsField::Verbosity {
+                    params.verbosity = cycle_option(params.verbosity, Verbosity::ALL, forward);
+                } else {
+                    params.tone = cycle_option(params.tone, Tone::ALL, forward);
+                }
+                if !self.generation_changed.contains(&capability) {
+                    self.generation_changed.push(capability);
                 }
+                self.mark_changed(field);
             }
             _ => {}
         }
@@ -725,6 +1119,14 @@ impl SettingsState {
                 self.input_buffer = self.custom_instructions.clone();
                 self.editing = true;
             }
+            field if field.is_generation_param() => {
+                self.input_buffer = self
+                    .generation
+                    .get(&self.generation_capability)
+                    .and_then(|params| param_value(params, field))
+                    .unwrap_or_default();
+                self.editing = true;
+            }
             _ => {
                 // For other fields, cycle instead
                 self.cycle_current_field();
@@ -746,25 +1148,36 @@ impl SettingsState {
 
         let field = self.current_field();
         match field {
-            SettingsField::Model => {
-                if !self.input_buffer.is_empty() {
-                    self.model = self.input_buffer.clone();
-                    self.modified = true;
-                }
+            SettingsField::Model if !self.input_buffer.is_empty() => {
+                self.model = self.input_buffer.clone();
+                self.mark_changed(field);
             }
-            SettingsField::ApiKey => {
-                if !self.input_buffer.is_empty() {
-                    // Store actual key, update display
-                    let key = self.input_buffer.clone();
-                    self.api_key_display = Self::mask_api_key(&key);
-                    self.api_key_actual = Some(key);
-                    self.modified = true;
-                }
+            SettingsField::ApiKey if !self.input_buffer.is_empty() => {
+                // Store actual key, update display
+                let key = self.input_buffer.clone();
+                self.api_key_display = Self::mask_api_key(&key);
+                self.api_key_actual = Some(key);
+                self.mark_changed(field);
             }
             SettingsField::CustomInstructions => {
                 // Allow empty (clears instructions)
                 self.custom_instructions = self.input_buffer.clone();
-                self.modified = true;
+                self.mark_changed(field);
+            }
+            field if field.is_generation_param() => {
+                // Empty clears the override
+                let capability = self.generation_capability.clone();
+                let params = self.generation.entry(capability.clone()).or_default();
+                match set_param_value(params, field, self.input_buffer.trim()) {
+                    Ok(()) => {
+                        self.error = None;
+                        if !self.generation_changed.contains(&capability) {
+                            self.generation_changed.push(capability);
+                        }
+                        self.mark_changed(field);
+                    }
+                    Err(e) => self.error = Some(e),
+                }
             }
             _ => {}
         }
@@ -774,6 +1187,88 @@ impl SettingsState {
     }
 }
 
+/// Display value of a generation parameter, if set
+fn param_value(params: &GenerationParams, field: SettingsField) -> Option<String> {
+    match field {
+        SettingsField::Temperature => params.temperature.map(|v| v.to_string()),
+        SettingsField::TopP => params.top_p.map(|v| v.to_string()),
+        SettingsField::MaxTokens => params.max_tokens.map(|v| v.to_string()),
+        SettingsField::PresencePenalty => params.presence_penalty.map(|v| v.to_string()),
+        SettingsField::FrequencyPenalty => params.frequency_penalty.map(|v| v.to_string()),
+        SettingsField::Verbosity => params.verbosity.map(|v| v.to_string()),
+        SettingsField::Tone => params.tone.map(|v| v.to_string()),
+        _ => None,
+    }
+}
+
+/// Keys of every available preset, sorted
+fn preset_keys() -> Vec<String> {
+    let mut keys: Vec<String> = crate::instruction_presets::get_instruction_preset_library()
+        .list_presets()
+        .into_iter()
+        .map(|(key, _)| key.clone())
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// The value after (or before) `current` in `all`, with unset between the
+/// last and first
+fn cycle_option<T: Copy + PartialEq>(current: Option<T>, all: &[T], forward: bool) -> Option<T> {
+    let len = all.len() + 1;
+    let idx = current
+        .and_then(|value| all.iter().position(|v| *v == value))
+        .map_or(0, |i| i + 1);
+    let next = if forward {
+        (idx + 1) % len
+    } else {
+        (idx + len - 1) % len
+    };
+    next.checked_sub(1).map(|i| all[i])
+}
+
+/// Parse and range-check a generation parameter; empty input unsets it
+fn set_param_value(
+    params: &mut GenerationParams,
+    field: SettingsField,
+    input: &str,
+) -> Result<(), String> {
+    let float = |min: f64, max: f64| -> Result<Option<f64>, String> {
+        if input.is_empty() {
+            return Ok(None);
+        }
+        match input.parse::<f64>() {
+            Ok(value) if (min..=max).contains(&value) => Ok(Some(value)),
+            _ => Err(format!(
+                "{} must be a number between {min} and {max}",
+                field.display_name()
+            )),
+        }
+    };
+
+    match field {
+        SettingsField::Temperature => params.temperature = float(0.0, 2.0)?,
+        SettingsField::TopP => params.top_p = float(0.0, 1.0)?,
+        SettingsField::PresencePenalty => params.presence_penalty = float(-2.0, 2.0)?,
+        SettingsField::FrequencyPenalty => params.frequency_penalty = float(-2.0, 2.0)?,
+        SettingsField::MaxTokens => {
+            params.max_tokens = if input.is_empty() {
+                None
+            } else {
+                Some(
+                    input
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|v| *v > 0)
+                        .ok_or_else(|| "Max Tokens must be a positive integer".to_string())?,
+                )
+            };
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 /// Target for ref selector modal
 #[derive(Debug, Clone, Copy)]
 pub enum RefSelectorTarget {
@@ -793,6 +1288,8 @@ pub enum RefSelectorTarget {
     ReleaseNotesFrom,
     /// Release notes to version
     ReleaseNotesTo,
+    /// Rebase base branch
+    RebaseBase,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -969,6 +1466,17 @@ pub struct StudioState {
     /// Companion session display data (updated periodically)
     pub companion_display: CompanionSessionDisplay,
 
+    /// Spell checker for generated text (None when disabled in config)
+    pub spell_checker: Option<Arc<SpellChecker>>,
+
+    /// Whether modes generate on their own (status bar toggle, starts from
+    /// `[auto_generate] enabled`)
+    pub auto_generate: bool,
+
+    /// Provider whose missing API key keeps Iris offline; Studio then runs
+    /// without AI features and scaffolds commit messages instead
+    pub degraded: Option<Provider>,
+
     /// Whether the UI needs redraw
     pub dirty: bool,
 
@@ -989,6 +1497,21 @@ impl StudioState {
             modes.commit.preset.clone_from(temp_preset);
         }
 
+        let spell_checker = config.spell_check.then(|| {
+            let root = repo.as_ref().map(|r| r.repo_path().as_path());
+            Arc::new(SpellChecker::load(root))
+        });
+        modes
+            .commit
+            .message_editor
+            .set_spell_checker(spell_checker.clone());
+        modes
+            .commit
+            .message_editor
+            .set_commit_style(config.commit_style.clone());
+
+        let auto_generate = config.auto_generate.enabled;
+        let degraded = config.missing_api_key();
         Self {
             repo,
             git_status: GitStatus::default(),
@@ -1003,6 +1526,9 @@ impl StudioState {
             iris_status: IrisStatus::Idle,
             companion: None,
             companion_display: CompanionSessionDisplay::default(),
+            spell_checker,
+            auto_generate,
+            degraded,
             dirty: true,
             last_render: std::time::Instant::now(),
         }
@@ -1026,6 +1552,108 @@ impl StudioState {
         Mode::Explore
     }
 
+    /// What makes `mode` generate on its own; `Manual` while the status bar
+    /// toggle is off and for modes that don't generate
+    pub fn auto_trigger(&self, mode: Mode) -> AutoTrigger {
+        if !self.auto_generate {
+            return AutoTrigger::Manual;
+        }
+        let triggers = &self.config.auto_generate;
+        match mode {
+            Mode::Commit => triggers.commit,
+            Mode::Review => triggers.review,
+            Mode::PR => triggers.pr,
+            Mode::Changelog => triggers.changelog,
+            Mode::ReleaseNotes => triggers.release_notes,
+            Mode::Explore | Mode::Dashboard | Mode::Stash | Mode::Rebase => AutoTrigger::Manual,
+        }
+    }
+
+    /// Flip the status bar auto-generate toggle
+    pub fn toggle_auto_generate(&mut self) {
+        self.auto_generate = !self.auto_generate;
+        self.notify(if self.auto_generate {
+            Notification::info("Auto-generate on")
+        } else {
+            Notification::info("Auto-generate off: press r to generate")
+        });
+        self.mark_dirty();
+    }
+
+    /// Output length and tone of the active mode, if it generates anything
+    pub fn output_style(&self) -> Option<(Verbosity, Tone)> {
+        let params = self
+            .config
+            .generation_params(self.active_mode.capability()?);
+        Some((
+            params.verbosity.unwrap_or_default(),
+            params.tone.unwrap_or_default(),
+        ))
+    }
+
+    /// Step the active mode's output length to the next level for this session
+    pub fn cycle_verbosity(&mut self) -> bool {
+        self.cycle_output_style(|params, (verbosity, _)| {
+            let next = Verbosity::ALL
+                .iter()
+                .position(|v| *v == verbosity)
+                .map_or(0, |i| (i + 1) % Verbosity::ALL.len());
+            params.verbosity = Some(Verbosity::ALL[next]);
+            format!("{} output", Verbosity::ALL[next])
+        })
+    }
+
+    /// Step the active mode's tone to the next one for this session
+    pub fn cycle_tone(&mut self) -> bool {
+        self.cycle_output_style(|params, (_, tone)| {
+            let next = Tone::ALL
+                .iter()
+                .position(|t| *t == tone)
+                .map_or(0, |i| (i + 1) % Tone::ALL.len());
+            params.tone = Some(Tone::ALL[next]);
+            format!("{} tone", Tone::ALL[next])
+        })
+    }
+
+    /// Apply `change` to the active mode's session generation params,
+    /// returning false when the mode doesn't generate
+    fn cycle_output_style(
+        &mut self,
+        change: impl FnOnce(&mut GenerationParams, (Verbosity, Tone)) -> String,
+    ) -> bool {
+        let (Some(capability), Some(style)) = (self.active_mode.capability(), self.output_style())
+        else {
+            self.notify(Notification::info(format!(
+                "{} mode doesn't generate text",
+                self.active_mode.display_name()
+            )));
+            return false;
+        };
+        let params = self
+            .config
+            .temp_generation
+            .entry(capability.to_string())
+            .or_default();
+        let label = change(params, style);
+        self.notify(Notification::info(format!(
+            "{}: {label} for this session",
+            self.active_mode.display_name()
+        )));
+        self.mark_dirty();
+        true
+    }
+
+    /// Put an undone commit's message back into the Commit editor
+    pub fn load_commit_draft(&mut self, record: &crate::undo::CommitRecord) {
+        let commit = &mut self.modes.commit;
+        // The draft belongs to these staged changes; don't regenerate over it
+        commit.generated_for = commit.staged_fingerprint;
+        commit.messages = vec![record.to_generated()];
+        commit.current_index = 0;
+        commit.message_editor.set_messages(commit.messages.clone());
+        self.switch_mode(Mode::Commit);
+    }
+
     /// Switch to a new mode with context preservation
     pub fn switch_mode(&mut self, new_mode: Mode) {
         if !new_mode.is_available() {
@@ -1059,6 +1687,12 @@ impl StudioState {
             Mode::Review | Mode::PR | Mode::Changelog | Mode::ReleaseNotes => PanelId::Center,
             // Explore: focus on file tree (left panel)
             Mode::Explore => PanelId::Left,
+            // Dashboard: focus on the hot files list (center panel)
+            Mode::Dashboard => PanelId::Center,
+            // Stash: focus on the stash list (left panel)
+            Mode::Stash => PanelId::Left,
+            // Rebase: focus on the plan (left panel)
+            Mode::Rebase => PanelId::Left,
         };
         self.dirty = true;
     }
@@ -1117,6 +1751,79 @@ impl StudioState {
         self.dirty = true;
     }
 
+    /// Expand the Explore selection (or current line) for chat context
+    ///
+    /// Returns `None` outside Explore mode or when no file is loaded.
+    pub fn explore_code_context(&self) -> Option<String> {
+        if self.active_mode != Mode::Explore {
+            return None;
+        }
+        let explore = &self.modes.explore;
+        let file = explore.current_file.as_ref()?;
+        let (start, end) = explore
+            .selection
+            .unwrap_or((explore.current_line, explore.current_line));
+        CodeContext::expand(file, explore.code_view.lines(), start, end)
+            .map(|ctx| ctx.to_prompt_section())
+    }
+
+    /// Build a chat pin for whatever is focused in the active mode
+    ///
+    /// Explore pins the selection (or the whole file). Diff-based modes pin
+    /// the hunk at the top of the diff when the diff panel is focused, and
+    /// the whole file diff otherwise.
+    pub fn focused_pin(&self) -> Option<PinnedContext> {
+        let diff_view = match self.active_mode {
+            Mode::Explore => {
+                let explore = &self.modes.explore;
+                let file = explore.current_file.as_ref()?;
+                let lines = explore.code_view.lines();
+                if let Some((start, end)) = explore.selection {
+                    let start = start.max(1);
+                    let end = end.min(lines.len());
+                    if start <= end {
+                        return Some(PinnedContext::new(
+                            format!("{}:{}-{}", file.display(), start, end),
+                            &lines[start - 1..end].join("\n"),
+                        ));
+                    }
+                }
+                if lines.is_empty() {
+                    return None;
+                }
+                return Some(PinnedContext::new(
+                    file.display().to_string(),
+                    &lines.join("\n"),
+                ));
+            }
+            Mode::Commit => &self.modes.commit.diff_view,
+            Mode::Review => &self.modes.review.diff_view,
+            Mode::PR => &self.modes.pr.diff_view,
+            Mode::Changelog => &self.modes.changelog.diff_view,
+            Mode::ReleaseNotes => &self.modes.release_notes.diff_view,
+            Mode::Stash => &self.modes.stash.diff_view,
+            Mode::Rebase => &self.modes.rebase.diff_view,
+            Mode::Dashboard => return None,
+        };
+
+        let diff = diff_view.current_diff()?;
+        if self.focused_panel == PanelId::Right
+            && let Some((idx, hunk)) = diff_view.current_hunk()
+        {
+            return Some(PinnedContext::new(
+                format!("{} (hunk {})", diff.path.display(), idx + 1),
+                &hunk.to_patch(),
+            ));
+        }
+        if diff.hunks.is_empty() {
+            return None;
+        }
+        Some(PinnedContext::new(
+            format!("{} (diff)", diff.path.display()),
+            &diff.to_patch(),
+        ))
+    }
+
     /// Open chat modal (universal, persists across modes)
     pub fn show_chat(&mut self) {
         // If chat is empty, initialize with context from all generated content
@@ -1204,6 +1911,51 @@ impl StudioState {
         self.dirty = true;
     }
 
+    /// Generation running in the active mode, if any
+    pub fn active_generation(&self) -> Option<TaskType> {
+        let modes = &self.modes;
+        match self.active_mode {
+            Mode::Commit if modes.commit.generating => Some(TaskType::Commit),
+            Mode::Review if modes.review.generating => Some(TaskType::Review),
+            Mode::PR if modes.pr.generating => Some(TaskType::PR),
+            Mode::Changelog if modes.changelog.generating => Some(TaskType::Changelog),
+            Mode::ReleaseNotes if modes.release_notes.generating => Some(TaskType::ReleaseNotes),
+            Mode::Explore if modes.explore.blame_loading => Some(TaskType::SemanticBlame),
+            _ => None,
+        }
+    }
+
+    /// Clear the in-progress flag for a kind of task
+    pub fn stop_generating(&mut self, task_type: TaskType) {
+        match task_type {
+            TaskType::Commit => self.modes.commit.generating = false,
+            TaskType::Review => self.modes.review.generating = false,
+            TaskType::PR => self.modes.pr.generating = false,
+            TaskType::Changelog => self.modes.changelog.generating = false,
+            TaskType::ReleaseNotes => self.modes.release_notes.generating = false,
+            TaskType::Chat => self.chat_state.is_responding = false,
+            TaskType::SemanticBlame => self.modes.explore.blame_loading = false,
+        }
+    }
+
+    /// Abandon a running generation, dropping any partial output
+    pub fn cancel_generation(&mut self, task_type: TaskType) {
+        self.stop_generating(task_type);
+        match task_type {
+            TaskType::Review => self.modes.review.streaming_content = None,
+            TaskType::PR => self.modes.pr.streaming_content = None,
+            TaskType::Changelog => self.modes.changelog.streaming_content = None,
+            TaskType::ReleaseNotes => self.modes.release_notes.streaming_content = None,
+            TaskType::Chat => self.chat_state.streaming_response = None,
+            TaskType::SemanticBlame => self.modes.explore.streaming_blame = None,
+            TaskType::Commit => {}
+        }
+        self.set_iris_idle();
+        self.notify(Notification::info(format!(
+            "Cancelled {task_type} generation"
+        )));
+    }
+
     /// Set Iris idle
     pub fn set_iris_idle(&mut self) {
         self.iris_status = IrisStatus::Idle;
@@ -1249,6 +2001,14 @@ impl StudioState {
         }
     }
 
+    /// Sources an agent answer cites, checked against the repository
+    pub fn verify_sources(&self, answer: &str) -> Vec<CheckedCitation> {
+        self.repo
+            .as_deref()
+            .map(|repo| verify_sources(answer, repo))
+            .unwrap_or_default()
+    }
+
     /// Get list of branch refs for selection
     pub fn get_branch_refs(&self) -> Vec<String> {
         let Some(git_repo) = &self.repo else {
@@ -1488,4 +2248,81 @@ impl StudioState {
         // Update display
         self.update_companion_display();
     }
+
+    /// Turn amend mode on or off, loading HEAD's message and the branch's
+    /// earlier amends when it turns on
+    pub fn set_amend_mode(&mut self, amend: bool) {
+        let commit = &mut self.modes.commit;
+        commit.amend_mode = amend;
+        commit.original_message = None;
+        commit.branch_revisions.clear();
+        // Generated messages were for the other mode
+        commit.messages.clear();
+        commit.message_editor.clear();
+        if amend {
+            if let Some(repo) = &self.repo
+                && let Ok(msg) = repo.get_head_commit_message()
+            {
+                // Start from HEAD's message; generating replaces it
+                commit.messages = vec![GeneratedMessage::from_text(&msg)];
+                commit.current_index = 0;
+                commit.message_editor.set_messages(commit.messages.clone());
+                commit.generated_for = commit.staged_fingerprint;
+                commit.original_message = Some(msg);
+            }
+            if let Some(companion) = &self.companion
+                && let Ok(Some(memory)) = companion.load_branch_memory(&self.git_status.branch)
+            {
+                commit.branch_revisions = memory.message_revisions;
+            }
+        }
+        self.mark_dirty();
+    }
+
+    /// Remember a commit message generated or edited on the current branch
+    pub fn record_message_draft(&self, message: &str, kind: DraftKind) {
+        if let Some(companion) = &self.companion
+            && let Err(e) = companion.record_message_draft(
+                &self.git_status.branch,
+                MessageDraft::new(message.to_string(), kind),
+            )
+        {
+            tracing::warn!("Failed to record message draft: {}", e);
+        }
+    }
+
+    /// Open the message history modal for the current branch
+    pub fn open_message_history(&mut self) {
+        let drafts = self
+            .companion
+            .as_ref()
+            .and_then(|companion| {
+                companion
+                    .load_branch_memory(&self.git_status.branch)
+                    .ok()
+                    .flatten()
+            })
+            .map(|memory| memory.message_drafts)
+            .unwrap_or_default();
+        if drafts.is_empty() {
+            self.notify(Notification::info("No earlier drafts on this branch"));
+            return;
+        }
+        self.modal = Some(Modal::MessageHistory(Box::new(MessageHistoryState::new(
+            drafts,
+        ))));
+        self.mark_dirty();
+    }
+
+    /// Bring a drafted message back as the current message
+    pub fn restore_message_draft(&mut self, message: &str) {
+        let commit = &mut self.modes.commit;
+        let restored = GeneratedMessage::from_text(message);
+        // Picked by hand; don't regenerate over it
+        commit.messages.push(restored.clone());
+        commit.current_index = commit.message_editor.add_messages(vec![restored]);
+        commit.generated_for = commit.staged_fingerprint;
+        self.notify(Notification::success("Restored message from history"));
+        self.mark_dirty();
+    }
 }
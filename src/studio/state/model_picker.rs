@@ -0,0 +1,167 @@
+//! Model picker modal state for Iris Studio
+//!
+//! Searchable list of the models offered by the configured provider, opened
+//! from the Settings modal. Typed text doubles as a manual entry when the
+//! list can't be fetched or doesn't contain the model you want.
+
+use super::SettingsState;
+
+/// State for the model picker modal
+#[derive(Debug, Clone)]
+pub struct ModelPickerState {
+    /// Settings modal to return to
+    pub settings: SettingsState,
+    /// Provider the list belongs to
+    pub provider: String,
+    /// Filter / manual entry text
+    pub input: String,
+    /// Models reported by the provider
+    pub models: Vec<String>,
+    /// Whether the list is still being fetched
+    pub loading: bool,
+    /// Fetch error, if any
+    pub error: Option<String>,
+    /// Selected index into the filtered list
+    pub selected: usize,
+    /// Scroll offset for long lists
+    pub scroll: usize,
+}
+
+impl ModelPickerState {
+    /// Start picking a model for the settings' current provider
+    pub fn new(settings: SettingsState) -> Self {
+        Self {
+            provider: settings.provider.clone(),
+            settings,
+            input: String::new(),
+            models: Vec::new(),
+            loading: true,
+            error: None,
+            selected: 0,
+            scroll: 0,
+        }
+    }
+
+    /// Apply the result of a model list fetch
+    pub fn set_models(&mut self, result: Result<Vec<String>, String>) {
+        self.loading = false;
+        match result {
+            Ok(models) => {
+                self.models = models;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+        // Start on the configured model when it's in the list
+        self.selected = self
+            .filtered()
+            .iter()
+            .position(|m| *m == self.settings.model)
+            .unwrap_or(0);
+    }
+
+    /// Models matching the current input
+    pub fn filtered(&self) -> Vec<&str> {
+        let needle = self.input.to_lowercase();
+        self.models
+            .iter()
+            .filter(|m| needle.is_empty() || m.to_lowercase().contains(&needle))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Append a character to the input
+    pub fn push_char(&mut self, c: char) {
+        self.input.push(c);
+        self.selected = 0;
+        self.scroll = 0;
+    }
+
+    /// Remove the last input character
+    pub fn pop_char(&mut self) {
+        self.input.pop();
+        self.selected = 0;
+        self.scroll = 0;
+    }
+
+    /// Move the selection down, keeping it within `visible` rows
+    pub fn select_next(&mut self, visible: usize) {
+        if self.selected + 1 < self.filtered().len() {
+            self.selected += 1;
+            if self.selected >= self.scroll + visible {
+                self.scroll = self.selected + 1 - visible;
+            }
+        }
+    }
+
+    /// Move the selection up
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        }
+    }
+
+    /// Model that Enter would pick: the highlighted match, else the typed text
+    pub fn choice(&self) -> Option<String> {
+        self.filtered()
+            .get(self.selected)
+            .map(|m| (*m).to_string())
+            .or_else(|| {
+                let typed = self.input.trim();
+                (!typed.is_empty()).then(|| typed.to_string())
+            })
+    }
+
+    /// Return to the settings modal, applying `model` if given
+    pub fn into_settings(self, model: Option<String>) -> SettingsState {
+        let mut settings = self.settings;
+        if let Some(model) = model {
+            settings.set_model(model);
+        }
+        settings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn picker() -> ModelPickerState {
+        let mut picker = ModelPickerState::new(SettingsState::from_config(&Config::default()));
+        picker.set_models(Ok(vec![
+            "gpt-4o".to_string(),
+            "gpt-5.1".to_string(),
+            "gpt-5.1-mini".to_string(),
+        ]));
+        picker
+    }
+
+    #[test]
+    fn test_filter_and_choice() {
+        let mut picker = picker();
+        assert!(!picker.loading);
+        for c in "5.1".chars() {
+            picker.push_char(c);
+        }
+        assert_eq!(picker.filtered(), vec!["gpt-5.1", "gpt-5.1-mini"]);
+        picker.select_next(10);
+        assert_eq!(picker.choice().as_deref(), Some("gpt-5.1-mini"));
+    }
+
+    #[test]
+    fn test_manual_entry_fallback() {
+        let mut picker = ModelPickerState::new(SettingsState::from_config(&Config::default()));
+        picker.set_models(Err("offline".to_string()));
+        assert_eq!(picker.error.as_deref(), Some("offline"));
+        assert_eq!(picker.choice(), None);
+
+        for c in "my-custom-model".chars() {
+            picker.push_char(c);
+        }
+        let settings = picker.clone().into_settings(picker.choice());
+        assert_eq!(settings.model, "my-custom-model");
+        assert!(settings.modified);
+    }
+}
@@ -1,13 +1,24 @@
 //! Mode-specific state structs for Iris Studio
 //!
 //! Each mode (Explore, Commit, Review, PR, Changelog, `ReleaseNotes`) has its own state struct.
+//! Dashboard, Stash and Rebase state live in `dashboard.rs`, `stash.rs` and
+//! `rebase.rs`.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::types::GeneratedMessage;
+use crate::companion::MessageRevision;
+use crate::integrations::ci::CiStatus;
+use crate::risk::RiskReport;
+use crate::types::{
+    CheckedCitation, GeneratedMessage, ReviewFinding, SuggestedVersion, parse_findings,
+};
 
-use super::super::components::{CodeViewState, DiffViewState, FileTreeState, MessageEditorState};
-use super::EmojiMode;
+use super::super::components::{
+    CodeViewState, DiffViewState, FileTreeState, HeatMapData, HeatMapMode, MessageEditorState,
+};
+use super::{CommitQueueState, EmojiMode, SemanticSearchState};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Explore Mode
@@ -46,14 +57,22 @@ pub struct ExploreState {
     pub selection_anchor: Option<usize>,
     /// Code view scroll offset
     pub code_scroll: usize,
-    /// Heat map enabled
-    pub show_heat_map: bool,
+    /// Active file tree heat map overlay
+    pub heat_map_mode: HeatMapMode,
+    /// Cached churn/ownership data for the heat map
+    pub heat_map_data: Option<Arc<HeatMapData>>,
+    /// When the cached heat map data was computed
+    pub heat_map_loaded_at: Option<Instant>,
+    /// Whether heat map data is loading
+    pub heat_map_loading: bool,
     /// File tree state
     pub file_tree: FileTreeState,
     /// Code view state
     pub code_view: CodeViewState,
     /// Current semantic blame result (for context panel)
     pub semantic_blame: Option<super::super::events::SemanticBlameResult>,
+    /// Sources the semantic blame explanation cites
+    pub blame_sources: Vec<CheckedCitation>,
     /// Streaming blame content (while generating)
     pub streaming_blame: Option<String>,
     /// Whether semantic blame is loading
@@ -74,6 +93,16 @@ pub struct ExploreState {
     pub global_log_loading: bool,
     /// Pending file log path (for deferred loading after event loop starts)
     pub pending_file_log: Option<PathBuf>,
+    /// Document to show in the code view on launch instead of the first file
+    pub document: Option<PathBuf>,
+    /// Line to select in `document` once it loads
+    pub document_line: Option<usize>,
+    /// Semantic search results, shown instead of the file tree while set
+    pub search: Option<SemanticSearchState>,
+    /// Whether Iris is writing the architecture overview
+    pub overview_loading: bool,
+    /// Whether the whole-file blame is loading
+    pub file_blame_loading: bool,
 }
 
 impl std::fmt::Debug for ExploreState {
@@ -83,11 +112,33 @@ impl std::fmt::Debug for ExploreState {
             .field("current_line", &self.current_line)
             .field("selection", &self.selection)
             .field("code_scroll", &self.code_scroll)
-            .field("show_heat_map", &self.show_heat_map)
+            .field("heat_map_mode", &self.heat_map_mode)
             .finish_non_exhaustive()
     }
 }
 
+impl ExploreState {
+    /// Number of days of history used for the heat map
+    pub const HEAT_MAP_WINDOW_DAYS: u32 = 90;
+
+    /// How long cached heat map data stays fresh
+    const HEAT_MAP_TTL: Duration = Duration::from_mins(5);
+
+    /// Whether the cached heat map data is missing or stale
+    pub fn heat_map_needs_load(&self) -> bool {
+        self.heat_map_data.is_none()
+            || self
+                .heat_map_loaded_at
+                .is_none_or(|at| at.elapsed() > Self::HEAT_MAP_TTL)
+    }
+
+    /// Push the current heat map mode and data into the file tree
+    pub fn sync_heat_overlay(&mut self) {
+        self.file_tree
+            .set_heat_overlay(self.heat_map_mode, self.heat_map_data.clone());
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Commit Mode
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -125,6 +176,18 @@ pub struct CommitState {
     pub amend_mode: bool,
     /// Original commit message (when amending)
     pub original_message: Option<String>,
+    /// Show the original vs revised message instead of the diff when amending
+    pub show_drift: bool,
+    /// Earlier amends on the current branch, oldest first
+    pub branch_revisions: Vec<MessageRevision>,
+    /// Risk score for the staged changes
+    pub risk: Option<RiskReport>,
+    /// Hash of the staged diff, to notice when the staged changes change
+    pub staged_fingerprint: Option<u64>,
+    /// `staged_fingerprint` when the current messages were generated
+    pub generated_for: Option<u64>,
+    /// Commits prepared to be made one after another
+    pub queue: CommitQueueState,
 }
 
 impl Default for CommitState {
@@ -145,6 +208,12 @@ impl Default for CommitState {
             show_all_files: false,
             amend_mode: false,
             original_message: None,
+            show_drift: true,
+            branch_revisions: Vec::new(),
+            risk: None,
+            staged_fingerprint: None,
+            generated_for: None,
+            queue: CommitQueueState::default(),
         }
     }
 }
@@ -158,6 +227,7 @@ impl std::fmt::Debug for CommitState {
             .field("editing_message", &self.editing_message)
             .field("generating", &self.generating)
             .field("amend_mode", &self.amend_mode)
+            .field("queued_commits", &self.queue.entries.len())
             .finish_non_exhaustive()
     }
 }
@@ -173,7 +243,7 @@ pub struct ReviewState {
     /// Diff view for selected file
     pub diff_view: DiffViewState,
     /// Generated review content (markdown)
-    pub review_content: String,
+    pub review_content: Arc<str>,
     /// Streaming content (while generating)
     pub streaming_content: Option<String>,
     /// Review scroll offset
@@ -184,6 +254,89 @@ pub struct ReviewState {
     pub from_ref: String,
     /// To ref for comparison (defaults to HEAD)
     pub to_ref: String,
+    /// Risk score for the reviewed changes
+    pub risk: Option<RiskReport>,
+    /// Findings parsed from `review_content`
+    pub findings: Vec<ReviewFinding>,
+    /// Finding last jumped to with `n`/`N`
+    pub selected_finding: Option<usize>,
+    /// Sources the review cites, checked against the repository
+    pub sources: Vec<CheckedCitation>,
+    /// Source selected in the footer with `[`/`]`
+    pub selected_source: Option<usize>,
+    /// Whether findings are being posted to a pull request
+    pub posting: bool,
+}
+
+impl ReviewState {
+    /// Replace the review, re-parsing its findings
+    pub fn set_content(&mut self, content: Arc<str>) {
+        self.findings = parse_findings(&content);
+        self.selected_finding = None;
+        self.sources.clear();
+        self.selected_source = None;
+        self.review_content = content;
+    }
+
+    /// Select the next (or previous) source in the footer, wrapping around.
+    /// Returns the new index.
+    pub fn select_source(&mut self, forward: bool) -> Option<usize> {
+        let count = self.sources.len();
+        if count == 0 {
+            return None;
+        }
+        let index = match (self.selected_source, forward) {
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+            (None, true) => 0,
+            (None, false) => count - 1,
+        };
+        self.selected_source = Some(index);
+        Some(index)
+    }
+
+    /// Jump to the next (or previous) finding, scrolling the review to it
+    /// and selecting its file and hunk. Returns the new index.
+    pub fn jump_to_finding(&mut self, forward: bool) -> Option<usize> {
+        let count = self.findings.len();
+        if count == 0 {
+            return None;
+        }
+        let index = match (self.selected_finding, forward) {
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+            // Start from whatever is on screen
+            (None, true) => self
+                .findings
+                .iter()
+                .position(|f| f.line >= self.review_scroll)
+                .unwrap_or(0),
+            (None, false) => self
+                .findings
+                .iter()
+                .rposition(|f| f.line <= self.review_scroll)
+                .unwrap_or(count - 1),
+        };
+        self.selected_finding = Some(index);
+        self.review_scroll = self.findings[index].line;
+
+        if let Some(location) = self.findings[index].location.clone() {
+            let cited = Path::new(&location.path);
+            // Reviews often cite a bare file name or a repo-relative path
+            let path = self
+                .diff_view
+                .file_paths()
+                .into_iter()
+                .find(|p| *p == cited || p.ends_with(cited))
+                .map(Path::to_path_buf);
+            if let Some(path) = path {
+                self.file_tree.select_path(&path);
+                self.diff_view.select_file_by_path(&path);
+                self.diff_view.scroll_to_new_line(location.start_line);
+            }
+        }
+        Some(index)
+    }
 }
 
 impl Default for ReviewState {
@@ -191,12 +344,18 @@ impl Default for ReviewState {
         Self {
             file_tree: FileTreeState::default(),
             diff_view: DiffViewState::default(),
-            review_content: String::new(),
+            review_content: Arc::default(),
             streaming_content: None,
             review_scroll: 0,
             generating: false,
             from_ref: "HEAD~1".to_string(),
             to_ref: "HEAD".to_string(),
+            risk: None,
+            findings: Vec::new(),
+            selected_finding: None,
+            sources: Vec::new(),
+            selected_source: None,
+            posting: false,
         }
     }
 }
@@ -206,6 +365,7 @@ impl std::fmt::Debug for ReviewState {
         f.debug_struct("ReviewState")
             .field("review_content_len", &self.review_content.len())
             .field("review_scroll", &self.review_scroll)
+            .field("findings", &self.findings.len())
             .field("generating", &self.generating)
             .finish_non_exhaustive()
     }
@@ -243,13 +403,38 @@ pub struct PrState {
     /// Diff view state
     pub diff_view: DiffViewState,
     /// Generated PR description (markdown)
-    pub pr_content: String,
+    pub pr_content: Arc<str>,
     /// Streaming content (while generating)
     pub streaming_content: Option<String>,
     /// PR content scroll offset
     pub pr_scroll: usize,
     /// Whether PR description is being generated
     pub generating: bool,
+    /// Whether a pull request is being opened on GitHub
+    pub opening: bool,
+    /// CI checks on the pushed head of the target branch, or why they
+    /// couldn't be read
+    pub ci: Option<Result<CiStatus, String>>,
+    /// Whether the CI status is being fetched
+    pub ci_loading: bool,
+}
+
+impl PrState {
+    /// Branch the pull request is opened from, resolving `HEAD` to the
+    /// checked out branch
+    pub fn head_branch(&self, current_branch: &str) -> Option<String> {
+        let head = if self.to_ref == "HEAD" {
+            current_branch
+        } else {
+            &self.to_ref
+        };
+        (!head.is_empty() && head != "HEAD detached").then(|| head.to_string())
+    }
+
+    /// CI status when it was read successfully
+    pub fn ci_status(&self) -> Option<&CiStatus> {
+        self.ci.as_ref().and_then(|ci| ci.as_ref().ok())
+    }
 }
 
 impl Default for PrState {
@@ -262,10 +447,13 @@ impl Default for PrState {
             commit_scroll: 0,
             file_tree: FileTreeState::new(),
             diff_view: DiffViewState::new(),
-            pr_content: String::new(),
+            pr_content: Arc::default(),
             streaming_content: None,
             pr_scroll: 0,
             generating: false,
+            opening: false,
+            ci: None,
+            ci_loading: false,
         }
     }
 }
@@ -313,7 +501,7 @@ pub struct ChangelogState {
     /// Diff view state
     pub diff_view: DiffViewState,
     /// Generated changelog content (markdown)
-    pub changelog_content: String,
+    pub changelog_content: Arc<str>,
     /// Streaming content (while generating)
     pub streaming_content: Option<String>,
     /// Changelog content scroll offset
@@ -331,7 +519,7 @@ impl Default for ChangelogState {
             selected_commit: 0,
             commit_scroll: 0,
             diff_view: DiffViewState::new(),
-            changelog_content: String::new(),
+            changelog_content: Arc::default(),
             streaming_content: None,
             changelog_scroll: 0,
             generating: false,
@@ -359,11 +547,13 @@ pub struct ReleaseNotesState {
     /// Diff view state
     pub diff_view: DiffViewState,
     /// Generated release notes content (markdown)
-    pub release_notes_content: String,
+    pub release_notes_content: Arc<str>,
     /// Streaming content (while generating)
     pub streaming_content: Option<String>,
     /// Release notes content scroll offset
     pub release_notes_scroll: usize,
+    /// Next version suggested by the range's commits
+    pub suggested_version: Option<SuggestedVersion>,
     /// Whether release notes are being generated
     pub generating: bool,
 }
@@ -377,9 +567,10 @@ impl Default for ReleaseNotesState {
             selected_commit: 0,
             commit_scroll: 0,
             diff_view: DiffViewState::new(),
-            release_notes_content: String::new(),
+            release_notes_content: Arc::default(),
             streaming_content: None,
             release_notes_scroll: 0,
+            suggested_version: None,
             generating: false,
         }
     }
@@ -398,4 +589,7 @@ pub struct ModeStates {
     pub pr: PrState,
     pub changelog: ChangelogState,
     pub release_notes: ReleaseNotesState,
+    pub dashboard: super::DashboardState,
+    pub stash: super::StashState,
+    pub rebase: super::RebaseState,
 }
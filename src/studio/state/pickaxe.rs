@@ -0,0 +1,132 @@
+//! Pickaxe search modal state for Iris Studio
+//!
+//! "When was this string introduced?" — `git log -S` (or `-G` for a regex)
+//! over the whole history, opened from Explore. A selected hit can be handed
+//! to the semantic blame agent to explain why that commit made the change.
+
+use crate::git::{PickaxeHit, PickaxeKind};
+
+/// State for the pickaxe search modal
+#[derive(Debug, Clone, Default)]
+pub struct PickaxeState {
+    /// String or regex being typed
+    pub query: String,
+    /// Whether to match a string (`-S`) or a regex (`-G`)
+    pub kind: PickaxeKind,
+    /// Query and kind the current results are for
+    pub searched: Option<(String, PickaxeKind)>,
+    /// Matching commits and files, newest first
+    pub results: Vec<PickaxeHit>,
+    /// Whether a search is running
+    pub loading: bool,
+    /// Why the last search failed
+    pub error: Option<String>,
+    /// Selected result index
+    pub selected: usize,
+}
+
+impl PickaxeState {
+    /// Start a search, pre-filled with `query`
+    pub fn new(query: String) -> Self {
+        Self {
+            query,
+            ..Self::default()
+        }
+    }
+
+    /// Whether the results are for what's in the search box now
+    pub fn is_current(&self) -> bool {
+        self.searched
+            .as_ref()
+            .is_some_and(|(query, kind)| *query == self.query && *kind == self.kind)
+    }
+
+    /// Mark a search for the current query as started
+    pub fn begin_search(&mut self) {
+        self.searched = Some((self.query.clone(), self.kind));
+        self.loading = true;
+        self.error = None;
+    }
+
+    /// Apply search results, unless the query moved on since they were asked for
+    pub fn set_results(
+        &mut self,
+        query: &str,
+        kind: PickaxeKind,
+        result: Result<Vec<PickaxeHit>, String>,
+    ) {
+        if self.searched.as_ref() != Some(&(query.to_string(), kind)) {
+            return;
+        }
+        self.loading = false;
+        self.selected = 0;
+        match result {
+            Ok(results) => self.results = results,
+            Err(e) => {
+                self.results.clear();
+                self.error = Some(e);
+            }
+        }
+    }
+
+    /// Append a character to the query
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+    }
+
+    /// Remove the last query character
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+    }
+
+    /// Switch between string and regex matching
+    pub fn toggle_kind(&mut self) {
+        self.kind = self.kind.toggle();
+    }
+
+    /// Move the selection down
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.results.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Move the selection up
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Currently selected hit
+    pub fn selected_hit(&self) -> Option<&PickaxeHit> {
+        self.results.get(self.selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_results_are_ignored() {
+        let mut state = PickaxeState::new("LEGACY".to_string());
+        assert!(!state.is_current());
+
+        state.begin_search();
+        assert!(state.is_current());
+        state.push_char('_');
+        assert!(!state.is_current());
+
+        // The answer for "LEGACY" arrives after a new search started
+        state.begin_search();
+        state.set_results("LEGACY", PickaxeKind::String, Err("old".to_string()));
+        assert!(state.loading);
+        assert!(state.error.is_none());
+
+        state.set_results("LEGACY_", PickaxeKind::String, Ok(Vec::new()));
+        assert!(!state.loading);
+        assert!(state.is_current());
+
+        state.toggle_kind();
+        assert!(!state.is_current());
+    }
+}
@@ -0,0 +1,284 @@
+//! Preset editor modal state for Iris Studio
+//!
+//! Creates or edits an instruction preset from the Settings modal. New
+//! presets, and edits of built-in ones, are saved to the user preset
+//! directory; a custom preset is saved back to the file it came from, so a
+//! repository preset isn't shadowed by a personal copy.
+
+use std::path::PathBuf;
+
+use super::SettingsState;
+use crate::instruction_presets::{
+    InstructionPreset, PresetType, is_valid_preset_key, user_presets_dir,
+};
+
+/// Emoji for a new preset
+const DEFAULT_EMOJI: &str = "✨";
+
+/// Field being edited in the preset editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresetEditorField {
+    Key,
+    Name,
+    Emoji,
+    Type,
+    Instructions,
+}
+
+impl PresetEditorField {
+    /// All fields in display order
+    pub const ALL: &'static [Self] = &[
+        Self::Key,
+        Self::Name,
+        Self::Emoji,
+        Self::Type,
+        Self::Instructions,
+    ];
+
+    /// Display label
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Key => "Key",
+            Self::Name => "Name",
+            Self::Emoji => "Emoji",
+            Self::Type => "Type",
+            Self::Instructions => "Instructions",
+        }
+    }
+}
+
+/// State for the preset editor modal
+#[derive(Debug, Clone)]
+pub struct PresetEditorState {
+    /// Settings modal to return to
+    pub settings: SettingsState,
+    /// Key of the preset being edited; `None` for a new one
+    pub editing: Option<String>,
+    /// File the edited preset was loaded from, if it's a custom preset
+    pub source: Option<PathBuf>,
+    /// Preset key, used as the file name and with `--preset`
+    pub key: String,
+    pub name: String,
+    pub emoji: String,
+    pub preset_type: PresetType,
+    /// Kept as-is; not editable here
+    pub description: String,
+    pub instructions: String,
+    /// Focused field
+    pub field: PresetEditorField,
+    /// Why the last save was refused
+    pub error: Option<String>,
+}
+
+impl PresetEditorState {
+    /// Start a new preset
+    pub fn new(settings: SettingsState) -> Self {
+        Self {
+            settings,
+            editing: None,
+            source: None,
+            key: String::new(),
+            name: String::new(),
+            emoji: DEFAULT_EMOJI.to_string(),
+            preset_type: PresetType::Both,
+            description: String::new(),
+            instructions: String::new(),
+            field: PresetEditorField::Key,
+            error: None,
+        }
+    }
+
+    /// Edit an existing preset, loaded from `source` if it's a custom one
+    pub fn edit(
+        settings: SettingsState,
+        key: &str,
+        preset: &InstructionPreset,
+        source: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            settings,
+            editing: Some(key.to_string()),
+            source,
+            key: key.to_string(),
+            name: preset.name.clone(),
+            emoji: preset.emoji.clone(),
+            preset_type: preset.preset_type,
+            description: preset.description.clone(),
+            instructions: preset.instructions.clone(),
+            field: PresetEditorField::Name,
+            error: None,
+        }
+    }
+
+    /// Fields that can be focused; an existing preset's key is fixed
+    pub fn fields(&self) -> &'static [PresetEditorField] {
+        if self.editing.is_some() {
+            &PresetEditorField::ALL[1..]
+        } else {
+            PresetEditorField::ALL
+        }
+    }
+
+    /// Focus the next field, wrapping around
+    pub fn next_field(&mut self) {
+        let fields = self.fields();
+        let idx = fields.iter().position(|f| *f == self.field).unwrap_or(0);
+        self.field = fields[(idx + 1) % fields.len()];
+    }
+
+    /// Focus the previous field, wrapping around
+    pub fn prev_field(&mut self) {
+        let fields = self.fields();
+        let idx = fields.iter().position(|f| *f == self.field).unwrap_or(0);
+        self.field = fields[(idx + fields.len() - 1) % fields.len()];
+    }
+
+    /// Text of the focused field, if it's a text field
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self.field {
+            PresetEditorField::Key => Some(&mut self.key),
+            PresetEditorField::Name => Some(&mut self.name),
+            PresetEditorField::Emoji => Some(&mut self.emoji),
+            PresetEditorField::Type => None,
+            PresetEditorField::Instructions => Some(&mut self.instructions),
+        }
+    }
+
+    /// Type a character into the focused field
+    pub fn push_char(&mut self, c: char) {
+        if self.field == PresetEditorField::Type {
+            if c == ' ' {
+                self.cycle_type(true);
+            }
+            return;
+        }
+        if let Some(text) = self.text_mut() {
+            text.push(c);
+            self.error = None;
+        }
+    }
+
+    /// Delete the last character of the focused field
+    pub fn pop_char(&mut self) {
+        if let Some(text) = self.text_mut() {
+            text.pop();
+            self.error = None;
+        }
+    }
+
+    /// Start a new line in the instructions
+    pub fn newline(&mut self) {
+        if self.field == PresetEditorField::Instructions {
+            self.instructions.push('\n');
+        }
+    }
+
+    /// Switch between commit, review and both
+    pub fn cycle_type(&mut self, forward: bool) {
+        const TYPES: [PresetType; 3] = [PresetType::Commit, PresetType::Review, PresetType::Both];
+        let idx = TYPES
+            .iter()
+            .position(|t| *t == self.preset_type)
+            .unwrap_or(2);
+        let next = if forward { idx + 1 } else { idx + 2 };
+        self.preset_type = TYPES[next % TYPES.len()];
+    }
+
+    /// Validated key and preset to save, or why they can't be saved
+    pub fn to_preset(&self) -> Result<(String, InstructionPreset), String> {
+        let key = self.key.trim();
+        if !is_valid_preset_key(key) {
+            return Err("Key must be letters, digits, '-' and '_'".to_string());
+        }
+        let instructions = self.instructions.trim();
+        if instructions.is_empty() {
+            return Err("Instructions can't be empty".to_string());
+        }
+        let name = self.name.trim();
+        let emoji = self.emoji.trim();
+        Ok((
+            key.to_string(),
+            InstructionPreset {
+                name: if name.is_empty() { key } else { name }.to_string(),
+                description: self.description.clone(),
+                instructions: instructions.to_string(),
+                emoji: if emoji.is_empty() {
+                    DEFAULT_EMOJI
+                } else {
+                    emoji
+                }
+                .to_string(),
+                preset_type: self.preset_type,
+            },
+        ))
+    }
+
+    /// Where the preset will be saved
+    pub fn save_path(&self, key: &str) -> Option<PathBuf> {
+        self.source
+            .clone()
+            .or_else(|| user_presets_dir().map(|dir| dir.join(format!("{key}.toml"))))
+    }
+
+    /// Return to the settings modal, selecting `saved` if a preset was saved
+    pub fn into_settings(self, saved: Option<String>) -> SettingsState {
+        let mut settings = self.settings;
+        if let Some(key) = saved {
+            settings.set_preset(key);
+        }
+        settings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_preset_editor_fields_and_validation() {
+        let settings = SettingsState::from_config(&Config::default());
+        let mut editor = PresetEditorState::new(settings.clone());
+        assert_eq!(editor.fields().len(), 5);
+
+        for c in "team style".chars() {
+            editor.push_char(c);
+        }
+        assert!(editor.to_preset().is_err());
+        editor.key = "team-style".to_string();
+        assert!(editor.to_preset().is_err(), "instructions are required");
+
+        editor.field = PresetEditorField::Type;
+        editor.push_char('x');
+        assert_eq!(editor.preset_type, PresetType::Both);
+        editor.push_char(' ');
+        assert_eq!(editor.preset_type, PresetType::Commit);
+        editor.cycle_type(false);
+        assert_eq!(editor.preset_type, PresetType::Both);
+
+        editor.field = PresetEditorField::Instructions;
+        for c in "Prefix with the ticket.".chars() {
+            editor.push_char(c);
+        }
+        editor.newline();
+        let (key, preset) = editor.to_preset().expect("valid preset");
+        assert_eq!(key, "team-style");
+        assert_eq!(preset.name, "team-style");
+        assert_eq!(preset.instructions, "Prefix with the ticket.");
+
+        let existing = InstructionPreset {
+            name: "Team".to_string(),
+            description: "Ours".to_string(),
+            instructions: "Be brief.".to_string(),
+            emoji: "🚀".to_string(),
+            preset_type: PresetType::Review,
+        };
+        let mut editor = PresetEditorState::edit(settings, "team", &existing, None);
+        assert_eq!(editor.field, PresetEditorField::Name);
+        editor.prev_field();
+        assert_eq!(editor.field, PresetEditorField::Instructions);
+        editor.next_field();
+        assert_eq!(editor.field, PresetEditorField::Name);
+        assert_eq!(editor.to_preset().expect("valid").1.description, "Ours");
+    }
+}
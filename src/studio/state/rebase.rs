@@ -0,0 +1,205 @@
+//! Rebase mode state for Iris Studio
+//!
+//! Holds the plan for rebasing the current branch: its commits in order and
+//! what to do with each. Iris suggests a plan, the user edits it step by
+//! step, and the finished plan runs through `git::rebase`.
+
+use crate::git::{RebaseAction, RebasePlan, RebaseStep};
+use crate::studio::components::{DiffViewState, parse_diff};
+
+/// State for Rebase mode
+#[derive(Debug)]
+pub struct RebaseState {
+    /// Branch or commit to rebase onto
+    pub base: String,
+    /// The plan, once loaded
+    pub plan: Option<RebasePlan>,
+    /// Why the plan couldn't be loaded
+    pub error: Option<String>,
+    /// Selected step
+    pub selected: usize,
+    /// Diff of the selected step's commit
+    pub diff_view: DiffViewState,
+    /// Id of the commit whose diff is shown
+    pub shown: Option<String>,
+    /// Whether Iris is writing a suggestion
+    pub suggesting: bool,
+    /// Scroll offset of the details panel
+    pub details_scroll: usize,
+}
+
+impl Default for RebaseState {
+    fn default() -> Self {
+        Self {
+            base: "main".to_string(),
+            plan: None,
+            error: None,
+            selected: 0,
+            diff_view: DiffViewState::default(),
+            shown: None,
+            suggesting: false,
+            details_scroll: 0,
+        }
+    }
+}
+
+impl RebaseState {
+    /// Replace the plan with a freshly loaded one, or record why loading failed
+    pub fn set_plan(&mut self, plan: Result<RebasePlan, String>) {
+        match plan {
+            Ok(plan) => {
+                self.selected = self.selected.min(plan.steps.len().saturating_sub(1));
+                self.plan = Some(plan);
+                self.error = None;
+            }
+            Err(error) => {
+                self.plan = None;
+                self.error = Some(error);
+                self.selected = 0;
+                self.show_diff(None, "");
+            }
+        }
+        self.details_scroll = 0;
+    }
+
+    /// Steps of the plan, empty until it's loaded
+    pub fn steps(&self) -> &[RebaseStep] {
+        self.plan.as_ref().map_or(&[], |plan| plan.steps.as_slice())
+    }
+
+    /// The selected step
+    pub fn selected_step(&self) -> Option<&RebaseStep> {
+        self.steps().get(self.selected)
+    }
+
+    /// Move the selection, clamped to the plan. Returns whether it moved.
+    pub fn move_selection(&mut self, delta: isize) -> bool {
+        let before = self.selected;
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.steps().len().saturating_sub(1));
+        self.details_scroll = 0;
+        self.selected != before
+    }
+
+    /// Move the selected step one place up or down, keeping it selected
+    pub fn move_step(&mut self, down: bool) -> bool {
+        let Some(plan) = &mut self.plan else {
+            return false;
+        };
+        match plan.move_step(self.selected, down) {
+            Some(index) => {
+                self.selected = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set the selected step's action
+    ///
+    /// A reword without a new subject is just a pick, so rewording goes
+    /// through [`Self::reword`] instead.
+    pub fn set_action(&mut self, action: RebaseAction) {
+        if let Some(step) = self.selected_step_mut() {
+            step.action = action;
+            if action != RebaseAction::Reword {
+                step.new_subject = None;
+            }
+        }
+    }
+
+    /// Give the selected step a new subject, or go back to picking it when
+    /// the subject is empty or unchanged
+    pub fn reword(&mut self, subject: &str) {
+        let Some(step) = self.selected_step_mut() else {
+            return;
+        };
+        let subject = subject.trim();
+        if subject.is_empty() || subject == step.commit.summary {
+            step.action = RebaseAction::Pick;
+            step.new_subject = None;
+        } else {
+            step.action = RebaseAction::Reword;
+            step.new_subject = Some(subject.to_string());
+        }
+    }
+
+    fn selected_step_mut(&mut self) -> Option<&mut RebaseStep> {
+        self.plan.as_mut()?.steps.get_mut(self.selected)
+    }
+
+    /// Whether the selected commit's diff still has to be loaded
+    pub fn needs_diff(&self) -> bool {
+        self.selected_step()
+            .is_some_and(|step| self.shown.as_ref() != Some(&step.commit.id))
+    }
+
+    /// Show `diff` as the changes of the commit with id `shown`
+    pub fn show_diff(&mut self, shown: Option<String>, diff: &str) {
+        self.diff_view.set_diffs(parse_diff(diff));
+        self.shown = shown;
+    }
+}
+
+/// State for the reword modal
+#[derive(Debug, Clone)]
+pub struct RebaseRewordState {
+    /// Short id of the commit being reworded, for the title
+    pub short_id: String,
+    /// New subject line
+    pub subject: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::RebaseCommit;
+
+    fn state() -> RebaseState {
+        let steps = (0..3)
+            .map(|position| RebaseStep {
+                commit: RebaseCommit {
+                    id: format!("{position}{}", "a".repeat(39)),
+                    position,
+                    summary: format!("commit {position}"),
+                    message: format!("commit {position}\n"),
+                    author: "Ada".to_string(),
+                    files: vec![],
+                },
+                action: RebaseAction::Pick,
+                new_subject: None,
+                rationale: None,
+            })
+            .collect();
+        let mut state = RebaseState::default();
+        state.set_plan(Ok(RebasePlan {
+            base: "main".to_string(),
+            onto: String::new(),
+            fork: String::new(),
+            branch: "feature".to_string(),
+            head: String::new(),
+            steps,
+        }));
+        state
+    }
+
+    #[test]
+    fn test_edit_plan() {
+        let mut state = state();
+        assert!(state.move_step(true));
+        assert_eq!(state.selected, 1);
+        assert_eq!(state.steps()[1].commit.position, 0);
+        assert!(state.move_selection(5));
+        assert_eq!(state.selected, 2);
+        assert!(!state.move_step(true));
+
+        state.reword("  feat: new subject ");
+        assert_eq!(state.steps()[2].subject(), "feat: new subject");
+        state.set_action(RebaseAction::Fixup);
+        assert_eq!(state.steps()[2].new_subject, None);
+        state.reword("commit 2");
+        assert_eq!(state.steps()[2].action, RebaseAction::Pick);
+    }
+}
@@ -0,0 +1,220 @@
+//! Review export modal state for Iris Studio
+//!
+//! Lists the review's findings as they'll be posted to the branch's open
+//! GitHub pull request. A finding becomes an inline comment when the lines
+//! it cites are in the reviewed diff; GitHub can't anchor comments anywhere
+//! else, so the rest go into the review's summary instead.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::integrations::github::ReviewComment;
+use crate::studio::components::diff_view::DiffLineType;
+use crate::studio::components::{DiffHunk, FileDiff};
+use crate::types::{FindingLocation, ReviewFinding, Severity};
+
+/// A finding as it will be posted
+#[derive(Debug, Clone)]
+pub struct ExportedFinding {
+    pub severity: Severity,
+    pub title: String,
+    /// Where the finding points, as the review cites it
+    pub cited: Option<String>,
+    /// Inline comment, or `None` when it goes into the summary
+    pub comment: Option<ReviewComment>,
+    /// Explanation, as markdown
+    pub body: String,
+}
+
+/// State for the review export modal
+#[derive(Debug, Clone, Default)]
+pub struct ReviewExportState {
+    /// Branch whose open pull request gets the review
+    pub head: String,
+    /// Revision the line numbers refer to
+    pub to_ref: String,
+    /// Reviewed range, for the summary
+    pub range: String,
+    pub findings: Vec<ExportedFinding>,
+    /// Scroll offset of the list
+    pub scroll: usize,
+}
+
+impl ReviewExportState {
+    /// Plan the export of `findings` against the reviewed `diffs`
+    pub fn new(
+        findings: &[ReviewFinding],
+        diffs: &[FileDiff],
+        head: String,
+        from_ref: &str,
+        to_ref: &str,
+    ) -> Self {
+        let findings = findings
+            .iter()
+            .map(|finding| {
+                let body = if finding.body.is_empty() {
+                    format!("**[{}] {}**", finding.severity.label(), finding.title)
+                } else {
+                    format!(
+                        "**[{}] {}**\n\n{}",
+                        finding.severity.label(),
+                        finding.title,
+                        finding.body
+                    )
+                };
+                let comment = finding
+                    .location
+                    .as_ref()
+                    .and_then(|location| anchor(location, diffs))
+                    .map(|(path, start, end)| ReviewComment::new(path, start, end, body));
+                ExportedFinding {
+                    severity: finding.severity,
+                    title: finding.title.clone(),
+                    cited: finding
+                        .location
+                        .as_ref()
+                        .map(|l| format!("{}:{}", l.path, l.start_line)),
+                    comment,
+                    body: finding.body.clone(),
+                }
+            })
+            .collect();
+        Self {
+            head,
+            to_ref: to_ref.to_string(),
+            range: format!("{from_ref}..{to_ref}"),
+            findings,
+            scroll: 0,
+        }
+    }
+
+    /// Inline comments to post
+    pub fn comments(&self) -> Vec<ReviewComment> {
+        self.findings
+            .iter()
+            .filter_map(|finding| finding.comment.clone())
+            .collect()
+    }
+
+    /// Number of findings posted inline
+    pub fn inline_count(&self) -> usize {
+        self.findings.iter().filter(|f| f.comment.is_some()).count()
+    }
+
+    /// Review summary: what was reviewed, then the findings that couldn't be
+    /// anchored to the diff
+    pub fn summary(&self) -> String {
+        let inline = self.inline_count();
+        let mut body = format!(
+            "Iris reviewed `{}`: {inline} inline comment{}.",
+            self.range,
+            if inline == 1 { "" } else { "s" }
+        );
+        let mut outside = self.findings.iter().filter(|f| f.comment.is_none());
+        if let Some(first) = outside.next() {
+            body.push_str("\n\n### Findings outside the diff");
+            for finding in std::iter::once(first).chain(outside) {
+                let _ = write!(
+                    body,
+                    "\n\n**[{}] {}**",
+                    finding.severity.label(),
+                    finding.title
+                );
+                if let Some(cited) = &finding.cited {
+                    let _ = write!(body, " (`{cited}`)");
+                }
+                if !finding.body.is_empty() {
+                    let _ = write!(body, "\n\n{}", finding.body);
+                }
+            }
+        }
+        body
+    }
+
+    /// Scroll the list down
+    pub fn scroll_down(&mut self) {
+        if self.scroll + 1 < self.findings.len() {
+            self.scroll += 1;
+        }
+    }
+
+    /// Scroll the list up
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
+
+/// The diff path and new-side lines a location can be commented on
+///
+/// The whole cited range is used when one hunk contains it, otherwise
+/// whichever end of it is in the diff.
+fn anchor(location: &FindingLocation, diffs: &[FileDiff]) -> Option<(String, usize, usize)> {
+    // Reviews sometimes cite just the file name
+    let cited = Path::new(&location.path);
+    let diff = diffs
+        .iter()
+        .find(|diff| diff.path == cited)
+        .or_else(|| diffs.iter().find(|diff| diff.path.ends_with(cited)))?;
+
+    let hunk_lines = |hunk: &DiffHunk| -> Vec<usize> {
+        hunk.lines
+            .iter()
+            .filter(|line| matches!(line.line_type, DiffLineType::Added | DiffLineType::Context))
+            .filter_map(|line| line.new_line_num)
+            .collect()
+    };
+    let hunks: Vec<Vec<usize>> = diff.hunks.iter().map(hunk_lines).collect();
+    let (start, end) = (location.start_line, location.end_line);
+    let (start, end) = if hunks
+        .iter()
+        .any(|lines| lines.contains(&start) && lines.contains(&end))
+    {
+        (start, end)
+    } else if hunks.iter().any(|lines| lines.contains(&end)) {
+        (end, end)
+    } else if hunks.iter().any(|lines| lines.contains(&start)) {
+        (start, start)
+    } else {
+        return None;
+    };
+    Some((diff.path.to_string_lossy().into_owned(), start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::studio::components::parse_diff;
+    use crate::types::parse_findings;
+
+    #[test]
+    fn test_findings_outside_the_diff_go_into_the_summary() {
+        let diffs = parse_diff(
+            "diff --git a/src/auth.rs b/src/auth.rs\n\
+             --- a/src/auth.rs\n\
+             +++ b/src/auth.rs\n\
+             @@ -10,3 +10,4 @@\n \
+             fn login() {\n\
+             +    let token = read();\n \
+             check();\n \
+             }\n",
+        );
+        let findings = parse_findings(
+            "## Findings\n\
+             - **[HIGH]** Token is never validated in `auth.rs:11-12`\n  Validate it.\n\
+             - **[LOW]** Old helper in `src/util.rs:40`\n",
+        );
+
+        let export =
+            ReviewExportState::new(&findings, &diffs, "feat/auth".to_string(), "main", "HEAD");
+        let comments = export.comments();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].path, "src/auth.rs");
+        assert_eq!((comments[0].start_line, comments[0].line), (Some(11), 12));
+        assert!(comments[0].body.contains("Validate it."));
+
+        let summary = export.summary();
+        assert!(summary.starts_with("Iris reviewed `main..HEAD`: 1 inline comment."));
+        assert!(summary.contains("**[LOW] Old helper in src/util.rs:40** (`src/util.rs:40`)"));
+        assert!(!summary.contains("Token is never validated"));
+    }
+}
@@ -0,0 +1,170 @@
+//! Semantic code search state for Iris Studio
+//!
+//! "Where do we rotate API keys?" — a plain-language question answered by the
+//! agent with its code search and file reading tools. The answer replaces the
+//! Explore file tree with a list of places to jump to until it's dismissed.
+
+use std::path::PathBuf;
+
+/// A place in the code that answers the question
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    /// Path relative to the repository root
+    pub path: PathBuf,
+    /// First line (1-based), if the agent gave one
+    pub line: Option<usize>,
+    /// Last line of a range
+    pub end_line: Option<usize>,
+    /// Why this place answers the question
+    pub reason: String,
+}
+
+impl SearchResult {
+    /// `path:line` or `path:start-end`
+    pub fn location(&self) -> String {
+        let path = self.path.display();
+        match (self.line, self.end_line) {
+            (Some(start), Some(end)) => format!("{path}:{start}-{end}"),
+            (Some(line), None) => format!("{path}:{line}"),
+            _ => path.to_string(),
+        }
+    }
+}
+
+/// A question and the places Iris found for it
+#[derive(Debug, Clone, Default)]
+pub struct SemanticSearchState {
+    pub query: String,
+    /// Places found, most relevant first
+    pub results: Vec<SearchResult>,
+    /// Whether Iris is still searching
+    pub loading: bool,
+    /// Why the search failed
+    pub error: Option<String>,
+    /// Iris's note when nothing answered the question
+    pub note: Option<String>,
+    /// Selected result index
+    pub selected: usize,
+    /// Scroll offset for long lists
+    pub scroll: usize,
+}
+
+impl SemanticSearchState {
+    /// Start searching for `query`
+    pub fn new(query: String) -> Self {
+        Self {
+            query,
+            loading: true,
+            ..Self::default()
+        }
+    }
+
+    /// Apply Iris's answer
+    pub fn set_results(&mut self, result: Result<String, String>) {
+        self.loading = false;
+        self.selected = 0;
+        self.scroll = 0;
+        match result {
+            Ok(answer) => (self.results, self.note) = parse_search_results(&answer),
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// Move the selection down, keeping it within `visible` rows
+    pub fn select_next(&mut self, visible: usize) {
+        if self.selected + 1 < self.results.len() {
+            self.selected += 1;
+            if self.selected >= self.scroll + visible {
+                self.scroll = self.selected + 1 - visible;
+            }
+        }
+    }
+
+    /// Move the selection up
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        }
+    }
+
+    /// Currently selected result
+    pub fn selected_result(&self) -> Option<&SearchResult> {
+        self.results.get(self.selected)
+    }
+}
+
+/// Parse the agent's `path:line - reason` lines; a `#` line is kept as the
+/// note for an empty answer
+pub fn parse_search_results(text: &str) -> (Vec<SearchResult>, Option<String>) {
+    let mut results = Vec::new();
+    let mut note = None;
+
+    for line in text.lines().map(str::trim) {
+        if let Some(comment) = line.strip_prefix('#') {
+            note.get_or_insert_with(|| comment.trim().to_string());
+            continue;
+        }
+        let line = line.trim_start_matches(['-', '*', ' ']).trim_matches('`');
+        let (location, reason) = line
+            .split_once(" - ")
+            .or_else(|| line.split_once(" — "))
+            .unwrap_or((line, ""));
+        let location = location.trim().trim_matches('`');
+        if location.is_empty() || location.contains(char::is_whitespace) {
+            continue;
+        }
+
+        let (path, lines) = match location.rsplit_once(':') {
+            Some((path, lines)) if lines.starts_with(|c: char| c.is_ascii_digit()) => {
+                (path, Some(lines))
+            }
+            _ => (location, None),
+        };
+        // Prose like "Summary:" isn't a path
+        if !path.contains(['/', '.']) {
+            continue;
+        }
+        let (start, end) = match lines.map(|l| l.split_once('-').unwrap_or((l, ""))) {
+            Some((start, end)) => (start.parse().ok(), end.parse().ok()),
+            None => (None, None),
+        };
+        results.push(SearchResult {
+            path: PathBuf::from(path.trim_start_matches("./")),
+            line: start,
+            end_line: end.filter(|end| start.is_some_and(|start| *end > start)),
+            reason: reason.trim().to_string(),
+        });
+    }
+
+    (results, note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_results() {
+        let (results, note) = parse_search_results(
+            "src/auth/keys.rs:142-180 - rotate_api_key() swaps the key\n\
+             - `src/jobs/schedule.rs:58` - nightly job\n\
+             README.md - documents rotation\n\
+             Here is what I found:\n\
+             Summary:\n",
+        );
+        assert!(note.is_none());
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].path, PathBuf::from("src/auth/keys.rs"));
+        assert_eq!(results[0].line, Some(142));
+        assert_eq!(results[0].end_line, Some(180));
+        assert_eq!(results[0].location(), "src/auth/keys.rs:142-180");
+        assert_eq!(results[1].line, Some(58));
+        assert_eq!(results[1].reason, "nightly job");
+        assert_eq!(results[2].line, None);
+
+        let (results, note) = parse_search_results("# Nothing here rotates keys\n");
+        assert!(results.is_empty());
+        assert_eq!(note.as_deref(), Some("Nothing here rotates keys"));
+    }
+}
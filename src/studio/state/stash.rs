@@ -0,0 +1,159 @@
+//! Stash mode state for Iris Studio
+//!
+//! Lists the repository's stashes and previews the selected one's changes.
+//! New stashes are named in a modal that Iris pre-fills with a description
+//! of the work being put away.
+
+use std::path::PathBuf;
+
+use crate::git::StashEntry;
+use crate::studio::components::{DiffViewState, FileGitStatus, FileTreeState, parse_diff};
+
+/// State for Stash mode
+#[derive(Debug, Default)]
+pub struct StashState {
+    /// Stashes, newest first
+    pub entries: Vec<StashEntry>,
+    /// Whether the list has been read since the mode was opened
+    pub loaded: bool,
+    /// Selected index in the stash list
+    pub selected: usize,
+    /// Files changed by the selected stash
+    pub file_tree: FileTreeState,
+    /// Diff of the selected stash
+    pub diff_view: DiffViewState,
+    /// Id of the stash whose diff is shown
+    pub shown: Option<String>,
+}
+
+impl StashState {
+    /// The selected stash
+    pub fn selected_entry(&self) -> Option<&StashEntry> {
+        self.entries.get(self.selected)
+    }
+
+    /// Replace the list, keeping the selection on the same stash when it
+    /// is still there
+    pub fn set_entries(&mut self, entries: Vec<StashEntry>) {
+        let selected_id = self.selected_entry().map(|entry| entry.id.clone());
+        self.entries = entries;
+        self.loaded = true;
+        self.selected = selected_id
+            .and_then(|id| self.entries.iter().position(|entry| entry.id == id))
+            .unwrap_or(self.selected)
+            .min(self.entries.len().saturating_sub(1));
+        if self.entries.is_empty() {
+            self.show_diff(None, "");
+        }
+    }
+
+    /// Move the selection, clamped to the list. Returns whether it moved.
+    pub fn move_selection(&mut self, delta: isize) -> bool {
+        let before = self.selected;
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.entries.len().saturating_sub(1));
+        self.selected != before
+    }
+
+    /// Whether the selected stash's diff still has to be loaded
+    pub fn needs_diff(&self) -> bool {
+        self.selected_entry()
+            .is_some_and(|entry| self.shown.as_ref() != Some(&entry.id))
+    }
+
+    /// Show `diff` as the changes of the stash with id `shown`
+    pub fn show_diff(&mut self, shown: Option<String>, diff: &str) {
+        let diffs = parse_diff(diff);
+        let files: Vec<PathBuf> = diffs.iter().map(|d| d.path.clone()).collect();
+        let statuses: Vec<_> = files
+            .iter()
+            .map(|p| (p.clone(), FileGitStatus::Modified))
+            .collect();
+        self.diff_view.set_diffs(diffs);
+        self.file_tree = FileTreeState::from_paths(&files, &statuses);
+        self.file_tree.expand_all();
+        self.shown = shown;
+    }
+}
+
+/// State for the new stash modal
+#[derive(Debug, Clone, Default)]
+pub struct StashCreateState {
+    /// Stash message; git's `WIP on <branch>` is used when empty
+    pub message: String,
+    /// Also stash untracked files
+    pub include_untracked: bool,
+    /// Whether Iris is writing a description
+    pub describing: bool,
+    /// Whether the user has typed, so a late description doesn't replace it
+    pub edited: bool,
+}
+
+impl StashCreateState {
+    pub fn new(include_untracked: bool) -> Self {
+        Self {
+            include_untracked,
+            describing: true,
+            ..Self::default()
+        }
+    }
+
+    /// Take Iris's description unless the user has written their own
+    pub fn set_description(&mut self, description: &str) {
+        self.describing = false;
+        if !self.edited {
+            self.message = description.trim().to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn entry(index: usize, id: &str) -> StashEntry {
+        StashEntry {
+            index,
+            id: id.to_string(),
+            message: format!("On main: stash {id}"),
+            time: Utc::now(),
+            has_untracked: false,
+        }
+    }
+
+    #[test]
+    fn test_selection_follows_stash_across_reloads() {
+        let mut state = StashState::default();
+        state.set_entries(vec![entry(0, "b"), entry(1, "a")]);
+        state.move_selection(1);
+        state.shown = Some("a".to_string());
+
+        // A new stash pushes the selected one down the list
+        state.set_entries(vec![entry(0, "c"), entry(1, "b"), entry(2, "a")]);
+        assert_eq!(state.selected, 2);
+        assert!(!state.needs_diff());
+
+        // Dropping it moves the selection to the stash that took its place
+        state.set_entries(vec![entry(0, "c"), entry(1, "b")]);
+        assert_eq!(state.selected, 1);
+        assert!(state.needs_diff());
+        assert!(!state.move_selection(5));
+    }
+
+    #[test]
+    fn test_description_does_not_replace_typed_message() {
+        let mut create = StashCreateState::new(false);
+        create.set_description("  wip retry backoff\n");
+        assert_eq!(create.message, "wip retry backoff");
+
+        let mut create = StashCreateState::new(false);
+        create.message = "mine".to_string();
+        create.edited = true;
+        create.set_description("wip retry backoff");
+        assert_eq!(create.message, "mine");
+        assert!(!create.describing);
+    }
+}
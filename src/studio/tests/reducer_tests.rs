@@ -249,3 +249,33 @@ fn test_agent_error_clears_generating_flag() {
     // Should have a notification
     assert!(!state.notifications.is_empty());
 }
+
+#[test]
+fn test_cycle_output_style_per_mode() {
+    use crate::config::{Tone, Verbosity};
+
+    let mut state = test_state();
+    state.active_mode = Mode::PR;
+    assert_eq!(
+        state.output_style(),
+        Some((Verbosity::Standard, Tone::Neutral))
+    );
+
+    assert!(state.cycle_verbosity());
+    assert!(state.cycle_tone());
+    assert!(state.cycle_tone());
+    assert_eq!(state.output_style(), Some((Verbosity::Detailed, Tone::Formal)));
+    assert!(state.cycle_verbosity());
+    assert_eq!(state.output_style(), Some((Verbosity::Concise, Tone::Formal)));
+
+    // Other modes keep their own style, and the saved config is untouched
+    state.active_mode = Mode::Commit;
+    assert_eq!(
+        state.output_style(),
+        Some((Verbosity::Standard, Tone::Neutral))
+    );
+    assert!(state.config.generation.is_empty());
+
+    state.active_mode = Mode::Dashboard;
+    assert!(!state.cycle_verbosity());
+}
@@ -5,7 +5,7 @@
 //! This module wraps the centralized token-based theme system,
 //! providing access to colors and styles through the theme API.
 
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 
 use crate::theme;
 use crate::theme::adapters::ratatui::{ToRatatuiColor, ToRatatuiStyle};
@@ -132,6 +132,27 @@ pub fn diff_context() -> Style {
     theme::current().style("diff_context").to_ratatui()
 }
 
+/// Style for the changed words within an added line
+pub fn diff_added_word() -> Style {
+    diff_word("diff_added_word", diff_added())
+}
+
+/// Style for the changed words within a removed line
+pub fn diff_removed_word() -> Style {
+    diff_word("diff_removed_word", diff_removed())
+}
+
+/// Custom themes written before word highlighting get the line style, bold
+/// on the selection background
+fn diff_word(name: &str, line: Style) -> Style {
+    let current = theme::current();
+    if current.has_style(name) {
+        current.style(name).to_ratatui()
+    } else {
+        line.bg(bg_selection_color()).add_modifier(Modifier::BOLD)
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Color Accessors
 // ═══════════════════════════════════════════════════════════════════════════════
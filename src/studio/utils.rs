@@ -1,8 +1,15 @@
 //! Utility functions for Iris Studio
 //!
 //! Common utilities used across the TUI, including string truncation.
+//!
+//! Truncation works on grapheme clusters, so an emoji with a skin tone or a
+//! flag is kept or dropped whole, and measures display width so CJK and
+//! emoji take the two columns a terminal gives them.
 
-use unicode_width::UnicodeWidthStr;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Tab Expansion
@@ -27,7 +34,7 @@ pub fn expand_tabs(s: &str, tab_width: usize) -> String {
         } else if !ch.is_control() {
             // Non-control character: add to result
             result.push(ch);
-            column += ch.to_string().width();
+            column += ch.width().unwrap_or(0);
         }
         // Control characters (except tab) are silently stripped
     }
@@ -39,7 +46,8 @@ pub fn expand_tabs(s: &str, tab_width: usize) -> String {
 // String Truncation Utilities
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// Truncate a string to a maximum character count, adding "..." if truncated.
+/// Truncate a string to a maximum count of characters as users see them
+/// (grapheme clusters), adding "..." if truncated.
 ///
 /// This is useful for simple text truncation where unicode display width
 /// isn't critical (e.g., log previews, notifications).
@@ -54,13 +62,16 @@ pub fn truncate_chars(s: &str, max_chars: usize) -> String {
         return String::new();
     }
 
-    let char_count = s.chars().count();
-    if char_count <= max_chars {
+    let count = s.graphemes(true).count();
+    if count <= max_chars {
         s.to_string()
     } else if max_chars <= 3 {
-        s.chars().take(max_chars).collect()
+        s.graphemes(true).take(max_chars).collect()
     } else {
-        format!("{}...", s.chars().take(max_chars - 3).collect::<String>())
+        format!(
+            "{}...",
+            s.graphemes(true).take(max_chars - 3).collect::<String>()
+        )
     }
 }
 
@@ -89,22 +100,98 @@ pub fn truncate_width(s: &str, max_width: usize) -> String {
     }
 
     // Reserve space for ellipsis (width = 1 for "…")
-    let target_width = max_width - 1;
-
-    let mut result = String::new();
-    let mut current_width = 0;
+    let (head, _) = split_at_width(s, max_width - 1);
+    format!("{head}…")
+}
 
-    for ch in s.chars() {
-        let ch_width = ch.to_string().width();
-        if current_width + ch_width > target_width {
-            break;
+/// Split a string after the longest run of whole graphemes that fits in
+/// `max_width` columns.
+///
+/// The head is never empty while `s` isn't, even if its first grapheme is
+/// wider than `max_width`, so wrapping loops always make progress.
+pub fn split_at_width(s: &str, max_width: usize) -> (&str, &str) {
+    let mut width = 0;
+    for (index, grapheme) in s.grapheme_indices(true) {
+        width += grapheme.width();
+        if width > max_width && index > 0 {
+            return s.split_at(index);
         }
-        result.push(ch);
-        current_width += ch_width;
     }
+    (s, "")
+}
 
-    result.push('…');
-    result
+/// Pad a string with spaces to `width` display columns.
+///
+/// Unlike `format!("{:<width$}")`, which counts chars, this keeps columns
+/// aligned when the text has emoji or CJK in it.
+pub fn pad_width(s: &str, width: usize) -> String {
+    format!("{s}{}", " ".repeat(width.saturating_sub(s.width())))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// File References
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// File stems that name their directory's module rather than themselves
+const MODULE_INDEX_STEMS: &[&str] = &["mod", "lib", "main", "index", "__init__"];
+
+/// Paths from `candidates` that `text` mentions, in `candidates` order.
+///
+/// A path counts as mentioned by its path (or any trailing part of it, like
+/// `app/mod.rs`), its file name, or its module name: the file stem, or the
+/// directory name for `mod.rs`-style files, so `studio::app` mentions
+/// `src/studio/app/mod.rs`. Matching ignores case.
+pub fn referenced_paths(text: &str, candidates: &[PathBuf]) -> Vec<PathBuf> {
+    let text = text.to_lowercase();
+    let tokens: HashSet<&str> = text
+        .split(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/')))
+        .map(|token| token.trim_matches(['.', '/']))
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    candidates
+        .iter()
+        .filter(|path| {
+            let path_str = path.to_string_lossy().to_lowercase();
+            let path_str = path_str.trim_end_matches('/');
+            tokens.iter().any(|token| {
+                path_str == *token
+                    || (token.contains(['/', '.']) && path_str.ends_with(&format!("/{token}")))
+                    || module_name(path).is_some_and(|name| name == *token)
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Lowercased module name of `path`, if it has a meaningful one
+fn module_name(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let name = if MODULE_INDEX_STEMS.contains(&stem) {
+        path.parent()?.file_name()?.to_str()?
+    } else {
+        stem
+    };
+    // Very short names (`a`, `io`) match too much prose
+    (name.chars().count() >= 3).then(|| name.to_lowercase())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Time Formatting
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Compact age of a timestamp, such as `3d ago`
+pub fn time_ago(at: chrono::DateTime<chrono::Utc>) -> String {
+    let elapsed = chrono::Utc::now() - at;
+    if elapsed.num_days() > 0 {
+        format!("{}d ago", elapsed.num_days())
+    } else if elapsed.num_hours() > 0 {
+        format!("{}h ago", elapsed.num_hours())
+    } else if elapsed.num_minutes() > 0 {
+        format!("{}m ago", elapsed.num_minutes())
+    } else {
+        "just now".to_string()
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -162,4 +249,56 @@ mod tests {
         let result = truncate_width(cjk, 6);
         assert!(result.width() <= 6);
     }
+
+    #[test]
+    fn test_truncation_keeps_graphemes_whole() {
+        // Family emoji is one grapheme of five chars, two columns wide
+        let family = "👨‍👩‍👧";
+        assert_eq!(truncate_chars(&format!("{family}{family}abc"), 4), "👨‍👩‍👧...");
+        assert_eq!(truncate_width("🇯🇵 flags", 3), "🇯🇵…");
+        assert_eq!(truncate_width("日本語のコミット", 7), "日本語…");
+
+        assert_eq!(split_at_width("ab日本", 3), ("ab", "日本"));
+        assert_eq!(split_at_width("日本", 1), ("日", "本"));
+        assert_eq!(split_at_width("", 4), ("", ""));
+        assert_eq!(pad_width("✨ feat", 9), "✨ feat  ");
+        assert_eq!(pad_width("long text", 4), "long text");
+    }
+
+    #[test]
+    fn test_referenced_paths() {
+        let candidates: Vec<PathBuf> = [
+            "src/agents/iris.rs",
+            "src/studio/app/mod.rs",
+            "src/studio/state/modes.rs",
+            "src/io.rs",
+            "README.md",
+            "docs/",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+
+        let found = referenced_paths(
+            "Rework studio::app event loop\n\nAlso touches Iris.rs, readme.md, io and the modes helpers.",
+            &candidates,
+        );
+        assert_eq!(
+            found,
+            [
+                "src/agents/iris.rs",
+                "src/studio/app/mod.rs",
+                "src/studio/state/modes.rs",
+                "README.md",
+            ]
+            .map(PathBuf::from)
+        );
+
+        let found = referenced_paths("Update state/modes.rs and docs/", &candidates);
+        assert_eq!(
+            found,
+            ["src/studio/state/modes.rs", "docs/"].map(PathBuf::from)
+        );
+        assert!(referenced_paths("Fix typo", &candidates).is_empty());
+    }
 }
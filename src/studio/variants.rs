@@ -0,0 +1,100 @@
+//! Commit message variants
+//!
+//! With `variant_count` above 1, Studio asks for several commit messages at
+//! once. The first variants use `variant_presets` in order; the rest keep the
+//! current preset and step through a temperature ladder, so `n`/`p` cycles
+//! through genuinely different takes.
+
+use crate::config::MAX_VARIANTS;
+
+/// Temperatures for variants beyond the configured presets; the first keeps
+/// the configured temperature
+const TEMPERATURE_LADDER: &[f64] = &[0.3, 1.0, 0.6, 1.2];
+
+/// How one variant is generated
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitVariant {
+    /// Instruction preset
+    pub preset: String,
+    /// Temperature override; `None` keeps the configured one
+    pub temperature: Option<f64>,
+}
+
+impl CommitVariant {
+    /// Label shown next to the message counter
+    pub fn label(&self) -> String {
+        match self.temperature {
+            Some(temperature) => format!("{} · temp {temperature:.1}", self.preset),
+            None => self.preset.clone(),
+        }
+    }
+}
+
+/// Variants to generate for `count`, starting from the current `preset`
+pub fn plan(count: usize, presets: &[String], preset: &str) -> Vec<CommitVariant> {
+    let count = count.clamp(1, MAX_VARIANTS);
+    let mut variants: Vec<CommitVariant> = presets
+        .iter()
+        .filter(|p| !p.trim().is_empty())
+        .take(count)
+        .map(|p| CommitVariant {
+            preset: p.trim().to_string(),
+            temperature: None,
+        })
+        .collect();
+
+    let from_presets = variants.len();
+    for i in from_presets..count {
+        // Without presets, the first variant is the one a single generation
+        // would produce
+        let rung = if from_presets == 0 {
+            i.checked_sub(1)
+        } else {
+            Some(i - from_presets)
+        };
+        variants.push(CommitVariant {
+            preset: preset.to_string(),
+            temperature: rung.map(|r| TEMPERATURE_LADDER[r % TEMPERATURE_LADDER.len()]),
+        });
+    }
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_uses_presets_then_temperatures() {
+        assert_eq!(
+            plan(1, &[], "default"),
+            [CommitVariant {
+                preset: "default".to_string(),
+                temperature: None,
+            }]
+        );
+
+        let labels: Vec<String> = plan(3, &[], "conventional")
+            .iter()
+            .map(CommitVariant::label)
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "conventional",
+                "conventional · temp 0.3",
+                "conventional · temp 1.0"
+            ]
+        );
+
+        let presets = vec!["concise".to_string(), "detailed".to_string()];
+        let labels: Vec<String> = plan(3, &presets, "default")
+            .iter()
+            .map(CommitVariant::label)
+            .collect();
+        assert_eq!(labels, ["concise", "detailed", "default · temp 0.3"]);
+
+        assert_eq!(plan(99, &[], "default").len(), MAX_VARIANTS);
+        assert_eq!(plan(0, &presets, "default").len(), 1);
+    }
+}
@@ -0,0 +1,147 @@
+//! Export a resolved theme for external tools.
+//!
+//! `git-iris theme export` prints the active theme's palette, tokens, styles
+//! and gradients with every reference resolved to a hex color, so dotfiles
+//! for fzf, bat or a shell prompt can be generated to match git-iris.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use clap::ValueEnum;
+use serde_json::{Value, json};
+
+use super::{Theme, ThemeColor, ThemeVariant};
+
+/// Output format for `git-iris theme export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ExportFormat {
+    /// JSON object with palette, tokens, styles and gradients
+    #[default]
+    Json,
+    /// CSS custom properties on `:root`
+    Css,
+    /// Shell variable assignments, for `eval` or `source`
+    Shell,
+}
+
+/// Render `theme` in the given format.
+#[must_use]
+pub fn export(theme: &Theme, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => {
+            let mut out = serde_json::to_string_pretty(&to_json(theme)).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        ExportFormat::Css => to_css(theme),
+        ExportFormat::Shell => to_shell(theme),
+    }
+}
+
+fn variant_name(variant: ThemeVariant) -> &'static str {
+    match variant {
+        ThemeVariant::Dark => "dark",
+        ThemeVariant::Light => "light",
+    }
+}
+
+/// Colors sorted by name, for stable output.
+fn sorted(colors: &std::collections::HashMap<String, ThemeColor>) -> BTreeMap<&str, String> {
+    colors
+        .iter()
+        .map(|(name, color)| (name.as_str(), color.to_hex()))
+        .collect()
+}
+
+fn gradients(theme: &Theme) -> BTreeMap<&str, Vec<String>> {
+    theme
+        .gradients
+        .iter()
+        .map(|(name, gradient)| {
+            let stops = gradient.stops().iter().map(ThemeColor::to_hex).collect();
+            (name.as_str(), stops)
+        })
+        .collect()
+}
+
+fn to_json(theme: &Theme) -> Value {
+    let styles: BTreeMap<&str, Value> = theme
+        .styles
+        .iter()
+        .map(|(name, style)| {
+            let style = json!({
+                "fg": style.fg.map(|c| c.to_hex()),
+                "bg": style.bg.map(|c| c.to_hex()),
+                "bold": style.bold,
+                "italic": style.italic,
+                "underline": style.underline,
+                "dim": style.dim,
+            });
+            (name.as_str(), style)
+        })
+        .collect();
+    json!({
+        "name": theme.meta.name,
+        "variant": variant_name(theme.meta.variant),
+        "palette": sorted(&theme.palette),
+        "tokens": sorted(&theme.tokens),
+        "styles": styles,
+        "gradients": gradients(theme),
+    })
+}
+
+/// `accent.primary` as a variable name part: `accent-primary`.
+fn kebab(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn to_css(theme: &Theme) -> String {
+    let mut out = format!(
+        "/* {} ({}) */\n:root {{\n",
+        theme.meta.name,
+        variant_name(theme.meta.variant)
+    );
+    for (name, hex) in sorted(&theme.palette) {
+        let _ = writeln!(out, "  --iris-palette-{}: {hex};", kebab(name));
+    }
+    for (name, hex) in sorted(&theme.tokens) {
+        let _ = writeln!(out, "  --iris-{}: {hex};", kebab(name));
+    }
+    for (name, stops) in gradients(theme) {
+        let _ = writeln!(
+            out,
+            "  --iris-gradient-{}: linear-gradient(90deg, {});",
+            kebab(name),
+            stops.join(", ")
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn to_shell(theme: &Theme) -> String {
+    let shout = |name: &str| kebab(name).replace('-', "_").to_uppercase();
+    let mut out = format!(
+        "IRIS_THEME_NAME='{}'\nIRIS_THEME_VARIANT='{}'\n",
+        theme.meta.name.replace('\'', r"'\''"),
+        variant_name(theme.meta.variant)
+    );
+    for (name, hex) in sorted(&theme.palette) {
+        let _ = writeln!(out, "IRIS_PALETTE_{}='{hex}'", shout(name));
+    }
+    for (name, hex) in sorted(&theme.tokens) {
+        let _ = writeln!(out, "IRIS_{}='{hex}'", shout(name));
+    }
+    for (name, stops) in gradients(theme) {
+        let _ = writeln!(out, "IRIS_GRADIENT_{}='{}'", shout(name), stops.join(" "));
+    }
+    out
+}
@@ -46,6 +46,12 @@ impl Gradient {
         }
     }
 
+    /// Get the color stops of this gradient.
+    #[must_use]
+    pub fn stops(&self) -> &[ThemeColor] {
+        &self.stops
+    }
+
     /// Get the number of color stops in this gradient.
     #[must_use]
     pub fn len(&self) -> usize {
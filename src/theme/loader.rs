@@ -130,10 +130,10 @@ mod tests {
 
     #[test]
     fn test_fallback_for_missing_token() {
-        let toml = r##"
+        let toml = r#"
             [meta]
             name = "Test"
-        "##;
+        "#;
 
         let theme = load_from_str(toml, None).unwrap();
         // Missing token should return fallback
@@ -54,6 +54,7 @@
 pub mod adapters;
 mod color;
 mod error;
+mod export;
 mod gradient;
 mod loader;
 mod resolver;
@@ -70,6 +71,7 @@ use parking_lot::RwLock;
 // Re-exports
 pub use color::ThemeColor;
 pub use error::ThemeError;
+pub use export::{ExportFormat, export};
 pub use gradient::Gradient;
 pub use schema::{ThemeMeta, ThemeVariant};
 pub use style::ThemeStyle;
@@ -119,14 +119,14 @@ mod tests {
 
     #[test]
     fn test_parse_style_def() {
-        let toml = r##"
+        let toml = r#"
             [meta]
             name = "Test"
 
             [styles]
             keyword = { fg = "purple", bold = true }
             selected = { fg = "cyan", bg = "highlight" }
-        "##;
+        "#;
 
         let theme: ThemeFile = toml::from_str(toml).unwrap();
         let keyword = theme.styles.get("keyword").unwrap();
@@ -137,14 +137,14 @@ mod tests {
 
     #[test]
     fn test_parse_gradients() {
-        let toml = r##"
+        let toml = r#"
             [meta]
             name = "Test"
 
             [gradients]
             primary = ["purple", "cyan"]
             warm = ["coral", "yellow", "orange"]
-        "##;
+        "#;
 
         let theme: ThemeFile = toml::from_str(toml).unwrap();
         assert_eq!(
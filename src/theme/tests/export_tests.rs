@@ -0,0 +1,35 @@
+//! Tests for theme export
+
+use crate::theme::{ExportFormat, Theme, export};
+
+#[test]
+fn test_export_json_resolves_references() {
+    let theme = Theme::builtin_neon();
+    let json: serde_json::Value =
+        serde_json::from_str(&export(&theme, ExportFormat::Json)).expect("valid JSON");
+
+    assert_eq!(json["name"], "SilkCircuit Neon");
+    assert_eq!(json["variant"], "dark");
+    assert_eq!(
+        json["tokens"]["accent.primary"],
+        theme.color("accent.primary").to_hex()
+    );
+    assert!(json["palette"].as_object().is_some_and(|p| !p.is_empty()));
+    assert!(json["styles"]["keyword"]["fg"].is_string());
+}
+
+#[test]
+fn test_export_css_and_shell_variable_names() {
+    let theme = Theme::builtin_neon();
+    let accent = theme.color("accent.primary").to_hex();
+
+    let css = export(&theme, ExportFormat::Css);
+    assert!(css.contains(":root {\n"));
+    assert!(css.contains(&format!("  --iris-accent-primary: {accent};\n")));
+    assert!(css.contains("  --iris-gradient-"));
+
+    let shell = export(&theme, ExportFormat::Shell);
+    assert!(shell.starts_with("IRIS_THEME_NAME='SilkCircuit Neon'\n"));
+    assert!(shell.contains(&format!("IRIS_ACCENT_PRIMARY='{accent}'\n")));
+    assert!(shell.lines().all(|line| line.starts_with("IRIS_")));
+}
@@ -1,4 +1,5 @@
 //! Tests for the theme system
 
 mod builtins_tests;
+mod export_tests;
 mod theme_tests;
@@ -0,0 +1,189 @@
+//! Review findings as GitHub Actions annotations
+//!
+//! `git-iris review --format github-annotations` prints workflow commands
+//! (`::error file=…,line=…::message`) so findings show up inline on the pull
+//! request diff when the review runs in a GitHub Actions job. Findings are
+//! grouped per file in the log, and a markdown summary goes to the job's
+//! `$GITHUB_STEP_SUMMARY` page.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use super::diagnostics::resolve_path;
+use super::review::{ReviewFinding, Severity};
+
+/// Group name for findings that cite no location
+const GENERAL_GROUP: &str = "General";
+
+impl Severity {
+    /// Workflow command that annotates at this severity
+    pub fn annotation_level(self) -> &'static str {
+        match self {
+            Self::Critical | Self::High => "error",
+            Self::Medium => "warning",
+            Self::Low => "notice",
+        }
+    }
+}
+
+/// Findings keyed by resolved path, in review order within each file;
+/// findings without a location come last under [`GENERAL_GROUP`]
+fn group_findings<'a>(
+    findings: &'a [ReviewFinding],
+    tracked_files: &[String],
+) -> Vec<(String, Vec<&'a ReviewFinding>)> {
+    let mut files: BTreeMap<String, Vec<&ReviewFinding>> = BTreeMap::new();
+    let mut general = Vec::new();
+    for finding in findings {
+        match &finding.location {
+            Some(location) => files
+                .entry(resolve_path(&location.path, tracked_files))
+                .or_default()
+                .push(finding),
+            None => general.push(finding),
+        }
+    }
+    let mut groups: Vec<_> = files.into_iter().collect();
+    if !general.is_empty() {
+        groups.push((GENERAL_GROUP.to_string(), general));
+    }
+    groups
+}
+
+/// Workflow commands annotating every finding, grouped per file
+///
+/// Paths are resolved against `tracked_files` like LSP diagnostics are, so
+/// annotations land on the right file in the PR diff. Findings without a
+/// location become annotations on the workflow run itself.
+pub fn github_annotations(findings: &[ReviewFinding], tracked_files: &[String]) -> String {
+    let mut out = String::new();
+    for (group, findings) in group_findings(findings, tracked_files) {
+        let count = findings.len();
+        let _ = writeln!(
+            out,
+            "::group::{} ({count} finding{})",
+            escape_data(&group),
+            if count == 1 { "" } else { "s" }
+        );
+        for finding in findings {
+            let mut properties = Vec::new();
+            if let Some(location) = &finding.location {
+                properties.push(format!("file={}", escape_property(&group)));
+                properties.push(format!("line={}", location.start_line));
+                if location.end_line > location.start_line {
+                    properties.push(format!("endLine={}", location.end_line));
+                }
+            }
+            properties.push(format!(
+                "title={}",
+                escape_property(&format!("[{}] {}", finding.severity.label(), finding.title))
+            ));
+            let message = if finding.body.is_empty() {
+                finding.title.as_str()
+            } else {
+                finding.body.as_str()
+            };
+            let _ = writeln!(
+                out,
+                "::{} {}::{}",
+                finding.severity.annotation_level(),
+                properties.join(","),
+                escape_data(message)
+            );
+        }
+        out.push_str("::endgroup::\n");
+    }
+    out
+}
+
+/// Markdown summary for `$GITHUB_STEP_SUMMARY`: counts by severity, then
+/// one row per finding
+pub fn github_step_summary(findings: &[ReviewFinding], tracked_files: &[String]) -> String {
+    let mut out = String::from("## Git-Iris Review\n\n");
+    if findings.is_empty() {
+        out.push_str("No findings.\n");
+        return out;
+    }
+
+    out.push_str("| Severity | Findings |\n| --- | ---: |\n");
+    for severity in [
+        Severity::Critical,
+        Severity::High,
+        Severity::Medium,
+        Severity::Low,
+    ] {
+        let count = findings.iter().filter(|f| f.severity == severity).count();
+        if count > 0 {
+            let _ = writeln!(out, "| {} | {count} |", severity.label());
+        }
+    }
+
+    out.push_str("\n| Severity | Location | Finding |\n| --- | --- | --- |\n");
+    for (group, findings) in group_findings(findings, tracked_files) {
+        for finding in findings {
+            let location = finding.location.as_ref().map_or_else(String::new, |l| {
+                if l.end_line > l.start_line {
+                    format!("`{group}:{}-{}`", l.start_line, l.end_line)
+                } else {
+                    format!("`{group}:{}`", l.start_line)
+                }
+            });
+            let _ = writeln!(
+                out,
+                "| {} | {location} | {} |",
+                finding.severity.label(),
+                finding.title.replace('|', "\\|")
+            );
+        }
+    }
+    out
+}
+
+/// Escape a workflow command message
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::parse_findings;
+
+    #[test]
+    fn test_annotations_group_and_escape() {
+        let review = "## Issues\n\n\
+            - [CRITICAL] **SQL injection in `auth.rs:45`**\n  Use bind parameters: 100% of the time.\n\n\
+            - [MEDIUM] Slow loop in `src/api/mod.rs:10-12`\n\n\
+            - [LOW] Missing changelog entry\n";
+        let tracked = ["src/auth.rs".to_string(), "src/api/mod.rs".to_string()];
+        let findings = parse_findings(review);
+
+        let annotations = github_annotations(&findings, &tracked);
+        assert_eq!(
+            annotations.lines().collect::<Vec<_>>(),
+            [
+                "::group::src/api/mod.rs (1 finding)",
+                "::warning file=src/api/mod.rs,line=10,endLine=12,title=[MEDIUM] Slow loop in src/api/mod.rs%3A10-12::Slow loop in src/api/mod.rs:10-12",
+                "::endgroup::",
+                "::group::src/auth.rs (1 finding)",
+                "::error file=src/auth.rs,line=45,title=[CRITICAL] SQL injection in auth.rs%3A45::Use bind parameters: 100%25 of the time.",
+                "::endgroup::",
+                "::group::General (1 finding)",
+                "::notice title=[LOW] Missing changelog entry::Missing changelog entry",
+                "::endgroup::",
+            ]
+        );
+
+        let summary = github_step_summary(&findings, &tracked);
+        assert!(summary.contains("| CRITICAL | 1 |\n| MEDIUM | 1 |\n| LOW | 1 |"));
+        assert!(summary.contains("| CRITICAL | `src/auth.rs:45` | SQL injection in auth.rs:45 |"));
+        assert!(github_step_summary(&[], &tracked).ends_with("No findings.\n"));
+    }
+}
@@ -0,0 +1,134 @@
+//! ASCII-only output enforcement
+//!
+//! When `ascii_only` is set, generated artifacts pass through [`to_ascii`],
+//! which replaces typographic punctuation with plain equivalents and strips
+//! emoji. Anything non-ASCII that remains (accented letters, CJK, …) is
+//! reported by [`check_ascii`] so it can be fixed before committing.
+
+use anyhow::{Result, bail};
+
+/// Maximum number of offending characters listed in a validation error
+const MAX_REPORTED: usize = 5;
+
+/// Replace Unicode punctuation with ASCII and drop emoji
+pub fn to_ascii(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_ascii() {
+            out.push(c);
+            continue;
+        }
+        if let Some(replacement) = ascii_replacement(c) {
+            out.push_str(replacement);
+            continue;
+        }
+        if is_emoji(c) {
+            // Drop the space after a leading emoji so "✨ Add" becomes "Add"
+            if chars.peek() == Some(&' ') && (out.is_empty() || out.ends_with([' ', '\n'])) {
+                chars.next();
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Fail with the offending characters if `text` isn't pure ASCII
+pub fn check_ascii(text: &str) -> Result<()> {
+    let offending: Vec<String> = text
+        .lines()
+        .enumerate()
+        .flat_map(|(line, content)| {
+            content
+                .chars()
+                .enumerate()
+                .filter(|(_, c)| !c.is_ascii())
+                .map(move |(col, c)| format!("'{c}' (line {}, col {})", line + 1, col + 1))
+        })
+        .collect();
+
+    if offending.is_empty() {
+        return Ok(());
+    }
+    let shown = offending
+        .iter()
+        .take(MAX_REPORTED)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    let more = offending.len().saturating_sub(MAX_REPORTED);
+    if more > 0 {
+        bail!("ascii_only: message contains non-ASCII characters: {shown} and {more} more");
+    }
+    bail!("ascii_only: message contains non-ASCII characters: {shown}")
+}
+
+/// ASCII stand-in for typographic punctuation and spacing
+fn ascii_replacement(c: char) -> Option<&'static str> {
+    Some(match c {
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => "'",
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' | '\u{00AB}'
+        | '\u{00BB}' => "\"",
+        '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2212}' | '\u{00B7}' => "-",
+        '\u{2014}' | '\u{2015}' => "--",
+        '\u{2026}' => "...",
+        '\u{2022}' | '\u{25CF}' | '\u{25E6}' => "*",
+        '\u{2192}' | '\u{27F6}' => "->",
+        '\u{2190}' | '\u{27F5}' => "<-",
+        '\u{2194}' => "<->",
+        '\u{21D2}' => "=>",
+        '\u{2264}' => "<=",
+        '\u{2265}' => ">=",
+        '\u{2260}' => "!=",
+        '\u{00D7}' => "x",
+        '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => " ",
+        '\u{200B}' | '\u{200C}' | '\u{2060}' | '\u{FEFF}' => "",
+        _ => return None,
+    })
+}
+
+/// Emoji, pictographs, and the joiners/selectors that combine them
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1F000}'..='\u{1FAFF}'
+            | '\u{2300}'..='\u{23FF}'
+            | '\u{2600}'..='\u{27BF}'
+            | '\u{2B00}'..='\u{2BFF}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{E0020}'..='\u{E007F}'
+            | '\u{200D}'
+            | '\u{20E3}'
+            | '\u{00A9}'
+            | '\u{00AE}'
+            | '\u{2122}'
+            | '\u{3030}'
+            | '\u{303D}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_ascii_replaces_punctuation_and_strips_emoji() {
+        assert_eq!(
+            to_ascii("## 🎯 What’s New — “fast” paths…\n✨ Add caching → 2× faster"),
+            "## What's New -- \"fast\" paths...\nAdd caching -> 2x faster"
+        );
+        assert_eq!(to_ascii("♻️ Refactor 👨‍💻 code"), "Refactor code");
+    }
+
+    #[test]
+    fn test_check_ascii_reports_positions() {
+        assert!(check_ascii("fix: plain ascii").is_ok());
+        let err = check_ascii("fix: café\nnaïve").expect_err("non-ascii");
+        let message = err.to_string();
+        assert!(message.contains("'é' (line 1, col 9)"));
+        assert!(message.contains("'ï' (line 2, col 3)"));
+    }
+}
@@ -0,0 +1,309 @@
+//! Sources cited by agent answers
+//!
+//! Chat, review and semantic blame answers end with a `## Sources` section
+//! listing the files, lines and commits the agent relied on. Parsing that
+//! section lets Studio show a sources footer with jump-to navigation, and
+//! checking each entry against the repository flags paths, line ranges and
+//! commits the model made up.
+
+use regex::Regex;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::git::GitRepo;
+
+/// `## Sources`, `**Sources**` or `Sources:` on a line of its own
+static SOURCES_HEADING: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:#{1,6}\s*sources:?|\*\*sources:?\*\*:?|sources:)\s*$")
+        .expect("valid regex")
+});
+
+/// `path`, `path:12` or `path:12-20`
+static FILE_REF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([^\s:]+)(?::(\d+)(?:-(\d+))?)?$").expect("valid regex"));
+
+/// An abbreviated or full commit hash
+static COMMIT_HASH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[0-9a-f]{7,40}$").expect("valid regex"));
+
+/// A file location or commit an answer cites
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Citation {
+    File {
+        path: String,
+        /// First cited line, 1-based
+        start_line: Option<usize>,
+        end_line: Option<usize>,
+    },
+    Commit {
+        hash: String,
+    },
+}
+
+impl fmt::Display for Citation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File {
+                path,
+                start_line: Some(start),
+                end_line: Some(end),
+            } if end > start => write!(f, "{path}:{start}-{end}"),
+            Self::File {
+                path,
+                start_line: Some(start),
+                ..
+            } => write!(f, "{path}:{start}"),
+            Self::File { path, .. } => f.write_str(path),
+            Self::Commit { hash } => write!(f, "commit {hash}"),
+        }
+    }
+}
+
+/// A citation after checking it against the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckedCitation {
+    /// The citation, with file paths resolved to the tracked path
+    pub citation: Citation,
+    /// Subject line of a cited commit
+    pub summary: Option<String>,
+    /// Why the citation doesn't hold up, if it doesn't
+    pub problem: Option<String>,
+}
+
+impl CheckedCitation {
+    pub fn is_verified(&self) -> bool {
+        self.problem.is_none()
+    }
+}
+
+/// Line index of the sources heading, if the answer has one
+pub fn sources_start(text: &str) -> Option<usize> {
+    text.lines()
+        .position(|line| SOURCES_HEADING.is_match(line.trim()))
+}
+
+/// The answer up to its sources section
+pub fn without_sources(text: &str) -> &str {
+    let Some(start) = sources_start(text) else {
+        return text;
+    };
+    let offset: usize = text.split_inclusive('\n').take(start).map(str::len).sum();
+    text[..offset].trim_end()
+}
+
+/// Citations listed in the answer's sources section
+///
+/// The section runs from the heading to the next heading or the end of the
+/// answer. Each list item starts with a location, optionally in backticks
+/// and followed by a note: `` - `src/auth.rs:40-52` – token refresh ``,
+/// `- commit 1a2b3c4`. Items that are neither are ignored.
+pub fn parse_citations(text: &str) -> Vec<Citation> {
+    let Some(start) = sources_start(text) else {
+        return Vec::new();
+    };
+    let mut citations = Vec::new();
+    for line in text.lines().skip(start + 1) {
+        let line = line.trim();
+        if line.starts_with('#') || (line.starts_with("**") && line.ends_with("**")) {
+            break;
+        }
+        let Some(item) = line
+            .strip_prefix(['-', '*', '+'])
+            .or_else(|| line.split_once(". ").map(|(_, rest)| rest))
+        else {
+            continue;
+        };
+        if let Some(citation) = parse_item(item.trim())
+            && !citations.contains(&citation)
+        {
+            citations.push(citation);
+        }
+    }
+    citations
+}
+
+fn parse_item(item: &str) -> Option<Citation> {
+    let item = item.strip_prefix("commit").map_or(item, str::trim_start);
+    let location = match item.strip_prefix('`') {
+        Some(quoted) => quoted.split('`').next()?,
+        None => item.split_whitespace().next()?,
+    };
+    let location = location.trim_end_matches([',', ';', '.']);
+
+    if COMMIT_HASH.is_match(location) {
+        return Some(Citation::Commit {
+            hash: location.to_string(),
+        });
+    }
+    let caps = FILE_REF.captures(location)?;
+    let path = caps[1].trim_start_matches("./");
+    // A bare word without a directory or extension is prose, not a path
+    if !path.contains(['/', '.']) {
+        return None;
+    }
+    let start_line = caps.get(2).and_then(|m| m.as_str().parse().ok());
+    Some(Citation::File {
+        path: path.to_string(),
+        start_line,
+        end_line: caps
+            .get(3)
+            .and_then(|m| m.as_str().parse().ok())
+            .or(start_line),
+    })
+}
+
+/// Check citations against the working tree and history
+///
+/// Paths are resolved against `tracked_files` the same way review findings
+/// are: exactly, or by a unique suffix match. Line ranges must fall within
+/// the file as it is in `root`. `commit_summary` returns the subject of a
+/// commit, or `None` if there is no such commit.
+pub fn check_citations(
+    citations: Vec<Citation>,
+    root: &Path,
+    tracked_files: &[String],
+    commit_summary: impl Fn(&str) -> Option<String>,
+) -> Vec<CheckedCitation> {
+    citations
+        .into_iter()
+        .map(|citation| match citation {
+            Citation::Commit { hash } => {
+                let summary = commit_summary(&hash);
+                let problem = summary.is_none().then(|| "unknown commit".to_string());
+                CheckedCitation {
+                    citation: Citation::Commit { hash },
+                    summary,
+                    problem,
+                }
+            }
+            Citation::File {
+                path,
+                start_line,
+                end_line,
+            } => {
+                let (path, problem) = match resolve_path(&path, tracked_files) {
+                    Ok(resolved) => {
+                        let problem = end_line.and_then(|end| {
+                            let lines = fs::read_to_string(root.join(&resolved))
+                                .map_or(0, |content| content.lines().count());
+                            (end > lines || start_line == Some(0))
+                                .then(|| format!("file has {lines} lines"))
+                        });
+                        (resolved, problem)
+                    }
+                    Err(problem) => (path, Some(problem)),
+                };
+                CheckedCitation {
+                    citation: Citation::File {
+                        path,
+                        start_line,
+                        end_line,
+                    },
+                    summary: None,
+                    problem,
+                }
+            }
+        })
+        .collect()
+}
+
+/// Parse and check the sources of an answer about `repo`
+pub fn verify_sources(text: &str, repo: &GitRepo) -> Vec<CheckedCitation> {
+    let citations = parse_citations(text);
+    if citations.is_empty() {
+        return Vec::new();
+    }
+    let tracked_files = repo.get_all_tracked_files().unwrap_or_default();
+    let git = repo.open_repo().ok();
+    check_citations(citations, repo.repo_path(), &tracked_files, |hash| {
+        let commit = git
+            .as_ref()?
+            .revparse_single(hash)
+            .ok()?
+            .peel_to_commit()
+            .ok()?;
+        Some(commit.summary().unwrap_or_default().to_string())
+    })
+}
+
+/// The tracked path `cited` refers to
+fn resolve_path(cited: &str, tracked_files: &[String]) -> Result<String, String> {
+    if tracked_files.iter().any(|f| f == cited) {
+        return Ok(cited.to_string());
+    }
+    let matches: Vec<&String> = tracked_files
+        .iter()
+        .filter(|f| Path::new(f).ends_with(cited))
+        .collect();
+    match matches.as_slice() {
+        [only] => Ok((*only).clone()),
+        [] => Err("no such file".to_string()),
+        several => Err(format!("matches {} files", several.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_check_citations() {
+        let answer = "The token is refreshed in `refresh()`.\n\n\
+            ## Sources\n\n\
+            - `src/auth.rs:2-3` – refresh logic\n\
+            - auth.rs:2\n\
+            * commit 1a2b3c4d\n\
+            1. `src/missing.rs`\n\
+            - `src/auth.rs:40`\n\
+            - deadbeefcafe\n\
+            - see the docs\n\n\
+            ## Next steps\n\n\
+            - `src/other.rs:1`\n";
+        let citations = parse_citations(answer);
+        assert_eq!(sources_start(answer), Some(2));
+        assert_eq!(
+            citations
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "src/auth.rs:2-3",
+                "auth.rs:2",
+                "commit 1a2b3c4d",
+                "src/missing.rs",
+                "src/auth.rs:40",
+                "commit deadbeefcafe",
+            ]
+        );
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::create_dir_all(dir.path().join("src")).expect("mkdir");
+        fs::write(dir.path().join("src/auth.rs"), "a\nb\nc\n").expect("write");
+        let tracked = ["src/auth.rs".to_string()];
+        let checked = check_citations(citations, dir.path(), &tracked, |hash| {
+            (hash == "1a2b3c4d").then(|| "Add token refresh".to_string())
+        });
+
+        let problems: Vec<Option<&str>> = checked.iter().map(|c| c.problem.as_deref()).collect();
+        assert_eq!(
+            problems,
+            [
+                None,
+                None,
+                None,
+                Some("no such file"),
+                Some("file has 3 lines"),
+                Some("unknown commit"),
+            ]
+        );
+        assert_eq!(checked[1].citation.to_string(), "src/auth.rs:2");
+        assert_eq!(checked[2].summary.as_deref(), Some("Add token refresh"));
+        assert!(parse_citations("No sources here.").is_empty());
+        assert_eq!(
+            without_sources(answer),
+            "The token is refreshed in `refresh()`."
+        );
+    }
+}
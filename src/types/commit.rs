@@ -17,6 +17,25 @@ pub struct GeneratedMessage {
     /// Brief completion status message for the UI (e.g., "Auth refactor ready.")
     #[serde(default)]
     pub completion_message: Option<String>,
+    /// Which preset/temperature produced this message, when Studio generated
+    /// several variants at once
+    #[serde(skip)]
+    pub variant: Option<String>,
+}
+
+impl GeneratedMessage {
+    /// Split an existing commit message into an editable title and body
+    pub fn from_text(text: &str) -> Self {
+        let text = text.trim();
+        let (title, body) = text.split_once('\n').unwrap_or((text, ""));
+        Self {
+            emoji: None,
+            title: title.trim().to_string(),
+            message: body.trim().to_string(),
+            completion_message: None,
+            variant: None,
+        }
+    }
 }
 
 /// Formats a commit message from a `GeneratedMessage`
@@ -0,0 +1,175 @@
+//! Review findings as editor diagnostics
+//!
+//! `git-iris review --format lsp-diagnostics` prints a JSON object mapping
+//! each repository-relative path to Language Server Protocol `Diagnostic`
+//! objects, so editor plugins can show findings as squiggles without parsing
+//! the markdown review themselves.
+
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::review::{ReviewFinding, Severity};
+
+/// Output format for `git-iris review`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ReviewFormat {
+    /// Markdown review, styled for the terminal unless `--raw`
+    #[default]
+    Markdown,
+    /// JSON map of file to LSP diagnostics
+    LspDiagnostics,
+    /// GitHub Actions workflow commands, plus a job summary
+    GithubAnnotations,
+    /// SARIF 2.1.0 log, for GitHub code scanning
+    Sarif,
+}
+
+/// Zero-based line and UTF-16 character offset, as LSP counts them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// An LSP `Diagnostic`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub range: Range,
+    /// `DiagnosticSeverity`: 1 error, 2 warning, 3 information
+    pub severity: u8,
+    /// Severity as the review wrote it, e.g. `high`
+    pub code: String,
+    pub source: &'static str,
+    pub message: String,
+}
+
+impl Severity {
+    /// LSP `DiagnosticSeverity` for this finding severity
+    pub fn lsp_severity(self) -> u8 {
+        match self {
+            Self::Critical | Self::High => 1,
+            Self::Medium => 2,
+            Self::Low => 3,
+        }
+    }
+}
+
+/// Diagnostics per file for findings that cite a location
+///
+/// Reviews often cite a bare file name (`auth.rs:45`); cited paths are
+/// resolved against `tracked_files` when exactly one tracked path ends with
+/// them. Findings without a location have nowhere to go and are left out.
+pub fn lsp_diagnostics(
+    findings: &[ReviewFinding],
+    tracked_files: &[String],
+) -> BTreeMap<String, Vec<Diagnostic>> {
+    let mut files: BTreeMap<String, Vec<Diagnostic>> = BTreeMap::new();
+    for finding in findings {
+        let Some(location) = &finding.location else {
+            continue;
+        };
+        let message = if finding.body.is_empty() {
+            finding.title.clone()
+        } else {
+            format!("{}\n\n{}", finding.title, finding.body)
+        };
+        // Cover whole lines: from the start of the first to the start of
+        // the line after the last
+        let line = |n: usize| u32::try_from(n.saturating_sub(1)).unwrap_or(u32::MAX);
+        let diagnostic = Diagnostic {
+            range: Range {
+                start: Position {
+                    line: line(location.start_line),
+                    character: 0,
+                },
+                end: Position {
+                    line: line(location.end_line).saturating_add(1),
+                    character: 0,
+                },
+            },
+            severity: finding.severity.lsp_severity(),
+            code: finding.severity.label().to_lowercase(),
+            source: "git-iris",
+            message,
+        };
+        files
+            .entry(resolve_path(&location.path, tracked_files))
+            .or_default()
+            .push(diagnostic);
+    }
+    files
+}
+
+/// The tracked path `cited` refers to, or `cited` itself if none or several
+pub(super) fn resolve_path(cited: &str, tracked_files: &[String]) -> String {
+    if tracked_files.iter().any(|f| f == cited) {
+        return cited.to_string();
+    }
+    let mut matches = tracked_files
+        .iter()
+        .filter(|f| Path::new(f).ends_with(cited));
+    match (matches.next(), matches.next()) {
+        (Some(only), None) => only.clone(),
+        _ => cited.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::parse_findings;
+
+    #[test]
+    fn test_lsp_diagnostics_resolve_and_range() {
+        let review = "## Issues\n\n\
+            - [CRITICAL] **SQL injection in `auth.rs:45`**\n  Use bind parameters.\n\n\
+            - [MEDIUM] Slow loop in `src/api/mod.rs:10-12`\n\n\
+            - [LOW] Ambiguous `mod.rs:3`\n\n\
+            - [HIGH] No location at all\n";
+        let tracked = [
+            "src/auth.rs".to_string(),
+            "src/api/mod.rs".to_string(),
+            "src/db/mod.rs".to_string(),
+        ];
+
+        let files = lsp_diagnostics(&parse_findings(review), &tracked);
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            ["mod.rs", "src/api/mod.rs", "src/auth.rs"]
+        );
+
+        let auth = &files["src/auth.rs"][0];
+        assert_eq!(auth.severity, 1);
+        assert_eq!(auth.code, "critical");
+        assert_eq!(
+            auth.range.start,
+            Position {
+                line: 44,
+                character: 0
+            }
+        );
+        assert_eq!(
+            auth.range.end,
+            Position {
+                line: 45,
+                character: 0
+            }
+        );
+        assert_eq!(
+            auth.message,
+            "SQL injection in auth.rs:45\n\nUse bind parameters."
+        );
+
+        let api = &files["src/api/mod.rs"][0];
+        assert_eq!(api.severity, 2);
+        assert_eq!((api.range.start.line, api.range.end.line), (9, 12));
+    }
+}
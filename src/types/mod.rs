@@ -6,24 +6,58 @@
 //! - Code reviews
 //! - Changelogs
 //! - Release notes
+//!
+//! It also holds the ASCII-only formatting pass applied to all of them, the
+//! commit style rules, review findings as editor diagnostics, GitHub Actions
+//! annotations and SARIF, and the sources answers cite.
 
+mod annotations;
+mod ascii;
 mod changelog;
+mod citations;
 mod commit;
+mod diagnostics;
 mod pr;
 mod release_notes;
 mod review;
+mod sarif;
+mod style;
+
+// ASCII-only enforcement
+pub use ascii::{check_ascii, to_ascii};
 
 // Commit types
 pub use self::commit::{GeneratedMessage, format_commit_message};
 
+// Commit style rules
+pub use style::{CommitStyle, StyleViolation, SubjectCase};
+
 // PR types
 pub use pr::MarkdownPullRequest;
 
 // Review types
-pub use review::{MarkdownReview, render_markdown_for_terminal};
+pub use review::{
+    FindingLocation, MarkdownReview, ReviewFinding, Severity, parse_findings,
+    render_markdown_for_terminal,
+};
+
+// Review findings for editors
+pub use diagnostics::{Diagnostic, Position, Range, ReviewFormat, lsp_diagnostics};
+
+// Review findings for GitHub Actions
+pub use annotations::{github_annotations, github_step_summary};
+
+// Review findings for code scanning
+pub use sarif::sarif_log;
+
+// Sources cited by answers
+pub use citations::{
+    CheckedCitation, Citation, check_citations, parse_citations, sources_start, verify_sources,
+    without_sources,
+};
 
 // Changelog types
 pub use changelog::{ChangeEntry, ChangeMetrics, ChangelogType, MarkdownChangelog};
 
 // Release notes types
-pub use release_notes::MarkdownReleaseNotes;
+pub use release_notes::{MarkdownReleaseNotes, SuggestedVersion, VersionBump};
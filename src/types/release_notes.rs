@@ -1,17 +1,34 @@
 //! Release notes types and formatting
 //!
 //! This module provides markdown-based release notes output that lets the LLM drive
-//! the structure while we beautify it for terminal display.
+//! the structure while we beautify it for terminal display. Alongside the
+//! notes, a deterministic pass over the range's commits suggests the next
+//! semantic version.
 
+use crate::git::is_breaking_message;
 use crate::types::review::render_markdown_for_terminal;
+use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::LazyLock;
+
+static VERSION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(.*?)(\d+)\.(\d+)(?:\.(\d+))?(?:[-+][\w.+-]*)?$").expect("valid version regex")
+});
+
+/// Most commit subjects quoted in a justification
+const MAX_CITED_COMMITS: usize = 3;
 
 /// Markdown-based release notes that lets the LLM determine structure
 #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct MarkdownReleaseNotes {
     /// The full markdown content of the release notes
     pub content: String,
+    /// Next version suggested from the commits (computed, not generated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub suggested_version: Option<SuggestedVersion>,
 }
 
 impl MarkdownReleaseNotes {
@@ -25,3 +42,231 @@ impl MarkdownReleaseNotes {
         &self.content
     }
 }
+
+/// Semantic version component to bump
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionBump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl fmt::Display for VersionBump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Patch => "patch",
+            Self::Minor => "minor",
+            Self::Major => "major",
+        })
+    }
+}
+
+/// Recommended next version for a range of commits
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SuggestedVersion {
+    pub bump: VersionBump,
+    /// Version the range starts from, when it could be determined
+    pub current: Option<String>,
+    /// `current` with the bump applied
+    pub next: Option<String>,
+    /// Why this bump was chosen
+    pub justification: String,
+}
+
+impl SuggestedVersion {
+    /// Suggest a bump from the commit messages in a range
+    ///
+    /// Commits marked breaking (`type!:` or a `BREAKING CHANGE` footer) and
+    /// public API removed in the diff (`removed_api`) call for a major bump,
+    /// features (`feat`, ✨, or subjects starting with "Add") for a minor one,
+    /// anything else for a patch. Before 1.0.0, breaking changes bump the
+    /// minor version instead. `current` is the version tag the range starts
+    /// from; without one there is no `next`.
+    pub fn from_commits(current: Option<&str>, messages: &[&str], removed_api: &[String]) -> Self {
+        fn subject<'a>(message: &&'a str) -> &'a str {
+            message.lines().next().unwrap_or_default().trim()
+        }
+        let breaking: Vec<&str> = messages
+            .iter()
+            .filter(|m| is_breaking_message(m))
+            .map(subject)
+            .collect();
+        let features: Vec<&str> = messages
+            .iter()
+            .filter(|m| !is_breaking_message(m))
+            .map(subject)
+            .filter(|s| is_feature(s))
+            .collect();
+
+        let version = current.and_then(Version::parse);
+        let pre_release = version.as_ref().is_some_and(|v| v.major == 0);
+
+        let (mut bump, mut justification) = if !breaking.is_empty() || !removed_api.is_empty() {
+            let mut reasons = Vec::new();
+            if !breaking.is_empty() {
+                reasons.push(format!(
+                    "{} marked breaking ({})",
+                    plural(breaking.len(), "commit"),
+                    cite(&breaking)
+                ));
+            }
+            if !removed_api.is_empty() {
+                reasons.push(format!("removed public API ({})", cite(removed_api)));
+            }
+            (VersionBump::Major, reasons.join("; "))
+        } else if !features.is_empty() {
+            (
+                VersionBump::Minor,
+                format!(
+                    "{} ({})",
+                    plural(features.len(), "new feature"),
+                    cite(&features)
+                ),
+            )
+        } else {
+            (
+                VersionBump::Patch,
+                format!(
+                    "{} with fixes and maintenance only, no new features or breaking changes",
+                    plural(messages.len(), "commit")
+                ),
+            )
+        };
+        if bump == VersionBump::Major && pre_release {
+            bump = VersionBump::Minor;
+            justification.push_str("; before 1.0.0 breaking changes bump the minor version");
+        }
+
+        Self {
+            bump,
+            current: current.map(str::to_string),
+            next: version.map(|v| v.bump(bump)),
+            justification,
+        }
+    }
+}
+
+impl fmt::Display for SuggestedVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.current, &self.next) {
+            (Some(current), Some(next)) => write!(f, "{current} → {next} ({})", self.bump),
+            _ => write!(f, "{} bump", self.bump),
+        }?;
+        write!(f, ": {}", self.justification)
+    }
+}
+
+/// A `major.minor.patch` version with any prefix (`v`, `release-`) kept
+struct Version {
+    prefix: String,
+    major: u64,
+    minor: u64,
+    patch: u64,
+}
+
+impl Version {
+    fn parse(tag: &str) -> Option<Self> {
+        let caps = VERSION.captures(tag.trim())?;
+        Some(Self {
+            prefix: caps[1].to_string(),
+            major: caps[2].parse().ok()?,
+            minor: caps[3].parse().ok()?,
+            patch: caps.get(4).map_or(Some(0), |p| p.as_str().parse().ok())?,
+        })
+    }
+
+    fn bump(&self, bump: VersionBump) -> String {
+        let (major, minor, patch) = match bump {
+            VersionBump::Major => (self.major + 1, 0, 0),
+            VersionBump::Minor => (self.major, self.minor + 1, 0),
+            VersionBump::Patch => (self.major, self.minor, self.patch + 1),
+        };
+        format!("{}{major}.{minor}.{patch}", self.prefix)
+    }
+}
+
+/// Whether a commit subject describes a new feature
+fn is_feature(subject: &str) -> bool {
+    let conventional = subject.split_once(':').is_some_and(|(prefix, _)| {
+        let kind = prefix.split_once('(').map_or(prefix, |(kind, _)| kind);
+        kind == "feat"
+    });
+    let subject = subject.trim_start_matches(|c: char| !c.is_alphanumeric());
+    conventional
+        || subject.starts_with("✨")
+        || ["Add ", "Introduce ", "Support "]
+            .iter()
+            .any(|verb| subject.starts_with(verb))
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// The first few items, then how many more
+fn cite<S: AsRef<str>>(items: &[S]) -> String {
+    let mut cited: Vec<&str> = items
+        .iter()
+        .take(MAX_CITED_COMMITS)
+        .map(AsRef::as_ref)
+        .collect();
+    let more = items.len().saturating_sub(MAX_CITED_COMMITS);
+    let more = format!("{more} more");
+    if items.len() > MAX_CITED_COMMITS {
+        cited.push(&more);
+    }
+    cited.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggested_version_bumps() {
+        let fix = SuggestedVersion::from_commits(
+            Some("v1.4.2"),
+            &["fix: handle empty input", "docs: update README"],
+            &[],
+        );
+        assert_eq!(fix.bump, VersionBump::Patch);
+        assert_eq!(fix.next.as_deref(), Some("v1.4.3"));
+
+        let feature = SuggestedVersion::from_commits(
+            Some("v1.4.2"),
+            &["feat(cli): add --json", "Add Ollama provider", "fix: typo"],
+            &[],
+        );
+        assert_eq!(feature.bump, VersionBump::Minor);
+        assert_eq!(feature.next.as_deref(), Some("v1.5.0"));
+        assert_eq!(
+            feature.justification,
+            "2 new features (feat(cli): add --json, Add Ollama provider)"
+        );
+
+        let breaking = SuggestedVersion::from_commits(
+            Some("1.4"),
+            &["feat!: drop the legacy API\n\nBREAKING CHANGE: gone"],
+            &[],
+        );
+        assert_eq!(breaking.bump, VersionBump::Major);
+        assert_eq!(breaking.next.as_deref(), Some("2.0.0"));
+
+        let removed = vec!["removed `pub fn old_api` from src/lib.rs".to_string()];
+        let pre_release = SuggestedVersion::from_commits(Some("v0.3.1"), &["fix: x"], &removed);
+        assert_eq!(pre_release.bump, VersionBump::Minor);
+        assert_eq!(pre_release.next.as_deref(), Some("v0.4.0"));
+
+        let untagged = SuggestedVersion::from_commits(Some("HEAD~3"), &["fix: x"], &[]);
+        assert_eq!(untagged.next, None);
+        assert_eq!(
+            untagged.to_string(),
+            "patch bump: 1 commit with fixes and maintenance only, no new features or breaking changes"
+        );
+    }
+}
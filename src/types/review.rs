@@ -4,9 +4,11 @@
 //! the review structure while we beautify it for terminal display.
 
 use colored::Colorize;
+use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::Write;
+use std::sync::LazyLock;
 
 /// Helper to get themed colors for terminal output
 mod colors {
@@ -62,6 +64,155 @@ impl MarkdownReview {
     }
 }
 
+/// A line that opens a finding: optional list or heading marker, then a
+/// severity badge, then the title
+static FINDING_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*(?:[-*+]|\d+\.|#{1,6})?\s*(?:\*\*)?\[(critical|high|medium|low)\](?:\*\*)?\s*(.*)$")
+        .expect("valid regex")
+});
+
+/// A `path/to/file.rs:12` or `file.rs:12-20` reference
+static LOCATION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"([\w./-]*\w\.\w+):(\d+)(?:-(\d+))?").expect("valid regex"));
+
+/// Severity of a review finding, least severe first
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    /// Parse a badge name like `HIGH`, ignoring case
+    pub fn parse(badge: &str) -> Option<Self> {
+        match badge.to_ascii_uppercase().as_str() {
+            "CRITICAL" => Some(Self::Critical),
+            "HIGH" => Some(Self::High),
+            "MEDIUM" => Some(Self::Medium),
+            "LOW" => Some(Self::Low),
+            _ => None,
+        }
+    }
+
+    /// Badge text, as the review writes it
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Critical => "CRITICAL",
+            Self::High => "HIGH",
+            Self::Medium => "MEDIUM",
+            Self::Low => "LOW",
+        }
+    }
+}
+
+/// Where in the code a finding points
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FindingLocation {
+    /// Path as the review cites it, possibly just a file name
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// One issue in a review: an entry starting with a severity badge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewFinding {
+    pub severity: Severity,
+    /// Title with the badge and markdown emphasis removed
+    pub title: String,
+    /// Explanation and fix following the title, as markdown
+    pub body: String,
+    /// Line of the review the finding starts on, zero-based
+    pub line: usize,
+    /// First `file:line` reference in the finding, if any
+    pub location: Option<FindingLocation>,
+}
+
+impl MarkdownReview {
+    /// Findings in review order
+    pub fn findings(&self) -> Vec<ReviewFinding> {
+        parse_findings(&self.content)
+    }
+}
+
+/// Split a markdown review into its findings
+///
+/// The review prompt asks for one list entry per issue, opening with a
+/// `[SEVERITY]` badge and citing `file:line`. A finding runs until the next
+/// finding or heading; its location is the first reference inside it.
+pub fn parse_findings(markdown: &str) -> Vec<ReviewFinding> {
+    let mut findings: Vec<ReviewFinding> = Vec::new();
+    let mut in_code_block = false;
+    // Whether lines still belong to the last finding
+    let mut in_finding = false;
+
+    for (index, line) in markdown.lines().enumerate() {
+        let is_fence = line.trim_start().starts_with("```");
+        if !in_code_block && !is_fence {
+            if let Some(caps) = FINDING_LINE.captures(line)
+                && let Some(severity) = Severity::parse(&caps[1])
+            {
+                let title = caps[2].replace("**", "").replace('`', "");
+                findings.push(ReviewFinding {
+                    severity,
+                    title: title.trim().to_string(),
+                    body: String::new(),
+                    line: index,
+                    location: parse_location(line),
+                });
+                in_finding = true;
+                continue;
+            }
+            if line.trim_start().starts_with('#') {
+                in_finding = false;
+            }
+        }
+        if is_fence {
+            in_code_block = !in_code_block;
+        }
+
+        if in_finding && let Some(finding) = findings.last_mut() {
+            if !in_code_block && !is_fence && finding.location.is_none() {
+                finding.location = parse_location(line);
+            }
+            if !finding.body.is_empty() || !line.trim().is_empty() {
+                // Keep indentation inside code blocks
+                let text = if in_code_block && !is_fence {
+                    line.trim_end()
+                } else {
+                    line.trim()
+                };
+                finding.body.push_str(text);
+                finding.body.push('\n');
+            }
+        }
+    }
+
+    for finding in &mut findings {
+        finding.body.truncate(finding.body.trim_end().len());
+    }
+    findings
+}
+
+fn parse_location(line: &str) -> Option<FindingLocation> {
+    let caps = LOCATION.captures(line)?;
+    let start_line = caps[2].parse().ok()?;
+    let end_line = caps
+        .get(3)
+        .and_then(|m| m.as_str().parse().ok())
+        .unwrap_or(start_line);
+    Some(FindingLocation {
+        path: caps[1].trim_start_matches("./").to_string(),
+        start_line,
+        end_line: end_line.max(start_line),
+    })
+}
+
 /// Render markdown content with `SilkCircuit` terminal styling
 ///
 /// This function parses markdown and applies our color palette for beautiful
@@ -350,3 +501,52 @@ fn style_line_content(content: &str) -> String {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_findings() {
+        let review = "# Code Review\n\n\
+            ## Security\n\n\
+            - [CRITICAL] **SQL Injection in `src/auth.rs:45`**\n  \
+              Fix: use `query!`.\n\n\
+            - **[High]** Missing rate limiting\n  \
+              The handler in `api.rs:120-150` never throttles.\n\n\
+            ```text\n- [LOW] not a finding\n```\n\
+            ## Strengths\n\n\
+            Clear naming in `lib.rs:3`.\n\n\
+            ### [low] Stale comment\n";
+
+        let findings = parse_findings(review);
+        assert_eq!(findings.len(), 3);
+
+        assert_eq!(findings[0].severity, Severity::Critical);
+        assert_eq!(findings[0].title, "SQL Injection in src/auth.rs:45");
+        assert_eq!(findings[0].line, 4);
+        assert_eq!(findings[0].body, "Fix: use `query!`.");
+        assert_eq!(
+            findings[0].location,
+            Some(FindingLocation {
+                path: "src/auth.rs".to_string(),
+                start_line: 45,
+                end_line: 45,
+            })
+        );
+
+        assert_eq!(findings[1].severity, Severity::High);
+        assert_eq!(findings[1].title, "Missing rate limiting");
+        assert!(
+            findings[1]
+                .body
+                .ends_with("```text\n- [LOW] not a finding\n```")
+        );
+        let location = findings[1].location.as_ref().expect("location");
+        assert_eq!((location.start_line, location.end_line), (120, 150));
+
+        // References outside any finding are ignored
+        assert_eq!(findings[2].severity, Severity::Low);
+        assert_eq!(findings[2].location, None);
+    }
+}
@@ -0,0 +1,142 @@
+//! Review findings as a SARIF log
+//!
+//! `git-iris review --format sarif` prints a [SARIF 2.1.0] log, the format
+//! GitHub code scanning and most static analysis dashboards ingest. Each
+//! severity is a rule, so findings can be filtered and dismissed by severity
+//! in the code scanning UI.
+//!
+//! [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use serde_json::{Value, json};
+
+use super::diagnostics::resolve_path;
+use super::review::{ReviewFinding, Severity};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Severities as rules, most severe first
+const RULES: [Severity; 4] = [
+    Severity::Critical,
+    Severity::High,
+    Severity::Medium,
+    Severity::Low,
+];
+
+impl Severity {
+    /// SARIF result level for this severity
+    pub fn sarif_level(self) -> &'static str {
+        match self {
+            Self::Critical | Self::High => "error",
+            Self::Medium => "warning",
+            Self::Low => "note",
+        }
+    }
+}
+
+/// A SARIF log with one run holding every finding that cites a location
+///
+/// Paths are resolved against `tracked_files` like LSP diagnostics are.
+/// Code scanning rejects results without a location, so findings that cite
+/// none are left out.
+pub fn sarif_log(findings: &[ReviewFinding], tracked_files: &[String]) -> Value {
+    let rules: Vec<Value> = RULES
+        .iter()
+        .map(|severity| {
+            let name = severity.label().to_lowercase();
+            json!({
+                "id": name,
+                "name": format!("{name}-severity-finding"),
+                "shortDescription": {
+                    "text": format!("{} severity review finding", severity.label()),
+                },
+                "defaultConfiguration": { "level": severity.sarif_level() },
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = findings
+        .iter()
+        .filter_map(|finding| {
+            let location = finding.location.as_ref()?;
+            let message = if finding.body.is_empty() {
+                finding.title.clone()
+            } else {
+                format!("{}\n\n{}", finding.title, finding.body)
+            };
+            Some(json!({
+                "ruleId": finding.severity.label().to_lowercase(),
+                "ruleIndex": RULES.iter().position(|s| *s == finding.severity),
+                "level": finding.severity.sarif_level(),
+                "message": { "text": message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": resolve_path(&location.path, tracked_files),
+                            "uriBaseId": "%SRCROOT%",
+                        },
+                        "region": {
+                            "startLine": location.start_line.max(1),
+                            "endLine": location.end_line.max(location.start_line).max(1),
+                        },
+                    },
+                }],
+            }))
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "git-iris",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_HOMEPAGE"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::parse_findings;
+
+    #[test]
+    fn test_sarif_log_results_and_rules() {
+        let review = "## Issues\n\n\
+            - [CRITICAL] **SQL injection in `auth.rs:45`**\n  Use bind parameters.\n\n\
+            - [LOW] Naming in `src/api/mod.rs:10-12`\n\n\
+            - [HIGH] No location at all\n";
+        let tracked = ["src/auth.rs".to_string(), "src/api/mod.rs".to_string()];
+
+        let log = sarif_log(&parse_findings(review), &tracked);
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "high");
+
+        let results = run["results"].as_array().expect("results");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["ruleId"], "critical");
+        assert_eq!(results[0]["ruleIndex"], 0);
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(
+            results[0]["message"]["text"],
+            "SQL injection in auth.rs:45\n\nUse bind parameters."
+        );
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/auth.rs");
+        assert_eq!(location["region"]["startLine"], 45);
+
+        assert_eq!(results[1]["level"], "note");
+        let region = &results[1]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(
+            (&region["startLine"], &region["endLine"]),
+            (&json!(10), &json!(12))
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Commit style rules
+//!
+//! Teams often have house rules the model doesn't reliably follow: subject
+//! casing, banned words, a ticket prefix. [`CommitStyle`] fixes the subject
+//! casing of generated messages and reports anything it can't fix, so the
+//! rules hold no matter what the model returns. Casing uses Unicode case
+//! mappings, so the rules work for any cased script.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::LazyLock;
+
+use super::GeneratedMessage;
+
+/// `type(scope)!: ` at the start of a conventional commit subject
+static CONVENTIONAL_PREFIX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-z]+(?:\([^)]*\))?!?:\s+").expect("valid regex"));
+
+/// Casing of the first word of the subject description
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubjectCase {
+    /// No rule
+    #[default]
+    Any,
+    /// `Add retry logic`
+    Sentence,
+    /// `add retry logic`
+    Lower,
+}
+
+impl SubjectCase {
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn is_any(&self) -> bool {
+        *self == Self::Any
+    }
+}
+
+/// `[commit_style]` rules applied to every commit message
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitStyle {
+    /// Required casing of the subject's first word
+    #[serde(default, skip_serializing_if = "SubjectCase::is_any")]
+    pub subject_case: SubjectCase,
+    /// Words and phrases that must not appear, matched case-insensitively
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbidden_words: Vec<String>,
+    /// Patterns the subject must start with; any one of them is enough
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_prefix: Vec<String>,
+}
+
+/// A rule a commit message breaks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StyleViolation {
+    /// The subject's first word has the wrong case
+    SubjectCase(SubjectCase),
+    /// A forbidden word or phrase appears
+    ForbiddenWord(String),
+    /// The subject matches none of the required prefixes
+    MissingPrefix,
+    /// A `required_prefix` entry isn't a valid regex
+    InvalidPattern(String),
+}
+
+impl fmt::Display for StyleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SubjectCase(SubjectCase::Lower) => f.write_str("subject should start lowercase"),
+            Self::SubjectCase(_) => f.write_str("subject should start with a capital"),
+            Self::ForbiddenWord(word) => write!(f, "contains \"{word}\""),
+            Self::MissingPrefix => f.write_str("subject is missing the required prefix"),
+            Self::InvalidPattern(pattern) => write!(f, "invalid prefix pattern `{pattern}`"),
+        }
+    }
+}
+
+impl CommitStyle {
+    /// Whether no rules are configured
+    pub fn is_empty(&self) -> bool {
+        self.subject_case.is_any()
+            && self.forbidden_words.is_empty()
+            && self.required_prefix.is_empty()
+    }
+
+    /// Fix the subject casing of a generated message
+    pub fn apply(&self, message: &mut GeneratedMessage) {
+        if self.subject_case.is_any() {
+            return;
+        }
+        let start = self.description_start(&message.title);
+        let (prefix, description) = message.title.split_at(start);
+        if let Some(fixed) = recase(description, self.subject_case) {
+            message.title = format!("{prefix}{fixed}");
+        }
+    }
+
+    /// Rules a full commit message (subject, blank line, body) breaks
+    pub fn check(&self, message: &str) -> Vec<StyleViolation> {
+        let subject = strip_leading_emoji(message.lines().next().unwrap_or(""));
+        let mut violations = Vec::new();
+
+        if !self.required_prefix.is_empty() {
+            let mut matched = false;
+            for pattern in &self.required_prefix {
+                match Regex::new(pattern) {
+                    Ok(re) => matched |= re.find(subject).is_some_and(|m| m.start() == 0),
+                    Err(_) => violations.push(StyleViolation::InvalidPattern(pattern.clone())),
+                }
+            }
+            if !matched {
+                violations.push(StyleViolation::MissingPrefix);
+            }
+        }
+
+        if !self.subject_case.is_any() {
+            let description = &subject[self.description_start(subject)..];
+            if recase(description, self.subject_case).is_some() {
+                violations.push(StyleViolation::SubjectCase(self.subject_case));
+            }
+        }
+
+        let message_words = words(message);
+        for forbidden in &self.forbidden_words {
+            let phrase = words(forbidden);
+            if !phrase.is_empty()
+                && message_words
+                    .windows(phrase.len())
+                    .any(|w| w == phrase.as_slice())
+            {
+                violations.push(StyleViolation::ForbiddenWord(forbidden.clone()));
+            }
+        }
+
+        violations
+    }
+
+    /// Byte offset where the subject's description begins, after a required
+    /// or conventional prefix
+    fn description_start(&self, subject: &str) -> usize {
+        self.required_prefix
+            .iter()
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .find_map(|re| re.find(subject).filter(|m| m.start() == 0))
+            .or_else(|| CONVENTIONAL_PREFIX.find(subject))
+            .map_or(0, |m| m.end())
+    }
+}
+
+/// The description with its first word recased, or `None` if it already
+/// complies or the word shouldn't be touched (acronyms, identifiers, code)
+fn recase(description: &str, case: SubjectCase) -> Option<String> {
+    let trimmed = description.trim_start();
+    let (indent, description) = description.split_at(description.len() - trimmed.len());
+    let word = description.split_whitespace().next()?;
+    let mut chars = word.chars();
+    let first = chars.next()?;
+    let rest = chars.as_str();
+
+    // `API`, `README`, `iOS`, `snake_case`: leave as written
+    if rest.chars().any(char::is_uppercase) || word.contains(['_', '`', '.', '/']) {
+        return None;
+    }
+    let fixed: String = match case {
+        SubjectCase::Sentence if first.is_lowercase() => first.to_uppercase().collect(),
+        SubjectCase::Lower if first.is_uppercase() => first.to_lowercase().collect(),
+        _ => return None,
+    };
+    Some(format!(
+        "{indent}{fixed}{}",
+        &description[first.len_utf8()..]
+    ))
+}
+
+/// Subject without a leading emoji (gitmoji)
+fn strip_leading_emoji(subject: &str) -> &str {
+    subject
+        .trim_start_matches(|c: char| !c.is_ascii() && !c.is_alphanumeric())
+        .trim_start()
+}
+
+/// Lowercased words of `text`
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(title: &str) -> GeneratedMessage {
+        GeneratedMessage {
+            emoji: None,
+            title: title.to_string(),
+            message: String::new(),
+            completion_message: None,
+            variant: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_fixes_subject_case() {
+        let sentence = CommitStyle {
+            subject_case: SubjectCase::Sentence,
+            ..CommitStyle::default()
+        };
+        let mut msg = message("feat(auth): add token refresh");
+        sentence.apply(&mut msg);
+        assert_eq!(msg.title, "feat(auth): Add token refresh");
+
+        let mut msg = message("überarbeite die Paketliste");
+        sentence.apply(&mut msg);
+        assert_eq!(msg.title, "Überarbeite die Paketliste");
+
+        let lower = CommitStyle {
+            subject_case: SubjectCase::Lower,
+            required_prefix: vec![r"[A-Z]+-\d+: ".to_string()],
+            ..CommitStyle::default()
+        };
+        let mut msg = message("PROJ-42: Обновить зависимости");
+        lower.apply(&mut msg);
+        assert_eq!(msg.title, "PROJ-42: обновить зависимости");
+
+        // Acronyms and identifiers are left alone
+        let mut msg = message("API client retries");
+        lower.apply(&mut msg);
+        assert_eq!(msg.title, "API client retries");
+    }
+
+    #[test]
+    fn test_check_reports_violations() {
+        let style = CommitStyle {
+            subject_case: SubjectCase::Sentence,
+            forbidden_words: vec!["WIP".to_string(), "quick fix".to_string()],
+            required_prefix: vec![r"[A-Z]+-\d+ ".to_string(), "(".to_string()],
+        };
+        let violations = style.check("✨ add wip parser\n\nA quick  fix for the lexer");
+        assert_eq!(
+            violations,
+            [
+                StyleViolation::InvalidPattern("(".to_string()),
+                StyleViolation::MissingPrefix,
+                StyleViolation::SubjectCase(SubjectCase::Sentence),
+                StyleViolation::ForbiddenWord("WIP".to_string()),
+                StyleViolation::ForbiddenWord("quick fix".to_string()),
+            ]
+        );
+
+        let clean = style.check("✨ PROJ-7 Add parser\n\nWipes the cache first");
+        assert_eq!(clean, [StyleViolation::InvalidPattern("(".to_string())]);
+        assert!(CommitStyle::default().check("anything goes").is_empty());
+    }
+}
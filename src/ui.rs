@@ -88,6 +88,24 @@ pub fn is_quiet_mode() -> bool {
     *QUIET_MODE.lock()
 }
 
+/// Whether messages go to stderr, leaving stdout for a command's result
+static MESSAGES_TO_STDERR: std::sync::LazyLock<Mutex<bool>> =
+    std::sync::LazyLock::new(|| Mutex::new(false));
+
+/// Print messages on stderr instead of stdout
+pub fn set_messages_to_stderr(enabled: bool) {
+    *MESSAGES_TO_STDERR.lock() = enabled;
+}
+
+/// Print a message line on stdout, or stderr when stdout is reserved
+fn emit(line: impl std::fmt::Display) {
+    if *MESSAGES_TO_STDERR.lock() {
+        eprintln!("{line}");
+    } else {
+        println!("{line}");
+    }
+}
+
 pub fn create_spinner(message: &str) -> ProgressBar {
     // Don't create a spinner in quiet mode
     if is_quiet_mode() {
@@ -138,7 +156,7 @@ pub fn create_spinner(message: &str) -> ProgressBar {
 pub fn print_info(message: &str) {
     if !is_quiet_mode() {
         let color = theme::current().color("info");
-        println!("{}", message.truecolor(color.r, color.g, color.b).bold());
+        emit(message.truecolor(color.r, color.g, color.b).bold());
     }
 }
 
@@ -146,7 +164,7 @@ pub fn print_info(message: &str) {
 pub fn print_warning(message: &str) {
     if !is_quiet_mode() {
         let color = theme::current().color("warning");
-        println!("{}", message.truecolor(color.r, color.g, color.b).bold());
+        emit(message.truecolor(color.r, color.g, color.b).bold());
     }
 }
 
@@ -161,7 +179,7 @@ pub fn print_error(message: &str) {
 pub fn print_success(message: &str) {
     if !is_quiet_mode() {
         let color = theme::current().color("success");
-        println!("{}", message.truecolor(color.r, color.g, color.b).bold());
+        emit(message.truecolor(color.r, color.g, color.b).bold());
     }
 }
 
@@ -172,12 +190,12 @@ pub fn print_version(version: &str) {
         let cyan = t.color("accent.secondary");
         let green = t.color("success");
 
-        println!(
+        emit(format!(
             "{} {} {}",
             "🔮 Git-Iris".truecolor(purple.r, purple.g, purple.b).bold(),
             "version".truecolor(cyan.r, cyan.g, cyan.b),
             version.truecolor(green.r, green.g, green.b)
-        );
+        ));
     }
 }
 
@@ -185,23 +203,47 @@ pub fn print_version(version: &str) {
 pub fn print_bordered_content(content: &str) {
     if !is_quiet_mode() {
         let color = theme::current().color("accent.primary");
-        println!("{}", "━".repeat(50).truecolor(color.r, color.g, color.b));
-        println!("{content}");
-        println!("{}", "━".repeat(50).truecolor(color.r, color.g, color.b));
+        emit("━".repeat(50).truecolor(color.r, color.g, color.b));
+        emit(content);
+        emit("━".repeat(50).truecolor(color.r, color.g, color.b));
     }
 }
 
 /// Print a simple message (respects quiet mode)
 pub fn print_message(message: &str) {
     if !is_quiet_mode() {
-        println!("{message}");
+        emit(message);
+    }
+}
+
+/// Ask a yes/no question on the terminal; anything but `y`/`yes` declines
+pub fn confirm(question: &str) -> bool {
+    use std::io::Write;
+
+    let color = theme::current().color("warning");
+    let prompt = format!(
+        "{} [y/N] ",
+        question.truecolor(color.r, color.g, color.b).bold()
+    );
+    let flushed = if *MESSAGES_TO_STDERR.lock() {
+        eprint!("{prompt}");
+        std::io::stderr().flush()
+    } else {
+        print!("{prompt}");
+        std::io::stdout().flush()
+    };
+    if flushed.is_err() {
+        return false;
     }
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).is_ok()
+        && matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
 }
 
 /// Print an empty line (respects quiet mode)
 pub fn print_newline() {
     if !is_quiet_mode() {
-        println!();
+        emit("");
     }
 }
 
@@ -0,0 +1,105 @@
+//! Undo the last commit made by git-iris
+//!
+//! Commits git-iris makes are recorded in companion storage. Undoing one
+//! soft-resets it, so its changes stay staged, and keeps its message as a
+//! draft for the Commit editor. Only the recorded commit can be undone, and
+//! only while it is still `HEAD` and no remote has it.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::companion::CompanionStorage;
+use crate::git::GitRepo;
+use crate::log_debug;
+use crate::types::GeneratedMessage;
+
+/// A commit git-iris made, or the draft left by undoing it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitRecord {
+    /// Full commit id
+    pub hash: String,
+    pub branch: String,
+    /// Message as committed
+    pub message: String,
+    pub committed_at: DateTime<Utc>,
+}
+
+impl CommitRecord {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..7.min(self.hash.len())]
+    }
+
+    /// First line of the message
+    pub fn subject(&self) -> &str {
+        self.message.lines().next().unwrap_or("")
+    }
+
+    /// The message as an editable generated message
+    pub fn to_generated(&self) -> GeneratedMessage {
+        GeneratedMessage::from_text(&self.message)
+    }
+}
+
+/// A commit that was undone
+#[derive(Debug, Clone)]
+pub struct UndoneCommit {
+    pub record: CommitRecord,
+    /// Paths the commit changed, now staged again
+    pub files: Vec<PathBuf>,
+}
+
+/// Remember `HEAD` as the last commit git-iris made
+///
+/// Call right after committing. Failures are logged, never surfaced: a
+/// missing record only means the commit can't be undone with git-iris.
+pub fn record_commit(repo: &GitRepo) {
+    let record = (|| -> Result<CommitRecord> {
+        Ok(CommitRecord {
+            hash: repo.head_commit_id()?,
+            branch: repo.get_current_branch()?,
+            message: repo.get_head_commit_message()?,
+            committed_at: Utc::now(),
+        })
+    })();
+    let saved = record
+        .and_then(|record| CompanionStorage::new(repo.repo_path())?.save_last_commit(&record));
+    if let Err(e) = saved {
+        log_debug!("Failed to record commit for undo: {}", e);
+    }
+}
+
+/// Soft-reset the last commit git-iris made
+///
+/// With `keep_draft`, the message is saved as a draft for the next Studio
+/// session on its branch. The record of the commit is only cleared once that
+/// draft is on disk, so the message is never lost in between.
+pub fn undo_last_commit(repo: &GitRepo, keep_draft: bool) -> Result<UndoneCommit> {
+    let storage = CompanionStorage::new(repo.repo_path())?;
+    let record = storage
+        .load_last_commit()?
+        .context("No commit made by git-iris to undo")?;
+    let files = repo.undo_commit(&record.hash)?;
+    if keep_draft {
+        storage.save_draft(&record).with_context(|| {
+            format!(
+                "Undid {} but couldn't save its message as a draft; it was:\n\n{}",
+                record.short_hash(),
+                record.message
+            )
+        })?;
+    }
+    storage.clear_last_commit()?;
+    Ok(UndoneCommit { record, files })
+}
+
+/// Draft left by `git-iris undo` on `branch`, removed once taken
+pub fn take_draft(repo: &GitRepo, branch: &str) -> Option<CommitRecord> {
+    CompanionStorage::new(repo.repo_path())
+        .and_then(|storage| storage.take_draft(branch))
+        .unwrap_or_else(|e| {
+            log_debug!("Failed to load commit draft: {}", e);
+            None
+        })
+}
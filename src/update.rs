@@ -0,0 +1,392 @@
+//! Update checks and self-update
+//!
+//! With `check_for_updates = true`, Studio asks the GitHub releases API for
+//! the latest git-iris release at most once a day and mentions a newer
+//! version, with its highlights, in a notification. `git-iris self-update`
+//! replaces a downloaded binary with the latest release asset once it matches
+//! the release's `SHA256SUMS`; installs managed by cargo, Homebrew or a
+//! system package manager are pointed at their own upgrade command instead.
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Latest release of git-iris
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/hyperb1iss/git-iris/releases/latest";
+
+/// How long a cached release lookup is trusted
+const CHECK_INTERVAL_HOURS: i64 = 24;
+
+/// Request timeout for the release lookup
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// Timeout for downloading a release binary
+const DOWNLOAD_TIMEOUT_SECS: u64 = 300;
+
+/// Release asset listing the SHA-256 of every other asset
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// Most highlights quoted from release notes
+const MAX_HIGHLIGHTS: usize = 3;
+
+/// Version of the running binary
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A published release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub html_url: String,
+    /// Release notes (markdown)
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// A file attached to a release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// Version without a leading `v`
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    /// Whether this release is newer than the running binary
+    pub fn is_newer(&self) -> bool {
+        is_newer(self.version(), CURRENT_VERSION)
+    }
+
+    /// Headline changes from the release notes: the `###` headings (the
+    /// Highlights section), else the first bullets
+    pub fn highlights(&self) -> Vec<String> {
+        let clean = |line: &str| {
+            line.trim_start_matches(['#', '-', '*', ' '])
+                .replace("**", "")
+                .trim()
+                .to_string()
+        };
+        let headings: Vec<String> = self
+            .body
+            .lines()
+            .filter(|line| line.starts_with("### "))
+            .map(clean)
+            .filter(|line| !line.is_empty())
+            .take(MAX_HIGHLIGHTS)
+            .collect();
+        if !headings.is_empty() {
+            return headings;
+        }
+        self.body
+            .lines()
+            .filter(|line| line.starts_with("- ") || line.starts_with("* "))
+            .map(clean)
+            .filter(|line| !line.is_empty())
+            .take(MAX_HIGHLIGHTS)
+            .collect()
+    }
+
+    /// Binary asset for the platform this build targets
+    pub fn platform_asset(&self) -> Option<&ReleaseAsset> {
+        let name = platform_asset_name()?;
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+
+    /// `SHA256SUMS` asset, in `sha256sum` format
+    pub fn checksums_asset(&self) -> Option<&ReleaseAsset> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name == CHECKSUMS_ASSET)
+    }
+}
+
+/// Checksum `sums` (`sha256sum` output) lists for the file `name`
+pub fn expected_checksum(sums: &str, name: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let (hash, file) = line.trim().split_once(char::is_whitespace)?;
+        // Binary mode marks the name with `*`
+        let file = file.trim_start().trim_start_matches('*');
+        (file == name).then(|| hash.to_ascii_lowercase())
+    })
+}
+
+/// Lowercase hex SHA-256 of `bytes`
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Whether `candidate` is a later `major.minor.patch` than `current`
+///
+/// Pre-release and build suffixes are ignored, so `2.0.0-rc.1` counts as
+/// `2.0.0`.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Option<(u64, u64, u64)> {
+        let core = version.trim_start_matches('v').split(['-', '+']).next()?;
+        let mut numbers = core.split('.').map(str::parse::<u64>);
+        Some((
+            numbers.next()?.ok()?,
+            numbers.next().unwrap_or(Ok(0)).ok()?,
+            numbers.next().unwrap_or(Ok(0)).ok()?,
+        ))
+    }
+    match (parts(candidate), parts(current)) {
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+/// Release asset name for this platform, matching the CI build names
+pub fn platform_asset_name() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Some("git-iris-linux-amd64"),
+        ("linux", "aarch64") => Some("git-iris-linux-arm64"),
+        ("macos", "aarch64") => Some("git-iris-macos-arm64"),
+        ("windows", "x86_64") => Some("git-iris-windows-gnu.exe"),
+        _ => None,
+    }
+}
+
+fn client(timeout_secs: u64) -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout_secs))
+        .user_agent(concat!("git-iris/", env!("CARGO_PKG_VERSION")))
+        .build()?)
+}
+
+/// Ask GitHub for the latest release
+pub async fn fetch_latest_release() -> Result<Release> {
+    let response = client(REQUEST_TIMEOUT_SECS)?
+        .get(LATEST_RELEASE_URL)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .context("Failed to reach GitHub")?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!("GitHub returned {status} for the latest release");
+    }
+    response.json().await.context("Invalid release response")
+}
+
+/// Cached result of the last lookup
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedRelease {
+    checked_at: DateTime<Utc>,
+    release: Release,
+}
+
+fn cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("git-iris").join("latest-release.json"))
+}
+
+/// Latest release if it's newer than the running binary, looking it up at
+/// most once per check interval
+pub async fn check_for_update() -> Result<Option<Release>> {
+    let cached = cache_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<CachedRelease>(&content).ok())
+        .filter(|cached| Utc::now() - cached.checked_at < Duration::hours(CHECK_INTERVAL_HOURS));
+
+    if let Some(cached) = cached {
+        return Ok(cached.release.is_newer().then_some(cached.release));
+    }
+
+    let release = fetch_latest_release().await?;
+    if let Some(path) = cache_path() {
+        let cached = CachedRelease {
+            checked_at: Utc::now(),
+            release: release.clone(),
+        };
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(e) = std::fs::write(&path, serde_json::to_string_pretty(&cached)?) {
+            crate::log_debug!("Release cache not saved: {}", e);
+        }
+    }
+    Ok(release.is_newer().then_some(release))
+}
+
+/// One-line notice for an available update, with its highlights
+pub fn update_notice(release: &Release) -> String {
+    let highlights = release.highlights();
+    let mut notice = format!("git-iris {} is available", release.tag_name);
+    if !highlights.is_empty() {
+        notice.push_str(": ");
+        notice.push_str(&highlights.join(" · "));
+    }
+    notice.push_str(" (git-iris self-update)");
+    notice
+}
+
+/// Upgrade command for installs a package manager owns, based on where the
+/// binary lives
+pub fn managed_upgrade_command(exe: &Path) -> Option<&'static str> {
+    let path = exe.to_string_lossy();
+    if path.contains("/.cargo/bin/") || path.contains("\\.cargo\\bin\\") {
+        Some("cargo install git-iris")
+    } else if path.contains("/Cellar/") || path.starts_with("/opt/homebrew/") {
+        Some("brew upgrade git-iris")
+    } else if path.starts_with("/usr/bin/") || path.starts_with("/bin/") {
+        Some("your system package manager (apt, dnf, …)")
+    } else {
+        None
+    }
+}
+
+/// Replace the running binary with the release's asset for this platform
+///
+/// The download is checked against the release's `SHA256SUMS` first and
+/// nothing is replaced when it doesn't match. It then goes to a temporary
+/// file next to the binary, which is renamed over it, so a failed write
+/// leaves the old binary in place.
+pub async fn self_update(release: &Release) -> Result<PathBuf> {
+    crate::read_only::ensure_writable("Updating git-iris")?;
+    let exe = std::env::current_exe().context("Can't locate the running binary")?;
+    let exe = exe.canonicalize().unwrap_or(exe);
+    if let Some(command) = managed_upgrade_command(&exe) {
+        bail!(
+            "{} is managed by a package manager; upgrade with {command}",
+            exe.display()
+        );
+    }
+    let Some(asset) = release.platform_asset() else {
+        bail!(
+            "Release {} has no binary for {}-{}; download it from {}",
+            release.tag_name,
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            release.html_url
+        );
+    };
+
+    let Some(checksums) = release.checksums_asset() else {
+        bail!(
+            "Release {} publishes no {CHECKSUMS_ASSET}, so its binary can't be verified; download it from {}",
+            release.tag_name,
+            release.html_url
+        );
+    };
+    let sums = download(checksums, REQUEST_TIMEOUT_SECS).await?;
+    let Some(expected) = expected_checksum(&String::from_utf8_lossy(&sums), &asset.name) else {
+        bail!(
+            "{CHECKSUMS_ASSET} of {} has no entry for {}",
+            release.tag_name,
+            asset.name
+        );
+    };
+
+    let bytes = download(asset, DOWNLOAD_TIMEOUT_SECS).await?;
+    let actual = sha256_hex(&bytes);
+    if actual != expected {
+        bail!(
+            "Downloaded {} doesn't match {CHECKSUMS_ASSET} (expected {expected}, got {actual}); the binary was left unchanged",
+            asset.name
+        );
+    }
+
+    let staged = exe.with_extension("update");
+    std::fs::write(&staged, &bytes)
+        .with_context(|| format!("Failed to write {}", staged.display()))?;
+    replace_binary(&staged, &exe)?;
+    Ok(exe)
+}
+
+/// Download a release asset
+async fn download(asset: &ReleaseAsset, timeout_secs: u64) -> Result<Vec<u8>> {
+    let response = client(timeout_secs)?
+        .get(&asset.browser_download_url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to download {}", asset.name))?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!("Download of {} failed: {status}", asset.name);
+    }
+    let bytes = response.bytes().await.context("Download interrupted")?;
+    if bytes.is_empty() {
+        bail!("Downloaded {} is empty", asset.name);
+    }
+    Ok(bytes.to_vec())
+}
+
+#[cfg(unix)]
+fn replace_binary(staged: &Path, exe: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(staged, std::fs::Permissions::from_mode(0o755))?;
+    std::fs::rename(staged, exe).with_context(|| format!("Failed to replace {}", exe.display()))
+}
+
+#[cfg(not(unix))]
+fn replace_binary(staged: &Path, exe: &Path) -> Result<()> {
+    // A running executable can't be overwritten on Windows, but it can be
+    // moved aside
+    let old = exe.with_extension("old");
+    let _ = std::fs::remove_file(&old);
+    std::fs::rename(exe, &old)
+        .with_context(|| format!("Failed to move {} aside", exe.display()))?;
+    std::fs::rename(staged, exe).with_context(|| format!("Failed to replace {}", exe.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_comparison_and_highlights() {
+        assert!(is_newer("2.0.0", "1.99.0"));
+        assert!(is_newer("v1.99.1", "1.99.0"));
+        assert!(!is_newer("1.99.0", "1.99.0"));
+        assert!(!is_newer("2.0.0-rc.1", "2.0.0"));
+        assert!(!is_newer("nightly", "1.99.0"));
+
+        let release = Release {
+            tag_name: "v2.0.0".to_string(),
+            html_url: "https://github.com/hyperb1iss/git-iris/releases/tag/v2.0.0".to_string(),
+            body: "# Release Notes v2.0.0\n\n## Highlights\n\n### **Stash Mode**\nBrowse stashes.\n\n### Ollama Provider\nLocal models.\n\n## Fixes\n\n- Fixed a crash\n".to_string(),
+            assets: Vec::new(),
+        };
+        assert_eq!(release.highlights(), ["Stash Mode", "Ollama Provider"]);
+        assert_eq!(
+            update_notice(&release),
+            "git-iris v2.0.0 is available: Stash Mode · Ollama Provider (git-iris self-update)"
+        );
+        assert_eq!(
+            managed_upgrade_command(Path::new("/home/me/.cargo/bin/git-iris")),
+            Some("cargo install git-iris")
+        );
+        assert_eq!(
+            managed_upgrade_command(Path::new("/home/me/bin/git-iris")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_checksum_lookup() {
+        let binary = b"new git-iris";
+        let hash = sha256_hex(binary);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        let sums = format!(
+            "{}  git-iris-macos-arm64\n{hash} *git-iris-linux-amd64\n",
+            "0".repeat(64)
+        );
+        assert_eq!(expected_checksum(&sums, "git-iris-linux-amd64"), Some(hash));
+        assert_eq!(
+            expected_checksum(&sums, "git-iris-macos-arm64"),
+            Some("0".repeat(64))
+        );
+        assert_eq!(expected_checksum(&sums, "git-iris-linux-arm64"), None);
+    }
+}
@@ -0,0 +1,238 @@
+//! Upgrade guides for releases with breaking changes
+//!
+//! With `release-notes --upgrade-guide`, each removed piece of public API
+//! that breaking change detection finds is paired with the diff hunk it was
+//! removed in. The hunk gives a before snippet (context and removed lines)
+//! and an after snippet (context and added lines), so the guide Iris writes
+//! shows real code from the release rather than invented examples. If the
+//! notes come back without an Upgrade Guide section, one is built from the
+//! snippets alone.
+
+use std::path::Path;
+
+use crate::context::StagedFile;
+use crate::git::BreakingChange;
+
+/// Heading of the generated section
+const SECTION_HEADING: &str = "## Upgrade Guide";
+
+/// Directory `--write-upgrade-guide` writes guides to
+pub const GUIDE_DIR: &str = "docs/upgrading";
+
+/// Context lines kept above the removed line
+const CONTEXT_LINES: usize = 3;
+
+/// Most lines in one snippet
+const MAX_SNIPPET_LINES: usize = 15;
+
+/// One breaking change with the code around it before and after the release
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradeStep {
+    pub change: BreakingChange,
+    pub before: String,
+    /// Empty when the hunk only removed code
+    pub after: String,
+    /// Code fence language for the snippets
+    pub language: &'static str,
+}
+
+/// Upgrade steps for a release
+#[derive(Debug, Clone, Default)]
+pub struct UpgradeGuide {
+    pub steps: Vec<UpgradeStep>,
+}
+
+impl UpgradeGuide {
+    /// Pair each breaking change with the hunk of `files` it was removed in
+    pub fn from_diffs(files: &[StagedFile], changes: &[BreakingChange]) -> Self {
+        let steps = changes
+            .iter()
+            .filter_map(|change| {
+                let file = files.iter().find(|file| file.path == change.path)?;
+                let (before, after) = snippets(&file.diff, &change.symbol)?;
+                Some(UpgradeStep {
+                    change: change.clone(),
+                    before,
+                    after,
+                    language: fence_language(&change.path),
+                })
+            })
+            .collect();
+        Self { steps }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Instructions appended to release notes prompts
+    pub fn prompt(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let mut prompt = format!(
+            "\n\n{SECTION_HEADING}\nThis release breaks public API. End the release notes with a `{SECTION_HEADING}` section: one `###` subsection per change below saying who is affected and what to do, with a before and an after code block. Base the snippets on the diff excerpts below and trim them to the lines that matter; when a change has no replacement in its file, find where the functionality moved (or say it was removed) before writing the after block.\n"
+        );
+        for step in &self.steps {
+            prompt.push_str(&format!("\n### {}\n", step.change));
+            prompt.push_str(&code_block("Before", step.language, &step.before));
+            if !step.after.is_empty() {
+                prompt.push_str(&code_block("After", step.language, &step.after));
+            }
+        }
+        prompt
+    }
+
+    /// The section built from the snippets alone
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "{SECTION_HEADING}\n\nThis release removes public API. Update code that uses the items below.\n"
+        );
+        for step in &self.steps {
+            out.push_str(&format!("\n### {}\n", step.change));
+            out.push_str(&code_block("Before", step.language, &step.before));
+            if step.after.is_empty() {
+                out.push_str("\nRemoved with no replacement in this file.\n");
+            } else {
+                out.push_str(&code_block("After", step.language, &step.after));
+            }
+        }
+        out
+    }
+
+    /// Append the section to release notes unless they already have one
+    pub fn append_to(&self, content: &str) -> String {
+        if self.is_empty() || section(content).is_some() {
+            return content.to_string();
+        }
+        format!("{}\n\n{}", content.trim_end(), self.to_markdown())
+    }
+}
+
+/// The Upgrade Guide section of release notes, up to the next heading of the
+/// same or a higher level
+pub fn section(content: &str) -> Option<String> {
+    let mut lines = content.lines();
+    let heading = lines.find(|line| is_guide_heading(line))?;
+    let level = heading_level(heading)?;
+    let mut out = vec![heading];
+    out.extend(lines.take_while(|line| heading_level(line).is_none_or(|l| l > level)));
+    Some(out.join("\n").trim_end().to_string() + "\n")
+}
+
+/// Where `--write-upgrade-guide` writes the guide for `version`
+pub fn guide_path(version: &str) -> String {
+    let version = version.trim().replace(['/', '\\'], "-");
+    format!("{GUIDE_DIR}/{version}.md")
+}
+
+fn is_guide_heading(line: &str) -> bool {
+    heading_level(line).is_some()
+        && line
+            .trim_start_matches('#')
+            .trim()
+            .eq_ignore_ascii_case("upgrade guide")
+}
+
+/// Level of a markdown heading line, `None` for other lines
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    (level > 0 && line[level..].starts_with(' ')).then_some(level)
+}
+
+fn code_block(label: &str, language: &str, code: &str) -> String {
+    format!("\n{label}:\n\n```{language}\n{}\n```\n", code.trim_end())
+}
+
+/// Before and after versions of the hunk lines around the first removed
+/// line mentioning `symbol`
+fn snippets(diff: &str, symbol: &str) -> Option<(String, String)> {
+    let diff = format!("\n{diff}");
+    let hunks = diff
+        .split("\n@@")
+        .skip(1)
+        .map(|hunk| hunk.lines().skip(1).collect::<Vec<_>>());
+    for lines in hunks {
+        let Some(index) = lines
+            .iter()
+            .position(|line| line.starts_with('-') && line.contains(symbol))
+        else {
+            continue;
+        };
+        let window = &lines[index.saturating_sub(CONTEXT_LINES)..];
+        let side = |skip: char| {
+            window
+                .iter()
+                .filter(|line| !line.starts_with(skip) && !line.starts_with('\\'))
+                .map(|line| line.get(1..).unwrap_or_default())
+                .take(MAX_SNIPPET_LINES)
+                .collect::<Vec<_>>()
+        };
+        let before = side('+').join("\n");
+        let after = side('-');
+        let changed = window
+            .iter()
+            .any(|line| line.starts_with('+') && !line.starts_with("+++"));
+        let after = if changed {
+            after.join("\n")
+        } else {
+            String::new()
+        };
+        return Some((before, after));
+    }
+    None
+}
+
+fn fence_language(path: &str) -> &'static str {
+    match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+        Some("rs") => "rust",
+        Some("ts" | "tsx" | "mts" | "cts") => "typescript",
+        Some("js" | "jsx" | "mjs" | "cjs") => "javascript",
+        Some("proto") => "protobuf",
+        Some("graphql" | "gql") => "graphql",
+        Some("json" | "avsc") => "json",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ChangeType;
+    use crate::git::detect_breaking_changes;
+
+    #[test]
+    fn test_guide_from_breaking_changes() {
+        let diff = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,6 +1,6 @@\n use std::io;\n \n-pub fn connect(url: &str) -> Client {\n-    Client::new(url)\n+pub fn open(options: Options) -> Client {\n+    Client::with_options(options)\n }\n";
+        let files = [StagedFile {
+            path: "src/lib.rs".to_string(),
+            change_type: ChangeType::Modified,
+            diff: diff.to_string(),
+            content: None,
+            content_excluded: false,
+        }];
+        let guide = UpgradeGuide::from_diffs(&files, &detect_breaking_changes(&files));
+        assert_eq!(guide.steps.len(), 1);
+        let step = &guide.steps[0];
+        assert_eq!(
+            step.before,
+            "use std::io;\n\npub fn connect(url: &str) -> Client {\n    Client::new(url)\n}"
+        );
+        assert!(step.after.contains("pub fn open(options: Options)"));
+        assert_eq!(step.language, "rust");
+
+        let notes = guide.append_to("# Release Notes v2.0.0\n\n## Highlights\n\nNew API.\n");
+        let extracted = section(&notes).expect("section");
+        assert!(extracted.starts_with("## Upgrade Guide\n"));
+        assert!(extracted.contains("### removed `pub fn connect` from src/lib.rs"));
+        assert!(extracted.contains("After:\n\n```rust\nuse std::io;\n\npub fn open"));
+        assert_eq!(guide.append_to(&notes), notes);
+
+        let written = "## Upgrade Guide\n\n### Rename\n\nDo it.\n\n## Contributors\n\n- a\n";
+        assert_eq!(
+            section(written).as_deref(),
+            Some("## Upgrade Guide\n\n### Rename\n\nDo it.\n")
+        );
+        assert_eq!(guide_path("v2.0.0"), "docs/upgrading/v2.0.0.md");
+    }
+}
@@ -0,0 +1,380 @@
+//! Token and cost accounting
+//!
+//! Every agent call appends an entry to a per-repository ledger at
+//! `.git/iris/usage.jsonl` with the tokens the provider reported and an
+//! estimated cost. Prices are per million tokens, from a built-in table that
+//! `[pricing]` in the config overrides; local Ollama models are free, and
+//! models with no known price are recorded without a cost. `git-iris usage`
+//! summarizes the ledger, and Studio shows the session's running total in
+//! the status bar.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::hash::BuildHasher;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::agents::ContextReport;
+use crate::git::GitRepo;
+use crate::providers::Provider;
+
+/// Ledger file name inside `.git/iris/`
+const LEDGER_FILE: &str = "usage.jsonl";
+
+/// USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+}
+
+impl ModelPrice {
+    const fn new(input: f64, output: f64) -> Self {
+        Self { input, output }
+    }
+
+    /// Cost of a call in USD
+    pub fn cost(&self, input_tokens: u64, output_tokens: u64) -> f64 {
+        (per_token(input_tokens) * self.input + per_token(output_tokens) * self.output)
+            / 1_000_000.0
+    }
+}
+
+/// Token count as a float; counts never get near f64's integer precision
+fn per_token(tokens: u64) -> f64 {
+    u32::try_from(tokens).map_or(f64::from(u32::MAX), f64::from)
+}
+
+/// Published prices by model name prefix; the longest matching prefix wins
+const PRICES: &[(&str, ModelPrice)] = &[
+    ("claude-opus-4-5", ModelPrice::new(5.0, 25.0)),
+    ("claude-opus-4", ModelPrice::new(15.0, 75.0)),
+    ("claude-sonnet-4", ModelPrice::new(3.0, 15.0)),
+    ("claude-3-7-sonnet", ModelPrice::new(3.0, 15.0)),
+    ("claude-haiku-4-5", ModelPrice::new(1.0, 5.0)),
+    ("claude-3-5-haiku", ModelPrice::new(0.8, 4.0)),
+    ("gpt-5", ModelPrice::new(1.25, 10.0)),
+    ("gpt-5-mini", ModelPrice::new(0.25, 2.0)),
+    ("gpt-5.1-mini", ModelPrice::new(0.25, 2.0)),
+    ("gpt-5-nano", ModelPrice::new(0.05, 0.4)),
+    ("gpt-4.1", ModelPrice::new(2.0, 8.0)),
+    ("gpt-4.1-mini", ModelPrice::new(0.4, 1.6)),
+    ("gpt-4o", ModelPrice::new(2.5, 10.0)),
+    ("gpt-4o-mini", ModelPrice::new(0.15, 0.6)),
+    ("gemini-3-pro", ModelPrice::new(2.0, 12.0)),
+    ("gemini-2.5-pro", ModelPrice::new(1.25, 10.0)),
+    ("gemini-2.5-flash", ModelPrice::new(0.3, 2.5)),
+    ("gemini-2.5-flash-lite", ModelPrice::new(0.1, 0.4)),
+];
+
+/// Price for a model: a `[pricing]` entry, else the built-in table
+///
+/// Ollama runs locally and costs nothing.
+pub fn price_for<S: BuildHasher>(
+    overrides: &HashMap<String, ModelPrice, S>,
+    provider: &str,
+    model: &str,
+) -> Option<ModelPrice> {
+    if let Some(price) = overrides.get(model) {
+        return Some(*price);
+    }
+    if provider == Provider::Ollama.name() {
+        return Some(ModelPrice::new(0.0, 0.0));
+    }
+    PRICES
+        .iter()
+        .filter(|(prefix, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, price)| *price)
+}
+
+/// One agent call in the ledger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub at: DateTime<Utc>,
+    pub capability: String,
+    pub provider: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Estimated cost in USD; `None` when the model's price is unknown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+}
+
+impl LedgerEntry {
+    /// Entry for a finished call, preferring the provider's billed input
+    /// count over the local estimate
+    pub fn from_report<S: BuildHasher>(
+        report: &ContextReport,
+        provider: &str,
+        overrides: &HashMap<String, ModelPrice, S>,
+    ) -> Self {
+        let input_tokens = if report.billed_input_tokens > 0 {
+            report.billed_input_tokens
+        } else {
+            u64::try_from(report.input_tokens()).unwrap_or(u64::MAX)
+        };
+        let output_tokens = u64::try_from(report.output_tokens).unwrap_or(u64::MAX);
+        Self {
+            at: report.created_at,
+            capability: report.capability.clone(),
+            provider: provider.to_string(),
+            model: report.model.clone(),
+            input_tokens,
+            output_tokens,
+            cost_usd: price_for(overrides, provider, &report.model)
+                .map(|price| price.cost(input_tokens, output_tokens)),
+        }
+    }
+
+    pub fn tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+/// The usage ledger of one repository
+pub struct Ledger {
+    path: PathBuf,
+}
+
+impl Ledger {
+    /// Ledger under the repository's git directory, shared by its worktrees
+    pub fn for_repo(repo: &GitRepo) -> Result<Self> {
+        let git = repo.open_repo()?;
+        Ok(Self::at(git.commondir().join("iris").join(LEDGER_FILE)))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, entry: &LedgerEntry) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    /// Entries since `since` (all for `None`), oldest first; unreadable
+    /// lines are skipped
+    pub fn load(&self, since: Option<DateTime<Utc>>) -> Result<Vec<LedgerEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<LedgerEntry>(line).ok())
+            .filter(|entry| since.is_none_or(|since| entry.at >= since))
+            .collect())
+    }
+}
+
+/// Tokens and cost added up
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UsageTotals {
+    pub calls: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    /// Calls whose model had no known price
+    pub unpriced_calls: usize,
+}
+
+impl UsageTotals {
+    pub fn add(&mut self, entry: &LedgerEntry) {
+        self.calls += 1;
+        self.input_tokens += entry.input_tokens;
+        self.output_tokens += entry.output_tokens;
+        match entry.cost_usd {
+            Some(cost) => self.cost_usd += cost,
+            None => self.unpriced_calls += 1,
+        }
+    }
+
+    pub fn tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+
+    /// `$0.42`, with `+` when some calls couldn't be priced
+    pub fn cost_label(&self) -> String {
+        let plus = if self.unpriced_calls > 0 { "+" } else { "" };
+        format!("${:.2}{plus}", self.cost_usd)
+    }
+
+    /// Compact label for the Studio status bar: `12.3k tok · $0.04`
+    pub fn status_label(&self) -> String {
+        format!("{} tok · {}", compact(self.tokens()), self.cost_label())
+    }
+}
+
+/// `950`, `12.3k`, `1.2M`
+pub fn compact(tokens: u64) -> String {
+    let scaled = |divisor: u64, suffix: &str| {
+        let tenths = tokens * 10 / divisor;
+        format!("{}.{}{suffix}", tenths / 10, tenths % 10)
+    };
+    match tokens {
+        0..1_000 => tokens.to_string(),
+        1_000..1_000_000 => scaled(1_000, "k"),
+        _ => scaled(1_000_000, "M"),
+    }
+}
+
+/// Ledger totals by capability, model and day
+#[derive(Debug, Clone, Default)]
+pub struct UsageReport {
+    pub total: UsageTotals,
+    pub by_capability: BTreeMap<String, UsageTotals>,
+    pub by_model: BTreeMap<String, UsageTotals>,
+    pub by_day: BTreeMap<String, UsageTotals>,
+}
+
+impl UsageReport {
+    pub fn from_entries(entries: &[LedgerEntry]) -> Self {
+        let mut report = Self::default();
+        for entry in entries {
+            report.total.add(entry);
+            report
+                .by_capability
+                .entry(entry.capability.clone())
+                .or_default()
+                .add(entry);
+            report
+                .by_model
+                .entry(format!("{} ({})", entry.model, entry.provider))
+                .or_default()
+                .add(entry);
+            report
+                .by_day
+                .entry(entry.at.format("%Y-%m-%d").to_string())
+                .or_default()
+                .add(entry);
+        }
+        report
+    }
+
+    /// Render the report as markdown
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# Token Usage\n\n");
+        md.push_str(&format!(
+            "{} calls, {} tokens ({} in, {} out), estimated cost {}.\n",
+            self.total.calls,
+            compact(self.total.tokens()),
+            compact(self.total.input_tokens),
+            compact(self.total.output_tokens),
+            self.total.cost_label()
+        ));
+        if self.total.unpriced_calls > 0 {
+            md.push_str(&format!(
+                "\n{} calls used models without a known price; add them under `[pricing]` to include them.\n",
+                self.total.unpriced_calls
+            ));
+        }
+
+        push_table(
+            &mut md,
+            "By Capability",
+            "Capability",
+            by_cost(&self.by_capability),
+        );
+        push_table(&mut md, "By Model", "Model", by_cost(&self.by_model));
+        push_table(&mut md, "By Day", "Day", self.by_day.iter().rev().collect());
+        md
+    }
+}
+
+/// Rows of a breakdown, most expensive first
+fn by_cost(map: &BTreeMap<String, UsageTotals>) -> Vec<(&String, &UsageTotals)> {
+    let mut rows: Vec<(&String, &UsageTotals)> = map.iter().collect();
+    rows.sort_by(|a, b| b.1.cost_usd.total_cmp(&a.1.cost_usd).then(a.0.cmp(b.0)));
+    rows
+}
+
+fn push_table(md: &mut String, heading: &str, column: &str, rows: Vec<(&String, &UsageTotals)>) {
+    md.push_str(&format!(
+        "\n## {heading}\n\n| {column} | Calls | Input | Output | Cost |\n| --- | --- | --- | --- | --- |\n"
+    ));
+    for (name, totals) in rows {
+        md.push_str(&format!(
+            "| {name} | {} | {} | {} | {} |\n",
+            totals.calls,
+            compact(totals.input_tokens),
+            compact(totals.output_tokens),
+            totals.cost_label()
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(capability: &str, model: &str, input: u64, output: u64) -> LedgerEntry {
+        LedgerEntry {
+            at: Utc::now(),
+            capability: capability.to_string(),
+            provider: "anthropic".to_string(),
+            model: model.to_string(),
+            input_tokens: input,
+            output_tokens: output,
+            cost_usd: price_for(&HashMap::new(), "anthropic", model)
+                .map(|price| price.cost(input, output)),
+        }
+    }
+
+    #[test]
+    fn test_prices_and_ledger_totals() {
+        let none = HashMap::new();
+        let sonnet = price_for(&none, "anthropic", "claude-sonnet-4-5-20250929").expect("price");
+        assert!((sonnet.cost(1_000_000, 100_000) - 4.5).abs() < 1e-9);
+        assert_eq!(
+            price_for(&none, "openai", "gpt-5.1-mini"),
+            Some(ModelPrice::new(0.25, 2.0))
+        );
+        assert_eq!(
+            price_for(&none, "ollama", "qwen2.5-coder:7b"),
+            Some(ModelPrice::new(0.0, 0.0))
+        );
+        assert_eq!(price_for(&none, "openai", "o9-experimental"), None);
+        let overrides = HashMap::from([("o9-experimental".to_string(), ModelPrice::new(1.0, 1.0))]);
+        assert!(price_for(&overrides, "openai", "o9-experimental").is_some());
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let ledger = Ledger::at(dir.path().join("iris").join(LEDGER_FILE));
+        for entry in [
+            entry("commit", "claude-sonnet-4-5-20250929", 12_000, 300),
+            entry("review", "claude-sonnet-4-5-20250929", 40_000, 2_000),
+            entry("commit", "mystery-model", 1_000, 100),
+        ] {
+            ledger.append(&entry).expect("append");
+        }
+        let entries = ledger.load(None).expect("load");
+        let report = UsageReport::from_entries(&entries);
+        assert_eq!(report.total.calls, 3);
+        assert_eq!(report.total.tokens(), 55_400);
+        assert_eq!(report.total.unpriced_calls, 1);
+        assert_eq!(report.by_capability["commit"].calls, 2);
+        assert_eq!(report.total.status_label(), "55.4k tok · $0.19+");
+        assert!(
+            report
+                .to_markdown()
+                .contains("| review | 1 | 40.0k | 2.0k | $0.15 |")
+        );
+    }
+}
@@ -171,7 +171,10 @@ async fn test_agent_setup_service_from_temp_dir() {
 fn test_git_tools_exist() {
     // Test that our Git tools are available and have proper types
     let _git_status = GitStatus;
-    let _git_diff = GitDiff;
+    let _git_diff = GitDiff::new(
+        &std::sync::Arc::default(),
+        git_iris::agents::context::budget::DEFAULT_DIFF_BUDGET,
+    );
     let _git_log = GitLog;
     let _git_repo_info = GitRepoInfo;
     let _git_changed_files = GitChangedFiles;
@@ -1,7 +1,8 @@
 use git_iris::context::ChangeType;
+use git_iris::git::GitRepo;
 use git2::Repository;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // Use our centralized test infrastructure
 #[path = "test_utils.rs"]
@@ -211,3 +212,169 @@ async fn test_binary_file() {
     // Check if the status is correct
     assert!(matches!(binary_file.change_type, ChangeType::Added));
 }
+
+#[test]
+fn test_undo_commit_keeps_changes_staged() {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir).expect("Failed to create GitTestHelper");
+    let initial = git_repo.head_commit_id().expect("Failed to read HEAD");
+
+    helper
+        .create_and_stage_file("undo_me.txt", "Undo me")
+        .expect("Failed to create and stage file");
+    let commit = helper.commit("Commit to undo").expect("Failed to commit");
+
+    // Only the expected commit can be undone
+    assert!(git_repo.undo_commit(&initial).is_err());
+
+    // A commit a remote has is never undone
+    let repo = Repository::open(temp_dir.path()).expect("Failed to open repository");
+    repo.reference("refs/remotes/origin/main", commit, true, "push")
+        .expect("Failed to create remote ref");
+    let err = git_repo
+        .undo_commit(&commit.to_string())
+        .expect_err("Pushed commit should not be undone");
+    assert!(err.to_string().contains("origin/main"));
+    repo.find_reference("refs/remotes/origin/main")
+        .and_then(|mut r| r.delete())
+        .expect("Failed to delete remote ref");
+
+    let files = git_repo
+        .undo_commit(&commit.to_string())
+        .expect("Failed to undo commit");
+    assert_eq!(files, [Path::new("undo_me.txt")]);
+    assert_eq!(git_repo.head_commit_id().expect("HEAD"), initial);
+
+    let context = git_repo
+        .get_git_info(&MockDataBuilder::config())
+        .expect("Failed to get git info");
+    assert_eq!(context.staged_files.len(), 1);
+    assert_eq!(context.staged_files[0].path, "undo_me.txt");
+}
+
+#[test]
+fn test_staged_patch_restages_exactly_what_was_staged() {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir).expect("Failed to create GitTestHelper");
+    let lines = "one\ntwo\nthree\nfour\nfive\nsix\nseven\n";
+    helper
+        .create_and_stage_file("queued.txt", lines)
+        .expect("Failed to create and stage file");
+    helper.commit("Add queued file").expect("Failed to commit");
+    let files = [PathBuf::from("queued.txt")];
+    let path = temp_dir.path().join("queued.txt");
+
+    // The first change is staged, the second only in the working tree
+    fs::write(&path, lines.replace("one", "ONE")).expect("Failed to write file");
+    git_repo
+        .stage_file(&files[0])
+        .expect("Failed to stage file");
+    fs::write(&path, lines.replace("one", "ONE").replace("seven", "SEVEN"))
+        .expect("Failed to write file");
+    let first = git_repo.staged_patch(&files).expect("Failed to read patch");
+
+    // The second change queued on its own, against the same HEAD
+    fs::write(&path, lines.replace("seven", "SEVEN")).expect("Failed to write file");
+    git_repo
+        .stage_file(&files[0])
+        .expect("Failed to stage file");
+    let second = git_repo.staged_patch(&files).expect("Failed to read patch");
+
+    // Edited again after queueing
+    fs::write(&path, format!("{}eight\n", lines.replace("seven", "SEVEN")))
+        .expect("Failed to write file");
+
+    let committed = || {
+        git_repo
+            .read_committed_file("HEAD", "queued.txt")
+            .expect("Failed to read committed file")
+            .map(|content| String::from_utf8(content).expect("UTF-8 content"))
+    };
+    git_repo.stage_patch(&first).expect("Failed to stage patch");
+    git_repo.commit("First").expect("Failed to commit");
+    assert_eq!(committed(), Some(lines.replace("one", "ONE")));
+
+    git_repo
+        .stage_patch(&second)
+        .expect("Failed to stage patch");
+    git_repo.commit("Second").expect("Failed to commit");
+    assert_eq!(
+        committed(),
+        Some(lines.replace("one", "ONE").replace("seven", "SEVEN"))
+    );
+    assert!(
+        fs::read_to_string(&path)
+            .expect("Failed to read file")
+            .ends_with("eight\n")
+    );
+
+    // Before the first commit the patch is staged onto an empty index
+    let unborn_dir = tempfile::TempDir::new().expect("Failed to create temporary directory");
+    Repository::init(unborn_dir.path()).expect("Failed to initialize repository");
+    fs::write(unborn_dir.path().join("first.txt"), "first\n").expect("Failed to write file");
+    let unborn = GitRepo::new(unborn_dir.path()).expect("Failed to open repository");
+    let first_files = [PathBuf::from("first.txt")];
+    unborn
+        .stage_file(&first_files[0])
+        .expect("Failed to stage file");
+    let unborn_patch = unborn
+        .staged_patch(&first_files)
+        .expect("Failed to read patch");
+    unborn
+        .stage_patch(&unborn_patch)
+        .expect("Failed to stage patch");
+    assert!(
+        unborn
+            .get_staged_diff_full()
+            .expect("Failed to read staged diff")
+            .contains("+first")
+    );
+}
+
+#[test]
+fn test_bare_repository_reads_history_but_not_work_tree() {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir).expect("Failed to create GitTestHelper");
+    helper
+        .create_and_stage_file("second.txt", "Second")
+        .expect("Failed to create and stage file");
+    helper.commit("Add second file").expect("Failed to commit");
+
+    // Opening from a subdirectory finds the work tree root
+    let subdir = temp_dir.path().join("nested");
+    fs::create_dir(&subdir).expect("Failed to create subdirectory");
+    let from_subdir = GitRepo::new(&subdir).expect("Failed to open from subdirectory");
+    assert_eq!(from_subdir.repo_path(), git_repo.repo_path());
+
+    let bare_dir = tempfile::TempDir::new().expect("Failed to create temporary directory");
+    let bare_path = bare_dir.path().join("repo.git");
+    git2::build::RepoBuilder::new()
+        .bare(true)
+        .clone(temp_dir.path().to_str().expect("UTF-8 path"), &bare_path)
+        .expect("Failed to clone bare repository");
+
+    let bare = GitRepo::new(&bare_path).expect("Failed to open bare repository");
+    assert!(bare.is_bare());
+    assert!(!git_repo.is_bare());
+
+    let files = bare
+        .get_commit_range_files("HEAD~1", "HEAD")
+        .expect("Ranges work without a work tree");
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path, "second.txt");
+    assert_eq!(
+        bare.read_committed_file("HEAD", "second.txt")
+            .expect("Failed to read committed file"),
+        Some(b"Second".to_vec())
+    );
+    assert_eq!(
+        bare.read_committed_file("HEAD", "missing.txt")
+            .expect("Missing files are not an error"),
+        None
+    );
+
+    let err = bare
+        .extract_files_info(false)
+        .expect_err("Staged changes need a work tree");
+    assert!(err.to_string().contains("bare"));
+}
@@ -8,7 +8,7 @@ use tempfile::TempDir;
 // Use our centralized test infrastructure
 #[path = "test_utils.rs"]
 mod test_utils;
-use test_utils::setup_git_repo_with_tags;
+use test_utils::{GitTestHelper, setup_git_repo, setup_git_repo_with_tags};
 
 /// Sets up a temporary Git repository for testing
 #[allow(dead_code)]
@@ -19,7 +19,7 @@ fn setup_test_repo() -> Result<(TempDir, Repository)> {
 #[test]
 fn test_markdown_changelog_format() {
     let changelog = MarkdownChangelog {
-        content: r#"## [1.0.0] - 2023-06-01
+        content: r"## [1.0.0] - 2023-06-01
 
 This release adds new features and fixes bugs.
 
@@ -42,7 +42,7 @@ This release adds new features and fixes bugs.
 - Files Changed: 12
 - Insertions: +245
 - Deletions: -87
-"#
+"
         .to_string(),
     };
 
@@ -109,12 +109,73 @@ fn test_update_changelog_file_with_version_name() -> Result<()> {
     Ok(())
 }
 
+/// Generated releases land under `[Unreleased]` or as a dated version,
+/// keeping the entries already in the file
+#[test]
+fn test_update_changelog_file_keeps_prior_entries() -> Result<()> {
+    use git_iris::changelog::ChangelogGenerator;
+    use git_iris::git::GitRepo;
+    use std::sync::Arc;
+
+    let temp_dir = TempDir::new()?;
+    let (_, repo) = setup_test_repo()?;
+    let git_repo = Arc::new(GitRepo::new(repo.path())?);
+    let changelog_path = temp_dir.path().join("CHANGELOG.md");
+    let path = changelog_path
+        .to_str()
+        .expect("Invalid path for changelog file");
+    std::fs::write(
+        &changelog_path,
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Dark mode\n\n## [0.9.0] - 2022-01-01\n\n- First release\n",
+    )?;
+
+    ChangelogGenerator::update_changelog_file(
+        "## [Unreleased]\n\n### Added\n\n- Search\n",
+        path,
+        &git_repo,
+        "HEAD",
+        None,
+    )?;
+    ChangelogGenerator::update_changelog_file(
+        "## [1.0.0]\n\n### Fixed\n\n- Crash on start\n",
+        path,
+        &git_repo,
+        "HEAD",
+        None,
+    )?;
+
+    let updated = std::fs::read_to_string(&changelog_path)?;
+    assert!(
+        updated.contains("## [Unreleased]\n\n### Added\n\n- Dark mode\n- Search\n\n## [1.0.0] - ")
+    );
+    assert!(updated.contains("## [0.9.0] - 2022-01-01\n\n- First release"));
+    Ok(())
+}
+
+#[test]
+fn test_commits_touching_path() -> Result<()> {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir)?;
+    helper.create_tag("v1.0.0", "First release")?;
+    helper.create_and_stage_file("crates/core/src/lib.rs", "pub fn core() {}")?;
+    helper.commit("Add core")?;
+    helper.create_and_stage_file("crates/core-utils/src/lib.rs", "pub fn utils() {}")?;
+    helper.commit("Add utils")?;
+    helper.create_and_stage_file("README.md", "# Workspace")?;
+    helper.commit("Update readme")?;
+
+    let commits = git_repo.get_commits_touching_path("v1.0.0", "HEAD", "crates/core/")?;
+    let messages: Vec<&str> = commits.iter().map(|c| c.message.trim()).collect();
+    assert_eq!(messages, ["Add core"]);
+    Ok(())
+}
+
 #[test]
 fn test_markdown_release_notes_format() {
     use git_iris::types::MarkdownReleaseNotes;
 
     let release_notes = MarkdownReleaseNotes {
-        content: r#"# Release Notes v1.0.0
+        content: r"# Release Notes v1.0.0
 
 **Released:** 2023-06-01
 
@@ -133,8 +194,9 @@ A great new feature was added.
 ## Breaking Changes
 
 - API endpoint changed
-"#
+"
         .to_string(),
+        suggested_version: None,
     };
 
     // Test raw content
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use git_iris::companion::{BranchMemory, CompanionStorage, SessionState};
+use git2::WorktreeAddOptions;
+use tempfile::TempDir;
+
+#[path = "test_utils.rs"]
+mod test_utils;
+use test_utils::{GitTestHelper, setup_git_repo};
+
+#[test]
+fn test_worktrees_keep_their_own_sessions() -> Result<()> {
+    let (temp_dir, _git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir)?;
+    let base = TempDir::new()?;
+
+    // A second worktree with `hotfix` checked out
+    let head = helper.repo.head()?.peel_to_commit()?;
+    let branch = helper.repo.branch("hotfix", &head, false)?;
+    let hotfix_path = base.path().join("hotfix");
+    let mut options = WorktreeAddOptions::new();
+    options.reference(Some(branch.get()));
+    helper
+        .repo
+        .worktree("hotfix", &hotfix_path, Some(&options))?;
+
+    let main = CompanionStorage::in_dir(base.path(), temp_dir.path())?;
+    let hotfix = CompanionStorage::in_dir(base.path(), &hotfix_path)?;
+
+    let main_session = SessionState::new(temp_dir.path().to_path_buf(), "main".to_string());
+    let hotfix_session = SessionState::new(hotfix_path.clone(), "hotfix".to_string());
+    main.save_session(&main_session)?;
+    hotfix.save_session(&hotfix_session)?;
+    // A session on a branch the worktree has since left
+    hotfix.save_session(&SessionState::new(hotfix_path, "old".to_string()))?;
+
+    assert_eq!(
+        main.load_session("main")?.map(|s| s.session_id),
+        Some(main_session.session_id)
+    );
+    assert!(main.load_session("hotfix")?.is_none());
+    assert_eq!(
+        hotfix.load_session("hotfix")?.map(|s| s.session_id),
+        Some(hotfix_session.session_id)
+    );
+
+    let others: Vec<String> = main
+        .other_worktree_sessions()?
+        .into_iter()
+        .map(|s| s.branch)
+        .collect();
+    assert_eq!(others, ["hotfix"]);
+    assert_eq!(hotfix.other_worktree_sessions()?[0].branch, "main");
+
+    // Branch memory belongs to the repository, not the worktree
+    hotfix.save_branch_memory(&BranchMemory::new("hotfix".to_string()))?;
+    assert!(main.load_branch_memory("hotfix")?.is_some());
+    Ok(())
+}
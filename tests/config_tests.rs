@@ -1,5 +1,7 @@
+use git_iris::agents::StatusPersonality;
+use git_iris::clipboard::Osc52Mode;
 use git_iris::common::CommonParams;
-use git_iris::config::Config;
+use git_iris::config::{Config, GenerationParams, Tone, Verbosity};
 use git_iris::providers::ProviderConfig;
 use std::collections::HashMap;
 use std::env;
@@ -57,6 +59,8 @@ fn test_project_config_security() {
             .insert((*provider_name).to_string(), provider_config);
     }
 
+    config.github.token = "secret_github_token".to_string();
+
     // Save as project config
     config
         .save_as_project_config()
@@ -70,6 +74,10 @@ fn test_project_config_security() {
     let content = fs::read_to_string(config_path).expect("Failed to read project config file");
 
     // Verify no API keys are in the file
+    assert!(
+        !content.contains("secret_github_token"),
+        "GitHub token was found in project config file"
+    );
     for provider_name in &["openai", "anthropic", "cohere"] {
         let api_key = format!("secret_{provider_name}_api_key");
         assert!(
@@ -127,6 +135,8 @@ fn test_project_config_security() {
         gitmoji: Some(true),
         gitmoji_flag: false,
         no_gitmoji: false,
+        verbosity: Some(Verbosity::Concise),
+        tone: None,
         repository_url: None,
     };
 
@@ -135,6 +145,10 @@ fn test_project_config_security() {
     common
         .apply_to_config(&mut config)
         .expect("Failed to apply common params");
+    assert_eq!(
+        config.generation_params("review").verbosity,
+        Some(Verbosity::Concise)
+    );
 
     // Set an API key
     let provider_config = config
@@ -156,6 +170,8 @@ fn test_project_config_security() {
         !content.contains("cli_integration_api_key"),
         "API key from CLI integration was found in project config file"
     );
+    // Session overrides aren't saved
+    assert!(!content.contains("verbosity"));
 
     // Clean up - restore original directory
     env::set_current_dir(original_dir).expect("Failed to restore original directory");
@@ -167,14 +183,35 @@ fn test_project_config_only_serializes_changed_values() {
     // This avoids current directory race conditions in parallel tests
     let config = Config {
         default_provider: String::new(),
+        fallback_providers: Vec::new(),
         providers: HashMap::new(),
         use_gitmoji: false, // Explicitly changed from default
         instructions: String::new(),
         instruction_preset: "conventional".to_string(), // Explicitly changed from default
         theme: String::new(),
         subagent_timeout_secs: 120,
+        generation: HashMap::new(),
+        variant_count: 1,
+        variant_presets: Vec::new(),
+        clipboard_osc52: Osc52Mode::Auto,
+        status_personality: StatusPersonality::default(),
+        spell_check: true,
+        ascii_only: false,
+        critical_paths: Vec::new(),
+        commit_style: git_iris::types::CommitStyle::default(),
+        context: git_iris::config::ContextConfig::default(),
+        auto_generate: git_iris::config::AutoGenerateConfig::default(),
+        github: git_iris::integrations::github::GitHubConfig::default(),
+        gitlab: git_iris::integrations::gitlab::GitLabConfig::default(),
+        outputs: git_iris::outputs::OutputsConfig::default(),
+        check_for_updates: false,
+        local_analytics: false,
+        use_keyring: false,
+        pricing: HashMap::new(),
+        hooks: git_iris::lifecycle::LifecycleHooks::default(),
         temp_instructions: None,
         temp_preset: None,
+        temp_generation: HashMap::new(),
         is_project_config: true,
         gitmoji_override: None,
     };
@@ -227,19 +264,43 @@ fn test_project_config_with_provider_only_serializes_set_fields() {
             fast_model: None,
             token_limit: None,
             additional_params: HashMap::new(),
+            organization: None,
+            project: None,
+            headers: HashMap::new(),
         },
     );
 
     let config = Config {
         default_provider: "anthropic".to_string(),
+        fallback_providers: Vec::new(),
         providers,
         use_gitmoji: true, // default, should NOT serialize
         instructions: String::new(),
         instruction_preset: "default".to_string(), // default, should NOT serialize
         theme: String::new(),
         subagent_timeout_secs: 120,
+        generation: HashMap::new(),
+        variant_count: 1,
+        variant_presets: Vec::new(),
+        clipboard_osc52: Osc52Mode::Auto,
+        status_personality: StatusPersonality::default(),
+        spell_check: true,
+        ascii_only: false,
+        critical_paths: Vec::new(),
+        commit_style: git_iris::types::CommitStyle::default(),
+        context: git_iris::config::ContextConfig::default(),
+        auto_generate: git_iris::config::AutoGenerateConfig::default(),
+        github: git_iris::integrations::github::GitHubConfig::default(),
+        gitlab: git_iris::integrations::gitlab::GitLabConfig::default(),
+        outputs: git_iris::outputs::OutputsConfig::default(),
+        check_for_updates: false,
+        local_analytics: false,
+        use_keyring: false,
+        pricing: HashMap::new(),
+        hooks: git_iris::lifecycle::LifecycleHooks::default(),
         temp_instructions: None,
         temp_preset: None,
+        temp_generation: HashMap::new(),
         is_project_config: true,
         gitmoji_override: None,
     };
@@ -296,6 +357,9 @@ fn test_provider_config_skip_serialization() {
         fast_model: None,
         token_limit: None,
         additional_params: HashMap::new(),
+        organization: None,
+        project: None,
+        headers: HashMap::new(),
     };
 
     let serialized = toml::to_string(&config).expect("Failed to serialize");
@@ -334,6 +398,9 @@ fn test_provider_config_serializes_set_values() {
         fast_model: Some("gpt-4o-mini".to_string()),
         token_limit: Some(4096),
         additional_params: params,
+        organization: None,
+        project: Some("proj_billing".to_string()),
+        headers: HashMap::new(),
     };
 
     let serialized = toml::to_string(&config).expect("Failed to serialize");
@@ -355,6 +422,14 @@ fn test_provider_config_serializes_set_values() {
         serialized.contains("temperature"),
         "additional_params should serialize"
     );
+    assert!(
+        serialized.contains(r#"project = "proj_billing""#),
+        "project should serialize"
+    );
+    assert!(
+        !serialized.contains("organization") && !serialized.contains("headers"),
+        "unset organization and headers should not serialize"
+    );
 
     // Empty api_key should NOT appear
     assert!(
@@ -362,3 +437,176 @@ fn test_provider_config_serializes_set_values() {
         "empty api_key should not serialize"
     );
 }
+
+#[test]
+fn test_empty_project_config_serializes_nothing() {
+    let config = Config::empty_project_config();
+    assert!(config.is_project_config);
+
+    let toml_string = toml::to_string(&config).expect("Failed to serialize empty project config");
+    assert!(
+        toml_string.trim().is_empty(),
+        "Empty project config should not write any values, got: {toml_string}"
+    );
+}
+
+#[test]
+fn test_generation_params_layering() {
+    let mut config = Config::default();
+    let toml_str = r"
+        [generation.default]
+        temperature = 0.7
+        max_tokens = 8000
+
+        [generation.review]
+        temperature = 0.0
+    ";
+    let personal: Config = toml::from_str(toml_str).expect("Failed to parse generation config");
+    config.generation = personal.generation;
+
+    let review = config.generation_params("review");
+    assert_eq!(review.temperature, Some(0.0));
+    assert_eq!(review.max_tokens, Some(8000));
+    assert_eq!(config.generation_params("commit").temperature, Some(0.7));
+
+    // Project values override individual fields only
+    let mut project = Config::empty_project_config();
+    project.generation.insert(
+        "review".to_string(),
+        GenerationParams {
+            top_p: Some(0.9),
+            ..GenerationParams::default()
+        },
+    );
+    config.merge_with_project_config(project);
+
+    let review = config.generation_params("review");
+    assert_eq!(review.temperature, Some(0.0));
+    assert_eq!(review.top_p, Some(0.9));
+}
+
+#[test]
+fn test_verbosity_and_tone_layering() {
+    let toml_str = r#"
+        [generation.default]
+        tone = "formal"
+
+        [generation.pr]
+        verbosity = "detailed"
+        tone = "friendly"
+    "#;
+    let mut config: Config = toml::from_str(toml_str).expect("Failed to parse generation config");
+    let pr = config.generation_params("pr");
+    assert_eq!(pr.verbosity, Some(Verbosity::Detailed));
+    assert_eq!(pr.tone, Some(Tone::Friendly));
+    assert_eq!(config.generation_params("commit").tone, Some(Tone::Formal));
+
+    // A CLI override applies to every capability, a session toggle to one
+    config
+        .temp_generation
+        .entry("default".to_string())
+        .or_default()
+        .verbosity = Some(Verbosity::Concise);
+    config.temp_generation.insert(
+        "commit".to_string(),
+        GenerationParams {
+            verbosity: Some(Verbosity::Standard),
+            ..GenerationParams::default()
+        },
+    );
+    let pr = config.generation_params("pr");
+    assert_eq!(pr.verbosity, Some(Verbosity::Concise));
+    assert_eq!(pr.tone, Some(Tone::Friendly));
+    assert_eq!(pr.max_tokens_or(16384), 8192);
+    assert_eq!(
+        config.generation_params("commit").verbosity,
+        Some(Verbosity::Standard)
+    );
+}
+
+#[test]
+fn test_commit_style_from_project_config() {
+    let toml_str = r#"
+        [commit_style]
+        subject_case = "lower"
+        forbidden_words = ["WIP"]
+        required_prefix = ['[A-Z]+-\d+: ']
+    "#;
+    let project: Config = toml::from_str(toml_str).expect("Failed to parse commit style");
+
+    let mut config = Config::default();
+    assert!(config.commit_style.is_empty());
+    config.merge_with_project_config(project);
+
+    assert_eq!(
+        config.commit_style.subject_case,
+        git_iris::types::SubjectCase::Lower
+    );
+    assert!(
+        config
+            .commit_style
+            .check("PROJ-1: tidy the parser\n\nStill WIP")
+            .iter()
+            .any(|v| matches!(v, git_iris::types::StyleViolation::ForbiddenWord(_)))
+    );
+}
+
+#[test]
+fn test_auto_generate_triggers() {
+    let toml_str = r#"
+        [auto_generate]
+        commit = "on-staged-change"
+        changelog = "manual"
+    "#;
+    let config: Config = toml::from_str(toml_str).expect("Failed to parse auto_generate");
+
+    let auto = &config.auto_generate;
+    assert!(auto.enabled);
+    assert_eq!(auto.commit, git_iris::config::AutoTrigger::OnStagedChange);
+    assert_eq!(auto.changelog, git_iris::config::AutoTrigger::Manual);
+    assert_eq!(auto.review, git_iris::config::AutoTrigger::OnEntry);
+
+    // Only the changed triggers are written back
+    let written = toml::to_string(&config.auto_generate).expect("serialize");
+    assert_eq!(
+        written.trim(),
+        "commit = \"on-staged-change\"\nchangelog = \"manual\""
+    );
+}
+
+#[test]
+fn test_fallback_providers_from_project_config() {
+    let toml_str = r#"
+        default_provider = "anthropic"
+        fallback_providers = ["openai", "ollama"]
+    "#;
+    let project: Config = toml::from_str(toml_str).expect("Failed to parse fallback providers");
+
+    let mut config = Config::default();
+    assert!(config.fallback_providers.is_empty());
+    config.merge_with_project_config(project);
+    assert_eq!(config.fallback_providers, ["openai", "ollama"]);
+
+    // Ollama needs no key, so a keyless primary still has somewhere to go
+    assert!(config.missing_api_key().is_none());
+}
+
+#[test]
+fn test_project_config_cannot_redirect_forge_tokens() {
+    let toml_str = r#"
+        [github]
+        api_url = "https://attacker.example.com"
+
+        [gitlab]
+        api_url = "https://attacker.example.com/api/v4"
+    "#;
+    let project: Config = toml::from_str(toml_str).expect("Failed to parse project forge config");
+
+    let mut config = Config::default();
+    config.github.token = "personal-token".to_string();
+    config.merge_with_project_config(project);
+
+    assert!(config.github.api_url.is_empty());
+    assert!(config.gitlab.api_url.is_empty());
+    assert_eq!(config.github.token, "personal-token");
+}
@@ -214,3 +214,16 @@ async fn test_multiple_staged_files_with_exclusions() {
         assert_ne!(file.diff, "[Content excluded]");
     }
 }
+
+#[test]
+fn test_windows_paths_are_normalized() {
+    use git_iris::git::{normalize_path, should_exclude_file};
+
+    assert_eq!(
+        normalize_path(r"src\studio\app.rs"),
+        Path::new("src/studio/app.rs")
+    );
+    assert_eq!(normalize_path("./README.md"), Path::new("README.md"));
+    assert!(should_exclude_file(r"web\node_modules\pkg\index.js"));
+    assert!(!should_exclude_file(r"src\main.rs"));
+}
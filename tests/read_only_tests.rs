@@ -0,0 +1,36 @@
+use anyhow::Result;
+use git_iris::error::IrisError;
+use std::fs;
+use std::path::Path;
+
+#[path = "test_utils.rs"]
+mod test_utils;
+use test_utils::setup_git_repo;
+
+// The only test in this binary: read-only mode is process-wide
+#[test]
+fn test_read_only_mode_refuses_changes() -> Result<()> {
+    let (temp_dir, git_repo) = setup_git_repo();
+    fs::write(temp_dir.path().join("initial.txt"), "Changed content")?;
+
+    git_iris::read_only::set_read_only(true);
+    let error = git_repo
+        .stage_file(Path::new("initial.txt"))
+        .expect_err("staging is refused");
+    assert_eq!(
+        IrisError::find(&error),
+        Some(IrisError::ReadOnly {
+            action: "Staging files".to_string()
+        })
+    );
+    assert!(git_repo.commit("Nothing to see").is_err());
+    assert!(git_repo.create_stash(None, false).is_err());
+
+    // Analysis still works
+    let unstaged = git_repo.get_unstaged_files()?;
+    assert_eq!(unstaged.len(), 1);
+
+    git_iris::read_only::set_read_only(false);
+    git_repo.stage_file(Path::new("initial.txt"))?;
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use anyhow::Result;
+use git_iris::git::RebaseAction;
+use std::fs;
+
+#[path = "test_utils.rs"]
+mod test_utils;
+use test_utils::{GitTestHelper, setup_git_repo};
+
+/// Check out `feature` with four commits on top of `main`
+fn feature_branch(helper: &GitTestHelper) -> Result<Vec<String>> {
+    helper.create_branch("feature")?;
+    helper.checkout_branch("feature")?;
+    let mut ids = Vec::new();
+    for (path, content, message) in [
+        ("parser.txt", "parse retires", "Add parser"),
+        ("parser.txt", "parse retries", "fix typo"),
+        ("docs.txt", "how to parse", "docs"),
+        ("scratch.txt", "tmp", "wip"),
+    ] {
+        helper.create_and_stage_file(path, content)?;
+        ids.push(helper.commit(message)?.to_string());
+    }
+    Ok(ids)
+}
+
+fn branch_messages(helper: &GitTestHelper) -> Result<Vec<String>> {
+    let mut walk = helper.repo.revwalk()?;
+    walk.push_head()?;
+    walk.map(|id| {
+        let commit = helper.repo.find_commit(id?)?;
+        Ok(commit.message().unwrap_or_default().trim_end().to_string())
+    })
+    .collect()
+}
+
+#[test]
+fn test_rebase_folds_rewords_and_drops() -> Result<()> {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir)?;
+    let ids = feature_branch(&helper)?;
+
+    let mut plan = git_repo.rebase_plan("main")?;
+    assert_eq!(plan.branch, "feature");
+    assert_eq!(plan.steps.len(), 4);
+    assert!(plan.is_unchanged());
+
+    plan.apply_suggestion(&format!(
+        "pick {}\nfixup {}\n# Fixes a typo in the parser\nreword {} docs: describe the parser\ndrop {}\n",
+        &ids[0][..7],
+        &ids[1][..7],
+        &ids[2][..7],
+        &ids[3][..7],
+    ))?;
+    assert_eq!(plan.steps[1].action, RebaseAction::Fixup);
+    assert_eq!(
+        plan.steps[1].rationale.as_deref(),
+        Some("Fixes a typo in the parser")
+    );
+    assert_eq!(plan.result_count(), 2);
+
+    let outcome = git_repo.execute_rebase(&plan)?;
+    assert_eq!(outcome.old_head, ids[3]);
+    assert_eq!(outcome.commits, 2);
+    assert_eq!(
+        branch_messages(&helper)?,
+        ["docs: describe the parser", "Add parser", "Initial commit"]
+    );
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("parser.txt"))?,
+        "parse retries"
+    );
+    assert!(!temp_dir.path().join("scratch.txt").exists());
+    assert!(git_repo.get_worktree_diff_full(true)?.is_empty());
+
+    // The old branch tip is one reflog entry back
+    assert_eq!(git_repo.get_reflog(2)?[1].commit, ids[3]);
+    Ok(())
+}
+
+#[test]
+fn test_rebase_replays_onto_base_tip() -> Result<()> {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir)?;
+    feature_branch(&helper)?;
+    helper.checkout_branch("main")?;
+    helper.create_and_stage_file("main.txt", "new on main")?;
+    helper.commit("Update main")?;
+    helper.checkout_branch("feature")?;
+
+    let plan = git_repo.rebase_plan("main")?;
+    assert_eq!(plan.steps.len(), 4);
+    assert!(!plan.is_unchanged());
+    git_repo.execute_rebase(&plan)?;
+    assert_eq!(branch_messages(&helper)?[4], "Update main");
+    assert!(temp_dir.path().join("main.txt").exists());
+    Ok(())
+}
+
+#[test]
+fn test_conflicting_plan_leaves_branch_alone() -> Result<()> {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir)?;
+    let ids = feature_branch(&helper)?;
+
+    // Dropping the first parser commit leaves the typo fix with nothing to apply to
+    let mut plan = git_repo.rebase_plan("main")?;
+    plan.steps[0].action = RebaseAction::Drop;
+    let error = git_repo
+        .execute_rebase(&plan)
+        .expect_err("the typo fix should conflict");
+    assert!(error.to_string().contains("conflicts"));
+    assert_eq!(
+        helper.repo.head()?.target().map(|id| id.to_string()),
+        Some(ids[3].clone())
+    );
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("parser.txt"))?,
+        "parse retries"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rebase_refuses_dirty_work_tree() -> Result<()> {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir)?;
+    feature_branch(&helper)?;
+    let mut plan = git_repo.rebase_plan("main")?;
+    plan.steps[3].action = RebaseAction::Drop;
+
+    fs::write(temp_dir.path().join("docs.txt"), "unsaved edit")?;
+    assert!(git_repo.execute_rebase(&plan).is_err());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("docs.txt"))?,
+        "unsaved edit"
+    );
+    Ok(())
+}
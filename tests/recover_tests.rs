@@ -0,0 +1,58 @@
+use anyhow::Result;
+use git2::ResetType;
+
+#[path = "test_utils.rs"]
+mod test_utils;
+use test_utils::{GitTestHelper, setup_git_repo};
+
+#[test]
+fn test_reflog_finds_commits_lost_to_reset() -> Result<()> {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir)?;
+    let base = helper.repo.head()?.peel_to_commit()?;
+
+    helper.create_and_stage_file("feature.txt", "work in progress")?;
+    let lost = helper.commit("Add feature draft")?;
+    helper.repo.reset(base.as_object(), ResetType::Hard, None)?;
+
+    let entries = git_repo.get_reflog(10)?;
+    let lost_entry = entries
+        .iter()
+        .find(|e| e.commit == lost.to_string())
+        .expect("reset commit should be in the reflog");
+    assert_eq!(lost_entry.subject, "Add feature draft");
+    assert_eq!(lost_entry.commits_not_in_head, 1);
+    assert!(!lost_entry.reachable);
+    assert!(lost_entry.is_lost());
+
+    // HEAD@{0} is the reset back to the base commit
+    assert_eq!(entries[0].commit, base.id().to_string());
+    assert!(!entries[0].is_lost());
+
+    git_repo.create_branch_from_reflog(lost_entry.index, "recovered/feature")?;
+    let branch = helper
+        .repo
+        .find_branch("recovered/feature", git2::BranchType::Local)?;
+    assert_eq!(branch.get().target(), Some(lost));
+    assert!(git_repo.get_reflog(10)?.iter().all(|e| !e.is_lost()));
+    Ok(())
+}
+
+#[test]
+fn test_reset_to_reflog_refuses_dirty_worktree() -> Result<()> {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir)?;
+    helper.create_and_stage_file("feature.txt", "first")?;
+    helper.commit("Add feature")?;
+
+    helper.create_and_stage_file("feature.txt", "uncommitted")?;
+    assert!(git_repo.reset_to_reflog(1).is_err());
+
+    helper.commit("Update feature")?;
+    git_repo.reset_to_reflog(2)?;
+    assert_eq!(
+        helper.repo.head()?.peel_to_commit()?.summary(),
+        Some("Initial commit")
+    );
+    Ok(())
+}
@@ -1,5 +1,11 @@
 use git_iris::config::Config;
-use git_iris::git::GitRepo;
+use git_iris::git::{GitRepo, remote_cache};
+use git2::Repository;
+use tempfile::TempDir;
+
+#[path = "test_utils.rs"]
+mod test_utils;
+use test_utils::{GitTestHelper, setup_git_repo_with_tags};
 
 #[tokio::test]
 async fn test_remote_repository_support() {
@@ -92,3 +98,55 @@ async fn test_remote_repository_support() {
         println!("Consider using --skip-remote-tests if network is slow");
     }
 }
+
+#[test]
+fn test_remote_cache_mirrors_and_updates() {
+    let (source_dir, source) = setup_git_repo_with_tags().expect("Failed to create source repo");
+    let mut config = source.config().expect("Failed to get repository config");
+    config
+        .set_str("user.name", "Test User")
+        .expect("Failed to set user name");
+    config
+        .set_str("user.email", "test@example.com")
+        .expect("Failed to set user email");
+    let url = format!("file://{}", source_dir.path().display());
+    let cache = TempDir::new().expect("Failed to create cache directory");
+
+    let mirror_path =
+        remote_cache::fetch_mirror(cache.path(), &url).expect("Failed to mirror repository");
+    let mirror = Repository::open_bare(&mirror_path).expect("Mirror should be a bare repository");
+    assert!(mirror.find_reference("refs/tags/v1.0.0").is_ok());
+    let head = mirror
+        .head()
+        .expect("Mirror HEAD")
+        .peel_to_commit()
+        .expect("HEAD commit");
+
+    // A second run reuses the mirror and only fetches what's new
+    let helper = GitTestHelper::new(&source_dir).expect("Failed to create GitTestHelper");
+    helper
+        .create_and_stage_file("later.txt", "Later")
+        .expect("Failed to create and stage file");
+    let new_commit = helper.commit("Later change").expect("Failed to commit");
+    let again = remote_cache::fetch_mirror(cache.path(), &url).expect("Failed to update mirror");
+    assert_eq!(again, mirror_path);
+    let updated = mirror
+        .head()
+        .expect("Mirror HEAD")
+        .peel_to_commit()
+        .expect("HEAD commit");
+    assert_eq!(updated.id(), new_commit);
+    assert_eq!(updated.parent_id(0).expect("parent"), head.id());
+
+    let mirrors = remote_cache::list_mirrors(cache.path()).expect("Failed to list mirrors");
+    assert_eq!(mirrors.len(), 1);
+    assert_eq!(mirrors[0].url, url);
+    assert!(mirrors[0].size > 0);
+
+    let removed = remote_cache::clear_mirrors(cache.path(), Some("file:///elsewhere"))
+        .expect("Failed to clear");
+    assert!(removed.is_empty());
+    let removed = remote_cache::clear_mirrors(cache.path(), None).expect("Failed to clear");
+    assert_eq!(removed.len(), 1);
+    assert!(!mirror_path.exists());
+}
@@ -38,3 +38,38 @@ async fn test_perform_commit() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_amend_commit_keeps_author_and_date() -> Result<()> {
+    let (temp_dir, _git_repo) = setup_test_repo()?;
+    let repo = git2::Repository::open(temp_dir.path())?;
+
+    // HEAD written by someone else, a while ago
+    let author = git2::Signature::new(
+        "Original Author",
+        "original@example.com",
+        &git2::Time::new(1_600_000_000, 0),
+    )?;
+    let head = repo.head()?.peel_to_commit()?;
+    let tree = head.tree()?;
+    repo.commit(
+        Some("HEAD"),
+        &author,
+        &author,
+        "Draft message",
+        &tree,
+        &[&head],
+    )?;
+
+    let service = GitCommitService::new(Arc::new(GitRepo::new(temp_dir.path())?), false, false);
+    service.amend_commit("Final message")?;
+
+    let amended = repo.head()?.peel_to_commit()?;
+    assert_eq!(amended.message(), Some("Final message"));
+    assert_eq!(amended.author().name(), Some("Original Author"));
+    assert_eq!(amended.author().when().seconds(), 1_600_000_000);
+    assert_eq!(amended.committer().name(), Some("Test User"));
+    assert_eq!(amended.parent_id(0)?, head.id());
+
+    Ok(())
+}
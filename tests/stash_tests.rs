@@ -0,0 +1,64 @@
+use anyhow::Result;
+use std::fs;
+
+#[path = "test_utils.rs"]
+mod test_utils;
+use test_utils::{GitTestHelper, setup_git_repo};
+
+#[test]
+fn test_stash_round_trip() -> Result<()> {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir)?;
+    assert!(git_repo.list_stashes()?.is_empty());
+    assert!(git_repo.create_stash(None, false).is_err());
+
+    helper.create_and_stage_file("initial.txt", "Changed content")?;
+    fs::write(temp_dir.path().join("notes.txt"), "scratch")?;
+    let first = git_repo.create_stash(Some("half-done parser"), true)?;
+    assert_eq!(first.index, 0);
+    assert_eq!(first.description(), "half-done parser");
+    assert!(first.branch().is_some());
+    assert!(first.has_untracked);
+    assert!(!temp_dir.path().join("notes.txt").exists());
+
+    let diff = git_repo.get_stash_diff(0, &first.id)?;
+    assert!(diff.contains("initial.txt"));
+    assert!(diff.contains("+Changed content"));
+    assert!(diff.contains("notes.txt"));
+
+    helper.create_and_stage_file("initial.txt", "Other content")?;
+    let second = git_repo.create_stash(None, false)?;
+    assert!(second.description().contains("Initial commit"));
+    let stashes = git_repo.list_stashes()?;
+    assert_eq!(stashes.len(), 2);
+    assert_eq!(stashes[1].id, first.id);
+
+    // The first stash moved to index 1, so its old index no longer matches
+    assert!(git_repo.pop_stash(0, &first.id).is_err());
+    git_repo.drop_stash(0, &second.id)?;
+    git_repo.pop_stash(0, &first.id)?;
+    assert!(git_repo.list_stashes()?.is_empty());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt"))?,
+        "Changed content"
+    );
+    assert!(temp_dir.path().join("notes.txt").exists());
+    Ok(())
+}
+
+#[test]
+fn test_apply_keeps_stash() -> Result<()> {
+    let (temp_dir, git_repo) = setup_git_repo();
+    let helper = GitTestHelper::new(&temp_dir)?;
+    helper.create_and_stage_file("initial.txt", "Changed content")?;
+    let entry = git_repo.create_stash(Some("keep me"), false)?;
+
+    git_repo.apply_stash(0, &entry.id)?;
+    assert_eq!(git_repo.list_stashes()?.len(), 1);
+    assert!(
+        git_repo
+            .get_worktree_diff_full(false)?
+            .contains("+Changed content")
+    );
+    Ok(())
+}